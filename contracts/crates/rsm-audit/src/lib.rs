@@ -0,0 +1,431 @@
+//! rsm-audit: cross-program invariants of rsm-token, dna-token and
+//! genesis-engine, checked from a snapshot of their accounts.
+//!
+//! `source` reads the snapshot over RPC; the checks here are pure so they
+//! can be tested against fixtures:
+//!
+//! - `token_supply`: the minted genomes' `tokens_minted` plus the dust
+//!   consolidated to the reward pool and the challengers' dispute rewards
+//!   add up to `config.total_minted`
+//! - `genesis_genomes`: every `GenesisRecord` has the dna-token `Genome` it
+//!   registered
+//! - `rna_burned`: `engine.total_rna_burned` equals the RNA burned by the
+//!   engine's events: finalized geneses, still-queued geneses, forfeited
+//!   genesis commits and incubation accelerations
+
+pub mod source;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use rsm_core::TRAIT_COUNT;
+use rsm_events::RsmEvent;
+use rsm_genesis_verify::{dna_genome_address, DnaGenome, GenesisRecord};
+use rsm_token::{GenomeData, TokenConfig};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Mismatches listed per check; the rest are only counted.
+pub const MAX_LISTED_MISMATCHES: usize = 20;
+
+#[derive(Error, Debug)]
+pub enum AuditError {
+    #[error("RPC request failed: {0}")]
+    Rpc(#[from] Box<solana_client::client_error::ClientError>),
+    #[error("Account {0} is missing")]
+    MissingAccount(Pubkey),
+    #[error(transparent)]
+    Decode(#[from] rsm_genesis_verify::VerifyError),
+    #[error("Failed to decode {0}: {1}")]
+    Layout(&'static str, std::io::Error),
+    #[error(transparent)]
+    Event(#[from] rsm_events::EventError),
+}
+
+/// Mirror of the leading fields of genesis-engine's `GenesisEngine`
+/// account, up to the queue cursors.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EngineState {
+    pub authority: Pubkey,
+    pub genesis_cost: u64,
+    pub total_genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub active: bool,
+    pub rarity_weights: [u16; 4],
+    pub rarity_multipliers: [u16; 4],
+    pub incubation_slots: u64,
+    pub acceleration_cost_per_slot: u64,
+    pub trait_dominance: [u8; TRAIT_COUNT],
+    pub mutation_bps: u16,
+    pub min_parent_holding: u64,
+    pub queue_head: u64,
+    pub queue_tail: u64,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub name: &'static str,
+    pub passed: bool,
+    pub expected: String,
+    pub actual: String,
+    /// Up to `MAX_LISTED_MISMATCHES` offending items
+    pub mismatches: Vec<String>,
+}
+
+impl Check {
+    fn compare(name: &'static str, expected: u64, actual: u64) -> Self {
+        Self {
+            name,
+            passed: expected == actual,
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+            mismatches: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub slot: u64,
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    pub fn is_valid(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+}
+
+/// Everything the checks read, as of `slot`.
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    pub slot: u64,
+    pub config: Option<TokenConfig>,
+    pub genomes: Vec<GenomeData>,
+    pub engine: Option<EngineState>,
+    pub geneses: Vec<GenesisRecord>,
+    /// dna-token genomes by address
+    pub dna_genomes: HashMap<Pubkey, DnaGenome>,
+    pub dna_program: Option<Pubkey>,
+    /// Events logged by genesis-engine itself, not by its CPIs
+    pub genesis_events: Vec<RsmEvent>,
+}
+
+/// Runs every check the snapshot has the accounts for.
+pub fn audit(snapshot: &Snapshot) -> Report {
+    let mut checks = Vec::new();
+    if let Some(config) = &snapshot.config {
+        checks.push(token_supply(config, &snapshot.genomes));
+    }
+    if let Some(dna_program) = &snapshot.dna_program {
+        checks.push(genesis_genomes(dna_program, &snapshot.geneses, &snapshot.dna_genomes));
+    }
+    if let Some(engine) = &snapshot.engine {
+        checks.push(rna_burned(engine, &snapshot.genesis_events));
+    }
+    Report { slot: snapshot.slot, checks }
+}
+
+/// `config.total_minted` against what the minted genomes hold plus the
+/// dust consolidated and the dispute rewards paid outside any genome.
+pub fn token_supply(config: &TokenConfig, genomes: &[GenomeData]) -> Check {
+    let held: u128 = genomes
+        .iter()
+        .filter(|genome| genome.is_minted)
+        .map(|genome| genome.tokens_minted as u128)
+        .sum();
+    let computed = held + config.dust.consolidated as u128 + config.dispute_rewards as u128;
+    Check {
+        name: "token_supply",
+        passed: computed == config.total_minted as u128,
+        expected: computed.to_string(),
+        actual: config.total_minted.to_string(),
+        mismatches: Vec::new(),
+    }
+}
+
+/// Every genesis record against the dna-token genome at its genome id.
+pub fn genesis_genomes(
+    dna_program: &Pubkey,
+    geneses: &[GenesisRecord],
+    dna_genomes: &HashMap<Pubkey, DnaGenome>,
+) -> Check {
+    let mut mismatches = Vec::new();
+    let mut matched = 0u64;
+    for record in geneses {
+        let address = dna_genome_address(dna_program, record.genome_id);
+        match dna_genomes.get(&address) {
+            Some(genome) if genome.genome_id == record.genome_id => matched += 1,
+            Some(genome) => mismatches.push(format!(
+                "genesis {} registered genome {} at {}",
+                record.genome_id, genome.genome_id, address
+            )),
+            None => mismatches.push(format!("genesis {} has no genome at {}", record.genome_id, address)),
+        }
+    }
+    let mut check = Check::compare("genesis_genomes", geneses.len() as u64, matched);
+    check.mismatches = mismatches.into_iter().take(MAX_LISTED_MISMATCHES).collect();
+    check
+}
+
+/// RNA burned according to the engine's events. A queued genesis burns at
+/// enqueue and is logged again when finalized, so queue events only count
+/// for positions the queue has not reached yet. A commit's escrow is only
+/// burned when it is revealed, as a finalized genesis, or forfeited.
+pub fn burned_by_events(engine: &EngineState, events: &[RsmEvent]) -> u128 {
+    events
+        .iter()
+        .map(|event| match event {
+            RsmEvent::GenesisCreatedEvent(created) => created.rna_burned as u128,
+            RsmEvent::GenesisQueuedEvent(queued) if queued.position >= engine.queue_head => queued.rna_locked as u128,
+            RsmEvent::GenesisCommitForfeitedEvent(forfeited) => forfeited.rna_forfeited as u128,
+            RsmEvent::IncubationAcceleratedEvent(accelerated) => accelerated.rna_burned as u128,
+            _ => 0,
+        })
+        .sum()
+}
+
+pub fn rna_burned(engine: &EngineState, events: &[RsmEvent]) -> Check {
+    let burned = burned_by_events(engine, events);
+    Check {
+        name: "rna_burned",
+        passed: burned == engine.total_rna_burned as u128,
+        expected: burned.to_string(),
+        actual: engine.total_rna_burned.to_string(),
+        mismatches: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsm_events::{
+        GenesisCommitForfeitedEvent, GenesisCommittedEvent, GenesisCreatedEvent, GenesisQueuedEvent,
+        IncubationAcceleratedEvent, Rarity,
+    };
+
+    fn engine(total_rna_burned: u64, queue_head: u64) -> EngineState {
+        EngineState {
+            authority: Pubkey::new_unique(),
+            genesis_cost: 1_000,
+            total_genomes_created: 0,
+            total_rna_burned,
+            active: true,
+            rarity_weights: [7000, 2000, 900, 100],
+            rarity_multipliers: [100, 150, 200, 300],
+            incubation_slots: 0,
+            acceleration_cost_per_slot: 0,
+            trait_dominance: [128; TRAIT_COUNT],
+            mutation_bps: 0,
+            min_parent_holding: 0,
+            queue_head,
+            queue_tail: queue_head,
+        }
+    }
+
+    fn created(genome_id: u64, rna_burned: u64) -> RsmEvent {
+        RsmEvent::GenesisCreatedEvent(GenesisCreatedEvent {
+            genome_id,
+            creator: Pubkey::default(),
+            rna_burned,
+            consciousness: 50,
+            rarity: Rarity::Common,
+            rna_multiplier: 100,
+            timestamp: 0,
+            name: "Helix".to_string(),
+            metadata_uri_hash: [0u8; 32],
+            matures_at_slot: 0,
+            species: 0,
+            traits: [0u8; 8],
+            mutations: Vec::new(),
+        })
+    }
+
+    fn queued(position: u64, rna_locked: u64) -> RsmEvent {
+        RsmEvent::GenesisQueuedEvent(GenesisQueuedEvent {
+            position,
+            creator: Pubkey::default(),
+            species: 0,
+            rna_locked,
+            slot: 0,
+        })
+    }
+
+    fn dna_genome(genome_id: u64) -> DnaGenome {
+        let mut name = [0u8; 32];
+        name[..5].copy_from_slice(b"Helix");
+        DnaGenome {
+            genome_id,
+            total_supply: 1_000_000,
+            market_supply: 857_100,
+            founder_supply: 142_900,
+            created_at: 0,
+            minted_at: 0,
+            matures_at_slot: 0,
+            circulating_supply: 0,
+            total_burned: 0,
+            created_slot: 0,
+            mint_events: 0,
+            rna_multiplier: 100,
+            species: 0,
+            sequence_hash: [0u8; 32],
+            block_hash: [0u8; 32],
+            metadata_uri_hash: [0u8; 32],
+            founder: Pubkey::default(),
+            mint: Pubkey::default(),
+            name,
+            blockchain: *b"genesis\0",
+            traits: [0u8; TRAIT_COUNT],
+            consciousness: 50,
+            minted: 0,
+            retired: 0,
+            indexed: 0,
+            metadata_corrections: 0,
+            padding: [0u8; 3],
+            content_hash: [0u8; 32],
+            holding_cap_slots: 0,
+            minted_slot: 0,
+            holding_cap_bps: 0,
+            holding_cap_padding: [0u8; 6],
+        }
+    }
+
+    fn genome(tokens_minted: u64, is_minted: bool) -> GenomeData {
+        let mut genome = GenomeData::try_from_slice(&[0u8; GenomeData::LEN]).unwrap();
+        genome.tokens_minted = tokens_minted;
+        genome.is_minted = is_minted;
+        genome
+    }
+
+    fn record(genome_id: u64) -> GenesisRecord {
+        GenesisRecord {
+            genome_id,
+            creator: Pubkey::default(),
+            parent_genomes: vec![1, 2],
+            rna_burned: 1_000,
+            consciousness: 50,
+            created_at: 0,
+            entropy_seed: [0u8; 32],
+            rarity: rsm_genesis_verify::Rarity::Common,
+            rna_multiplier: 100,
+            name: "Helix".to_string(),
+            metadata_uri_hash: [0u8; 32],
+            matures_at_slot: 0,
+            species: 0,
+            created_slot: 0,
+            rarity_weights: [7000, 2000, 900, 100],
+            rarity_multipliers: [100, 150, 200, 300],
+            traits: [0u8; TRAIT_COUNT],
+            trait_dominance: [128; TRAIT_COUNT],
+            mutation_bps: 0,
+            mutations: Vec::new(),
+            consciousness_penalty: 0,
+            total_supply: 1_000_000,
+            genesis_event: None,
+        }
+    }
+
+    #[test]
+    fn test_token_supply_includes_consolidated_dust() {
+        let mut config = TokenConfig::try_from_slice(&[0u8; TokenConfig::LEN]).unwrap();
+        config.total_minted = 1_500;
+        config.dust.consolidated = 200;
+        // Burned genomes keep no tokens in the total
+        let genomes = vec![genome(1_000, true), genome(300, true), genome(700, false)];
+        assert!(token_supply(&config, &genomes).passed);
+
+        config.total_minted = 1_600;
+        let check = token_supply(&config, &genomes);
+        assert!(!check.passed);
+        assert_eq!((check.expected.as_str(), check.actual.as_str()), ("1500", "1600"));
+
+        // A challenger's reward left its genome but stays in supply
+        config.dispute_rewards = 100;
+        assert!(token_supply(&config, &genomes).passed);
+    }
+
+    #[test]
+    fn test_rna_burned_counts_queue_once() {
+        // Position 0 was queued then finalized; position 1 is still queued
+        let events = vec![
+            created(10, 1_000),
+            queued(0, 900),
+            created(11, 900),
+            queued(1, 800),
+            RsmEvent::IncubationAcceleratedEvent(IncubationAcceleratedEvent {
+                genome_id: 10,
+                creator: Pubkey::default(),
+                rna_burned: 50,
+                matures_at_slot: 0,
+                slot: 0,
+            }),
+        ];
+        assert_eq!(burned_by_events(&engine(0, 1), &events), 2_750);
+        assert!(rna_burned(&engine(2_750, 1), &events).passed);
+
+        let short = rna_burned(&engine(3_000, 1), &events);
+        assert!(!short.passed);
+        assert_eq!((short.expected.as_str(), short.actual.as_str()), ("2750", "3000"));
+    }
+
+    #[test]
+    fn test_rna_burned_counts_forfeits_not_commits() {
+        let committed = |rna_escrowed| {
+            RsmEvent::GenesisCommittedEvent(GenesisCommittedEvent {
+                creator: Pubkey::default(),
+                species: 0,
+                commitment: [0; 32],
+                rna_escrowed,
+                slot: 0,
+            })
+        };
+        // One commit was revealed, the other forfeited
+        let events = vec![
+            committed(700),
+            created(10, 700),
+            committed(600),
+            RsmEvent::GenesisCommitForfeitedEvent(GenesisCommitForfeitedEvent {
+                creator: Pubkey::default(),
+                species: 0,
+                rna_forfeited: 600,
+                commit_slot: 0,
+                slot: 600,
+            }),
+        ];
+        assert_eq!(burned_by_events(&engine(0, 0), &events), 1_300);
+    }
+
+    #[test]
+    fn test_genesis_genomes_need_their_genome() {
+        let dna_program = Pubkey::new_unique();
+        let mut dna_genomes = HashMap::new();
+        dna_genomes.insert(dna_genome_address(&dna_program, 10), dna_genome(10));
+        dna_genomes.insert(dna_genome_address(&dna_program, 11), dna_genome(99));
+
+        let check = genesis_genomes(&dna_program, &[record(10)], &dna_genomes);
+        assert!(check.passed);
+
+        let check = genesis_genomes(&dna_program, &[record(10), record(11), record(12)], &dna_genomes);
+        assert!(!check.passed);
+        assert_eq!(check.actual, "1");
+        assert_eq!(check.mismatches.len(), 2);
+        assert!(check.mismatches[0].starts_with("genesis 11 registered genome 99"));
+        assert!(check.mismatches[1].starts_with("genesis 12 has no genome"));
+    }
+
+    #[test]
+    fn test_report_is_valid_only_when_every_check_passes() {
+        let snapshot = Snapshot {
+            slot: 7,
+            engine: Some(engine(1_000, 0)),
+            genesis_events: vec![created(10, 1_000)],
+            ..Snapshot::default()
+        };
+        let report = audit(&snapshot);
+        assert_eq!(report.checks.len(), 1);
+        assert!(report.is_valid());
+
+        let snapshot = Snapshot { engine: Some(engine(999, 0)), ..snapshot };
+        assert!(!audit(&snapshot).is_valid());
+    }
+}
@@ -0,0 +1,48 @@
+//! rsm-audit: checks the cross-program invariants and prints the report as
+//! JSON. Exits with status 1 when any invariant does not hold.
+
+use clap::Parser;
+use rsm_audit::{audit, source::Source};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::{error::Error, process};
+
+#[derive(Parser, Debug)]
+#[command(name = "rsm-audit", about = "Check invariants across rsm-token, dna-token and genesis-engine")]
+struct Args {
+    #[arg(long, default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+    /// rsm-token program, for the supply check
+    #[arg(long, requires = "mint")]
+    program: Option<Pubkey>,
+    #[arg(long, requires = "program")]
+    mint: Option<Pubkey>,
+    /// dna-token program, to match genesis records to their genomes
+    #[arg(long, requires = "genesis_program")]
+    dna_program: Option<Pubkey>,
+    #[arg(long)]
+    genesis_program: Option<Pubkey>,
+    /// GenesisEngine account, to check its RNA burn total against the
+    /// program's events; walks its whole transaction history
+    #[arg(long, requires = "genesis_program")]
+    engine: Option<Pubkey>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let source = Source {
+        rpc: RpcClient::new_with_commitment(args.rpc_url, CommitmentConfig::confirmed()),
+        rsm_program: args.program,
+        mint: args.mint,
+        dna_program: args.dna_program,
+        genesis_program: args.genesis_program,
+        engine: args.engine,
+    };
+
+    let report = audit(&source.load()?);
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !report.is_valid() {
+        process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Reads the audited programs' accounts and genesis-engine's event history
+//! over RPC.
+
+use crate::{AuditError, Snapshot};
+use borsh::BorshDeserialize;
+use rsm_events::{decode_transaction_meta, RsmEvent};
+use rsm_genesis_verify::{decode_account, DnaGenome, GenesisRecord};
+use rsm_token::{config_address, genome_address, GenomeData, TokenConfig};
+use solana_client::{
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::RpcTransactionConfig,
+};
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::str::FromStr;
+
+pub struct Source {
+    pub rpc: RpcClient,
+    /// rsm-token program and mint; the supply check is skipped without them
+    pub rsm_program: Option<Pubkey>,
+    pub mint: Option<Pubkey>,
+    /// dna-token; genesis records are not matched to genomes without it
+    pub dna_program: Option<Pubkey>,
+    pub genesis_program: Option<Pubkey>,
+    /// GenesisEngine account; the burn check is skipped without it
+    pub engine: Option<Pubkey>,
+}
+
+impl Source {
+    /// Accounts are read after the slot, so a snapshot taken while the
+    /// programs are in use can disagree with itself; rerun to confirm.
+    pub fn load(&self) -> Result<Snapshot, AuditError> {
+        let mut snapshot = Snapshot { slot: self.rpc.get_slot().map_err(Box::new)?, ..Snapshot::default() };
+
+        if let (Some(program), Some(mint)) = (self.rsm_program, self.mint) {
+            let config = config_address(&program, &mint).0;
+            let data = self.account_data(&config)?;
+            snapshot.config = Some(decode_config(&data)?);
+            snapshot.genomes = self
+                .program_accounts(&program)?
+                .into_iter()
+                .filter_map(|(address, account)| decode_genome(&program, &config, &address, &account.data))
+                .collect();
+        }
+
+        if let Some(genesis) = self.genesis_program {
+            snapshot.geneses = self
+                .program_accounts(&genesis)?
+                .into_iter()
+                .filter_map(|(_, account)| decode_account::<GenesisRecord>("GenesisRecord", &account.data).ok())
+                .collect();
+            if let Some(dna) = self.dna_program {
+                snapshot.dna_genomes = self
+                    .program_accounts(&dna)?
+                    .into_iter()
+                    .filter_map(|(address, account)| {
+                        decode_account::<DnaGenome>("GenomeV2", &account.data).ok().map(|genome| (address, genome))
+                    })
+                    .collect();
+                snapshot.dna_program = Some(dna);
+            }
+            if let Some(engine) = self.engine {
+                snapshot.engine = Some(decode_account("GenesisEngine", &self.account_data(&engine)?)?);
+                snapshot.genesis_events = self.program_events(&genesis)?;
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Every event `program` logged itself in its successful transactions,
+    /// walking its whole signature history.
+    pub fn program_events(&self, program: &Pubkey) -> Result<Vec<RsmEvent>, AuditError> {
+        let mut events = Vec::new();
+        let mut before = None;
+        loop {
+            let config = GetConfirmedSignaturesForAddress2Config {
+                before,
+                commitment: Some(CommitmentConfig::confirmed()),
+                ..GetConfirmedSignaturesForAddress2Config::default()
+            };
+            let statuses = self.rpc.get_signatures_for_address_with_config(program, config).map_err(Box::new)?;
+            let Some(last) = statuses.last() else {
+                break;
+            };
+            before = Signature::from_str(&last.signature).ok();
+
+            for status in statuses.iter().filter(|status| status.err.is_none()) {
+                let Ok(signature) = Signature::from_str(&status.signature) else {
+                    continue;
+                };
+                let config = RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                };
+                let transaction = self.rpc.get_transaction_with_config(&signature, config).map_err(Box::new)?;
+                let Some(meta) = transaction.transaction.meta else {
+                    continue;
+                };
+                events.extend(
+                    decode_transaction_meta(&meta)?
+                        .into_iter()
+                        .filter(|decoded| decoded.program_id.as_ref() == Some(program))
+                        .map(|decoded| decoded.event),
+                );
+            }
+        }
+        Ok(events)
+    }
+
+    fn account_data(&self, address: &Pubkey) -> Result<Vec<u8>, AuditError> {
+        self.rpc
+            .get_account_data(address)
+            .map_err(|_| AuditError::MissingAccount(*address))
+    }
+
+    fn program_accounts(&self, program: &Pubkey) -> Result<Vec<(Pubkey, Account)>, AuditError> {
+        Ok(self.rpc.get_program_accounts(program).map_err(Box::new)?)
+    }
+}
+
+/// The config at the current schema, migrated in memory if needed.
+pub fn decode_config(data: &[u8]) -> Result<TokenConfig, AuditError> {
+    let migrated = TokenConfig::SCHEMA
+        .migrate(data)
+        .map_err(|err| AuditError::Layout("TokenConfig", std::io::Error::other(err)))?;
+    TokenConfig::try_from_slice(migrated.as_deref().unwrap_or(data)).map_err(|err| AuditError::Layout("TokenConfig", err))
+}
+
+/// A genome of `config`, migrated in memory if it still has an old
+/// schema; other rsm-token accounts are skipped.
+pub fn decode_genome(program: &Pubkey, config: &Pubkey, address: &Pubkey, data: &[u8]) -> Option<GenomeData> {
+    let migrated = GenomeData::SCHEMA.migrate(data).ok()?;
+    let genome = GenomeData::try_from_slice(migrated.as_deref().unwrap_or(data)).ok()?;
+    (genome_address(program, config, &genome.hash).0 == *address).then_some(genome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    #[test]
+    fn test_decode_genome_checks_address() {
+        let (program, config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut genome = GenomeData::try_from_slice(&[0u8; GenomeData::LEN]).unwrap();
+        genome.schema_version = GenomeData::SCHEMA.current();
+        genome.hash = [7; 32];
+        let bytes = genome.try_to_vec().unwrap();
+        let address = genome_address(&program, &config, &[7; 32]).0;
+        assert!(decode_genome(&program, &config, &address, &bytes).is_some());
+        assert!(decode_genome(&program, &Pubkey::new_unique(), &address, &bytes).is_none());
+        assert!(decode_genome(&program, &config, &address, &bytes[1..]).is_none());
+    }
+}
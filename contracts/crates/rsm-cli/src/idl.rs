@@ -0,0 +1,102 @@
+//! `rsm idl fetch`: reads the IDL a program published with `anchor idl
+//! init`, so clients can decode it without the source tree.
+//!
+//! Anchor stores the IDL at `create_with_seed(base, "anchor:idl", program)`
+//! where `base` is the program's PDA for no seeds. The account holds a
+//! discriminator, the IDL authority, a u32 length and that many bytes of
+//! zlib-compressed JSON.
+
+use crate::Result;
+use clap::{Args, Subcommand};
+use flate2::read::ZlibDecoder;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, hash::hash, pubkey::Pubkey};
+use std::{fs, io::Read, path::PathBuf};
+
+const IDL_SEED: &str = "anchor:idl";
+const HEADER_LEN: usize = 8 + 32 + 4;
+
+#[derive(Args, Debug)]
+pub struct IdlArgs {
+    #[command(subcommand)]
+    command: IdlCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum IdlCommand {
+    /// Print a program's on-chain IDL as JSON
+    Fetch {
+        program: Pubkey,
+        #[arg(long, default_value = "http://127.0.0.1:8899")]
+        rpc_url: String,
+        /// Write the IDL here instead of printing it
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+pub async fn run(args: IdlArgs) -> Result<()> {
+    match args.command {
+        IdlCommand::Fetch { program, rpc_url, out } => {
+            let rpc = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+            let address = idl_address(&program)?;
+            let data = rpc
+                .get_account_data(&address)
+                .await
+                .map_err(|err| format!("no IDL published for {} at {}: {}", program, address, err))?;
+            let idl = serde_json::to_string_pretty(&decode_idl(&data)?)?;
+            match out {
+                Some(path) => fs::write(path, idl + "\n")?,
+                None => println!("{}", idl),
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn idl_address(program: &Pubkey) -> Result<Pubkey> {
+    let base = Pubkey::find_program_address(&[], program).0;
+    Ok(Pubkey::create_with_seed(&base, IDL_SEED, program)?)
+}
+
+fn decode_idl(data: &[u8]) -> Result<serde_json::Value> {
+    if data.len() < HEADER_LEN || data[..8] != hash(b"account:IdlAccount").to_bytes()[..8] {
+        return Err("account is not an Anchor IDL account".into());
+    }
+    let len = u32::from_le_bytes(data[40..HEADER_LEN].try_into()?) as usize;
+    let compressed = data.get(HEADER_LEN..HEADER_LEN + len).ok_or("IDL account is shorter than its data length")?;
+    let mut json = Vec::new();
+    ZlibDecoder::new(compressed).read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::ZlibEncoder, Compression};
+    use std::io::Write;
+
+    fn account(idl: &str, slack: usize) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(idl.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let mut data = hash(b"account:IdlAccount").to_bytes()[..8].to_vec();
+        data.extend(Pubkey::new_unique().to_bytes());
+        data.extend((compressed.len() as u32).to_le_bytes());
+        data.extend(compressed);
+        data.extend(vec![0; slack]);
+        data
+    }
+
+    #[test]
+    fn test_decode_idl() {
+        let data = account(r#"{"name":"rsm_dna_token","instructions":[]}"#, 64);
+        assert_eq!(decode_idl(&data).unwrap()["name"], "rsm_dna_token");
+        assert!(decode_idl(&data[..HEADER_LEN]).is_err());
+        assert!(decode_idl(&data[1..]).is_err());
+
+        let program = Pubkey::new_unique();
+        let base = Pubkey::find_program_address(&[], &program).0;
+        assert_eq!(idl_address(&program).unwrap(), Pubkey::create_with_seed(&base, "anchor:idl", &program).unwrap());
+    }
+}
@@ -0,0 +1,36 @@
+//! rsm: operator command line for an RSM deployment.
+//!
+//! `rsm watch` tails the programs' logs over WebSocket and prints every
+//! event they emit as it lands, effectively journalctl for the protocol.
+//! `rsm idl fetch` prints the IDL a program published on-chain.
+
+mod idl;
+mod watch;
+
+use clap::{Parser, Subcommand};
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser, Debug)]
+#[command(name = "rsm", about = "Operate an RSM deployment")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Tail program logs and print their decoded events
+    Watch(watch::WatchArgs),
+    /// Work with IDLs published on-chain
+    Idl(idl::IdlArgs),
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    match Args::parse().command {
+        Command::Watch(args) => watch::run(args).await,
+        Command::Idl(args) => idl::run(args).await,
+    }
+}
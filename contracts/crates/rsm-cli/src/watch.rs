@@ -0,0 +1,346 @@
+//! `rsm watch`: one `logsSubscribe` per program and a `slotSubscribe`, all
+//! on a single connection. Events print as timestamped lines colored by
+//! program, or as NDJSON with `--json`; failed transactions are skipped, as
+//! their events were rolled back. A dropped connection is re-established
+//! with exponential backoff. Slots whose notification never arrived, lost
+//! or missed while reconnecting, are reported as gaps; their events are not
+//! replayed.
+
+use crate::Result;
+use clap::Args;
+use futures::{stream::select_all, StreamExt};
+use rsm_events::{decode_log, DecodedEvent, RsmEvent};
+use serde::Deserialize;
+use serde_json::json;
+use solana_client::{
+    nonblocking::pubsub_client::PubsubClient,
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::{
+    collections::{BTreeMap, HashSet, VecDeque},
+    fs,
+    io::IsTerminal,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+const MIN_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// A transaction mentioning several watched programs arrives once per
+/// subscription; this many recent signatures are kept to print it once.
+const RECENT_SIGNATURES: usize = 4096;
+// ANSI SGR codes: programs cycle through the colors
+const PROGRAM_COLORS: [&str; 6] = ["36", "35", "33", "32", "34", "96"];
+const GAP_COLOR: &str = "1;31";
+const BOLD: &str = "1";
+const DIM: &str = "2";
+
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    #[arg(long, default_value = "ws://127.0.0.1:8900")]
+    ws_url: String,
+    /// Manifest written by rsm-localnet; every program in it is watched
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+    /// Program to watch, as NAME=ID or a bare ID; repeatable
+    #[arg(long = "program")]
+    programs: Vec<String>,
+    #[arg(long, default_value = "confirmed")]
+    commitment: String,
+    /// Print NDJSON instead of colored lines
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    programs: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Program {
+    name: String,
+    id: Pubkey,
+}
+
+/// `NAME=ID`, or a bare ID that names itself.
+fn parse_program(arg: &str) -> Result<Program> {
+    let (name, id) = arg.split_once('=').unwrap_or((arg, arg));
+    Ok(Program { name: name.to_string(), id: id.parse()? })
+}
+
+pub async fn run(args: WatchArgs) -> Result<()> {
+    let mut programs = Vec::new();
+    if let Some(path) = &args.manifest {
+        let manifest: Manifest = serde_json::from_str(&fs::read_to_string(path)?)?;
+        for (name, id) in manifest.programs {
+            programs.push(Program { name, id: id.parse()? });
+        }
+    }
+    for arg in &args.programs {
+        programs.push(parse_program(arg)?);
+    }
+    if programs.is_empty() {
+        return Err("nothing to watch: pass --manifest or --program".into());
+    }
+    let color = !args.json && std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let mut watcher = Watcher {
+        printer: Printer { programs, json: args.json, color },
+        commitment: CommitmentConfig::from_str(&args.commitment)?,
+        slots: SlotTracker::default(),
+        recent: RecentSignatures::default(),
+    };
+
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        match watcher.session(&args.ws_url, &mut backoff).await {
+            Ok(()) => eprintln!("rsm watch: connection closed, reconnecting in {:?}", backoff),
+            Err(err) => eprintln!("rsm watch: {}, reconnecting in {:?}", err, backoff),
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+struct Watcher {
+    printer: Printer,
+    commitment: CommitmentConfig,
+    slots: SlotTracker,
+    recent: RecentSignatures,
+}
+
+impl Watcher {
+    /// One connection: subscribe to every program's logs and to slots, then
+    /// print until the socket goes away.
+    async fn session(&mut self, ws_url: &str, backoff: &mut Duration) -> Result<()> {
+        let pubsub = PubsubClient::new(ws_url).await?;
+        let mut logs = Vec::new();
+        let mut unsubscribes = Vec::new();
+        for program in &self.printer.programs {
+            let filter = RpcTransactionLogsFilter::Mentions(vec![program.id.to_string()]);
+            let config = RpcTransactionLogsConfig { commitment: Some(self.commitment) };
+            let (notifications, unsubscribe) = pubsub.logs_subscribe(filter, config).await?;
+            logs.push(notifications);
+            unsubscribes.push(unsubscribe);
+        }
+        let (mut slots, unsubscribe) = pubsub.slot_subscribe().await?;
+        unsubscribes.push(unsubscribe);
+        *backoff = MIN_BACKOFF;
+
+        let mut logs = select_all(logs);
+        loop {
+            tokio::select! {
+                slot = slots.next() => {
+                    let Some(slot) = slot else { break };
+                    if let Some(gap) = self.slots.observe(slot.slot, slot.parent) {
+                        println!("{}", self.printer.gap_line(SystemTime::now(), &gap));
+                    }
+                }
+                notification = logs.next() => {
+                    let Some(notification) = notification else { break };
+                    let response = notification.value;
+                    if response.err.is_some() || !self.recent.insert(&response.signature) {
+                        continue;
+                    }
+                    match decode_log(&response.logs) {
+                        Ok(decoded) => {
+                            for event in &decoded {
+                                let line = self.printer.event_line(SystemTime::now(), notification.context.slot, &response.signature, event);
+                                println!("{}", line);
+                            }
+                        }
+                        Err(err) => eprintln!("rsm watch: {}: {}", response.signature, err),
+                    }
+                }
+            }
+        }
+
+        for unsubscribe in unsubscribes {
+            unsubscribe().await;
+        }
+        drop(logs);
+        drop(slots);
+        pubsub.shutdown().await?;
+        Ok(())
+    }
+}
+
+/// Slots `first..=last` that were never observed.
+#[derive(Debug, PartialEq, Eq)]
+struct SlotGap {
+    first: u64,
+    last: u64,
+}
+
+#[derive(Default)]
+struct SlotTracker {
+    newest: Option<u64>,
+}
+
+impl SlotTracker {
+    /// Record a slot notification. Skipped leader slots never get one, so a
+    /// gap is judged by the slot's parent rather than its number.
+    fn observe(&mut self, slot: u64, parent: u64) -> Option<SlotGap> {
+        let gap = match self.newest {
+            Some(newest) if parent > newest => Some(SlotGap { first: newest + 1, last: parent }),
+            _ => None,
+        };
+        self.newest = Some(self.newest.map_or(slot, |newest| newest.max(slot)));
+        gap
+    }
+}
+
+#[derive(Default)]
+struct RecentSignatures {
+    order: VecDeque<String>,
+    seen: HashSet<String>,
+}
+
+impl RecentSignatures {
+    /// False if `signature` was already seen.
+    fn insert(&mut self, signature: &str) -> bool {
+        if !self.seen.insert(signature.to_string()) {
+            return false;
+        }
+        self.order.push_back(signature.to_string());
+        if self.order.len() > RECENT_SIGNATURES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+struct Printer {
+    programs: Vec<Program>,
+    json: bool,
+    color: bool,
+}
+
+impl Printer {
+    fn event_line(&self, at: SystemTime, slot: u64, signature: &str, decoded: &DecodedEvent) -> String {
+        let time = humantime::format_rfc3339_millis(at).to_string();
+        let watched = decoded.program_id.and_then(|id| self.programs.iter().position(|program| program.id == id));
+        let name = match (watched, decoded.program_id) {
+            (Some(index), _) => self.programs[index].name.clone(),
+            (None, Some(id)) => id.to_string(),
+            (None, None) => "?".to_string(),
+        };
+        if self.json {
+            return json!({
+                "time": time,
+                "slot": slot,
+                "signature": signature,
+                "program": name,
+                "event": decoded.event,
+            })
+            .to_string();
+        }
+        let color = watched.map_or(DIM, |index| PROGRAM_COLORS[index % PROGRAM_COLORS.len()]);
+        format!(
+            "{} {:>10} {} {} {}",
+            self.paint(DIM, &time),
+            slot,
+            self.paint(color, &name),
+            self.paint(BOLD, decoded.event.name()),
+            event_fields(&decoded.event),
+        )
+    }
+
+    fn gap_line(&self, at: SystemTime, gap: &SlotGap) -> String {
+        let time = humantime::format_rfc3339_millis(at).to_string();
+        if self.json {
+            return json!({ "time": time, "gap": { "first": gap.first, "last": gap.last } }).to_string();
+        }
+        let warning = format!("slots {}..={} were not observed; their events are missing", gap.first, gap.last);
+        format!("{} {}", self.paint(DIM, &time), self.paint(GAP_COLOR, &warning))
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// The event's fields as `{ name: value, .. }`, without the event name the
+/// derived `Debug` repeats.
+fn event_fields(event: &RsmEvent) -> String {
+    let debug = format!("{:?}", event);
+    debug
+        .strip_prefix(event.name())
+        .and_then(|rest| rest.strip_prefix('('))
+        .and_then(|rest| rest.strip_suffix(')'))
+        .and_then(|rest| rest.strip_prefix(event.name()))
+        .map_or(debug.clone(), |fields| fields.trim_start().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsm_events::PauseToggledEvent;
+    use std::time::UNIX_EPOCH;
+
+
+    #[test]
+    fn test_slot_gaps_follow_parents() {
+        let mut slots = SlotTracker::default();
+        assert_eq!(slots.observe(100, 99), None);
+        // 101 was skipped by its leader, so 102 builds on 100
+        assert_eq!(slots.observe(102, 100), None);
+        assert_eq!(slots.observe(106, 105), Some(SlotGap { first: 103, last: 105 }));
+        // A late notification from another fork is not a gap
+        assert_eq!(slots.observe(104, 103), None);
+        assert_eq!(slots.observe(107, 106), None);
+    }
+
+    #[test]
+    fn test_recent_signatures_print_once() {
+        let mut recent = RecentSignatures::default();
+        assert!(recent.insert("a"));
+        assert!(!recent.insert("a"));
+        for i in 0..RECENT_SIGNATURES {
+            recent.insert(&i.to_string());
+        }
+        // Evicted once RECENT_SIGNATURES newer ones arrived
+        assert!(recent.insert("a"));
+    }
+
+    #[test]
+    fn test_event_lines() {
+        let program = Pubkey::new_unique();
+        let printer = Printer {
+            programs: vec![parse_program(&format!("rsm_pause={}", program)).unwrap()],
+            json: false,
+            color: false,
+        };
+        let event = PauseToggledEvent { paused: true, signer: program, reason_code: 3, timestamp: 0 };
+        let decoded = DecodedEvent { program_id: Some(program), event: RsmEvent::PauseToggledEvent(event) };
+        let at = UNIX_EPOCH + Duration::from_millis(1_500);
+
+        let line = printer.event_line(at, 42, "sig", &decoded);
+        assert_eq!(
+            line,
+            format!(
+                "1970-01-01T00:00:01.500Z         42 rsm_pause PauseToggledEvent {{ paused: true, signer: {}, reason_code: 3, timestamp: 0 }}",
+                program
+            )
+        );
+
+        let json = Printer { json: true, ..printer };
+        let line: serde_json::Value = serde_json::from_str(&json.event_line(at, 42, "sig", &decoded)).unwrap();
+        assert_eq!(line["slot"], 42);
+        assert_eq!(line["program"], "rsm_pause");
+        assert_eq!(line["event"]["type"], "PauseToggledEvent");
+        assert_eq!(line["event"]["data"]["paused"], true);
+
+        let gap: serde_json::Value = serde_json::from_str(&json.gap_line(at, &SlotGap { first: 7, last: 9 })).unwrap();
+        assert_eq!(gap["gap"]["first"], 7);
+        assert_eq!(gap["gap"]["last"], 9);
+    }
+}
@@ -0,0 +1,287 @@
+//! Memoized account reads for UIs that poll the same accounts.
+//!
+//! An `AccountCache` keeps the decoded state of every account it was asked
+//! for and watches each one over the WebSocket, so repeated reads cost no
+//! RPC round trip. The first read of an address opens its subscription,
+//! whose backfill doubles as the fetch; each account-change notification
+//! then replaces the cached value. While the socket is down the entry is
+//! invalidated and reads go to RPC until the watcher reconnects and
+//! backfills. `evict` stops watching one account; dropping the cache stops
+//! them all.
+
+use crate::{
+    subscribe::{parse_account, watch_account},
+    AccountUpdate, ClientError, RsmClient, UpdateStream,
+};
+use borsh::BorshDeserialize;
+use futures::StreamExt;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, hash::hash, pubkey::Pubkey};
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+    sync::{Arc, Mutex, Weak},
+};
+use tokio::task::AbortHandle;
+
+type Entries<T> = Mutex<HashMap<Pubkey, Entry<T>>>;
+
+struct Entry<T> {
+    /// `None` while invalidated
+    value: Option<Option<Arc<T>>>,
+    slot: u64,
+    watcher: AbortHandle,
+}
+
+impl<T> Entry<T> {
+    /// Takes a watcher update unless it is older than the cached state. An
+    /// error means notifications may be missed until the watcher backfills,
+    /// so the value is dropped.
+    fn apply(&mut self, update: Result<AccountUpdate<T>, ClientError>) {
+        match update {
+            Ok(update) if update.slot >= self.slot => {
+                self.slot = update.slot;
+                self.value = Some(update.value.map(Arc::new));
+            }
+            Ok(_) => {}
+            Err(_) => self.value = None,
+        }
+    }
+}
+
+pub struct AccountCache<T> {
+    rpc: Arc<RpcClient>,
+    ws_url: String,
+    commitment: CommitmentConfig,
+    entries: Arc<Entries<T>>,
+}
+
+impl RsmClient {
+    /// An empty cache of `T` accounts on this client's endpoints, e.g.
+    /// `TokenConfig` at `config_address`, `GenomeData` at `genome_address`
+    /// or the `GenesisEngine`.
+    pub fn account_cache<T>(&self) -> AccountCache<T> {
+        AccountCache {
+            rpc: self.rpc.clone(),
+            ws_url: self.ws_url.clone(),
+            commitment: self.commitment,
+            entries: Arc::default(),
+        }
+    }
+}
+
+impl<T> AccountCache<T>
+where
+    T: BorshDeserialize + Send + Sync + 'static,
+{
+    /// The account's current state, `None` if it does not exist.
+    pub async fn get(&self, address: &Pubkey) -> Result<Option<Arc<T>>, ClientError> {
+        let cached = self.entries.lock().unwrap().get(address).map(|entry| entry.value.clone());
+        match cached {
+            Some(Some(value)) => return Ok(value),
+            Some(None) => return self.read(address).await,
+            None => {}
+        }
+
+        let mut updates: UpdateStream<AccountUpdate<T>> =
+            watch_account(self.rpc.clone(), self.ws_url.clone(), self.commitment, *address);
+        let Some(Ok(first)) = updates.next().await else {
+            // No socket to keep an entry current; read without caching
+            return self.read(address).await;
+        };
+        let value = first.value.map(Arc::new);
+
+        let mut entries = self.entries.lock().unwrap();
+        // A concurrent read of the same address may have got there first
+        if !entries.contains_key(address) {
+            let watcher = tokio::spawn(follow(Arc::downgrade(&self.entries), *address, updates)).abort_handle();
+            entries.insert(*address, Entry { value: Some(value.clone()), slot: first.slot, watcher });
+        }
+        Ok(value)
+    }
+
+    /// Stops watching `address` and forgets its state.
+    pub fn evict(&self, address: &Pubkey) {
+        if let Some(entry) = self.entries.lock().unwrap().remove(address) {
+            entry.watcher.abort();
+        }
+    }
+
+    pub fn is_cached(&self, address: &Pubkey) -> bool {
+        self.entries.lock().unwrap().get(address).is_some_and(|entry| entry.value.is_some())
+    }
+
+    async fn read(&self, address: &Pubkey) -> Result<Option<Arc<T>>, ClientError> {
+        let response = self.rpc.get_account_with_commitment(address, self.commitment).await?;
+        Ok(match response.value {
+            Some(account) => parse_account::<T>(address, &account)?.map(Arc::new),
+            None => None,
+        })
+    }
+}
+
+impl<T> Drop for AccountCache<T> {
+    fn drop(&mut self) {
+        for entry in self.entries.lock().unwrap().values() {
+            entry.watcher.abort();
+        }
+    }
+}
+
+/// Feeds `address`'s watcher updates into its entry until the entry is
+/// evicted or the cache dropped; ending drops the stream, which stops the
+/// watcher.
+async fn follow<T>(entries: Weak<Entries<T>>, address: Pubkey, mut updates: UpdateStream<AccountUpdate<T>>) {
+    while let Some(update) = updates.next().await {
+        let Some(entries) = entries.upgrade() else {
+            return;
+        };
+        let mut entries = entries.lock().unwrap();
+        let Some(entry) = entries.get_mut(&address) else {
+            return;
+        };
+        entry.apply(update);
+    }
+}
+
+/// Mirror of genesis-engine's `GenesisEngine` account. Decoding checks and
+/// skips the Anchor discriminator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenesisEngine {
+    pub authority: Pubkey,
+    pub genesis_cost: u64,
+    pub total_genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub active: bool,
+    pub rarity_weights: [u16; 4],
+    pub rarity_multipliers: [u16; 4],
+    pub incubation_slots: u64,
+    pub acceleration_cost_per_slot: u64,
+    pub trait_dominance: [u8; 8],
+    pub mutation_bps: u16,
+    pub min_parent_holding: u64,
+    pub queue_head: u64,
+    pub queue_tail: u64,
+    pub max_genesis_per_slot: u32,
+    pub last_genesis_slot: u64,
+    pub geneses_in_slot: u32,
+    pub has_mutation_catalog: bool,
+    pub consanguinity_generations: u8,
+    pub consanguinity_penalty: u8,
+    pub consanguinity_cost_bps: u16,
+    pub offspring_supply_bps: u16,
+    pub min_offspring_supply: u64,
+    pub max_offspring_supply: u64,
+    pub has_price_oracle: bool,
+    pub has_guardian: bool,
+}
+
+impl GenesisEngine {
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"account:GenesisEngine").to_bytes()[..8]);
+        discriminator
+    }
+}
+
+impl BorshDeserialize for GenesisEngine {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        if <[u8; 8]>::deserialize_reader(reader)? != Self::discriminator() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a GenesisEngine account"));
+        }
+        Ok(Self {
+            authority: BorshDeserialize::deserialize_reader(reader)?,
+            genesis_cost: BorshDeserialize::deserialize_reader(reader)?,
+            total_genomes_created: BorshDeserialize::deserialize_reader(reader)?,
+            total_rna_burned: BorshDeserialize::deserialize_reader(reader)?,
+            active: BorshDeserialize::deserialize_reader(reader)?,
+            rarity_weights: BorshDeserialize::deserialize_reader(reader)?,
+            rarity_multipliers: BorshDeserialize::deserialize_reader(reader)?,
+            incubation_slots: BorshDeserialize::deserialize_reader(reader)?,
+            acceleration_cost_per_slot: BorshDeserialize::deserialize_reader(reader)?,
+            trait_dominance: BorshDeserialize::deserialize_reader(reader)?,
+            mutation_bps: BorshDeserialize::deserialize_reader(reader)?,
+            min_parent_holding: BorshDeserialize::deserialize_reader(reader)?,
+            queue_head: BorshDeserialize::deserialize_reader(reader)?,
+            queue_tail: BorshDeserialize::deserialize_reader(reader)?,
+            max_genesis_per_slot: BorshDeserialize::deserialize_reader(reader)?,
+            last_genesis_slot: BorshDeserialize::deserialize_reader(reader)?,
+            geneses_in_slot: BorshDeserialize::deserialize_reader(reader)?,
+            has_mutation_catalog: BorshDeserialize::deserialize_reader(reader)?,
+            consanguinity_generations: BorshDeserialize::deserialize_reader(reader)?,
+            consanguinity_penalty: BorshDeserialize::deserialize_reader(reader)?,
+            consanguinity_cost_bps: BorshDeserialize::deserialize_reader(reader)?,
+            offspring_supply_bps: BorshDeserialize::deserialize_reader(reader)?,
+            min_offspring_supply: BorshDeserialize::deserialize_reader(reader)?,
+            max_offspring_supply: BorshDeserialize::deserialize_reader(reader)?,
+            has_price_oracle: BorshDeserialize::deserialize_reader(reader)?,
+            has_guardian: BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+    use solana_sdk::account::Account;
+
+    fn entry(slot: u64) -> Entry<u64> {
+        let watcher = tokio::spawn(async {}).abort_handle();
+        Entry { value: Some(Some(Arc::new(1))), slot, watcher }
+    }
+
+    #[tokio::test]
+    async fn test_entry_applies_newer_updates() {
+        let mut entry = entry(10);
+        entry.apply(Ok(AccountUpdate { slot: 9, value: Some(2) }));
+        assert_eq!(entry.value, Some(Some(Arc::new(1))));
+        entry.apply(Ok(AccountUpdate { slot: 12, value: Some(3) }));
+        assert_eq!((entry.value.clone(), entry.slot), (Some(Some(Arc::new(3))), 12));
+        entry.apply(Ok(AccountUpdate { slot: 13, value: None }));
+        assert_eq!(entry.value, Some(None));
+
+        // A dropped socket invalidates until the next update
+        entry.apply(Err(ClientError::Encoding("socket closed".to_string())));
+        assert_eq!(entry.value, None);
+        entry.apply(Ok(AccountUpdate { slot: 14, value: Some(4) }));
+        assert_eq!(entry.value, Some(Some(Arc::new(4))));
+    }
+
+    #[tokio::test]
+    async fn test_cached_reads_skip_rpc() {
+        let client = RsmClient::new(
+            "http://127.0.0.1:1",
+            "ws://127.0.0.1:1",
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let cache = client.account_cache::<u64>();
+        let address = Pubkey::new_unique();
+        cache.entries.lock().unwrap().insert(address, entry(10));
+        assert!(cache.is_cached(&address));
+        assert_eq!(cache.get(&address).await.unwrap(), Some(Arc::new(1)));
+
+        cache.evict(&address);
+        assert!(!cache.is_cached(&address));
+        assert!(cache.get(&address).await.is_err());
+    }
+
+    #[test]
+    fn test_engine_checks_discriminator() {
+        let mut data = GenesisEngine::discriminator().to_vec();
+        data.extend(Pubkey::new_unique().to_bytes());
+        data.extend(1_000u64.try_to_vec().unwrap());
+        data.resize(8 + 200, 0);
+        let account = Account { lamports: 1, data, ..Account::default() };
+        let address = Pubkey::new_unique();
+
+        let engine: GenesisEngine = parse_account(&address, &account).unwrap().unwrap();
+        assert_eq!((engine.genesis_cost, engine.max_offspring_supply), (1_000, 0));
+
+        let mut other = account.clone();
+        other.data[0] ^= 1;
+        assert!(matches!(parse_account::<GenesisEngine>(&address, &other), Err(ClientError::Layout { .. })));
+    }
+}
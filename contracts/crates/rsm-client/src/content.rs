@@ -0,0 +1,99 @@
+//! Checks a genome's off-chain metadata against the hashes dna-token
+//! stores: `metadata_uri_hash` commits to the URI and `content_hash` to the
+//! JSON or FASTA document it serves, so a marketplace can tell when either
+//! was swapped after the genome was created.
+
+use solana_sdk::hash::{hash, Hash};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContentError {
+    #[error("Genome has no content hash to verify against")]
+    NoContentHash,
+    #[error("URI {uri} does not match the genome's metadata_uri_hash")]
+    UriMismatch { uri: String },
+    #[error("Off-chain content hashes to {actual}, the genome committed to {expected}")]
+    ContentMismatch { expected: Hash, actual: Hash },
+    #[error("Fetching {uri} failed: {reason}")]
+    Fetch { uri: String, reason: String },
+}
+
+/// sha256 of off-chain content, as dna-token's `content_hash` stores it.
+pub fn content_hash(content: &[u8]) -> [u8; 32] {
+    hash(content).to_bytes()
+}
+
+/// Checks `content` served from `uri` against a genome's hashes. A zero
+/// `metadata_uri_hash` skips the URI check, since founder genomes may
+/// not have one.
+pub fn verify_content(
+    uri: &str,
+    content: &[u8],
+    metadata_uri_hash: &[u8; 32],
+    expected: &[u8; 32],
+) -> Result<(), ContentError> {
+    if *expected == [0u8; 32] {
+        return Err(ContentError::NoContentHash);
+    }
+    if *metadata_uri_hash != [0u8; 32] && hash(uri.as_bytes()).to_bytes() != *metadata_uri_hash {
+        return Err(ContentError::UriMismatch { uri: uri.to_string() });
+    }
+    let actual = content_hash(content);
+    if actual != *expected {
+        return Err(ContentError::ContentMismatch {
+            expected: Hash::new_from_array(*expected),
+            actual: Hash::new_from_array(actual),
+        });
+    }
+    Ok(())
+}
+
+/// Fetches `uri` and verifies it like `verify_content`, returning the
+/// content only if it is what the genome committed to.
+pub async fn fetch_verified_content(
+    http: &reqwest::Client,
+    uri: &str,
+    metadata_uri_hash: &[u8; 32],
+    expected: &[u8; 32],
+) -> Result<Vec<u8>, ContentError> {
+    let fetch_error = |err: reqwest::Error| ContentError::Fetch { uri: uri.to_string(), reason: err.to_string() };
+    let content = http
+        .get(uri)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(fetch_error)?
+        .bytes()
+        .await
+        .map_err(fetch_error)?;
+    verify_content(uri, &content, metadata_uri_hash, expected)?;
+    Ok(content.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URI: &str = "https://example.com/genomes/1.fasta";
+    const FASTA: &[u8] = b">genome-1\nACGTTGCAACGGTACC\n";
+
+    #[test]
+    fn test_verify_content() {
+        let uri_hash = hash(URI.as_bytes()).to_bytes();
+        let expected = content_hash(FASTA);
+        assert!(verify_content(URI, FASTA, &uri_hash, &expected).is_ok());
+        // Founder genomes without a URI hash only check the content
+        assert!(verify_content(URI, FASTA, &[0u8; 32], &expected).is_ok());
+
+        let tampered = b">genome-1\nACGTTGCAACGGTACA\n";
+        assert!(matches!(
+            verify_content(URI, tampered, &uri_hash, &expected),
+            Err(ContentError::ContentMismatch { actual, .. }) if actual.to_bytes() == content_hash(tampered)
+        ));
+        assert!(matches!(
+            verify_content("https://example.com/genomes/2.fasta", FASTA, &uri_hash, &expected),
+            Err(ContentError::UriMismatch { .. })
+        ));
+        assert!(matches!(verify_content(URI, FASTA, &uri_hash, &[0u8; 32]), Err(ContentError::NoContentHash)));
+    }
+}
@@ -0,0 +1,459 @@
+//! Typed errors decoded from failed transactions.
+//!
+//! The programs report failures as `InstructionError::Custom(code)`.
+//! rsm-token numbers `RSMError` from 0; dna-token and genesis-engine are
+//! Anchor programs and number their `ErrorCode` from 6000. `Programs` maps
+//! the failing instruction back to the program that raised the code and
+//! decodes it into that program's error, with its on-chain message and a
+//! hint at the usual fix.
+//!
+//! Only the program of the failing top-level instruction is known, so a
+//! code raised in a CPI (e.g. rsm-token behind a multisig execute) is
+//! reported raw against the outer program.
+
+use crate::ClientError;
+use rsm_token::RSMError;
+use solana_sdk::{
+    instruction::InstructionError, message::Message, pubkey::Pubkey, transaction::TransactionError,
+};
+use std::fmt;
+use thiserror::Error;
+
+/// First custom code of an Anchor `#[error_code]` enum.
+pub const ANCHOR_ERROR_OFFSET: u32 = 6000;
+
+/// `RSMError` in code order. Append here when rsm-token appends a variant.
+const RSM_TOKEN_ERRORS: &[RSMError] = &[
+    RSMError::EmissionAnomaly,
+    RSMError::StaleNonce,
+    RSMError::DedupeBufferFull,
+    RSMError::AlreadyMinted,
+    RSMError::GenomeStillMinted,
+    RSMError::AuditPageOutOfOrder,
+    RSMError::AuditFinished,
+    RSMError::AuditStale,
+    RSMError::DelegateExpired,
+    RSMError::DelegateLimitExceeded,
+    RSMError::NoPendingRaise,
+    RSMError::RaiseTimelocked,
+    RSMError::RaiseExceedsCap,
+    RSMError::ScoreNotExpired,
+    RSMError::NoPendingClawback,
+    RSMError::ClawbackDelayed,
+    RSMError::ClawbackDue,
+    RSMError::BurnApprovalExpired,
+    RSMError::BurnAllowanceExceeded,
+    RSMError::NothingVested,
+    RSMError::InvalidSplit,
+    RSMError::MissingGenomeOrigin,
+    RSMError::DisputesDisabled,
+    RSMError::ChallengeWindowClosed,
+    RSMError::InvalidEvidence,
+    RSMError::MintNotInflated,
+    RSMError::NoteTooLong,
+    RSMError::MigrationFailed,
+    RSMError::RecipientSignatureRequired,
+    RSMError::MintWindowClosed,
+    RSMError::DustBelowThreshold,
+    RSMError::UnknownSlotHash,
+    RSMError::RecipientNotAllowlisted,
+    RSMError::AutoStakeNotOptedIn,
+    RSMError::RegistrantMismatch,
+    RSMError::MintQuorumNotMet,
+    RSMError::ClawbackNotPending,
+    RSMError::ClawbackNotDelegated,
+    RSMError::AuditIncomplete,
+    RSMError::DuplicateMint,
+    RSMError::BondHeld,
+    RSMError::BondLocked,
+    RSMError::NoBond,
+];
+
+fn rsm_token_hint(err: RSMError) -> &'static str {
+    match err {
+        RSMError::EmissionAnomaly => "The circuit breaker is latched; wait for governance to review the window and ResetBreaker",
+        RSMError::StaleNonce => "Rebuild the mint with a current recent_slot and resend",
+        RSMError::DedupeBufferFull => "Retry once older nonces fall out of the validity window",
+        RSMError::AlreadyMinted => "Nothing to do; this genome has already been minted",
+        RSMError::GenomeStillMinted => "Burn or claw back the genome's tokens before closing it",
+        RSMError::AuditPageOutOfOrder => "Sort the page's accounts by address and drop repeats",
+        RSMError::AuditFinished => "Start a new audit",
+        RSMError::AuditStale => "Finish the audit, or restart it if supply moved meanwhile",
+        RSMError::DelegateExpired => "Ask the authority to renew the mint delegate",
+        RSMError::DelegateLimitExceeded => "Mint less, or ask the authority to raise the delegate's limit",
+        RSMError::NoPendingRaise => "Schedule a max supply raise first",
+        RSMError::RaiseTimelocked => "Execute the raise after its timelock has passed",
+        RSMError::RaiseExceedsCap => "Schedule a smaller raise that fits the yearly cap",
+        RSMError::ScoreNotExpired => "Re-score the genome after its score expires",
+        RSMError::NoPendingClawback => "Schedule a clawback first",
+        RSMError::ClawbackDelayed => "Execute the clawback after its delay has passed",
+        RSMError::ClawbackDue => "Execute the due clawback before this instruction",
+        RSMError::BurnApprovalExpired => "Ask the holder for a fresh burn approval",
+        RSMError::BurnAllowanceExceeded => "Burn at most the approved amount",
+        RSMError::NothingVested => "Withdraw again after more has vested",
+        RSMError::InvalidSplit => "Pass 1-5 positive split shares that sum to 10000 bps",
+        RSMError::MissingGenomeOrigin => "Set the mint's origin to the slot or time the genome was first seen",
+        RSMError::DisputesDisabled => "Ask governance to enable disputes",
+        RSMError::ChallengeWindowClosed => "Mints can only be challenged within the challenge window",
+        RSMError::InvalidEvidence => "Attach re-score evidence signed by the configured evidence signer",
+        RSMError::MintNotInflated => "The dispute fails; the mint matches the re-score",
+        RSMError::NoteTooLong => "Shorten the note to at most 64 bytes",
+        RSMError::MigrationFailed => "Check that the account belongs to this program and mint",
+        RSMError::RecipientSignatureRequired => "Add the recipient as a signer of the mint",
+        RSMError::MintWindowClosed => "Mint again once the next epoch's window opens",
+        RSMError::DustBelowThreshold => "Consolidate once accumulated dust reaches the threshold",
+        RSMError::UnknownSlotHash => "Mint against the newest slot in the SlotHashes sysvar and resend",
+        RSMError::RecipientNotAllowlisted => "Pass the recipient's Merkle proof or its membership token account",
+        RSMError::AutoStakeNotOptedIn => "Have the recipient call set_auto_stake for this staking program, or mint without auto-stake",
+        RSMError::RegistrantMismatch => "Mint to the genome's registrant, without a split",
+        RSMError::MintQuorumNotMet => "Pass the quorum's required number of controller co-signers after the split accounts, each signing",
+        RSMError::ClawbackNotPending => "Schedule a new clawback with fresh evidence; this record is closed",
+        RSMError::ClawbackNotDelegated => "Have the token account owner approve the config as delegate for at least the clawback amount",
+        RSMError::AuditIncomplete => "Restart the audit and page through every genome account; configs without a genome census can only have their supply raised",
+        RSMError::DuplicateMint => "Nothing to do; an earlier attempt with this nonce already landed",
+        RSMError::BondHeld => "ReleaseBond once the clawback window closes, then burn the genome",
+        RSMError::BondLocked => "Wait for the score validity and clawback delay to pass with no open dispute",
+        RSMError::NoBond => "Nothing to release; the bond was already paid out or slashed",
+    }
+}
+
+macro_rules! anchor_errors {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $msg:literal, $hint:literal;)* }) => {
+        $(#[$meta])*
+        #[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+        pub enum $name {
+            $(#[error($msg)] $variant,)*
+        }
+
+        impl $name {
+            const ALL: &'static [$name] = &[$($name::$variant,)*];
+
+            pub fn from_code(code: u32) -> Option<Self> {
+                let index = code.checked_sub(ANCHOR_ERROR_OFFSET)?;
+                Self::ALL.get(index as usize).copied()
+            }
+
+            pub fn code(self) -> u32 {
+                ANCHOR_ERROR_OFFSET + self as u32
+            }
+
+            pub fn hint(self) -> &'static str {
+                match self {
+                    $($name::$variant => $hint,)*
+                }
+            }
+        }
+    };
+}
+
+anchor_errors! {
+    /// dna-token `ErrorCode`, in declaration order.
+    DnaTokenError {
+        AlreadyMinted => "Genome already minted", "Nothing to do; this genome has already been minted";
+        InvalidAmount => "Invalid token amount", "Pass a positive amount within the account's balance";
+        Overflow => "Math overflow", "Use a smaller amount";
+        Unauthorized => "Unauthorized", "Sign with the authority this instruction expects";
+        InvalidSnapshotId => "Snapshot id must be the next in sequence", "Use the next snapshot id after the latest one";
+        SnapshotFinalized => "Snapshot already finalized", "Open a new snapshot instead";
+        SnapshotNotFinalized => "Snapshot not finalized", "Wait for the snapshot to be finalized";
+        CommitmentMismatch => "Snapshot data does not match commitment", "Rebuild the snapshot from the committed data";
+        InvalidProof => "Invalid merkle proof", "Regenerate the proof against the snapshot's root";
+        InvalidGenomeName => "Genome name must be 1-32 bytes", "Use a name of 1-32 bytes";
+        InvalidRate => "License rate must be positive", "Set a positive license rate";
+        GenomeIncubating => "Genome is still incubating", "Retry after the genome matures";
+        GenomeMintMismatch => "Token account does not hold this genome", "Pass a token account of the genome's mint";
+        InvalidMaturity => "Maturity can only be moved earlier", "Pass a maturity before the current one";
+        InvalidVotingAccounts => "Voting accounts must be (genome, token account) pairs", "Pass remaining accounts as (genome, token account) pairs";
+        DuplicateAccount => "Token account passed twice", "Pass each token account once";
+        CompressedGenomeMismatch => "Replacement record is for a different genome", "Pass the compressed record of this genome";
+        GenomeNotMinted => "Genome has not been minted", "Mint the genome first";
+        ZeroIndexWeight => "Genome has no index weight", "Ask the authority to give the genome an index weight";
+        IndexCapExceeded => "Deposit exceeds the genome's index cap", "Deposit less than the genome's remaining index cap";
+        InsufficientIndexDeposits => "Not enough of this genome in the index vault", "Redeem less, or redeem into another genome";
+        InvalidHoldTiers => "Hold tiers must have increasing durations and non-decreasing multipliers of at least 1.0x", "Order tiers by duration with multipliers of at least 1.0x that never decrease";
+        HoldAccountMismatch => "Token account is not the one tracked by this hold", "Pass the token account the hold was opened with";
+        EcosystemPaused => "Ecosystem is paused", "Retry after governance unpauses the ecosystem";
+        GenomeRetired => "Genome is retired", "Retired genomes can no longer be minted or traded";
+        PermitExpired => "Permit deadline has passed", "Ask the holder for a permit with a later deadline";
+        InvalidPermitNonce => "Permit nonce is not the holder's next nonce", "Re-sign the permit with the holder's current nonce";
+        InvalidPermitSignature => "Permit signature missing or not by the holder", "Include the holder's ed25519 signature over the permit";
+        PermitNotDelegated => "Token account has not delegated to the permit authority", "Approve the permit authority as delegate first";
+        InvalidBundle => "Bundle needs 1-8 legs, each with its escrow and token accounts", "Pass 1-8 legs, each with its escrow and token accounts";
+        InvalidFounderRate => "Founder rate cannot be negative", "Set a founder rate of zero or more";
+        InvalidConsciousnessBucket => "Consciousness page is not for this genome's bucket", "Pass the page of the genome's consciousness bucket";
+        ConsciousnessPageFull => "Consciousness page is full; open the next one", "Open the bucket's next page and retry";
+        PreviousPageNotFull => "Previous consciousness page is missing or not full", "Fill the bucket's current page before opening another";
+        GenomeAlreadyIndexed => "Genome is already in its consciousness bucket", "Nothing to do; the genome is already indexed";
+        ReceiptConsumed => "Burn receipt has already been consumed", "Each burn receipt can be redeemed once";
+        ListingRevoked => "Seller revoked the listing delegate or no longer holds the listed tokens", "Refresh listings and pick one that is still live";
+        SupplyStatsTracked => "Genome supply statistics are already tracked", "Nothing to do; the genome is already backfilled";
+        MintAuthorityFinalized => "Mint authority is already finalized", "Nothing to do; revoke a PDA-held authority as the registry authority or founder";
+        CorrectionWindowClosed => "Genome metadata can no longer be corrected", "Corrections need an unminted, non-genesis genome within a day of initialization";
+        InvalidCollectionName => "Collection name must be 1-32 bytes", "Use a non-empty name of at most 32 bytes";
+        FreezeAuthorityNotDelegated => "Genome mint's freeze authority is not delegated to the program", "Have the mint's freeze authority call delegate_freeze_authority first";
+        NoDisputedAccounts => "Pass the token accounts to freeze or thaw", "List the genome's token accounts as remaining accounts";
+        InvalidBlockchainTag => "Blockchain name must be at most 8 bytes", "Use a blockchain tag of at most 8 bytes";
+        NotLegacyGenome => "Account is not a legacy genome", "Nothing to do; the genome already has the current layout";
+        HoldAlreadyMigrated => "Hold already has balance checkpoints", "Nothing to do; the hold already has the current layout";
+        GenomeAlreadyExtended => "Genome already has the current layout", "Nothing to do; the genome already has the current layout";
+        ContentHashAlreadySet => "Genome content hash is already set", "The content hash is permanent; compare off-chain content against it";
+        InvalidContentHash => "Content hash must not be zero", "Pass the sha256 of the genome's off-chain JSON or FASTA";
+        InvalidHoldingCap => "Holding cap must be at most 10000 bps and have a window exactly when set", "Pass both a cap and a window in slots, or 0 for both";
+        HoldingCapExceeded => "Transfer would take the recipient above the genome's holding cap", "Send less, or wait until the holding cap window after minting ends";
+        InvalidGrowthCurve => "Growth curve needs a lock, a point cost and a per-claim bound, and stops at 100 at most", "Pass nonzero min_lock_epochs, base_experience and max_points_per_claim, and max_consciousness <= 100";
+        LockTooShort => "Stake must lock for at least the growth curve's minimum epochs", "Raise lock_epochs to the curve's min_lock_epochs";
+        NotEnoughExperience => "Stake has not earned enough experience for a consciousness point", "Wait more epochs, or the genome is at the curve's max_consciousness";
+        ConsciousnessPageRequired => "Genome is leaving its consciousness bucket; pass the page it is listed on", "Pass the ConsciousnessPage holding the genome, then re-index it";
+        StakeLocked => "Stake is still locked", "Unstake from the stake's unlock_epoch";
+        RegistryAlreadyExtended => "Registry already has the current layout", "Nothing to do; the registry already has the current layout";
+    }
+}
+
+anchor_errors! {
+    /// genesis-engine `ErrorCode`, in declaration order.
+    GenesisError {
+        Overflow => "Math overflow", "Use smaller values";
+        EngineInactive => "Genesis engine is inactive", "Retry after the authority reactivates the engine";
+        Unauthorized => "Unauthorized", "Sign with the authority this instruction expects";
+        InvalidRarityWeights => "Rarity weights must not all be zero", "Give at least one rarity a positive weight";
+        InvalidGenomeName => "Genome name must be 1-32 printable characters without padding", "Use 1-32 printable characters without leading or trailing spaces";
+        ProfaneGenomeName => "Genome name contains a blocked term", "Choose another name";
+        AccelerationDisabled => "Incubation acceleration is disabled", "Wait for the genome to mature";
+        AlreadyMature => "Genome has already matured", "Nothing to do; the genome is mature";
+        GenomeMismatch => "DNA genome does not match genesis record", "Pass the DNA genome created by this genesis";
+        PopulationCapReached => "Species pool is at its population cap", "Breed into another species pool";
+        ParentMismatch => "Parent accounts do not match parent_genome_ids", "Pass parent accounts in parent_genome_ids order";
+        SpeciesMismatch => "Parents are not of the pool's species", "Use parents of the pool's species, or a hybrid permit";
+        InvalidHybridPermit => "Hybrid permit is for another holder or species", "Pass a hybrid permit issued to you for these species";
+        InvalidPoolCurve => "Pool base cost must be positive", "Set a positive base cost";
+        EcosystemPaused => "Ecosystem is paused", "Retry after governance unpauses the ecosystem";
+        InvalidDominance => "Mutation rate exceeds 10000 bps", "Set a mutation rate of at most 10000 bps";
+        ParentNotHeld => "Parent genome is unminted or not held by the creator", "Hold the minimum amount of each parent genome";
+        InvalidMinParentHolding => "Minimum parent holding must be positive", "Set a positive minimum parent holding";
+        QueueNotEmpty => "Geneses are queued; enqueue instead of creating directly", "Enqueue the genesis instead";
+        SlotCapacityReached => "Genesis throughput cap for this slot is reached", "Retry in a later slot or enqueue";
+        InvalidQueueCap => "Genesis throughput cap must be positive", "Set a positive throughput cap";
+        MutationCatalogRequired => "Engine has a mutation catalog; pass it", "Add the mutation catalog account";
+        InvalidMutation => "Mutation needs a 1-32 byte name and a weight of at most 10000 bps", "Use a 1-32 byte name and a weight of at most 10000 bps";
+        MutationCatalogFull => "Mutation catalog is full", "Remove a mutation before adding another";
+        UnknownMutation => "No mutation with this id in the catalog", "Use an id from the engine's mutation catalog";
+        LineageMismatch => "Pass the Lineage of each genesis-born parent, in parent order", "Add each genesis-born parent's Lineage after the parent pairs";
+        InvalidConsanguinity => "Consanguinity depth exceeds the generations a lineage records", "Use a depth of at most the recorded lineage generations";
+        InvalidOffspringSupply => "Offspring supply needs a positive scale and 0 < min <= max", "Pass scale 0 to disable, or a positive scale with 0 < min <= max";
+        InvalidGenesisEvent => "Genesis event needs start < end and at most 8 valid mutations", "End the event after it starts and keep to 8 mutations with 1-32 byte names and weights up to 10000 bps";
+        GenesisEventClosed => "Genesis event is not running at this slot", "Breed without the event, or wait for its start slot";
+        GenesisEventFull => "Genesis event has no participant slots left", "Breed without the event";
+        InvalidPriceOracle => "Price oracle needs a positive staleness bound and confidence of at most 10000 bps", "Set a staleness bound above zero and a confidence bound of at most 10000 bps";
+        PriceOracleRequired => "Engine prices genesis in USD; pass its price oracle and feed", "Pass the engine's PriceOracle and its Pyth price feed";
+        InvalidPriceFeed => "Price feed is not the oracle's Pyth feed", "Pass the PriceUpdateV2 account the oracle names, for its feed id";
+        StalePrice => "Price update is older than the oracle allows", "Post a fresh Pyth price update and retry";
+        PriceTooUncertain => "Price confidence interval is wider than the oracle allows", "Retry once the feed's confidence narrows";
+        PriceNotVerified => "Price update is not fully verified", "Post the price update with full Wormhole verification";
+        RevealTooEarly => "Genesis can be revealed from the slot after its commit", "Reveal in a later slot";
+        RevealWindowClosed => "Reveal window has closed; the commit can only be forfeited", "Reveal within 512 slots of the commit; this escrow is lost";
+        CommitmentMismatch => "Entropy and salt do not match the commitment", "Reveal the exact entropy and salt hashed at commit";
+        RevealWindowOpen => "Commit can still be revealed", "Forfeit after the reveal window has passed";
+        GuardianRequired => "Engine has a genesis guardian; pass it", "Pass the engine's GenesisGuardian account";
+        GuardianSignatureRequired => "Genesis costs more than the guardian threshold; the guardian must co-sign", "Have the engine's guardian sign the transaction too";
+        QueuePending => "Queued geneses must be finalized before the engine state is exported", "Run process_queue until the queue is empty, then export";
+        SnapshotAlreadyImported => "Snapshot has already been imported", "A snapshot carries over once; export again only from an engine not yet imported";
+        InvalidSnapshot => "Snapshot version or digest does not match", "Re-export the state with the deployed program version";
+        EngineNotFresh => "Engine has already created genomes; import into a fresh engine", "Initialize a new engine and import into it before any genesis";
+        InvalidGenePool => "Gene pool donor boost exceeds 10000 bps", "Pass a donor streak boost of at most 10000 bps";
+        GenePoolFull => "Gene pool is full until its genomes are raffled", "Create the genome without donating, or wait for governance to run distribute_gene_pool";
+        GenePoolEmpty => "Gene pool has no genomes to raffle", "Wait for a genesis to be donated before distributing";
+        InvalidStakeAccounts => "Stakes must be dna-token GenomeStake accounts in increasing address order", "Sort the GenomeStake accounts by address and drop duplicates";
+        NoActiveStakers => "No stake passed is still locked with DNA in it", "Pass GenomeStake accounts whose lock has not yet ended";
+        InvalidRnaMint => "RNA mint is not the engine's", "Pass the RNA mint the engine was initialized with";
+        GenesisEventRequired => "Commit was made under a genesis event; pass it", "Pass the GenesisEvent the genome was created under to reveal_genesis";
+        GenePoolRequired => "Commit donates its child; pass the engine's gene pool", "Pass the engine's GenePool to reveal_genesis";
+        QueueSeedExpired => "Queued genesis's seed slot has left SlotHashes; reseed it first", "Run reseed_queued_genesis, then process_queue from the next slot";
+        QueueSeedAvailable => "Queued genesis's seed slot hash is still available", "Nothing to do; run process_queue";
+        QueueSeedPending => "Queued genesis can be processed from the slot after its seed slot", "Retry process_queue in the next slot";
+        TooManyParents => "A genesis takes at most 4 parents", "Breed from at most MAX_GENESIS_PARENTS parent genomes";
+    }
+}
+
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RsmProgramError {
+    #[error("rsm-token: {0}")]
+    RsmToken(RSMError),
+    #[error("dna-token: {0}")]
+    DnaToken(DnaTokenError),
+    #[error("genesis-engine: {0}")]
+    Genesis(GenesisError),
+}
+
+impl RsmProgramError {
+    pub fn code(&self) -> u32 {
+        match *self {
+            RsmProgramError::RsmToken(err) => err as u32,
+            RsmProgramError::DnaToken(err) => err.code(),
+            RsmProgramError::Genesis(err) => err.code(),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match *self {
+            RsmProgramError::RsmToken(err) => rsm_token_hint(err),
+            RsmProgramError::DnaToken(err) => err.hint(),
+            RsmProgramError::Genesis(err) => err.hint(),
+        }
+    }
+}
+
+/// A custom error raised by one instruction of a failed transaction.
+/// `error` is `None` when the program is not one of ours or the code is
+/// not in its table (e.g. an Anchor framework error below 6000).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramFailure {
+    pub instruction_index: u8,
+    pub program_id: Pubkey,
+    pub code: u32,
+    pub error: Option<RsmProgramError>,
+}
+
+impl fmt::Display for ProgramFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error {
+            Some(err) => write!(
+                f,
+                "Instruction {} failed with {} (0x{:x}); {}",
+                self.instruction_index,
+                err,
+                self.code,
+                err.hint()
+            ),
+            None => write!(
+                f,
+                "Instruction {} failed in {} with custom error 0x{:x}",
+                self.instruction_index, self.program_id, self.code
+            ),
+        }
+    }
+}
+
+/// Program ids of one deployment, for telling whose code a failure is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Programs {
+    pub rsm_token: Pubkey,
+    pub dna_token: Pubkey,
+    pub genesis_engine: Pubkey,
+}
+
+impl Programs {
+    pub fn decode(&self, program_id: &Pubkey, code: u32) -> Option<RsmProgramError> {
+        if *program_id == self.rsm_token {
+            RSM_TOKEN_ERRORS.get(code as usize).copied().map(RsmProgramError::RsmToken)
+        } else if *program_id == self.dna_token {
+            DnaTokenError::from_code(code).map(RsmProgramError::DnaToken)
+        } else if *program_id == self.genesis_engine {
+            GenesisError::from_code(code).map(RsmProgramError::Genesis)
+        } else {
+            None
+        }
+    }
+
+    /// `message` is the one the failed transaction carried; it names the
+    /// program of each instruction. Returns `None` unless an instruction
+    /// failed with a custom code.
+    pub fn decode_transaction_error(&self, err: &TransactionError, message: &Message) -> Option<ProgramFailure> {
+        let TransactionError::InstructionError(index, InstructionError::Custom(code)) = *err else {
+            return None;
+        };
+        let program_id = *message.instructions.get(index as usize)?.program_id(&message.account_keys);
+        Some(ProgramFailure {
+            instruction_index: index,
+            program_id,
+            code,
+            error: self.decode(&program_id, code),
+        })
+    }
+
+    /// Covers both a failed send and a failed preflight simulation.
+    pub fn decode_client_error(&self, err: &ClientError, message: &Message) -> Option<ProgramFailure> {
+        let ClientError::Rpc(err) = err else {
+            return None;
+        };
+        self.decode_transaction_error(&err.get_transaction_error()?, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions;
+    use solana_client::{
+        client_error::{ClientError as RpcError, ClientErrorKind},
+        rpc_request::{RpcError as RpcRequestError, RpcResponseErrorData},
+        rpc_response::RpcSimulateTransactionResult,
+    };
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    fn programs() -> Programs {
+        Programs {
+            rsm_token: Pubkey::new_unique(),
+            dna_token: Pubkey::new_unique(),
+            genesis_engine: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        for (code, err) in RSM_TOKEN_ERRORS.iter().enumerate() {
+            assert_eq!(*err as u32, code as u32);
+        }
+        assert_eq!(DnaTokenError::from_code(6000), Some(DnaTokenError::AlreadyMinted));
+        assert_eq!(DnaTokenError::SupplyStatsTracked.code(), 6037);
+        assert_eq!(GenesisError::from_code(GenesisError::InvalidConsanguinity.code()), Some(GenesisError::InvalidConsanguinity));
+        assert_eq!(GenesisError::from_code(3012), None);
+        assert_eq!(GenesisError::from_code(6059), None);
+    }
+
+    #[test]
+    fn test_decode_by_failing_program() {
+        let programs = programs();
+        let payer = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let message = Message::new(
+            &[
+                instructions::configure_dust(&programs.rsm_token, &config, &payer, 10, Pubkey::new_unique()),
+                Instruction::new_with_bytes(programs.genesis_engine, &[], vec![AccountMeta::new(payer, true)]),
+            ],
+            Some(&payer),
+        );
+
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(RSMError::MintWindowClosed as u32));
+        let failure = programs.decode_transaction_error(&err, &message).unwrap();
+        assert_eq!(failure.program_id, programs.rsm_token);
+        assert_eq!(failure.error, Some(RsmProgramError::RsmToken(RSMError::MintWindowClosed)));
+
+        // Same code, different program
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(6019));
+        let failure = programs.decode_transaction_error(&err, &message).unwrap();
+        assert_eq!(failure.error, Some(RsmProgramError::Genesis(GenesisError::SlotCapacityReached)));
+        assert_eq!(failure.error.unwrap().hint(), "Retry in a later slot or enqueue");
+
+        // Preflight failures carry the error in the simulation result
+        let rpc = RpcError::from(ClientErrorKind::RpcError(RpcRequestError::RpcResponseError {
+            code: -32002,
+            message: "Transaction simulation failed".to_string(),
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(RpcSimulateTransactionResult {
+                err: Some(err),
+                logs: None,
+                accounts: None,
+                units_consumed: None,
+                return_data: None,
+                inner_instructions: None,
+            }),
+        }));
+        let failure = programs.decode_client_error(&ClientError::from(rpc), &message).unwrap();
+        assert_eq!(failure.code, 6019);
+
+        // Anchor framework errors and unknown programs stay raw
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(3012));
+        assert_eq!(programs.decode_transaction_error(&err, &message).unwrap().error, None);
+        let err = TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature);
+        assert!(programs.decode_transaction_error(&err, &message).is_none());
+    }
+}
@@ -0,0 +1,641 @@
+//! Builders for the instructions an authority or controller signs.
+//!
+//! rsm-token admin instructions take the config and the signing key, which
+//! may be the multisig signer PDA (wrap the result with `multisig::propose`)
+//! or an air-gapped keypair (wrap it in an `OfflineTransaction`). Mint,
+//! burn and genome-account builders come from `rsm_token_interface` and are
+//! re-exported as `token`; `create_genome` and `reveal_genesis` cover the
+//! genesis engine.
+
+use crate::multisig::anchor_data;
+use borsh::BorshSerialize;
+use rsm_token::{clawback_address, genome_address, mint_delegate_address, RSMInstruction};
+use solana_sdk::{
+    hash::hashv,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+pub use rsm_token_interface::{accounts as token_accounts, instruction as token};
+
+pub const GENESIS_GENOME_ID_OFFSET: u64 = 100_000;
+
+fn rsm_ix(program: &Pubkey, instruction: RSMInstruction, accounts: Vec<AccountMeta>) -> Instruction {
+    let data = instruction.try_to_vec().expect("borsh serialization into a Vec cannot fail");
+    Instruction::new_with_bytes(*program, &data, accounts)
+}
+
+fn config_and_signer(config: &Pubkey, signer: &Pubkey) -> Vec<AccountMeta> {
+    vec![AccountMeta::new(*config, false), AccountMeta::new_readonly(*signer, true)]
+}
+
+pub fn initialize(
+    program: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    agi_controller: Pubkey,
+    max_supply: u64,
+    decimals: u8,
+) -> Instruction {
+    let (config, _) = rsm_token::config_address(program, mint);
+    rsm_ix(
+        program,
+        RSMInstruction::Initialize { max_supply, agi_controller, decimals },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Signed by the agi_controller; weights must sum to 100.
+pub fn update_agi_params(program: &Pubkey, config: &Pubkey, agi_controller: &Pubkey, weights: [u8; 4]) -> Instruction {
+    let [complexity_weight, uniqueness_weight, entropy_weight, blockchain_weight] = weights;
+    rsm_ix(
+        program,
+        RSMInstruction::UpdateAGIParams { complexity_weight, uniqueness_weight, entropy_weight, blockchain_weight },
+        config_and_signer(config, agi_controller),
+    )
+}
+
+pub fn set_score_oracle(program: &Pubkey, config: &Pubkey, authority: &Pubkey, score_oracle: Pubkey) -> Instruction {
+    rsm_ix(program, RSMInstruction::SetScoreOracle { score_oracle }, config_and_signer(config, authority))
+}
+
+pub fn configure_breaker(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    window_slots: u64,
+    max_multiple_pct: u16,
+) -> Instruction {
+    rsm_ix(
+        program,
+        RSMInstruction::ConfigureBreaker { window_slots, max_multiple_pct },
+        config_and_signer(config, authority),
+    )
+}
+
+pub fn reset_breaker(program: &Pubkey, config: &Pubkey, authority: &Pubkey) -> Instruction {
+    let mut accounts = config_and_signer(config, authority);
+    accounts.push(AccountMeta::new_readonly(sysvar::clock::id(), false));
+    rsm_ix(program, RSMInstruction::ResetBreaker, accounts)
+}
+
+/// `signer` is the agi_controller or the authority; `amount` is the mint
+/// that failed with EmissionAnomaly.
+pub fn trip_breaker(program: &Pubkey, config: &Pubkey, signer: &Pubkey, amount: u64) -> Instruction {
+    let mut accounts = config_and_signer(config, signer);
+    accounts.push(AccountMeta::new_readonly(sysvar::clock::id(), false));
+    rsm_ix(program, RSMInstruction::TripBreaker { amount }, accounts)
+}
+
+/// The agi_controller pays for the delegate record.
+pub fn create_mint_delegate(
+    program: &Pubkey,
+    config: &Pubkey,
+    agi_controller: &Pubkey,
+    delegate: Pubkey,
+    max_amount: u64,
+    max_mints: u32,
+    expiry_slot: u64,
+) -> Instruction {
+    let (record, _) = mint_delegate_address(program, config, &delegate);
+    rsm_ix(
+        program,
+        RSMInstruction::CreateMintDelegate { delegate, max_amount, max_mints, expiry_slot },
+        vec![
+            AccountMeta::new_readonly(*config, false),
+            AccountMeta::new(record, false),
+            AccountMeta::new(*agi_controller, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Rent from the closed record goes to the agi_controller.
+pub fn revoke_mint_delegate(program: &Pubkey, config: &Pubkey, agi_controller: &Pubkey, delegate: &Pubkey) -> Instruction {
+    let (record, _) = mint_delegate_address(program, config, delegate);
+    rsm_ix(
+        program,
+        RSMInstruction::RevokeMintDelegate,
+        vec![
+            AccountMeta::new_readonly(*config, false),
+            AccountMeta::new(record, false),
+            AccountMeta::new(*agi_controller, true),
+        ],
+    )
+}
+
+/// `current_executor` must co-sign once an executor is bound.
+pub fn set_governance_executor(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    current_executor: Option<&Pubkey>,
+    executor: Pubkey,
+) -> Instruction {
+    let mut accounts = config_and_signer(config, authority);
+    if let Some(current_executor) = current_executor {
+        accounts.push(AccountMeta::new_readonly(*current_executor, true));
+    }
+    rsm_ix(program, RSMInstruction::SetGovernanceExecutor { executor }, accounts)
+}
+
+fn supply_signers(config: &Pubkey, authority: &Pubkey, executor: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*executor, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ]
+}
+
+pub fn schedule_max_supply_raise(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    executor: &Pubkey,
+    new_max_supply: u64,
+) -> Instruction {
+    rsm_ix(
+        program,
+        RSMInstruction::ScheduleMaxSupplyRaise { new_max_supply },
+        supply_signers(config, authority, executor),
+    )
+}
+
+pub fn raise_max_supply(program: &Pubkey, config: &Pubkey, authority: &Pubkey, executor: &Pubkey) -> Instruction {
+    rsm_ix(program, RSMInstruction::RaiseMaxSupply, supply_signers(config, authority, executor))
+}
+
+/// Either the authority or the governance executor can cancel.
+pub fn cancel_max_supply_raise(program: &Pubkey, config: &Pubkey, signer: &Pubkey) -> Instruction {
+    rsm_ix(program, RSMInstruction::CancelMaxSupplyRaise, config_and_signer(config, signer))
+}
+
+pub fn configure_score_validity(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    validity_secs: i64,
+    bond_bps: u16,
+) -> Instruction {
+    rsm_ix(
+        program,
+        RSMInstruction::ConfigureScoreValidity { validity_secs, bond_bps },
+        config_and_signer(config, authority),
+    )
+}
+
+pub fn configure_streaming(program: &Pubkey, config: &Pubkey, authority: &Pubkey, stream_slots: u64) -> Instruction {
+    rsm_ix(program, RSMInstruction::ConfigureStreaming { stream_slots }, config_and_signer(config, authority))
+}
+
+/// `threshold_secs` of 0 turns quality decay off.
+pub fn configure_decay(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    threshold_secs: i64,
+    half_life_secs: i64,
+    floor_bps: u16,
+) -> Instruction {
+    rsm_ix(
+        program,
+        RSMInstruction::ConfigureDecay { threshold_secs, half_life_secs, floor_bps },
+        config_and_signer(config, authority),
+    )
+}
+
+/// `window_secs` of 0 turns disputes off; otherwise `evidence_signer` is
+/// the oracle key whose signed re-scores can challenge a mint.
+pub fn configure_disputes(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    window_secs: i64,
+    reward_bps: u16,
+    evidence_signer: Pubkey,
+) -> Instruction {
+    rsm_ix(
+        program,
+        RSMInstruction::ConfigureDisputes { window_secs, reward_bps, evidence_signer },
+        config_and_signer(config, authority),
+    )
+}
+
+/// `target_per_epoch` of 0 turns emission smoothing off.
+pub fn configure_emission_target(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    target_per_epoch: u64,
+    epoch_slots: u64,
+) -> Instruction {
+    rsm_ix(
+        program,
+        RSMInstruction::ConfigureEmissionTarget { target_per_epoch, epoch_slots },
+        config_and_signer(config, authority),
+    )
+}
+
+pub fn configure_recipient_consent(program: &Pubkey, config: &Pubkey, authority: &Pubkey, required: bool) -> Instruction {
+    rsm_ix(program, RSMInstruction::ConfigureRecipientConsent { required }, config_and_signer(config, authority))
+}
+
+pub fn configure_mint_window(program: &Pubkey, config: &Pubkey, authority: &Pubkey, start_bps: u16, end_bps: u16) -> Instruction {
+    rsm_ix(program, RSMInstruction::ConfigureMintWindow { start_bps, end_bps }, config_and_signer(config, authority))
+}
+
+pub fn configure_dust(program: &Pubkey, config: &Pubkey, authority: &Pubkey, threshold: u64, reward_pool: Pubkey) -> Instruction {
+    rsm_ix(program, RSMInstruction::ConfigureDust { threshold, reward_pool }, config_and_signer(config, authority))
+}
+
+/// A default `membership_mint` and zero `merkle_root` turn the allowlist off.
+pub fn configure_allowlist(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    membership_mint: Pubkey,
+    merkle_root: [u8; 32],
+) -> Instruction {
+    rsm_ix(
+        program,
+        RSMInstruction::ConfigureAllowlist { membership_mint, merkle_root },
+        config_and_signer(config, authority),
+    )
+}
+
+/// Clears the quality histogram in `TokenConfig`; `reset_per_epoch` also
+/// clears it at each new epoch.
+pub fn configure_quality_histogram(program: &Pubkey, config: &Pubkey, authority: &Pubkey, reset_per_epoch: bool) -> Instruction {
+    rsm_ix(program, RSMInstruction::ConfigureQualityHistogram { reset_per_epoch }, config_and_signer(config, authority))
+}
+
+/// Mints above `threshold` then need `required` of `controllers` to
+/// co-sign; threshold 0 turns the quorum off.
+pub fn configure_mint_quorum(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    threshold: u64,
+    required: u8,
+    controllers: Vec<Pubkey>,
+) -> Instruction {
+    rsm_ix(
+        program,
+        RSMInstruction::ConfigureMintQuorum { threshold, required, controllers },
+        config_and_signer(config, authority),
+    )
+}
+
+/// `token_program` is the program owning `mint`.
+pub fn consolidate_dust(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    executor: &Pubkey,
+    mint: &Pubkey,
+    reward_pool: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*executor, true),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    rsm_ix(program, RSMInstruction::ConsolidateDust, accounts)
+}
+
+/// Records the evidence and queues the burn from `token_account`; the
+/// authority pays the ClawbackRecord's rent.
+#[allow(clippy::too_many_arguments)]
+pub fn schedule_emergency_clawback(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    executor: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+    evidence_hash: [u8; 32],
+    reason_code: u16,
+) -> Instruction {
+    let (record, _) = clawback_address(program, config, &evidence_hash);
+    let mut accounts = supply_signers(config, authority, executor);
+    accounts[1].is_writable = true;
+    accounts.extend([
+        AccountMeta::new_readonly(*token_account, false),
+        AccountMeta::new(record, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]);
+    rsm_ix(program, RSMInstruction::ScheduleEmergencyClawback { amount, evidence_hash, reason_code }, accounts)
+}
+
+/// `token_program` is the program owning `mint`; the burned amount also
+/// comes off the genome of `genome_hash`.
+#[allow(clippy::too_many_arguments)]
+pub fn emergency_clawback(
+    program: &Pubkey,
+    config: &Pubkey,
+    authority: &Pubkey,
+    executor: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    evidence_hash: [u8; 32],
+    genome_hash: [u8; 32],
+) -> Instruction {
+    let mut accounts = supply_signers(config, authority, executor);
+    accounts.extend([
+        AccountMeta::new(clawback_address(program, config, &evidence_hash).0, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new(genome_address(program, config, &genome_hash).0, false),
+    ]);
+    rsm_ix(program, RSMInstruction::EmergencyClawback { evidence_hash }, accounts)
+}
+
+/// Either the authority or the governance executor can cancel.
+pub fn cancel_emergency_clawback(program: &Pubkey, config: &Pubkey, signer: &Pubkey, evidence_hash: [u8; 32]) -> Instruction {
+    let mut accounts = config_and_signer(config, signer);
+    accounts.push(AccountMeta::new(clawback_address(program, config, &evidence_hash).0, false));
+    rsm_ix(program, RSMInstruction::CancelEmergencyClawback { evidence_hash }, accounts)
+}
+
+/// Accounts for genesis-engine's `create_genome`, which escrows the cost
+/// under a commitment; `RevealGenesis` rolls and registers the child.
+#[derive(Clone, Debug)]
+pub struct CreateGenome {
+    pub genesis_program: Pubkey,
+    pub pause_program: Pubkey,
+    /// The engine PDA, `["engine", authority]`
+    pub engine: Pubkey,
+    pub species: u16,
+    pub rna_mint: Pubkey,
+    pub user_rna_account: Pubkey,
+    pub user: Pubkey,
+    /// (genome_id, dna-token Genome account, `user`'s token account of the
+    /// genome's mint) per parent; the holding proves ownership. Parents
+    /// from genesis also pass their Lineage, derived from the id
+    pub parents: Vec<(u64, Pubkey, Pubkey)>,
+    pub hybrid_permit: Option<Pubkey>,
+    /// Id of an open genesis event to breed under
+    pub genesis_event: Option<u64>,
+    /// The engine's Pyth price feed, required once it prices genesis in USD
+    pub price_feed: Option<Pubkey>,
+    /// Whether the engine has a genesis guardian, which must then be passed
+    pub genesis_guardian: bool,
+    /// The guardian, co-signing a genesis that costs more than its threshold
+    pub guardian: Option<Pubkey>,
+    /// Whether to donate the child to the engine's gene pool
+    pub donate: bool,
+}
+
+impl CreateGenome {
+    /// `commitment` is `genesis_commitment(entropy_seed, salt)`.
+    pub fn instruction(&self, commitment: [u8; 32], name: &str, metadata_uri_hash: [u8; 32]) -> Instruction {
+        let pda = |seeds: &[&[u8]], program: &Pubkey| Pubkey::find_program_address(seeds, program).0;
+        let genesis = &self.genesis_program;
+        let pool = pda(&[b"pool", self.engine.as_ref(), &self.species.to_le_bytes()], genesis);
+        let genesis_commit = pda(&[b"genesis_commit", self.engine.as_ref(), self.user.as_ref()], genesis);
+        let user_stats = pda(&[b"user_stats", self.engine.as_ref(), self.user.as_ref()], genesis);
+        let lineage = |genome_id: u64| pda(&[b"lineage", self.engine.as_ref(), &genome_id.to_le_bytes()], genesis);
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.engine, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(genesis_commit, false),
+            AccountMeta::new(pda(&[b"commit_escrow", genesis_commit.as_ref()], genesis), false),
+            AccountMeta::new_readonly(self.rna_mint, false),
+            AccountMeta::new(self.user_rna_account, false),
+            AccountMeta::new(self.user, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(user_stats, false),
+            AccountMeta::new_readonly(pda(&[b"global_pause"], &self.pause_program), false),
+            // Anchor reads the program id as "no hybrid permit"
+            match self.hybrid_permit {
+                Some(permit) => AccountMeta::new(permit, false),
+                None => AccountMeta::new_readonly(*genesis, false),
+            },
+            match self.genesis_event {
+                Some(event_id) => AccountMeta::new(genesis_event_address(genesis, &self.engine, event_id), false),
+                None => AccountMeta::new_readonly(*genesis, false),
+            },
+        ];
+        let (price_oracle, price_feed) = match self.price_feed {
+            Some(feed) => (pda(&[b"price_oracle", self.engine.as_ref()], genesis), feed),
+            None => (*genesis, *genesis),
+        };
+        accounts.push(AccountMeta::new_readonly(price_oracle, false));
+        accounts.push(AccountMeta::new_readonly(price_feed, false));
+        accounts.push(if self.genesis_guardian {
+            AccountMeta::new_readonly(pda(&[b"genesis_guardian", self.engine.as_ref()], genesis), false)
+        } else {
+            AccountMeta::new_readonly(*genesis, false)
+        });
+        accounts.push(match self.guardian {
+            Some(guardian) => AccountMeta::new_readonly(guardian, true),
+            None => AccountMeta::new_readonly(*genesis, false),
+        });
+        accounts.push(if self.donate {
+            AccountMeta::new_readonly(pda(&[b"gene_pool", self.engine.as_ref()], genesis), false)
+        } else {
+            AccountMeta::new_readonly(*genesis, false)
+        });
+        for (_, parent, holding) in &self.parents {
+            accounts.push(AccountMeta::new_readonly(*parent, false));
+            accounts.push(AccountMeta::new_readonly(*holding, false));
+        }
+        for (genome_id, _, _) in &self.parents {
+            if *genome_id >= GENESIS_GENOME_ID_OFFSET {
+                accounts.push(AccountMeta::new_readonly(lineage(*genome_id), false));
+            }
+        }
+
+        let parent_ids: Vec<u64> = self.parents.iter().map(|(id, _, _)| *id).collect();
+        Instruction::new_with_bytes(
+            *genesis,
+            &anchor_data("create_genome", (parent_ids, commitment, name.to_string(), metadata_uri_hash)),
+            accounts,
+        )
+    }
+}
+
+/// Accounts for genesis-engine's `reveal_genesis`, from the slot after
+/// `create_genome` or `commit_genesis` until 512 slots after it.
+#[derive(Clone, Debug)]
+pub struct RevealGenesis {
+    pub genesis_program: Pubkey,
+    pub dna_program: Pubkey,
+    pub pause_program: Pubkey,
+    pub engine: Pubkey,
+    /// New keypair; signs alongside `user`
+    pub genesis_record: Pubkey,
+    pub rna_mint: Pubkey,
+    pub user: Pubkey,
+    /// The engine's `total_genomes_created` when the transaction lands
+    pub genomes_created: u64,
+    /// Whether the engine has a mutation catalog, which must then be passed
+    pub mutation_catalog: bool,
+    /// The genesis event the commit was made under
+    pub genesis_event: Option<u64>,
+    /// Whether the commit donates the child to the engine's gene pool
+    pub donate: bool,
+}
+
+impl RevealGenesis {
+    pub fn child_genome_id(&self) -> u64 {
+        self.genomes_created + GENESIS_GENOME_ID_OFFSET
+    }
+
+    pub fn instruction(&self, entropy_seed: [u8; 32], salt: [u8; 32]) -> Instruction {
+        let pda = |seeds: &[&[u8]], program: &Pubkey| Pubkey::find_program_address(seeds, program).0;
+        let genesis = &self.genesis_program;
+        let genesis_commit = pda(&[b"genesis_commit", self.engine.as_ref(), self.user.as_ref()], genesis);
+        let child_id = self.child_genome_id().to_le_bytes();
+        let none = AccountMeta::new_readonly(*genesis, false);
+
+        let accounts = vec![
+            AccountMeta::new(self.engine, false),
+            AccountMeta::new(genesis_commit, false),
+            AccountMeta::new(pda(&[b"commit_escrow", genesis_commit.as_ref()], genesis), false),
+            AccountMeta::new(self.rna_mint, false),
+            AccountMeta::new(self.genesis_record, true),
+            AccountMeta::new_readonly(pda(&[b"registry"], &self.dna_program), false),
+            AccountMeta::new(pda(&[b"genome", &child_id], &self.dna_program), false),
+            AccountMeta::new_readonly(pda(&[b"genesis_authority", self.engine.as_ref()], genesis), false),
+            AccountMeta::new(self.user, true),
+            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+            AccountMeta::new_readonly(self.dna_program, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(pda(&[b"global_pause"], &self.pause_program), false),
+            if self.mutation_catalog {
+                AccountMeta::new_readonly(pda(&[b"mutation_catalog", self.engine.as_ref()], genesis), false)
+            } else {
+                none.clone()
+            },
+            AccountMeta::new(pda(&[b"lineage", self.engine.as_ref(), &child_id], genesis), false),
+            match self.genesis_event {
+                Some(event_id) => AccountMeta::new_readonly(genesis_event_address(genesis, &self.engine, event_id), false),
+                None => none.clone(),
+            },
+            if self.donate { AccountMeta::new(pda(&[b"gene_pool", self.engine.as_ref()], genesis), false) } else { none },
+        ];
+
+        Instruction::new_with_bytes(*genesis, &anchor_data("reveal_genesis", (entropy_seed, salt)), accounts)
+    }
+}
+
+/// The commitment `create_genome` and `commit_genesis` escrow under.
+pub fn genesis_commitment(entropy_seed: &[u8; 32], salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[entropy_seed.as_ref(), salt.as_ref()]).to_bytes()
+}
+
+fn genesis_event_address(genesis_program: &Pubkey, engine: &Pubkey, event_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"genesis_event", engine.as_ref(), &event_id.to_le_bytes()], genesis_program).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshDeserialize;
+
+    #[test]
+    fn test_admin_builders_encode_rsm_instructions() {
+        let (program, config, authority) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = configure_breaker(&program, &config, &authority, 216_000, 300);
+        assert!(matches!(
+            RSMInstruction::try_from_slice(&ix.data).unwrap(),
+            RSMInstruction::ConfigureBreaker { window_slots: 216_000, max_multiple_pct: 300 }
+        ));
+        assert_eq!(ix.accounts, config_and_signer(&config, &authority));
+
+        let executor = Pubkey::new_unique();
+        let ix = set_governance_executor(&program, &config, &authority, Some(&executor), Pubkey::new_unique());
+        assert_eq!(ix.accounts[2], AccountMeta::new_readonly(executor, true));
+    }
+
+    #[test]
+    fn test_create_genome_accounts() {
+        let create = CreateGenome {
+            genesis_program: Pubkey::new_unique(),
+            pause_program: Pubkey::new_unique(),
+            engine: Pubkey::new_unique(),
+            species: 3,
+            rna_mint: Pubkey::new_unique(),
+            user_rna_account: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            parents: vec![
+                (1, Pubkey::new_unique(), Pubkey::new_unique()),
+                (100_002, Pubkey::new_unique(), Pubkey::new_unique()),
+            ],
+            hybrid_permit: None,
+            genesis_event: None,
+            price_feed: Some(Pubkey::new_unique()),
+            genesis_guardian: true,
+            guardian: Some(Pubkey::new_unique()),
+            donate: true,
+        };
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &create.genesis_program).0;
+        let ix = create.instruction(genesis_commitment(&[9u8; 32], &[4u8; 32]), "Helix", [3u8; 32]);
+        let genesis_commit = pda(&[b"genesis_commit", create.engine.as_ref(), create.user.as_ref()]);
+        assert_eq!(ix.accounts.len(), 23);
+        assert_eq!(ix.accounts[2], AccountMeta::new(genesis_commit, false));
+        assert_eq!(ix.accounts[3].pubkey, pda(&[b"commit_escrow", genesis_commit.as_ref()]));
+        assert_eq!(ix.accounts[11], AccountMeta::new_readonly(create.genesis_program, false));
+        assert_eq!(ix.accounts[12], AccountMeta::new_readonly(create.genesis_program, false));
+        assert_eq!(ix.accounts[13].pubkey, pda(&[b"price_oracle", create.engine.as_ref()]));
+        assert_eq!(ix.accounts[14].pubkey, create.price_feed.unwrap());
+        assert_eq!(ix.accounts[15].pubkey, pda(&[b"genesis_guardian", create.engine.as_ref()]));
+        assert_eq!(ix.accounts[16], AccountMeta::new_readonly(create.guardian.unwrap(), true));
+        assert_eq!(ix.accounts[17].pubkey, pda(&[b"gene_pool", create.engine.as_ref()]));
+        assert_eq!(ix.accounts[20].pubkey, create.parents[1].1);
+        assert_eq!(ix.accounts[21].pubkey, create.parents[1].2);
+        // Only the genesis-born parent passes a lineage
+        assert_eq!(ix.accounts[22].pubkey, pda(&[b"lineage", create.engine.as_ref(), &100_002u64.to_le_bytes()]));
+        assert_eq!(ix.data[8..12], 2u32.to_le_bytes());
+        assert_eq!(ix.data[28..60], genesis_commitment(&[9u8; 32], &[4u8; 32]));
+    }
+
+    #[test]
+    fn test_reveal_genesis_accounts() {
+        let reveal = RevealGenesis {
+            genesis_program: Pubkey::new_unique(),
+            dna_program: Pubkey::new_unique(),
+            pause_program: Pubkey::new_unique(),
+            engine: Pubkey::new_unique(),
+            genesis_record: Pubkey::new_unique(),
+            rna_mint: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            genomes_created: 7,
+            mutation_catalog: true,
+            genesis_event: Some(2),
+            donate: true,
+        };
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &reveal.genesis_program).0;
+        let ix = reveal.instruction([9u8; 32], [4u8; 32]);
+        assert_eq!(ix.accounts.len(), 18);
+        assert_eq!(
+            ix.accounts[6].pubkey,
+            Pubkey::find_program_address(&[b"genome", &100_007u64.to_le_bytes()], &reveal.dna_program).0
+        );
+        assert_eq!(ix.accounts[9].pubkey, sysvar::slot_hashes::id());
+        assert_eq!(ix.accounts[14].pubkey, pda(&[b"mutation_catalog", reveal.engine.as_ref()]));
+        assert_eq!(
+            ix.accounts[15],
+            AccountMeta::new(pda(&[b"lineage", reveal.engine.as_ref(), &100_007u64.to_le_bytes()]), false)
+        );
+        assert_eq!(ix.accounts[16].pubkey, pda(&[b"genesis_event", reveal.engine.as_ref(), &2u64.to_le_bytes()]));
+        assert_eq!(ix.accounts[17], AccountMeta::new(pda(&[b"gene_pool", reveal.engine.as_ref()]), false));
+    }
+}
@@ -0,0 +1,138 @@
+//! Client SDK for the RSM programs.
+//!
+//! `RsmClient` bundles the RPC and WebSocket endpoints with the program ids
+//! and mint of one deployment. The `subscribe` module layers typed,
+//! self-healing streams on top of `PubsubClient`; `multisig` builds the
+//! instructions that route authority actions through rsm_multisig.
+//! `instructions` builds the admin, mint and genesis instructions, and
+//! `offline` exports them unsigned for air-gapped or hardware signing.
+//! `errors` decodes the programs' custom error codes from failed
+//! transactions. `cache` memoizes decoded accounts and keeps them current
+//! from the same subscriptions. `content` verifies a genome's off-chain
+//! metadata against the hashes dna-token committed to.
+
+pub mod cache;
+pub mod content;
+pub mod errors;
+pub mod instructions;
+pub mod multisig;
+pub mod offline;
+pub mod subscribe;
+
+pub use cache::{AccountCache, GenesisEngine};
+pub use content::{fetch_verified_content, verify_content, ContentError};
+pub use errors::{ProgramFailure, Programs, RsmProgramError};
+pub use offline::OfflineTransaction;
+pub use subscribe::{AccountUpdate, GenesisUpdate, UpdateStream};
+
+use rsm_events::EventError;
+use solana_client::{
+    client_error::ClientError as RpcError, nonblocking::pubsub_client::PubsubClientError,
+    nonblocking::rpc_client::RpcClient,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{ParseSignatureError, Signature},
+    signer::SignerError,
+};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<RpcError>),
+    #[error("WebSocket subscription failed: {0}")]
+    Pubsub(Box<PubsubClientError>),
+    #[error("Account {address} failed to deserialize: {reason}")]
+    Layout { address: Pubkey, reason: String },
+    #[error("Invalid transaction signature: {0}")]
+    Signature(#[from] ParseSignatureError),
+    #[error(transparent)]
+    Event(#[from] EventError),
+    #[error("Invalid offline transaction encoding: {0}")]
+    Encoding(String),
+    #[error("{0} is not a signer of this transaction")]
+    UnknownSigner(Pubkey),
+    #[error("Signature from {0} does not match the message")]
+    InvalidSignature(Pubkey),
+    #[error("Transactions carry different messages")]
+    MessageMismatch,
+    #[error("Missing signatures from {0:?}")]
+    MissingSignatures(Vec<Pubkey>),
+    #[error(transparent)]
+    Signer(#[from] SignerError),
+}
+
+impl From<RpcError> for ClientError {
+    fn from(err: RpcError) -> Self {
+        ClientError::Rpc(Box::new(err))
+    }
+}
+
+impl From<PubsubClientError> for ClientError {
+    fn from(err: PubsubClientError) -> Self {
+        ClientError::Pubsub(Box::new(err))
+    }
+}
+
+pub struct RsmClient {
+    rpc: Arc<RpcClient>,
+    ws_url: String,
+    commitment: CommitmentConfig,
+    pub rsm_program: Pubkey,
+    pub mint: Pubkey,
+    pub genesis_program: Pubkey,
+}
+
+impl RsmClient {
+    /// Defaults to `confirmed`; backfill uses `getTransaction`, which does
+    /// not accept `processed`.
+    pub fn new(rpc_url: &str, ws_url: &str, rsm_program: Pubkey, mint: Pubkey, genesis_program: Pubkey) -> Self {
+        let commitment = CommitmentConfig::confirmed();
+        Self {
+            rpc: Arc::new(RpcClient::new_with_commitment(rpc_url.to_string(), commitment)),
+            ws_url: ws_url.to_string(),
+            commitment,
+            rsm_program,
+            mint,
+            genesis_program,
+        }
+    }
+
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.rpc = Arc::new(RpcClient::new_with_commitment(self.rpc.url(), commitment));
+        self.commitment = commitment;
+        self
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    pub fn config_address(&self) -> Pubkey {
+        rsm_token::config_address(&self.rsm_program, &self.mint).0
+    }
+
+    pub fn genome_address(&self, genome_hash: &[u8; 32]) -> Pubkey {
+        rsm_token::genome_address(&self.rsm_program, &self.config_address(), genome_hash).0
+    }
+
+    /// Unsigned transaction against the latest blockhash, for export.
+    pub async fn offline_transaction(
+        &self,
+        instructions: &[Instruction],
+        fee_payer: &Pubkey,
+    ) -> Result<OfflineTransaction, ClientError> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        Ok(OfflineTransaction::new(instructions, fee_payer, blockhash))
+    }
+
+    /// Submits an imported transaction once every signer has signed.
+    pub async fn submit_offline(&self, transaction: OfflineTransaction) -> Result<Signature, ClientError> {
+        let transaction = transaction.into_transaction()?;
+        Ok(self.rpc.send_and_confirm_transaction(&transaction).await?)
+    }
+}
@@ -0,0 +1,222 @@
+//! Instruction builders for the rsm_multisig program.
+//!
+//! Any instruction that needs an ecosystem authority (rsm-token admin
+//! instructions, dna-token registry calls, treasury governance) is wrapped
+//! with `propose`, approved by the owners and run with `execute`, with the
+//! multisig's signer PDA standing in for the single keypair.
+
+use borsh::BorshSerialize;
+use solana_sdk::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+pub const SIGNER_SEED: &[u8] = b"signer";
+pub const TRANSACTION_SEED: &[u8] = b"transaction";
+
+/// Mirror of the program's `TransactionAccount`.
+#[derive(BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl From<&AccountMeta> for TransactionAccount {
+    fn from(meta: &AccountMeta) -> Self {
+        Self {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        }
+    }
+}
+
+pub fn multisig_address(program: &Pubkey, create_key: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MULTISIG_SEED, create_key.as_ref()], program).0
+}
+
+/// The key to hand authority roles to.
+pub fn signer_address(program: &Pubkey, multisig: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SIGNER_SEED, multisig.as_ref()], program).0
+}
+
+pub fn transaction_address(program: &Pubkey, multisig: &Pubkey, index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[TRANSACTION_SEED, multisig.as_ref(), &index.to_le_bytes()],
+        program,
+    )
+    .0
+}
+
+/// Anchor instruction data: sha256("global:<name>")[..8] followed by borsh args.
+pub(crate) fn anchor_data<A: BorshSerialize>(name: &str, args: A) -> Vec<u8> {
+    let mut data = hash(format!("global:{}", name).as_bytes()).to_bytes()[..8].to_vec();
+    data.extend(args.try_to_vec().expect("borsh serialization into a Vec cannot fail"));
+    data
+}
+
+pub fn create_multisig(
+    program: &Pubkey,
+    create_key: &Pubkey,
+    payer: &Pubkey,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+    timelock_secs: i64,
+) -> Instruction {
+    let multisig = multisig_address(program, create_key);
+    Instruction::new_with_bytes(
+        *program,
+        &anchor_data("create_multisig", (owners, threshold, timelock_secs)),
+        vec![
+            AccountMeta::new(multisig, false),
+            AccountMeta::new_readonly(signer_address(program, &multisig), false),
+            AccountMeta::new_readonly(*create_key, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Proposes `inner` as transaction `index`, which must be the multisig's
+/// current `transaction_count`.
+pub fn propose(
+    program: &Pubkey,
+    multisig: &Pubkey,
+    index: u64,
+    proposer: &Pubkey,
+    inner: &Instruction,
+    memo: &str,
+) -> Instruction {
+    let accounts: Vec<TransactionAccount> = inner.accounts.iter().map(Into::into).collect();
+    Instruction::new_with_bytes(
+        *program,
+        &anchor_data(
+            "propose_transaction",
+            (inner.program_id, accounts, inner.data.clone(), memo.to_string()),
+        ),
+        vec![
+            AccountMeta::new(*multisig, false),
+            AccountMeta::new(transaction_address(program, multisig, index), false),
+            AccountMeta::new(*proposer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn approve(program: &Pubkey, multisig: &Pubkey, transaction: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program,
+        &anchor_data("approve_transaction", ()),
+        vec![
+            AccountMeta::new_readonly(*multisig, false),
+            AccountMeta::new(*transaction, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+/// Executes the proposal of `inner`; its accounts and program are passed
+/// as remaining accounts, with the signer PDA demoted to a non-signer.
+pub fn execute(
+    program: &Pubkey,
+    multisig: &Pubkey,
+    transaction: &Pubkey,
+    executor: &Pubkey,
+    inner: &Instruction,
+) -> Instruction {
+    let signer = signer_address(program, multisig);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*multisig, false),
+        AccountMeta::new_readonly(signer, false),
+        AccountMeta::new(*transaction, false),
+        AccountMeta::new_readonly(*executor, true),
+    ];
+    accounts.extend(inner.accounts.iter().map(|meta| AccountMeta {
+        pubkey: meta.pubkey,
+        is_signer: meta.is_signer && meta.pubkey != signer,
+        is_writable: meta.is_writable,
+    }));
+    accounts.push(AccountMeta::new_readonly(inner.program_id, false));
+    Instruction::new_with_bytes(*program, &anchor_data("execute_transaction", ()), accounts)
+}
+
+pub fn cancel(program: &Pubkey, transaction: &Pubkey, proposer: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program,
+        &anchor_data("cancel_transaction", ()),
+        vec![
+            AccountMeta::new(*transaction, false),
+            AccountMeta::new(*proposer, true),
+        ],
+    )
+}
+
+/// Inner instruction for `propose`; the multisig can only reconfigure itself.
+pub fn set_owners(program: &Pubkey, multisig: &Pubkey, owners: Vec<Pubkey>, threshold: u8) -> Instruction {
+    Instruction::new_with_bytes(
+        *program,
+        &anchor_data("set_owners", (owners, threshold)),
+        vec![
+            AccountMeta::new(*multisig, false),
+            AccountMeta::new_readonly(signer_address(program, multisig), true),
+        ],
+    )
+}
+
+/// Inner instruction for `propose`.
+pub fn set_timelock(program: &Pubkey, multisig: &Pubkey, timelock_secs: i64) -> Instruction {
+    Instruction::new_with_bytes(
+        *program,
+        &anchor_data("set_timelock", timelock_secs),
+        vec![
+            AccountMeta::new(*multisig, false),
+            AccountMeta::new_readonly(signer_address(program, multisig), true),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_propose_encodes_inner_instruction() {
+        let program = Pubkey::new_unique();
+        let multisig = multisig_address(&program, &Pubkey::new_unique());
+        let inner = set_timelock(&program, &multisig, 86_400);
+        let ix = propose(&program, &multisig, 3, &Pubkey::new_unique(), &inner, "raise timelock");
+
+        let mut expected = hash(b"global:propose_transaction").to_bytes()[..8].to_vec();
+        expected.extend(program.to_bytes());
+        expected.extend(2u32.to_le_bytes());
+        expected.extend(multisig.to_bytes());
+        expected.extend([0, 1]);
+        expected.extend(signer_address(&program, &multisig).to_bytes());
+        expected.extend([1, 0]);
+        expected.extend((inner.data.len() as u32).to_le_bytes());
+        expected.extend(&inner.data);
+        expected.extend(14u32.to_le_bytes());
+        expected.extend(b"raise timelock");
+        assert_eq!(ix.data, expected);
+        assert_eq!(ix.accounts[1].pubkey, transaction_address(&program, &multisig, 3));
+    }
+
+    #[test]
+    fn test_execute_demotes_signer_pda() {
+        let program = Pubkey::new_unique();
+        let multisig = multisig_address(&program, &Pubkey::new_unique());
+        let signer = signer_address(&program, &multisig);
+        let inner = set_owners(&program, &multisig, vec![Pubkey::new_unique()], 1);
+        let ix = execute(&program, &multisig, &Pubkey::new_unique(), &Pubkey::new_unique(), &inner);
+
+        let remaining = &ix.accounts[4..];
+        assert_eq!(remaining.len(), inner.accounts.len() + 1);
+        assert!(remaining.iter().all(|meta| !meta.is_signer));
+        assert!(remaining.iter().any(|meta| meta.pubkey == signer));
+        assert_eq!(remaining.last().unwrap().pubkey, program);
+    }
+}
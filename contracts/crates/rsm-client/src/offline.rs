@@ -0,0 +1,207 @@
+//! Unsigned transactions for offline and hardware signing.
+//!
+//! An `OfflineTransaction` is built online against a blockhash, exported as
+//! base64 to an air-gapped machine or hardware wallet, and imported back
+//! once signed. Signers either sign the imported transaction directly or
+//! sign the exported message bytes elsewhere and return the signature,
+//! which `add_signature` checks before attaching it. Multisig owners can
+//! each sign their own copy; `merge` collects the signatures into one.
+//!
+//! A blockhash expires after ~150 slots, so transactions that travel
+//! between machines should use `with_durable_nonce`.
+
+use crate::ClientError;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+    transaction::Transaction,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OfflineTransaction {
+    transaction: Transaction,
+}
+
+impl OfflineTransaction {
+    pub fn new(instructions: &[Instruction], fee_payer: &Pubkey, blockhash: Hash) -> Self {
+        let message = Message::new_with_blockhash(instructions, Some(fee_payer), &blockhash);
+        Self {
+            transaction: Transaction::new_unsigned(message),
+        }
+    }
+
+    /// Prepends `AdvanceNonceAccount`; `nonce` is the blockhash currently
+    /// stored in `nonce_account`.
+    pub fn with_durable_nonce(
+        instructions: &[Instruction],
+        fee_payer: &Pubkey,
+        nonce_account: &Pubkey,
+        nonce_authority: &Pubkey,
+        nonce: Hash,
+    ) -> Self {
+        let mut message = Message::new_with_nonce(instructions.to_vec(), Some(fee_payer), nonce_account, nonce_authority);
+        message.recent_blockhash = nonce;
+        Self {
+            transaction: Transaction::new_unsigned(message),
+        }
+    }
+
+    pub fn from_base64(encoded: &str) -> Result<Self, ClientError> {
+        let bytes = STANDARD
+            .decode(encoded.trim())
+            .map_err(|err| ClientError::Encoding(err.to_string()))?;
+        let transaction: Transaction =
+            bincode::deserialize(&bytes).map_err(|err| ClientError::Encoding(err.to_string()))?;
+        if transaction.signatures.len() != transaction.message.header.num_required_signatures as usize {
+            return Err(ClientError::Encoding("signature count does not match the message".to_string()));
+        }
+        Ok(Self { transaction })
+    }
+
+    /// The transaction with whatever signatures it has so far.
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(bincode::serialize(&self.transaction).expect("bincode serialization into a Vec cannot fail"))
+    }
+
+    /// The bytes each signer signs.
+    pub fn message_base64(&self) -> String {
+        STANDARD.encode(self.transaction.message_data())
+    }
+
+    pub fn message(&self) -> &Message {
+        &self.transaction.message
+    }
+
+    /// Required signers in signature order; the fee payer comes first.
+    pub fn signers(&self) -> &[Pubkey] {
+        let required = self.transaction.message.header.num_required_signatures as usize;
+        &self.transaction.message.account_keys[..required]
+    }
+
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        self.signers()
+            .iter()
+            .zip(&self.transaction.signatures)
+            .filter(|(_, signature)| **signature == Signature::default())
+            .map(|(signer, _)| *signer)
+            .collect()
+    }
+
+    /// Signs with local keypairs or hardware wallet signers, keeping the
+    /// exported blockhash.
+    pub fn sign(&mut self, signers: &[&dyn Signer]) -> Result<(), ClientError> {
+        let blockhash = self.transaction.message.recent_blockhash;
+        self.transaction.try_partial_sign(signers, blockhash)?;
+        Ok(())
+    }
+
+    /// Attaches a signature over `message_base64` produced elsewhere.
+    pub fn add_signature(&mut self, signer: &Pubkey, signature: Signature) -> Result<(), ClientError> {
+        let index = self
+            .signers()
+            .iter()
+            .position(|key| key == signer)
+            .ok_or(ClientError::UnknownSigner(*signer))?;
+        if !signature.verify(signer.as_ref(), &self.transaction.message_data()) {
+            return Err(ClientError::InvalidSignature(*signer));
+        }
+        self.transaction.signatures[index] = signature;
+        Ok(())
+    }
+
+    /// Copies the signatures on another copy of the same message.
+    pub fn merge(&mut self, other: &OfflineTransaction) -> Result<(), ClientError> {
+        if other.transaction.message != self.transaction.message {
+            return Err(ClientError::MessageMismatch);
+        }
+        for (ours, theirs) in self.transaction.signatures.iter_mut().zip(&other.transaction.signatures) {
+            if *theirs != Signature::default() {
+                *ours = *theirs;
+            }
+        }
+        Ok(())
+    }
+
+    /// The transaction ready to submit, once every signer has signed.
+    pub fn into_transaction(self) -> Result<Transaction, ClientError> {
+        let missing = self.missing_signers();
+        if !missing.is_empty() {
+            return Err(ClientError::MissingSignatures(missing));
+        }
+        Ok(self.transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{instructions, multisig};
+    use solana_sdk::{signature::Keypair, system_program};
+
+    #[test]
+    fn test_round_trip_with_external_signatures() {
+        let program = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let (authority, executor, fee_payer) = (Keypair::new(), Keypair::new(), Keypair::new());
+        let ix = instructions::raise_max_supply(&program, &config, &authority.pubkey(), &executor.pubkey());
+        let unsigned = OfflineTransaction::new(&[ix], &fee_payer.pubkey(), Hash::new_unique());
+        assert_eq!(unsigned.signers().len(), 3);
+        assert_eq!(unsigned.signers()[0], fee_payer.pubkey());
+
+        // Air-gapped authority signs the exported message bytes
+        let mut imported = OfflineTransaction::from_base64(&unsigned.to_base64()).unwrap();
+        let message = STANDARD.decode(unsigned.message_base64()).unwrap();
+        imported.add_signature(&authority.pubkey(), authority.sign_message(&message)).unwrap();
+        assert!(matches!(
+            imported.add_signature(&executor.pubkey(), fee_payer.sign_message(&message)),
+            Err(ClientError::InvalidSignature(_))
+        ));
+        assert!(matches!(
+            imported.add_signature(&Pubkey::new_unique(), authority.sign_message(&message)),
+            Err(ClientError::UnknownSigner(_))
+        ));
+
+        // Executor signs a separate copy; the copies merge
+        let mut executor_copy = OfflineTransaction::from_base64(&unsigned.to_base64()).unwrap();
+        executor_copy.sign(&[&executor]).unwrap();
+        imported.merge(&executor_copy).unwrap();
+        assert_eq!(imported.missing_signers(), vec![fee_payer.pubkey()]);
+        assert!(matches!(
+            imported.clone().into_transaction(),
+            Err(ClientError::MissingSignatures(_))
+        ));
+
+        imported.sign(&[&fee_payer]).unwrap();
+        let transaction = imported.into_transaction().unwrap();
+        assert!(transaction.verify().is_ok());
+    }
+
+    #[test]
+    fn test_durable_nonce_multisig_proposal() {
+        let multisig_program = Pubkey::new_unique();
+        let multisig = multisig::multisig_address(&multisig_program, &Pubkey::new_unique());
+        let owner = Keypair::new();
+        let (nonce_account, nonce) = (Pubkey::new_unique(), Hash::new_unique());
+        let inner = instructions::configure_streaming(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &multisig::signer_address(&multisig_program, &multisig),
+            432_000,
+        );
+        let propose = multisig::propose(&multisig_program, &multisig, 0, &owner.pubkey(), &inner, "stream mints");
+
+        let mut tx = OfflineTransaction::with_durable_nonce(&[propose], &owner.pubkey(), &nonce_account, &owner.pubkey(), nonce);
+        assert_eq!(tx.message().recent_blockhash, nonce);
+        assert_eq!(tx.message().program_id(0), Some(&system_program::id()));
+        assert_eq!(tx.signers(), &[owner.pubkey()][..]);
+
+        tx.sign(&[&owner]).unwrap();
+        let other = OfflineTransaction::new(&[inner], &owner.pubkey(), nonce);
+        assert!(matches!(tx.merge(&other), Err(ClientError::MessageMismatch)));
+        assert!(tx.into_transaction().unwrap().verify().is_ok());
+    }
+}
@@ -0,0 +1,462 @@
+//! Typed WebSocket subscriptions with reconnect and backfill.
+//!
+//! Each helper spawns a task on the current tokio runtime that owns its own
+//! `PubsubClient`. When the socket drops the task reconnects with
+//! exponential backoff, then backfills over RPC whatever changed while it
+//! was away: the current account state, or the genesis transactions since
+//! the last one delivered. Errors are yielded as `Err` items and are not
+//! fatal; the stream only ends when the receiver is dropped.
+
+use crate::{ClientError, RsmClient};
+use borsh::BorshDeserialize;
+use futures::StreamExt;
+use rsm_events::{decode_log, decode_transaction_meta, DecodedEvent, GenesisCreatedEvent, RsmEvent};
+use rsm_token::{GenomeData, TokenConfig};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::{
+    nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient},
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::{RpcAccountInfoConfig, RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::{collections::HashSet, str::FromStr, sync::Arc, time::Duration};
+use tokio::sync::mpsc::{self, Sender};
+use tokio_stream::wrappers::ReceiverStream;
+
+const CHANNEL_CAPACITY: usize = 64;
+const MIN_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+pub type UpdateStream<T> = ReceiverStream<Result<T, ClientError>>;
+
+/// Account state as of `slot`; `None` once the account has been closed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AccountUpdate<T> {
+    pub slot: u64,
+    pub value: Option<T>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GenesisUpdate {
+    pub signature: Signature,
+    pub slot: u64,
+    pub event: GenesisCreatedEvent,
+}
+
+impl RsmClient {
+    /// Stream of changes to the rsm-token genome account for `genome_hash`.
+    pub fn subscribe_genome(&self, genome_hash: [u8; 32]) -> UpdateStream<AccountUpdate<GenomeData>> {
+        self.subscribe_account(self.genome_address(&genome_hash))
+    }
+
+    /// Stream of changes to this mint's rsm-token config.
+    pub fn subscribe_config(&self) -> UpdateStream<AccountUpdate<TokenConfig>> {
+        self.subscribe_account(self.config_address())
+    }
+
+    /// Stream of `GenesisCreatedEvent`s emitted by the genesis engine, in
+    /// transaction order, starting from the moment of subscription.
+    pub fn subscribe_genesis_events(&self) -> UpdateStream<GenesisUpdate> {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let watcher = GenesisWatcher {
+            rpc: self.rpc.clone(),
+            ws_url: self.ws_url.clone(),
+            program: self.genesis_program,
+            commitment: self.commitment,
+            cursor: None,
+        };
+        tokio::spawn(watcher.run(tx));
+        ReceiverStream::new(rx)
+    }
+
+    fn subscribe_account<T>(&self, address: Pubkey) -> UpdateStream<AccountUpdate<T>>
+    where
+        T: BorshDeserialize + Send + 'static,
+    {
+        watch_account(self.rpc.clone(), self.ws_url.clone(), self.commitment, address)
+    }
+}
+
+/// Spawns the watcher behind `subscribe_account`; it stops once the
+/// returned stream is dropped.
+pub(crate) fn watch_account<T>(
+    rpc: Arc<RpcClient>,
+    ws_url: String,
+    commitment: CommitmentConfig,
+    address: Pubkey,
+) -> UpdateStream<AccountUpdate<T>>
+where
+    T: BorshDeserialize + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+    let watcher = AccountWatcher { rpc, ws_url, address, commitment, last_slot: 0 };
+    tokio::spawn(watcher.run(tx));
+    ReceiverStream::new(rx)
+}
+
+struct Backoff(Duration);
+
+impl Backoff {
+    fn new() -> Self {
+        Backoff(MIN_BACKOFF)
+    }
+
+    fn reset(&mut self) {
+        self.0 = MIN_BACKOFF;
+    }
+
+    fn next(&mut self) -> Duration {
+        let delay = self.0;
+        self.0 = (self.0 * 2).min(MAX_BACKOFF);
+        delay
+    }
+}
+
+/// Deserialize an account notification; closed accounts map to `None`.
+fn decode_account<T: BorshDeserialize>(address: &Pubkey, account: &UiAccount) -> Result<Option<T>, ClientError> {
+    let account: Account = account.decode().ok_or_else(|| ClientError::Layout {
+        address: *address,
+        reason: "unsupported account encoding".to_string(),
+    })?;
+    parse_account(address, &account)
+}
+
+pub(crate) fn parse_account<T: BorshDeserialize>(address: &Pubkey, account: &Account) -> Result<Option<T>, ClientError> {
+    if account.lamports == 0 || account.data.is_empty() {
+        return Ok(None);
+    }
+    T::deserialize(&mut &account.data[..])
+        .map(Some)
+        .map_err(|err| ClientError::Layout { address: *address, reason: err.to_string() })
+}
+
+struct AccountWatcher {
+    rpc: Arc<RpcClient>,
+    ws_url: String,
+    address: Pubkey,
+    commitment: CommitmentConfig,
+    last_slot: u64,
+}
+
+impl AccountWatcher {
+    async fn run<T: BorshDeserialize>(mut self, tx: Sender<Result<AccountUpdate<T>, ClientError>>) {
+        let mut backoff = Backoff::new();
+        while !tx.is_closed() {
+            if let Err(err) = self.session(&tx, &mut backoff).await {
+                if tx.send(Err(err)).await.is_err() {
+                    return;
+                }
+            }
+            tokio::time::sleep(backoff.next()).await;
+        }
+    }
+
+    /// One connection: subscribe, backfill the current state, then forward
+    /// notifications until the socket or the receiver goes away.
+    async fn session<T: BorshDeserialize>(
+        &mut self,
+        tx: &Sender<Result<AccountUpdate<T>, ClientError>>,
+        backoff: &mut Backoff,
+    ) -> Result<(), ClientError> {
+        let pubsub = PubsubClient::new(&self.ws_url).await?;
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(self.commitment),
+            ..RpcAccountInfoConfig::default()
+        };
+        let (mut notifications, unsubscribe) = pubsub.account_subscribe(&self.address, Some(config)).await?;
+        backoff.reset();
+
+        // Subscribed first, so nothing between this read and the first
+        // notification is missed
+        let current = self.rpc.get_account_with_commitment(&self.address, self.commitment).await?;
+        if current.context.slot > self.last_slot {
+            self.last_slot = current.context.slot;
+            let value = match current.value {
+                Some(account) => parse_account(&self.address, &account),
+                None => Ok(None),
+            };
+            if !self.forward(tx, value).await {
+                return Ok(());
+            }
+        }
+
+        loop {
+            tokio::select! {
+                notification = notifications.next() => {
+                    let Some(notification) = notification else { break };
+                    // Older than the backfilled state
+                    if notification.context.slot < self.last_slot {
+                        continue;
+                    }
+                    self.last_slot = notification.context.slot;
+                    let value = decode_account(&self.address, &notification.value);
+                    if !self.forward(tx, value).await {
+                        break;
+                    }
+                }
+                _ = tx.closed() => break,
+            }
+        }
+
+        unsubscribe().await;
+        drop(notifications);
+        pubsub.shutdown().await?;
+        Ok(())
+    }
+
+    async fn forward<T>(
+        &self,
+        tx: &Sender<Result<AccountUpdate<T>, ClientError>>,
+        value: Result<Option<T>, ClientError>,
+    ) -> bool {
+        let update = value.map(|value| AccountUpdate { slot: self.last_slot, value });
+        tx.send(update).await.is_ok()
+    }
+}
+
+/// `GenesisCreatedEvent`s logged by `program` itself, including via CPI.
+fn genesis_events(
+    decoded: Vec<DecodedEvent>,
+    program: &Pubkey,
+    signature: Signature,
+    slot: u64,
+) -> impl Iterator<Item = GenesisUpdate> + '_ {
+    decoded.into_iter().filter_map(move |decoded| match decoded.event {
+        RsmEvent::GenesisCreatedEvent(event) if decoded.program_id.as_ref() == Some(program) => {
+            Some(GenesisUpdate { signature, slot, event })
+        }
+        _ => None,
+    })
+}
+
+struct GenesisWatcher {
+    rpc: Arc<RpcClient>,
+    ws_url: String,
+    program: Pubkey,
+    commitment: CommitmentConfig,
+    /// Newest transaction already accounted for; `Some(None)` once started
+    /// against a program with no history.
+    cursor: Option<Option<Signature>>,
+}
+
+impl GenesisWatcher {
+    async fn run(mut self, tx: Sender<Result<GenesisUpdate, ClientError>>) {
+        let mut backoff = Backoff::new();
+        while !tx.is_closed() {
+            if let Err(err) = self.session(&tx, &mut backoff).await {
+                if tx.send(Err(err)).await.is_err() {
+                    return;
+                }
+            }
+            tokio::time::sleep(backoff.next()).await;
+        }
+    }
+
+    async fn session(
+        &mut self,
+        tx: &Sender<Result<GenesisUpdate, ClientError>>,
+        backoff: &mut Backoff,
+    ) -> Result<(), ClientError> {
+        let pubsub = PubsubClient::new(&self.ws_url).await?;
+        let config = RpcTransactionLogsConfig { commitment: Some(self.commitment) };
+        let filter = RpcTransactionLogsFilter::Mentions(vec![self.program.to_string()]);
+        let (mut notifications, unsubscribe) = pubsub.logs_subscribe(filter, config).await?;
+        backoff.reset();
+
+        let mut backfilled = HashSet::new();
+        match self.cursor {
+            None => self.cursor = Some(self.latest_signature().await?),
+            Some(until) => {
+                for (signature, slot) in self.signatures_since(until).await? {
+                    backfilled.insert(signature);
+                    for update in self.transaction_events(&signature, slot).await? {
+                        if tx.send(Ok(update)).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                    self.cursor = Some(Some(signature));
+                }
+            }
+        }
+
+        loop {
+            tokio::select! {
+                notification = notifications.next() => {
+                    let Some(notification) = notification else { break };
+                    let logs = notification.value;
+                    if logs.err.is_some() {
+                        continue;
+                    }
+                    let signature = Signature::from_str(&logs.signature)?;
+                    if backfilled.contains(&signature) {
+                        continue;
+                    }
+                    self.cursor = Some(Some(signature));
+                    let decoded = match decode_log(&logs.logs) {
+                        Ok(decoded) => decoded,
+                        Err(err) => {
+                            if tx.send(Err(err.into())).await.is_err() {
+                                break;
+                            }
+                            continue;
+                        }
+                    };
+                    for update in genesis_events(decoded, &self.program, signature, notification.context.slot) {
+                        if tx.send(Ok(update)).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                _ = tx.closed() => break,
+            }
+        }
+
+        unsubscribe().await;
+        drop(notifications);
+        pubsub.shutdown().await?;
+        Ok(())
+    }
+
+    async fn latest_signature(&self) -> Result<Option<Signature>, ClientError> {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            limit: Some(1),
+            commitment: Some(self.commitment),
+            ..GetConfirmedSignaturesForAddress2Config::default()
+        };
+        let statuses = self.rpc.get_signatures_for_address_with_config(&self.program, config).await?;
+        match statuses.first() {
+            Some(status) => Ok(Some(Signature::from_str(&status.signature)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Successful transactions after `until`, oldest first. The RPC caps
+    /// this at 1000; a longer outage only recovers the newest 1000.
+    async fn signatures_since(&self, until: Option<Signature>) -> Result<Vec<(Signature, u64)>, ClientError> {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            until,
+            commitment: Some(self.commitment),
+            ..GetConfirmedSignaturesForAddress2Config::default()
+        };
+        let statuses = self.rpc.get_signatures_for_address_with_config(&self.program, config).await?;
+        statuses
+            .into_iter()
+            .rev()
+            .filter(|status| status.err.is_none())
+            .map(|status| Ok((Signature::from_str(&status.signature)?, status.slot)))
+            .collect()
+    }
+
+    async fn transaction_events(&self, signature: &Signature, slot: u64) -> Result<Vec<GenesisUpdate>, ClientError> {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Json),
+            commitment: Some(self.commitment),
+            max_supported_transaction_version: Some(0),
+        };
+        let transaction = self.rpc.get_transaction_with_config(signature, config).await?;
+        let Some(meta) = transaction.transaction.meta else {
+            return Ok(Vec::new());
+        };
+        let decoded = decode_transaction_meta(&meta)?;
+        Ok(genesis_events(decoded, &self.program, *signature, slot).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+    use rsm_events::Rarity;
+
+    fn genesis_created(genome_id: u64) -> GenesisCreatedEvent {
+        GenesisCreatedEvent {
+            genome_id,
+            creator: Pubkey::new_unique(),
+            rna_burned: 1_000,
+            consciousness: 50,
+            rarity: Rarity::Common,
+            rna_multiplier: 100,
+            timestamp: 0,
+            name: "Helix".to_string(),
+            metadata_uri_hash: [1u8; 32],
+            matures_at_slot: 0,
+            species: 0,
+            traits: [0u8; 8],
+            mutations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let mut backoff = Backoff::new();
+        assert_eq!(backoff.next(), MIN_BACKOFF);
+        assert_eq!(backoff.next(), MIN_BACKOFF * 2);
+        for _ in 0..10 {
+            backoff.next();
+        }
+        assert_eq!(backoff.next(), MAX_BACKOFF);
+        backoff.reset();
+        assert_eq!(backoff.next(), MIN_BACKOFF);
+    }
+
+    #[test]
+    fn test_parse_account() {
+        let address = Pubkey::new_unique();
+        let genome = GenomeData {
+            schema_version: GenomeData::SCHEMA.current(),
+            hash: [7u8; 32],
+            owner: Pubkey::new_unique(),
+            tokens_minted: 500,
+            complexity: 90,
+            uniqueness: 80,
+            is_minted: true,
+            mint_timestamp: 0,
+            rent_payer: Pubkey::new_unique(),
+            entropy: 150,
+            mint_slot: 1_000,
+            scored_at: 0,
+            bonded_amount: 50,
+            pending_clawback: 0,
+            clawback_eta: 0,
+            emission_scale_bps: 10_000,
+            blockchain_entropy: 0,
+            registrant: Pubkey::new_unique(),
+            note: Vec::new(),
+        };
+        let mut account = Account::new(1, GenomeData::LEN, &Pubkey::new_unique());
+        genome.serialize(&mut &mut account.data[..]).unwrap();
+
+        let parsed: GenomeData = parse_account(&address, &account).unwrap().unwrap();
+        assert_eq!(parsed.tokens_minted, 500);
+
+        account.lamports = 0;
+        assert!(parse_account::<GenomeData>(&address, &account).unwrap().is_none());
+
+        let truncated = Account::new(1, 8, &Pubkey::new_unique());
+        assert!(matches!(
+            parse_account::<GenomeData>(&address, &truncated),
+            Err(ClientError::Layout { .. })
+        ));
+    }
+
+    #[test]
+    fn test_genesis_events_from_program_only() {
+        let engine = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let event = genesis_created(100_000);
+        let decoded = vec![
+            DecodedEvent {
+                program_id: Some(engine),
+                event: RsmEvent::GenesisCreatedEvent(event.clone()),
+            },
+            DecodedEvent {
+                program_id: Some(other),
+                event: RsmEvent::GenesisCreatedEvent(genesis_created(1)),
+            },
+        ];
+        let updates: Vec<_> = genesis_events(decoded, &engine, Signature::default(), 7).collect();
+        assert_eq!(updates, vec![GenesisUpdate { signature: Signature::default(), slot: 7, event }]);
+    }
+}
@@ -0,0 +1,23 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rsm_core::{simulate_genesis, CatalogMutation, DominanceModel, GenesisSeeds, TraitVector, TRAIT_COUNT};
+
+fn genetics(c: &mut Criterion) {
+    let parents: Vec<TraitVector> = (0..4u8).map(|parent| [parent.wrapping_mul(61); TRAIT_COUNT]).collect();
+    let model = DominanceModel::default();
+    let catalog: Vec<CatalogMutation> = (0..16u16)
+        .map(|id| CatalogMutation { id, deltas: [id as i8 - 8; TRAIT_COUNT], weight_bps: 1_000 })
+        .collect();
+    let weights = [7000, 2200, 700, 100];
+
+    c.bench_function("simulate_genesis", |b| {
+        let mut seed = 0u8;
+        b.iter(|| {
+            seed = seed.wrapping_add(1);
+            let seeds = GenesisSeeds::from_seed(&[seed; 32]);
+            simulate_genesis(black_box(&parents), &model, &catalog, &weights, &seeds)
+        })
+    });
+}
+
+criterion_group!(benches, genetics);
+criterion_main!(benches);
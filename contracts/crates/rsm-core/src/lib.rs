@@ -0,0 +1,599 @@
+//! Genetics shared by the on-chain programs and off-chain simulation.
+//!
+//! Every genome carries a vector of `TRAIT_COUNT` alleles. genesis-engine
+//! breeds a child's vector from its parents with `recombine`, driven by the
+//! governance-set `DominanceModel` and a seed derived from the genesis
+//! inputs, so the same function replays or simulates any genesis exactly.
+//! A governance catalog of named mutations is then rolled against the child
+//! with `roll_mutations` and the hits shift its traits via `apply_mutations`.
+//! Each child also inherits a bounded `Ancestor` list from `child_lineage`,
+//! and `common_ancestor_generation` tells how closely its parents are related.
+//! `ancestor_royalties` splits a share of a child's RNA yield over that list.
+//! A governance `SupplyRule` sets the child's DNA supply from its parents'.
+//! `simulate_genesis` runs the rarity roll, recombination and catalog
+//! mutations in the engine's order from explicit `GenesisSeeds`; the
+//! engine finalizes every genesis through it, so any output pinned by the
+//! snapshot tests is what the chain produces for the same inputs.
+//! The crate has no dependencies and builds for the Solana target.
+//!
+//! For off-chain services, the `serde` feature derives `Serialize` and
+//! `Deserialize` for the public types and `schema` adds their JSON schemas,
+//! by name, from `schemas()`. Account and event layouts are not defined
+//! here; rsm-events mirrors the events with serde already.
+
+pub const TRAIT_COUNT: usize = 8;
+pub const MUTATION_DENOMINATOR: u64 = 10_000;
+/// Dominant allele wins 3 times in 4
+pub const DEFAULT_DOMINANCE: u8 = 191;
+pub const DEFAULT_MUTATION_BPS: u16 = 100;
+/// Deepest generation a lineage records; parents are generation 1
+pub const MAX_LINEAGE_GENERATIONS: u8 = 4;
+/// Most ancestors a lineage keeps, nearest generations first
+pub const MAX_LINEAGE_ANCESTORS: usize = 16;
+pub const SUPPLY_SCALE_DENOMINATOR: u64 = 10_000;
+pub const ROYALTY_DENOMINATOR: u64 = 10_000;
+/// Rarity multipliers are in hundredths: 100 = 1.0x
+pub const RARITY_MULTIPLIER_DENOMINATOR: u64 = 100;
+/// Common, Rare, Epic, Mythic
+pub const RARITY_TIERS: usize = 4;
+
+pub type TraitVector = [u8; TRAIT_COUNT];
+
+/// Per trait, the dominant allele is the higher one of the two inherited,
+/// and wins with probability `dominance / 255`: 255 always expresses it,
+/// 128 is a coin flip, 0 always expresses the recessive one. Each trait
+/// then mutates to a random allele with probability `mutation_bps / 10000`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DominanceModel {
+    pub dominance: [u8; TRAIT_COUNT],
+    pub mutation_bps: u16,
+}
+
+impl Default for DominanceModel {
+    fn default() -> Self {
+        Self {
+            dominance: [DEFAULT_DOMINANCE; TRAIT_COUNT],
+            mutation_bps: DEFAULT_MUTATION_BPS,
+        }
+    }
+}
+
+impl DominanceModel {
+    pub fn is_valid(&self) -> bool {
+        self.mutation_bps as u64 <= MUTATION_DENOMINATOR
+    }
+}
+
+/// A named shift of the trait vector from the governance catalog; each
+/// genesis receives it with probability `weight_bps / 10000`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CatalogMutation {
+    pub id: u16,
+    pub deltas: [i8; TRAIT_COUNT],
+    pub weight_bps: u16,
+}
+
+/// A genome `generation` steps above a child: its parents are 1, their
+/// parents 2 and so on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Ancestor {
+    pub genome_id: u64,
+    pub generation: u8,
+}
+
+/// A child's DNA supply is the geometric mean of its parents' supplies,
+/// scaled by `scale_bps / 10000` and its rarity multiplier, then clamped
+/// to `[min_supply, max_supply]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SupplyRule {
+    pub scale_bps: u16,
+    pub min_supply: u64,
+    pub max_supply: u64,
+}
+
+impl SupplyRule {
+    pub fn is_valid(&self) -> bool {
+        self.scale_bps > 0 && self.min_supply > 0 && self.min_supply <= self.max_supply
+    }
+    
+    pub fn offspring_supply(&self, parent_supplies: &[u64], rarity_multiplier: u16) -> u64 {
+        let scaled = geometric_mean(parent_supplies) as u128 * self.scale_bps as u128 * rarity_multiplier as u128
+            / (SUPPLY_SCALE_DENOMINATOR as u128 * RARITY_MULTIPLIER_DENOMINATOR as u128);
+        u64::try_from(scaled).unwrap_or(u64::MAX).max(self.min_supply).min(self.max_supply)
+    }
+}
+
+/// The random inputs of one genesis. The engine hashes each from the
+/// GenesisRecord's entropy, creator, genome id and slot; simulations can
+/// draw them from any seed with `from_seed`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GenesisSeeds {
+    pub rarity_roll: u64,
+    pub traits: [u8; 32],
+    pub mutations: [u8; 32],
+}
+
+impl GenesisSeeds {
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let mut rng = SeedRng::new(seed);
+        let mut draw = || {
+            let mut bytes = [0u8; 32];
+            for chunk in bytes.chunks_exact_mut(8) {
+                chunk.copy_from_slice(&rng.next_u64().to_le_bytes());
+            }
+            bytes
+        };
+        let (traits, mutations) = (draw(), draw());
+        Self { rarity_roll: rng.next_u64(), traits, mutations }
+    }
+}
+
+/// What `simulate_genesis` rolls for a child: its rarity tier as an index
+/// into the rarity tables, its trait vector and the catalog mutations it
+/// received, already applied to `traits`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Offspring {
+    pub rarity_tier: u8,
+    pub traits: TraitVector,
+    pub mutations: Vec<CatalogMutation>,
+}
+
+/// SplitMix64; deterministic and cheap enough for on-chain use.
+#[derive(Clone, Debug)]
+pub struct SeedRng(u64);
+
+impl SeedRng {
+    pub fn new(seed: &[u8; 32]) -> Self {
+        let state = seed
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().expect("8-byte chunk")))
+            .fold(0, |acc, word| mix(acc ^ word));
+        Self(state)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        mix(self.0)
+    }
+
+    /// Uniform enough in `0..bound` for bounds far below 2^64.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+/// JSON schemas of the public types, keyed by type name.
+#[cfg(feature = "schema")]
+pub fn schemas() -> Vec<(&'static str, schemars::schema::RootSchema)> {
+    vec![
+        ("DominanceModel", schemars::schema_for!(DominanceModel)),
+        ("TraitVector", schemars::schema_for!(TraitVector)),
+        ("CatalogMutation", schemars::schema_for!(CatalogMutation)),
+        ("Ancestor", schemars::schema_for!(Ancestor)),
+        ("SupplyRule", schemars::schema_for!(SupplyRule)),
+        ("GenesisSeeds", schemars::schema_for!(GenesisSeeds)),
+        ("Offspring", schemars::schema_for!(Offspring)),
+    ]
+}
+
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Breeds a child trait vector. Each trait inherits from two distinct
+/// parents picked by the seed (the only parent's allele with one parent, a
+/// random allele with none), resolves dominance, then may mutate.
+pub fn recombine(parents: &[TraitVector], model: &DominanceModel, seed: &[u8; 32]) -> TraitVector {
+    let mut rng = SeedRng::new(seed);
+    let mut child = [0u8; TRAIT_COUNT];
+    for (index, trait_value) in child.iter_mut().enumerate() {
+        let allele = match parents.len() {
+            0 => rng.next_u64() as u8,
+            1 => parents[0][index],
+            count => {
+                let count = count as u64;
+                let first = rng.below(count);
+                let second = (first + 1 + rng.below(count - 1)) % count;
+                let (a, b) = (parents[first as usize][index], parents[second as usize][index]);
+                let (dominant, recessive) = if a >= b { (a, b) } else { (b, a) };
+                if rng.below(255) < model.dominance[index] as u64 {
+                    dominant
+                } else {
+                    recessive
+                }
+            }
+        };
+        *trait_value = if rng.below(MUTATION_DENOMINATOR) < model.mutation_bps as u64 {
+            rng.next_u64() as u8
+        } else {
+            allele
+        };
+    }
+    child
+}
+
+/// The catalog mutations a child receives, each rolled independently from
+/// the seed, in catalog order.
+pub fn roll_mutations(catalog: &[CatalogMutation], seed: &[u8; 32]) -> Vec<CatalogMutation> {
+    let mut rng = SeedRng::new(seed);
+    catalog
+        .iter()
+        .filter(|mutation| rng.below(MUTATION_DENOMINATOR) < mutation.weight_bps as u64)
+        .copied()
+        .collect()
+}
+
+/// `traits` shifted by each mutation's deltas in turn, saturating at 0 and 255.
+pub fn apply_mutations(mut traits: TraitVector, mutations: &[CatalogMutation]) -> TraitVector {
+    for mutation in mutations {
+        for (allele, delta) in traits.iter_mut().zip(mutation.deltas) {
+            *allele = allele.saturating_add_signed(delta);
+        }
+    }
+    traits
+}
+
+/// Tier picked with probability weight / sum(weights); Common (0) when
+/// every weight is 0.
+pub fn rarity_tier(roll: u64, weights: &[u16; RARITY_TIERS]) -> u8 {
+    let total: u64 = weights.iter().map(|weight| *weight as u64).sum();
+    if total == 0 {
+        return 0;
+    }
+    let mut point = roll % total;
+    for (tier, weight) in weights.iter().enumerate() {
+        if point < *weight as u64 {
+            return tier as u8;
+        }
+        point -= *weight as u64;
+    }
+    0
+}
+
+/// One genesis end to end: the rarity tier from `seeds.rarity_roll`, the
+/// child's traits recombined from `parents` with `seeds.traits`, then the
+/// catalog mutations rolled with `seeds.mutations` and applied. Pure, so
+/// the same inputs give the same offspring on-chain and off.
+pub fn simulate_genesis(
+    parents: &[TraitVector],
+    model: &DominanceModel,
+    catalog: &[CatalogMutation],
+    rarity_weights: &[u16; RARITY_TIERS],
+    seeds: &GenesisSeeds,
+) -> Offspring {
+    let traits = recombine(parents, model, &seeds.traits);
+    let mutations = roll_mutations(catalog, &seeds.mutations);
+    Offspring {
+        rarity_tier: rarity_tier(seeds.rarity_roll, rarity_weights),
+        traits: apply_mutations(traits, &mutations),
+        mutations,
+    }
+}
+
+/// Each parent as its child sees it: the parent at generation 1, then its
+/// own ancestors one generation further.
+fn ancestors_through<'a>(parent_id: u64, lineage: &'a [Ancestor]) -> impl Iterator<Item = Ancestor> + 'a {
+    let parent = Ancestor { genome_id: parent_id, generation: 1 };
+    std::iter::once(parent).chain(
+        lineage.iter().map(|ancestor| Ancestor { generation: ancestor.generation.saturating_add(1), ..*ancestor }),
+    )
+}
+
+/// A child's lineage from its parents' `(genome_id, lineage)`: ancestors up
+/// to MAX_LINEAGE_GENERATIONS, each at the nearest generation it is reached
+/// through, sorted nearest first and cut at MAX_LINEAGE_ANCESTORS.
+pub fn child_lineage(parents: &[(u64, &[Ancestor])]) -> Vec<Ancestor> {
+    let mut lineage: Vec<Ancestor> = parents
+        .iter()
+        .flat_map(|(parent_id, ancestors)| ancestors_through(*parent_id, ancestors))
+        .filter(|ancestor| ancestor.generation <= MAX_LINEAGE_GENERATIONS)
+        .collect();
+    lineage.sort_by_key(|ancestor| (ancestor.genome_id, ancestor.generation));
+    lineage.dedup_by_key(|ancestor| ancestor.genome_id);
+    lineage.sort_by_key(|ancestor| (ancestor.generation, ancestor.genome_id));
+    lineage.truncate(MAX_LINEAGE_ANCESTORS);
+    lineage
+}
+
+/// Generation, seen from the child, of the nearest ancestor two parents
+/// share; an ancestor counts at the farther of its two generations, and a
+/// parent passed twice is shared at generation 1. None if no two parents'
+/// lineages meet. Lineages are bounded, so distant kinship can go unseen.
+pub fn common_ancestor_generation(parents: &[(u64, &[Ancestor])]) -> Option<u8> {
+    let mut nearest: Option<u8> = None;
+    for (index, (first_id, first)) in parents.iter().enumerate() {
+        for (second_id, second) in &parents[index + 1..] {
+            for a in ancestors_through(*first_id, first) {
+                for b in ancestors_through(*second_id, second).filter(|b| b.genome_id == a.genome_id) {
+                    let generation = a.generation.max(b.generation);
+                    nearest = Some(nearest.map_or(generation, |nearest| nearest.min(generation)));
+                }
+            }
+        }
+    }
+    nearest
+}
+
+/// Royalty each ancestor in `lineage` (nearest first) is owed on `amount`
+/// of yield: `bps_per_generation` of it per generation up to `generations`,
+/// split evenly between that generation's ancestors and rounded down.
+/// Farther ancestors are left out, so the result may be shorter.
+pub fn ancestor_royalties(amount: u64, lineage: &[Ancestor], bps_per_generation: u16, generations: u8) -> Vec<(Ancestor, u64)> {
+    let share = amount as u128 * bps_per_generation as u128 / ROYALTY_DENOMINATOR as u128;
+    lineage
+        .iter()
+        .take_while(|ancestor| ancestor.generation <= generations)
+        .map(|ancestor| {
+            let kin = lineage.iter().filter(|other| other.generation == ancestor.generation).count() as u128;
+            (*ancestor, (share / kin) as u64)
+        })
+        .collect()
+}
+
+/// Integer geometric mean, rounded down; 0 without values or with a 0.
+/// Exact: candidates are compared against the product in wide integers.
+pub fn geometric_mean(values: &[u64]) -> u64 {
+    let (Some(&low), Some(&high)) = (values.iter().min(), values.iter().max()) else {
+        return 0;
+    };
+    let product = wide_product(values.iter().copied());
+    // Largest root in [low, high] whose power does not exceed the product
+    let (mut low, mut high) = (low, high);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if wide_cmp(&wide_product(values.iter().map(|_| mid)), &product).is_le() {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
+/// Product as little-endian u64 limbs.
+fn wide_product(factors: impl Iterator<Item = u64>) -> Vec<u64> {
+    let mut limbs = vec![1u64];
+    for factor in factors {
+        let mut carry = 0u128;
+        for limb in limbs.iter_mut() {
+            let wide = *limb as u128 * factor as u128 + carry;
+            *limb = wide as u64;
+            carry = wide >> 64;
+        }
+        if carry > 0 {
+            limbs.push(carry as u64);
+        }
+    }
+    limbs
+}
+
+fn wide_cmp(a: &[u64], b: &[u64]) -> std::cmp::Ordering {
+    let significant = |limbs: &[u64]| limbs.iter().rposition(|limb| *limb != 0).map_or(0, |top| top + 1);
+    let (a, b) = (&a[..significant(a)], &b[..significant(b)]);
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dominance_extremes() {
+        let parents = [[10u8; TRAIT_COUNT], [200u8; TRAIT_COUNT]];
+        let mut model = DominanceModel { dominance: [255; TRAIT_COUNT], mutation_bps: 0 };
+        for seed in 0..32u8 {
+            assert_eq!(recombine(&parents, &model, &[seed; 32]), [200; TRAIT_COUNT]);
+        }
+
+        model.dominance = [0; TRAIT_COUNT];
+        model.dominance[3] = 255;
+        let child = recombine(&parents, &model, &[7; 32]);
+        assert_eq!(child[3], 200);
+        assert!(child.iter().enumerate().all(|(i, allele)| i == 3 || *allele == 10));
+    }
+
+    #[test]
+    fn test_recombination_is_deterministic() {
+        let parents = [[1, 2, 3, 4, 5, 6, 7, 8], [9, 10, 11, 12, 13, 14, 15, 16], [17; TRAIT_COUNT]];
+        let model = DominanceModel::default();
+        let child = recombine(&parents, &model, &[42; 32]);
+        assert_eq!(child, recombine(&parents, &model, &[42; 32]));
+        assert_ne!(child, recombine(&parents, &model, &[43; 32]));
+
+        // Without mutation every allele comes from some parent
+        let model = DominanceModel { mutation_bps: 0, ..model };
+        let child = recombine(&parents, &model, &[42; 32]);
+        assert!(child.iter().enumerate().all(|(i, allele)| parents.iter().any(|p| p[i] == *allele)));
+    }
+
+    #[test]
+    fn test_mutation_and_single_parent() {
+        let parent = [[77u8; TRAIT_COUNT]];
+        let clone = DominanceModel { mutation_bps: 0, ..DominanceModel::default() };
+        assert_eq!(recombine(&parent, &clone, &[1; 32]), [77; TRAIT_COUNT]);
+
+        let always = DominanceModel { mutation_bps: 10_000, ..clone };
+        let mutated: Vec<_> = (0..16u8).map(|seed| recombine(&parent, &always, &[seed; 32])).collect();
+        assert!(mutated.iter().any(|child| *child != [77; TRAIT_COUNT]));
+        assert!(!DominanceModel { mutation_bps: 10_001, ..clone }.is_valid());
+    }
+
+    #[test]
+    fn test_catalog_mutations() {
+        let mut deltas = [0i8; TRAIT_COUNT];
+        deltas[0] = 100;
+        deltas[1] = -20;
+        let always = CatalogMutation { id: 4, deltas, weight_bps: 10_000 };
+        let never = CatalogMutation { id: 5, deltas, weight_bps: 0 };
+        let catalog = [never, always, always];
+        for seed in 0..8u8 {
+            assert_eq!(roll_mutations(&catalog, &[seed; 32]), vec![always, always]);
+        }
+
+        let coin = CatalogMutation { weight_bps: 5_000, ..always };
+        let hits: Vec<usize> = (0..32u8).map(|seed| roll_mutations(&[coin], &[seed; 32]).len()).collect();
+        assert!(hits.contains(&0) && hits.contains(&1));
+
+        let traits = apply_mutations([200, 10, 5, 5, 5, 5, 5, 5], &[always, always]);
+        assert_eq!(traits, [255, 0, 5, 5, 5, 5, 5, 5]);
+        assert_eq!(apply_mutations(traits, &[]), traits);
+    }
+
+    #[test]
+    fn test_lineage_and_common_ancestors() {
+        let at = |genome_id, generation| Ancestor { genome_id, generation };
+        // 10 and 11 are siblings of founders 1 and 2; 20 descends from 10 and 3
+        let siblings = [at(1, 1), at(2, 1)];
+        let cousin = child_lineage(&[(10, &siblings), (3, &[])]);
+        assert_eq!(cousin, vec![at(3, 1), at(10, 1), at(1, 2), at(2, 2)]);
+
+        assert_eq!(common_ancestor_generation(&[(10, &siblings), (11, &siblings)]), Some(2));
+        assert_eq!(common_ancestor_generation(&[(20, &cousin), (11, &siblings)]), Some(3));
+        assert_eq!(common_ancestor_generation(&[(20, &cousin), (10, &siblings)]), Some(2));
+        assert_eq!(common_ancestor_generation(&[(4, &[]), (4, &[])]), Some(1));
+        assert_eq!(common_ancestor_generation(&[(10, &siblings), (3, &[])]), None);
+        assert_eq!(common_ancestor_generation(&[(10, &siblings)]), None);
+
+        // Reached through both parents, an ancestor keeps its nearer generation
+        let child = child_lineage(&[(20, &cousin), (10, &siblings)]);
+        assert_eq!(&child[..3], &[at(10, 1), at(20, 1), at(1, 2)]);
+        assert_eq!(child.iter().filter(|ancestor| ancestor.genome_id == 10).count(), 1);
+
+        // Deep or wide lineages are cut, farthest generations first
+        let deep = [at(7, MAX_LINEAGE_GENERATIONS)];
+        assert!(child_lineage(&[(8, &deep)]).iter().all(|ancestor| ancestor.genome_id != 7));
+        let wide: Vec<Ancestor> = (100..140).map(|id| at(id, 2)).collect();
+        let child = child_lineage(&[(5, &wide), (6, &[])]);
+        assert_eq!(child.len(), MAX_LINEAGE_ANCESTORS);
+        assert_eq!(&child[..2], &[at(5, 1), at(6, 1)]);
+    }
+
+    #[test]
+    fn test_ancestor_royalties() {
+        let at = |genome_id, generation| Ancestor { genome_id, generation };
+        let lineage = [at(3, 1), at(10, 1), at(1, 2), at(2, 2), at(4, 2), at(9, 3)];
+        // 2% a generation: 1% to each parent, a third of 2% to each grandparent
+        let royalties = ancestor_royalties(10_000, &lineage, 200, 2);
+        assert_eq!(
+            royalties,
+            vec![(at(3, 1), 100), (at(10, 1), 100), (at(1, 2), 66), (at(2, 2), 66), (at(4, 2), 66)]
+        );
+        assert_eq!(ancestor_royalties(10_000, &lineage, 200, 3).last(), Some(&(at(9, 3), 200)));
+        assert!(ancestor_royalties(10_000, &lineage, 200, 0).is_empty());
+        assert!(ancestor_royalties(10_000, &[], 200, 3).is_empty());
+        assert!(ancestor_royalties(40, &lineage, 200, 3).iter().all(|(_, royalty)| *royalty == 0));
+    }
+
+    #[test]
+    fn test_rarity_tier() {
+        let weights = [7000, 2200, 700, 100];
+        assert_eq!(rarity_tier(0, &weights), 0);
+        assert_eq!(rarity_tier(6_999, &weights), 0);
+        assert_eq!(rarity_tier(7_000, &weights), 1);
+        assert_eq!(rarity_tier(9_899, &weights), 2);
+        assert_eq!(rarity_tier(9_900, &weights), 3);
+        assert_eq!(rarity_tier(19_900, &weights), 3);
+        assert_eq!(rarity_tier(12, &[0, 0, 5, 0]), 2);
+        assert_eq!(rarity_tier(12, &[0; RARITY_TIERS]), 0);
+    }
+
+    // Pinned outputs: a change here changes every replayed and simulated
+    // genesis, so update them only together with the on-chain engine
+    #[test]
+    fn test_genetics_snapshots() {
+        let mut rng = SeedRng::new(&[9; 32]);
+        assert_eq!(
+            [rng.next_u64(), rng.next_u64(), rng.next_u64()],
+            [9_303_307_979_056_903_211, 15_850_731_708_872_917_202, 11_010_125_436_851_628_076]
+        );
+
+        let seeds = GenesisSeeds::from_seed(&[42; 32]);
+        assert_eq!(seeds.rarity_roll, 17_448_324_484_524_115_513);
+        assert_eq!(&seeds.traits[..8], &[100, 148, 145, 47, 63, 55, 172, 71]);
+        assert_eq!(&seeds.mutations[..8], &[49, 147, 53, 197, 85, 31, 153, 148]);
+
+        let parents = [[12, 200, 37, 90, 150, 3, 255, 64], [180, 20, 99, 91, 7, 240, 0, 128]];
+        let mut deltas = [0i8; TRAIT_COUNT];
+        deltas[2] = 40;
+        deltas[5] = -90;
+        let catalog = [
+            CatalogMutation { id: 1, deltas, weight_bps: 5_000 },
+            CatalogMutation { id: 2, deltas: [-3; TRAIT_COUNT], weight_bps: 2_500 },
+            CatalogMutation { id: 3, deltas: [9; TRAIT_COUNT], weight_bps: 7_500 },
+        ];
+        let simulate = |seed: u8| {
+            let seeds = GenesisSeeds::from_seed(&[seed; 32]);
+            simulate_genesis(&parents, &DominanceModel::default(), &catalog, &[7000, 2200, 700, 100], &seeds)
+        };
+        let snapshots: [(u8, u8, TraitVector, &[u16]); 4] = [
+            (0, 0, [177, 197, 96, 88, 4, 237, 252, 125], &[2]),
+            (1, 1, [189, 209, 148, 100, 159, 159, 255, 137], &[1, 3]),
+            (42, 0, [180, 200, 139, 91, 150, 150, 255, 128], &[1]),
+            (255, 0, [186, 206, 145, 97, 13, 9, 255, 134], &[1, 2, 3]),
+        ];
+        for (seed, rarity_tier, traits, mutation_ids) in snapshots {
+            let offspring = simulate(seed);
+            assert_eq!((offspring.rarity_tier, offspring.traits), (rarity_tier, traits), "seed {seed}");
+            let ids: Vec<u16> = offspring.mutations.iter().map(|mutation| mutation.id).collect();
+            assert_eq!(ids, mutation_ids, "seed {seed}");
+        }
+
+        // The pipeline is exactly its parts, in the engine's order
+        let seeds = GenesisSeeds::from_seed(&[1; 32]);
+        let traits = recombine(&parents, &DominanceModel::default(), &seeds.traits);
+        let mutations = roll_mutations(&catalog, &seeds.mutations);
+        assert_eq!(simulate(1).traits, apply_mutations(traits, &mutations));
+    }
+
+    #[test]
+    fn test_geometric_mean_and_offspring_supply() {
+        assert_eq!(geometric_mean(&[]), 0);
+        assert_eq!(geometric_mean(&[7]), 7);
+        assert_eq!(geometric_mean(&[4, 9]), 6);
+        assert_eq!(geometric_mean(&[2, 3]), 2);
+        assert_eq!(geometric_mean(&[1_000_000, 0]), 0);
+        assert_eq!(geometric_mean(&[u64::MAX, u64::MAX, u64::MAX]), u64::MAX);
+        assert_eq!(geometric_mean(&[1, u64::MAX]), 4_294_967_295);
+
+        let rule = SupplyRule { scale_bps: 10_000, min_supply: 1_000, max_supply: 5_000_000 };
+        assert!(rule.is_valid());
+        assert_eq!(rule.offspring_supply(&[1_000_000, 4_000_000], 100), 2_000_000);
+        assert_eq!(rule.offspring_supply(&[1_000_000, 4_000_000], 150), 3_000_000);
+        // Clamped at both ends
+        assert_eq!(rule.offspring_supply(&[1_000_000, 4_000_000], 500), 5_000_000);
+        assert_eq!(rule.offspring_supply(&[], 100), 1_000);
+        let half = SupplyRule { scale_bps: 5_000, ..rule };
+        assert_eq!(half.offspring_supply(&[1_000_000, 1_000_000], 100), 500_000);
+        assert!(!SupplyRule { min_supply: 6_000_000, ..rule }.is_valid());
+        assert!(!SupplyRule { scale_bps: 0, ..rule }.is_valid());
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_json_round_trip_and_schemas() {
+        let model = DominanceModel { mutation_bps: 250, ..DominanceModel::default() };
+        let json = serde_json::to_value(model).unwrap();
+        assert_eq!(json["mutation_bps"], 250);
+        assert_eq!(serde_json::from_value::<DominanceModel>(json).unwrap(), model);
+
+        let schemas = schemas();
+        let (_, schema) = schemas.iter().find(|(name, _)| *name == "DominanceModel").unwrap();
+        let schema = serde_json::to_value(schema).unwrap();
+        assert_eq!(schema["required"], serde_json::json!(["dominance", "mutation_bps"]));
+        assert_eq!(schema["properties"]["dominance"]["maxItems"], TRAIT_COUNT);
+    }
+}
@@ -0,0 +1,842 @@
+//! Typed mirrors of every event emitted by the RSM programs.
+//!
+//! Both the Anchor programs (`emit!`) and the native rsm-token program
+//! (`emit_event`) log events through `sol_log_data` as an 8-byte
+//! discriminator, `sha256("event:<Name>")[..8]`, followed by the borsh
+//! encoded body. The layouts below must stay field-for-field identical to the
+//! on-chain structs; `decode_log` turns RPC log lines back into `RsmEvent`s.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_program::{hash::hash, pubkey::Pubkey};
+use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionStatusMeta};
+use std::str::FromStr;
+use thiserror::Error;
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum EventError {
+    #[error("Invalid base64 in program data: {0}")]
+    Base64(String),
+    #[error("Event {name} failed to deserialize: {reason}")]
+    Layout { name: &'static str, reason: String },
+}
+
+/// Event discriminator as computed by Anchor and by rsm-token's `emit_event`.
+pub fn discriminator(name: &str) -> [u8; 8] {
+    let mut out = [0u8; 8];
+    out.copy_from_slice(&hash(format!("event:{}", name).as_bytes()).to_bytes()[..8]);
+    out
+}
+
+pub trait Event: BorshSerialize + BorshDeserialize {
+    const NAME: &'static str;
+
+    fn discriminator() -> [u8; 8] {
+        discriminator(Self::NAME)
+    }
+
+    /// Discriminator followed by the borsh body, exactly as logged on chain.
+    fn to_log_bytes(&self) -> Vec<u8> {
+        let mut out = Self::discriminator().to_vec();
+        out.extend(self.try_to_vec().expect("event serialization is infallible"));
+        out
+    }
+}
+
+macro_rules! events {
+    ($($name:ident),* $(,)?) => {
+        $(impl Event for $name {
+            const NAME: &'static str = stringify!($name);
+        })*
+
+        #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(tag = "type", content = "data")]
+        pub enum RsmEvent {
+            $($name($name),)*
+        }
+
+        impl RsmEvent {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(RsmEvent::$name(_) => $name::NAME,)*
+                }
+            }
+
+            /// Decode a raw `sol_log_data` payload. Returns `Ok(None)` for
+            /// data that does not start with a known discriminator.
+            pub fn decode(data: &[u8]) -> Result<Option<Self>, EventError> {
+                if data.len() < 8 {
+                    return Ok(None);
+                }
+                let (disc, mut body) = data.split_at(8);
+                $(if disc == $name::discriminator() {
+                    return <$name as BorshDeserialize>::deserialize(&mut body)
+                        .map(|event| Some(RsmEvent::$name(event)))
+                        .map_err(|e| EventError::Layout {
+                            name: $name::NAME,
+                            reason: e.to_string(),
+                        });
+                })*
+                Ok(None)
+            }
+        }
+    };
+}
+
+// rsm-token
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomeMintedEvent {
+    pub genome_hash: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub entropy: u16,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomeBurnedEvent {
+    pub genome_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SupplyAuditEvent {
+    pub config: Pubkey,
+    pub auditor: Pubkey,
+    pub recorded_total: u64,
+    pub computed_total: u64,
+    pub discrepancy: i128,
+    pub genomes_counted: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SupplyRepairedEvent {
+    pub config: Pubkey,
+    pub old_total: u64,
+    pub new_total: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomeRescoredEvent {
+    pub genome_hash: [u8; 32],
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub rescored_amount: u64,
+    pub clawback: u64,
+    pub clawback_eta: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ClawbackExecutedEvent {
+    pub genome_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StreamWithdrawnEvent {
+    pub genome_hash: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub released: u64,
+    pub completed: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintSplitPaidEvent {
+    pub genome_hash: [u8; 32],
+    pub token_account: Pubkey,
+    pub bps: u16,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MaxSupplyRaisedEvent {
+    pub config: Pubkey,
+    pub old_max_supply: u64,
+    pub new_max_supply: u64,
+    pub executed_at: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomeNoteSetEvent {
+    pub genome_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub note: Vec<u8>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintChallengedEvent {
+    pub genome_hash: [u8; 32],
+    pub challenger: Pubkey,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub slashed: u64,
+    pub reward: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DustConsolidatedEvent {
+    pub config: Pubkey,
+    pub reward_pool: Pubkey,
+    pub amount: u64,
+    pub consolidated: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SpeciesDiscoveredEvent {
+    pub species: u16,
+    pub genome_hash: [u8; 32],
+    pub discoverer: Pubkey,
+    pub bonus_bps: u16,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintAutoStakedEvent {
+    pub genome_hash: [u8; 32],
+    pub recipient: Pubkey,
+    pub staking_program: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomePreRegisteredEvent {
+    pub genome_hash: [u8; 32],
+    pub registrant: Pubkey,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EmergencyClawbackEvent {
+    pub config: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub evidence_hash: [u8; 32],
+    pub reason_code: u16,
+}
+
+// rsm-token-anchor
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ConfigMigratedEvent {
+    pub config: Pubkey,
+    pub legacy_config: Pubkey,
+    pub mint: Pubkey,
+    pub total_minted: u64,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomeMigratedEvent {
+    pub genome: Pubkey,
+    pub legacy_genome: Pubkey,
+    pub genome_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub tokens_minted: u64,
+    pub slot: u64,
+}
+
+// rna-token
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenesisEvent {
+    pub creator: Pubkey,
+    pub rna_burned: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AncestorRoyaltyPaidEvent {
+    pub genome_id: u64,
+    pub ancestor_id: u64,
+    pub generation: u8,
+    pub founder: Pubkey,
+    pub amount: u64,
+}
+
+// dna-token
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotTakenEvent {
+    pub snapshot_id: u64,
+    pub merkle_root: [u8; 32],
+    pub total_tracked_supply: u64,
+    pub holder_count: u32,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CompressedGenomeEvent {
+    pub merkle_tree: Pubkey,
+    pub genome_id: u64,
+    pub leaf_index: u32,
+    pub previous_leaf: [u8; 32],
+    pub leaf: [u8; 32],
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IndexSwapEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub owner: Pubkey,
+    pub dna_amount: u64,
+    pub index_amount: u64,
+    pub redeemed: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LicenseSettledEvent {
+    pub license: Pubkey,
+    pub genome_id: u64,
+    pub licensee: Pubkey,
+    pub founder: Pubkey,
+    pub paid: u64,
+    pub refunded: u64,
+    pub settled_slot: u64,
+    pub closed: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomeRetiredEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub reason_code: u32,
+    pub burned: u64,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PermitTransferEvent {
+    pub genome: Pubkey,
+    pub holder: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+    pub submitter: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BundleSoldEvent {
+    pub bundle: Pubkey,
+    pub bundle_id: u64,
+    pub creator: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub legs: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DelegatedSaleEvent {
+    pub listing: Pubkey,
+    pub genome_id: u64,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub price: u64,
+    pub remaining: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FounderRateUpdatedEvent {
+    pub authority: Pubkey,
+    pub rate_bps: i16,
+    pub mints_updated: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DnaBurnedEvent {
+    pub receipt: Pubkey,
+    pub burner: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReceiptConsumedEvent {
+    pub receipt: Pubkey,
+    pub consumer: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintAuthorityFinalizedEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub mint: Pubkey,
+    pub mint_authority: Option<Pubkey>,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomeMetadataCorrectedEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub correction_id: u8,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisputeFreezeEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub claim_id: u64,
+    pub accounts: Vec<Pubkey>,
+    pub frozen: bool,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ConsciousnessGrownEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub staker: Pubkey,
+    pub previous: u8,
+    pub consciousness: u8,
+    pub experience_spent: u128,
+    pub experience_left: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FounderTransferredEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub previous: Pubkey,
+    pub founder: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CollectionMembershipEvent {
+    pub collection: Pubkey,
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub added: bool,
+    pub genome_count: u64,
+    pub total_consciousness: u64,
+}
+
+// genesis-engine
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Rare,
+    Epic,
+    Mythic,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenesisCreatedEvent {
+    pub genome_id: u64,
+    pub creator: Pubkey,
+    pub rna_burned: u64,
+    pub consciousness: u8,
+    pub rarity: Rarity,
+    pub rna_multiplier: u16,
+    pub timestamp: i64,
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub matures_at_slot: u64,
+    pub species: u16,
+    pub traits: [u8; 8],
+    pub mutations: Vec<u16>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenesisQueuedEvent {
+    pub position: u64,
+    pub creator: Pubkey,
+    pub species: u16,
+    pub rna_locked: u64,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenesisCommittedEvent {
+    pub creator: Pubkey,
+    pub species: u16,
+    pub commitment: [u8; 32],
+    pub rna_escrowed: u64,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenesisCommitForfeitedEvent {
+    pub creator: Pubkey,
+    pub species: u16,
+    pub rna_forfeited: u64,
+    pub commit_slot: u64,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IncubationAcceleratedEvent {
+    pub genome_id: u64,
+    pub creator: Pubkey,
+    pub rna_burned: u64,
+    pub matures_at_slot: u64,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenesisEventCreatedEvent {
+    pub event_id: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub cost: u64,
+    pub max_participants: u64,
+    pub mutations: Vec<u16>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EngineStateExportedEvent {
+    pub engine: Pubkey,
+    pub authority: Pubkey,
+    pub version: u8,
+    pub total_genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub digest: [u8; 32],
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EngineStateImportedEvent {
+    pub engine: Pubkey,
+    pub source_engine: Pubkey,
+    pub total_genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomeDonatedEvent {
+    pub genome_id: u64,
+    pub donor: Pubkey,
+    pub discount_bps: u16,
+    pub pool_size: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenePoolDistributedEvent {
+    pub genome_id: u64,
+    pub donor: Pubkey,
+    pub winner: Pubkey,
+    pub stake: Pubkey,
+    pub candidates: u32,
+    pub slot: u64,
+}
+
+// genome-marketplace
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ListingCreatedEvent {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+    pub price_per_token: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ListingSoldEvent {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+    pub total_price: u64,
+    pub fee: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ListingCancelledEvent {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub genome_id: u64,
+    pub returned: u64,
+    pub timestamp: i64,
+}
+
+// treasury
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BuybackBurnEvent {
+    pub epoch: u64,
+    pub burned: u64,
+    pub to_stakers: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ColdStorageSweepEvent {
+    pub epoch: u64,
+    pub amount: u64,
+    pub cold_storage: Pubkey,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+// consciousness-oracle
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ScoreFinalizedEvent {
+    pub genome_hash: [u8; 32],
+    pub consciousness: u8,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub submissions: u8,
+    pub slashed: u64,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ScorerSlashedEvent {
+    pub scorer: Pubkey,
+    pub genome_hash: [u8; 32],
+    pub amount: u64,
+}
+
+// multisig
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionProposedEvent {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    pub memo: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionExecutedEvent {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub index: u64,
+    pub executor: Pubkey,
+}
+
+// pause
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PauseToggledEvent {
+    pub paused: bool,
+    pub signer: Pubkey,
+    pub reason_code: u32,
+    pub timestamp: i64,
+}
+
+events!(
+    GenomeMintedEvent,
+    GenomeBurnedEvent,
+    SupplyAuditEvent,
+    SupplyRepairedEvent,
+    GenomeRescoredEvent,
+    ClawbackExecutedEvent,
+    StreamWithdrawnEvent,
+    MintSplitPaidEvent,
+    MaxSupplyRaisedEvent,
+    GenomeNoteSetEvent,
+    MintChallengedEvent,
+    DustConsolidatedEvent,
+    SpeciesDiscoveredEvent,
+    MintAutoStakedEvent,
+    GenomePreRegisteredEvent,
+    EmergencyClawbackEvent,
+    ConfigMigratedEvent,
+    GenomeMigratedEvent,
+    GenesisEvent,
+    AncestorRoyaltyPaidEvent,
+    SnapshotTakenEvent,
+    CompressedGenomeEvent,
+    IndexSwapEvent,
+    LicenseSettledEvent,
+    GenomeRetiredEvent,
+    PermitTransferEvent,
+    BundleSoldEvent,
+    DelegatedSaleEvent,
+    FounderRateUpdatedEvent,
+    DnaBurnedEvent,
+    ReceiptConsumedEvent,
+    MintAuthorityFinalizedEvent,
+    GenomeMetadataCorrectedEvent,
+    DisputeFreezeEvent,
+    ConsciousnessGrownEvent,
+    FounderTransferredEvent,
+    CollectionMembershipEvent,
+    GenesisCreatedEvent,
+    GenesisQueuedEvent,
+    GenesisCommittedEvent,
+    GenesisCommitForfeitedEvent,
+    IncubationAcceleratedEvent,
+    GenesisEventCreatedEvent,
+    EngineStateExportedEvent,
+    EngineStateImportedEvent,
+    GenomeDonatedEvent,
+    GenePoolDistributedEvent,
+    ListingCreatedEvent,
+    ListingSoldEvent,
+    ListingCancelledEvent,
+    BuybackBurnEvent,
+    ColdStorageSweepEvent,
+    ScoreFinalizedEvent,
+    ScorerSlashedEvent,
+    TransactionProposedEvent,
+    TransactionExecutedEvent,
+    PauseToggledEvent,
+);
+
+/// An event together with the program that was executing when it was logged.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedEvent {
+    pub program_id: Option<Pubkey>,
+    pub event: RsmEvent,
+}
+
+/// Decode every known event from a transaction's log messages.
+///
+/// The invoke stack is tracked from the runtime's `invoke`/`success`/`failed`
+/// lines so each event is attributed to the program that logged it, including
+/// events emitted from inside a CPI. Unknown discriminators are skipped.
+pub fn decode_log(logs: &[String]) -> Result<Vec<DecodedEvent>, EventError> {
+    let mut stack: Vec<Pubkey> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        if let Some(payload) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            let mut data = Vec::new();
+            for segment in payload.split_whitespace() {
+                let bytes = STANDARD
+                    .decode(segment)
+                    .map_err(|_| EventError::Base64(segment.to_string()))?;
+                data.extend(bytes);
+            }
+            if let Some(event) = RsmEvent::decode(&data)? {
+                events.push(DecodedEvent {
+                    program_id: stack.last().copied(),
+                    event,
+                });
+            }
+            continue;
+        }
+
+        let mut words = line.split_whitespace();
+        if words.next() != Some("Program") {
+            continue;
+        }
+        let (Some(program), Some(action)) = (words.next(), words.next()) else {
+            continue;
+        };
+        let Ok(program) = Pubkey::from_str(program) else {
+            continue;
+        };
+        match action {
+            "invoke" => stack.push(program),
+            "success" | "failed:" | "failed" => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    Ok(events)
+}
+
+/// Decode events from the transaction meta returned by `getTransaction`.
+pub fn decode_transaction_meta(
+    meta: &UiTransactionStatusMeta,
+) -> Result<Vec<DecodedEvent>, EventError> {
+    match &meta.log_messages {
+        OptionSerializer::Some(logs) => decode_log(logs),
+        _ => Ok(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_line(chunks: &[&[u8]]) -> String {
+        let parts: Vec<String> = chunks.iter().map(|c| STANDARD.encode(c)).collect();
+        format!("{}{}", PROGRAM_DATA_PREFIX, parts.join(" "))
+    }
+
+    #[test]
+    fn test_discriminator_matches_anchor() {
+        let expected = &hash(b"event:BuybackBurnEvent").to_bytes()[..8];
+        assert_eq!(BuybackBurnEvent::discriminator(), expected);
+        assert_ne!(GenesisEvent::discriminator(), GenesisCreatedEvent::discriminator());
+    }
+
+    #[test]
+    fn test_decode_log_attributes_cpi_events() {
+        let outer = Pubkey::new_unique();
+        let inner = Pubkey::new_unique();
+
+        let minted = GenomeMintedEvent {
+            genome_hash: [7u8; 32],
+            recipient: Pubkey::new_unique(),
+            amount: 1_000,
+            complexity: 80,
+            uniqueness: 60,
+            entropy: 512,
+            slot: 42,
+        };
+        let sold = ListingSoldEvent {
+            listing: Pubkey::new_unique(),
+            seller: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            genome_id: 3,
+            amount: 10,
+            total_price: 500,
+            fee: 5,
+            remaining: 0,
+            timestamp: 1_700_000_000,
+        };
+
+        // rsm-token logs discriminator and body as separate slices, Anchor as one
+        let body = minted.try_to_vec().unwrap();
+        let logs = vec![
+            format!("Program {} invoke [1]", outer),
+            format!("Program {} invoke [2]", inner),
+            data_line(&[&GenomeMintedEvent::discriminator(), &body]),
+            format!("Program {} success", inner),
+            "Program log: Instruction: Buy".to_string(),
+            data_line(&[&sold.to_log_bytes()]),
+            data_line(&[&[0u8; 16]]),
+            format!("Program {} success", outer),
+        ];
+
+        let events = decode_log(&logs).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].program_id, Some(inner));
+        assert_eq!(events[0].event, RsmEvent::GenomeMintedEvent(minted));
+        assert_eq!(events[1].program_id, Some(outer));
+        assert_eq!(events[1].event.name(), "ListingSoldEvent");
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_body() {
+        let mut data = SnapshotTakenEvent::discriminator().to_vec();
+        data.extend_from_slice(&[1, 2, 3]);
+        assert!(matches!(
+            RsmEvent::decode(&data),
+            Err(EventError::Layout { name: "SnapshotTakenEvent", .. })
+        ));
+    }
+}
@@ -0,0 +1,466 @@
+//! Deterministic replay of genesis-engine trait derivation.
+//!
+//! `GenesisRecord` and the derivation functions mirror genesis-engine. A
+//! record is replayed from its stored entropy, creator, parents, creation
+//! slot and rarity tables, and the result is compared against the traits
+//! the record stores and against the dna-token genome it registered. The
+//! trait vector is replayed too when the parent genomes are supplied; the
+//! catalog mutations the record lists are applied on top, as recorded, and
+//! consciousness loses the consanguinity penalty the record carries.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use rsm_core::{CatalogMutation, DominanceModel, TraitVector, RARITY_TIERS, TRAIT_COUNT};
+use serde::Serialize;
+use solana_sdk::{
+    hash::{hash, hashv},
+    pubkey::Pubkey,
+};
+use std::fmt::Debug;
+use thiserror::Error;
+
+pub const DNA_GENOME_SEED: &[u8] = b"genome";
+
+#[derive(Error, Debug)]
+pub enum VerifyError {
+    #[error("Account is not a {0}")]
+    Discriminator(&'static str),
+    #[error("Failed to decode {0}: {1}")]
+    Decode(&'static str, std::io::Error),
+}
+
+/// Mirror of genesis-engine's `Rarity`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Rare,
+    Epic,
+    Mythic,
+}
+
+impl Rarity {
+    pub fn from_index(index: usize) -> Self {
+        match index {
+            1 => Rarity::Rare,
+            2 => Rarity::Epic,
+            3 => Rarity::Mythic,
+            _ => Rarity::Common,
+        }
+    }
+}
+
+/// Mirror of genesis-engine's `GenesisRecord` account.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenesisRecord {
+    pub genome_id: u64,
+    pub creator: Pubkey,
+    pub parent_genomes: Vec<u64>,
+    pub rna_burned: u64,
+    pub consciousness: u8,
+    pub created_at: i64,
+    pub entropy_seed: [u8; 32],
+    pub rarity: Rarity,
+    pub rna_multiplier: u16,
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub matures_at_slot: u64,
+    pub species: u16,
+    pub created_slot: u64,
+    pub rarity_weights: [u16; 4],
+    pub rarity_multipliers: [u16; 4],
+    pub traits: TraitVector,
+    pub trait_dominance: [u8; TRAIT_COUNT],
+    pub mutation_bps: u16,
+    pub mutations: Vec<ReceivedMutation>,
+    pub consciousness_penalty: u8,
+    pub total_supply: u64,
+    pub genesis_event: Option<u64>,
+}
+
+/// Mirror of genesis-engine's `ReceivedMutation`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReceivedMutation {
+    pub id: u16,
+    pub trait_deltas: [i8; TRAIT_COUNT],
+}
+
+/// Mirror of dna-token's zero-copy `GenomeV2` account. The layout has no
+/// padding between fields, so Borsh reads it as is.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DnaGenome {
+    pub genome_id: u64,
+    pub total_supply: u64,
+    pub market_supply: u64,
+    pub founder_supply: u64,
+    pub created_at: i64,
+    pub minted_at: i64,
+    pub matures_at_slot: u64,
+    pub circulating_supply: u64,
+    pub total_burned: u64,
+    pub created_slot: u64,
+    pub mint_events: u32,
+    pub rna_multiplier: u16,
+    pub species: u16,
+    pub sequence_hash: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub metadata_uri_hash: [u8; 32],
+    pub founder: Pubkey,
+    pub mint: Pubkey,
+    pub name: [u8; 32],
+    pub blockchain: [u8; 8],
+    pub traits: TraitVector,
+    pub consciousness: u8,
+    pub minted: u8,
+    pub retired: u8,
+    pub indexed: u8,
+    pub metadata_corrections: u8,
+    pub padding: [u8; 3],
+    pub content_hash: [u8; 32],
+    pub holding_cap_slots: u64,
+    pub minted_slot: u64,
+    pub holding_cap_bps: u16,
+    pub holding_cap_padding: [u8; 6],
+}
+
+impl DnaGenome {
+    /// The name up to its zero padding.
+    pub fn name(&self) -> &str {
+        let len = self.name.iter().position(|&byte| byte == 0).unwrap_or(self.name.len());
+        std::str::from_utf8(&self.name[..len]).unwrap_or_default()
+    }
+}
+
+/// Anchor account discriminator: sha256("account:<name>")[..8].
+pub fn account_discriminator(name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("account:{}", name).as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Decode an Anchor account, ignoring the zero padding after the data.
+pub fn decode_account<T: BorshDeserialize>(name: &'static str, data: &[u8]) -> Result<T, VerifyError> {
+    if data.len() < 8 || data[..8] != account_discriminator(name) {
+        return Err(VerifyError::Discriminator(name));
+    }
+    T::deserialize(&mut &data[8..]).map_err(|err| VerifyError::Decode(name, err))
+}
+
+pub fn dna_genome_address(dna_program: &Pubkey, genome_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[DNA_GENOME_SEED, &genome_id.to_le_bytes()], dna_program).0
+}
+
+pub fn derive_consciousness(parent_genome_ids: &[u64]) -> u8 {
+    if !parent_genome_ids.is_empty() {
+        50
+    } else {
+        0
+    }
+}
+
+pub fn rarity_roll(entropy_seed: &[u8; 32], creator: &Pubkey, genome_id: u64, slot: u64) -> u64 {
+    let roll_hash = hashv(&[
+        entropy_seed,
+        creator.as_ref(),
+        &genome_id.to_le_bytes(),
+        &slot.to_le_bytes(),
+    ]);
+    let mut roll_bytes = [0u8; 8];
+    roll_bytes.copy_from_slice(&roll_hash.to_bytes()[..8]);
+    u64::from_le_bytes(roll_bytes)
+}
+
+pub fn roll_rarity(roll: u64, weights: &[u16; RARITY_TIERS]) -> Rarity {
+    Rarity::from_index(rsm_core::rarity_tier(roll, weights) as usize)
+}
+
+pub fn trait_seed(entropy_seed: &[u8; 32], creator: &Pubkey, genome_id: u64, slot: u64) -> [u8; 32] {
+    hashv(&[
+        b"traits",
+        entropy_seed,
+        creator.as_ref(),
+        &genome_id.to_le_bytes(),
+        &slot.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// The child trait vector, from the parent genomes in `parent_genomes` order.
+pub fn derive_trait_vector(record: &GenesisRecord, parents: &[DnaGenome]) -> TraitVector {
+    let alleles: Vec<TraitVector> = parents.iter().map(|parent| parent.traits).collect();
+    let model = DominanceModel {
+        dominance: record.trait_dominance,
+        mutation_bps: record.mutation_bps,
+    };
+    let seed = trait_seed(&record.entropy_seed, &record.creator, record.genome_id, record.created_slot);
+    let mutations: Vec<CatalogMutation> = record
+        .mutations
+        .iter()
+        .map(|mutation| CatalogMutation { id: mutation.id, deltas: mutation.trait_deltas, weight_bps: 0 })
+        .collect();
+    rsm_core::apply_mutations(rsm_core::recombine(&alleles, &model, &seed), &mutations)
+}
+
+/// Traits a genesis record must carry, recomputed from its inputs.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct DerivedTraits {
+    pub consciousness: u8,
+    pub rarity: Rarity,
+    pub rna_multiplier: u16,
+    /// Only replayed when the parent genomes are supplied
+    pub traits: Option<TraitVector>,
+}
+
+pub fn derive_traits(record: &GenesisRecord, parents: Option<&[DnaGenome]>) -> DerivedTraits {
+    let roll = rarity_roll(
+        &record.entropy_seed,
+        &record.creator,
+        record.genome_id,
+        record.created_slot,
+    );
+    let rarity = roll_rarity(roll, &record.rarity_weights);
+    DerivedTraits {
+        consciousness: derive_consciousness(&record.parent_genomes).saturating_sub(record.consciousness_penalty),
+        rarity,
+        rna_multiplier: record.rarity_multipliers[rarity as usize],
+        traits: parents.map(|parents| derive_trait_vector(record, parents)),
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    /// `record.<field>` or `genome.<field>`
+    pub field: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Check {
+    fn new<T: Debug + PartialEq>(field: &'static str, expected: T, actual: T) -> Self {
+        Check {
+            field,
+            expected: format!("{:?}", expected),
+            actual: format!("{:?}", actual),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Report {
+    pub genome_id: u64,
+    pub derived: DerivedTraits,
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    pub fn is_valid(&self) -> bool {
+        self.checks.iter().all(Check::passed)
+    }
+}
+
+/// Replay `record` and compare against its stored traits and, when given,
+/// the dna-token genome registered for it and its parent genomes.
+pub fn verify(record: &GenesisRecord, genome: Option<&DnaGenome>, parents: Option<&[DnaGenome]>) -> Report {
+    let derived = derive_traits(record, parents);
+    let mut checks = vec![
+        Check::new("record.consciousness", derived.consciousness, record.consciousness),
+        Check::new("record.rarity", derived.rarity, record.rarity),
+        Check::new("record.rna_multiplier", derived.rna_multiplier, record.rna_multiplier),
+    ];
+    if let Some(traits) = derived.traits {
+        checks.push(Check::new("record.traits", traits, record.traits));
+    }
+
+    if let Some(genome) = genome {
+        checks.extend([
+            Check::new("genome.genome_id", record.genome_id, genome.genome_id),
+            Check::new("genome.consciousness", derived.consciousness, genome.consciousness),
+            Check::new("genome.rna_multiplier", derived.rna_multiplier, genome.rna_multiplier),
+            Check::new("genome.species", record.species, genome.species),
+            Check::new("genome.name", record.name.as_str(), genome.name()),
+            Check::new("genome.metadata_uri_hash", record.metadata_uri_hash, genome.metadata_uri_hash),
+            Check::new("genome.matures_at_slot", record.matures_at_slot, genome.matures_at_slot),
+            Check::new("genome.founder", record.creator, genome.founder),
+            Check::new("genome.traits", record.traits, genome.traits),
+        ]);
+    }
+
+    Report {
+        genome_id: record.genome_id,
+        derived,
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn honest_record() -> GenesisRecord {
+        let mut record = GenesisRecord {
+            genome_id: 100_042,
+            creator: Pubkey::new_unique(),
+            parent_genomes: vec![7, 19],
+            rna_burned: 1_000,
+            consciousness: 0,
+            created_at: 1_700_000_000,
+            entropy_seed: [9u8; 32],
+            rarity: Rarity::Common,
+            rna_multiplier: 0,
+            name: "Helix".to_string(),
+            metadata_uri_hash: [3u8; 32],
+            matures_at_slot: 500_000,
+            species: 2,
+            created_slot: 68_000,
+            rarity_weights: [7000, 2200, 700, 100],
+            rarity_multipliers: [100, 150, 250, 500],
+            traits: [0u8; TRAIT_COUNT],
+            trait_dominance: [191; TRAIT_COUNT],
+            mutation_bps: 100,
+            mutations: Vec::new(),
+            consciousness_penalty: 0,
+            total_supply: 1_000_000,
+            genesis_event: None,
+        };
+        let derived = derive_traits(&record, Some(&parents()));
+        record.consciousness = derived.consciousness;
+        record.rarity = derived.rarity;
+        record.rna_multiplier = derived.rna_multiplier;
+        record.traits = derived.traits.unwrap();
+        record
+    }
+
+    fn genome(genome_id: u64, blockchain: &[u8], traits: TraitVector) -> DnaGenome {
+        let mut tag = [0u8; 8];
+        tag[..blockchain.len()].copy_from_slice(blockchain);
+        DnaGenome {
+            genome_id,
+            total_supply: 1_000_000,
+            market_supply: 857_100,
+            founder_supply: 142_900,
+            created_at: 0,
+            minted_at: 0,
+            matures_at_slot: 0,
+            circulating_supply: 0,
+            total_burned: 0,
+            created_slot: 0,
+            mint_events: 0,
+            rna_multiplier: 100,
+            species: 2,
+            sequence_hash: [0u8; 32],
+            block_hash: [0u8; 32],
+            metadata_uri_hash: [0u8; 32],
+            founder: Pubkey::default(),
+            mint: Pubkey::default(),
+            name: [0u8; 32],
+            blockchain: tag,
+            traits,
+            consciousness: 0,
+            minted: 1,
+            retired: 0,
+            indexed: 0,
+            metadata_corrections: 0,
+            padding: [0u8; 3],
+            content_hash: [0u8; 32],
+            holding_cap_slots: 0,
+            minted_slot: 0,
+            holding_cap_bps: 0,
+            holding_cap_padding: [0u8; 6],
+        }
+    }
+
+    // A founder genome and a bred one
+    fn parents() -> Vec<DnaGenome> {
+        vec![
+            genome(7, b"bitcoin", [12, 34, 56, 78, 90, 112, 134, 156]),
+            genome(19, b"genesis", [40, 80, 120, 160, 200, 240, 20, 60]),
+        ]
+    }
+
+    #[test]
+    fn test_replay_flags_tampered_rarity() {
+        let record = honest_record();
+        let mut data = account_discriminator("GenesisRecord").to_vec();
+        data.extend(record.try_to_vec().unwrap());
+        data.resize(8 + 500, 0);
+        let decoded: GenesisRecord = decode_account("GenesisRecord", &data).unwrap();
+        assert!(verify(&decoded, None, Some(&parents())).is_valid());
+
+        // An inbred child replays with the penalty it was bred under
+        let mut inbred = honest_record();
+        inbred.consciousness_penalty = 20;
+        assert!(!verify(&inbred, None, None).is_valid());
+        inbred.consciousness -= 20;
+        assert!(verify(&inbred, None, None).is_valid());
+
+        let mut tampered = decoded;
+        tampered.rarity = if tampered.rarity == Rarity::Mythic { Rarity::Common } else { Rarity::Mythic };
+        let report = verify(&tampered, None, None);
+        assert!(!report.is_valid());
+        let failed: Vec<_> = report.checks.iter().filter(|c| !c.passed()).map(|c| c.field).collect();
+        assert_eq!(failed, vec!["record.rarity"]);
+
+        assert!(matches!(
+            decode_account::<GenesisRecord>("Genome", &data),
+            Err(VerifyError::Discriminator("Genome"))
+        ));
+    }
+
+    #[test]
+    fn test_genome_must_match_record() {
+        let record = honest_record();
+        let mut genome = genome(record.genome_id, b"genesis", record.traits);
+        genome.consciousness = record.consciousness;
+        genome.rna_multiplier = record.rna_multiplier;
+        genome.species = record.species;
+        genome.name[..record.name.len()].copy_from_slice(record.name.as_bytes());
+        genome.metadata_uri_hash = record.metadata_uri_hash;
+        genome.founder = record.creator;
+        genome.matures_at_slot = record.matures_at_slot;
+        assert!(verify(&record, Some(&genome), None).is_valid());
+
+        // Decodes from the zero-copy account data
+        let mut data = account_discriminator("GenomeV2").to_vec();
+        data.extend(genome.try_to_vec().unwrap());
+        assert_eq!(data.len(), 8 + 360);
+        assert_eq!(decode_account::<DnaGenome>("GenomeV2", &data).unwrap(), genome);
+
+        genome.rna_multiplier += 1;
+        let report = verify(&record, Some(&genome), None);
+        let failed: Vec<_> = report.checks.iter().filter(|c| !c.passed()).map(|c| c.field).collect();
+        assert_eq!(failed, vec!["genome.rna_multiplier"]);
+    }
+
+    #[test]
+    fn test_replay_trait_vector() {
+        let parents = parents();
+        let mut tampered = honest_record();
+        tampered.traits[0] = tampered.traits[0].wrapping_add(1);
+        let report = verify(&tampered, None, Some(&parents));
+        let failed: Vec<_> = report.checks.iter().filter(|c| !c.passed()).map(|c| c.field).collect();
+        assert_eq!(failed, vec!["record.traits"]);
+
+        // A changed dominance model yields a different child
+        let mut record = honest_record();
+        record.trait_dominance = [0; TRAIT_COUNT];
+        record.mutation_bps = 0;
+        let recessive = derive_trait_vector(&record, &parents);
+        record.trait_dominance = [255; TRAIT_COUNT];
+        let dominant = derive_trait_vector(&record, &parents);
+        let alleles = [parents[0].traits, parents[1].traits];
+        for i in 0..TRAIT_COUNT {
+            assert_eq!(recessive[i], alleles[0][i].min(alleles[1][i]));
+            assert_eq!(dominant[i], alleles[0][i].max(alleles[1][i]));
+        }
+
+        // Recorded catalog mutations shift the replayed child
+        let mut trait_deltas = [0i8; TRAIT_COUNT];
+        trait_deltas[2] = -128;
+        record.mutations = vec![ReceivedMutation { id: 3, trait_deltas }];
+        let mutated = derive_trait_vector(&record, &parents);
+        assert_eq!(mutated[2], dominant[2].saturating_sub(128));
+        assert_eq!(mutated[..2], dominant[..2]);
+    }
+}
@@ -0,0 +1,89 @@
+//! rsm-genesis-verify: replays a genesis record's trait derivation.
+//!
+//! The record is read from RPC by pubkey, or from a raw account dump
+//! (`solana account <pubkey> --output-file <path>`). Exits with status 1
+//! when any derived trait disagrees with what is stored on chain.
+
+use clap::Parser;
+use rsm_genesis_verify::{decode_account, dna_genome_address, verify, DnaGenome, GenesisRecord};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::{error::Error, fs, path::PathBuf, process};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser, Debug)]
+#[command(name = "rsm-genesis-verify", about = "Replay genesis-engine trait derivation for a genome")]
+struct Args {
+    /// GenesisRecord account to fetch
+    #[arg(required_unless_present = "file", conflicts_with = "file")]
+    record: Option<Pubkey>,
+    /// Raw GenesisRecord account data instead of fetching `record`
+    #[arg(long)]
+    file: Option<PathBuf>,
+    #[arg(long, default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+    /// Also compare against the genome registered with this dna-token
+    /// program, and replay the trait vector from its parent genomes
+    #[arg(long)]
+    dna_program: Option<Pubkey>,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let rpc = RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::confirmed());
+
+    let data = match (&args.file, &args.record) {
+        (Some(path), _) => fs::read(path)?,
+        (None, Some(record)) => rpc.get_account_data(record)?,
+        (None, None) => unreachable!("clap requires a record or --file"),
+    };
+    let record: GenesisRecord = decode_account("GenesisRecord", &data)?;
+
+    let fetch_genome = |dna_program: &Pubkey, genome_id: u64| -> Result<DnaGenome> {
+        let data = rpc.get_account_data(&dna_genome_address(dna_program, genome_id))?;
+        Ok(decode_account::<DnaGenome>("GenomeV2", &data)?)
+    };
+    let (genome, parents) = match args.dna_program {
+        Some(dna_program) => {
+            let parents = record
+                .parent_genomes
+                .iter()
+                .map(|parent_id| fetch_genome(&dna_program, *parent_id))
+                .collect::<Result<Vec<_>>>()?;
+            (Some(fetch_genome(&dna_program, record.genome_id)?), Some(parents))
+        }
+        None => (None, None),
+    };
+
+    let report = verify(&record, genome.as_ref(), parents.as_deref());
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "genome {}: replayed {:?}, consciousness {}, multiplier {}",
+            report.genome_id,
+            report.derived.rarity,
+            report.derived.consciousness,
+            report.derived.rna_multiplier
+        );
+        for check in &report.checks {
+            if check.passed() {
+                println!("  ok        {} = {}", check.field, check.actual);
+            } else {
+                println!(
+                    "  MISMATCH  {}: expected {}, found {}",
+                    check.field, check.expected, check.actual
+                );
+            }
+        }
+    }
+
+    if !report.is_valid() {
+        process::exit(1);
+    }
+    Ok(())
+}
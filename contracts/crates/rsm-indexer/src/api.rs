@@ -0,0 +1,115 @@
+//! HTTP routes over the latest `Index` snapshot.
+//!
+//! List endpoints take `offset` and `limit` (at most `MAX_PAGE_SIZE`) and
+//! answer with a `Page`; `/genomes` and `/genesis/leaderboard` also filter
+//! by `consciousness_min` / `consciousness_max`, and `/genomes` and
+//! `/emissions` by `finality`. `/status` reports the snapshot's slots.
+
+use crate::{parse_hash, EmissionQuery, GenomeQuery, Index, LeaderboardQuery};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde_json::json;
+use std::sync::{Arc, RwLock};
+
+pub type SharedIndex = Arc<RwLock<Index>>;
+
+pub fn router(index: SharedIndex) -> Router {
+    Router::new()
+        .route("/genomes", get(genomes))
+        .route("/genomes/:hash", get(genome))
+        .route("/emissions", get(emissions))
+        .route("/genesis/leaderboard", get(leaderboard))
+        .route("/status", get(status))
+        .with_state(index)
+}
+
+#[derive(Debug)]
+pub enum ApiError {
+    BadHash,
+    NotFound,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::BadHash => (StatusCode::BAD_REQUEST, "genome hash must be 64 hex characters"),
+            ApiError::NotFound => (StatusCode::NOT_FOUND, "genome not indexed"),
+        };
+        (status, Json(json!({ "error": message }))).into_response()
+    }
+}
+
+fn read(index: &SharedIndex) -> std::sync::RwLockReadGuard<'_, Index> {
+    // A panicked refresh leaves the previous snapshot intact
+    index.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+async fn genomes(State(index): State<SharedIndex>, Query(query): Query<GenomeQuery>) -> Response {
+    Json(read(&index).genomes(&query)).into_response()
+}
+
+async fn genome(State(index): State<SharedIndex>, Path(hash): Path<String>) -> Result<Response, ApiError> {
+    let hash = parse_hash(&hash).ok_or(ApiError::BadHash)?;
+    let index = read(&index);
+    let genome = index.genome(&hash).ok_or(ApiError::NotFound)?;
+    Ok(Json(genome).into_response())
+}
+
+async fn emissions(State(index): State<SharedIndex>, Query(query): Query<EmissionQuery>) -> Response {
+    Json(read(&index).emissions(&query)).into_response()
+}
+
+async fn leaderboard(State(index): State<SharedIndex>, Query(query): Query<LeaderboardQuery>) -> Response {
+    Json(read(&index).leaderboard(&query)).into_response()
+}
+
+async fn status(State(index): State<SharedIndex>) -> Response {
+    Json(read(&index).status()).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::sample, to_hex};
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    async fn call(uri: &str) -> (StatusCode, Value) {
+        let app = router(Arc::new(RwLock::new(sample())));
+        let response = app.oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_routes() {
+        let (status, page) = call("/genomes?consciousness_min=30&consciousness_max=95&limit=1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!((page["total"].as_u64(), page["items"].as_array().unwrap().len()), (Some(2), 1));
+
+        let (status, genome) = call(&format!("/genomes/{}", to_hex(&[2; 32]))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(genome["consciousness"], 90);
+        assert_eq!(call(&format!("/genomes/{}", to_hex(&[9; 32]))).await.0, StatusCode::NOT_FOUND);
+        assert_eq!(call("/genomes/xyz").await.0, StatusCode::BAD_REQUEST);
+
+        let (_, emissions) = call("/emissions?epoch=2").await;
+        assert_eq!(emissions["items"][0]["amount"], 300);
+        let (_, board) = call("/genesis/leaderboard?offset=1").await;
+        assert_eq!(board["items"][0]["rank"], 2);
+
+        let (_, settled) = call("/emissions?finality=finalized").await;
+        assert_eq!(settled["total"], 2);
+        assert_eq!(call(&format!("/genomes/{}", to_hex(&[3; 32]))).await.1["finality"], "confirmed");
+        let (_, status) = call("/status").await;
+        assert_eq!((status["slots"]["confirmed"].as_u64(), status["finalized_genomes"].as_u64()), (Some(1_000_000), Some(2)));
+    }
+}
@@ -0,0 +1,503 @@
+//! rsm-indexer: serves genomes, emissions and the genesis leaderboard over
+//! HTTP so clients do not scan program accounts themselves.
+//!
+//! `source` snapshots rsm-token genomes, the consciousness oracle's
+//! canonical scores and genesis-engine records into an `Index`, which the
+//! daemon rebuilds on a poll interval. `api` answers queries from the
+//! latest snapshot.
+//!
+//! Each snapshot records the cluster's processed, confirmed and finalized
+//! slots, and every genome is tagged with whether its mint is finalized. A
+//! confirmed mint that the next snapshot no longer holds was dropped by a
+//! fork; `Index::follow` counts those so stats never silently shrink.
+
+pub mod api;
+pub mod source;
+
+use rsm_genesis_verify::{GenesisRecord, Rarity};
+use rsm_token::{CanonicalScore, GenomeData};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use thiserror::Error;
+
+/// Epoch length used to bucket emissions, as on mainnet-beta.
+pub const SLOTS_PER_EPOCH: u64 = 432_000;
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+pub const MAX_PAGE_SIZE: usize = 500;
+
+#[derive(Error, Debug)]
+pub enum IndexerError {
+    #[error("RPC request failed: {0}")]
+    Rpc(#[from] Box<solana_client::client_error::ClientError>),
+    #[error("RPC node is at slot {slot}, behind the served snapshot at slot {min}")]
+    Behind { slot: u64, min: u64 },
+}
+
+/// How settled a mint is. A `Confirmed` mint can still be rolled back by a
+/// fork until the cluster finalizes its slot.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Finality {
+    Confirmed,
+    Finalized,
+}
+
+/// The cluster's newest slot at each commitment level when a snapshot was
+/// taken.
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SlotLevels {
+    pub processed: u64,
+    pub confirmed: u64,
+    pub finalized: u64,
+}
+
+impl SlotLevels {
+    pub fn finality(&self, slot: u64) -> Finality {
+        if slot <= self.finalized {
+            Finality::Finalized
+        } else {
+            Finality::Confirmed
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct GenomeView {
+    /// Hex-encoded genome hash
+    pub hash: String,
+    pub owner: String,
+    pub tokens_minted: u64,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub entropy: u16,
+    pub mint_slot: u64,
+    pub mint_timestamp: i64,
+    /// Whether `mint_slot` was finalized when the snapshot was taken
+    pub finality: Finality,
+    /// From the oracle's canonical score; `None` for unscored genomes
+    pub consciousness: Option<u8>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct EpochEmission {
+    pub epoch: u64,
+    pub genomes: u64,
+    pub amount: u64,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreatorRank {
+    pub rank: usize,
+    pub creator: String,
+    pub geneses: u64,
+    pub rna_burned: u64,
+    pub best_rarity: Rarity,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Page<T> {
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub items: Vec<T>,
+}
+
+impl<T: Clone> Page<T> {
+    fn of<'a>(items: impl Iterator<Item = &'a T>, offset: Option<usize>, limit: Option<usize>) -> Self
+    where
+        T: 'a,
+    {
+        let offset = offset.unwrap_or(0);
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let items: Vec<&T> = items.collect();
+        Self {
+            total: items.len(),
+            offset,
+            limit,
+            items: items.into_iter().skip(offset).take(limit).cloned().collect(),
+        }
+    }
+}
+
+/// Inclusive consciousness bounds; unscored items only pass an open range.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConsciousnessRange {
+    pub consciousness_min: Option<u8>,
+    pub consciousness_max: Option<u8>,
+}
+
+impl ConsciousnessRange {
+    pub fn contains(&self, consciousness: Option<u8>) -> bool {
+        if self.consciousness_min.is_none() && self.consciousness_max.is_none() {
+            return true;
+        }
+        consciousness.is_some_and(|value| {
+            self.consciousness_min.is_none_or(|min| value >= min)
+                && self.consciousness_max.is_none_or(|max| value <= max)
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct GenomeQuery {
+    pub consciousness_min: Option<u8>,
+    pub consciousness_max: Option<u8>,
+    pub owner: Option<String>,
+    /// Only mints at least this settled
+    pub finality: Option<Finality>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct EmissionQuery {
+    pub epoch: Option<u64>,
+    /// Only mints at least this settled
+    pub finality: Option<Finality>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct LeaderboardQuery {
+    pub consciousness_min: Option<u8>,
+    pub consciousness_max: Option<u8>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct IndexStatus {
+    pub slots: SlotLevels,
+    pub genomes: usize,
+    pub finalized_genomes: usize,
+    pub rolled_back: u64,
+}
+
+/// Finalized genomes minted in a slot range, with their emissions.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Backfill {
+    pub slots: SlotLevels,
+    pub from_slot: u64,
+    pub to_slot: u64,
+    pub genomes: Vec<GenomeView>,
+    pub emissions: Vec<EpochEmission>,
+}
+
+/// A snapshot of everything the API serves, ordered by genome hash.
+#[derive(Debug, Default)]
+pub struct Index {
+    pub levels: SlotLevels,
+    /// Unfinalized mints that forks dropped, counted across snapshots
+    pub rolled_back: u64,
+    genomes: BTreeMap<[u8; 32], GenomeView>,
+    geneses: Vec<GenesisRecord>,
+}
+
+impl Index {
+    /// Only minted genomes are indexed; placeholders and burned genomes
+    /// hold no tokens.
+    pub fn build(levels: SlotLevels, genomes: Vec<GenomeData>, scores: Vec<CanonicalScore>, geneses: Vec<GenesisRecord>) -> Self {
+        let scores: HashMap<[u8; 32], u8> =
+            scores.into_iter().map(|score| (score.genome_hash, score.consciousness)).collect();
+        let genomes = genomes
+            .into_iter()
+            .filter(|genome| genome.is_minted)
+            .map(|genome| {
+                let view = GenomeView {
+                    hash: to_hex(&genome.hash),
+                    owner: genome.owner.to_string(),
+                    tokens_minted: genome.tokens_minted,
+                    complexity: genome.complexity,
+                    uniqueness: genome.uniqueness,
+                    entropy: genome.entropy,
+                    mint_slot: genome.mint_slot,
+                    mint_timestamp: genome.mint_timestamp,
+                    finality: levels.finality(genome.mint_slot),
+                    consciousness: scores.get(&genome.hash).copied(),
+                };
+                (genome.hash, view)
+            })
+            .collect();
+        Self { levels, rolled_back: 0, genomes, geneses }
+    }
+
+    /// Carries the rollback count over from `previous`, the snapshot this
+    /// one replaces, adding the unfinalized mints it held that are now gone
+    /// or were minted again at another slot. Returns their hashes. A burn
+    /// before finalization is counted too; it cannot be told apart.
+    pub fn follow(&mut self, previous: &Index) -> Vec<String> {
+        let dropped: Vec<String> = previous
+            .genomes
+            .iter()
+            .filter(|(hash, view)| {
+                view.finality == Finality::Confirmed
+                    && self.genomes.get(*hash).is_none_or(|now| now.mint_slot != view.mint_slot)
+            })
+            .map(|(_, view)| view.hash.clone())
+            .collect();
+        self.rolled_back = previous.rolled_back + dropped.len() as u64;
+        dropped
+    }
+
+    pub fn status(&self) -> IndexStatus {
+        IndexStatus {
+            slots: self.levels,
+            genomes: self.genomes.len(),
+            finalized_genomes: self.genomes.values().filter(|genome| genome.finality == Finality::Finalized).count(),
+            rolled_back: self.rolled_back,
+        }
+    }
+
+    pub fn genomes(&self, query: &GenomeQuery) -> Page<GenomeView> {
+        let range = ConsciousnessRange {
+            consciousness_min: query.consciousness_min,
+            consciousness_max: query.consciousness_max,
+        };
+        let matches = self.genomes.values().filter(|genome| {
+            range.contains(genome.consciousness)
+                && query.owner.as_ref().is_none_or(|owner| genome.owner == *owner)
+                && query.finality.is_none_or(|wanted| genome.finality >= wanted)
+        });
+        Page::of(matches, query.offset, query.limit)
+    }
+
+    pub fn genome(&self, hash: &[u8; 32]) -> Option<&GenomeView> {
+        self.genomes.get(hash)
+    }
+
+    /// Tokens still held against genomes minted in each epoch, so clawed
+    /// back and burned amounts no longer count.
+    pub fn emissions(&self, query: &EmissionQuery) -> Page<EpochEmission> {
+        let epochs = epoch_emissions(self.genomes.values().filter(|genome| {
+            query.epoch.is_none_or(|wanted| wanted == genome.mint_slot / SLOTS_PER_EPOCH)
+                && query.finality.is_none_or(|wanted| genome.finality >= wanted)
+        }));
+        Page::of(epochs.iter(), query.offset, query.limit)
+    }
+
+    /// For consumers re-seeding their stats over a gap: only finalized
+    /// mints are included, so the result cannot be rolled back later.
+    pub fn backfill(&self, from_slot: u64, to_slot: u64) -> Backfill {
+        let genomes: Vec<GenomeView> = self
+            .genomes
+            .values()
+            .filter(|genome| {
+                genome.finality == Finality::Finalized && (from_slot..=to_slot).contains(&genome.mint_slot)
+            })
+            .cloned()
+            .collect();
+        Backfill { slots: self.levels, from_slot, to_slot, emissions: epoch_emissions(genomes.iter()), genomes }
+    }
+
+    /// Creators by number of geneses, then RNA burned.
+    pub fn leaderboard(&self, query: &LeaderboardQuery) -> Page<CreatorRank> {
+        let range = ConsciousnessRange {
+            consciousness_min: query.consciousness_min,
+            consciousness_max: query.consciousness_max,
+        };
+        let mut creators: HashMap<String, CreatorRank> = HashMap::new();
+        for record in self.geneses.iter().filter(|record| range.contains(Some(record.consciousness))) {
+            let creator = record.creator.to_string();
+            let entry = creators.entry(creator.clone()).or_insert(CreatorRank {
+                rank: 0,
+                creator,
+                geneses: 0,
+                rna_burned: 0,
+                best_rarity: Rarity::Common,
+            });
+            entry.geneses += 1;
+            entry.rna_burned = entry.rna_burned.saturating_add(record.rna_burned);
+            if (record.rarity as u8) > (entry.best_rarity as u8) {
+                entry.best_rarity = record.rarity;
+            }
+        }
+        let mut ranked: Vec<CreatorRank> = creators.into_values().collect();
+        ranked.sort_by(|a, b| {
+            (b.geneses, b.rna_burned).cmp(&(a.geneses, a.rna_burned)).then_with(|| a.creator.cmp(&b.creator))
+        });
+        for (position, entry) in ranked.iter_mut().enumerate() {
+            entry.rank = position + 1;
+        }
+        Page::of(ranked.iter(), query.offset, query.limit)
+    }
+}
+
+fn epoch_emissions<'a>(genomes: impl Iterator<Item = &'a GenomeView>) -> Vec<EpochEmission> {
+    let mut epochs: BTreeMap<u64, EpochEmission> = BTreeMap::new();
+    for genome in genomes {
+        let epoch = genome.mint_slot / SLOTS_PER_EPOCH;
+        let entry = epochs.entry(epoch).or_insert(EpochEmission { epoch, genomes: 0, amount: 0 });
+        entry.genomes += 1;
+        entry.amount = entry.amount.saturating_add(genome.tokens_minted);
+    }
+    epochs.into_values().collect()
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn parse_hash(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut hash = [0u8; 32];
+    for (byte, pair) in hash.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(hash)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use borsh::BorshDeserialize;
+    use solana_sdk::pubkey::Pubkey;
+
+    pub(crate) fn genome(seed: u8, owner: Pubkey, amount: u64, slot: u64) -> GenomeData {
+        let mut genome = GenomeData::try_from_slice(&[0u8; GenomeData::LEN]).unwrap();
+        genome.hash = [seed; 32];
+        genome.owner = owner;
+        genome.tokens_minted = amount;
+        genome.mint_slot = slot;
+        genome.is_minted = true;
+        genome
+    }
+
+    pub(crate) fn score(seed: u8, consciousness: u8) -> CanonicalScore {
+        CanonicalScore {
+            genome_hash: [seed; 32],
+            consciousness,
+            complexity: 0,
+            uniqueness: 0,
+            submissions: 3,
+            finalized_at: 0,
+            bump: 255,
+        }
+    }
+
+    pub(crate) fn genesis(creator: Pubkey, rna_burned: u64, consciousness: u8, rarity: Rarity) -> GenesisRecord {
+        GenesisRecord {
+            genome_id: 0,
+            creator,
+            parent_genomes: Vec::new(),
+            rna_burned,
+            consciousness,
+            created_at: 0,
+            entropy_seed: [0; 32],
+            rarity,
+            rna_multiplier: 100,
+            name: String::new(),
+            metadata_uri_hash: [0; 32],
+            matures_at_slot: 0,
+            species: 0,
+            created_slot: 0,
+            rarity_weights: [0; 4],
+            rarity_multipliers: [0; 4],
+            traits: [0; 8],
+            trait_dominance: [0; 8],
+            mutation_bps: 0,
+            mutations: Vec::new(),
+            consciousness_penalty: 0,
+            total_supply: 1_000_000,
+            genesis_event: None,
+        }
+    }
+
+    pub(crate) fn sample() -> Index {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut burned = genome(4, alice, 0, 10);
+        burned.is_minted = false;
+        Index::build(
+            SlotLevels { processed: 1_000_002, confirmed: 1_000_000, finalized: SLOTS_PER_EPOCH * 2 - 1 },
+            vec![
+                genome(1, alice, 100, 10),
+                genome(2, bob, 200, SLOTS_PER_EPOCH + 5),
+                genome(3, alice, 300, SLOTS_PER_EPOCH * 2),
+                burned,
+            ],
+            vec![score(1, 40), score(2, 90)],
+            vec![
+                genesis(alice, 1_000, 50, Rarity::Rare),
+                genesis(bob, 500, 80, Rarity::Common),
+                genesis(bob, 700, 20, Rarity::Mythic),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_genome_filters_and_pages() {
+        let index = sample();
+        let all = index.genomes(&GenomeQuery::default());
+        assert_eq!(all.total, 3);
+        assert_eq!(all.items[0].hash, to_hex(&[1; 32]));
+
+        let scored = index.genomes(&GenomeQuery { consciousness_min: Some(50), ..GenomeQuery::default() });
+        assert_eq!(scored.items.iter().map(|g| g.tokens_minted).collect::<Vec<_>>(), vec![200]);
+        let low = index.genomes(&GenomeQuery { consciousness_max: Some(50), ..GenomeQuery::default() });
+        assert_eq!(low.items.len(), 1);
+
+        let owner = all.items[0].owner.clone();
+        let page = index.genomes(&GenomeQuery { owner: Some(owner), offset: Some(1), limit: Some(1), ..GenomeQuery::default() });
+        assert_eq!((page.total, page.items.len()), (2, 1));
+        assert_eq!(page.items[0].tokens_minted, 300);
+        assert!(index.genome(&[4; 32]).is_none());
+        assert_eq!(index.genome(&[2; 32]).unwrap().consciousness, Some(90));
+    }
+
+    #[test]
+    fn test_emissions_by_epoch() {
+        let index = sample();
+        let all = index.emissions(&EmissionQuery::default());
+        assert_eq!(all.items.iter().map(|e| (e.epoch, e.amount)).collect::<Vec<_>>(), vec![(0, 100), (1, 200), (2, 300)]);
+        let one = index.emissions(&EmissionQuery { epoch: Some(1), ..EmissionQuery::default() });
+        assert_eq!(one.items, vec![EpochEmission { epoch: 1, genomes: 1, amount: 200 }]);
+        assert!(index.emissions(&EmissionQuery { epoch: Some(9), ..EmissionQuery::default() }).items.is_empty());
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let index = sample();
+        let board = index.leaderboard(&LeaderboardQuery::default());
+        assert_eq!(board.items.iter().map(|c| (c.rank, c.geneses, c.rna_burned)).collect::<Vec<_>>(), vec![(1, 2, 1_200), (2, 1, 1_000)]);
+        assert_eq!(board.items[0].best_rarity, Rarity::Mythic);
+
+        let filtered = index.leaderboard(&LeaderboardQuery { consciousness_min: Some(50), ..LeaderboardQuery::default() });
+        assert_eq!(filtered.items.iter().map(|c| c.rna_burned).collect::<Vec<_>>(), vec![1_000, 500]);
+        assert_eq!(parse_hash(&to_hex(&[0xAB; 32])), Some([0xAB; 32]));
+        assert_eq!(parse_hash("zz"), None);
+    }
+
+    #[test]
+    fn test_finality_and_rollbacks() {
+        let index = sample();
+        assert_eq!(index.genome(&[3; 32]).unwrap().finality, Finality::Confirmed);
+        let settled = index.genomes(&GenomeQuery { finality: Some(Finality::Finalized), ..GenomeQuery::default() });
+        assert_eq!(settled.items.iter().map(|g| g.tokens_minted).collect::<Vec<_>>(), vec![100, 200]);
+        assert_eq!(index.genomes(&GenomeQuery { finality: Some(Finality::Confirmed), ..GenomeQuery::default() }).total, 3);
+        let emissions = index.emissions(&EmissionQuery { finality: Some(Finality::Finalized), ..EmissionQuery::default() });
+        assert_eq!(emissions.items.iter().map(|e| e.epoch).collect::<Vec<_>>(), vec![0, 1]);
+
+        let backfill = index.backfill(0, SLOTS_PER_EPOCH * 3);
+        assert_eq!(backfill.genomes.len(), 2);
+        assert_eq!(backfill.emissions.iter().map(|e| e.amount).sum::<u64>(), 300);
+        assert!(index.backfill(20, SLOTS_PER_EPOCH).genomes.is_empty());
+
+        // The fork drops genome 3 and re-mints 4 unfinalized; finalized genome 2 is untouched
+        let owner = Pubkey::new_unique();
+        let levels = SlotLevels { processed: 1_000_010, confirmed: 1_000_008, finalized: 999_000 };
+        let mut next = Index::build(
+            levels,
+            vec![genome(1, owner, 100, 10), genome(2, owner, 200, SLOTS_PER_EPOCH + 5), genome(4, owner, 50, 1_000_005)],
+            Vec::new(),
+            Vec::new(),
+        );
+        assert_eq!(next.follow(&index), vec![to_hex(&[3; 32])]);
+        assert_eq!(next.status(), IndexStatus { slots: levels, genomes: 3, finalized_genomes: 2, rolled_back: 1 });
+
+        let mut forked = Index::build(levels, vec![genome(4, owner, 50, 1_000_007)], Vec::new(), Vec::new());
+        assert_eq!(forked.follow(&next), vec![to_hex(&[4; 32])]);
+        assert_eq!(forked.rolled_back, 2);
+    }
+}
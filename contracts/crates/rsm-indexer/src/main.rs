@@ -0,0 +1,119 @@
+//! rsm-indexer daemon: refreshes the index every `--poll-secs` and serves
+//! it over HTTP. Requests are answered from the last complete snapshot
+//! while the next one loads.
+//!
+//! `--commitment confirmed` (the default) serves mints as soon as they are
+//! confirmed and logs the ones a fork later drops; `finalized` only serves
+//! mints that cannot be rolled back. `rsm-indexer backfill` instead prints
+//! the finalized mints of a slot range once, for re-seeding stats over a gap.
+
+use clap::{Parser, Subcommand, ValueEnum};
+use rsm_indexer::{api, source::Source, SlotLevels};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::{
+    error::Error,
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+    thread,
+    time::Duration,
+};
+
+#[derive(Parser, Debug)]
+#[command(name = "rsm-indexer", about = "Serve indexed genomes, emissions and geneses over HTTP")]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(long, default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+    /// rsm-token program
+    #[arg(long)]
+    program: Pubkey,
+    #[arg(long)]
+    mint: Pubkey,
+    /// Consciousness oracle program, for genome consciousness
+    #[arg(long)]
+    oracle_program: Option<Pubkey>,
+    /// genesis-engine program, for the leaderboard
+    #[arg(long)]
+    genesis_program: Option<Pubkey>,
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+    #[arg(long, default_value_t = 30)]
+    poll_secs: u64,
+    /// Commitment accounts are read at
+    #[arg(long, value_enum, default_value_t = Commitment::Confirmed)]
+    commitment: Commitment,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the finalized mints of a slot range and their emissions as JSON
+    Backfill {
+        #[arg(long)]
+        from_slot: u64,
+        #[arg(long)]
+        to_slot: u64,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Commitment {
+    Confirmed,
+    Finalized,
+}
+
+impl From<Commitment> for CommitmentConfig {
+    fn from(commitment: Commitment) -> Self {
+        match commitment {
+            Commitment::Confirmed => CommitmentConfig::confirmed(),
+            Commitment::Finalized => CommitmentConfig::finalized(),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let commitment = match args.command {
+        Some(Command::Backfill { .. }) => Commitment::Finalized,
+        None => args.commitment,
+    };
+    let source = Source {
+        rpc: RpcClient::new_with_commitment(args.rpc_url.clone(), commitment.into()),
+        rsm_program: args.program,
+        mint: args.mint,
+        oracle_program: args.oracle_program,
+        genesis_program: args.genesis_program,
+    };
+
+    if let Some(Command::Backfill { from_slot, to_slot }) = args.command {
+        let index = source.load(&SlotLevels::default())?;
+        println!("{}", serde_json::to_string_pretty(&index.backfill(from_slot, to_slot))?);
+        return Ok(());
+    }
+
+    let index = Arc::new(RwLock::new(source.load(&SlotLevels::default())?));
+    let shared = index.clone();
+    let poll = Duration::from_secs(args.poll_secs);
+    thread::spawn(move || loop {
+        thread::sleep(poll);
+        let served = shared.read().unwrap_or_else(|poisoned| poisoned.into_inner()).levels;
+        match source.load(&served) {
+            Ok(mut snapshot) => {
+                let mut current = shared.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+                let dropped = snapshot.follow(&current);
+                if !dropped.is_empty() {
+                    eprintln!("Fork rolled back {} unfinalized mint(s): {}", dropped.len(), dropped.join(", "));
+                }
+                *current = snapshot;
+            }
+            Err(err) => eprintln!("Refresh failed, serving the previous snapshot: {}", err),
+        }
+    });
+
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    println!("Serving index on http://{}", listener.local_addr()?);
+    axum::serve(listener, api::router(index)).await?;
+    Ok(())
+}
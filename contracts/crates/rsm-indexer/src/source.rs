@@ -0,0 +1,130 @@
+//! Snapshots the indexed programs over RPC.
+
+use crate::{Index, IndexerError, SlotLevels};
+use borsh::BorshDeserialize;
+use rsm_genesis_verify::{decode_account, GenesisRecord};
+use rsm_token::{config_address, genome_address, CanonicalScore, GenomeData};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+};
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+pub struct Source {
+    /// Accounts are read at this client's commitment
+    pub rpc: RpcClient,
+    pub rsm_program: Pubkey,
+    pub mint: Pubkey,
+    /// Consciousness oracle; genomes are unscored without it
+    pub oracle_program: Option<Pubkey>,
+    /// genesis-engine; the leaderboard is empty without it
+    pub genesis_program: Option<Pubkey>,
+}
+
+impl Source {
+    /// Reads the slot levels first and then every account from a bank no
+    /// older than them. `after` holds the served snapshot's levels; a node
+    /// behind it is refused so a lagging RPC cannot roll stats back.
+    pub fn load(&self, after: &SlotLevels) -> Result<Index, IndexerError> {
+        let levels = self.levels()?;
+        let (slot, min) = (self.context_slot(&levels), self.context_slot(after));
+        if slot < min {
+            return Err(IndexerError::Behind { slot, min });
+        }
+        let config = config_address(&self.rsm_program, &self.mint).0;
+        let genomes = self
+            .program_accounts(&self.rsm_program, slot)?
+            .into_iter()
+            .filter_map(|(address, account)| decode_genome(&self.rsm_program, &config, &address, &account.data))
+            .collect();
+        let scores = match self.oracle_program {
+            Some(oracle) => self
+                .program_accounts(&oracle, slot)?
+                .into_iter()
+                .filter_map(|(_, account)| decode_score(&account.data))
+                .collect(),
+            None => Vec::new(),
+        };
+        let geneses = match self.genesis_program {
+            Some(genesis) => self
+                .program_accounts(&genesis, slot)?
+                .into_iter()
+                .filter_map(|(_, account)| decode_account::<GenesisRecord>("GenesisRecord", &account.data).ok())
+                .collect(),
+            None => Vec::new(),
+        };
+        Ok(Index::build(levels, genomes, scores, geneses))
+    }
+
+    fn levels(&self) -> Result<SlotLevels, IndexerError> {
+        let slot = |commitment| self.rpc.get_slot_with_commitment(commitment).map_err(Box::new);
+        Ok(SlotLevels {
+            processed: slot(CommitmentConfig::processed())?,
+            confirmed: slot(CommitmentConfig::confirmed())?,
+            finalized: slot(CommitmentConfig::finalized())?,
+        })
+    }
+
+    fn context_slot(&self, levels: &SlotLevels) -> u64 {
+        if self.rpc.commitment().is_finalized() {
+            levels.finalized
+        } else {
+            levels.confirmed
+        }
+    }
+
+    fn program_accounts(&self, program: &Pubkey, min_context_slot: u64) -> Result<Vec<(Pubkey, Account)>, IndexerError> {
+        let config = RpcProgramAccountsConfig {
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64Zstd),
+                commitment: Some(self.rpc.commitment()),
+                min_context_slot: Some(min_context_slot),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        Ok(self.rpc.get_program_accounts_with_config(program, config).map_err(Box::new)?)
+    }
+}
+
+/// A current-schema genome of `config`; other rsm-token accounts and
+/// genomes still awaiting migration are skipped.
+pub fn decode_genome(program: &Pubkey, config: &Pubkey, address: &Pubkey, data: &[u8]) -> Option<GenomeData> {
+    if GenomeData::SCHEMA.version_of(data) != Ok(GenomeData::SCHEMA.current()) {
+        return None;
+    }
+    let genome = GenomeData::try_from_slice(data).ok()?;
+    (genome_address(program, config, &genome.hash).0 == *address).then_some(genome)
+}
+
+pub fn decode_score(data: &[u8]) -> Option<CanonicalScore> {
+    if data.len() < 8 || data[..8] != CanonicalScore::discriminator() {
+        return None;
+    }
+    CanonicalScore::deserialize(&mut &data[8..]).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{genome, score};
+    use borsh::BorshSerialize;
+
+    #[test]
+    fn test_decode_accounts() {
+        let (program, config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = genome(7, Pubkey::new_unique(), 10, 0);
+        data.schema_version = GenomeData::SCHEMA.current();
+        let bytes = data.try_to_vec().unwrap();
+        let address = genome_address(&program, &config, &[7; 32]).0;
+        assert!(decode_genome(&program, &config, &address, &bytes).is_some());
+        assert!(decode_genome(&program, &Pubkey::new_unique(), &address, &bytes).is_none());
+        assert!(decode_genome(&program, &config, &address, &bytes[1..]).is_none());
+
+        let mut account = CanonicalScore::discriminator().to_vec();
+        account.extend(score(7, 64).try_to_vec().unwrap());
+        assert_eq!(decode_score(&account).unwrap().consciousness, 64);
+        assert!(decode_score(&account[1..]).is_none());
+    }
+}
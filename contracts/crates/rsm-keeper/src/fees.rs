@@ -0,0 +1,96 @@
+//! Adaptive compute unit pricing.
+//!
+//! The base price is a percentile of `getRecentPrioritizationFees` over the
+//! writable accounts a transaction touches; every blockhash refresh
+//! escalates it so a stuck backlog bids its way in, up to a hard cap.
+
+use crate::KeeperError;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Clone, Debug)]
+pub struct FeeEstimator {
+    /// Percentile (0-100) of recent fees to bid
+    pub percentile: u8,
+    /// Floor in micro-lamports per compute unit
+    pub min_unit_price: u64,
+    /// Cap in micro-lamports per compute unit
+    pub max_unit_price: u64,
+    /// Added per blockhash refresh, in percent of the previous price
+    pub escalation_pct: u64,
+}
+
+impl Default for FeeEstimator {
+    fn default() -> Self {
+        Self {
+            percentile: 75,
+            min_unit_price: 1_000,
+            max_unit_price: 5_000_000,
+            escalation_pct: 50,
+        }
+    }
+}
+
+impl FeeEstimator {
+    pub fn estimate(&self, rpc: &RpcClient, writable: &[Pubkey], refresh: u32) -> Result<u64, KeeperError> {
+        let fees = rpc
+            .get_recent_prioritization_fees(writable)?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect();
+        Ok(self.unit_price(fees, refresh))
+    }
+
+    pub fn unit_price(&self, mut recent: Vec<u64>, refresh: u32) -> u64 {
+        let mut price = percentile(&mut recent, self.percentile).max(self.min_unit_price);
+        for _ in 0..refresh {
+            if price >= self.max_unit_price {
+                break;
+            }
+            price = price.saturating_add(price.saturating_mul(self.escalation_pct) / 100);
+        }
+        price.min(self.max_unit_price)
+    }
+}
+
+/// Nearest-rank percentile; zero for an empty sample.
+pub fn percentile(samples: &mut [u64], pct: u8) -> u64 {
+    if samples.is_empty() {
+        return 0;
+    }
+    samples.sort_unstable();
+    let rank = (samples.len() * pct.min(100) as usize).div_ceil(100);
+    samples[rank.saturating_sub(1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let mut fees = vec![50, 10, 40, 20, 30];
+        assert_eq!(percentile(&mut fees, 0), 10);
+        assert_eq!(percentile(&mut fees, 50), 30);
+        assert_eq!(percentile(&mut fees, 75), 40);
+        assert_eq!(percentile(&mut fees, 100), 50);
+        assert_eq!(percentile(&mut [], 75), 0);
+    }
+
+    #[test]
+    fn test_unit_price_floor_escalation_and_cap() {
+        let estimator = FeeEstimator {
+            percentile: 50,
+            min_unit_price: 100,
+            max_unit_price: 1_000,
+            escalation_pct: 50,
+        };
+        // Quiet network: the floor applies
+        assert_eq!(estimator.unit_price(vec![0, 0, 0], 0), 100);
+        assert_eq!(estimator.unit_price(vec![200, 400, 600], 0), 400);
+        assert_eq!(estimator.unit_price(vec![200, 400, 600], 1), 600);
+        assert_eq!(estimator.unit_price(vec![200, 400, 600], 2), 900);
+        assert_eq!(estimator.unit_price(vec![200, 400, 600], 3), 1_000);
+        assert_eq!(estimator.unit_price(vec![u64::MAX], 4), 1_000);
+    }
+}
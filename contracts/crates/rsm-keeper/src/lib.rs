@@ -0,0 +1,62 @@
+//! rsm-keeper: submits queued genome mints to rsm-token.
+//!
+//! The `fees` module prices transactions from recent prioritization fees and
+//! `sender` lands them through congestion: rebroadcasting until the
+//! blockhash expires, refreshing it with an escalated fee, and finally
+//! falling back to a durable nonce. RPC latency is recorded in the
+//! sender's `rsm_metrics::PipelineMetrics`. Jobs queued without scores are
+//! scored by a pluggable `scoring::Scorer`.
+
+pub mod fees;
+pub mod scoring;
+pub mod sender;
+
+pub use fees::FeeEstimator;
+pub use scoring::{GenomeInput, Scorer, Scores};
+pub use sender::{SendConfig, Sender};
+
+use solana_client::client_error::ClientError as RpcError;
+use solana_sdk::{signature::Signature, signer::SignerError, transaction::TransactionError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum KeeperError {
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<RpcError>),
+    #[error("Signing failed: {0}")]
+    Signer(#[from] SignerError),
+    #[error("Transaction {signature} failed: {err}")]
+    Failed {
+        signature: Signature,
+        err: TransactionError,
+    },
+    #[error("Transaction expired after {attempts} attempts")]
+    Expired { attempts: u32 },
+    #[error("Nonce account {0} is unusable: {1}")]
+    Nonce(solana_sdk::pubkey::Pubkey, String),
+    #[error("Failed to build instructions: {0}")]
+    Build(String),
+    #[error("Scoring failed: {0}")]
+    Score(String),
+}
+
+impl KeeperError {
+    /// Label for `rsm_errors_total`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            KeeperError::Rpc(_) => "rpc",
+            KeeperError::Signer(_) => "signer",
+            KeeperError::Failed { .. } => "transaction_failed",
+            KeeperError::Expired { .. } => "expired",
+            KeeperError::Nonce(..) => "nonce",
+            KeeperError::Build(_) => "build",
+            KeeperError::Score(_) => "score",
+        }
+    }
+}
+
+impl From<RpcError> for KeeperError {
+    fn from(err: RpcError) -> Self {
+        KeeperError::Rpc(Box::new(err))
+    }
+}
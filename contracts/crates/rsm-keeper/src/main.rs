@@ -0,0 +1,591 @@
+//! rsm-keeper daemon: drains a JSON-lines backlog of genome mints.
+//!
+//! Each line is a `MintJob`. Progress is a byte offset kept next to the
+//! backlog, so the daemon can be restarted at any point; jobs that exhaust
+//! their attempts are appended to `<backlog>.failed`. A job without scores
+//! is scored by the `--scorer`. With `--metrics-addr`
+//! the daemon serves Prometheus metrics on `/metrics`.
+//!
+//! `rsm-keeper migrate` instead sweeps the program's accounts once and
+//! upgrades the config and every genome of `--mint` still stored under an
+//! older schema version.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use clap::{Parser, Subcommand};
+use rsm_keeper::{scoring, FeeEstimator, GenomeInput, KeeperError, Scorer, Scores, SendConfig, Sender};
+use rsm_metrics::PipelineMetrics;
+use rsm_token::{
+    blockchain_entropy, bond_amount, bond_vault_address, calculate_token_amount, config_address, genome_address, mint_delegate_address,
+    newest_slot_hash, oracle_config_address, scale_amount, stream_address, AccountKind, EmissionSmoothing, GenomeData, GenomeOrigin, MintArgs, MintWindow, RSMError,
+    RSMInstruction, TokenConfig, DEDUPE_SEED,
+};
+use serde::{Deserialize, Serialize};
+use solana_client::{rpc_client::RpcClient, rpc_config::RpcTransactionConfig};
+use spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account_idempotent};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signature, Signer},
+    system_program, sysvar,
+    transaction::TransactionError,
+};
+use std::{
+    error::Error,
+    fs::{self, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser, Debug)]
+#[command(name = "rsm-keeper", about = "Submit queued genome mints to rsm-token", subcommand_negates_reqs = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(long, default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+    /// Fee payer and AGI controller (or a registered mint delegate)
+    #[arg(long)]
+    keypair: PathBuf,
+    #[arg(long)]
+    program: Pubkey,
+    #[arg(long)]
+    mint: Pubkey,
+    /// JSON-lines file of mint jobs
+    #[arg(long, required = true)]
+    backlog: Option<PathBuf>,
+    #[arg(long, default_value_t = 5)]
+    poll_secs: u64,
+    /// Exit once the backlog is drained instead of waiting for new jobs
+    #[arg(long)]
+    once: bool,
+    /// Attempts per job; a missed slot prediction fails on-chain and is retried
+    #[arg(long, default_value_t = 5)]
+    job_attempts: u32,
+    /// Percentile of recent prioritization fees to bid
+    #[arg(long, default_value_t = 75)]
+    fee_percentile: u8,
+    /// Micro-lamports per compute unit
+    #[arg(long, default_value_t = 1_000)]
+    min_unit_price: u64,
+    /// Micro-lamports per compute unit
+    #[arg(long, default_value_t = 5_000_000)]
+    max_unit_price: u64,
+    #[arg(long, default_value_t = 50)]
+    escalation_pct: u64,
+    #[arg(long, default_value_t = 200_000)]
+    compute_unit_limit: u32,
+    #[arg(long, default_value_t = 2_000)]
+    rebroadcast_ms: u64,
+    #[arg(long, default_value_t = 3)]
+    max_refreshes: u32,
+    /// Durable nonce account (authority = keypair) for the last attempt
+    #[arg(long)]
+    nonce_account: Option<Pubkey>,
+    #[arg(long, default_value_t = 90)]
+    nonce_timeout_secs: u64,
+    /// Serve Prometheus metrics on this address, e.g. 0.0.0.0:9464
+    #[arg(long)]
+    metrics_addr: Option<SocketAddr>,
+    /// Scores jobs queued without them: `entropy`, `file:<path>` or an
+    /// oracle URL
+    #[arg(long)]
+    scorer: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Upgrade the config and genomes of --mint to their current schema
+    Migrate {
+        /// List the accounts that need migrating without sending anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct MintJob {
+    /// Hex-encoded 32-byte genome hash
+    genome_hash: String,
+    recipient: String,
+    /// Scores; a job without them is scored by the --scorer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    complexity: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uniqueness: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entropy: Option<u16>,
+    /// Nucleotide sequence for scorers that read it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sequence: Option<String>,
+    /// Origin block of the genome; required once quality decay is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    origin_slot: Option<u64>,
+    /// Origin time (unix seconds), for genomes dated by timestamp instead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    origin_timestamp: Option<i64>,
+}
+
+impl MintJob {
+    fn origin(&self) -> GenomeOrigin {
+        match (self.origin_slot, self.origin_timestamp) {
+            (Some(slot), _) => GenomeOrigin::Slot(slot),
+            (None, Some(timestamp)) => GenomeOrigin::Timestamp(timestamp),
+            (None, None) => GenomeOrigin::Unknown,
+        }
+    }
+
+    /// The job's own scores, or the scorer's when it has none.
+    fn scores(&self, genome_hash: [u8; 32], scorer: Option<&dyn Scorer>) -> std::result::Result<Scores, KeeperError> {
+        let scores = match (self.complexity, self.uniqueness, self.entropy, scorer) {
+            (Some(complexity), Some(uniqueness), Some(entropy), _) => Scores { complexity, uniqueness, entropy },
+            (_, _, _, Some(scorer)) => scorer.score(&GenomeInput { genome_hash, sequence: self.sequence.clone() })?,
+            _ => return Err(KeeperError::Score("job has no scores and no --scorer is set".to_string())),
+        };
+        scores.validate()
+    }
+}
+
+struct Keeper {
+    sender: Sender,
+    program: Pubkey,
+    mint: Pubkey,
+    config: Pubkey,
+    dedupe: Pubkey,
+    job_attempts: u32,
+    next_nonce: u64,
+    /// Fetch each landed mint to record its compute units
+    track_compute: bool,
+    scorer: Option<Box<dyn Scorer>>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let payer = read_keypair_file(&args.keypair)?;
+    let rpc = RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::confirmed());
+    let config = config_address(&args.program, &args.mint).0;
+    let dedupe = Pubkey::find_program_address(&[DEDUPE_SEED, config.as_ref()], &args.program).0;
+
+    let send_config = SendConfig {
+        fees: FeeEstimator {
+            percentile: args.fee_percentile,
+            min_unit_price: args.min_unit_price,
+            max_unit_price: args.max_unit_price,
+            escalation_pct: args.escalation_pct,
+        },
+        compute_unit_limit: args.compute_unit_limit,
+        rebroadcast_interval: Duration::from_millis(args.rebroadcast_ms),
+        max_refreshes: args.max_refreshes,
+        nonce_account: args.nonce_account,
+        nonce_timeout: Duration::from_secs(args.nonce_timeout_secs),
+    };
+    let metrics = PipelineMetrics::new();
+    if let Some(addr) = args.metrics_addr {
+        let bound = rsm_metrics::serve(addr, metrics.clone())?;
+        println!("Serving metrics on http://{}/metrics", bound);
+    }
+    let mut keeper = Keeper {
+        sender: Sender::new(rpc, payer, send_config).with_metrics(metrics.clone()),
+        program: args.program,
+        mint: args.mint,
+        config,
+        dedupe,
+        job_attempts: args.job_attempts,
+        next_nonce: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64,
+        track_compute: args.metrics_addr.is_some(),
+        scorer: args.scorer.as_deref().map(scoring::from_spec).transpose()?,
+    };
+    if let Some(Command::Migrate { dry_run }) = args.command {
+        let count = keeper.migrate_all(dry_run)?;
+        println!("{} account(s) {}", count, if dry_run { "need migrating" } else { "migrated" });
+        return Ok(());
+    }
+    let backlog = args.backlog.expect("--backlog is required without a subcommand");
+
+    let offset_path = sibling(&backlog, "offset");
+    let failed_path = sibling(&backlog, "failed");
+    let mut offset: u64 = fs::read_to_string(&offset_path)
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(0);
+
+    loop {
+        for line in read_complete_lines(&backlog, offset)? {
+            // Advance past the job before submitting so a crash never replays
+            // a mint that may have landed
+            offset += line.len() as u64 + 1;
+            fs::write(&offset_path, offset.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let job: MintJob = match serde_json::from_str(&line) {
+                Ok(job) => job,
+                Err(err) => {
+                    metrics.errors.inc("malformed_job");
+                    eprintln!("Skipping malformed job {:?}: {}", line, err);
+                    append_line(&failed_path, &line)?;
+                    continue;
+                }
+            };
+            match keeper.process(&job) {
+                Ok(signature) => println!("Minted {} to {}: {}", job.genome_hash, job.recipient, signature),
+                Err(err) => {
+                    metrics.errors.inc("job_abandoned");
+                    eprintln!("Giving up on {}: {}", job.genome_hash, err);
+                    append_line(&failed_path, &line)?;
+                }
+            }
+        }
+        if args.once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(args.poll_secs));
+    }
+}
+
+impl Keeper {
+    fn process(&mut self, job: &MintJob) -> Result<String> {
+        let genome_hash = parse_hash(&job.genome_hash)?;
+        let recipient: Pubkey = job.recipient.parse()?;
+        let scores = job.scores(genome_hash, self.scorer.as_deref()).inspect_err(|err| {
+            self.sender.metrics().errors.inc(err.kind());
+        })?;
+        let (genome, _) = genome_address(&self.program, &self.config, &genome_hash);
+        let token_config = TokenConfig::try_from_slice(&self.sender.rpc().get_account_data(&self.config)?)?;
+        if token_config.require_recipient_signature {
+            return Err("config requires recipients to co-sign mints, which the keeper cannot push".into());
+        }
+        self.wait_for_mint_window(&token_config.mint_window)?;
+
+        match self.sender.rpc().get_account(&genome) {
+            // A pre-registered genome only mints to its registrant
+            Ok(account) => {
+                if let Ok(data) = GenomeData::try_from_slice(&account.data) {
+                    if data.is_pending() && data.registrant != recipient {
+                        return Err(format!("genome is pre-registered to {}, not {}", data.registrant, recipient).into());
+                    }
+                }
+            }
+            Err(_) => {
+                let payer = self.sender.payer().pubkey();
+                let create = Instruction::new_with_bytes(
+                    self.program,
+                    &RSMInstruction::CreateGenomeAccount { genome_hash }.try_to_vec()?,
+                    vec![
+                        AccountMeta::new(self.config, false),
+                        AccountMeta::new(genome, false),
+                        AccountMeta::new(payer, true),
+                        AccountMeta::new_readonly(system_program::id(), false),
+                    ],
+                );
+                self.sender.send(&[], |_| Ok(vec![create.clone()]))?;
+            }
+        }
+
+        // One nonce per job: should an attempt reported as lost still land,
+        // the dedupe buffer fails its retry with DuplicateMint
+        let nonce = self.next_nonce;
+        self.next_nonce += 1;
+        let mut last_err = None;
+        for _ in 0..self.job_attempts {
+            let mut amount = 0;
+            let result = self.sender.send(&[], |processed_slot| {
+                let (ix, ix_amount) = self.mint_instruction(
+                    &token_config,
+                    &genome,
+                    &recipient,
+                    job,
+                    scores,
+                    genome_hash,
+                    nonce,
+                    processed_slot,
+                )?;
+                amount = ix_amount;
+                if token_config.stream_slots > 0 {
+                    return Ok(vec![ix]);
+                }
+                // An unstreamed mint pays the recipient's associated token account
+                let payer = self.sender.payer().pubkey();
+                let create = create_associated_token_account_idempotent(&payer, &recipient, &self.mint, &spl_token::id());
+                Ok(vec![create, ix])
+            });
+            match result {
+                // A mint that trips the circuit breaker lands without minting
+                Ok(signature) if !self.is_minted(&genome)? => {
+                    self.sender.metrics().errors.inc("breaker");
+                    return Err(format!("mint {} was held by the circuit breaker; ResetBreaker before retrying", signature).into());
+                }
+                Ok(signature) => {
+                    self.record_landed(&signature, amount);
+                    return Ok(signature.to_string());
+                }
+                Err(KeeperError::Failed {
+                    signature,
+                    err: TransactionError::InstructionError(_, InstructionError::Custom(code)),
+                }) if code == RSMError::DuplicateMint as u32 => {
+                    eprintln!("Mint for {} already landed in an earlier attempt", job.genome_hash);
+                    self.sender.metrics().record_mint(amount);
+                    return Ok(signature.to_string());
+                }
+                Err(err @ (KeeperError::Failed { .. } | KeeperError::Expired { .. })) => {
+                    self.sender.metrics().errors.inc(err.kind());
+                    eprintln!("Mint attempt for {} failed: {}", job.genome_hash, err);
+                    last_err = Some(err);
+                }
+                Err(err) => {
+                    self.sender.metrics().errors.inc(err.kind());
+                    return Err(err.into());
+                }
+            }
+        }
+        Err(last_err.map_or_else(|| "no attempts configured".into(), Into::into))
+    }
+
+    fn is_minted(&self, genome: &Pubkey) -> Result<bool> {
+        let data = self.sender.rpc().get_account_data(genome)?;
+        Ok(GenomeData::try_from_slice(&data)?.is_minted)
+    }
+
+    /// Sends one MigrateAccount per account still on an older schema.
+    fn migrate_all(&self, dry_run: bool) -> Result<usize> {
+        let accounts = self.sender.rpc().get_program_accounts(&self.program)?;
+        let payer = self.sender.payer().pubkey();
+        let mut count = 0;
+        for (address, account) in accounts {
+            let Some(kind) = self.migration_kind(&address, &account.data) else {
+                continue;
+            };
+            count += 1;
+            if dry_run {
+                println!("{:?} {} needs migrating", kind, address);
+                continue;
+            }
+            let mut metas = vec![
+                AccountMeta::new(address, false),
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ];
+            if kind == AccountKind::GenomeData {
+                metas.push(AccountMeta::new_readonly(self.config, false));
+            }
+            let ix = Instruction::new_with_bytes(
+                self.program,
+                &RSMInstruction::MigrateAccount { kind }.try_to_vec()?,
+                metas,
+            );
+            let signature = self.sender.send(&[], |_| Ok(vec![ix.clone()]))?;
+            println!("Migrated {:?} {}: {}", kind, address, signature);
+        }
+        Ok(count)
+    }
+
+    /// Whether `address` is this keeper's config or one of its genomes and
+    /// still needs migrating.
+    fn migration_kind(&self, address: &Pubkey, data: &[u8]) -> Option<AccountKind> {
+        if *address == self.config {
+            return TokenConfig::SCHEMA.needs_migration(data).then_some(AccountKind::TokenConfig);
+        }
+        let schema = &GenomeData::SCHEMA;
+        if !schema.needs_migration(data) {
+            return None;
+        }
+        // The hash follows the version byte once there is one
+        let offset = if schema.version_of(data) == Ok(0) { 0 } else { 1 };
+        let hash: [u8; 32] = data.get(offset..offset + 32)?.try_into().ok()?;
+        (genome_address(&self.program, &self.config, &hash).0 == *address).then_some(AccountKind::GenomeData)
+    }
+
+    /// Holds the backlog through the config's quiet period, so jobs keep
+    /// their order instead of failing until the next window opens.
+    fn wait_for_mint_window(&self, window: &MintWindow) -> Result<()> {
+        if !window.is_enabled() {
+            return Ok(());
+        }
+        let schedule = self.sender.rpc().get_epoch_schedule()?;
+        let mut announced = false;
+        while !window.is_open(&schedule, self.sender.rpc().get_slot()? + 1) {
+            if !announced {
+                println!("Mint window closed, waiting for the next epoch's window");
+                announced = true;
+            }
+            thread::sleep(Duration::from_secs(2));
+        }
+        Ok(())
+    }
+
+    fn record_landed(&self, signature: &Signature, amount: u64) {
+        let metrics = self.sender.metrics();
+        metrics.record_mint(amount);
+        if !self.track_compute {
+            return;
+        }
+        let config = RpcTransactionConfig {
+            commitment: Some(self.sender.rpc().commitment()),
+            max_supported_transaction_version: Some(0),
+            ..RpcTransactionConfig::default()
+        };
+        let fetched = metrics.time_rpc("getTransaction", || {
+            self.sender.rpc().get_transaction_with_config(signature, config).ok()
+        });
+        match fetched.and_then(|tx| tx.transaction.meta) {
+            Some(meta) => {
+                if let Some(units) = Option::<u64>::from(meta.compute_units_consumed) {
+                    metrics.compute_units.observe(units as f64);
+                }
+            }
+            None => metrics.errors.inc("rpc"),
+        }
+    }
+
+    /// The mint instruction and the amount it mints.
+    #[allow(clippy::too_many_arguments)]
+    fn mint_instruction(
+        &self,
+        token_config: &TokenConfig,
+        genome: &Pubkey,
+        recipient: &Pubkey,
+        job: &MintJob,
+        scores: Scores,
+        genome_hash: [u8; 32],
+        nonce: u64,
+        processed_slot: u64,
+    ) -> std::result::Result<(Instruction, u64), KeeperError> {
+        // The blockchain component comes from the hash of the newest slot
+        // the sysvar holds; decay and smoothing aim for the next slot
+        let slot_hashes = self.sender.rpc().get_account_data(&sysvar::slot_hashes::id())?;
+        let (recent_slot, slot_hash) = newest_slot_hash(&slot_hashes)
+            .ok_or_else(|| KeeperError::Build("SlotHashes sysvar is empty".to_string()))?;
+        let amount = scale_amount(
+            calculate_token_amount(
+                &token_config.agi_params,
+                scores.complexity,
+                scores.uniqueness,
+                scores.entropy,
+                blockchain_entropy(&genome_hash, &slot_hash),
+            ),
+            token_config.decimals,
+        )
+        .ok_or_else(|| KeeperError::Build("amount overflows".to_string()))?;
+        let origin = job.origin();
+        let amount = if token_config.decay.is_enabled() {
+            // Wall time stands in for the cluster clock; the curve moves in
+            // hour steps, so only a job landing on a step boundary can miss
+            // and be retried
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+            let age_secs = origin
+                .age_secs(processed_slot + 1, now)
+                .ok_or_else(|| KeeperError::Build("quality decay is on; job needs an origin".to_string()))?;
+            token_config.decay.apply(amount, age_secs)
+        } else {
+            amount
+        };
+        let amount = EmissionSmoothing::apply(amount, token_config.smoothing.scale_bps_at(processed_slot + 1));
+        // The keeper signs alone, so a mint needing the quorum cannot land
+        if token_config.mint_quorum.applies_to(amount) {
+            return Err(KeeperError::Build(format!(
+                "mint of {} needs {} controller co-signers",
+                amount, token_config.mint_quorum.required
+            )));
+        }
+        let data = RSMInstruction::MintFromGenome(MintArgs {
+            genome_hash,
+            complexity: scores.complexity,
+            uniqueness: scores.uniqueness,
+            entropy: scores.entropy,
+            amount,
+            mint_receipt: false,
+            nonce,
+            recent_slot,
+            split_bps: Vec::new(),
+            origin,
+            species: 0,
+            allowlist_proof: None,
+            auto_stake: false,
+        })
+        .try_to_vec()
+        .map_err(|e| KeeperError::Build(e.to_string()))?;
+
+        let signer = self.sender.payer().pubkey();
+        let mut accounts = vec![
+            AccountMeta::new(self.config, false),
+            AccountMeta::new(*genome, false),
+            AccountMeta::new(self.mint, false),
+            AccountMeta::new_readonly(*recipient, false),
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new(self.dedupe, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        ];
+        if signer != token_config.agi_controller {
+            let (delegate, _) = mint_delegate_address(&self.program, &self.config, &signer);
+            accounts.push(AccountMeta::new(delegate, false));
+        }
+        if token_config.score_oracle != Pubkey::default() {
+            let (oracle_config, _) = oracle_config_address(&token_config.score_oracle, &token_config.mint);
+            let (score, _) = Pubkey::find_program_address(
+                &[b"score", oracle_config.as_ref(), &genome_hash],
+                &token_config.score_oracle,
+            );
+            accounts.push(AccountMeta::new_readonly(score, false));
+        }
+        if token_config.stream_slots > 0 {
+            let (stream, _) = stream_address(&self.program, genome);
+            accounts.push(AccountMeta::new(stream, false));
+            accounts.push(AccountMeta::new(signer, true));
+            accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+        } else {
+            accounts.push(AccountMeta::new(get_associated_token_address(recipient, &self.mint), false));
+        }
+        if bond_amount(amount, token_config.bond_bps) > 0 {
+            accounts.push(AccountMeta::new(bond_vault_address(&self.program, &self.config).0, false));
+        }
+        Ok((Instruction::new_with_bytes(self.program, &data, accounts), amount))
+    }
+}
+
+fn parse_hash(hex: &str) -> Result<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(format!("genome hash must be 64 hex characters: {}", hex).into());
+    }
+    let mut hash = [0u8; 32];
+    for (byte, pair) in hash.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair)?, 16)?;
+    }
+    Ok(hash)
+}
+
+fn sibling(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+/// Lines after `offset` up to the last newline; a partially written job is
+/// left for the next poll.
+fn read_complete_lines(path: &Path, offset: u64) -> Result<Vec<String>> {
+    let mut file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = String::new();
+    file.read_to_string(&mut buf)?;
+    let Some(end) = buf.rfind('\n') else {
+        return Ok(Vec::new());
+    };
+    Ok(buf[..end].split('\n').map(str::to_string).collect())
+}
+
+fn append_line(path: &Path, line: &str) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
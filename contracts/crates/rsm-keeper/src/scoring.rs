@@ -0,0 +1,237 @@
+//! Scores for jobs queued without them.
+//!
+//! A `Scorer` turns a `GenomeInput` into the `Scores` a mint submits, so a
+//! lab can plug in its own complexity and uniqueness model and leave
+//! signing and submission to the keeper. Three come built in:
+//! `EntropyScorer` derives the scores from the sequence itself,
+//! `FileScorer` looks them up in a JSON file scored offline and
+//! `HttpScorer` asks an oracle service.
+
+use crate::KeeperError;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    time::Duration,
+};
+
+pub const MAX_COMPLEXITY: u8 = 100;
+pub const MAX_UNIQUENESS: u8 = 100;
+pub const MAX_ENTROPY: u16 = 200;
+
+/// What a scorer sees of a genome.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenomeInput {
+    pub genome_hash: [u8; 32],
+    /// Nucleotide sequence, when the job carries one
+    pub sequence: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scores {
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub entropy: u16,
+}
+
+impl Scores {
+    /// Rejects scores rsm-token would reject, before anything is sent.
+    pub fn validate(self) -> Result<Self, KeeperError> {
+        if self.complexity > MAX_COMPLEXITY || self.uniqueness > MAX_UNIQUENESS || self.entropy > MAX_ENTROPY {
+            return Err(KeeperError::Score(format!("scores out of range: {:?}", self)));
+        }
+        Ok(self)
+    }
+}
+
+pub trait Scorer {
+    fn score(&self, input: &GenomeInput) -> Result<Scores, KeeperError>;
+}
+
+/// Builds a scorer from `entropy`, `file:<path>` or an `http(s)://` URL.
+pub fn from_spec(spec: &str) -> Result<Box<dyn Scorer>, KeeperError> {
+    match spec.split_once(':') {
+        _ if spec == "entropy" => Ok(Box::new(EntropyScorer::default())),
+        Some(("file", path)) => Ok(Box::new(FileScorer::load(Path::new(path))?)),
+        Some(("http" | "https", _)) => Ok(Box::new(HttpScorer::new(spec, Duration::from_secs(10))?)),
+        _ => Err(KeeperError::Score(format!("unknown scorer {:?}; expected entropy, file:<path> or a URL", spec))),
+    }
+}
+
+/// Scores a sequence from its own statistics. Entropy is the Shannon
+/// entropy per base in hundredths of a bit (ACGT tops out at 2 bits, the
+/// program's 200), complexity that entropy as a share of 2 bits, and
+/// uniqueness the share of distinct `kmer_len`-mers out of as many as the
+/// sequence could hold.
+#[derive(Clone, Copy, Debug)]
+pub struct EntropyScorer {
+    pub kmer_len: usize,
+}
+
+impl Default for EntropyScorer {
+    fn default() -> Self {
+        Self { kmer_len: 4 }
+    }
+}
+
+impl Scorer for EntropyScorer {
+    fn score(&self, input: &GenomeInput) -> Result<Scores, KeeperError> {
+        let k = self.kmer_len.max(1);
+        let sequence = input
+            .sequence
+            .as_deref()
+            .map(|sequence| sequence.trim().to_ascii_uppercase().into_bytes())
+            .filter(|bases| bases.len() >= k)
+            .ok_or_else(|| KeeperError::Score(format!("entropy scoring needs a sequence of at least {} bases", k)))?;
+
+        let mut counts: HashMap<u8, usize> = HashMap::new();
+        for base in &sequence {
+            *counts.entry(*base).or_default() += 1;
+        }
+        let len = sequence.len() as f64;
+        let bits: f64 = counts
+            .values()
+            .map(|&count| {
+                let p = count as f64 / len;
+                -p * p.log2()
+            })
+            .sum();
+
+        let kmers: HashSet<&[u8]> = sequence.windows(k).collect();
+        let possible = 4usize.saturating_pow(k as u32).min(sequence.len() - k + 1);
+
+        Ok(Scores {
+            complexity: ((bits / 2.0 * 100.0).round() as u8).min(MAX_COMPLEXITY),
+            uniqueness: (kmers.len() * 100 / possible).min(MAX_UNIQUENESS as usize) as u8,
+            entropy: ((bits * 100.0).round() as u16).min(MAX_ENTROPY),
+        })
+    }
+}
+
+/// Scores computed offline: a JSON object from hex genome hash to
+/// `{"complexity", "uniqueness", "entropy"}`, read once at startup.
+#[derive(Clone, Debug, Default)]
+pub struct FileScorer {
+    scores: HashMap<String, Scores>,
+}
+
+impl FileScorer {
+    pub fn load(path: &Path) -> Result<Self, KeeperError> {
+        let read = |err: String| KeeperError::Score(format!("{}: {}", path.display(), err));
+        let text = fs::read_to_string(path).map_err(|err| read(err.to_string()))?;
+        let scores: HashMap<String, Scores> = serde_json::from_str(&text).map_err(|err| read(err.to_string()))?;
+        Ok(Self {
+            scores: scores.into_iter().map(|(hash, scores)| (hash.to_ascii_lowercase(), scores)).collect(),
+        })
+    }
+}
+
+impl Scorer for FileScorer {
+    fn score(&self, input: &GenomeInput) -> Result<Scores, KeeperError> {
+        let hash = to_hex(&input.genome_hash);
+        self.scores
+            .get(&hash)
+            .copied()
+            .ok_or_else(|| KeeperError::Score(format!("no scores on file for {}", hash)))
+    }
+}
+
+/// Posts `{"genome_hash": "<hex>", "sequence": ...}` to an oracle service
+/// and reads `Scores` back from the JSON response.
+pub struct HttpScorer {
+    url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl HttpScorer {
+    pub fn new(url: &str, timeout: Duration) -> Result<Self, KeeperError> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|err| KeeperError::Score(err.to_string()))?;
+        Ok(Self { url: url.to_string(), client })
+    }
+}
+
+impl Scorer for HttpScorer {
+    fn score(&self, input: &GenomeInput) -> Result<Scores, KeeperError> {
+        let request = json!({ "genome_hash": to_hex(&input.genome_hash), "sequence": input.sequence });
+        self.client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.json())
+            .map_err(|err| KeeperError::Score(format!("{}: {}", self.url, err)))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    fn input(sequence: Option<&str>) -> GenomeInput {
+        GenomeInput { genome_hash: [0xab; 32], sequence: sequence.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_entropy_scorer() {
+        let scorer = EntropyScorer::default();
+        // Every base equally often, case-insensitively
+        let scores = scorer.score(&input(Some("acgtacgtaaccggttagct"))).unwrap();
+        assert_eq!((scores.complexity, scores.entropy), (100, 200));
+        let repeat = scorer.score(&input(Some("AAAAAAAA"))).unwrap();
+        assert_eq!(repeat, Scores { complexity: 0, uniqueness: 20, entropy: 0 });
+        let scores = scorer.score(&input(Some("ACGTACGTACGT"))).unwrap();
+        assert_eq!(scores.uniqueness, 44);
+        assert!(scorer.score(&input(Some("ACG"))).is_err());
+        assert!(scorer.score(&input(None)).is_err());
+    }
+
+    #[test]
+    fn test_file_scorer_and_validation() {
+        let path = std::env::temp_dir().join(format!("rsm-keeper-scores-{}.json", std::process::id()));
+        let hash = to_hex(&[0xab; 32]).to_ascii_uppercase();
+        fs::write(&path, format!(r#"{{"{}": {{"complexity": 90, "uniqueness": 85, "entropy": 150}}}}"#, hash)).unwrap();
+        let scorer = from_spec(&format!("file:{}", path.display())).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let scores = scorer.score(&input(None)).unwrap();
+        assert_eq!(scores, Scores { complexity: 90, uniqueness: 85, entropy: 150 });
+        assert!(scorer.score(&GenomeInput { genome_hash: [1; 32], sequence: None }).is_err());
+        assert!(Scores { entropy: 201, ..scores }.validate().is_err());
+        assert!(from_spec("magic").is_err());
+    }
+
+    #[test]
+    fn test_http_scorer() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/score", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![0u8; 4096];
+            let len = stream.read(&mut request).unwrap();
+            let body = r#"{"complexity": 70, "uniqueness": 60, "entropy": 120}"#;
+            write!(stream, "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}", body.len(), body)
+                .unwrap();
+            String::from_utf8_lossy(&request[..len]).into_owned()
+        });
+
+        let scores = from_spec(&url).unwrap().score(&input(Some("ACGT"))).unwrap();
+        assert_eq!(scores, Scores { complexity: 70, uniqueness: 60, entropy: 120 });
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /score"));
+        assert!(request.contains(&to_hex(&[0xab; 32])));
+    }
+}
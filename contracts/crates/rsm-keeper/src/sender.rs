@@ -0,0 +1,241 @@
+//! Congestion-tolerant transaction submission.
+//!
+//! Each attempt signs against a fresh blockhash, prices compute units with
+//! the `FeeEstimator` and rebroadcasts the same signed transaction until it
+//! lands or its blockhash expires. Once the refresh budget is spent, a
+//! configured durable nonce account gives the final attempt a blockhash
+//! that never expires on its own.
+
+use crate::{fees::FeeEstimator, KeeperError};
+use rsm_metrics::PipelineMetrics;
+use solana_client::{rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig};
+use solana_rpc_client_nonce_utils::{data_from_account, get_account_with_commitment};
+use solana_sdk::{
+    clock::Slot,
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use std::{
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Debug)]
+pub struct SendConfig {
+    pub fees: FeeEstimator,
+    pub compute_unit_limit: u32,
+    pub rebroadcast_interval: Duration,
+    /// Fresh blockhashes to try before the durable nonce fallback
+    pub max_refreshes: u32,
+    /// Nonce account whose authority is the fee payer
+    pub nonce_account: Option<Pubkey>,
+    pub nonce_timeout: Duration,
+}
+
+impl Default for SendConfig {
+    fn default() -> Self {
+        Self {
+            fees: FeeEstimator::default(),
+            compute_unit_limit: 200_000,
+            rebroadcast_interval: Duration::from_secs(2),
+            max_refreshes: 3,
+            nonce_account: None,
+            nonce_timeout: Duration::from_secs(90),
+        }
+    }
+}
+
+enum Status {
+    Unknown,
+    Seen,
+    Landed(Signature),
+}
+
+pub struct Sender {
+    rpc: RpcClient,
+    payer: Keypair,
+    config: SendConfig,
+    metrics: Arc<PipelineMetrics>,
+}
+
+impl Sender {
+    pub fn new(rpc: RpcClient, payer: Keypair, config: SendConfig) -> Self {
+        Self { rpc, payer, config, metrics: PipelineMetrics::new() }
+    }
+
+    pub fn with_metrics(self, metrics: Arc<PipelineMetrics>) -> Self {
+        Self { metrics, ..self }
+    }
+
+    pub fn metrics(&self) -> &PipelineMetrics {
+        &self.metrics
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    pub fn payer(&self) -> &Keypair {
+        &self.payer
+    }
+
+    /// Lands the instructions returned by `build`, signed by the payer and
+    /// `signers`. `build` receives the latest processed slot and is re-run
+    /// for every attempt so slot-bound instruction data stays current.
+    pub fn send<F>(&self, signers: &[&Keypair], mut build: F) -> Result<Signature, KeeperError>
+    where
+        F: FnMut(Slot) -> Result<Vec<Instruction>, KeeperError>,
+    {
+        for refresh in 0..=self.config.max_refreshes {
+            let (blockhash, last_valid_height) = self.metrics.time_rpc("getLatestBlockhash", || {
+                self.rpc
+                    .get_latest_blockhash_with_commitment(self.rpc.commitment())
+                    .map_err(KeeperError::from)
+            })?;
+            let instructions = build(self.processed_slot()?)?;
+            let transaction = self.sign(None, instructions, refresh, blockhash, signers)?;
+            let signature = self.broadcast(&transaction, || {
+                let height = self
+                    .metrics
+                    .time_rpc("getBlockHeight", || self.rpc.get_block_height().map_err(KeeperError::from))?;
+                Ok(height > last_valid_height)
+            })?;
+            if let Some(signature) = signature {
+                return Ok(signature);
+            }
+        }
+
+        let attempts = self.config.max_refreshes + 1;
+        let Some(nonce) = self.config.nonce_account else {
+            return Err(KeeperError::Expired { attempts });
+        };
+
+        let nonce_hash = self.nonce_blockhash(&nonce)?;
+        let instructions = build(self.processed_slot()?)?;
+        let transaction = self.sign(Some(&nonce), instructions, attempts, nonce_hash, signers)?;
+        let deadline = Instant::now() + self.config.nonce_timeout;
+        // Once the nonce advances the transaction can never land
+        let signature = self.broadcast(&transaction, || {
+            Ok(Instant::now() > deadline || self.nonce_blockhash(&nonce)? != nonce_hash)
+        })?;
+        signature.ok_or(KeeperError::Expired { attempts: attempts + 1 })
+    }
+
+    fn processed_slot(&self) -> Result<Slot, KeeperError> {
+        self.metrics.time_rpc("getSlot", || {
+            self.rpc
+                .get_slot_with_commitment(CommitmentConfig::processed())
+                .map_err(KeeperError::from)
+        })
+    }
+
+    fn sign(
+        &self,
+        nonce: Option<&Pubkey>,
+        instructions: Vec<Instruction>,
+        refresh: u32,
+        blockhash: Hash,
+        signers: &[&Keypair],
+    ) -> Result<Transaction, KeeperError> {
+        let mut writable: Vec<Pubkey> = instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect();
+        writable.sort_unstable();
+        writable.dedup();
+        let unit_price = self
+            .metrics
+            .time_rpc("getRecentPrioritizationFees", || self.config.fees.estimate(&self.rpc, &writable, refresh))?;
+
+        // AdvanceNonceAccount must be the first instruction
+        let mut all = Vec::with_capacity(instructions.len() + 3);
+        if let Some(nonce) = nonce {
+            all.push(system_instruction::advance_nonce_account(nonce, &self.payer.pubkey()));
+        }
+        all.push(ComputeBudgetInstruction::set_compute_unit_limit(self.config.compute_unit_limit));
+        all.push(ComputeBudgetInstruction::set_compute_unit_price(unit_price));
+        all.extend(instructions);
+
+        let mut keypairs = vec![&self.payer];
+        keypairs.extend(signers.iter().filter(|k| k.pubkey() != self.payer.pubkey()));
+        let mut transaction = Transaction::new_with_payer(&all, Some(&self.payer.pubkey()));
+        transaction.try_sign(&keypairs, blockhash)?;
+        Ok(transaction)
+    }
+
+    /// Resends `transaction` every `rebroadcast_interval` until it lands at
+    /// the client commitment or `expired` reports it can no longer land.
+    fn broadcast<E>(&self, transaction: &Transaction, mut expired: E) -> Result<Option<Signature>, KeeperError>
+    where
+        E: FnMut() -> Result<bool, KeeperError>,
+    {
+        let config = RpcSendTransactionConfig {
+            skip_preflight: true,
+            max_retries: Some(0),
+            ..RpcSendTransactionConfig::default()
+        };
+        loop {
+            // Send errors are transient here; the next round resends
+            self.metrics
+                .time_rpc("sendTransaction", || self.rpc.send_transaction_with_config(transaction, config).is_ok());
+            thread::sleep(self.config.rebroadcast_interval);
+
+            match self.status(&transaction.signatures[0])? {
+                Status::Landed(signature) => return Ok(Some(signature)),
+                // Processed but not yet at the target commitment: keep waiting
+                Status::Seen => continue,
+                Status::Unknown => {
+                    if expired()? {
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+    }
+
+    fn status(&self, signature: &Signature) -> Result<Status, KeeperError> {
+        let status = self
+            .metrics
+            .time_rpc("getSignatureStatuses", || {
+                self.rpc.get_signature_statuses(&[*signature]).map_err(KeeperError::from)
+            })?
+            .value
+            .pop()
+            .flatten();
+        match status {
+            Some(status) => {
+                if let Some(err) = status.err {
+                    return Err(KeeperError::Failed { signature: *signature, err });
+                }
+                if status.satisfies_commitment(self.rpc.commitment()) {
+                    Ok(Status::Landed(*signature))
+                } else {
+                    Ok(Status::Seen)
+                }
+            }
+            None => Ok(Status::Unknown),
+        }
+    }
+
+    fn nonce_blockhash(&self, nonce: &Pubkey) -> Result<Hash, KeeperError> {
+        let unusable = |reason: String| KeeperError::Nonce(*nonce, reason);
+        let account = self
+            .metrics
+            .time_rpc("getAccountInfo", || get_account_with_commitment(&self.rpc, nonce, self.rpc.commitment()))
+            .map_err(|e| unusable(e.to_string()))?;
+        let data = data_from_account(&account).map_err(|e| unusable(e.to_string()))?;
+        if data.authority != self.payer.pubkey() {
+            return Err(unusable(format!("authority {} is not the fee payer", data.authority)));
+        }
+        Ok(data.blockhash())
+    }
+}
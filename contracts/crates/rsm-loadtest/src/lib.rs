@@ -0,0 +1,12 @@
+//! rsm-loadtest: synthetic genome workloads for capacity planning.
+//!
+//! `workload` generates reproducible genomes from a seed, scored by the
+//! keeper's entropy scorer and split between mints and geneses; `report`
+//! turns the outcome of every operation into latency, compute-unit and
+//! failure statistics per flow.
+
+pub mod report;
+pub mod workload;
+
+pub use report::{Outcome, Percentiles, Report};
+pub use workload::{Flow, SyntheticGenome};
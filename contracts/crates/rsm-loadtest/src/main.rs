@@ -0,0 +1,432 @@
+//! rsm-loadtest: drives synthetic genome mints and geneses against a
+//! localnet at a fixed rate and reports what landed.
+//!
+//! Addresses and keypairs come from the manifest `rsm-localnet` writes.
+//! Operations are released at `--tps` to a pool of `--workers`; each one
+//! that misses is retried up to `--attempts` times. Compute units are
+//! fetched once the run is over, outside the timed path.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use clap::Parser;
+use rsm_client::instructions::{genesis_commitment, CreateGenome, RevealGenesis};
+use rsm_loadtest::{workload, Flow, Outcome, Report, SyntheticGenome};
+use rsm_token::{
+    blockchain_entropy, calculate_token_amount, genome_address, newest_slot_hash, scale_amount, EmissionSmoothing,
+    MintArgs, RSMInstruction, TokenConfig,
+};
+use serde::Deserialize;
+use solana_client::{
+    client_error::ClientError, client_error::ClientErrorKind, rpc_client::RpcClient, rpc_config::RpcTransactionConfig,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature, Signer},
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account_idempotent};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    path::PathBuf,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Discriminator, authority and genesis_cost precede the engine's
+/// total_genomes_created.
+const ENGINE_GENOMES_CREATED_OFFSET: usize = 8 + 32 + 8;
+
+#[derive(Parser, Debug)]
+#[command(name = "rsm-loadtest", about = "Drive synthetic genome workloads against a localnet")]
+struct Args {
+    /// Manifest written by rsm-localnet
+    #[arg(long, default_value = ".rsm-localnet/manifest.json")]
+    manifest: PathBuf,
+    #[arg(long, default_value_t = 1_000)]
+    genomes: usize,
+    /// Operations released per second
+    #[arg(long, default_value_t = 20.0)]
+    tps: f64,
+    /// Operations in flight at once
+    #[arg(long, default_value_t = 8)]
+    workers: usize,
+    /// Seed of the synthetic genomes; the same seed replays the same workload
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Attempts per operation; a missed slot prediction fails on-chain and is retried
+    #[arg(long, default_value_t = 3)]
+    attempts: u32,
+    /// Percent of genomes sent through genesis instead of a mint
+    #[arg(long, default_value_t = 0)]
+    genesis_pct: u8,
+    /// Initialized genesis engine, required with --genesis-pct
+    #[arg(long)]
+    genesis_engine: Option<Pubkey>,
+    #[arg(long, default_value_t = 0)]
+    species: u16,
+    /// The payer's RNA account that genesis burns from
+    #[arg(long)]
+    rna_account: Option<Pubkey>,
+    /// Whether the engine has a mutation catalog
+    #[arg(long)]
+    mutation_catalog: bool,
+    /// The engine's Pyth price feed, when it prices genesis in USD
+    #[arg(long)]
+    price_feed: Option<Pubkey>,
+    /// Whether the engine has a genesis guardian; the load test cannot
+    /// co-sign, so genesis must cost less than its threshold
+    #[arg(long)]
+    genesis_guardian: bool,
+    /// Skip fetching each landed transaction for its compute units
+    #[arg(long)]
+    skip_compute_units: bool,
+    /// Also write the report as JSON
+    #[arg(long)]
+    report: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    rpc_url: String,
+    payer_keypair: PathBuf,
+    agi_controller_keypair: PathBuf,
+    programs: BTreeMap<String, String>,
+    mints: BTreeMap<String, String>,
+    rsm_token: RsmTokenFixture,
+}
+
+#[derive(Deserialize)]
+struct RsmTokenFixture {
+    config: String,
+    dedupe: String,
+}
+
+impl Manifest {
+    fn program(&self, name: &str) -> Result<Pubkey> {
+        let id = self.programs.get(name).ok_or_else(|| format!("manifest has no {} program", name))?;
+        Ok(id.parse()?)
+    }
+
+    fn mint(&self, name: &str) -> Result<Pubkey> {
+        let mint = self.mints.get(name).ok_or_else(|| format!("manifest has no {} mint", name))?;
+        Ok(mint.parse()?)
+    }
+}
+
+struct Target {
+    rpc_url: String,
+    program: Pubkey,
+    mint: Pubkey,
+    config: Pubkey,
+    dedupe: Pubkey,
+    payer: Keypair,
+    controller: Keypair,
+    attempts: u32,
+    genesis: Option<GenesisTarget>,
+}
+
+struct GenesisTarget {
+    program: Pubkey,
+    dna_program: Pubkey,
+    pause_program: Pubkey,
+    engine: Pubkey,
+    species: u16,
+    rna_mint: Pubkey,
+    rna_account: Pubkey,
+    mutation_catalog: bool,
+    price_feed: Option<Pubkey>,
+    genesis_guardian: bool,
+    /// The payer has one commit at a time, so its geneses run one by one
+    commit: Mutex<()>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    if args.tps <= 0.0 || args.workers == 0 || args.attempts == 0 || args.genesis_pct > 100 {
+        return Err("--tps, --workers and --attempts must be positive and --genesis-pct at most 100".into());
+    }
+    let manifest: Manifest = serde_json::from_str(&fs::read_to_string(&args.manifest)?)?;
+    let genesis = match (args.genesis_pct, args.genesis_engine, args.rna_account) {
+        (0, _, _) => None,
+        (_, Some(engine), Some(rna_account)) => Some(GenesisTarget {
+            program: manifest.program("genesis_engine")?,
+            dna_program: manifest.program("rsm_dna_token")?,
+            pause_program: manifest.program("rsm_pause")?,
+            engine,
+            species: args.species,
+            rna_mint: manifest.mint("rna")?,
+            rna_account,
+            mutation_catalog: args.mutation_catalog,
+            price_feed: args.price_feed,
+            genesis_guardian: args.genesis_guardian,
+            commit: Mutex::new(()),
+        }),
+        _ => return Err("--genesis-pct needs --genesis-engine and --rna-account".into()),
+    };
+    let target = Arc::new(Target {
+        rpc_url: manifest.rpc_url.clone(),
+        program: manifest.program("rsm_token")?,
+        mint: manifest.mint("rsm")?,
+        config: manifest.rsm_token.config.parse()?,
+        dedupe: manifest.rsm_token.dedupe.parse()?,
+        payer: read_keypair_file(&manifest.payer_keypair)?,
+        controller: read_keypair_file(&manifest.agi_controller_keypair)?,
+        attempts: args.attempts,
+        genesis,
+    });
+
+    let rpc = RpcClient::new_with_commitment(target.rpc_url.clone(), CommitmentConfig::confirmed());
+    let config = TokenConfig::try_from_slice(&rpc.get_account_data(&target.config)?)?;
+    if config.score_oracle != Pubkey::default() || config.stream_slots > 0 || config.decay.is_enabled() {
+        return Err("rsm-loadtest mints against configs without a score oracle, streaming or quality decay".into());
+    }
+
+    let genomes = workload::generate(args.seed, args.genomes, args.genesis_pct);
+    println!("Driving {} genomes at {} TPS with {} workers", genomes.len(), args.tps, args.workers);
+    let (mut outcomes, elapsed) = drive(&target, genomes, args.tps, args.workers);
+    if !args.skip_compute_units {
+        fetch_compute_units(&rpc, &mut outcomes);
+    }
+
+    let report = Report::new(args.tps, elapsed, &outcomes);
+    print!("{}", report);
+    if let Some(path) = args.report {
+        fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+        println!("Report written to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Releases one genome every 1/`tps` seconds to the workers and collects
+/// every outcome, along with the time until the last one finished.
+fn drive(target: &Arc<Target>, genomes: Vec<SyntheticGenome>, tps: f64, workers: usize) -> (Vec<Outcome>, Duration) {
+    let (job_tx, job_rx) = mpsc::channel::<(SyntheticGenome, Instant)>();
+    let job_rx = Arc::new(Mutex::new(job_rx));
+    let (outcome_tx, outcome_rx) = mpsc::channel();
+    let handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let (target, job_rx, outcome_tx) = (target.clone(), job_rx.clone(), outcome_tx.clone());
+            thread::spawn(move || {
+                let rpc = RpcClient::new_with_commitment(target.rpc_url.clone(), CommitmentConfig::confirmed());
+                loop {
+                    let job = job_rx.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv();
+                    let Ok((genome, due)) = job else {
+                        return;
+                    };
+                    let _ = outcome_tx.send(run(&rpc, &target, &genome, due));
+                }
+            })
+        })
+        .collect();
+    drop(outcome_tx);
+
+    let start = Instant::now();
+    let interval = Duration::from_secs_f64(1.0 / tps);
+    let total = genomes.len();
+    for (i, genome) in genomes.into_iter().enumerate() {
+        let due = start + interval.mul_f64(i as f64);
+        if let Some(wait) = due.checked_duration_since(Instant::now()) {
+            thread::sleep(wait);
+        }
+        if (i + 1) % 100 == 0 {
+            println!("Released {}/{}", i + 1, total);
+        }
+        let _ = job_tx.send((genome, due));
+    }
+    drop(job_tx);
+
+    let outcomes: Vec<Outcome> = outcome_rx.iter().collect();
+    for handle in handles {
+        let _ = handle.join();
+    }
+    (outcomes, start.elapsed())
+}
+
+fn run(rpc: &RpcClient, target: &Target, genome: &SyntheticGenome, due: Instant) -> Outcome {
+    let mut attempts = 0;
+    let mut result = Err("not attempted".to_string());
+    while attempts < target.attempts && result.is_err() {
+        attempts += 1;
+        result = match genome.flow {
+            Flow::Mint => mint(rpc, target, genome, attempts),
+            Flow::Genesis => genesis(rpc, target, genome),
+        };
+    }
+    Outcome { flow: genome.flow, latency: due.elapsed(), attempts, result, compute_units: None }
+}
+
+/// Creates the genome account and mints it to the payer in one
+/// transaction against the newest slot in the SlotHashes sysvar.
+fn mint(
+    rpc: &RpcClient,
+    target: &Target,
+    genome: &SyntheticGenome,
+    attempt: u32,
+) -> std::result::Result<Signature, String> {
+    let config = rpc.get_account_data(&target.config).map_err(|err| failure_reason(&err))?;
+    let config = TokenConfig::try_from_slice(&config).map_err(|err| format!("config: {}", err))?;
+    let slot_hashes = rpc.get_account_data(&sysvar::slot_hashes::id()).map_err(|err| failure_reason(&err))?;
+    let (recent_slot, slot_hash) = newest_slot_hash(&slot_hashes).ok_or("SlotHashes sysvar is empty")?;
+    let scores = genome.scores;
+    let amount = scale_amount(
+        calculate_token_amount(
+            &config.agi_params,
+            scores.complexity,
+            scores.uniqueness,
+            scores.entropy,
+            blockchain_entropy(&genome.hash, &slot_hash),
+        ),
+        config.decimals,
+    )
+    .ok_or("amount overflows")?;
+    let amount = EmissionSmoothing::apply(amount, config.smoothing.scale_bps_at(recent_slot + 1));
+
+    let (address, _) = genome_address(&target.program, &target.config, &genome.hash);
+    let payer = target.payer.pubkey();
+    let data = |instruction: RSMInstruction| instruction.try_to_vec().map_err(|err| err.to_string());
+    let create = Instruction::new_with_bytes(
+        target.program,
+        &data(RSMInstruction::CreateGenomeAccount { genome_hash: genome.hash })?,
+        vec![
+            AccountMeta::new(target.config, false),
+            AccountMeta::new(address, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+    let mint = Instruction::new_with_bytes(
+        target.program,
+        &data(RSMInstruction::MintFromGenome(MintArgs {
+            genome_hash: genome.hash,
+            complexity: scores.complexity,
+            uniqueness: scores.uniqueness,
+            entropy: scores.entropy,
+            amount,
+            mint_receipt: false,
+            nonce: genome.index * target.attempts as u64 + attempt as u64,
+            recent_slot,
+            split_bps: Vec::new(),
+            origin: rsm_token::GenomeOrigin::Unknown,
+            species: 0,
+            allowlist_proof: None,
+            auto_stake: false,
+        }))?,
+        vec![
+            AccountMeta::new(target.config, false),
+            AccountMeta::new(address, false),
+            AccountMeta::new(target.mint, false),
+            // The payer is the recipient, so it can co-sign when consent is required
+            AccountMeta::new_readonly(payer, config.require_recipient_signature),
+            AccountMeta::new_readonly(target.controller.pubkey(), true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new(target.dedupe, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+            AccountMeta::new(get_associated_token_address(&payer, &target.mint), false),
+        ],
+    );
+    let create_ata = create_associated_token_account_idempotent(&payer, &payer, &target.mint, &spl_token::id());
+    send(rpc, &[create, create_ata, mint], &[&target.payer, &target.controller])
+}
+
+/// A founder genesis by the payer: commits to the genome, then reveals it
+/// for the engine's next genome id. A commit left by an earlier attempt
+/// of the same genome is revealed instead of committed again.
+fn genesis(rpc: &RpcClient, target: &Target, genome: &SyntheticGenome) -> std::result::Result<Signature, String> {
+    let engine = target.genesis.as_ref().ok_or("genesis is not configured")?;
+    let _commit = engine.commit.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let name = format!("loadtest-{}", genome.index);
+    let salt = hash(name.as_bytes()).to_bytes();
+    let create = CreateGenome {
+        genesis_program: engine.program,
+        pause_program: engine.pause_program,
+        engine: engine.engine,
+        species: engine.species,
+        rna_mint: engine.rna_mint,
+        user_rna_account: engine.rna_account,
+        user: target.payer.pubkey(),
+        parents: Vec::new(),
+        hybrid_permit: None,
+        genesis_event: None,
+        price_feed: engine.price_feed,
+        genesis_guardian: engine.genesis_guardian,
+        guardian: None,
+        donate: false,
+    };
+    let commit = create.instruction(genesis_commitment(&genome.hash, &salt), &name, salt);
+    // The commit PDA is the instruction's third account
+    if rpc.get_account(&commit.accounts[2].pubkey).is_err() {
+        send(rpc, &[commit], &[&target.payer])?;
+    }
+
+    let data = rpc.get_account_data(&engine.engine).map_err(|err| failure_reason(&err))?;
+    let genomes_created = data
+        .get(ENGINE_GENOMES_CREATED_OFFSET..ENGINE_GENOMES_CREATED_OFFSET + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or("genesis engine account is too short")?;
+    let record = Keypair::new();
+    let reveal = RevealGenesis {
+        genesis_program: engine.program,
+        dna_program: engine.dna_program,
+        pause_program: engine.pause_program,
+        engine: engine.engine,
+        genesis_record: record.pubkey(),
+        rna_mint: engine.rna_mint,
+        user: target.payer.pubkey(),
+        genomes_created,
+        mutation_catalog: engine.mutation_catalog,
+        genesis_event: None,
+        donate: false,
+    };
+    send(rpc, &[reveal.instruction(genome.hash, salt)], &[&target.payer, &record])
+}
+
+fn send(rpc: &RpcClient, instructions: &[Instruction], signers: &[&Keypair]) -> std::result::Result<Signature, String> {
+    let blockhash = rpc.get_latest_blockhash().map_err(|err| failure_reason(&err))?;
+    let tx = Transaction::new_signed_with_payer(instructions, Some(&signers[0].pubkey()), signers, blockhash);
+    rpc.send_and_confirm_transaction(&tx).map_err(|err| failure_reason(&err))
+}
+
+/// Groups failures by cause: the transaction error when the cluster
+/// returned one, the kind of RPC failure otherwise.
+fn failure_reason(err: &ClientError) -> String {
+    if let Some(tx_err) = err.get_transaction_error() {
+        return tx_err.to_string();
+    }
+    match err.kind() {
+        ClientErrorKind::Io(_) => "rpc: io".to_string(),
+        ClientErrorKind::Reqwest(_) => "rpc: http".to_string(),
+        ClientErrorKind::RpcError(_) => "rpc: request failed".to_string(),
+        ClientErrorKind::SerdeJson(_) => "rpc: malformed response".to_string(),
+        ClientErrorKind::SigningError(_) => "signing".to_string(),
+        ClientErrorKind::TransactionError(_) => "transaction".to_string(),
+        ClientErrorKind::Custom(message) => message.clone(),
+    }
+}
+
+fn fetch_compute_units(rpc: &RpcClient, outcomes: &mut [Outcome]) {
+    let config = RpcTransactionConfig {
+        commitment: Some(rpc.commitment()),
+        max_supported_transaction_version: Some(0),
+        ..RpcTransactionConfig::default()
+    };
+    for outcome in outcomes {
+        let Ok(signature) = &outcome.result else {
+            continue;
+        };
+        outcome.compute_units = rpc
+            .get_transaction_with_config(signature, config)
+            .ok()
+            .and_then(|tx| tx.transaction.meta)
+            .and_then(|meta| Option::<u64>::from(meta.compute_units_consumed));
+    }
+}
@@ -0,0 +1,182 @@
+//! Latency, compute-unit and failure statistics of a run.
+
+use crate::Flow;
+use serde::Serialize;
+use solana_sdk::signature::Signature;
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+/// What became of one synthetic genome.
+#[derive(Clone, Debug)]
+pub struct Outcome {
+    pub flow: Flow,
+    /// From the operation's scheduled send time, so time spent queued
+    /// behind busy workers counts against it
+    pub latency: Duration,
+    pub attempts: u32,
+    /// The landed transaction, or the last attempt's failure
+    pub result: Result<Signature, String>,
+    /// Filled in after the run, when fetched
+    pub compute_units: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Percentiles {
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+impl Percentiles {
+    /// Nearest-rank percentiles; None without samples.
+    pub fn of(mut samples: Vec<u64>) -> Option<Self> {
+        samples.sort_unstable();
+        let max = *samples.last()?;
+        let rank = |pct: usize| samples[(samples.len() * pct).div_ceil(100).max(1) - 1];
+        Some(Self { p50: rank(50), p90: rank(90), p99: rank(99), max })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FlowReport {
+    pub flow: Flow,
+    pub sent: usize,
+    pub landed: usize,
+    pub failed: usize,
+    /// Attempts beyond the first, landed or not
+    pub retries: u64,
+    /// Of landed operations
+    pub latency_ms: Option<Percentiles>,
+    pub compute_units: Option<Percentiles>,
+    /// Failed operations by their last error
+    pub failures: BTreeMap<String, usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Report {
+    pub target_tps: f64,
+    /// Landed operations per second of the run
+    pub achieved_tps: f64,
+    pub elapsed_secs: f64,
+    pub flows: Vec<FlowReport>,
+}
+
+impl Report {
+    pub fn new(target_tps: f64, elapsed: Duration, outcomes: &[Outcome]) -> Self {
+        let mut by_flow: BTreeMap<Flow, Vec<&Outcome>> = BTreeMap::new();
+        for outcome in outcomes {
+            by_flow.entry(outcome.flow).or_default().push(outcome);
+        }
+        let flows: Vec<FlowReport> = by_flow
+            .into_iter()
+            .map(|(flow, outcomes)| {
+                let landed: Vec<&Outcome> = outcomes.iter().copied().filter(|outcome| outcome.result.is_ok()).collect();
+                let mut failures = BTreeMap::new();
+                for outcome in &outcomes {
+                    if let Err(reason) = &outcome.result {
+                        *failures.entry(reason.clone()).or_default() += 1;
+                    }
+                }
+                FlowReport {
+                    flow,
+                    sent: outcomes.len(),
+                    landed: landed.len(),
+                    failed: outcomes.len() - landed.len(),
+                    retries: outcomes.iter().map(|outcome| outcome.attempts.saturating_sub(1) as u64).sum(),
+                    latency_ms: Percentiles::of(
+                        landed.iter().map(|outcome| outcome.latency.as_millis() as u64).collect(),
+                    ),
+                    compute_units: Percentiles::of(landed.iter().filter_map(|outcome| outcome.compute_units).collect()),
+                    failures,
+                }
+            })
+            .collect();
+        let landed: usize = flows.iter().map(|flow| flow.landed).sum();
+        let elapsed_secs = elapsed.as_secs_f64();
+        Self {
+            target_tps,
+            achieved_tps: if elapsed_secs > 0.0 { landed as f64 / elapsed_secs } else { 0.0 },
+            elapsed_secs,
+            flows,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:.1}s at {:.1} TPS landed (target {:.1})",
+            self.elapsed_secs, self.achieved_tps, self.target_tps
+        )?;
+        let show = |percentiles: Option<Percentiles>| match percentiles {
+            Some(p) => format!("p50 {} / p90 {} / p99 {} / max {}", p.p50, p.p90, p.p99, p.max),
+            None => "-".to_string(),
+        };
+        for flow in &self.flows {
+            writeln!(
+                f,
+                "{:?}: {} sent, {} landed, {} failed, {} retries",
+                flow.flow, flow.sent, flow.landed, flow.failed, flow.retries
+            )?;
+            writeln!(f, "  latency ms     {}", show(flow.latency_ms))?;
+            writeln!(f, "  compute units  {}", show(flow.compute_units))?;
+            for (reason, count) in &flow.failures {
+                writeln!(f, "  {:>6} x {}", count, reason)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(
+        flow: Flow,
+        latency_ms: u64,
+        attempts: u32,
+        result: Result<Signature, &str>,
+        units: Option<u64>,
+    ) -> Outcome {
+        Outcome {
+            flow,
+            latency: Duration::from_millis(latency_ms),
+            attempts,
+            result: result.map_err(str::to_string),
+            compute_units: units,
+        }
+    }
+
+    #[test]
+    fn test_percentiles() {
+        assert_eq!(Percentiles::of(Vec::new()), None);
+        assert_eq!(Percentiles::of(vec![5]), Some(Percentiles { p50: 5, p90: 5, p99: 5, max: 5 }));
+        let p = Percentiles::of((1..=100).rev().collect()).unwrap();
+        assert_eq!(p, Percentiles { p50: 50, p90: 90, p99: 99, max: 100 });
+    }
+
+    #[test]
+    fn test_report() {
+        let ok = Ok(Signature::default());
+        let outcomes = [
+            outcome(Flow::Mint, 400, 1, ok, Some(30_000)),
+            outcome(Flow::Mint, 900, 2, ok, None),
+            outcome(Flow::Mint, 2_000, 3, Err("custom program error: 0x1c"), None),
+            outcome(Flow::Genesis, 700, 1, ok, Some(90_000)),
+            outcome(Flow::Genesis, 100, 1, Err("rpc: io"), None),
+        ];
+        let report = Report::new(10.0, Duration::from_secs(2), &outcomes);
+        assert_eq!(report.achieved_tps, 1.5);
+
+        let mint = &report.flows[0];
+        assert_eq!((mint.flow, mint.sent, mint.landed, mint.failed, mint.retries), (Flow::Mint, 3, 2, 1, 3));
+        assert_eq!(mint.latency_ms.unwrap().max, 900);
+        assert_eq!(mint.compute_units.unwrap().p50, 30_000);
+        assert_eq!(mint.failures["custom program error: 0x1c"], 1);
+        let genesis = &report.flows[1];
+        assert_eq!((genesis.landed, genesis.failed), (1, 1));
+        assert!(report.to_string().contains("Genesis: 2 sent, 1 landed"));
+    }
+}
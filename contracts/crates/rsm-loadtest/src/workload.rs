@@ -0,0 +1,77 @@
+//! Synthetic genomes, generated deterministically from a seed.
+//!
+//! Sequences vary in length and in how strongly one base dominates, so the
+//! entropy scorer spreads them across the reward tiers instead of scoring
+//! every genome alike.
+
+use rsm_core::SeedRng;
+use rsm_keeper::{scoring::EntropyScorer, GenomeInput, Scorer, Scores};
+use serde::Serialize;
+use solana_sdk::hash::hashv;
+
+pub const MIN_SEQUENCE_LEN: usize = 64;
+pub const MAX_SEQUENCE_LEN: usize = 512;
+const BASES: &[u8; 4] = b"ACGT";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Flow {
+    /// CreateGenomeAccount and MintFromGenome on rsm-token
+    Mint,
+    /// Founder `create_genome` and `reveal_genesis` on genesis-engine
+    Genesis,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntheticGenome {
+    pub index: u64,
+    pub hash: [u8; 32],
+    pub scores: Scores,
+    pub flow: Flow,
+}
+
+/// `count` genomes from `seed`, each sent through genesis with
+/// probability `genesis_pct`% and minted otherwise.
+pub fn generate(seed: u64, count: usize, genesis_pct: u8) -> Vec<SyntheticGenome> {
+    let mut rng = SeedRng::new(&hashv(&[b"rsm-loadtest", &seed.to_le_bytes()]).to_bytes());
+    let scorer = EntropyScorer::default();
+    (0..count as u64)
+        .map(|index| {
+            let len = MIN_SEQUENCE_LEN + rng.below((MAX_SEQUENCE_LEN - MIN_SEQUENCE_LEN + 1) as u64) as usize;
+            // The favored base takes 0 to 3 of every 4 draws on top of its share
+            let favored = BASES[rng.below(4) as usize];
+            let bias = rng.below(4);
+            let sequence: String = (0..len)
+                .map(|_| if rng.below(4) < bias { favored } else { BASES[rng.below(4) as usize] } as char)
+                .collect();
+            let hash = hashv(&[b"rsm-loadtest-genome", &seed.to_le_bytes(), &index.to_le_bytes()]).to_bytes();
+            let scores = scorer
+                .score(&GenomeInput { genome_hash: hash, sequence: Some(sequence) })
+                .expect("sequences are longer than a k-mer");
+            let flow = if rng.below(100) < genesis_pct as u64 { Flow::Genesis } else { Flow::Mint };
+            SyntheticGenome { index, hash, scores, flow }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_generate() {
+        let genomes = generate(7, 1_000, 20);
+        assert_eq!(genomes, generate(7, 1_000, 20));
+        assert_ne!(genomes[0].hash, generate(8, 1, 20)[0].hash);
+        assert_eq!(genomes.iter().map(|genome| genome.hash).collect::<HashSet<_>>().len(), 1_000);
+        assert!(genomes.iter().all(|genome| genome.scores.validate().is_ok()));
+
+        // Scores spread out rather than clustering on one tier
+        let complexities: HashSet<u8> = genomes.iter().map(|genome| genome.scores.complexity).collect();
+        assert!(complexities.len() > 10);
+        let geneses = genomes.iter().filter(|genome| genome.flow == Flow::Genesis).count();
+        assert!((150..250).contains(&geneses));
+        assert!(generate(7, 100, 0).iter().all(|genome| genome.flow == Flow::Mint));
+    }
+}
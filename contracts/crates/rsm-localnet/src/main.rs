@@ -0,0 +1,466 @@
+//! rsm-localnet: one-command local environment for frontend and keeper work.
+//!
+//! Starts solana-test-validator with the built programs preloaded, creates the
+//! RSM/DNA/RNA mints and the rsm-token config, mints a handful of
+//! representative genomes and writes every address to a JSON manifest.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use clap::Parser;
+use rsm_token::{
+    blockchain_entropy, calculate_token_amount, config_address, genome_address, newest_slot_hash, AGIParameters,
+    GenomeData, GenomeOrigin, MintArgs, RSMInstruction, DECIMALS, DEDUPE_SEED, MAX_SUPPLY,
+};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, write_keypair_file, Keypair, Signer},
+    system_instruction, system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account_idempotent};
+use spl_token::instruction::AuthorityType;
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Programs preloaded into the validator: (lib name, build output dir, required).
+/// Anchor programs share the `contracts` workspace target directory.
+const PROGRAMS: &[(&str, &str, bool)] = &[
+    (
+        "rsm_token",
+        "rsm-token-solana/programs/rsm-token/target/deploy",
+        true,
+    ),
+    ("rsm_pause", "contracts/target/deploy", true),
+    ("rsm_dna_token", "contracts/target/deploy", true),
+    ("agi_rna_token", "contracts/target/deploy", true),
+    ("genesis_engine", "contracts/target/deploy", false),
+    ("genome_marketplace", "contracts/target/deploy", false),
+    ("rsm_treasury", "contracts/target/deploy", false),
+    ("consciousness_oracle", "contracts/target/deploy", false),
+    ("rsm_multisig", "contracts/target/deploy", false),
+    ("rsm_faucet", "contracts/target/deploy", false),
+];
+
+/// (complexity, uniqueness, entropy) for the fixture genomes, cycled.
+/// Covers the 150% tier, the 125% tier and the base rate.
+const GENOME_PROFILES: &[(u8, u8, u16)] = &[(95, 92, 800), (84, 81, 600), (60, 45, 300)];
+
+const MINT_ATTEMPTS: u64 = 20;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "rsm-localnet",
+    about = "Start a seeded local validator for RSM development"
+)]
+struct Args {
+    /// Repository root containing the program build outputs
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+    /// Directory for the ledger, keypairs and manifest
+    #[arg(long, default_value = ".rsm-localnet")]
+    out_dir: PathBuf,
+    #[arg(long, default_value_t = 8899)]
+    rpc_port: u16,
+    /// Number of representative genomes to mint
+    #[arg(long, default_value_t = 3)]
+    genomes: usize,
+    /// Stop the validator once the manifest is written instead of keeping it running
+    #[arg(long)]
+    exit: bool,
+}
+
+#[derive(Serialize)]
+struct Manifest {
+    rpc_url: String,
+    payer_keypair: String,
+    agi_controller_keypair: String,
+    programs: BTreeMap<String, String>,
+    mints: BTreeMap<String, String>,
+    rsm_token: RsmTokenFixture,
+    genomes: Vec<GenomeFixture>,
+}
+
+#[derive(Serialize)]
+struct RsmTokenFixture {
+    config: String,
+    dedupe: String,
+    authority: String,
+    agi_controller: String,
+    max_supply: u64,
+}
+
+#[derive(Serialize)]
+struct GenomeFixture {
+    hash: String,
+    address: String,
+    owner: String,
+    complexity: u8,
+    uniqueness: u8,
+    entropy: u16,
+    amount: u64,
+    signature: String,
+}
+
+/// Kills the validator on drop so a failed setup never leaves it running.
+struct Validator(Child);
+
+impl Drop for Validator {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    fs::create_dir_all(&args.out_dir)?;
+
+    let programs = locate_programs(&args.root)?;
+    let mut validator = start_validator(&args, &programs)?;
+
+    let rpc_url = format!("http://127.0.0.1:{}", args.rpc_port);
+    let rpc = RpcClient::new_with_commitment(rpc_url.clone(), CommitmentConfig::confirmed());
+    wait_for_health(&rpc, Duration::from_secs(60))?;
+
+    let payer = Keypair::new();
+    let controller = Keypair::new();
+    airdrop(&rpc, &payer.pubkey(), 100 * LAMPORTS_PER_SOL)?;
+
+    let rsm_program = programs["rsm_token"].0;
+    let rsm_mint = create_mint(&rpc, &payer, DECIMALS)?;
+    let dna_mint = create_mint(&rpc, &payer, 0)?;
+    let rna_mint = create_mint(&rpc, &payer, 9)?;
+
+    let (config, dedupe) =
+        init_rsm_token(&rpc, &rsm_program, &payer, &controller.pubkey(), &rsm_mint)?;
+
+    let mut genomes = Vec::with_capacity(args.genomes);
+    for i in 0..args.genomes {
+        let profile = GENOME_PROFILES[i % GENOME_PROFILES.len()];
+        genomes.push(mint_genome(
+            &rpc,
+            &rsm_program,
+            &config,
+            &dedupe,
+            &rsm_mint,
+            &payer,
+            &controller,
+            i,
+            profile,
+        )?);
+    }
+
+    let payer_path = args.out_dir.join("payer.json");
+    let controller_path = args.out_dir.join("agi-controller.json");
+    write_keypair_file(&payer, &payer_path)?;
+    write_keypair_file(&controller, &controller_path)?;
+
+    let manifest = Manifest {
+        rpc_url,
+        payer_keypair: payer_path.display().to_string(),
+        agi_controller_keypair: controller_path.display().to_string(),
+        programs: programs
+            .iter()
+            .map(|(name, (id, _))| (name.to_string(), id.to_string()))
+            .collect(),
+        mints: BTreeMap::from([
+            ("rsm".to_string(), rsm_mint.to_string()),
+            ("dna".to_string(), dna_mint.to_string()),
+            ("rna".to_string(), rna_mint.to_string()),
+        ]),
+        rsm_token: RsmTokenFixture {
+            config: config.to_string(),
+            dedupe: dedupe.to_string(),
+            authority: payer.pubkey().to_string(),
+            agi_controller: controller.pubkey().to_string(),
+            max_supply: MAX_SUPPLY,
+        },
+        genomes,
+    };
+    let manifest_path = args.out_dir.join("manifest.json");
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+    println!("Manifest written to {}", manifest_path.display());
+
+    if args.exit {
+        return Ok(());
+    }
+
+    println!("Validator running on {}, Ctrl-C to stop", manifest.rpc_url);
+    validator.0.wait()?;
+    Ok(())
+}
+
+/// Resolve program ids from the `<lib>-keypair.json` written next to each
+/// `.so`, so Anchor's `declare_id!` matches the preloaded address.
+fn locate_programs(root: &Path) -> Result<BTreeMap<&'static str, (Pubkey, PathBuf)>> {
+    let mut found = BTreeMap::new();
+    for (name, dir, required) in PROGRAMS {
+        let dir = root.join(dir);
+        let so = dir.join(format!("{}.so", name));
+        let keypair = dir.join(format!("{}-keypair.json", name));
+        if !so.exists() || !keypair.exists() {
+            if *required {
+                return Err(format!("{} not built: expected {}", name, so.display()).into());
+            }
+            println!("Skipping {}: not built", name);
+            continue;
+        }
+        let id = read_keypair_file(&keypair)?.pubkey();
+        found.insert(*name, (id, so));
+    }
+    Ok(found)
+}
+
+fn start_validator(args: &Args, programs: &BTreeMap<&str, (Pubkey, PathBuf)>) -> Result<Validator> {
+    let mut cmd = Command::new("solana-test-validator");
+    cmd.arg("--reset")
+        .arg("--quiet")
+        .arg("--ledger")
+        .arg(args.out_dir.join("ledger"))
+        .arg("--rpc-port")
+        .arg(args.rpc_port.to_string());
+    for (id, so) in programs.values() {
+        cmd.arg("--bpf-program").arg(id.to_string()).arg(so);
+    }
+    let child = cmd
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to start solana-test-validator: {}", e))?;
+    Ok(Validator(child))
+}
+
+fn wait_for_health(rpc: &RpcClient, timeout: Duration) -> Result<()> {
+    let start = Instant::now();
+    while rpc.get_health().is_err() {
+        if start.elapsed() > timeout {
+            return Err("validator did not become healthy".into());
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+    Ok(())
+}
+
+fn airdrop(rpc: &RpcClient, to: &Pubkey, lamports: u64) -> Result<()> {
+    let signature = rpc.request_airdrop(to, lamports)?;
+    while !rpc.confirm_transaction(&signature)? {
+        thread::sleep(Duration::from_millis(200));
+    }
+    Ok(())
+}
+
+fn send(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> Result<String> {
+    let mut all: Vec<&Keypair> = vec![payer];
+    all.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all,
+        rpc.get_latest_blockhash()?,
+    );
+    Ok(rpc.send_and_confirm_transaction(&tx)?.to_string())
+}
+
+fn create_account_ix(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    account: &Pubkey,
+    space: usize,
+    owner: &Pubkey,
+) -> Result<Instruction> {
+    let lamports = rpc.get_minimum_balance_for_rent_exemption(space)?;
+    Ok(system_instruction::create_account(
+        payer,
+        account,
+        lamports,
+        space as u64,
+        owner,
+    ))
+}
+
+fn create_mint(rpc: &RpcClient, payer: &Keypair, decimals: u8) -> Result<Pubkey> {
+    let mint = Keypair::new();
+    let instructions = [
+        create_account_ix(
+            rpc,
+            &payer.pubkey(),
+            &mint.pubkey(),
+            spl_token::state::Mint::LEN,
+            &spl_token::id(),
+        )?,
+        spl_token::instruction::initialize_mint2(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &payer.pubkey(),
+            None,
+            decimals,
+        )?,
+    ];
+    send(rpc, &instructions, payer, &[&mint])?;
+    Ok(mint.pubkey())
+}
+
+fn init_rsm_token(
+    rpc: &RpcClient,
+    program: &Pubkey,
+    payer: &Keypair,
+    controller: &Pubkey,
+    mint: &Pubkey,
+) -> Result<(Pubkey, Pubkey)> {
+    let (config, _) = config_address(program, mint);
+    let (dedupe, _) = Pubkey::find_program_address(&[DEDUPE_SEED, config.as_ref()], program);
+
+    let initialize = Instruction::new_with_bytes(
+        *program,
+        &RSMInstruction::Initialize {
+            max_supply: MAX_SUPPLY,
+            agi_controller: *controller,
+            decimals: DECIMALS,
+        }
+        .try_to_vec()?,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+    let create_dedupe = Instruction::new_with_bytes(
+        *program,
+        &RSMInstruction::CreateDedupeBuffer.try_to_vec()?,
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(dedupe, false),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+    // Mints pay out by CPI, signed by the config
+    let hand_over = spl_token::instruction::set_authority(
+        &spl_token::id(),
+        mint,
+        Some(&config),
+        AuthorityType::MintTokens,
+        &payer.pubkey(),
+        &[],
+    )?;
+    send(rpc, &[initialize, create_dedupe, hand_over], payer, &[])?;
+    Ok((config, dedupe))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn mint_genome(
+    rpc: &RpcClient,
+    program: &Pubkey,
+    config: &Pubkey,
+    dedupe: &Pubkey,
+    mint: &Pubkey,
+    payer: &Keypair,
+    controller: &Keypair,
+    index: usize,
+    (complexity, uniqueness, entropy): (u8, u8, u16),
+) -> Result<GenomeFixture> {
+    let genome_hash = hash(format!("rsm-localnet-genome-{}", index).as_bytes()).to_bytes();
+    let (genome, _) = genome_address(program, config, &genome_hash);
+
+    send(
+        rpc,
+        &[Instruction::new_with_bytes(
+            *program,
+            &RSMInstruction::CreateGenomeAccount { genome_hash }.try_to_vec()?,
+            vec![
+                AccountMeta::new(*config, false),
+                AccountMeta::new(genome, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        )],
+        payer,
+        &[],
+    )?;
+
+    // The amount is priced off the newest slot hash; retry with a fresh
+    // nonce should that slot be forked away before the mint lands
+    let params = AGIParameters::default();
+    for attempt in 0..MINT_ATTEMPTS {
+        let slot_hashes = rpc.get_account_data(&sysvar::slot_hashes::id())?;
+        let (recent_slot, slot_hash) = newest_slot_hash(&slot_hashes).ok_or("SlotHashes sysvar is empty")?;
+        let amount = calculate_token_amount(
+            &params,
+            complexity,
+            uniqueness,
+            entropy,
+            blockchain_entropy(&genome_hash, &slot_hash),
+        );
+        let mint_ix = Instruction::new_with_bytes(
+            *program,
+            &RSMInstruction::MintFromGenome(MintArgs {
+                genome_hash,
+                complexity,
+                uniqueness,
+                entropy,
+                amount,
+                mint_receipt: false,
+                nonce: index as u64 * MINT_ATTEMPTS + attempt + 1,
+                recent_slot,
+                split_bps: Vec::new(),
+                origin: GenomeOrigin::Unknown,
+                species: 0,
+                allowlist_proof: None,
+                auto_stake: false,
+            })
+            .try_to_vec()?,
+            vec![
+                AccountMeta::new(*config, false),
+                AccountMeta::new(genome, false),
+                AccountMeta::new(*mint, false),
+                AccountMeta::new_readonly(payer.pubkey(), false),
+                AccountMeta::new_readonly(controller.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new(*dedupe, false),
+                AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+                AccountMeta::new(get_associated_token_address(&payer.pubkey(), mint), false),
+            ],
+        );
+        let create_ata = create_associated_token_account_idempotent(&payer.pubkey(), &payer.pubkey(), mint, &spl_token::id());
+        let Ok(signature) = send(rpc, &[create_ata, mint_ix], payer, &[controller]) else {
+            continue;
+        };
+        let data = GenomeData::try_from_slice(&rpc.get_account_data(&genome)?)?;
+        return Ok(GenomeFixture {
+            hash: genome_hash.iter().map(|b| format!("{:02x}", b)).collect(),
+            address: genome.to_string(),
+            owner: data.owner.to_string(),
+            complexity,
+            uniqueness,
+            entropy,
+            amount: data.tokens_minted,
+            signature,
+        });
+    }
+    Err(format!(
+        "genome {} did not mint after {} attempts",
+        index, MINT_ATTEMPTS
+    )
+    .into())
+}
@@ -0,0 +1,337 @@
+//! Prometheus metrics shared by the RSM off-chain services.
+//!
+//! `PipelineMetrics` holds the series operators alert on: mints landed,
+//! tokens emitted, genesis events seen, errors by kind, compute units per
+//! transaction, RPC latency per method and the time of the last success.
+//! A pipeline that stops advancing `rsm_last_success_timestamp_seconds`
+//! while `rsm_errors_total` climbs is stuck. `serve` exposes the text
+//! format on `GET /metrics`; every series is always present, so a service
+//! that never mints simply reports zero.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicI64, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Seconds
+pub const LATENCY_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+pub const COMPUTE_UNIT_BUCKETS: &[f64] =
+    &[5_000.0, 10_000.0, 25_000.0, 50_000.0, 100_000.0, 200_000.0, 400_000.0, 800_000.0, 1_400_000.0];
+
+#[derive(Debug, Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    pub fn add(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Gauge(AtomicI64);
+
+impl Gauge {
+    pub fn set(&self, value: i64) {
+        self.0.store(value, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Counters keyed by one label value.
+#[derive(Debug, Default)]
+pub struct CounterVec(Mutex<BTreeMap<String, u64>>);
+
+impl CounterVec {
+    pub fn inc(&self, label: &str) {
+        *self.0.lock().unwrap().entry(label.to_string()).or_default() += 1;
+    }
+
+    pub fn get(&self, label: &str) -> u64 {
+        self.0.lock().unwrap().get(label).copied().unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Buckets {
+    counts: Vec<u64>,
+    count: u64,
+    sum: f64,
+}
+
+impl Buckets {
+    fn new(bounds: &[f64]) -> Self {
+        Self { counts: vec![0; bounds.len()], count: 0, sum: 0.0 }
+    }
+
+    fn observe(&mut self, bounds: &[f64], value: f64) {
+        // Stored per bucket; rendering makes them cumulative
+        if let Some(index) = bounds.iter().position(|bound| value <= *bound) {
+            self.counts[index] += 1;
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+
+    fn render(&self, out: &mut String, name: &str, bounds: &[f64], labels: &str) {
+        let mut cumulative = 0;
+        for (bound, count) in bounds.iter().zip(&self.counts) {
+            cumulative += count;
+            let _ = writeln!(out, "{}_bucket{{{}le=\"{}\"}} {}", name, labels, bound, cumulative);
+        }
+        let _ = writeln!(out, "{}_bucket{{{}le=\"+Inf\"}} {}", name, labels, self.count);
+        let labels = labels.trim_end_matches(',');
+        let labels = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+        let _ = writeln!(out, "{}_sum{} {}", name, labels, self.sum);
+        let _ = writeln!(out, "{}_count{} {}", name, labels, self.count);
+    }
+}
+
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    buckets: Mutex<Buckets>,
+}
+
+impl Histogram {
+    pub fn new(bounds: &'static [f64]) -> Self {
+        Self { bounds, buckets: Mutex::new(Buckets::new(bounds)) }
+    }
+
+    pub fn observe(&self, value: f64) {
+        self.buckets.lock().unwrap().observe(self.bounds, value);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.buckets.lock().unwrap().count
+    }
+}
+
+/// Histograms keyed by one label value.
+#[derive(Debug)]
+pub struct HistogramVec {
+    bounds: &'static [f64],
+    buckets: Mutex<BTreeMap<String, Buckets>>,
+}
+
+impl HistogramVec {
+    pub fn new(bounds: &'static [f64]) -> Self {
+        Self { bounds, buckets: Mutex::new(BTreeMap::new()) }
+    }
+
+    pub fn observe(&self, label: &str, value: f64) {
+        self.buckets
+            .lock()
+            .unwrap()
+            .entry(label.to_string())
+            .or_insert_with(|| Buckets::new(self.bounds))
+            .observe(self.bounds, value);
+    }
+
+    pub fn count(&self, label: &str) -> u64 {
+        self.buckets.lock().unwrap().get(label).map_or(0, |buckets| buckets.count)
+    }
+}
+
+#[derive(Debug)]
+pub struct PipelineMetrics {
+    pub mints: Counter,
+    /// Token base units
+    pub emitted: Counter,
+    pub genesis: Counter,
+    /// By error kind
+    pub errors: CounterVec,
+    pub compute_units: Histogram,
+    /// By RPC method
+    pub rpc_latency: HistogramVec,
+    pub last_success: Gauge,
+}
+
+impl Default for PipelineMetrics {
+    fn default() -> Self {
+        Self {
+            mints: Counter::default(),
+            emitted: Counter::default(),
+            genesis: Counter::default(),
+            errors: CounterVec::default(),
+            compute_units: Histogram::new(COMPUTE_UNIT_BUCKETS),
+            rpc_latency: HistogramVec::new(LATENCY_BUCKETS),
+            last_success: Gauge::default(),
+        }
+    }
+}
+
+impl PipelineMetrics {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub fn record_mint(&self, amount: u64) {
+        self.mints.inc();
+        self.emitted.add(amount);
+        self.touch();
+    }
+
+    pub fn record_genesis(&self) {
+        self.genesis.inc();
+        self.touch();
+    }
+
+    /// Marks forward progress for stuck-pipeline alerts.
+    pub fn touch(&self) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+        self.last_success.set(now);
+    }
+
+    /// Runs an RPC call and records its latency under `method`.
+    pub fn time_rpc<T>(&self, method: &str, call: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = call();
+        self.rpc_latency.observe(method, start.elapsed().as_secs_f64());
+        result
+    }
+
+    /// Text exposition format 0.0.4.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let header = |out: &mut String, name: &str, kind: &str, help: &str| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        };
+
+        header(&mut out, "rsm_mints_total", "counter", "Genome mints landed");
+        let _ = writeln!(out, "rsm_mints_total {}", self.mints.get());
+        header(&mut out, "rsm_emitted_total", "counter", "RSM emitted by landed mints, in base units");
+        let _ = writeln!(out, "rsm_emitted_total {}", self.emitted.get());
+        header(&mut out, "rsm_genesis_total", "counter", "Genesis events processed");
+        let _ = writeln!(out, "rsm_genesis_total {}", self.genesis.get());
+
+        header(&mut out, "rsm_errors_total", "counter", "Errors by kind");
+        for (kind, count) in self.errors.0.lock().unwrap().iter() {
+            let _ = writeln!(out, "rsm_errors_total{{kind=\"{}\"}} {}", escape(kind), count);
+        }
+
+        header(&mut out, "rsm_compute_units", "histogram", "Compute units consumed per transaction");
+        self.compute_units.buckets.lock().unwrap().render(&mut out, "rsm_compute_units", COMPUTE_UNIT_BUCKETS, "");
+
+        header(&mut out, "rsm_rpc_latency_seconds", "histogram", "RPC request latency by method");
+        for (method, buckets) in self.rpc_latency.buckets.lock().unwrap().iter() {
+            let labels = format!("method=\"{}\",", escape(method));
+            buckets.render(&mut out, "rsm_rpc_latency_seconds", self.rpc_latency.bounds, &labels);
+        }
+
+        header(&mut out, "rsm_last_success_timestamp_seconds", "gauge", "Unix time of the last landed mint or genesis");
+        let _ = writeln!(out, "rsm_last_success_timestamp_seconds {}", self.last_success.get());
+        out
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Serves `GET /metrics` from a background thread; returns the bound address.
+pub fn serve(addr: impl ToSocketAddrs, metrics: Arc<PipelineMetrics>) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local = listener.local_addr()?;
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // A misbehaving scraper only fails its own request
+            let _ = respond(stream, &metrics);
+        }
+    });
+    Ok(local)
+}
+
+fn respond(stream: TcpStream, metrics: &PipelineMetrics) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+    let (status, body) = if request_line.starts_with("GET ") && path.split('?').next() == Some("/metrics") {
+        ("200 OK", metrics.render())
+    } else {
+        ("404 Not Found", "not found\n".to_string())
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_render_exposition_format() {
+        let metrics = PipelineMetrics::default();
+        metrics.record_mint(1_500);
+        metrics.record_mint(500);
+        metrics.errors.inc("expired");
+        metrics.errors.inc("expired");
+        metrics.compute_units.observe(30_000.0);
+        metrics.rpc_latency.observe("getSlot", 0.02);
+        metrics.rpc_latency.observe("getSlot", 3.0);
+        assert_eq!(metrics.time_rpc("sendTransaction", || 7), 7);
+
+        let text = metrics.render();
+        assert!(text.contains("rsm_mints_total 2\n"));
+        assert!(text.contains("rsm_emitted_total 2000\n"));
+        assert!(text.contains("rsm_genesis_total 0\n"));
+        assert!(text.contains("rsm_errors_total{kind=\"expired\"} 2\n"));
+        assert!(text.contains("rsm_compute_units_bucket{le=\"25000\"} 0\n"));
+        assert!(text.contains("rsm_compute_units_bucket{le=\"50000\"} 1\n"));
+        assert!(text.contains("rsm_compute_units_count 1\n"));
+        // Buckets are cumulative
+        assert!(text.contains("rsm_rpc_latency_seconds_bucket{method=\"getSlot\",le=\"0.025\"} 1\n"));
+        assert!(text.contains("rsm_rpc_latency_seconds_bucket{method=\"getSlot\",le=\"5\"} 2\n"));
+        assert!(text.contains("rsm_rpc_latency_seconds_bucket{method=\"getSlot\",le=\"+Inf\"} 2\n"));
+        assert!(text.contains("rsm_rpc_latency_seconds_sum{method=\"getSlot\"} 3.02\n"));
+        assert_eq!(metrics.rpc_latency.count("sendTransaction"), 1);
+        assert!(metrics.last_success.get() > 0);
+    }
+
+    #[test]
+    fn test_serve_metrics_endpoint() {
+        let metrics = PipelineMetrics::new();
+        metrics.record_genesis();
+        let addr = serve("127.0.0.1:0", metrics.clone()).unwrap();
+
+        let get = |path: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let response = get("/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("rsm_genesis_total 1\n"));
+        assert!(get("/").starts_with("HTTP/1.1 404"));
+    }
+}
@@ -0,0 +1,131 @@
+//! Versioned account layouts shared by the programs.
+//!
+//! A migrated account starts with a one-byte schema version. A program
+//! describes each account type as a `Schema`: the upgrade steps from every
+//! past version to the next, and how to recognise data written before the
+//! account carried a version at all (version 0). `migrate` runs the steps
+//! the stored version still needs, so a layout change ships as one more
+//! step plus a sweep of `MigrateAccount` calls instead of a redeploy that
+//! strands existing accounts. The crate has no dependencies and builds for
+//! the Solana target.
+
+use std::fmt;
+
+/// Rewrites data of one version as the next one, version byte included.
+pub type Upgrade = fn(&[u8]) -> Result<Vec<u8>, MigrationError>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigrationError {
+    /// Stored version is newer than the program knows
+    UnknownVersion(u8),
+    /// Data does not fit the layout of its version
+    Malformed,
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::UnknownVersion(version) => write!(f, "unknown schema version {}", version),
+            MigrationError::Malformed => write!(f, "account data does not match its schema version"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+pub struct Schema {
+    pub name: &'static str,
+    /// `upgrades[i]` turns version `i` into version `i + 1`; the current
+    /// version is the number of steps
+    pub upgrades: &'static [Upgrade],
+    /// Recognises version 0 data, which has no version byte
+    pub is_unversioned: fn(&[u8]) -> bool,
+}
+
+impl Schema {
+    pub const fn current(&self) -> u8 {
+        self.upgrades.len() as u8
+    }
+
+    pub fn version_of(&self, data: &[u8]) -> Result<u8, MigrationError> {
+        if (self.is_unversioned)(data) {
+            return Ok(0);
+        }
+        match data.first() {
+            Some(&version) if version > self.current() => Err(MigrationError::UnknownVersion(version)),
+            Some(&version) if version > 0 => Ok(version),
+            _ => Err(MigrationError::Malformed),
+        }
+    }
+
+    pub fn needs_migration(&self, data: &[u8]) -> bool {
+        self.version_of(data).is_ok_and(|version| version < self.current())
+    }
+
+    /// The data upgraded to the current version, or `None` if it already is.
+    pub fn migrate(&self, data: &[u8]) -> Result<Option<Vec<u8>>, MigrationError> {
+        let from = self.version_of(data)?;
+        if from == self.current() {
+            return Ok(None);
+        }
+        let mut upgraded = data.to_vec();
+        for (version, upgrade) in self.upgrades.iter().enumerate().skip(from as usize) {
+            upgraded = upgrade(&upgraded)?;
+            if upgraded.first() != Some(&(version as u8 + 1)) {
+                return Err(MigrationError::Malformed);
+            }
+        }
+        Ok(Some(upgraded))
+    }
+}
+
+/// The usual first step: version 0 data gains the version byte and is
+/// otherwise unchanged.
+pub fn add_version_byte(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    let mut versioned = Vec::with_capacity(data.len() + 1);
+    versioned.push(1);
+    versioned.extend_from_slice(data);
+    Ok(versioned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // v0: [a, b] without a version byte; v1 adds it; v2 appends a zeroed u16
+    fn append_u16(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+        if data.len() != 3 {
+            return Err(MigrationError::Malformed);
+        }
+        let mut upgraded = data.to_vec();
+        upgraded[0] = 2;
+        upgraded.extend_from_slice(&[0, 0]);
+        Ok(upgraded)
+    }
+
+    const SCHEMA: Schema = Schema {
+        name: "Example",
+        upgrades: &[add_version_byte, append_u16],
+        is_unversioned: |data| data.len() == 2,
+    };
+
+    #[test]
+    fn test_migrate_through_every_step() {
+        assert_eq!(SCHEMA.current(), 2);
+        assert_eq!(SCHEMA.version_of(&[7, 8]), Ok(0));
+        assert_eq!(SCHEMA.migrate(&[7, 8]), Ok(Some(vec![2, 7, 8, 0, 0])));
+        assert_eq!(SCHEMA.migrate(&[1, 7, 8]), Ok(Some(vec![2, 7, 8, 0, 0])));
+        assert_eq!(SCHEMA.migrate(&[2, 7, 8, 0, 0]), Ok(None));
+        assert!(SCHEMA.needs_migration(&[1, 7, 8]));
+        assert!(!SCHEMA.needs_migration(&[2, 7, 8, 0, 0]));
+    }
+
+    #[test]
+    fn test_rejects_unknown_and_malformed() {
+        assert_eq!(SCHEMA.migrate(&[3, 7, 8, 0, 0]), Err(MigrationError::UnknownVersion(3)));
+        assert_eq!(SCHEMA.migrate(&[0, 7, 8]), Err(MigrationError::Malformed));
+        assert_eq!(SCHEMA.migrate(&[1, 7, 8, 9]), Err(MigrationError::Malformed));
+        assert_eq!(SCHEMA.migrate(&[]), Err(MigrationError::Malformed));
+        assert!(!SCHEMA.needs_migration(&[9]));
+    }
+}
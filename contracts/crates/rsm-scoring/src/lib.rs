@@ -0,0 +1,285 @@
+//! Reference scoring of DNA sequences into rsm-token's mint inputs.
+//!
+//! `score` turns a nucleotide sequence into the `complexity` (0-100),
+//! `uniqueness` (0-100) and `entropy` (0-200) a mint submits:
+//!
+//! - complexity is how well the sequence resists compression, the size of
+//!   its Lempel-Ziv encoding as a share of the raw sequence;
+//! - uniqueness is the k-mer Jaccard distance to the nearest sequence in a
+//!   local `KmerIndex`, so a genome nobody has scored before gets 100;
+//! - entropy is the Shannon entropy per base in hundredths of a bit.
+//!
+//! Every step is exact except two logarithms whose results are rounded to
+//! whole scores, so any implementation following these rules reproduces
+//! the golden vectors in the tests. The crate has no dependencies.
+
+use std::{
+    collections::HashSet,
+    fmt,
+};
+
+pub const MAX_COMPLEXITY: u8 = 100;
+pub const MAX_UNIQUENESS: u8 = 100;
+pub const MAX_ENTROPY: u16 = 200;
+pub const DEFAULT_KMER_LEN: usize = 8;
+/// k-mers are packed 2 bits per base into a `u64`
+pub const MAX_KMER_LEN: usize = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScoringError {
+    /// Not one of A, C, G or T; `position` counts bases, not whitespace
+    InvalidBase { position: usize, base: char },
+    /// Too short to hold a single k-mer
+    TooShort { len: usize, kmer_len: usize },
+    InvalidKmerLen(usize),
+}
+
+impl fmt::Display for ScoringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoringError::InvalidBase { position, base } => write!(f, "invalid base {:?} at position {}", base, position),
+            ScoringError::TooShort { len, kmer_len } => {
+                write!(f, "sequence of {} bases is shorter than the {}-mers it is scored by", len, kmer_len)
+            }
+            ScoringError::InvalidKmerLen(len) => write!(f, "k-mer length must be 1-{}, got {}", MAX_KMER_LEN, len),
+        }
+    }
+}
+
+impl std::error::Error for ScoringError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scores {
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub entropy: u16,
+}
+
+/// A sequence as 2-bit base codes (A=0, C=1, G=2, T=3), case-insensitive
+/// and ignoring whitespace.
+pub fn parse_sequence(sequence: &str) -> Result<Vec<u8>, ScoringError> {
+    sequence
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .enumerate()
+        .map(|(position, base)| match base.to_ascii_uppercase() {
+            'A' => Ok(0),
+            'C' => Ok(1),
+            'G' => Ok(2),
+            'T' => Ok(3),
+            _ => Err(ScoringError::InvalidBase { position, base }),
+        })
+        .collect()
+}
+
+/// Scores `sequence` against `index`; the sequence itself is not added.
+pub fn score(sequence: &str, index: &KmerIndex) -> Result<Scores, ScoringError> {
+    let bases = parse_sequence(sequence)?;
+    Ok(Scores { complexity: complexity(&bases), uniqueness: index.uniqueness(&bases)?, entropy: entropy(&bases) })
+}
+
+/// Compressed size as a percentage of the raw sequence, capped at 100.
+/// The sequence is parsed into Lempel-Ziv (1976) phrases, each extending
+/// the longest run already seen by one base. A phrase costs about `log4(n)`
+/// bases to encode, so `c` phrases of `n` bases compress to
+/// `c * log4(n) / n`: random sequences score about 100, repeats near 0.
+/// Quadratic in the worst case.
+pub fn complexity(bases: &[u8]) -> u8 {
+    if bases.is_empty() {
+        return 0;
+    }
+    let len = bases.len() as f64;
+    let ratio = lz76_phrases(bases) as f64 * len.log2() / 2.0 / len;
+    ((ratio * 100.0).round() as u64).min(MAX_COMPLEXITY as u64) as u8
+}
+
+/// Shannon entropy per base in hundredths of a bit; four equally common
+/// bases give the maximum of 200.
+pub fn entropy(bases: &[u8]) -> u16 {
+    let mut counts = [0u64; 4];
+    for &base in bases {
+        counts[base as usize] += 1;
+    }
+    let len = bases.len() as f64;
+    let bits: f64 = counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum();
+    ((bits * 100.0).round() as u16).min(MAX_ENTROPY)
+}
+
+/// The k-mer sets of previously scored sequences.
+#[derive(Clone, Debug)]
+pub struct KmerIndex {
+    kmer_len: usize,
+    sequences: Vec<HashSet<u64>>,
+}
+
+impl Default for KmerIndex {
+    fn default() -> Self {
+        Self { kmer_len: DEFAULT_KMER_LEN, sequences: Vec::new() }
+    }
+}
+
+impl KmerIndex {
+    pub fn new(kmer_len: usize) -> Result<Self, ScoringError> {
+        if kmer_len == 0 || kmer_len > MAX_KMER_LEN {
+            return Err(ScoringError::InvalidKmerLen(kmer_len));
+        }
+        Ok(Self { kmer_len, sequences: Vec::new() })
+    }
+
+    pub fn kmer_len(&self) -> usize {
+        self.kmer_len
+    }
+
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    pub fn insert(&mut self, bases: &[u8]) -> Result<(), ScoringError> {
+        let kmers = self.kmers(bases)?;
+        self.sequences.push(kmers);
+        Ok(())
+    }
+
+    /// Jaccard distance of the k-mer sets, in percent, to the closest
+    /// indexed sequence; 100 when the index is empty.
+    pub fn uniqueness(&self, bases: &[u8]) -> Result<u8, ScoringError> {
+        let kmers = self.kmers(bases)?;
+        let closest = self
+            .sequences
+            .iter()
+            .map(|other| {
+                let shared = kmers.intersection(other).count() as u64;
+                let union = (kmers.len() + other.len()) as u64 - shared;
+                (100 * (union - shared) + union / 2) / union
+            })
+            .min()
+            .unwrap_or(MAX_UNIQUENESS as u64);
+        Ok(closest as u8)
+    }
+
+    fn kmers(&self, bases: &[u8]) -> Result<HashSet<u64>, ScoringError> {
+        if bases.len() < self.kmer_len {
+            return Err(ScoringError::TooShort { len: bases.len(), kmer_len: self.kmer_len });
+        }
+        Ok(bases
+            .windows(self.kmer_len)
+            .map(|window| window.iter().fold(0u64, |kmer, &base| kmer << 2 | base as u64))
+            .collect())
+    }
+}
+
+/// Phrase count of the Lempel-Ziv (1976) parsing, by Kaspar and
+/// Schuster's algorithm.
+fn lz76_phrases(bases: &[u8]) -> usize {
+    let n = bases.len();
+    if n < 2 {
+        return n;
+    }
+    let (mut i, mut k, mut l, mut phrases, mut k_max) = (0, 1, 1, 1, 1);
+    loop {
+        if bases[i + k - 1] == bases[l + k - 1] {
+            k += 1;
+            if l + k > n {
+                return phrases + 1;
+            }
+        } else {
+            k_max = k_max.max(k);
+            i += 1;
+            if i == l {
+                phrases += 1;
+                l += k_max;
+                if l + 1 > n {
+                    return phrases;
+                }
+                (i, k, k_max) = (0, 1, 1);
+            } else {
+                k = 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REFERENCE: &str = "ACGTTGCAACGGTACCTAGGCTAAGTCCGATTCAGCATGCAAGTTCGACTGATCCAGTTAGC";
+
+    fn index() -> KmerIndex {
+        let mut index = KmerIndex::new(4).unwrap();
+        index.insert(&parse_sequence(REFERENCE).unwrap()).unwrap();
+        index
+    }
+
+    #[test]
+    fn test_golden_vectors() {
+        let vectors = [
+            (REFERENCE, Scores { complexity: 100, uniqueness: 0, entropy: 200 }),
+            // One base changed at the end
+            (
+                "ACGTTGCAACGGTACCTAGGCTAAGTCCGATTCAGCATGCAAGTTCGACTGATCCAGTTAGA",
+                Scores { complexity: 100, uniqueness: 4, entropy: 200 },
+            ),
+            (
+                "GGGCCCATATGCGCTTAAGGCCTTAGCATCGATCGGATCCTAGGCATTACGCGTAATCGGCAT",
+                Scores { complexity: 100, uniqueness: 85, entropy: 200 },
+            ),
+            ("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA", Scores { complexity: 16, uniqueness: 100, entropy: 0 }),
+            ("ACGTACGTACGTACGTACGTACGTACGTACGT", Scores { complexity: 39, uniqueness: 96, entropy: 200 }),
+            ("atatatatatatatatatat gcgc", Scores { complexity: 48, uniqueness: 98, entropy: 165 }),
+            ("TTTTGGGGCCCCAAAA", Scores { complexity: 63, uniqueness: 100, entropy: 200 }),
+        ];
+        let index = index();
+        for (sequence, expected) in vectors {
+            assert_eq!(score(sequence, &index).unwrap(), expected, "{}", sequence);
+        }
+    }
+
+    #[test]
+    fn test_random_sequence_is_complex() {
+        let mut state = 12_345u64;
+        let bases: Vec<u8> = (0..10_000)
+            .map(|_| {
+                state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+                (state >> 62) as u8
+            })
+            .collect();
+        assert_eq!(complexity(&bases), 99);
+        assert_eq!(entropy(&bases), 200);
+        // Repeating it halves what is new
+        assert_eq!(complexity(&bases[..5_000].repeat(2)), 54);
+    }
+
+    #[test]
+    fn test_kmer_index() {
+        assert_eq!(KmerIndex::new(0).unwrap_err(), ScoringError::InvalidKmerLen(0));
+        assert_eq!(KmerIndex::new(33).unwrap_err(), ScoringError::InvalidKmerLen(33));
+        let mut index = index();
+        assert_eq!(score("ACG", &index), Err(ScoringError::TooShort { len: 3, kmer_len: 4 }));
+        assert_eq!(index.uniqueness(&parse_sequence("ACGT").unwrap()), Ok(98));
+
+        // The closest indexed sequence decides
+        index.insert(&parse_sequence("ACGTACGT").unwrap()).unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.uniqueness(&parse_sequence("ACGT").unwrap()), Ok(75));
+        assert_eq!(KmerIndex::default().uniqueness(&parse_sequence("ACGTACGT").unwrap()), Ok(100));
+    }
+
+    #[test]
+    fn test_parse_sequence() {
+        assert_eq!(parse_sequence("ac gt\nTGCA").unwrap(), vec![0, 1, 2, 3, 3, 2, 1, 0]);
+        assert_eq!(parse_sequence("ACNT"), Err(ScoringError::InvalidBase { position: 2, base: 'N' }));
+        assert_eq!(parse_sequence("").unwrap(), Vec::<u8>::new());
+    }
+}
@@ -44,6 +44,27 @@ cd rna-token && anchor build && cd ..
 echo "Building Genesis Engine..."
 cd genesis-engine && anchor build && cd ..
 
+echo "Building Genome Marketplace..."
+cd genome-marketplace && anchor build && cd ..
+
+echo "Building RSM Treasury..."
+cd treasury && anchor build && cd ..
+
+echo "Building Consciousness Oracle..."
+cd consciousness-oracle && anchor build && cd ..
+
+echo "Building RSM Multisig..."
+cd multisig && anchor build && cd ..
+
+echo "Building RSM Pause..."
+cd pause && anchor build && cd ..
+
+echo "Building RSM Faucet (devnet only)..."
+cd faucet && anchor build && cd ..
+
+echo "Building RSM Token (Anchor)..."
+cd rsm-token-anchor && anchor build && cd ..
+
 echo ""
 echo "✅ All contracts built!"
 echo ""
@@ -54,6 +75,25 @@ echo "📤 Deploying to devnet..."
 cd dna-token && anchor deploy --provider.cluster devnet && cd ..
 cd rna-token && anchor deploy --provider.cluster devnet && cd ..
 cd genesis-engine && anchor deploy --provider.cluster devnet && cd ..
+cd genome-marketplace && anchor deploy --provider.cluster devnet && cd ..
+cd treasury && anchor deploy --provider.cluster devnet && cd ..
+cd consciousness-oracle && anchor deploy --provider.cluster devnet && cd ..
+cd multisig && anchor deploy --provider.cluster devnet && cd ..
+cd pause && anchor deploy --provider.cluster devnet && cd ..
+cd faucet && anchor deploy --provider.cluster devnet && cd ..
+cd rsm-token-anchor && anchor deploy --provider.cluster devnet && cd ..
+
+# Publish the DNA Token IDL on-chain so explorers and anchor clients can
+# decode it without the source tree; a redeploy upgrades it instead
+echo ""
+echo "📚 Publishing DNA Token IDL..."
+DNA_PROGRAM=$(solana address -k ../target/deploy/rsm_dna_token-keypair.json)
+DNA_IDL=../target/idl/rsm_dna_token.json
+if anchor idl fetch "$DNA_PROGRAM" --provider.cluster devnet &> /dev/null; then
+    anchor idl upgrade --filepath "$DNA_IDL" --provider.cluster devnet "$DNA_PROGRAM"
+else
+    anchor idl init --filepath "$DNA_IDL" --provider.cluster devnet "$DNA_PROGRAM"
+fi
 
 echo ""
 echo "✅ Deployment complete!"
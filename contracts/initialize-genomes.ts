@@ -62,15 +62,19 @@ async function initializeGenomes() {
             const rnaMultiplier = Math.floor((1 + genome.consciousness / 100) * 100); // e.g. 200 = 2.0x
             
             const tx = await program.methods
-                .initializeGenome(
-                    new anchor.BN(genome.id),
-                    genome.dna_tetrad,
-                    genome.consciousness,
-                    genome.blockchain,
-                    genome.block_hash,
-                    new anchor.BN(genome.total_supply),
-                    rnaMultiplier
-                )
+                .initializeGenome({
+                    genomeId: new anchor.BN(genome.id),
+                    dnaSequence: genome.dna_tetrad,
+                    consciousness: genome.consciousness,
+                    blockchain: genome.blockchain,
+                    blockHash: genome.block_hash,
+                    totalSupply: new anchor.BN(genome.total_supply),
+                    rnaMultiplier,
+                    contentHash: new Array(32).fill(0),
+                    holdingCapBps: 0,
+                    holdingCapSlots: new anchor.BN(0),
+                    founder: provider.wallet.publicKey,
+                })
                 .rpc();
             
             success++;
@@ -0,0 +1,778 @@
+// Consciousness Oracle - Stake-weighted genome scoring
+// Scorers stake RSM and submit scores per genome hash
+// Stake-weighted median becomes canonical, outliers slashed
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+
+declare_id!("ORCxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+
+pub const MAX_SUBMISSIONS: usize = 9;
+pub const UNBONDING_PERIOD: i64 = 7 * 86400; // 7 days
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const STAKE_AUTHORITY_SEED: &[u8] = b"stake_authority";
+
+#[program]
+pub mod consciousness_oracle {
+    use super::*;
+
+    // Initialize oracle
+    pub fn initialize_oracle(
+        ctx: Context<InitializeOracle>,
+        min_stake: u64,
+        quorum: u8,             // submissions required to finalize
+        outlier_tolerance: u8,  // max distance from median before slashing
+        slash_bps: u16,         // share of stake slashed per outlier round
+    ) -> Result<()> {
+        require!(
+            quorum > 0 && quorum as usize <= MAX_SUBMISSIONS,
+            ErrorCode::InvalidQuorum
+        );
+        require!(slash_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidSlash);
+
+        let config = &mut ctx.accounts.config;
+
+        config.authority = ctx.accounts.authority.key();
+        config.rsm_mint = ctx.accounts.rsm_mint.key();
+        config.stake_vault = ctx.accounts.stake_vault.key();
+        config.slash_destination = ctx.accounts.slash_destination.key();
+        config.min_stake = min_stake;
+        config.quorum = quorum;
+        config.outlier_tolerance = outlier_tolerance;
+        config.slash_bps = slash_bps;
+        config.scorer_count = 0;
+        config.total_staked = 0;
+        config.bump = ctx.bumps.config;
+        config.minter_program = Pubkey::default();
+
+        msg!("Oracle initialized: min stake {}, quorum {}", min_stake, quorum);
+        Ok(())
+    }
+
+    // Update oracle parameters (authority only)
+    pub fn update_params(
+        ctx: Context<UpdateParams>,
+        min_stake: u64,
+        quorum: u8,
+        outlier_tolerance: u8,
+        slash_bps: u16,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            ctx.accounts.authority.key() == config.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            quorum > 0 && quorum as usize <= MAX_SUBMISSIONS,
+            ErrorCode::InvalidQuorum
+        );
+        require!(slash_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidSlash);
+
+        config.min_stake = min_stake;
+        config.quorum = quorum;
+        config.outlier_tolerance = outlier_tolerance;
+        config.slash_bps = slash_bps;
+
+        msg!("Oracle params updated");
+        Ok(())
+    }
+
+    // Register as scorer by staking RSM
+    pub fn register_scorer(
+        ctx: Context<RegisterScorer>,
+        stake_amount: u64,
+    ) -> Result<()> {
+        require!(
+            stake_amount >= ctx.accounts.config.min_stake,
+            ErrorCode::InsufficientStake
+        );
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.scorer_rsm_account.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.wallet.to_account_info(),
+                },
+            ),
+            stake_amount,
+        )?;
+
+        let scorer = &mut ctx.accounts.scorer;
+        scorer.wallet = ctx.accounts.wallet.key();
+        scorer.stake = stake_amount;
+        scorer.submissions = 0;
+        scorer.slashed_total = 0;
+        scorer.unbonding_at = 0;
+        scorer.bump = ctx.bumps.scorer;
+
+        let config = &mut ctx.accounts.config;
+        config.scorer_count = config.scorer_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        config.total_staked = config.total_staked
+            .checked_add(stake_amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        msg!("Scorer {} registered with {} RSM stake", scorer.wallet, stake_amount);
+        Ok(())
+    }
+
+    // Bind the program whose stake authority PDA may stake RSM it minted
+    // into the vault for a scorer (authority only); Pubkey::default()
+    // unbinds it
+    pub fn set_minter_program(
+        ctx: Context<UpdateParams>,
+        minter_program: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            ctx.accounts.authority.key() == config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        config.minter_program = minter_program;
+
+        msg!("Minter program set to {}", minter_program);
+        Ok(())
+    }
+
+    // Add RSM the minter program minted straight into the stake vault to
+    // a registered scorer's stake; rsm-token calls this for auto-staked
+    // mints
+    pub fn stake_minted(
+        ctx: Context<StakeMinted>,
+        amount: u64,
+    ) -> Result<()> {
+        let scorer = &mut ctx.accounts.scorer;
+        require!(scorer.unbonding_at == 0, ErrorCode::AlreadyUnbonding);
+
+        scorer.stake = scorer.stake
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        let config = &mut ctx.accounts.config;
+        config.total_staked = config.total_staked
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        msg!("Scorer {} staked {} minted RSM", scorer.wallet, amount);
+        Ok(())
+    }
+
+    // Begin unbonding; no further submissions accepted
+    pub fn request_unbond(
+        ctx: Context<RequestUnbond>,
+    ) -> Result<()> {
+        let scorer = &mut ctx.accounts.scorer;
+        require!(scorer.unbonding_at == 0, ErrorCode::AlreadyUnbonding);
+
+        scorer.unbonding_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(UNBONDING_PERIOD)
+            .ok_or(ErrorCode::Overflow)?;
+
+        msg!("Scorer {} unbonding until {}", scorer.wallet, scorer.unbonding_at);
+        Ok(())
+    }
+
+    // Withdraw remaining stake after unbonding and close scorer
+    pub fn withdraw_stake(
+        ctx: Context<WithdrawStake>,
+    ) -> Result<()> {
+        let scorer = &ctx.accounts.scorer;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            scorer.unbonding_at != 0 && now >= scorer.unbonding_at,
+            ErrorCode::StillBonded
+        );
+
+        let stake = scorer.stake;
+        let rsm_mint = ctx.accounts.config.rsm_mint;
+        let bump = [ctx.accounts.config.bump];
+        let seeds: &[&[u8]] = &[b"oracle", rsm_mint.as_ref(), &bump];
+
+        if stake > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.stake_vault.to_account_info(),
+                        to: ctx.accounts.scorer_rsm_account.to_account_info(),
+                        authority: ctx.accounts.config.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                stake,
+            )?;
+        }
+
+        let config = &mut ctx.accounts.config;
+        config.scorer_count = config.scorer_count.saturating_sub(1);
+        config.total_staked = config.total_staked.saturating_sub(stake);
+
+        msg!("Scorer withdrew {} RSM", stake);
+        Ok(())
+    }
+
+    // Open a scoring round for a genome hash
+    pub fn open_round(
+        ctx: Context<OpenRound>,
+        genome_hash: [u8; 32],
+    ) -> Result<()> {
+        let round = &mut ctx.accounts.round;
+
+        round.genome_hash = genome_hash;
+        round.submissions = Vec::new();
+        round.finalized = false;
+        round.opened_at = Clock::get()?.unix_timestamp;
+        round.bump = ctx.bumps.round;
+
+        msg!("Scoring round opened");
+        Ok(())
+    }
+
+    // Submit scores for a genome
+    pub fn submit_score(
+        ctx: Context<SubmitScore>,
+        consciousness: u8,
+        complexity: u8,
+        uniqueness: u8,
+    ) -> Result<()> {
+        require!(
+            consciousness <= 100 && complexity <= 100 && uniqueness <= 100,
+            ErrorCode::InvalidScore
+        );
+
+        let scorer = &mut ctx.accounts.scorer;
+        let round = &mut ctx.accounts.round;
+
+        require!(scorer.unbonding_at == 0, ErrorCode::AlreadyUnbonding);
+        require!(
+            scorer.stake >= ctx.accounts.config.min_stake,
+            ErrorCode::InsufficientStake
+        );
+        require!(!round.finalized, ErrorCode::RoundFinalized);
+        require!(round.submissions.len() < MAX_SUBMISSIONS, ErrorCode::RoundFull);
+        require!(
+            !round.submissions.iter().any(|s| s.scorer == scorer.key()),
+            ErrorCode::DuplicateSubmission
+        );
+
+        round.submissions.push(ScoreSubmission {
+            scorer: scorer.key(),
+            consciousness,
+            complexity,
+            uniqueness,
+            stake: scorer.stake,
+        });
+        scorer.submissions = scorer.submissions
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
+        msg!("Score submitted: C={} X={} U={}", consciousness, complexity, uniqueness);
+        Ok(())
+    }
+
+    // Finalize round: write canonical score, slash outliers
+    // Every outlier's Scorer account is passed (mut) in remaining_accounts
+    pub fn finalize_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeRound<'info>>,
+    ) -> Result<()> {
+        let round = &ctx.accounts.round;
+        let config = &ctx.accounts.config;
+
+        require!(!round.finalized, ErrorCode::RoundFinalized);
+        require!(
+            round.submissions.len() >= config.quorum as usize,
+            ErrorCode::QuorumNotReached
+        );
+
+        let consciousness = weighted_median(&round.submissions, |s| s.consciousness);
+        let complexity = weighted_median(&round.submissions, |s| s.complexity);
+        let uniqueness = weighted_median(&round.submissions, |s| s.uniqueness);
+
+        let tolerance = config.outlier_tolerance;
+        let is_outlier = |s: &ScoreSubmission| {
+            consciousness.abs_diff(s.consciousness) > tolerance
+                || complexity.abs_diff(s.complexity) > tolerance
+                || uniqueness.abs_diff(s.uniqueness) > tolerance
+        };
+        // The finalizer cannot spare an outlier by leaving its account out
+        let outliers = round.submissions.iter().filter(|s| is_outlier(s)).count();
+        require!(
+            ctx.remaining_accounts.len() == outliers,
+            ErrorCode::OutliersMissing
+        );
+
+        let slash_bps = config.slash_bps;
+        let rsm_mint = config.rsm_mint;
+        let bump = [config.bump];
+        let seeds: &[&[u8]] = &[b"oracle", rsm_mint.as_ref(), &bump];
+
+        let mut slashed_total: u64 = 0;
+        let mut seen: Vec<Pubkey> = Vec::new();
+
+        for account in ctx.remaining_accounts.iter() {
+            require!(!seen.contains(account.key), ErrorCode::DuplicateSubmission);
+            seen.push(*account.key);
+
+            let mut scorer: Account<Scorer> = Account::try_from(account)?;
+            let submission = round
+                .submissions
+                .iter()
+                .find(|s| s.scorer == *account.key)
+                .ok_or(ErrorCode::NotAParticipant)?;
+
+            require!(is_outlier(submission), ErrorCode::NotAnOutlier);
+
+            let slash = ((scorer.stake as u128 * slash_bps as u128)
+                / BPS_DENOMINATOR as u128) as u64;
+            if slash == 0 {
+                continue;
+            }
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.stake_vault.to_account_info(),
+                        to: ctx.accounts.slash_destination.to_account_info(),
+                        authority: ctx.accounts.config.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                slash,
+            )?;
+
+            scorer.stake -= slash;
+            scorer.slashed_total = scorer.slashed_total
+                .checked_add(slash)
+                .ok_or(ErrorCode::Overflow)?;
+            scorer.exit(&crate::ID)?;
+
+            slashed_total = slashed_total
+                .checked_add(slash)
+                .ok_or(ErrorCode::Overflow)?;
+
+            emit!(ScorerSlashedEvent {
+                scorer: *account.key,
+                genome_hash: round.genome_hash,
+                amount: slash,
+            });
+        }
+
+        let submissions = round.submissions.len() as u8;
+        let genome_hash = round.genome_hash;
+        let now = Clock::get()?.unix_timestamp;
+
+        let score = &mut ctx.accounts.score;
+        score.genome_hash = genome_hash;
+        score.consciousness = consciousness;
+        score.complexity = complexity;
+        score.uniqueness = uniqueness;
+        score.submissions = submissions;
+        score.finalized_at = now;
+        score.bump = ctx.bumps.score;
+
+        ctx.accounts.round.finalized = true;
+
+        let config = &mut ctx.accounts.config;
+        config.total_staked = config.total_staked.saturating_sub(slashed_total);
+
+        emit!(ScoreFinalizedEvent {
+            genome_hash,
+            consciousness,
+            complexity,
+            uniqueness,
+            submissions,
+            slashed: slashed_total,
+            timestamp: now,
+        });
+
+        msg!("Canonical score: C={} X={} U={}", consciousness, complexity, uniqueness);
+        Ok(())
+    }
+}
+
+// Stake-weighted median of one score dimension
+pub fn weighted_median(
+    submissions: &[ScoreSubmission],
+    value: fn(&ScoreSubmission) -> u8,
+) -> u8 {
+    let mut points: Vec<(u8, u64)> = submissions
+        .iter()
+        .map(|s| (value(s), s.stake))
+        .collect();
+    points.sort_by_key(|p| p.0);
+
+    let total: u128 = points.iter().map(|p| p.1 as u128).sum();
+    let mut acc: u128 = 0;
+    for (v, stake) in points.iter() {
+        acc += *stake as u128;
+        if acc * 2 >= total {
+            return *v;
+        }
+    }
+    points.last().map(|p| p.0).unwrap_or(0)
+}
+
+// Signer PDA a minter program uses to call stake_minted
+pub fn stake_authority_address(minter_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[STAKE_AUTHORITY_SEED], minter_program).0
+}
+
+// Account structures
+#[account]
+pub struct OracleConfig {
+    pub authority: Pubkey,
+    pub rsm_mint: Pubkey,
+    pub stake_vault: Pubkey,
+    pub slash_destination: Pubkey,
+    pub min_stake: u64,
+    pub quorum: u8,
+    pub outlier_tolerance: u8,
+    pub slash_bps: u16,
+    pub scorer_count: u32,
+    pub total_staked: u64,
+    pub bump: u8,
+    pub minter_program: Pubkey, // may stake minted RSM for scorers
+}
+
+#[account]
+pub struct Scorer {
+    pub wallet: Pubkey,
+    pub stake: u64,
+    pub submissions: u64,
+    pub slashed_total: u64,
+    pub unbonding_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct ScoreRound {
+    pub genome_hash: [u8; 32],
+    pub submissions: Vec<ScoreSubmission>,
+    pub finalized: bool,
+    pub opened_at: i64,
+    pub bump: u8,
+}
+
+// Canonical score read by rsm-token minting (layout is mirrored there)
+#[account]
+pub struct CanonicalScore {
+    pub genome_hash: [u8; 32],
+    pub consciousness: u8,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub submissions: u8,
+    pub finalized_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ScoreSubmission {
+    pub scorer: Pubkey,
+    pub consciousness: u8,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub stake: u64,
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct InitializeOracle<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 200,
+        seeds = [b"oracle", rsm_mint.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, OracleConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"stake_vault", config.key().as_ref()],
+        bump,
+        token::mint = rsm_mint,
+        token::authority = config
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    pub rsm_mint: Account<'info, Mint>,
+
+    #[account(token::mint = rsm_mint)]
+    pub slash_destination: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateParams<'info> {
+    #[account(mut)]
+    pub config: Account<'info, OracleConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterScorer<'info> {
+    #[account(mut)]
+    pub config: Account<'info, OracleConfig>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + 100,
+        seeds = [b"scorer", config.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub scorer: Account<'info, Scorer>,
+
+    #[account(
+        mut,
+        address = config.stake_vault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = config.rsm_mint,
+        token::authority = wallet
+    )]
+    pub scorer_rsm_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeMinted<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle", config.rsm_mint.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, OracleConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"scorer", config.key().as_ref(), wallet.key().as_ref()],
+        bump = scorer.bump
+    )]
+    pub scorer: Account<'info, Scorer>,
+
+    #[account(address = config.stake_vault)]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: the scorer's wallet, only used to derive the scorer PDA
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = config.minter_program != Pubkey::default()
+            && minter_authority.key() == stake_authority_address(&config.minter_program)
+            @ ErrorCode::Unauthorized
+    )]
+    pub minter_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnbond<'info> {
+    pub config: Account<'info, OracleConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"scorer", config.key().as_ref(), wallet.key().as_ref()],
+        bump = scorer.bump
+    )]
+    pub scorer: Account<'info, Scorer>,
+
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle", config.rsm_mint.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, OracleConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"scorer", config.key().as_ref(), wallet.key().as_ref()],
+        bump = scorer.bump,
+        close = wallet
+    )]
+    pub scorer: Account<'info, Scorer>,
+
+    #[account(
+        mut,
+        address = config.stake_vault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = config.rsm_mint,
+        token::authority = wallet
+    )]
+    pub scorer_rsm_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(genome_hash: [u8; 32])]
+pub struct OpenRound<'info> {
+    pub config: Account<'info, OracleConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 500,
+        seeds = [b"round", config.key().as_ref(), genome_hash.as_ref()],
+        bump
+    )]
+    pub round: Account<'info, ScoreRound>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitScore<'info> {
+    pub config: Account<'info, OracleConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"round", config.key().as_ref(), round.genome_hash.as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, ScoreRound>,
+
+    #[account(
+        mut,
+        seeds = [b"scorer", config.key().as_ref(), wallet.key().as_ref()],
+        bump = scorer.bump
+    )]
+    pub scorer: Account<'info, Scorer>,
+
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle", config.rsm_mint.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, OracleConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"round", config.key().as_ref(), round.genome_hash.as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, ScoreRound>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 100,
+        seeds = [b"score", config.key().as_ref(), round.genome_hash.as_ref()],
+        bump
+    )]
+    pub score: Account<'info, CanonicalScore>,
+
+    #[account(
+        mut,
+        address = config.stake_vault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = config.slash_destination
+    )]
+    pub slash_destination: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Events
+#[event]
+pub struct ScoreFinalizedEvent {
+    pub genome_hash: [u8; 32],
+    pub consciousness: u8,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub submissions: u8,
+    pub slashed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ScorerSlashedEvent {
+    pub scorer: Pubkey,
+    pub genome_hash: [u8; 32],
+    pub amount: u64,
+}
+
+// Errors
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Math overflow")]
+    Overflow,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Quorum must be between 1 and 9")]
+    InvalidQuorum,
+    #[msg("Slash share must be at most 10000 bps")]
+    InvalidSlash,
+    #[msg("Stake below minimum")]
+    InsufficientStake,
+    #[msg("Scores must be between 0 and 100")]
+    InvalidScore,
+    #[msg("Scorer is unbonding")]
+    AlreadyUnbonding,
+    #[msg("Unbonding period not over")]
+    StillBonded,
+    #[msg("Round already finalized")]
+    RoundFinalized,
+    #[msg("Round has no free submission slots")]
+    RoundFull,
+    #[msg("Scorer already submitted for this round")]
+    DuplicateSubmission,
+    #[msg("Not enough submissions to finalize")]
+    QuorumNotReached,
+    #[msg("Scorer did not take part in this round")]
+    NotAParticipant,
+    #[msg("Scorer is within tolerance of the median")]
+    NotAnOutlier,
+    #[msg("Every outlier's scorer account must be passed")]
+    OutliersMissing,
+}
@@ -3,51 +3,149 @@
 // 67,686+ genome types
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, MintTo, Transfer};
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::{ed25519_program, program::{invoke, invoke_signed}, sysvar::instructions as ix_sysvar};
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token::{self, Token, TokenAccount, Mint, MintTo, Transfer, Burn, CloseAccount, InitializeAccount3, Approve, Revoke};
+use anchor_spl::token_2022::{self, spl_token_2022, InitializeMint2, Token2022};
+use anchor_spl::token_interface::{self, FreezeAccount, Mint as InterfaceMint, SetAuthority, ThawAccount, TokenAccount as InterfaceTokenAccount, TokenInterface};
+use spl_token_2022::extension::{interest_bearing_mint, ExtensionType};
+use spl_token_2022::instruction::AuthorityType;
+use rsm_core::{TraitVector, TRAIT_COUNT};
+use rsm_pause::{GlobalPause, GLOBAL_PAUSE_SEED};
+use spl_account_compression::{program::SplAccountCompression, wrap_application_data_v1, Noop};
 
 declare_id!("DNAxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
+pub const MAX_GENOME_NAME_LEN: usize = 32;
+pub const BLOCKCHAIN_TAG_LEN: usize = 8;
+pub const GENESIS_BLOCKCHAIN_TAG: [u8; BLOCKCHAIN_TAG_LEN] = *b"genesis\0";
+pub const GENESIS_AUTHORITY_SEED: &[u8] = b"genesis_authority";
+pub const GENOME_TREE_SEED: &[u8] = b"genome_tree";
+pub const TREE_AUTHORITY_SEED: &[u8] = b"tree_authority";
+pub const INDEX_SEED: &[u8] = b"index";
+pub const HOLD_TIERS_SEED: &[u8] = b"hold_tiers";
+pub const HOLD_SEED: &[u8] = b"hold";
+pub const HOLD_TIER_COUNT: usize = 4;
+pub const RETIREMENT_SEED: &[u8] = b"retirement";
+pub const PERMIT_AUTHORITY_SEED: &[u8] = b"permit_authority";
+pub const PERMIT_NONCE_SEED: &[u8] = b"permit_nonce";
+pub const PERMIT_DOMAIN: &[u8] = b"rsm-dna-permit";
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const BUNDLE_SEED: &[u8] = b"bundle";
+pub const BUNDLE_ESCROW_SEED: &[u8] = b"bundle_escrow";
+pub const MAX_BUNDLE_LEGS: usize = 8;
+pub const FOUNDER_MINT_SEED: &[u8] = b"founder_mint";
+pub const CONSCIOUSNESS_PAGE_SEED: &[u8] = b"consciousness_page";
+pub const CONSCIOUSNESS_BUCKET_WIDTH: u8 = 10;
+pub const CONSCIOUSNESS_PAGE_CAPACITY: usize = 256;
+pub const BURN_RECEIPT_SEED: &[u8] = b"burn_receipt";
+pub const LISTING_AUTHORITY_SEED: &[u8] = b"listing_authority";
+pub const DELEGATED_LISTING_SEED: &[u8] = b"delegated_listing";
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+pub const METADATA_CORRECTION_SEED: &[u8] = b"metadata_correction";
+pub const METADATA_CORRECTION_WINDOW_SLOTS: u64 = 216_000; // ~1 day
+pub const COLLECTION_SEED: &[u8] = b"collection";
+pub const COLLECTION_ENTRY_SEED: &[u8] = b"collection_entry";
+pub const MAX_COLLECTION_NAME_LEN: usize = 32;
+pub const FREEZE_AUTHORITY_SEED: &[u8] = b"freeze_authority";
+pub const DISPUTE_CONFIG_SEED: &[u8] = b"dispute_config";
+pub const DISPUTE_AUTHORITY_SEED: &[u8] = b"dispute_authority";
+pub const GROWTH_CURVE_SEED: &[u8] = b"growth_curve";
+pub const GENOME_STAKE_SEED: &[u8] = b"genome_stake";
+pub const GENOME_STAKE_VAULT_SEED: &[u8] = b"genome_stake_vault";
+pub const MAX_GROWN_CONSCIOUSNESS: u8 = 100;
+
 #[program]
 pub mod rsm_dna_token {
     use super::*;
 
-    // Initialize new genome token type
+    /// Initialize new genome token type; only the registry authority may,
+    /// naming the founder the founder supply goes to
     pub fn initialize_genome(
         ctx: Context<InitializeGenome>,
-        genome_id: u64,
-        dna_sequence: String,
-        consciousness: u8,
-        blockchain: String,
-        block_hash: String,
-        total_supply: u64,
-        rna_multiplier: u16, // e.g. 200 = 2.0x
+        args: InitializeGenomeArgs,
     ) -> Result<()> {
-        let genome = &mut ctx.accounts.genome;
+        require!(args.blockchain.len() <= BLOCKCHAIN_TAG_LEN, ErrorCode::InvalidBlockchainTag);
+        require!(
+            args.holding_cap_bps as u64 <= BPS_DENOMINATOR && (args.holding_cap_bps == 0) == (args.holding_cap_slots == 0),
+            ErrorCode::InvalidHoldingCap
+        );
+        let genome_key = ctx.accounts.genome.key();
+        let genome = &mut ctx.accounts.genome.load_init()?;
         
-        genome.genome_id = genome_id;
-        genome.dna_sequence = dna_sequence;
-        genome.consciousness = consciousness;
-        genome.blockchain = blockchain;
-        genome.block_hash = block_hash;
-        genome.total_supply = total_supply;
-        genome.rna_multiplier = rna_multiplier;
-        genome.market_supply = (total_supply * 8571) / 10000; // 85.71%
-        genome.founder_supply = (total_supply * 1429) / 10000; // 14.29%
-        genome.minted = false;
+        // Name, mint, flags and counters start zeroed
+        genome.genome_id = args.genome_id;
+        genome.set_sequence(&args.dna_sequence, &args.block_hash);
+        genome.consciousness = args.consciousness;
+        genome.blockchain = padded(&args.blockchain);
+        genome.total_supply = args.total_supply;
+        genome.rna_multiplier = args.rna_multiplier;
+        genome.market_supply = (args.total_supply * 8571) / 10000; // 85.71%
+        genome.founder_supply = (args.total_supply * 1429) / 10000; // 14.29%
         genome.created_at = Clock::get()?.unix_timestamp;
+        genome.founder = args.founder;
+        genome.created_slot = Clock::get()?.slot;
+        genome.content_hash = args.content_hash;
+        genome.holding_cap_bps = args.holding_cap_bps;
+        genome.holding_cap_slots = args.holding_cap_slots;
+        
+        // Listed in its consciousness bucket right away when the open page
+        // of the bucket is passed
+        if let Some(page) = ctx.accounts.consciousness_page.as_mut() {
+            add_to_consciousness_page(page, genome, genome_key)?;
+        }
+        
+        msg!("Genome {} initialized: {} DNA tokens", args.genome_id, args.total_supply);
+        Ok(())
+    }
+
+    /// Open page `page` of a consciousness bucket; anyone may pay for it.
+    /// Pages fill in order, so page n > 0 needs page n - 1 to be full
+    pub fn create_consciousness_page(
+        ctx: Context<CreateConsciousnessPage>,
+        bucket: u8,
+        page: u32,
+    ) -> Result<()> {
+        require!(bucket <= consciousness_bucket(u8::MAX), ErrorCode::InvalidConsciousnessBucket);
+        let previous_full = ctx
+            .accounts
+            .previous_page
+            .as_ref()
+            .is_some_and(|previous| previous.genomes.len() == CONSCIOUSNESS_PAGE_CAPACITY);
+        require!(page == 0 || previous_full, ErrorCode::PreviousPageNotFull);
+        
+        let consciousness_page = &mut ctx.accounts.consciousness_page;
+        consciousness_page.bucket = bucket;
+        consciousness_page.page = page;
+        consciousness_page.genomes = Vec::new();
+        consciousness_page.bump = ctx.bumps.consciousness_page;
+        
+        msg!("Consciousness bucket {} page {} opened", bucket, page);
+        Ok(())
+    }
+
+    /// List a genome created without its bucket page, such as genesis
+    /// children and genomes from before the index (permissionless)
+    pub fn index_by_consciousness(ctx: Context<IndexByConsciousness>) -> Result<()> {
+        let genome_key = ctx.accounts.genome.key();
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        add_to_consciousness_page(&mut ctx.accounts.consciousness_page, genome, genome_key)?;
         
-        msg!("Genome {} initialized: {} DNA tokens", genome_id, total_supply);
+        msg!("Genome {} listed in consciousness bucket {}", genome.genome_id, ctx.accounts.consciousness_page.bucket);
         Ok(())
     }
 
-    // Mint DNA tokens for genome
+    /// Mint DNA tokens for genome
     pub fn mint_genome_tokens(
         ctx: Context<MintGenomeTokens>,
         amount: u64,
     ) -> Result<()> {
-        let genome = &mut ctx.accounts.genome;
+        let genome = &mut ctx.accounts.genome.load_mut()?;
         
-        require!(!genome.minted, ErrorCode::AlreadyMinted);
+        require!(!genome.is_minted(), ErrorCode::AlreadyMinted);
         require!(amount == genome.total_supply, ErrorCode::InvalidAmount);
         
         // Mint to market (85.71%)
@@ -76,8 +174,11 @@ pub mod rsm_dna_token {
             genome.founder_supply,
         )?;
         
-        genome.minted = true;
+        genome.minted = 1;
         genome.minted_at = Clock::get()?.unix_timestamp;
+        genome.minted_slot = Clock::get()?.slot;
+        genome.mint = ctx.accounts.mint.key();
+        genome.record_mint(amount)?;
         
         msg!("Genome {} minted: {} market, {} founder",
             genome.genome_id,
@@ -88,11 +189,159 @@ pub mod rsm_dna_token {
         Ok(())
     }
 
-    // Transfer DNA tokens
+    /// Create the genome's interest-bearing Token-2022 founder mint, accruing
+    /// at the registry's founder rate; the registry is mint and rate authority
+    pub fn create_founder_mint(ctx: Context<CreateFounderMint>) -> Result<()> {
+        let genome = &ctx.accounts.genome.load()?;
+        let registry = &ctx.accounts.registry;
+        let creator = ctx.accounts.authority.key();
+        require!(creator == registry.authority || creator == genome.founder, ErrorCode::Unauthorized);
+        
+        let genome_key = ctx.accounts.genome.key();
+        let founder_mint = ctx.accounts.founder_mint.to_account_info();
+        let seeds: &[&[u8]] = &[FOUNDER_MINT_SEED, genome_key.as_ref(), &[ctx.bumps.founder_mint]];
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::InterestBearingConfig,
+        ])?;
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: founder_mint.clone(),
+                },
+                &[seeds],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &token_2022::ID,
+        )?;
+        invoke(
+            &interest_bearing_mint::instruction::initialize(
+                &token_2022::ID,
+                founder_mint.key,
+                Some(registry.key()),
+                registry.founder_rate_bps,
+            )?,
+            std::slice::from_ref(&founder_mint),
+        )?;
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 { mint: founder_mint.clone() },
+            ),
+            ctx.accounts.mint.decimals,
+            &registry.key(),
+            None,
+        )?;
+        
+        msg!("Founder mint {} created at {} bps", founder_mint.key, registry.founder_rate_bps);
+        Ok(())
+    }
+
+    /// Mint a Token-2022 genome: market supply in the genome mint, founder
+    /// supply in its interest-bearing founder mint
+    pub fn mint_genome_tokens_2022(
+        ctx: Context<MintGenomeTokens2022>,
+        amount: u64,
+    ) -> Result<()> {
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        
+        require!(!genome.is_minted(), ErrorCode::AlreadyMinted);
+        require!(amount == genome.total_supply, ErrorCode::InvalidAmount);
+        
+        token_2022::mint_to(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_2022::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.market_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            genome.market_supply,
+        )?;
+        
+        let seeds: &[&[u8]] = &[b"registry", &[ctx.accounts.registry.bump]];
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_2022::MintTo {
+                    mint: ctx.accounts.founder_mint.to_account_info(),
+                    to: ctx.accounts.founder_account.to_account_info(),
+                    authority: ctx.accounts.registry.to_account_info(),
+                },
+                &[seeds],
+            ),
+            genome.founder_supply,
+        )?;
+        
+        genome.minted = 1;
+        genome.minted_at = Clock::get()?.unix_timestamp;
+        genome.minted_slot = Clock::get()?.slot;
+        genome.mint = ctx.accounts.mint.key();
+        genome.record_mint(amount)?;
+        
+        msg!("Genome {} minted under Token-2022: {} market, {} founder",
+            genome.genome_id,
+            genome.market_supply,
+            genome.founder_supply
+        );
+        
+        Ok(())
+    }
+
+    /// Set the founder rate (registry authority only) and apply it to the
+    /// founder mints passed as remaining accounts
+    pub fn update_founder_rate<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateFounderRate<'info>>,
+        rate_bps: i16,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(rate_bps >= 0, ErrorCode::InvalidFounderRate);
+        
+        registry.founder_rate_bps = rate_bps;
+        let registry_info = registry.to_account_info();
+        let seeds: &[&[u8]] = &[b"registry", &[registry.bump]];
+        for founder_mint in ctx.remaining_accounts {
+            // Token-2022 rejects any mint whose rate authority is not the registry
+            invoke_signed(
+                &interest_bearing_mint::instruction::update_rate(
+                    &token_2022::ID,
+                    founder_mint.key,
+                    registry_info.key,
+                    &[],
+                    rate_bps,
+                )?,
+                &[founder_mint.clone(), registry_info.clone()],
+                &[seeds],
+            )?;
+        }
+        
+        emit!(FounderRateUpdatedEvent {
+            authority: ctx.accounts.authority.key(),
+            rate_bps,
+            mints_updated: ctx.remaining_accounts.len() as u32,
+        });
+        
+        msg!("Founder rate set to {} bps on {} mints", rate_bps, ctx.remaining_accounts.len());
+        Ok(())
+    }
+
+    /// Transfer DNA tokens (blocked while the genome is incubating)
     pub fn transfer_dna(
         ctx: Context<TransferDNA>,
         amount: u64,
     ) -> Result<()> {
+        require!(
+            Clock::get()?.slot >= ctx.accounts.genome.load()?.matures_at_slot,
+            ErrorCode::GenomeIncubating
+        );
+        
         token::transfer(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
@@ -105,105 +354,4925 @@ pub mod rsm_dna_token {
             amount,
         )?;
         
+        ctx.accounts.to.reload()?;
+        require!(
+            ctx.accounts.genome.load()?.within_holding_cap(ctx.accounts.to.amount, Clock::get()?.slot),
+            ErrorCode::HoldingCapExceeded
+        );
+        
+        // Transferring out restarts the sender's hold
+        ctx.accounts.from.reload()?;
+        restart_hold(
+            &ctx.accounts.from_hold,
+            &ctx.accounts.from,
+            ctx.accounts.genome.load()?.rna_multiplier,
+            Clock::get()?.unix_timestamp,
+        )?;
+        
+        // A delegated listing shrinks to what is left, and an emptied one
+        // gives up its delegate
+        if let Some(listing) = ctx.accounts.from_listing.as_mut() {
+            ctx.accounts.from.reload()?;
+            listing.amount = listing.amount.min(ctx.accounts.from.amount);
+            if listing.amount == 0 && ctx.accounts.from.delegate == COption::Some(listing_authority_address()) {
+                token::revoke(CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Revoke {
+                        source: ctx.accounts.from.to_account_info(),
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                ))?;
+            }
+        }
+        
         msg!("Transferred {} DNA tokens", amount);
         Ok(())
     }
 
-    // Get genome metadata
-    pub fn get_genome_info(
-        ctx: Context<GetGenomeInfo>,
-    ) -> Result<GenomeInfo> {
-        let genome = &ctx.accounts.genome;
+    /// Burn DNA and record it in a receipt at ["burn_receipt", burner,
+    /// receipt_id] that a partner program can consume once
+    pub fn burn_dna(
+        ctx: Context<BurnDNA>,
+        receipt_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
         
-        Ok(GenomeInfo {
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.from.to_account_info(),
+                    authority: ctx.accounts.burner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        // Only burns through dna-token are counted; a holder burning with
+        // spl-token directly leaves circulating_supply high
+        ctx.accounts.genome.load_mut()?.record_burn(amount)?;
+        
+        let clock = Clock::get()?;
+        // Burning, like transferring out, restarts the holder's hold
+        ctx.accounts.from.reload()?;
+        restart_hold(&ctx.accounts.from_hold, &ctx.accounts.from, ctx.accounts.genome.load()?.rna_multiplier, clock.unix_timestamp)?;
+        
+        let genome = &ctx.accounts.genome.load()?;
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.burner = ctx.accounts.burner.key();
+        receipt.receipt_id = receipt_id;
+        receipt.genome = ctx.accounts.genome.key();
+        receipt.genome_id = genome.genome_id;
+        receipt.amount = amount;
+        receipt.slot = clock.slot;
+        receipt.consumed = false;
+        receipt.consumer = Pubkey::default();
+        receipt.bump = ctx.bumps.receipt;
+        
+        emit!(DnaBurnedEvent {
+            receipt: receipt.key(),
+            burner: receipt.burner,
             genome_id: genome.genome_id,
-            dna_sequence: genome.dna_sequence.clone(),
-            consciousness: genome.consciousness,
-            blockchain: genome.blockchain.clone(),
-            total_supply: genome.total_supply,
-            rna_multiplier: genome.rna_multiplier,
-            minted: genome.minted,
-        })
+            amount,
+            slot: clock.slot,
+        });
+        
+        msg!("Burned {} DNA of genome {}, receipt {}", amount, genome.genome_id, receipt_id);
+        Ok(())
     }
-}
 
-// Account structures
-#[account]
-pub struct Genome {
-    pub genome_id: u64,
-    pub dna_sequence: String,
-    pub consciousness: u8,
-    pub blockchain: String,
-    pub block_hash: String,
-    pub total_supply: u64,
-    pub market_supply: u64,
-    pub founder_supply: u64,
-    pub rna_multiplier: u16,
-    pub minted: bool,
-    pub created_at: i64,
-    pub minted_at: i64,
-}
+    /// Spend a burn receipt, once. Meant to be called by CPI from a partner
+    /// program, which signs as `consumer` (usually with a PDA) while the
+    /// burner's signature authorizes the spend; returns the amount burned
+    pub fn consume_receipt(
+        ctx: Context<ConsumeReceipt>,
+    ) -> Result<u64> {
+        let receipt = &mut ctx.accounts.receipt;
+        require!(!receipt.consumed, ErrorCode::ReceiptConsumed);
+        
+        receipt.consumed = true;
+        receipt.consumer = ctx.accounts.consumer.key();
+        
+        emit!(ReceiptConsumedEvent {
+            receipt: receipt.key(),
+            consumer: receipt.consumer,
+            genome_id: receipt.genome_id,
+            amount: receipt.amount,
+        });
+        
+        msg!("Burn receipt {} of {} consumed by {}", receipt.receipt_id, receipt.burner, receipt.consumer);
+        Ok(receipt.amount)
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct GenomeInfo {
-    pub genome_id: u64,
-    pub dna_sequence: String,
-    pub consciousness: u8,
-    pub blockchain: String,
-    pub total_supply: u64,
-    pub rna_multiplier: u16,
-    pub minted: bool,
-}
+    /// Transfer DNA on the holder's off-chain signed permit; anyone can
+    /// submit it and pay the fees. The holder approves the permit authority
+    /// PDA as delegate of the token account once, and the Ed25519 program
+    /// instruction verifying the permit must come right before this one
+    pub fn transfer_with_permit(
+        ctx: Context<TransferWithPermit>,
+        amount: u64,
+        nonce: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(clock.slot >= ctx.accounts.genome.load()?.matures_at_slot, ErrorCode::GenomeIncubating);
+        require!(clock.unix_timestamp <= deadline, ErrorCode::PermitExpired);
+        
+        let holder = ctx.accounts.from.owner;
+        let permit_nonce = &mut ctx.accounts.permit_nonce;
+        if permit_nonce.owner == Pubkey::default() {
+            permit_nonce.owner = holder;
+            permit_nonce.bump = ctx.bumps.permit_nonce;
+        }
+        require!(nonce == permit_nonce.next_nonce, ErrorCode::InvalidPermitNonce);
+        permit_nonce.next_nonce = nonce.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        
+        let message = permit_message(
+            &ctx.accounts.genome.load()?.mint,
+            &ctx.accounts.from.key(),
+            &ctx.accounts.to.key(),
+            amount,
+            nonce,
+            deadline,
+        );
+        let instructions = ctx.accounts.instructions.to_account_info();
+        let current = ix_sysvar::load_current_index_checked(&instructions)?;
+        require!(current > 0, ErrorCode::InvalidPermitSignature);
+        let verify_ix = ix_sysvar::load_instruction_at_checked(current as usize - 1, &instructions)?;
+        require!(
+            verify_ix.program_id == ed25519_program::ID && ed25519_verifies(&verify_ix.data, &holder, &message),
+            ErrorCode::InvalidPermitSignature
+        );
+        
+        let bump = ctx.bumps.permit_authority;
+        let seeds: &[&[u8]] = &[PERMIT_AUTHORITY_SEED, &[bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.from.to_account_info(),
+                    to: ctx.accounts.to.to_account_info(),
+                    authority: ctx.accounts.permit_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+        
+        ctx.accounts.to.reload()?;
+        require!(
+            ctx.accounts.genome.load()?.within_holding_cap(ctx.accounts.to.amount, clock.slot),
+            ErrorCode::HoldingCapExceeded
+        );
+        
+        ctx.accounts.from.reload()?;
+        restart_hold(&ctx.accounts.from_hold, &ctx.accounts.from, ctx.accounts.genome.load()?.rna_multiplier, clock.unix_timestamp)?;
+        
+        emit!(PermitTransferEvent {
+            genome: ctx.accounts.genome.key(),
+            holder,
+            from: ctx.accounts.from.key(),
+            to: ctx.accounts.to.key(),
+            amount,
+            nonce,
+            submitter: ctx.accounts.submitter.key(),
+        });
+        
+        msg!("Transferred {} DNA tokens on permit {} of {}", amount, nonce, holder);
+        Ok(())
+    }
 
-// Context structures
-#[derive(Accounts)]
-pub struct InitializeGenome<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + 500
-    )]
-    pub genome: Account<'info, Genome>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Initialize registry holding the DNA authority. Only the program's
+    /// upgrade authority may, so nobody can claim it between deploy and init
+    pub fn initialize_registry(
+        ctx: Context<InitializeRegistry>,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        
+        registry.authority = ctx.accounts.authority.key();
+        registry.snapshot_count = 0;
+        registry.genesis_program = Pubkey::default();
+        registry.bump = ctx.bumps.registry;
+        registry.founder_rate_bps = 0;
+        registry.genesis_engine = Pubkey::default();
+        
+        msg!("DNA registry initialized, authority: {}", registry.authority);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct MintGenomeTokens<'info> {
-    #[account(mut)]
-    pub genome: Account<'info, Genome>,
-    
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
-    
-    #[account(mut)]
-    pub market_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub founder_account: Account<'info, TokenAccount>,
-    
-    pub authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
-}
+    /// Bind the genesis engine allowed to register child genomes: the
+    /// program and the one engine account of it whose authority PDA signs
+    pub fn set_genesis_program(
+        ctx: Context<SetGenesisProgram>,
+        genesis_program: Pubkey,
+        genesis_engine: Pubkey,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        registry.genesis_program = genesis_program;
+        registry.genesis_engine = genesis_engine;
+        
+        msg!("Genesis program set: {}, engine {}", genesis_program, genesis_engine);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct TransferDNA<'info> {
-    #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub to: Account<'info, TokenAccount>,
-    
-    pub authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
-}
+    /// Bind the dispute program allowed to freeze genome token accounts
+    /// while it adjudicates a fraud claim (registry authority only)
+    pub fn set_dispute_program(
+        ctx: Context<SetDisputeProgram>,
+        dispute_program: Pubkey,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let config = &mut ctx.accounts.dispute_config;
+        config.dispute_program = dispute_program;
+        config.bump = ctx.bumps.dispute_config;
+        
+        msg!("Dispute program set: {}", dispute_program);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct GetGenomeInfo<'info> {
-    pub genome: Account<'info, Genome>,
+    /// Register a genesis-created child genome (CPI from genesis engine only).
+    /// The payer becomes its founder unless a founder is passed, as for
+    /// children donated to a gene pool
+    pub fn register_genesis_genome(
+        ctx: Context<RegisterGenesisGenome>,
+        args: RegisterGenesisGenomeArgs,
+    ) -> Result<()> {
+        require!(
+            !args.name.is_empty() && args.name.len() <= MAX_GENOME_NAME_LEN,
+            ErrorCode::InvalidGenomeName
+        );
+        require!(args.total_supply > 0, ErrorCode::InvalidAmount);
+        
+        // Sequence, mint, flags and counters start zeroed
+        let genome = &mut ctx.accounts.genome.load_init()?;
+        genome.genome_id = args.genome_id;
+        genome.consciousness = args.consciousness;
+        genome.blockchain = GENESIS_BLOCKCHAIN_TAG;
+        genome.total_supply = args.total_supply;
+        genome.market_supply = (args.total_supply as u128 * 8571 / 10000) as u64; // 85.71%
+        genome.founder_supply = (args.total_supply as u128 * 1429 / 10000) as u64; // 14.29%
+        genome.rna_multiplier = args.rna_multiplier;
+        genome.created_at = Clock::get()?.unix_timestamp;
+        genome.name = padded(&args.name);
+        genome.metadata_uri_hash = args.metadata_uri_hash;
+        genome.founder = ctx.accounts.founder.as_ref().map_or(ctx.accounts.payer.key(), |founder| founder.key());
+        genome.matures_at_slot = args.matures_at_slot;
+        genome.species = args.species;
+        genome.traits = args.traits;
+        genome.created_slot = Clock::get()?.slot;
+        
+        msg!("Genesis genome {} registered: {}", args.genome_id, genome.name());
+        Ok(())
+    }
+
+    /// Commit to a holder snapshot: hash(merkle_root, total_tracked_supply, holder_count)
+    pub fn commit_snapshot(
+        ctx: Context<CommitSnapshot>,
+        snapshot_id: u64,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(snapshot_id == registry.snapshot_count, ErrorCode::InvalidSnapshotId);
+        
+        let clock = Clock::get()?;
+        let snapshot = &mut ctx.accounts.snapshot;
+        snapshot.snapshot_id = snapshot_id;
+        snapshot.commitment = commitment;
+        snapshot.slot = clock.slot;
+        snapshot.committed_at = clock.unix_timestamp;
+        snapshot.finalized = false;
+        snapshot.bump = ctx.bumps.snapshot;
+        
+        registry.snapshot_count = registry.snapshot_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        msg!("Snapshot {} committed at slot {}", snapshot_id, clock.slot);
+        Ok(())
+    }
+
+    /// Reveal snapshot root and supply, verified against the commitment
+    pub fn take_snapshot(
+        ctx: Context<TakeSnapshot>,
+        merkle_root: [u8; 32],
+        total_tracked_supply: u64,
+        holder_count: u32,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let snapshot = &mut ctx.accounts.snapshot;
+        require!(!snapshot.finalized, ErrorCode::SnapshotFinalized);
+        require!(
+            snapshot_commitment(&merkle_root, total_tracked_supply, holder_count) == snapshot.commitment,
+            ErrorCode::CommitmentMismatch
+        );
+        
+        snapshot.merkle_root = merkle_root;
+        snapshot.total_tracked_supply = total_tracked_supply;
+        snapshot.holder_count = holder_count;
+        snapshot.finalized = true;
+        
+        emit!(SnapshotTakenEvent {
+            snapshot_id: snapshot.snapshot_id,
+            merkle_root,
+            total_tracked_supply,
+            holder_count,
+            slot: snapshot.slot,
+        });
+        
+        msg!("Snapshot {} finalized: {} holders, {} DNA", snapshot.snapshot_id, holder_count, total_tracked_supply);
+        Ok(())
+    }
+
+    /// Prove a holder balance against a snapshot (returned for CPI callers)
+    pub fn prove_snapshot_balance(
+        ctx: Context<ProveSnapshotBalance>,
+        holder: Pubkey,
+        balance: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<u64> {
+        let snapshot = &ctx.accounts.snapshot;
+        require!(snapshot.finalized, ErrorCode::SnapshotNotFinalized);
+        require!(
+            verify_holder_balance(&snapshot.merkle_root, &holder, balance, &proof),
+            ErrorCode::InvalidProof
+        );
+        
+        Ok(balance)
+    }
+
+    /// Classify a genome into a species (registry authority)
+    pub fn set_genome_species(
+        ctx: Context<SetGenomeSpecies>,
+        species: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        genome.species = species;
+        
+        msg!("Genome {} assigned to species {}", genome.genome_id, species);
+        Ok(())
+    }
+
+    /// Pull a genesis genome's maturity forward (CPI from genesis engine only)
+    pub fn set_genome_maturity(
+        ctx: Context<SetGenomeMaturity>,
+        matures_at_slot: u64,
+    ) -> Result<()> {
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        require!(matures_at_slot <= genome.matures_at_slot, ErrorCode::InvalidMaturity);
+        
+        genome.matures_at_slot = matures_at_slot;
+        
+        msg!("Genome {} matures at slot {}", genome.genome_id, matures_at_slot);
+        Ok(())
+    }
+
+    /// Sunset a genome (registry authority): burn the authority's remaining
+    /// supply, freeze the genome for good and record why
+    pub fn retire_genome(
+        ctx: Context<RetireGenome>,
+        reason_code: u32,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        let burned = if genome.is_minted() {
+            let mint = ctx.accounts.mint.as_ref().ok_or(ErrorCode::GenomeMintMismatch)?;
+            let holding = ctx.accounts.authority_holding.as_ref().ok_or(ErrorCode::GenomeMintMismatch)?;
+            require!(
+                mint.key() == genome.mint && holding.mint == genome.mint,
+                ErrorCode::GenomeMintMismatch
+            );
+            
+            token::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: mint.to_account_info(),
+                        from: holding.to_account_info(),
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                holding.amount,
+            )?;
+            holding.amount
+        } else {
+            0
+        };
+        genome.record_burn(burned)?;
+        genome.retired = 1;
+        
+        let clock = Clock::get()?;
+        let retirement = &mut ctx.accounts.retirement;
+        retirement.genome = ctx.accounts.genome.key();
+        retirement.genome_id = genome.genome_id;
+        retirement.reason_code = reason_code;
+        retirement.burned = burned;
+        retirement.slot = clock.slot;
+        retirement.retired_at = clock.unix_timestamp;
+        retirement.bump = ctx.bumps.retirement;
+        
+        emit!(GenomeRetiredEvent {
+            genome: ctx.accounts.genome.key(),
+            genome_id: genome.genome_id,
+            reason_code,
+            burned,
+            slot: clock.slot,
+        });
+        
+        msg!("Genome {} retired (reason {}), {} DNA burned", genome.genome_id, reason_code, burned);
+        Ok(())
+    }
+
+    /// Open a usage license streaming RNA per slot to the genome founder
+    pub fn license_genome(
+        ctx: Context<LicenseGenome>,
+        rate_per_slot: u64,
+        deposit: u64,
+    ) -> Result<()> {
+        require!(rate_per_slot > 0, ErrorCode::InvalidRate);
+        require!(deposit >= rate_per_slot, ErrorCode::InvalidAmount);
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.licensee_rna_account.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                    authority: ctx.accounts.licensee.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+        
+        let slot = Clock::get()?.slot;
+        let license = &mut ctx.accounts.license;
+        license.genome = ctx.accounts.genome.key();
+        license.genome_id = ctx.accounts.genome.load()?.genome_id;
+        license.founder = ctx.accounts.genome.load()?.founder;
+        license.licensee = ctx.accounts.licensee.key();
+        license.rna_mint = ctx.accounts.rna_mint.key();
+        license.rate_per_slot = rate_per_slot;
+        license.start_slot = slot;
+        license.settled_slot = slot;
+        license.total_deposited = deposit;
+        license.total_paid = 0;
+        license.bump = ctx.bumps.license;
+        license.escrow_bump = ctx.bumps.escrow;
+        
+        msg!("Genome {} licensed at {} RNA/slot, {} slots prepaid",
+            license.genome_id, rate_per_slot, deposit / rate_per_slot);
+        Ok(())
+    }
+
+    /// Add RNA to a license escrow (anyone may fund it)
+    pub fn top_up_license(
+        ctx: Context<TopUpLicense>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder_rna_account.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let license = &mut ctx.accounts.license;
+        license.total_deposited = license.total_deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        msg!("License topped up by {} RNA", amount);
+        Ok(())
+    }
+
+    /// Pay the founder for slots elapsed since the last settlement (permissionless)
+    pub fn settle_license(
+        ctx: Context<SettleLicense>,
+    ) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let paid = pay_license(
+            &mut ctx.accounts.license,
+            &ctx.accounts.escrow,
+            &ctx.accounts.founder_rna_account,
+            &ctx.accounts.token_program,
+            slot,
+        )?;
+        
+        let license = &ctx.accounts.license;
+        emit!(LicenseSettledEvent {
+            license: license.key(),
+            genome_id: license.genome_id,
+            licensee: license.licensee,
+            founder: license.founder,
+            paid,
+            refunded: 0,
+            settled_slot: license.settled_slot,
+            closed: false,
+        });
+        
+        msg!("License settled: {} RNA to founder, covered until slot {}", paid, license.settled_slot);
+        Ok(())
+    }
+
+    /// Settle, refund the unused escrow to the licensee and end the license
+    pub fn close_license(
+        ctx: Context<CloseLicense>,
+    ) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let paid = pay_license(
+            &mut ctx.accounts.license,
+            &ctx.accounts.escrow,
+            &ctx.accounts.founder_rna_account,
+            &ctx.accounts.token_program,
+            slot,
+        )?;
+        
+        ctx.accounts.escrow.reload()?;
+        let refunded = ctx.accounts.escrow.amount;
+        
+        let license = &ctx.accounts.license;
+        let genome = license.genome;
+        let licensee = license.licensee;
+        let bump = [license.bump];
+        let seeds: &[&[u8]] = &[b"license", genome.as_ref(), licensee.as_ref(), &bump];
+        
+        if refunded > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.escrow.to_account_info(),
+                        to: ctx.accounts.licensee_rna_account.to_account_info(),
+                        authority: ctx.accounts.license.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                refunded,
+            )?;
+        }
+        
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow.to_account_info(),
+                destination: ctx.accounts.licensee.to_account_info(),
+                authority: ctx.accounts.license.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+        
+        emit!(LicenseSettledEvent {
+            license: license.key(),
+            genome_id: license.genome_id,
+            licensee,
+            founder: license.founder,
+            paid,
+            refunded,
+            settled_slot: license.settled_slot,
+            closed: true,
+        });
+        
+        msg!("License closed: {} RNA to founder, {} RNA refunded", paid, refunded);
+        Ok(())
+    }
+
+    /// Escrow fixed amounts of several genomes as one package priced in RNA
+    /// (registry authority, or the founder of every genome in it). Remaining
+    /// accounts are (genome, genome mint, creator token account, escrow) per
+    /// leg; each escrow is created at ["bundle_escrow", bundle, mint]
+    pub fn create_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateBundle<'info>>,
+        bundle_id: u64,
+        price: u64,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        require!(price > 0, ErrorCode::InvalidAmount);
+        require!(
+            !amounts.is_empty()
+                && amounts.len() <= MAX_BUNDLE_LEGS
+                && ctx.remaining_accounts.len() == amounts.len() * 4,
+            ErrorCode::InvalidBundle
+        );
+        
+        let creator = ctx.accounts.creator.key();
+        let is_authority = creator == ctx.accounts.registry.authority;
+        let bundle_key = ctx.accounts.bundle.key();
+        let slot = Clock::get()?.slot;
+        let escrow_rent = Rent::get()?.minimum_balance(TokenAccount::LEN);
+        let mut legs: Vec<BundleLeg> = Vec::with_capacity(amounts.len());
+        
+        for (accounts, amount) in ctx.remaining_accounts.chunks_exact(4).zip(amounts) {
+            let genome_loader = AccountLoader::<GenomeV2>::try_from(&accounts[0])?;
+            let genome = genome_loader.load()?;
+            let (mint, source, escrow) = (&accounts[1], &accounts[2], &accounts[3]);
+            let holding: Account<TokenAccount> = Account::try_from(source)?;
+            
+            require!(amount > 0, ErrorCode::InvalidAmount);
+            require!(is_authority || genome.founder == creator, ErrorCode::Unauthorized);
+            require!(
+                genome.is_minted() && mint.key() == genome.mint && holding.mint == genome.mint,
+                ErrorCode::GenomeMintMismatch
+            );
+            require!(!genome.is_retired(), ErrorCode::GenomeRetired);
+            require!(slot >= genome.matures_at_slot, ErrorCode::GenomeIncubating);
+            require!(legs.iter().all(|leg| leg.mint != genome.mint), ErrorCode::DuplicateAccount);
+            
+            let (escrow_key, escrow_bump) = Pubkey::find_program_address(
+                &[BUNDLE_ESCROW_SEED, bundle_key.as_ref(), mint.key.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(escrow.key(), escrow_key, ErrorCode::InvalidBundle);
+            let escrow_seeds: &[&[u8]] = &[BUNDLE_ESCROW_SEED, bundle_key.as_ref(), mint.key.as_ref(), &[escrow_bump]];
+            
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    CreateAccount {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: escrow.clone(),
+                    },
+                    &[escrow_seeds],
+                ),
+                escrow_rent,
+                TokenAccount::LEN as u64,
+                &token::ID,
+            )?;
+            token::initialize_account3(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeAccount3 {
+                    account: escrow.clone(),
+                    mint: mint.clone(),
+                    authority: ctx.accounts.bundle.to_account_info(),
+                },
+            ))?;
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: source.clone(),
+                        to: escrow.clone(),
+                        authority: ctx.accounts.creator.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            
+            legs.push(BundleLeg {
+                genome: genome_loader.key(),
+                mint: genome.mint,
+                amount,
+            });
+        }
+        
+        let bundle = &mut ctx.accounts.bundle;
+        bundle.creator = creator;
+        bundle.bundle_id = bundle_id;
+        bundle.rna_mint = ctx.accounts.rna_mint.key();
+        bundle.price = price;
+        bundle.legs = legs;
+        bundle.created_at = Clock::get()?.unix_timestamp;
+        bundle.bump = ctx.bumps.bundle;
+        
+        msg!("Bundle {} created: {} genomes for {} RNA", bundle_id, bundle.legs.len(), price);
+        Ok(())
+    }
+    
+    /// Pay the bundle price to its creator and receive every leg at once.
+    /// Remaining accounts are (escrow, buyer token account) per leg, in order
+    pub fn buy_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyBundle<'info>>,
+    ) -> Result<()> {
+        let bundle = &ctx.accounts.bundle;
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_rna_account.to_account_info(),
+                    to: ctx.accounts.creator_rna_account.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            bundle.price,
+        )?;
+        
+        release_bundle(
+            bundle,
+            ctx.remaining_accounts,
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.token_program,
+        )?;
+        
+        emit!(BundleSoldEvent {
+            bundle: bundle.key(),
+            bundle_id: bundle.bundle_id,
+            creator: bundle.creator,
+            buyer: ctx.accounts.buyer.key(),
+            price: bundle.price,
+            legs: bundle.legs.len() as u8,
+        });
+        
+        msg!("Bundle {} sold for {} RNA", bundle.bundle_id, bundle.price);
+        Ok(())
+    }
+    
+    /// Withdraw an unsold bundle back to its creator. Remaining accounts are
+    /// (escrow, creator token account) per leg, in order
+    pub fn cancel_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelBundle<'info>>,
+    ) -> Result<()> {
+        release_bundle(
+            &ctx.accounts.bundle,
+            ctx.remaining_accounts,
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.token_program,
+        )?;
+        
+        msg!("Bundle {} cancelled", ctx.accounts.bundle.bundle_id);
+        Ok(())
+    }
+    
+    /// List DNA for RNA without escrow: the seller's token account approves
+    /// the listing authority for `amount`, and tokens only move at sale time.
+    /// One listing per token account at ["delegated_listing", token_account]
+    pub fn list_with_delegate(
+        ctx: Context<ListWithDelegate>,
+        amount: u64,
+        price_per_token: u64,
+    ) -> Result<()> {
+        require!(
+            amount > 0 && amount <= ctx.accounts.token_account.amount && price_per_token > 0,
+            ErrorCode::InvalidAmount
+        );
+        let clock = Clock::get()?;
+        require!(
+            clock.slot >= ctx.accounts.genome.load()?.matures_at_slot,
+            ErrorCode::GenomeIncubating
+        );
+        
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.token_account.to_account_info(),
+                    delegate: ctx.accounts.listing_authority.to_account_info(),
+                    authority: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let listing = &mut ctx.accounts.listing;
+        listing.seller = ctx.accounts.seller.key();
+        listing.token_account = ctx.accounts.token_account.key();
+        listing.genome = ctx.accounts.genome.key();
+        listing.rna_mint = ctx.accounts.rna_mint.key();
+        listing.amount = amount;
+        listing.price_per_token = price_per_token;
+        listing.created_at = clock.unix_timestamp;
+        listing.bump = ctx.bumps.listing;
+        
+        msg!("Listed {} DNA of genome {} at {} RNA each", amount, ctx.accounts.genome.load()?.genome_id, price_per_token);
+        Ok(())
+    }
+    
+    /// Buy `amount` of a delegated listing: RNA goes to the seller and the
+    /// listing authority pulls the DNA from the seller's token account. Fails
+    /// if the seller revoked the delegate or no longer holds the tokens
+    pub fn execute_delegated_sale(
+        ctx: Context<ExecuteDelegatedSale>,
+        amount: u64,
+    ) -> Result<()> {
+        let listing = &ctx.accounts.listing;
+        require!(amount > 0 && amount <= listing.amount, ErrorCode::InvalidAmount);
+        let token_account = &ctx.accounts.token_account;
+        require!(
+            token_account.delegate == COption::Some(ctx.accounts.listing_authority.key())
+                && token_account.delegated_amount >= amount
+                && token_account.amount >= amount,
+            ErrorCode::ListingRevoked
+        );
+        let price = amount.checked_mul(listing.price_per_token).ok_or(ErrorCode::Overflow)?;
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_rna_account.to_account_info(),
+                    to: ctx.accounts.seller_rna_account.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            price,
+        )?;
+        
+        let bump = ctx.bumps.listing_authority;
+        let seeds: &[&[u8]] = &[LISTING_AUTHORITY_SEED, &[bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.token_account.to_account_info(),
+                    to: ctx.accounts.buyer_token_account.to_account_info(),
+                    authority: ctx.accounts.listing_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+        
+        ctx.accounts.buyer_token_account.reload()?;
+        require!(
+            ctx.accounts.genome.load()?.within_holding_cap(ctx.accounts.buyer_token_account.amount, Clock::get()?.slot),
+            ErrorCode::HoldingCapExceeded
+        );
+        
+        // A sale is a transfer out, so it restarts the seller's hold
+        ctx.accounts.token_account.reload()?;
+        restart_hold(
+            &ctx.accounts.seller_hold,
+            &ctx.accounts.token_account,
+            ctx.accounts.genome.load()?.rna_multiplier,
+            Clock::get()?.unix_timestamp,
+        )?;
+        
+        let listing = &mut ctx.accounts.listing;
+        listing.amount -= amount;
+        emit!(DelegatedSaleEvent {
+            listing: listing.key(),
+            genome_id: ctx.accounts.genome.load()?.genome_id,
+            seller: listing.seller,
+            buyer: ctx.accounts.buyer.key(),
+            amount,
+            price,
+            remaining: listing.amount,
+        });
+        
+        msg!("Sold {} DNA of genome {} for {} RNA", amount, ctx.accounts.genome.load()?.genome_id, price);
+        if listing.amount == 0 {
+            ctx.accounts.listing.close(ctx.accounts.seller.to_account_info())?;
+        }
+        Ok(())
+    }
+    
+    /// Close a delegated listing, revoking the listing authority if it is
+    /// still the token account's delegate
+    pub fn cancel_delegated_listing(
+        ctx: Context<CancelDelegatedListing>,
+    ) -> Result<()> {
+        if ctx.accounts.token_account.delegate == COption::Some(ctx.accounts.listing_authority.key()) {
+            token::revoke(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Revoke {
+                    source: ctx.accounts.token_account.to_account_info(),
+                    authority: ctx.accounts.seller.to_account_info(),
+                },
+            ))?;
+        }
+        
+        msg!("Delegated listing {} cancelled", ctx.accounts.listing.key());
+        Ok(())
+    }
+
+    /// Create the GNOME index and its mint (registry authority only)
+    pub fn initialize_index(
+        ctx: Context<InitializeIndex>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let index = &mut ctx.accounts.index;
+        index.index_mint = ctx.accounts.index_mint.key();
+        index.total_value = 0;
+        index.bump = ctx.bumps.index;
+        
+        msg!("GNOME index initialized: mint {}", index.index_mint);
+        Ok(())
+    }
+
+    /// Admit a minted genome to the index with a DNA deposit cap (registry authority only)
+    pub fn list_index_genome(
+        ctx: Context<ListIndexGenome>,
+        cap: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let genome = &ctx.accounts.genome.load()?;
+        require!(genome.is_minted(), ErrorCode::GenomeNotMinted);
+        require!(!genome.is_retired(), ErrorCode::GenomeRetired);
+        let weight = index_weight(genome.consciousness, genome.rna_multiplier);
+        require!(weight > 0, ErrorCode::ZeroIndexWeight);
+        
+        let entry = &mut ctx.accounts.index_genome;
+        entry.genome = ctx.accounts.genome.key();
+        entry.genome_id = genome.genome_id;
+        entry.cap = cap;
+        entry.deposited = 0;
+        entry.weight = weight;
+        entry.bump = ctx.bumps.index_genome;
+        entry.vault_bump = ctx.bumps.vault;
+        
+        msg!("Genome {} listed in index: weight {}, cap {}", genome.genome_id, weight, cap);
+        Ok(())
+    }
+
+    /// Change a listed genome's deposit cap; lowering it below the deposits only blocks new ones
+    pub fn set_index_cap(
+        ctx: Context<SetIndexCap>,
+        cap: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let entry = &mut ctx.accounts.index_genome;
+        entry.cap = cap;
+        
+        msg!("Genome {} index cap set to {}", entry.genome_id, cap);
+        Ok(())
+    }
+
+    /// Wrap DNA into the vault for weight-scaled GNOME index tokens
+    pub fn deposit_to_index(
+        ctx: Context<IndexSwap>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(!ctx.accounts.genome.load()?.is_retired(), ErrorCode::GenomeRetired);
+        require!(
+            Clock::get()?.slot >= ctx.accounts.genome.load()?.matures_at_slot,
+            ErrorCode::GenomeIncubating
+        );
+        
+        let entry = &ctx.accounts.index_genome;
+        let deposited = entry.deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(deposited <= entry.cap, ErrorCode::IndexCapExceeded);
+        let index_amount = amount
+            .checked_mul(entry.weight)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner_dna_account.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let bump = [ctx.accounts.index.bump];
+        let seeds: &[&[u8]] = &[INDEX_SEED, &bump];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.index_mint.to_account_info(),
+                    to: ctx.accounts.owner_index_account.to_account_info(),
+                    authority: ctx.accounts.index.to_account_info(),
+                },
+                &[seeds],
+            ),
+            index_amount,
+        )?;
+        
+        ctx.accounts.index_genome.deposited = deposited;
+        let index = &mut ctx.accounts.index;
+        index.total_value = index.total_value
+            .checked_add(index_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        emit!(IndexSwapEvent {
+            genome: ctx.accounts.genome.key(),
+            genome_id: ctx.accounts.genome.load()?.genome_id,
+            owner: ctx.accounts.owner.key(),
+            dna_amount: amount,
+            index_amount,
+            redeemed: false,
+        });
+        
+        msg!("Wrapped {} DNA into {} GNOME", amount, index_amount);
+        Ok(())
+    }
+
+    /// Burn GNOME index tokens to withdraw DNA of any listed genome from its vault
+    pub fn redeem_from_index(
+        ctx: Context<IndexSwap>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        let entry = &ctx.accounts.index_genome;
+        let deposited = entry.deposited
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientIndexDeposits)?;
+        let index_amount = amount
+            .checked_mul(entry.weight)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.index_mint.to_account_info(),
+                    from: ctx.accounts.owner_index_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            index_amount,
+        )?;
+        
+        let bump = [ctx.accounts.index.bump];
+        let seeds: &[&[u8]] = &[INDEX_SEED, &bump];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.owner_dna_account.to_account_info(),
+                    authority: ctx.accounts.index.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+        
+        ctx.accounts.index_genome.deposited = deposited;
+        let index = &mut ctx.accounts.index;
+        index.total_value = index.total_value
+            .checked_sub(index_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        emit!(IndexSwapEvent {
+            genome: ctx.accounts.genome.key(),
+            genome_id: ctx.accounts.genome.load()?.genome_id,
+            owner: ctx.accounts.owner.key(),
+            dna_amount: amount,
+            index_amount,
+            redeemed: true,
+        });
+        
+        msg!("Redeemed {} GNOME for {} DNA", index_amount, amount);
+        Ok(())
+    }
+
+    /// Create the hold tier table (registry authority only)
+    pub fn initialize_hold_tiers(
+        ctx: Context<InitializeHoldTiers>,
+        min_hold_secs: [i64; HOLD_TIER_COUNT],
+        multiplier_bps: [u16; HOLD_TIER_COUNT],
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        validate_hold_tiers(&min_hold_secs, &multiplier_bps)?;
+        
+        let tiers = &mut ctx.accounts.hold_tiers;
+        tiers.min_hold_secs = min_hold_secs;
+        tiers.multiplier_bps = multiplier_bps;
+        tiers.bump = ctx.bumps.hold_tiers;
+        
+        msg!("Hold tiers initialized: {:?}s at {:?} bps", min_hold_secs, multiplier_bps);
+        Ok(())
+    }
+
+    /// Replace the hold tier table (registry authority only); applies on the next refresh
+    pub fn set_hold_tiers(
+        ctx: Context<SetHoldTiers>,
+        min_hold_secs: [i64; HOLD_TIER_COUNT],
+        multiplier_bps: [u16; HOLD_TIER_COUNT],
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        validate_hold_tiers(&min_hold_secs, &multiplier_bps)?;
+        
+        let tiers = &mut ctx.accounts.hold_tiers;
+        tiers.min_hold_secs = min_hold_secs;
+        tiers.multiplier_bps = multiplier_bps;
+        
+        msg!("Hold tiers updated: {:?}s at {:?} bps", min_hold_secs, multiplier_bps);
+        Ok(())
+    }
+
+    /// Start tracking how long `holder` keeps the DNA in `holding`
+    pub fn start_hold(
+        ctx: Context<StartHold>,
+    ) -> Result<()> {
+        let genome = &ctx.accounts.genome.load()?;
+        let hold = &mut ctx.accounts.holder_state;
+        
+        hold.genome = ctx.accounts.genome.key();
+        hold.holder = ctx.accounts.holder.key();
+        hold.token_account = ctx.accounts.holding.key();
+        hold.bump = ctx.bumps.holder_state;
+        let now = Clock::get()?.unix_timestamp;
+        hold.reset(ctx.accounts.holding.amount, genome.rna_multiplier, now);
+        hold.checkpoint_balance = ctx.accounts.holding.amount;
+        hold.checkpoint_at = now;
+        
+        msg!("Hold started on genome {} with {} DNA", genome.genome_id, hold.baseline);
+        Ok(())
+    }
+
+    /// Recompute a holder's tier (permissionless); returns the effective rna_multiplier
+    /// A balance, current or checkpointed, below the hold's baseline means DNA left the account, which restarts the hold
+    pub fn refresh_hold(
+        ctx: Context<RefreshHold>,
+    ) -> Result<u16> {
+        let now = Clock::get()?.unix_timestamp;
+        let balance = ctx.accounts.holding.amount;
+        let rna_multiplier = ctx.accounts.genome.load()?.rna_multiplier;
+        let tiers = &ctx.accounts.hold_tiers;
+        let hold = &mut ctx.accounts.holder_state;
+        // Judged by the lowest balance a checkpoint saw, so DNA moved out
+        // and bought back before this refresh still restarts the hold
+        let lowest = hold.checkpoint_balance.min(balance);
+        hold.checkpoint(balance, now);
+        
+        if lowest < hold.baseline {
+            hold.reset(lowest, rna_multiplier, now);
+        } else {
+            let tier = hold_tier(&tiers.min_hold_secs, now.saturating_sub(hold.hold_start));
+            hold.tier = tier as u8;
+            hold.effective_multiplier = hold_multiplier(rna_multiplier, tier, &tiers.multiplier_bps)?;
+        }
+        
+        msg!("Hold tier {}: effective multiplier {}", hold.tier, hold.effective_multiplier);
+        Ok(hold.effective_multiplier)
+    }
+    
+    /// Checkpoint the holder's balance and hand over the balance-seconds
+    /// accrued since the last settlement (returned for CPI callers, e.g.
+    /// rna-token's claim_rna); the accrual restarts from zero
+    pub fn settle_hold_balance(
+        ctx: Context<SettleHoldBalance>,
+    ) -> Result<u128> {
+        let hold = &mut ctx.accounts.holder_state;
+        hold.checkpoint(ctx.accounts.holding.amount, Clock::get()?.unix_timestamp);
+        let balance_secs = std::mem::take(&mut hold.balance_secs);
+        
+        msg!("Settled {} balance-seconds of {}", balance_secs, hold.holder);
+        Ok(balance_secs)
+    }
+    
+    /// Grow a hold started before balance checkpoints to the current
+    /// layout; its checkpoint starts at the current balance
+    pub fn migrate_hold(ctx: Context<MigrateHold>) -> Result<()> {
+        let hold_info = ctx.accounts.holder_state.to_account_info();
+        require!(hold_info.data_len() == HolderState::LEGACY_SPACE, ErrorCode::HoldAlreadyMigrated);
+        
+        let rent = Rent::get()?.minimum_balance(HolderState::SPACE).saturating_sub(hold_info.lamports());
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer { from: ctx.accounts.holder.to_account_info(), to: hold_info.clone() },
+            ),
+            rent,
+        )?;
+        hold_info.realloc(HolderState::SPACE, true)?;
+        
+        let mut data = hold_info.try_borrow_mut_data()?;
+        let mut hold = HolderState::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(hold.holder, ctx.accounts.holder.key(), ErrorCode::Unauthorized);
+        require_keys_eq!(hold.token_account, ctx.accounts.holding.key(), ErrorCode::HoldAccountMismatch);
+        hold.checkpoint_balance = ctx.accounts.holding.amount;
+        hold.checkpoint_at = Clock::get()?.unix_timestamp;
+        hold.try_serialize(&mut &mut data[..])?;
+        
+        msg!("Hold of {} migrated to balance checkpoints", hold.holder);
+        Ok(())
+    }
+
+    /// Grow a genome created before content hashes or holding caps to
+    /// GenomeV2::SPACE (permissionless); the payer covers the extra rent and
+    /// the new fields start unset
+    pub fn extend_genome(ctx: Context<ExtendGenome>) -> Result<()> {
+        let genome_info = ctx.accounts.genome.to_account_info();
+        require!(
+            [GenomeV2::PRE_CONTENT_HASH_SPACE, GenomeV2::PRE_HOLDING_CAP_SPACE].contains(&genome_info.data_len())
+                && genome_info.try_borrow_data()?.starts_with(&GenomeV2::discriminator()),
+            ErrorCode::GenomeAlreadyExtended
+        );
+        
+        let rent = Rent::get()?.minimum_balance(GenomeV2::SPACE).saturating_sub(genome_info.lamports());
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer { from: ctx.accounts.payer.to_account_info(), to: genome_info.clone() },
+            ),
+            rent,
+        )?;
+        genome_info.realloc(GenomeV2::SPACE, true)?;
+        
+        let genome_id = u64::from_le_bytes(genome_info.try_borrow_data()?[8..16].try_into().unwrap());
+        msg!("Genome {} extended to the current layout", genome_id);
+        Ok(())
+    }
+
+    /// Grow a registry from before the genesis engine binding to the current
+    /// layout (permissionless, the payer covers the extra rent). It reads as
+    /// bound to no engine until set_genesis_program runs again
+    pub fn extend_registry(ctx: Context<ExtendRegistry>) -> Result<()> {
+        let registry_info = ctx.accounts.registry.to_account_info();
+        require!(
+            registry_info.data_len() == Registry::PRE_GENESIS_ENGINE_SPACE
+                && registry_info.try_borrow_data()?.starts_with(&Registry::discriminator()),
+            ErrorCode::RegistryAlreadyExtended
+        );
+        
+        let rent = Rent::get()?.minimum_balance(Registry::SPACE).saturating_sub(registry_info.lamports());
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer { from: ctx.accounts.payer.to_account_info(), to: registry_info.clone() },
+            ),
+            rent,
+        )?;
+        registry_info.realloc(Registry::SPACE, true)?;
+        
+        msg!("Registry extended to the current layout");
+        Ok(())
+    }
+
+    /// Commit a genome's off-chain content hash once, for genomes that
+    /// started without one (founder only); it cannot change after
+    pub fn set_content_hash(ctx: Context<SetContentHash>, content_hash: [u8; 32]) -> Result<()> {
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        require_keys_eq!(ctx.accounts.founder.key(), genome.founder, ErrorCode::Unauthorized);
+        require!(genome.content_hash == [0u8; 32], ErrorCode::ContentHashAlreadySet);
+        require!(content_hash != [0u8; 32], ErrorCode::InvalidContentHash);
+        genome.content_hash = content_hash;
+        
+        msg!("Genome {} committed to its off-chain content", genome.genome_id);
+        Ok(())
+    }
+
+    /// Set how staked DNA grows a genome's consciousness (registry authority):
+    /// stakes lock for at least min_lock_epochs, the point above consciousness
+    /// c costs base_experience * (1 + c * step_bps / 10000) experience, and a
+    /// claim adds at most max_points_per_claim, never past max_consciousness
+    pub fn set_growth_curve(
+        ctx: Context<SetGrowthCurve>,
+        min_lock_epochs: u64,
+        base_experience: u64,
+        step_bps: u16,
+        max_points_per_claim: u8,
+        max_consciousness: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            min_lock_epochs > 0
+                && base_experience > 0
+                && max_points_per_claim > 0
+                && max_consciousness <= MAX_GROWN_CONSCIOUSNESS,
+            ErrorCode::InvalidGrowthCurve
+        );
+        
+        let curve = &mut ctx.accounts.growth_curve;
+        curve.min_lock_epochs = min_lock_epochs;
+        curve.base_experience = base_experience;
+        curve.step_bps = step_bps;
+        curve.max_points_per_claim = max_points_per_claim;
+        curve.max_consciousness = max_consciousness;
+        curve.bump = ctx.bumps.growth_curve;
+        
+        msg!("Growth curve: {} epochs, {} experience per point, +{} bps per point", min_lock_epochs, base_experience, step_bps);
+        Ok(())
+    }
+    
+    /// Lock `amount` of a genome's DNA for `lock_epochs` epochs; every epoch
+    /// locked earns `amount` experience toward the genome's consciousness.
+    /// One stake per staker and genome at ["genome_stake", genome, staker]
+    pub fn stake_genome_tokens(
+        ctx: Context<StakeGenomeTokens>,
+        amount: u64,
+        lock_epochs: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(lock_epochs >= ctx.accounts.growth_curve.min_lock_epochs, ErrorCode::LockTooShort);
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.staker_account.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.staker.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let epoch = Clock::get()?.epoch;
+        let stake = &mut ctx.accounts.stake;
+        stake.genome = ctx.accounts.genome.key();
+        stake.staker = ctx.accounts.staker.key();
+        stake.amount = amount;
+        stake.start_epoch = epoch;
+        stake.unlock_epoch = epoch.checked_add(lock_epochs).ok_or(ErrorCode::Overflow)?;
+        stake.accrued_epoch = epoch;
+        stake.experience = 0;
+        stake.bump = ctx.bumps.stake;
+        stake.vault_bump = ctx.bumps.vault;
+        
+        msg!("Staked {} DNA of genome {} until epoch {}", amount, ctx.accounts.genome.load()?.genome_id, stake.unlock_epoch);
+        Ok(())
+    }
+    
+    /// Spend a stake's experience on consciousness along the growth curve
+    /// (staker only); returns the points gained. An indexed genome leaving
+    /// its consciousness bucket is taken off its page, passed as
+    /// consciousness_page, and can be indexed again in the new bucket
+    pub fn claim_consciousness_growth(
+        ctx: Context<ClaimConsciousnessGrowth>,
+    ) -> Result<u8> {
+        let stake = &mut ctx.accounts.stake;
+        stake.accrue(Clock::get()?.epoch);
+        
+        let genome_key = ctx.accounts.genome.key();
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        let (points, spent) = consciousness_growth(&ctx.accounts.growth_curve, genome.consciousness, stake.experience);
+        require!(points > 0, ErrorCode::NotEnoughExperience);
+        
+        let previous = genome.consciousness;
+        let consciousness = previous + points;
+        if genome.is_indexed() && consciousness_bucket(consciousness) != consciousness_bucket(previous) {
+            let page = ctx.accounts.consciousness_page.as_mut().ok_or(ErrorCode::ConsciousnessPageRequired)?;
+            let position = page.genomes.iter().position(|key| *key == genome_key)
+                .ok_or(ErrorCode::ConsciousnessPageRequired)?;
+            page.genomes.remove(position);
+            genome.indexed = 0;
+        }
+        genome.consciousness = consciousness;
+        stake.experience -= spent;
+        
+        emit!(ConsciousnessGrownEvent {
+            genome: genome_key,
+            genome_id: genome.genome_id,
+            staker: stake.staker,
+            previous,
+            consciousness,
+            experience_spent: spent,
+            experience_left: stake.experience,
+        });
+        
+        msg!("Genome {} consciousness {} -> {}", genome.genome_id, previous, consciousness);
+        Ok(points)
+    }
+    
+    /// Return a stake's DNA once its lock has passed and close it (staker
+    /// only); experience not yet claimed is forfeited
+    pub fn unstake_genome_tokens(
+        ctx: Context<UnstakeGenomeTokens>,
+    ) -> Result<()> {
+        let stake = &ctx.accounts.stake;
+        require!(Clock::get()?.epoch >= stake.unlock_epoch, ErrorCode::StakeLocked);
+        
+        let bump = [stake.bump];
+        let seeds: &[&[u8]] = &[GENOME_STAKE_SEED, stake.genome.as_ref(), stake.staker.as_ref(), &bump];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.staker_account.to_account_info(),
+                    authority: ctx.accounts.stake.to_account_info(),
+                },
+                &[seeds],
+            ),
+            stake.amount,
+        )?;
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.staker.to_account_info(),
+                authority: ctx.accounts.stake.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+        
+        msg!("Unstaked {} DNA, {} experience forfeited", stake.amount, stake.experience);
+        Ok(())
+    }
+    
+    /// Hand an unminted genome's founder role, and with it the founder
+    /// supply, to `new_founder` (current founder only)
+    pub fn transfer_founder(
+        ctx: Context<TransferFounder>,
+        new_founder: Pubkey,
+    ) -> Result<()> {
+        let genome_key = ctx.accounts.genome.key();
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        require!(genome.founder == ctx.accounts.founder.key(), ErrorCode::Unauthorized);
+        require!(!genome.is_minted(), ErrorCode::AlreadyMinted);
+        
+        genome.founder = new_founder;
+        
+        emit!(FounderTransferredEvent {
+            genome: genome_key,
+            genome_id: genome.genome_id,
+            previous: ctx.accounts.founder.key(),
+            founder: new_founder,
+        });
+        msg!("Genome {} founder is now {}", genome.genome_id, new_founder);
+        Ok(())
+    }
+
+    /// Get genome metadata
+    pub fn get_genome_info(
+        ctx: Context<GetGenomeInfo>,
+    ) -> Result<GenomeInfo> {
+        let genome = &ctx.accounts.genome.load()?;
+        
+        Ok(GenomeInfo {
+            genome_id: genome.genome_id,
+            sequence_hash: genome.sequence_hash,
+            consciousness: genome.consciousness,
+            blockchain: genome.blockchain_name().to_string(),
+            total_supply: genome.total_supply,
+            rna_multiplier: genome.rna_multiplier,
+            minted: genome.is_minted(),
+            name: genome.name().to_string(),
+            metadata_uri_hash: genome.metadata_uri_hash,
+            species: genome.species,
+            content_hash: genome.content_hash,
+        })
+    }
+
+    /// Get a genome's supply statistics, without scanning its token accounts
+    pub fn get_supply_stats(
+        ctx: Context<GetGenomeInfo>,
+    ) -> Result<GenomeSupplyStats> {
+        let genome = &ctx.accounts.genome.load()?;
+        
+        Ok(GenomeSupplyStats {
+            genome_id: genome.genome_id,
+            total_supply: genome.total_supply,
+            circulating_supply: genome.circulating_supply,
+            total_burned: genome.total_burned,
+            mint_events: genome.mint_events,
+        })
+    }
+
+    /// Fill in the supply statistics of a genome minted before they were
+    /// tracked, from its mints' current supply (permissionless, once)
+    pub fn backfill_supply_stats(ctx: Context<BackfillSupplyStats>) -> Result<()> {
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        require!(genome.is_minted() && genome.mint_events == 0, ErrorCode::SupplyStatsTracked);
+        // Token-2022 genomes keep the founder supply in their founder mint
+        let is_2022 = ctx.accounts.mint.to_account_info().owner == &token_2022::ID;
+        require!(ctx.accounts.founder_mint.is_some() == is_2022, ErrorCode::GenomeMintMismatch);
+        
+        let founder_supply = ctx.accounts.founder_mint.as_ref().map_or(0, |mint| mint.supply);
+        genome.circulating_supply = ctx.accounts.mint.supply
+            .checked_add(founder_supply)
+            .ok_or(ErrorCode::Overflow)?;
+        genome.total_burned = genome.total_supply.saturating_sub(genome.circulating_supply);
+        genome.mint_events = 1;
+        
+        msg!("Genome {} supply stats backfilled: {} circulating, {} burned",
+            genome.genome_id,
+            genome.circulating_supply,
+            genome.total_burned
+        );
+        Ok(())
+    }
+
+    /// Hand a minted genome's mint authority to the program's PDA for that
+    /// mint, or with `revoke` drop it for good. Genome supply is minted once
+    /// and nothing signs with the PDA, so either way holders can check on
+    /// the mint that no further DNA can be created. A PDA-held authority can
+    /// later be revoked by the registry authority or the founder
+    pub fn finalize_mint_authority(
+        ctx: Context<FinalizeMintAuthority>,
+        revoke: bool,
+    ) -> Result<()> {
+        let genome = &ctx.accounts.genome.load()?;
+        let mint = &ctx.accounts.mint;
+        let signer = ctx.accounts.authority.key();
+        let program_authority = ctx.accounts.mint_authority.key();
+        let new_authority = if revoke { None } else { Some(program_authority) };
+        
+        match mint.mint_authority {
+            COption::Some(current) if current == signer => {
+                token_interface::set_authority(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        SetAuthority {
+                            current_authority: ctx.accounts.authority.to_account_info(),
+                            account_or_mint: mint.to_account_info(),
+                        },
+                    ),
+                    AuthorityType::MintTokens,
+                    new_authority,
+                )?;
+            }
+            COption::Some(current) if current == program_authority => {
+                require!(revoke, ErrorCode::MintAuthorityFinalized);
+                require!(
+                    signer == ctx.accounts.registry.authority || signer == genome.founder,
+                    ErrorCode::Unauthorized
+                );
+                let mint_key = mint.key();
+                let seeds: &[&[u8]] = &[MINT_AUTHORITY_SEED, mint_key.as_ref(), &[ctx.bumps.mint_authority]];
+                token_interface::set_authority(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        SetAuthority {
+                            current_authority: ctx.accounts.mint_authority.to_account_info(),
+                            account_or_mint: mint.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    AuthorityType::MintTokens,
+                    None,
+                )?;
+            }
+            COption::Some(_) => return err!(ErrorCode::Unauthorized),
+            COption::None => return err!(ErrorCode::MintAuthorityFinalized),
+        }
+        
+        emit!(MintAuthorityFinalizedEvent {
+            genome: ctx.accounts.genome.key(),
+            genome_id: genome.genome_id,
+            mint: mint.key(),
+            mint_authority: new_authority,
+            slot: Clock::get()?.slot,
+        });
+        
+        msg!("Genome {} mint authority {}",
+            genome.genome_id,
+            if revoke { "revoked" } else { "handed to the program" }
+        );
+        Ok(())
+    }
+
+    /// Hand a minted genome's freeze authority from its current holder to
+    /// the program PDA at ["freeze_authority", mint], so the dispute program
+    /// can freeze the genome's token accounts
+    pub fn delegate_freeze_authority(ctx: Context<DelegateFreezeAuthority>) -> Result<()> {
+        let mint = &ctx.accounts.mint;
+        let freeze_authority = ctx.accounts.freeze_authority.key();
+        require!(
+            mint.freeze_authority == COption::Some(ctx.accounts.authority.key()),
+            ErrorCode::Unauthorized
+        );
+        
+        token_interface::set_authority(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.authority.to_account_info(),
+                    account_or_mint: mint.to_account_info(),
+                },
+            ),
+            AuthorityType::FreezeAccount,
+            Some(freeze_authority),
+        )?;
+        
+        msg!("Genome {} freeze authority handed to {}", ctx.accounts.genome.load()?.genome_id, freeze_authority);
+        Ok(())
+    }
+
+    /// Freeze the genome's token accounts passed in remaining_accounts while
+    /// fraud claim `claim_id` is adjudicated (dispute program CPI only)
+    pub fn dispute_freeze<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DisputeFreeze<'info>>,
+        claim_id: u64,
+    ) -> Result<()> {
+        set_dispute_frozen(ctx, claim_id, true)
+    }
+
+    /// Thaw token accounts frozen for claim `claim_id` once it is settled
+    /// (dispute program CPI only)
+    pub fn dispute_thaw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DisputeFreeze<'info>>,
+        claim_id: u64,
+    ) -> Result<()> {
+        set_dispute_frozen(ctx, claim_id, false)
+    }
+
+    /// Fix a typo in an unminted genome's dna_sequence or block_hash
+    /// (registry authority, within METADATA_CORRECTION_WINDOW_SLOTS of
+    /// initialization). The previous hashes are kept in a correction log
+    /// entry at ["metadata_correction", genome, correction_id]
+    pub fn correct_genome_metadata(
+        ctx: Context<CorrectGenomeMetadata>,
+        dna_sequence: String,
+        block_hash: String,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let slot = Clock::get()?.slot;
+        let genome_key = ctx.accounts.genome.key();
+        let genome = &mut ctx.accounts.genome.load_mut()?;
+        require!(!genome.is_genesis(), ErrorCode::CorrectionWindowClosed);
+        require!(
+            slot <= genome.created_slot.saturating_add(METADATA_CORRECTION_WINDOW_SLOTS),
+            ErrorCode::CorrectionWindowClosed
+        );
+        
+        let correction_id = genome.metadata_corrections;
+        let correction = &mut ctx.accounts.correction;
+        correction.genome = genome_key;
+        correction.genome_id = genome.genome_id;
+        correction.correction_id = correction_id;
+        correction.previous_sequence_hash = genome.sequence_hash;
+        correction.previous_block_hash = genome.block_hash;
+        correction.slot = slot;
+        correction.authority = ctx.accounts.authority.key();
+        correction.bump = ctx.bumps.correction;
+        
+        // Founder traits derive from the sequence
+        genome.set_sequence(&dna_sequence, &block_hash);
+        genome.metadata_corrections = correction_id.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        
+        emit!(GenomeMetadataCorrectedEvent {
+            genome: genome_key,
+            genome_id: genome.genome_id,
+            correction_id,
+            slot,
+        });
+        
+        msg!("Genome {} metadata corrected (#{})", genome.genome_id, correction_id);
+        Ok(())
+    }
+
+    /// Open a collection at ["collection", curator, collection_id]; the
+    /// signer becomes its curator
+    pub fn create_collection(
+        ctx: Context<CreateCollection>,
+        collection_id: u64,
+        name: String,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_COLLECTION_NAME_LEN,
+            ErrorCode::InvalidCollectionName
+        );
+        
+        let collection = &mut ctx.accounts.collection;
+        collection.curator = ctx.accounts.curator.key();
+        collection.collection_id = collection_id;
+        collection.name = name;
+        collection.genome_count = 0;
+        collection.total_consciousness = 0;
+        collection.created_at = Clock::get()?.unix_timestamp;
+        collection.bump = ctx.bumps.collection;
+        
+        msg!("Collection {} \"{}\" created", collection_id, collection.name);
+        Ok(())
+    }
+
+    /// Add a genome to a collection (curator only); its membership lives
+    /// at ["collection_entry", collection, genome] so it is counted once
+    pub fn add_to_collection(ctx: Context<AddToCollection>) -> Result<()> {
+        let genome = &ctx.accounts.genome.load()?;
+        let entry = &mut ctx.accounts.entry;
+        entry.collection = ctx.accounts.collection.key();
+        entry.genome = ctx.accounts.genome.key();
+        entry.consciousness = genome.consciousness;
+        entry.added_at = Clock::get()?.unix_timestamp;
+        entry.bump = ctx.bumps.entry;
+        
+        let collection = &mut ctx.accounts.collection;
+        collection.genome_count = collection.genome_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        collection.total_consciousness = collection.total_consciousness
+            .checked_add(genome.consciousness as u64)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        emit!(CollectionMembershipEvent {
+            collection: collection.key(),
+            genome: ctx.accounts.genome.key(),
+            genome_id: genome.genome_id,
+            added: true,
+            genome_count: collection.genome_count,
+            total_consciousness: collection.total_consciousness,
+        });
+        
+        msg!("Genome {} added to collection {}", genome.genome_id, collection.collection_id);
+        Ok(())
+    }
+
+    /// Remove a genome from a collection (curator only), closing its entry
+    pub fn remove_from_collection(ctx: Context<RemoveFromCollection>) -> Result<()> {
+        let entry = &ctx.accounts.entry;
+        let collection = &mut ctx.accounts.collection;
+        collection.genome_count = collection.genome_count.saturating_sub(1);
+        collection.total_consciousness = collection.total_consciousness.saturating_sub(entry.consciousness as u64);
+        
+        emit!(CollectionMembershipEvent {
+            collection: collection.key(),
+            genome: entry.genome,
+            genome_id: ctx.accounts.genome.load()?.genome_id,
+            added: false,
+            genome_count: collection.genome_count,
+            total_consciousness: collection.total_consciousness,
+        });
+        
+        msg!("Genome {} removed from collection {}", ctx.accounts.genome.load()?.genome_id, collection.collection_id);
+        Ok(())
+    }
+
+    /// Create the concurrent merkle tree backing compressed genome records.
+    /// The tree account is pre-allocated by the caller and owned by
+    /// spl-account-compression; the tree authority PDA signs every write
+    pub fn init_genome_tree(
+        ctx: Context<InitGenomeTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let tree = &mut ctx.accounts.genome_tree;
+        tree.merkle_tree = ctx.accounts.merkle_tree.key();
+        tree.num_leaves = 0;
+        tree.authority_bump = ctx.bumps.tree_authority;
+        tree.bump = ctx.bumps.genome_tree;
+        
+        let bump = [tree.authority_bump];
+        let seeds: &[&[u8]] = &[TREE_AUTHORITY_SEED, &bump];
+        spl_account_compression::cpi::init_empty_merkle_tree(
+            CpiContext::new_with_signer(
+                ctx.accounts.compression_program.to_account_info(),
+                spl_account_compression::cpi::accounts::Initialize {
+                    merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                    authority: ctx.accounts.tree_authority.to_account_info(),
+                    noop: ctx.accounts.noop.to_account_info(),
+                },
+                &[seeds],
+            ),
+            max_depth,
+            max_buffer_size,
+        )?;
+        
+        msg!("Genome tree {} initialized: depth {}", tree.merkle_tree, max_depth);
+        Ok(())
+    }
+
+    /// Append a cold genome record as a leaf; the full record is logged
+    /// through the noop program so indexers can rebuild it
+    pub fn append_compressed_genome(
+        ctx: Context<ModifyGenomeTree>,
+        record: CompressedGenome,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let leaf = compressed_genome_leaf(&record)?;
+        let leaf_index = u32::try_from(ctx.accounts.genome_tree.num_leaves)
+            .map_err(|_| ErrorCode::Overflow)?;
+        ctx.accounts.genome_tree.num_leaves += 1;
+        
+        let bump = [ctx.accounts.genome_tree.authority_bump];
+        let seeds: &[&[u8]] = &[TREE_AUTHORITY_SEED, &bump];
+        spl_account_compression::cpi::append(ctx.accounts.modify_ctx(&[seeds], Vec::new()), leaf)?;
+        wrap_application_data_v1(record.try_to_vec()?, &ctx.accounts.noop)?;
+        
+        emit!(CompressedGenomeEvent {
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            genome_id: record.genome_id,
+            leaf_index,
+            previous_leaf: [0u8; 32],
+            leaf,
+        });
+        
+        msg!("Compressed genome {} appended at leaf {}", record.genome_id, leaf_index);
+        Ok(())
+    }
+
+    /// Replace a compressed record; remaining accounts are the proof nodes
+    pub fn replace_compressed_genome<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ModifyGenomeTree<'info>>,
+        root: [u8; 32],
+        previous: CompressedGenome,
+        record: CompressedGenome,
+        index: u32,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(previous.genome_id == record.genome_id, ErrorCode::CompressedGenomeMismatch);
+        
+        let previous_leaf = compressed_genome_leaf(&previous)?;
+        let leaf = compressed_genome_leaf(&record)?;
+        
+        let bump = [ctx.accounts.genome_tree.authority_bump];
+        let seeds: &[&[u8]] = &[TREE_AUTHORITY_SEED, &bump];
+        spl_account_compression::cpi::replace_leaf(
+            ctx.accounts.modify_ctx(&[seeds], ctx.remaining_accounts.to_vec()),
+            root,
+            previous_leaf,
+            leaf,
+            index,
+        )?;
+        wrap_application_data_v1(record.try_to_vec()?, &ctx.accounts.noop)?;
+        
+        emit!(CompressedGenomeEvent {
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            genome_id: record.genome_id,
+            leaf_index: index,
+            previous_leaf,
+            leaf,
+        });
+        
+        msg!("Compressed genome {} replaced at leaf {}", record.genome_id, index);
+        Ok(())
+    }
+
+    /// Proof-verified read of a compressed record; remaining accounts are
+    /// the proof nodes
+    pub fn verify_compressed_genome<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyCompressedGenome<'info>>,
+        root: [u8; 32],
+        record: CompressedGenome,
+        index: u32,
+    ) -> Result<GenomeInfo> {
+        spl_account_compression::cpi::verify_leaf(
+            CpiContext::new(
+                ctx.accounts.compression_program.to_account_info(),
+                spl_account_compression::cpi::accounts::VerifyLeaf {
+                    merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            root,
+            compressed_genome_leaf(&record)?,
+            index,
+        )?;
+        
+        Ok(GenomeInfo {
+            genome_id: record.genome_id,
+            sequence_hash: sequence_hash(&record.dna_sequence),
+            consciousness: record.consciousness,
+            blockchain: record.blockchain,
+            total_supply: record.total_supply,
+            rna_multiplier: record.rna_multiplier,
+            minted: false,
+            name: String::new(),
+            metadata_uri_hash: [0u8; 32],
+            species: record.species,
+            content_hash: [0u8; 32],
+        })
+    }
+
+    /// Promote a compressed record to a regular genome account so it can be
+    /// minted; the leaf is zeroed so the record only lives in one place
+    pub fn decompress_genome<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DecompressGenome<'info>>,
+        root: [u8; 32],
+        record: CompressedGenome,
+        index: u32,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let previous_leaf = compressed_genome_leaf(&record)?;
+        let bump = [ctx.accounts.genome_tree.authority_bump];
+        let seeds: &[&[u8]] = &[TREE_AUTHORITY_SEED, &bump];
+        spl_account_compression::cpi::replace_leaf(
+            CpiContext::new_with_signer(
+                ctx.accounts.compression_program.to_account_info(),
+                spl_account_compression::cpi::accounts::Modify {
+                    merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                    authority: ctx.accounts.tree_authority.to_account_info(),
+                    noop: ctx.accounts.noop.to_account_info(),
+                },
+                &[seeds],
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            root,
+            previous_leaf,
+            [0u8; 32],
+            index,
+        )?;
+        
+        // Name, mint, flags and counters start zeroed; compressed records
+        // are past their correction window, so created_slot stays 0
+        let genome = &mut ctx.accounts.genome.load_init()?;
+        genome.genome_id = record.genome_id;
+        genome.set_sequence(&record.dna_sequence, &record.block_hash);
+        genome.consciousness = record.consciousness;
+        genome.blockchain = padded(&record.blockchain);
+        genome.total_supply = record.total_supply;
+        genome.market_supply = (record.total_supply * 8571) / 10000; // 85.71%
+        genome.founder_supply = (record.total_supply * 1429) / 10000; // 14.29%
+        genome.rna_multiplier = record.rna_multiplier;
+        genome.created_at = record.created_at;
+        genome.founder = record.founder;
+        genome.species = record.species;
+        
+        emit!(CompressedGenomeEvent {
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            genome_id: record.genome_id,
+            leaf_index: index,
+            previous_leaf,
+            leaf: [0u8; 32],
+        });
+        
+        msg!("Genome {} decompressed from leaf {}", record.genome_id, index);
+        Ok(())
+    }
+
+    /// Governance weight of `holder`: sum of balance * consciousness * rna_multiplier
+    /// Remaining accounts are (genome, holder token account) pairs; the result
+    /// is set as return data for CPI callers
+    pub fn voting_power<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VotingPower<'info>>,
+    ) -> Result<u64> {
+        let pairs = ctx.remaining_accounts.chunks_exact(2);
+        require!(pairs.remainder().is_empty(), ErrorCode::InvalidVotingAccounts);
+
+        let holder = ctx.accounts.holder.key();
+        let mut seen: Vec<Pubkey> = Vec::new();
+        let mut power: u64 = 0;
+
+        for pair in pairs {
+            let genome_loader = AccountLoader::<GenomeV2>::try_from(&pair[0])?;
+            let genome = genome_loader.load()?;
+            let holding: Account<TokenAccount> = Account::try_from(&pair[1])?;
+
+            require!(!seen.contains(pair[1].key), ErrorCode::DuplicateAccount);
+            seen.push(*pair[1].key);
+            require!(genome.is_minted() && holding.mint == genome.mint, ErrorCode::GenomeMintMismatch);
+            require!(holding.owner == holder, ErrorCode::Unauthorized);
+
+            let weight = holding.amount
+                .checked_mul(genome.consciousness as u64)
+                .and_then(|w| w.checked_mul(genome.rna_multiplier as u64))
+                .ok_or(ErrorCode::Overflow)?;
+            power = power.checked_add(weight).ok_or(ErrorCode::Overflow)?;
+        }
+
+        msg!("Voting power of {}: {}", holder, power);
+        Ok(power)
+    }
+
+    /// Rewrite a genome created before GenomeV2 in the fixed layout
+    /// (permissionless). The account shrinks to GenomeV2::SPACE and the rent
+    /// it no longer needs goes back to the founder
+    pub fn migrate_genome(ctx: Context<MigrateGenome>) -> Result<()> {
+        let genome_info = ctx.accounts.genome.to_account_info();
+        let legacy = {
+            let data = genome_info.try_borrow_data()?;
+            require!(data.starts_with(&Genome::discriminator()), ErrorCode::NotLegacyGenome);
+            Genome::try_deserialize(&mut &data[..])?
+        };
+        require_keys_eq!(ctx.accounts.founder.key(), legacy.founder, ErrorCode::Unauthorized);
+        
+        genome_info.realloc(GenomeV2::SPACE, false)?;
+        let excess = genome_info.lamports().saturating_sub(Rent::get()?.minimum_balance(GenomeV2::SPACE));
+        **genome_info.try_borrow_mut_lamports()? -= excess;
+        **ctx.accounts.founder.try_borrow_mut_lamports()? += excess;
+        
+        let migrated = GenomeV2::from_legacy(&legacy);
+        let mut data = genome_info.try_borrow_mut_data()?;
+        data[..8].copy_from_slice(&GenomeV2::discriminator());
+        data[8..].copy_from_slice(bytemuck::bytes_of(&migrated));
+        
+        msg!("Genome {} migrated to the fixed layout", legacy.genome_id);
+        Ok(())
+    }
+}
+
+// Signer PDA a genesis engine account uses when registering child genomes;
+// only the registry's bound engine is recognized
+pub fn genesis_authority_address(genesis_program: &Pubkey, genesis_engine: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[GENESIS_AUTHORITY_SEED, genesis_engine.as_ref()], genesis_program).0
+}
+
+// Signer PDA the dispute program uses to freeze and thaw genome accounts
+pub fn dispute_authority_address(dispute_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[DISPUTE_AUTHORITY_SEED], dispute_program).0
+}
+
+// PDA that holds a genome mint's freeze authority after
+// delegate_freeze_authority
+pub fn freeze_authority_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[FREEZE_AUTHORITY_SEED, mint.as_ref()], &ID).0
+}
+
+// Freeze or thaw each token account in remaining_accounts; the token
+// program rejects accounts of another mint
+fn set_dispute_frozen<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DisputeFreeze<'info>>,
+    claim_id: u64,
+    frozen: bool,
+) -> Result<()> {
+    require!(!ctx.remaining_accounts.is_empty(), ErrorCode::NoDisputedAccounts);
+    let mint = ctx.accounts.mint.to_account_info();
+    let mint_key = mint.key();
+    let seeds: &[&[u8]] = &[FREEZE_AUTHORITY_SEED, mint_key.as_ref(), &[ctx.bumps.freeze_authority]];
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let authority = ctx.accounts.freeze_authority.to_account_info();
+    for account in ctx.remaining_accounts {
+        if frozen {
+            token_interface::freeze_account(CpiContext::new_with_signer(
+                token_program.clone(),
+                FreezeAccount { account: account.clone(), mint: mint.clone(), authority: authority.clone() },
+                &[seeds],
+            ))?;
+        } else {
+            token_interface::thaw_account(CpiContext::new_with_signer(
+                token_program.clone(),
+                ThawAccount { account: account.clone(), mint: mint.clone(), authority: authority.clone() },
+                &[seeds],
+            ))?;
+        }
+    }
+    
+    emit!(DisputeFreezeEvent {
+        genome: ctx.accounts.genome.key(),
+        genome_id: ctx.accounts.genome.load()?.genome_id,
+        claim_id,
+        accounts: ctx.remaining_accounts.iter().map(|account| account.key()).collect(),
+        frozen,
+        slot: Clock::get()?.slot,
+    });
+    msg!(
+        "Claim {}: {} {} account(s) of genome {}",
+        claim_id,
+        if frozen { "froze" } else { "thawed" },
+        ctx.remaining_accounts.len(),
+        ctx.accounts.genome.load()?.genome_id
+    );
+    Ok(())
+}
+
+// PDA that holds a genome mint's authority after finalize_mint_authority
+pub fn mint_authority_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MINT_AUTHORITY_SEED, mint.as_ref()], &ID).0
+}
+
+// PDA delegate of token accounts with a delegated listing
+pub fn listing_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[LISTING_AUTHORITY_SEED], &ID).0
+}
+
+// Pro-rata amount owed for (settled_slot, slot], capped by the escrow balance.
+// Returns (payment, new settled slot); a short escrow only covers whole slots.
+// Alleles of a genome that has no parents, from its sequence
+// Genomes created before traits were stored read as all zero; genesis
+// treats those as founders too
+pub fn founder_traits(genome_id: u64, dna_sequence: &str) -> TraitVector {
+    let seed = hashv(&[b"traits", &genome_id.to_le_bytes(), dna_sequence.as_bytes()]).to_bytes();
+    let mut traits = TraitVector::default();
+    traits.copy_from_slice(&seed[..TRAIT_COUNT]);
+    traits
+}
+
+// Bytes the holder signs to permit a transfer
+pub fn permit_message(mint: &Pubkey, from: &Pubkey, to: &Pubkey, amount: u64, nonce: u64, deadline: i64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(PERMIT_DOMAIN.len() + 32 * 4 + 24);
+    message.extend_from_slice(PERMIT_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(mint.as_ref());
+    message.extend_from_slice(from.as_ref());
+    message.extend_from_slice(to.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message.extend_from_slice(&deadline.to_le_bytes());
+    message
+}
+
+// Whether Ed25519 program data checks exactly one signature by `signer`
+// over `message`, with every offset pointing into the instruction itself
+pub fn ed25519_verifies(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    if data.len() < OFFSETS_START + OFFSETS_LEN || data[0] != 1 {
+        return false;
+    }
+    let field = |index: usize| {
+        let at = OFFSETS_START + index * 2;
+        u16::from_le_bytes([data[at], data[at + 1]]) as usize
+    };
+    let (signature_ix, key_offset, key_ix) = (field(1), field(2), field(3));
+    let (message_offset, message_size, message_ix) = (field(4), field(5), field(6));
+    let this_ix = u16::MAX as usize;
+    if signature_ix != this_ix || key_ix != this_ix || message_ix != this_ix {
+        return false;
+    }
+    data.get(key_offset..key_offset + 32) == Some(signer.as_ref())
+        && message_size == message.len()
+        && data.get(message_offset..message_offset + message_size) == Some(message)
+}
+
+pub fn license_settlement(rate_per_slot: u64, settled_slot: u64, slot: u64, balance: u64) -> (u64, u64) {
+    let elapsed = slot.saturating_sub(settled_slot);
+    let affordable = balance / rate_per_slot;
+    let slots = elapsed.min(affordable);
+    (slots * rate_per_slot, settled_slot + slots)
+}
+
+// Consciousness bucket of a score: 10 points wide, with 90-100 (and
+// anything above) sharing the top bucket
+pub fn consciousness_bucket(consciousness: u8) -> u8 {
+    consciousness.min(99) / CONSCIOUSNESS_BUCKET_WIDTH
+}
+
+// Append `genome` to a page of its consciousness bucket, once
+fn add_to_consciousness_page(page: &mut ConsciousnessPage, genome: &mut GenomeV2, genome_key: Pubkey) -> Result<()> {
+    require!(!genome.is_indexed(), ErrorCode::GenomeAlreadyIndexed);
+    require!(page.bucket == consciousness_bucket(genome.consciousness), ErrorCode::InvalidConsciousnessBucket);
+    require!(page.genomes.len() < CONSCIOUSNESS_PAGE_CAPACITY, ErrorCode::ConsciousnessPageFull);
+    page.genomes.push(genome_key);
+    genome.indexed = 1;
+    Ok(())
+}
+
+// GNOME index units per DNA token: consciousness scaled by the RNA multiplier (200 = 2.0x)
+pub fn index_weight(consciousness: u8, rna_multiplier: u16) -> u64 {
+    consciousness as u64 * rna_multiplier as u64 / 100
+}
+
+// Empty every leg's escrow into the matching destination and close the
+// escrows, returning their rent to the creator. Tokens sent to an escrow
+// on top of the leg go along with it
+fn release_bundle<'info>(
+    bundle: &Account<'info, GenomeBundle>,
+    accounts: &'info [AccountInfo<'info>],
+    creator: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    require!(accounts.len() == bundle.legs.len() * 2, ErrorCode::InvalidBundle);
+    
+    let creator_key = bundle.creator;
+    let bundle_id = bundle.bundle_id.to_le_bytes();
+    let bump = [bundle.bump];
+    let seeds: &[&[u8]] = &[BUNDLE_SEED, creator_key.as_ref(), &bundle_id, &bump];
+    
+    for (pair, leg) in accounts.chunks_exact(2).zip(&bundle.legs) {
+        let escrow: Account<TokenAccount> = Account::try_from(&pair[0])?;
+        require!(
+            escrow.owner == bundle.key() && escrow.mint == leg.mint,
+            ErrorCode::InvalidBundle
+        );
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: pair[0].clone(),
+                    to: pair[1].clone(),
+                    authority: bundle.to_account_info(),
+                },
+                &[seeds],
+            ),
+            escrow.amount,
+        )?;
+        token::close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: pair[0].clone(),
+                destination: creator.clone(),
+                authority: bundle.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+    }
+    Ok(())
+}
+
+fn pay_license<'info>(
+    license: &mut Account<'info, GenomeLicense>,
+    escrow: &Account<'info, TokenAccount>,
+    founder_rna_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    slot: u64,
+) -> Result<u64> {
+    let (payment, settled_slot) =
+        license_settlement(license.rate_per_slot, license.settled_slot, slot, escrow.amount);
+    
+    if payment > 0 {
+        let genome = license.genome;
+        let licensee = license.licensee;
+        let bump = [license.bump];
+        let seeds: &[&[u8]] = &[b"license", genome.as_ref(), licensee.as_ref(), &bump];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: escrow.to_account_info(),
+                    to: founder_rna_account.to_account_info(),
+                    authority: license.to_account_info(),
+                },
+                &[seeds],
+            ),
+            payment,
+        )?;
+    }
+    
+    license.settled_slot = settled_slot;
+    license.total_paid = license.total_paid
+        .checked_add(payment)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok(payment)
+}
+
+// Leaf committed to the genome tree for a compressed record
+pub fn compressed_genome_leaf(record: &CompressedGenome) -> Result<[u8; 32]> {
+    Ok(hashv(&[b"compressed_genome", &record.try_to_vec()?]).to_bytes())
+}
+
+fn validate_hold_tiers(min_hold_secs: &[i64; HOLD_TIER_COUNT], multiplier_bps: &[u16; HOLD_TIER_COUNT]) -> Result<()> {
+    require!(min_hold_secs[0] > 0, ErrorCode::InvalidHoldTiers);
+    require!(multiplier_bps[0] as u64 >= BPS_DENOMINATOR, ErrorCode::InvalidHoldTiers);
+    for i in 1..HOLD_TIER_COUNT {
+        require!(
+            min_hold_secs[i] > min_hold_secs[i - 1] && multiplier_bps[i] >= multiplier_bps[i - 1],
+            ErrorCode::InvalidHoldTiers
+        );
+    }
+    Ok(())
+}
+
+// Restart the hold at `hold_info` after DNA left `token_account`, if its
+// owner started one there. A hold from before balance checkpoints must be
+// migrated first
+fn restart_hold(hold_info: &AccountInfo, token_account: &Account<TokenAccount>, rna_multiplier: u16, now: i64) -> Result<()> {
+    if hold_info.owner != &crate::ID {
+        return Ok(());
+    }
+    require!(hold_info.data_len() == HolderState::SPACE, ErrorCode::HoldNotMigrated);
+    let mut data = hold_info.try_borrow_mut_data()?;
+    let mut hold = HolderState::try_deserialize(&mut &data[..])?;
+    if hold.token_account != token_account.key() {
+        return Ok(());
+    }
+    hold.checkpoint(token_account.amount, now);
+    hold.reset(token_account.amount, rna_multiplier, now);
+    hold.try_serialize(&mut &mut data[..])
+}
+
+// Tiers reached after holding for `held_secs`: 0 = none, n = min_hold_secs[n - 1] passed
+pub fn hold_tier(min_hold_secs: &[i64; HOLD_TIER_COUNT], held_secs: i64) -> usize {
+    min_hold_secs.iter().take_while(|min| held_secs >= **min).count()
+}
+
+// rna_multiplier boosted by the tier's multiplier (10000 = 1.0x)
+pub fn hold_multiplier(rna_multiplier: u16, tier: usize, multiplier_bps: &[u16; HOLD_TIER_COUNT]) -> Result<u16> {
+    if tier == 0 {
+        return Ok(rna_multiplier);
+    }
+    let boosted = rna_multiplier as u64 * multiplier_bps[tier - 1] as u64 / BPS_DENOMINATOR;
+    u16::try_from(boosted).map_err(|_| error!(ErrorCode::Overflow))
+}
+
+// Experience the consciousness point above `consciousness` costs
+pub fn growth_point_cost(curve: &GrowthCurve, consciousness: u8) -> u128 {
+    let step = BPS_DENOMINATOR as u128 + curve.step_bps as u128 * consciousness as u128;
+    curve.base_experience as u128 * step / BPS_DENOMINATOR as u128
+}
+
+// Points one claim buys from `consciousness` with `experience`, and the
+// experience they cost
+pub fn consciousness_growth(curve: &GrowthCurve, consciousness: u8, experience: u128) -> (u8, u128) {
+    let (mut points, mut spent) = (0u8, 0u128);
+    while points < curve.max_points_per_claim && consciousness.saturating_add(points) < curve.max_consciousness {
+        let cost = growth_point_cost(curve, consciousness + points);
+        if experience - spent < cost {
+            break;
+        }
+        spent += cost;
+        points += 1;
+    }
+    (points, spent)
+}
+
+// Snapshot merkle helpers
+pub fn snapshot_commitment(merkle_root: &[u8; 32], total_tracked_supply: u64, holder_count: u32) -> [u8; 32] {
+    hashv(&[merkle_root, &total_tracked_supply.to_le_bytes(), &holder_count.to_le_bytes()]).to_bytes()
+}
+
+pub fn snapshot_leaf(holder: &Pubkey, balance: u64) -> [u8; 32] {
+    hashv(&[&[0u8], holder.as_ref(), &balance.to_le_bytes()]).to_bytes()
+}
+
+pub fn verify_holder_balance(root: &[u8; 32], holder: &Pubkey, balance: u64, proof: &[[u8; 32]]) -> bool {
+    let mut node = snapshot_leaf(holder, balance);
+    for sibling in proof {
+        node = if node <= *sibling {
+            hashv(&[&[1u8], &node, sibling]).to_bytes()
+        } else {
+            hashv(&[&[1u8], sibling, &node]).to_bytes()
+        };
+    }
+    node == *root
+}
+
+// Account structures
+/// Program-wide settings and the DNA authority, at ["registry"]
+#[account]
+pub struct Registry {
+    pub authority: Pubkey,
+    pub snapshot_count: u64,
+    pub bump: u8,
+    pub genesis_program: Pubkey,
+    /// interest on new and updated founder mints
+    pub founder_rate_bps: i16,
+    /// engine account of genesis_program whose authority PDA registers genomes
+    pub genesis_engine: Pubkey,
+}
+
+impl Registry {
+    pub const SPACE: usize = 8 + 32 + 8 + 1 + 32 + 2 + 32;
+    /// Allocated size of registries created before genesis_engine
+    pub const PRE_GENESIS_ENGINE_SPACE: usize = 8 + 100;
+}
+
+/// Committed, then revealed, merkle root of DNA holder balances at
+/// ["snapshot", snapshot_id]
+#[account]
+pub struct HolderSnapshot {
+    pub snapshot_id: u64,
+    pub commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub total_tracked_supply: u64,
+    pub holder_count: u32,
+    pub slot: u64,
+    pub committed_at: i64,
+    pub finalized: bool,
+    pub bump: u8,
+}
+
+/// Genome token type in a fixed zero-copy layout; the metadata strings are
+/// kept as a sequence hash, a zero-padded chain tag and the decoded block
+/// hash. Flags are 0 or 1
+#[account(zero_copy)]
+pub struct GenomeV2 {
+    pub genome_id: u64,
+    pub total_supply: u64,
+    pub market_supply: u64,
+    pub founder_supply: u64,
+    pub created_at: i64,
+    pub minted_at: i64,
+    pub matures_at_slot: u64,
+    /// minted less burned through this program
+    pub circulating_supply: u64,
+    pub total_burned: u64,
+    /// opens the metadata correction window
+    pub created_slot: u64,
+    /// mint instructions that issued supply
+    pub mint_events: u32,
+    pub rna_multiplier: u16,
+    pub species: u16,
+    /// sha256 of the DNA sequence
+    pub sequence_hash: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub metadata_uri_hash: [u8; 32],
+    pub founder: Pubkey,
+    pub mint: Pubkey,
+    /// zero-padded UTF-8
+    pub name: [u8; MAX_GENOME_NAME_LEN],
+    /// zero-padded chain name
+    pub blockchain: [u8; BLOCKCHAIN_TAG_LEN],
+    /// alleles inherited by genesis children
+    pub traits: TraitVector,
+    pub consciousness: u8,
+    pub minted: u8,
+    /// frozen for good by retire_genome
+    pub retired: u8,
+    /// listed in its consciousness bucket
+    pub indexed: u8,
+    /// next correction log id
+    pub metadata_corrections: u8,
+    pub padding: [u8; 3],
+    /// sha256 of the off-chain genome JSON/FASTA, 0 = none
+    pub content_hash: [u8; 32],
+    /// how long after minting the holding cap holds
+    pub holding_cap_slots: u64,
+    pub minted_slot: u64,
+    /// of market supply per wallet, 0 = no cap
+    pub holding_cap_bps: u16,
+    pub holding_cap_padding: [u8; 6],
+}
+
+impl GenomeV2 {
+    pub const SPACE: usize = 8 + std::mem::size_of::<GenomeV2>();
+    // Before the holding cap and, earlier, content_hash were appended;
+    // extend_genome grows these
+    pub const PRE_HOLDING_CAP_SPACE: usize = Self::SPACE - 24;
+    pub const PRE_CONTENT_HASH_SPACE: usize = Self::PRE_HOLDING_CAP_SPACE - 32;
+    
+    // Whether a wallet may hold `balance` at `slot`: for holding_cap_slots
+    // after minting, tracked transfers may not take a wallet above
+    // holding_cap_bps of the market supply
+    pub fn within_holding_cap(&self, balance: u64, slot: u64) -> bool {
+        self.holding_cap_bps == 0
+            || slot >= self.minted_slot.saturating_add(self.holding_cap_slots)
+            || balance as u128 * BPS_DENOMINATOR as u128 <= self.market_supply as u128 * self.holding_cap_bps as u128
+    }
+    
+    pub fn is_minted(&self) -> bool {
+        self.minted != 0
+    }
+    
+    pub fn is_retired(&self) -> bool {
+        self.retired != 0
+    }
+    
+    pub fn is_indexed(&self) -> bool {
+        self.indexed != 0
+    }
+    
+    pub fn is_genesis(&self) -> bool {
+        self.blockchain == GENESIS_BLOCKCHAIN_TAG
+    }
+    
+    pub fn name(&self) -> &str {
+        unpadded(&self.name)
+    }
+    
+    pub fn blockchain_name(&self) -> &str {
+        unpadded(&self.blockchain)
+    }
+    
+    // Sequence-derived fields, as initialization and corrections set them
+    pub fn set_sequence(&mut self, dna_sequence: &str, block_hash: &str) {
+        self.sequence_hash = sequence_hash(dna_sequence);
+        self.block_hash = parse_block_hash(block_hash);
+        self.traits = founder_traits(self.genome_id, dna_sequence);
+    }
+    
+    pub fn record_mint(&mut self, amount: u64) -> Result<()> {
+        self.circulating_supply = self.circulating_supply.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        self.mint_events = self.mint_events.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+    
+    pub fn record_burn(&mut self, amount: u64) -> Result<()> {
+        self.circulating_supply = self.circulating_supply.saturating_sub(amount);
+        self.total_burned = self.total_burned.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+    
+    // The fixed layout of a legacy genome; chain names past 8 bytes are
+    // cut, and genomes from before traits were stored get their founder
+    // traits
+    pub fn from_legacy(legacy: &Genome) -> Self {
+        let traits = if legacy.traits == [0u8; TRAIT_COUNT] && legacy.blockchain != "genesis" {
+            founder_traits(legacy.genome_id, &legacy.dna_sequence)
+        } else {
+            legacy.traits
+        };
+        Self {
+            genome_id: legacy.genome_id,
+            total_supply: legacy.total_supply,
+            market_supply: legacy.market_supply,
+            founder_supply: legacy.founder_supply,
+            created_at: legacy.created_at,
+            minted_at: legacy.minted_at,
+            matures_at_slot: legacy.matures_at_slot,
+            circulating_supply: legacy.circulating_supply,
+            total_burned: legacy.total_burned,
+            created_slot: legacy.created_slot,
+            mint_events: legacy.mint_events,
+            rna_multiplier: legacy.rna_multiplier,
+            species: legacy.species,
+            sequence_hash: if legacy.dna_sequence.is_empty() { [0u8; 32] } else { sequence_hash(&legacy.dna_sequence) },
+            block_hash: parse_block_hash(&legacy.block_hash),
+            metadata_uri_hash: legacy.metadata_uri_hash,
+            founder: legacy.founder,
+            mint: legacy.mint,
+            name: padded(&legacy.name),
+            blockchain: padded(&legacy.blockchain),
+            traits,
+            consciousness: legacy.consciousness,
+            minted: legacy.minted as u8,
+            retired: legacy.retired as u8,
+            indexed: legacy.indexed as u8,
+            metadata_corrections: legacy.metadata_corrections,
+            padding: [0u8; 3],
+            content_hash: [0u8; 32],
+            holding_cap_slots: 0,
+            minted_slot: 0,
+            holding_cap_bps: 0,
+            holding_cap_padding: [0u8; 6],
+        }
+    }
+}
+
+// The string `padded` stored, up to its first zero byte
+pub fn unpadded(bytes: &[u8]) -> &str {
+    let len = bytes.iter().position(|&byte| byte == 0).unwrap_or(bytes.len());
+    std::str::from_utf8(&bytes[..len]).unwrap_or_default()
+}
+
+// sha256 of a DNA sequence, as GenomeV2 stores it
+pub fn sequence_hash(dna_sequence: &str) -> [u8; 32] {
+    hashv(&[dna_sequence.as_bytes()]).to_bytes()
+}
+
+// A block hash as 32 bytes: 64 hex digits (optionally 0x-prefixed) or
+// base58; empty is all zero and any other format is stored hashed
+pub fn parse_block_hash(block_hash: &str) -> [u8; 32] {
+    if block_hash.is_empty() {
+        return [0u8; 32];
+    }
+    let hex = block_hash.strip_prefix("0x").unwrap_or(block_hash);
+    if hex.len() == 64 {
+        let mut bytes = [0u8; 32];
+        let decoded = bytes.iter_mut().enumerate().all(|(i, byte)| {
+            u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).map(|value| *byte = value).is_ok()
+        });
+        if decoded {
+            return bytes;
+        }
+    }
+    match block_hash.parse::<Pubkey>() {
+        Ok(key) => key.to_bytes(),
+        Err(_) => hashv(&[block_hash.as_bytes()]).to_bytes(),
+    }
+}
+
+// `value` zero-padded to N bytes, cut at N
+pub fn padded<const N: usize>(value: &str) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    let len = value.len().min(N);
+    bytes[..len].copy_from_slice(&value.as_bytes()[..len]);
+    bytes
+}
+
+/// String layout of genomes created before GenomeV2; only migrate_genome
+/// reads it
+#[account]
+pub struct Genome {
+    pub genome_id: u64,
+    pub dna_sequence: String,
+    pub consciousness: u8,
+    pub blockchain: String,
+    pub block_hash: String,
+    pub total_supply: u64,
+    pub market_supply: u64,
+    pub founder_supply: u64,
+    pub rna_multiplier: u16,
+    pub minted: bool,
+    pub created_at: i64,
+    pub minted_at: i64,
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub founder: Pubkey,
+    pub mint: Pubkey,
+    pub matures_at_slot: u64,
+    pub species: u16,
+    /// frozen for good by retire_genome
+    pub retired: bool,
+    /// alleles inherited by genesis children
+    pub traits: TraitVector,
+    /// listed in its consciousness bucket
+    pub indexed: bool,
+    /// minted less burned through this program
+    pub circulating_supply: u64,
+    pub total_burned: u64,
+    /// mint instructions that issued supply
+    pub mint_events: u32,
+    /// opens the metadata correction window
+    pub created_slot: u64,
+    /// next correction log id
+    pub metadata_corrections: u8,
+}
+
+/// Genome accounts of one consciousness bucket at ["consciousness_page",
+/// bucket, page]. Pages fill in order, so a client reads a bucket as pages
+/// 0, 1, ... up to the first one that is not full
+#[account]
+pub struct ConsciousnessPage {
+    pub bucket: u8,
+    pub page: u32,
+    pub genomes: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ConsciousnessPage {
+    pub const SPACE: usize = 8 + 1 + 4 + 4 + CONSCIOUSNESS_PAGE_CAPACITY * 32 + 1;
+}
+
+/// Proof that `burner` burned `amount` DNA of a genome, at ["burn_receipt",
+/// burner, receipt_id]; kept after consumption so it cannot be spent twice
+#[account]
+pub struct BurnReceipt {
+    pub burner: Pubkey,
+    pub receipt_id: u64,
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+    pub slot: u64,
+    pub consumed: bool,
+    /// signer that consumed it, default until then
+    pub consumer: Pubkey,
+    pub bump: u8,
+}
+
+/// Next permit nonce of a holder at ["permit_nonce", holder]
+#[account]
+pub struct PermitNonce {
+    pub owner: Pubkey,
+    pub next_nonce: u64,
+    pub bump: u8,
+}
+
+/// Commemorative record of a retired genome at ["retirement", genome]
+#[account]
+pub struct GenomeRetirement {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub reason_code: u32,
+    pub burned: u64,
+    pub slot: u64,
+    pub retired_at: i64,
+    pub bump: u8,
+}
+
+/// Values a metadata correction replaced, at ["metadata_correction",
+/// genome, correction_id]
+#[account]
+pub struct GenomeMetadataCorrection {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub correction_id: u8,
+    pub previous_sequence_hash: [u8; 32],
+    pub previous_block_hash: [u8; 32],
+    pub slot: u64,
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+impl GenomeMetadataCorrection {
+    pub const SPACE: usize = 8 + 32 + 8 + 1 + 32 + 32 + 8 + 32 + 1;
+}
+
+/// The dispute program bound to the registry, at ["dispute_config"]
+#[account]
+pub struct DisputeConfig {
+    pub dispute_program: Pubkey,
+    pub bump: u8,
+}
+
+impl DisputeConfig {
+    pub const SPACE: usize = 8 + 32 + 1;
+}
+
+/// Curated set of genomes at ["collection", curator, collection_id]
+#[account]
+pub struct Collection {
+    pub curator: Pubkey,
+    pub collection_id: u64,
+    pub name: String,
+    pub genome_count: u64,
+    /// average = total / genome_count
+    pub total_consciousness: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Collection {
+    pub const SPACE: usize = 8 + 32 + 8 + 4 + MAX_COLLECTION_NAME_LEN + 8 + 8 + 8 + 1;
+}
+
+/// A genome's membership of a collection at ["collection_entry",
+/// collection, genome]
+#[account]
+pub struct CollectionEntry {
+    pub collection: Pubkey,
+    pub genome: Pubkey,
+    /// as counted into the collection total
+    pub consciousness: u8,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl CollectionEntry {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 1;
+}
+
+/// RNA-priced package of genome legs at ["bundle", creator, bundle_id];
+/// closed when sold or cancelled
+#[account]
+pub struct GenomeBundle {
+    pub creator: Pubkey,
+    pub bundle_id: u64,
+    pub rna_mint: Pubkey,
+    pub price: u64,
+    pub legs: Vec<BundleLeg>,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Escrow-less offer of DNA from `token_account`, which has approved the
+/// listing authority as its delegate; closed when sold out or cancelled
+#[account]
+pub struct DelegatedListing {
+    pub seller: Pubkey,
+    pub token_account: Pubkey,
+    pub genome: Pubkey,
+    pub rna_mint: Pubkey,
+    pub amount: u64,
+    pub price_per_token: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl DelegatedListing {
+    pub const SPACE: usize = 8 + 32 * 4 + 8 + 8 + 8 + 1;
+}
+
+impl GenomeBundle {
+    pub const SPACE: usize = 8 + 32 + 8 + 32 + 8 + 4 + MAX_BUNDLE_LEGS * BundleLeg::LEN + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BundleLeg {
+    pub genome: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl BundleLeg {
+    pub const LEN: usize = 32 + 32 + 8;
+}
+
+/// RNA streamed per slot from a licensee to a genome founder, at
+/// ["license", genome, licensee]; the escrow holds the unpaid deposit
+#[account]
+pub struct GenomeLicense {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub founder: Pubkey,
+    pub licensee: Pubkey,
+    pub rna_mint: Pubkey,
+    pub rate_per_slot: u64,
+    pub start_slot: u64,
+    pub settled_slot: u64,
+    pub total_deposited: u64,
+    pub total_paid: u64,
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
+
+/// Minimum hold times and their yield multipliers, at ["hold_tiers"]
+#[account]
+pub struct HoldTiers {
+    pub min_hold_secs: [i64; HOLD_TIER_COUNT],
+    pub multiplier_bps: [u16; HOLD_TIER_COUNT],
+    pub bump: u8,
+}
+
+/// A holder's hold tier and balance checkpoint for one genome, at
+/// ["hold", genome, holder]
+#[account]
+pub struct HolderState {
+    pub genome: Pubkey,
+    pub holder: Pubkey,
+    pub token_account: Pubkey,
+    pub hold_start: i64,
+    /// lowest balance since hold_start
+    pub baseline: u64,
+    pub tier: u8,
+    pub effective_multiplier: u16,
+    pub bump: u8,
+    // Balance checkpoint for time-weighted RNA yield
+    pub checkpoint_balance: u64,
+    pub checkpoint_at: i64,
+    /// balance * seconds since the last settle_hold_balance
+    pub balance_secs: u128,
+}
+
+impl HolderState {
+    pub const SPACE: usize = 8 + 160;
+    // Holds started before balance checkpoints
+    pub const LEGACY_SPACE: usize = 8 + 128;
+    
+    pub fn reset(&mut self, balance: u64, rna_multiplier: u16, now: i64) {
+        self.hold_start = now;
+        self.baseline = balance;
+        self.tier = 0;
+        self.effective_multiplier = rna_multiplier;
+    }
+    
+    // Credit the time since the last checkpoint at the lower of the two
+    // balances, then record `balance`. A drop moved out behind the
+    // program's back therefore counts for the whole interval, and a flash
+    // buy only from the checkpoint that observes it
+    pub fn checkpoint(&mut self, balance: u64, now: i64) {
+        let elapsed = now.saturating_sub(self.checkpoint_at).max(0) as u128;
+        let credited = self.checkpoint_balance.min(balance) as u128;
+        self.balance_secs = self.balance_secs.saturating_add(credited * elapsed);
+        self.checkpoint_balance = balance;
+        self.checkpoint_at = now;
+    }
+}
+
+/// Governance curve turning staked DNA into consciousness, at ["growth_curve"]
+#[account]
+pub struct GrowthCurve {
+    pub min_lock_epochs: u64,
+    /// cost of the first point above 0
+    pub base_experience: u64,
+    /// extra cost per point of current consciousness
+    pub step_bps: u16,
+    pub max_points_per_claim: u8,
+    /// growth stops here
+    pub max_consciousness: u8,
+    pub bump: u8,
+}
+
+impl GrowthCurve {
+    pub const SPACE: usize = 8 + 8 + 8 + 2 + 1 + 1 + 1;
+}
+
+/// DNA locked for consciousness growth at ["genome_stake", genome, staker];
+/// its tokens sit in ["genome_stake_vault", stake]
+#[account]
+pub struct GenomeStake {
+    pub genome: Pubkey,
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub start_epoch: u64,
+    pub unlock_epoch: u64,
+    /// experience is counted up to here
+    pub accrued_epoch: u64,
+    /// DNA-epochs not yet spent on consciousness
+    pub experience: u128,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl GenomeStake {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 16 + 1 + 1;
+    
+    // Credit the epochs locked since the last accrual, up to the unlock
+    pub fn accrue(&mut self, epoch: u64) {
+        let until = epoch.min(self.unlock_epoch);
+        if until > self.accrued_epoch {
+            let epochs = (until - self.accrued_epoch) as u128;
+            self.experience = self.experience.saturating_add(self.amount as u128 * epochs);
+            self.accrued_epoch = until;
+        }
+    }
+}
+
+/// The GNOME index and its mint, at ["index"]
+#[account]
+pub struct IndexConfig {
+    pub index_mint: Pubkey,
+    /// GNOME outstanding
+    pub total_value: u64,
+    pub bump: u8,
+}
+
+/// A genome admitted to the GNOME index, at ["index_genome", genome]
+#[account]
+pub struct IndexGenome {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    /// max DNA held in the vault
+    pub cap: u64,
+    pub deposited: u64,
+    /// snapshotted at listing
+    pub weight: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+/// The concurrent merkle tree holding compressed genomes, at ["genome_tree"]
+#[account]
+pub struct GenomeTree {
+    pub merkle_tree: Pubkey,
+    pub num_leaves: u64,
+    pub authority_bump: u8,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeGenomeArgs {
+    pub genome_id: u64,
+    pub dna_sequence: String,
+    pub consciousness: u8,
+    pub blockchain: String,
+    pub block_hash: String,
+    pub total_supply: u64,
+    /// e.g. 200 = 2.0x
+    pub rna_multiplier: u16,
+    /// sha256 of the off-chain genome JSON/FASTA
+    pub content_hash: [u8; 32],
+    /// Most of the market supply one wallet may hold, 0 = no cap
+    pub holding_cap_bps: u16,
+    /// How long after minting the cap holds
+    pub holding_cap_slots: u64,
+    pub founder: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RegisterGenesisGenomeArgs {
+    pub genome_id: u64,
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub consciousness: u8,
+    pub rna_multiplier: u16,
+    pub matures_at_slot: u64,
+    pub species: u16,
+    pub traits: TraitVector,
+    pub total_supply: u64,
+}
+
+// Cold genome metadata stored as a leaf of the genome tree
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedGenome {
+    pub genome_id: u64,
+    pub dna_sequence: String,
+    pub consciousness: u8,
+    pub blockchain: String,
+    pub block_hash: String,
+    pub total_supply: u64,
+    pub rna_multiplier: u16,
+    pub species: u16,
+    pub founder: Pubkey,
+    pub created_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GenomeInfo {
+    pub genome_id: u64,
+    pub sequence_hash: [u8; 32],
+    pub consciousness: u8,
+    pub blockchain: String,
+    pub total_supply: u64,
+    pub rna_multiplier: u16,
+    pub minted: bool,
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub species: u16,
+    pub content_hash: [u8; 32],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GenomeSupplyStats {
+    pub genome_id: u64,
+    pub total_supply: u64,
+    pub circulating_supply: u64,
+    pub total_burned: u64,
+    pub mint_events: u32,
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct InitializeGenome<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = GenomeV2::SPACE
+    )]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump,
+        constraint = registry.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(mut)]
+    pub consciousness_page: Option<Account<'info, ConsciousnessPage>>,
+}
+
+#[derive(Accounts)]
+#[instruction(bucket: u8, page: u32)]
+pub struct CreateConsciousnessPage<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = ConsciousnessPage::SPACE,
+        seeds = [CONSCIOUSNESS_PAGE_SEED, &[bucket], &page.to_le_bytes()],
+        bump
+    )]
+    pub consciousness_page: Account<'info, ConsciousnessPage>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [CONSCIOUSNESS_PAGE_SEED, &[bucket], &page.saturating_sub(1).to_le_bytes()],
+        bump = previous_page.bump
+    )]
+    pub previous_page: Option<Account<'info, ConsciousnessPage>>,
+}
+
+#[derive(Accounts)]
+pub struct IndexByConsciousness<'info> {
+    #[account(mut)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(mut)]
+    pub consciousness_page: Account<'info, ConsciousnessPage>,
+}
+
+#[derive(Accounts)]
+pub struct MintGenomeTokens<'info> {
+    #[account(mut, constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub market_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub founder_account: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+pub struct CreateFounderMint<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    // Genome mint the founder mint takes its decimals from
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+    
+    /// CHECK: created here as a Token-2022 mint
+    #[account(
+        mut,
+        seeds = [FOUNDER_MINT_SEED, genome.key().as_ref()],
+        bump
+    )]
+    pub founder_mint: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintGenomeTokens2022<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(mut, constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+    
+    #[account(mut, token::mint = mint)]
+    pub market_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [FOUNDER_MINT_SEED, genome.key().as_ref()],
+        bump
+    )]
+    pub founder_mint: InterfaceAccount<'info, InterfaceMint>,
+    
+    #[account(
+        mut,
+        token::mint = founder_mint,
+        token::authority = genome.load()?.founder
+    )]
+    pub founder_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFounderRate<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct TransferDNA<'info> {
+    #[account(
+        constraint = from.mint == genome.load()?.mint @ ErrorCode::GenomeMintMismatch,
+        constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired
+    )]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(mut)]
+    pub from: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub to: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    /// CHECK: the sender's hold PDA, restarted in restart_hold if they
+    /// started one on `from`; required so an open hold cannot be left out
+    #[account(mut, seeds = [HOLD_SEED, genome.key().as_ref(), from.owner.as_ref()], bump)]
+    pub from_hold: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [DELEGATED_LISTING_SEED, from.key().as_ref()],
+        bump = from_listing.bump,
+        constraint = from_listing.seller == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub from_listing: Option<Account<'info, DelegatedListing>>,
+}
+
+#[derive(Accounts)]
+#[instruction(receipt_id: u64)]
+pub struct BurnDNA<'info> {
+    #[account(mut, constraint = from.mint == genome.load()?.mint @ ErrorCode::GenomeMintMismatch)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(mut, address = genome.load()?.mint @ ErrorCode::GenomeMintMismatch)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub from: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = burner,
+        space = 8 + 128,
+        seeds = [BURN_RECEIPT_SEED, burner.key().as_ref(), receipt_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, BurnReceipt>,
+    
+    #[account(mut)]
+    pub burner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    /// CHECK: the sender's hold PDA, restarted in restart_hold if they
+    /// started one on `from`; required so an open hold cannot be left out
+    #[account(mut, seeds = [HOLD_SEED, genome.key().as_ref(), from.owner.as_ref()], bump)]
+    pub from_hold: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConsumeReceipt<'info> {
+    #[account(
+        mut,
+        seeds = [BURN_RECEIPT_SEED, burner.key().as_ref(), receipt.receipt_id.to_le_bytes().as_ref()],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, BurnReceipt>,
+    
+    pub burner: Signer<'info>,
+    
+    pub consumer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferWithPermit<'info> {
+    #[account(
+        constraint = from.mint == genome.load()?.mint @ ErrorCode::GenomeMintMismatch,
+        constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired
+    )]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        mut,
+        constraint = from.delegate == COption::Some(permit_authority.key()) @ ErrorCode::PermitNotDelegated
+    )]
+    pub from: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub to: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = 8 + 32 + 8 + 1,
+        seeds = [PERMIT_NONCE_SEED, from.owner.as_ref()],
+        bump
+    )]
+    pub permit_nonce: Account<'info, PermitNonce>,
+    
+    /// CHECK: PDA delegate of permit-enabled token accounts
+    #[account(
+        seeds = [PERMIT_AUTHORITY_SEED],
+        bump
+    )]
+    pub permit_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    
+    /// CHECK: instructions sysvar, read for the Ed25519 verification
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    /// CHECK: the sender's hold PDA, restarted in restart_hold if they
+    /// started one on `from`; required so an open hold cannot be left out
+    #[account(mut, seeds = [HOLD_SEED, genome.key().as_ref(), from.owner.as_ref()], bump)]
+    pub from_hold: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeHoldTiers<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 64,
+        seeds = [HOLD_TIERS_SEED],
+        bump
+    )]
+    pub hold_tiers: Account<'info, HoldTiers>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetHoldTiers<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        mut,
+        seeds = [HOLD_TIERS_SEED],
+        bump = hold_tiers.bump
+    )]
+    pub hold_tiers: Account<'info, HoldTiers>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartHold<'info> {
+    #[account(constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        token::mint = genome.load()?.mint,
+        token::authority = holder
+    )]
+    pub holding: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = holder,
+        space = HolderState::SPACE,
+        seeds = [HOLD_SEED, genome.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshHold<'info> {
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        seeds = [HOLD_TIERS_SEED],
+        bump = hold_tiers.bump
+    )]
+    pub hold_tiers: Account<'info, HoldTiers>,
+    
+    #[account(
+        mut,
+        seeds = [HOLD_SEED, genome.key().as_ref(), holder_state.holder.as_ref()],
+        bump = holder_state.bump,
+        has_one = genome
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    
+    #[account(address = holder_state.token_account @ ErrorCode::HoldAccountMismatch)]
+    pub holding: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SettleHoldBalance<'info> {
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        mut,
+        seeds = [HOLD_SEED, genome.key().as_ref(), holder.key().as_ref()],
+        bump = holder_state.bump,
+        has_one = genome,
+        has_one = holder
+    )]
+    pub holder_state: Account<'info, HolderState>,
+    
+    #[account(address = holder_state.token_account @ ErrorCode::HoldAccountMismatch)]
+    pub holding: Account<'info, TokenAccount>,
+    
+    pub holder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateHold<'info> {
+    /// CHECK: a HolderState at the legacy size, checked in migrate_hold
+    #[account(mut, owner = crate::ID)]
+    pub holder_state: UncheckedAccount<'info>,
+    
+    pub holding: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetGenomeInfo<'info> {
+    pub genome: AccountLoader<'info, GenomeV2>,
+}
+
+#[derive(Accounts)]
+pub struct BackfillSupplyStats<'info> {
+    #[account(mut)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(address = genome.load()?.mint @ ErrorCode::GenomeMintMismatch)]
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+    
+    #[account(seeds = [FOUNDER_MINT_SEED, genome.key().as_ref()], bump)]
+    pub founder_mint: Option<InterfaceAccount<'info, InterfaceMint>>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeMintAuthority<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(constraint = genome.load()?.is_minted() @ ErrorCode::GenomeNotMinted)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        mut,
+        address = genome.load()?.mint @ ErrorCode::GenomeMintMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+    
+    /// CHECK: signing PDA only; never signs a mint
+    #[account(seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetDisputeProgram<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = DisputeConfig::SPACE,
+        seeds = [DISPUTE_CONFIG_SEED],
+        bump
+    )]
+    pub dispute_config: Account<'info, DisputeConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateFreezeAuthority<'info> {
+    #[account(constraint = genome.load()?.is_minted() @ ErrorCode::GenomeNotMinted)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        mut,
+        address = genome.load()?.mint @ ErrorCode::GenomeMintMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+    
+    /// CHECK: signing PDA only; receives the freeze authority
+    #[account(seeds = [FREEZE_AUTHORITY_SEED, mint.key().as_ref()], bump)]
+    pub freeze_authority: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeFreeze<'info> {
+    #[account(
+        seeds = [DISPUTE_CONFIG_SEED],
+        bump = dispute_config.bump
+    )]
+    pub dispute_config: Account<'info, DisputeConfig>,
+    
+    #[account(constraint = genome.load()?.is_minted() @ ErrorCode::GenomeNotMinted)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        address = genome.load()?.mint @ ErrorCode::GenomeMintMismatch,
+        mint::token_program = token_program,
+        constraint = mint.freeze_authority == COption::Some(freeze_authority.key())
+            @ ErrorCode::FreezeAuthorityNotDelegated
+    )]
+    pub mint: InterfaceAccount<'info, InterfaceMint>,
+    
+    /// CHECK: signing PDA only; the mint's freeze authority
+    #[account(seeds = [FREEZE_AUTHORITY_SEED, mint.key().as_ref()], bump)]
+    pub freeze_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        constraint = dispute_authority.key() == dispute_authority_address(&dispute_config.dispute_program)
+            @ ErrorCode::Unauthorized
+    )]
+    pub dispute_authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CorrectGenomeMetadata<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(mut, constraint = !genome.load()?.is_minted() @ ErrorCode::AlreadyMinted)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = GenomeMetadataCorrection::SPACE,
+        seeds = [METADATA_CORRECTION_SEED, genome.key().as_ref(), &[genome.load()?.metadata_corrections]],
+        bump
+    )]
+    pub correction: Account<'info, GenomeMetadataCorrection>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct CreateCollection<'info> {
+    #[account(
+        init,
+        payer = curator,
+        space = Collection::SPACE,
+        seeds = [COLLECTION_SEED, curator.key().as_ref(), &collection_id.to_le_bytes()],
+        bump
+    )]
+    pub collection: Account<'info, Collection>,
+    
+    #[account(mut)]
+    pub curator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddToCollection<'info> {
+    #[account(mut, has_one = curator @ ErrorCode::Unauthorized)]
+    pub collection: Account<'info, Collection>,
+    
+    #[account(constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        init,
+        payer = curator,
+        space = CollectionEntry::SPACE,
+        seeds = [COLLECTION_ENTRY_SEED, collection.key().as_ref(), genome.key().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, CollectionEntry>,
+    
+    #[account(mut)]
+    pub curator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromCollection<'info> {
+    #[account(mut, has_one = curator @ ErrorCode::Unauthorized)]
+    pub collection: Account<'info, Collection>,
+    
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        mut,
+        close = curator,
+        has_one = collection,
+        has_one = genome,
+        seeds = [COLLECTION_ENTRY_SEED, collection.key().as_ref(), genome.key().as_ref()],
+        bump = entry.bump
+    )]
+    pub entry: Account<'info, CollectionEntry>,
+    
+    #[account(mut)]
+    pub curator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VotingPower<'info> {
+    /// CHECK: only compared against token account owners
+    pub holder: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGenome<'info> {
+    /// CHECK: a legacy Genome, checked by discriminator in migrate_genome
+    #[account(mut, owner = crate::ID)]
+    pub genome: UncheckedAccount<'info>,
+    
+    /// CHECK: receives the freed rent; must be the genome's founder
+    #[account(mut)]
+    pub founder: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendGenome<'info> {
+    /// CHECK: a GenomeV2 at PRE_CONTENT_HASH_SPACE, checked in extend_genome
+    #[account(mut, owner = crate::ID)]
+    pub genome: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendRegistry<'info> {
+    /// CHECK: a Registry at PRE_GENESIS_ENGINE_SPACE, checked in extend_registry
+    #[account(mut, owner = crate::ID, seeds = [b"registry"], bump)]
+    pub registry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetContentHash<'info> {
+    #[account(mut)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    pub founder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGrowthCurve<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = GrowthCurve::SPACE,
+        seeds = [GROWTH_CURVE_SEED],
+        bump
+    )]
+    pub growth_curve: Account<'info, GrowthCurve>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeGenomeTokens<'info> {
+    #[account(constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        seeds = [GROWTH_CURVE_SEED],
+        bump = growth_curve.bump
+    )]
+    pub growth_curve: Account<'info, GrowthCurve>,
+    
+    #[account(
+        init,
+        payer = staker,
+        space = GenomeStake::SPACE,
+        seeds = [GENOME_STAKE_SEED, genome.key().as_ref(), staker.key().as_ref()],
+        bump
+    )]
+    pub stake: Account<'info, GenomeStake>,
+    
+    #[account(
+        init,
+        payer = staker,
+        seeds = [GENOME_STAKE_VAULT_SEED, stake.key().as_ref()],
+        bump,
+        token::mint = dna_mint,
+        token::authority = stake
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(constraint = dna_mint.key() == genome.load()?.mint @ ErrorCode::GenomeMintMismatch)]
+    pub dna_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = dna_mint,
+        token::authority = staker
+    )]
+    pub staker_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub staker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimConsciousnessGrowth<'info> {
+    #[account(mut, constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        seeds = [GROWTH_CURVE_SEED],
+        bump = growth_curve.bump
+    )]
+    pub growth_curve: Account<'info, GrowthCurve>,
+    
+    #[account(
+        mut,
+        seeds = [GENOME_STAKE_SEED, genome.key().as_ref(), staker.key().as_ref()],
+        bump = stake.bump,
+        has_one = genome,
+        has_one = staker
+    )]
+    pub stake: Account<'info, GenomeStake>,
+    
+    pub staker: Signer<'info>,
+    
+    #[account(mut)]
+    pub consciousness_page: Option<Account<'info, ConsciousnessPage>>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeGenomeTokens<'info> {
+    #[account(
+        mut,
+        seeds = [GENOME_STAKE_SEED, stake.genome.as_ref(), staker.key().as_ref()],
+        bump = stake.bump,
+        has_one = staker,
+        close = staker
+    )]
+    pub stake: Account<'info, GenomeStake>,
+    
+    #[account(
+        mut,
+        seeds = [GENOME_STAKE_VAULT_SEED, stake.key().as_ref()],
+        bump = stake.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = vault.mint,
+        token::authority = staker
+    )]
+    pub staker_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub staker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TransferFounder<'info> {
+    #[account(mut, constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    pub founder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Registry::SPACE,
+        seeds = [b"registry"],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::Unauthorized)]
+    pub program: Program<'info, crate::program::RsmDnaToken>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+}
+
+#[derive(Accounts)]
+pub struct SetGenesisProgram<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(genome_id: u64)]
+pub struct RegisterGenesisGenome<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = GenomeV2::SPACE,
+        seeds = [b"genome", genome_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        constraint = genesis_authority.key()
+            == genesis_authority_address(&registry.genesis_program, &registry.genesis_engine)
+            @ ErrorCode::Unauthorized
+    )]
+    pub genesis_authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: only its key is recorded, as the genome's founder
+    pub founder: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct SetGenomeSpecies<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(mut)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGenomeMaturity<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        mut,
+        seeds = [b"genome", genome.load()?.genome_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        constraint = genesis_authority.key()
+            == genesis_authority_address(&registry.genesis_program, &registry.genesis_engine)
+            @ ErrorCode::Unauthorized
+    )]
+    pub genesis_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RetireGenome<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(mut, constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 80,
+        seeds = [RETIREMENT_SEED, genome.key().as_ref()],
+        bump
+    )]
+    pub retirement: Account<'info, GenomeRetirement>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    // Required once the genome is minted
+    #[account(mut)]
+    pub mint: Option<Account<'info, Mint>>,
+    
+    #[account(mut, token::authority = authority)]
+    pub authority_holding: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct LicenseGenome<'info> {
+    #[account(constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        init,
+        payer = licensee,
+        space = 8 + 250,
+        seeds = [b"license", genome.key().as_ref(), licensee.key().as_ref()],
+        bump
+    )]
+    pub license: Account<'info, GenomeLicense>,
+    
+    #[account(
+        init,
+        payer = licensee,
+        seeds = [b"license_escrow", license.key().as_ref()],
+        bump,
+        token::mint = rna_mint,
+        token::authority = license
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = rna_mint,
+        token::authority = licensee
+    )]
+    pub licensee_rna_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub licensee: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+#[instruction(bundle_id: u64)]
+pub struct CreateBundle<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = GenomeBundle::SPACE,
+        seeds = [BUNDLE_SEED, creator.key().as_ref(), &bundle_id.to_le_bytes()],
+        bump
+    )]
+    pub bundle: Account<'info, GenomeBundle>,
+    
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+pub struct BuyBundle<'info> {
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator,
+        seeds = [BUNDLE_SEED, bundle.creator.as_ref(), &bundle.bundle_id.to_le_bytes()],
+        bump = bundle.bump
+    )]
+    pub bundle: Account<'info, GenomeBundle>,
+    
+    /// CHECK: bundle creator, receives the rent of the bundle and its escrows
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = bundle.rna_mint,
+        token::authority = creator
+    )]
+    pub creator_rna_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = bundle.rna_mint,
+        token::authority = buyer
+    )]
+    pub buyer_rna_account: Account<'info, TokenAccount>,
+    
+    pub buyer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+pub struct CancelBundle<'info> {
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator,
+        seeds = [BUNDLE_SEED, creator.key().as_ref(), &bundle.bundle_id.to_le_bytes()],
+        bump = bundle.bump
+    )]
+    pub bundle: Account<'info, GenomeBundle>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ListWithDelegate<'info> {
+    #[account(
+        constraint = token_account.mint == genome.load()?.mint @ ErrorCode::GenomeMintMismatch,
+        constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired
+    )]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(mut, token::authority = seller)]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = seller,
+        space = DelegatedListing::SPACE,
+        seeds = [DELEGATED_LISTING_SEED, token_account.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, DelegatedListing>,
+    
+    /// CHECK: PDA delegate of listed token accounts
+    #[account(
+        seeds = [LISTING_AUTHORITY_SEED],
+        bump
+    )]
+    pub listing_authority: UncheckedAccount<'info>,
+    
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDelegatedSale<'info> {
+    #[account(
+        mut,
+        has_one = seller,
+        has_one = token_account,
+        has_one = genome,
+        seeds = [DELEGATED_LISTING_SEED, token_account.key().as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, DelegatedListing>,
+    
+    #[account(constraint = !genome.load()?.is_retired() @ ErrorCode::GenomeRetired)]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = genome.load()?.mint)]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: listing seller, receives the listing rent once sold out
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = listing.rna_mint,
+        token::authority = seller
+    )]
+    pub seller_rna_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = listing.rna_mint,
+        token::authority = buyer
+    )]
+    pub buyer_rna_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: PDA delegate of listed token accounts
+    #[account(
+        seeds = [LISTING_AUTHORITY_SEED],
+        bump
+    )]
+    pub listing_authority: UncheckedAccount<'info>,
+    
+    pub buyer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    /// CHECK: the seller's hold PDA, restarted in restart_hold if they
+    /// started one on `token_account`
+    #[account(mut, seeds = [HOLD_SEED, genome.key().as_ref(), seller.key().as_ref()], bump)]
+    pub seller_hold: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDelegatedListing<'info> {
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        has_one = token_account,
+        seeds = [DELEGATED_LISTING_SEED, token_account.key().as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, DelegatedListing>,
+    
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: PDA delegate of listed token accounts
+    #[account(
+        seeds = [LISTING_AUTHORITY_SEED],
+        bump
+    )]
+    pub listing_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TopUpLicense<'info> {
+    #[account(mut)]
+    pub license: Account<'info, GenomeLicense>,
+    
+    #[account(
+        mut,
+        seeds = [b"license_escrow", license.key().as_ref()],
+        bump = license.escrow_bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = license.rna_mint,
+        token::authority = funder
+    )]
+    pub funder_rna_account: Account<'info, TokenAccount>,
+    
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+pub struct SettleLicense<'info> {
+    #[account(
+        mut,
+        seeds = [b"license", license.genome.as_ref(), license.licensee.as_ref()],
+        bump = license.bump
+    )]
+    pub license: Account<'info, GenomeLicense>,
+    
+    #[account(
+        mut,
+        seeds = [b"license_escrow", license.key().as_ref()],
+        bump = license.escrow_bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = license.rna_mint,
+        token::authority = license.founder
+    )]
+    pub founder_rna_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+pub struct CloseLicense<'info> {
+    #[account(
+        mut,
+        seeds = [b"license", license.genome.as_ref(), license.licensee.as_ref()],
+        bump = license.bump,
+        has_one = licensee,
+        close = licensee
+    )]
+    pub license: Account<'info, GenomeLicense>,
+    
+    #[account(
+        mut,
+        seeds = [b"license_escrow", license.key().as_ref()],
+        bump = license.escrow_bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = license.rna_mint,
+        token::authority = license.founder
+    )]
+    pub founder_rna_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = license.rna_mint,
+        token::authority = licensee
+    )]
+    pub licensee_rna_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub licensee: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+#[instruction(snapshot_id: u64)]
+pub struct CommitSnapshot<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 200,
+        seeds = [b"snapshot", snapshot_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub snapshot: Account<'info, HolderSnapshot>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TakeSnapshot<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        mut,
+        seeds = [b"snapshot", snapshot.snapshot_id.to_le_bytes().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, HolderSnapshot>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProveSnapshotBalance<'info> {
+    #[account(
+        seeds = [b"snapshot", snapshot.snapshot_id.to_le_bytes().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, HolderSnapshot>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeIndex<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 64,
+        seeds = [INDEX_SEED],
+        bump
+    )]
+    pub index: Account<'info, IndexConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"index_mint"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = index
+    )]
+    pub index_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ListIndexGenome<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        seeds = [INDEX_SEED],
+        bump = index.bump
+    )]
+    pub index: Account<'info, IndexConfig>,
+    
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 100,
+        seeds = [b"index_genome", genome.key().as_ref()],
+        bump
+    )]
+    pub index_genome: Account<'info, IndexGenome>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"index_vault", genome.key().as_ref()],
+        bump,
+        token::mint = dna_mint,
+        token::authority = index
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(constraint = dna_mint.key() == genome.load()?.mint @ ErrorCode::GenomeMintMismatch)]
+    pub dna_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetIndexCap<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(mut)]
+    pub index_genome: Account<'info, IndexGenome>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct IndexSwap<'info> {
+    #[account(
+        mut,
+        seeds = [INDEX_SEED],
+        bump = index.bump,
+        has_one = index_mint
+    )]
+    pub index: Account<'info, IndexConfig>,
+    
+    #[account(mut)]
+    pub index_mint: Account<'info, Mint>,
+    
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(
+        mut,
+        seeds = [b"index_genome", genome.key().as_ref()],
+        bump = index_genome.bump
+    )]
+    pub index_genome: Account<'info, IndexGenome>,
+    
+    #[account(
+        mut,
+        seeds = [b"index_vault", genome.key().as_ref()],
+        bump = index_genome.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = genome.load()?.mint,
+        token::authority = owner
+    )]
+    pub owner_dna_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = index_mint,
+        token::authority = owner
+    )]
+    pub owner_index_account: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+pub struct InitGenomeTree<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 64,
+        seeds = [GENOME_TREE_SEED],
+        bump
+    )]
+    pub genome_tree: Account<'info, GenomeTree>,
+    
+    /// CHECK: zeroed tree account, validated by spl-account-compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA signer for tree writes
+    #[account(
+        seeds = [TREE_AUTHORITY_SEED],
+        bump
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub noop: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ModifyGenomeTree<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        mut,
+        seeds = [GENOME_TREE_SEED],
+        bump = genome_tree.bump,
+        has_one = merkle_tree
+    )]
+    pub genome_tree: Account<'info, GenomeTree>,
+    
+    /// CHECK: validated by spl-account-compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA signer for tree writes
+    #[account(
+        seeds = [TREE_AUTHORITY_SEED],
+        bump = genome_tree.authority_bump
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub noop: Program<'info, Noop>,
+}
+
+impl<'info> ModifyGenomeTree<'info> {
+    fn modify_ctx<'a, 'b, 'c>(
+        &self,
+        signer_seeds: &'a [&'b [&'c [u8]]],
+        proof: Vec<AccountInfo<'info>>,
+    ) -> CpiContext<'a, 'b, 'c, 'info, spl_account_compression::cpi::accounts::Modify<'info>> {
+        CpiContext::new_with_signer(
+            self.compression_program.to_account_info(),
+            spl_account_compression::cpi::accounts::Modify {
+                merkle_tree: self.merkle_tree.to_account_info(),
+                authority: self.tree_authority.to_account_info(),
+                noop: self.noop.to_account_info(),
+            },
+            signer_seeds,
+        )
+        .with_remaining_accounts(proof)
+    }
+}
+
+#[derive(Accounts)]
+pub struct VerifyCompressedGenome<'info> {
+    #[account(
+        seeds = [GENOME_TREE_SEED],
+        bump = genome_tree.bump,
+        has_one = merkle_tree
+    )]
+    pub genome_tree: Account<'info, GenomeTree>,
+    
+    /// CHECK: validated by spl-account-compression
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], record: CompressedGenome)]
+pub struct DecompressGenome<'info> {
+    #[account(
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+    
+    #[account(
+        seeds = [GENOME_TREE_SEED],
+        bump = genome_tree.bump,
+        has_one = merkle_tree
+    )]
+    pub genome_tree: Account<'info, GenomeTree>,
+    
+    /// CHECK: validated by spl-account-compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA signer for tree writes
+    #[account(
+        seeds = [TREE_AUTHORITY_SEED],
+        bump = genome_tree.authority_bump
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = GenomeV2::SPACE,
+        seeds = [b"genome", record.genome_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub genome: AccountLoader<'info, GenomeV2>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub noop: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+// Events
+#[event]
+pub struct SnapshotTakenEvent {
+    pub snapshot_id: u64,
+    pub merkle_root: [u8; 32],
+    pub total_tracked_supply: u64,
+    pub holder_count: u32,
+    pub slot: u64,
+}
+
+#[event]
+pub struct CompressedGenomeEvent {
+    pub merkle_tree: Pubkey,
+    pub genome_id: u64,
+    pub leaf_index: u32,
+    pub previous_leaf: [u8; 32],
+    pub leaf: [u8; 32],
+}
+
+#[event]
+pub struct IndexSwapEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub owner: Pubkey,
+    pub dna_amount: u64,
+    pub index_amount: u64,
+    pub redeemed: bool,
+}
+
+#[event]
+pub struct LicenseSettledEvent {
+    pub license: Pubkey,
+    pub genome_id: u64,
+    pub licensee: Pubkey,
+    pub founder: Pubkey,
+    pub paid: u64,
+    pub refunded: u64,
+    pub settled_slot: u64,
+    pub closed: bool,
+}
+
+#[event]
+pub struct GenomeRetiredEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub reason_code: u32,
+    pub burned: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct PermitTransferEvent {
+    pub genome: Pubkey,
+    pub holder: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+    pub submitter: Pubkey,
+}
+
+#[event]
+pub struct DelegatedSaleEvent {
+    pub listing: Pubkey,
+    pub genome_id: u64,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub price: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct BundleSoldEvent {
+    pub bundle: Pubkey,
+    pub bundle_id: u64,
+    pub creator: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub legs: u8,
+}
+
+#[event]
+pub struct FounderRateUpdatedEvent {
+    pub authority: Pubkey,
+    pub rate_bps: i16,
+    pub mints_updated: u32,
+}
+
+#[event]
+pub struct DnaBurnedEvent {
+    pub receipt: Pubkey,
+    pub burner: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ReceiptConsumedEvent {
+    pub receipt: Pubkey,
+    pub consumer: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MintAuthorityFinalizedEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub mint: Pubkey,
+    pub mint_authority: Option<Pubkey>, // None once revoked
+    pub slot: u64,
+}
+
+#[event]
+pub struct CollectionMembershipEvent {
+    pub collection: Pubkey,
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub added: bool, // false when removed
+    pub genome_count: u64,
+    pub total_consciousness: u64,
+}
+
+#[event]
+pub struct GenomeMetadataCorrectedEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub correction_id: u8,
+    pub slot: u64,
+}
+
+#[event]
+pub struct DisputeFreezeEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub claim_id: u64,
+    pub accounts: Vec<Pubkey>,
+    pub frozen: bool, // false when thawed
+    pub slot: u64,
+}
+
+#[event]
+pub struct ConsciousnessGrownEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub staker: Pubkey,
+    pub previous: u8,
+    pub consciousness: u8,
+    pub experience_spent: u128,
+    pub experience_left: u128,
+}
+
+#[event]
+pub struct FounderTransferredEvent {
+    pub genome: Pubkey,
+    pub genome_id: u64,
+    pub previous: Pubkey,
+    pub founder: Pubkey,
 }
 
 // Errors
@@ -213,4 +5282,116 @@ pub enum ErrorCode {
     AlreadyMinted,
     #[msg("Invalid token amount")]
     InvalidAmount,
+    #[msg("Math overflow")]
+    Overflow,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Snapshot id must be the next in sequence")]
+    InvalidSnapshotId,
+    #[msg("Snapshot already finalized")]
+    SnapshotFinalized,
+    #[msg("Snapshot not finalized")]
+    SnapshotNotFinalized,
+    #[msg("Snapshot data does not match commitment")]
+    CommitmentMismatch,
+    #[msg("Invalid merkle proof")]
+    InvalidProof,
+    #[msg("Genome name must be 1-32 bytes")]
+    InvalidGenomeName,
+    #[msg("License rate must be positive")]
+    InvalidRate,
+    #[msg("Genome is still incubating")]
+    GenomeIncubating,
+    #[msg("Token account does not hold this genome")]
+    GenomeMintMismatch,
+    #[msg("Maturity can only be moved earlier")]
+    InvalidMaturity,
+    #[msg("Voting accounts must be (genome, token account) pairs")]
+    InvalidVotingAccounts,
+    #[msg("Token account passed twice")]
+    DuplicateAccount,
+    #[msg("Replacement record is for a different genome")]
+    CompressedGenomeMismatch,
+    #[msg("Genome has not been minted")]
+    GenomeNotMinted,
+    #[msg("Genome has no index weight")]
+    ZeroIndexWeight,
+    #[msg("Deposit exceeds the genome's index cap")]
+    IndexCapExceeded,
+    #[msg("Not enough of this genome in the index vault")]
+    InsufficientIndexDeposits,
+    #[msg("Hold tiers must have increasing durations and non-decreasing multipliers of at least 1.0x")]
+    InvalidHoldTiers,
+    #[msg("Token account is not the one tracked by this hold")]
+    HoldAccountMismatch,
+    #[msg("Ecosystem is paused")]
+    EcosystemPaused,
+    #[msg("Genome is retired")]
+    GenomeRetired,
+    #[msg("Permit deadline has passed")]
+    PermitExpired,
+    #[msg("Permit nonce is not the holder's next nonce")]
+    InvalidPermitNonce,
+    #[msg("Permit signature missing or not by the holder")]
+    InvalidPermitSignature,
+    #[msg("Token account has not delegated to the permit authority")]
+    PermitNotDelegated,
+    #[msg("Bundle needs 1-8 legs, each with its escrow and token accounts")]
+    InvalidBundle,
+    #[msg("Founder rate cannot be negative")]
+    InvalidFounderRate,
+    #[msg("Consciousness page is not for this genome's bucket")]
+    InvalidConsciousnessBucket,
+    #[msg("Consciousness page is full; open the next one")]
+    ConsciousnessPageFull,
+    #[msg("Previous consciousness page is missing or not full")]
+    PreviousPageNotFull,
+    #[msg("Genome is already in its consciousness bucket")]
+    GenomeAlreadyIndexed,
+    #[msg("Burn receipt has already been consumed")]
+    ReceiptConsumed,
+    #[msg("Seller revoked the listing delegate or no longer holds the listed tokens")]
+    ListingRevoked,
+    #[msg("Genome supply statistics are already tracked")]
+    SupplyStatsTracked,
+    #[msg("Mint authority is already finalized")]
+    MintAuthorityFinalized,
+    #[msg("Genome metadata can no longer be corrected")]
+    CorrectionWindowClosed,
+    #[msg("Collection name must be 1-32 bytes")]
+    InvalidCollectionName,
+    #[msg("Genome mint's freeze authority is not delegated to the program")]
+    FreezeAuthorityNotDelegated,
+    #[msg("Pass the token accounts to freeze or thaw")]
+    NoDisputedAccounts,
+    #[msg("Blockchain name must be at most 8 bytes")]
+    InvalidBlockchainTag,
+    #[msg("Account is not a legacy genome")]
+    NotLegacyGenome,
+    #[msg("Hold already has balance checkpoints")]
+    HoldAlreadyMigrated,
+    #[msg("Genome already has the current layout")]
+    GenomeAlreadyExtended,
+    #[msg("Genome content hash is already set")]
+    ContentHashAlreadySet,
+    #[msg("Content hash must not be zero")]
+    InvalidContentHash,
+    #[msg("Holding cap must be at most 10000 bps and have a window exactly when set")]
+    InvalidHoldingCap,
+    #[msg("Transfer would take the recipient above the genome's holding cap")]
+    HoldingCapExceeded,
+    #[msg("Growth curve needs a lock, a point cost and a per-claim bound, and stops at 100 at most")]
+    InvalidGrowthCurve,
+    #[msg("Stake must lock for at least the growth curve's minimum epochs")]
+    LockTooShort,
+    #[msg("Stake has not earned enough experience for a consciousness point")]
+    NotEnoughExperience,
+    #[msg("Genome is leaving its consciousness bucket; pass the page it is listed on")]
+    ConsciousnessPageRequired,
+    #[msg("Stake is still locked")]
+    StakeLocked,
+    #[msg("Registry already has the current layout")]
+    RegistryAlreadyExtended,
+    #[msg("Hold predates balance checkpoints; run migrate_hold first")]
+    HoldNotMigrated,
 }
@@ -0,0 +1,292 @@
+// RSM Faucet - Devnet RNA/RSM drips for integration testing
+// One faucet per mint, funded vault PDA
+// Per-wallet and global daily limits
+
+#[cfg(feature = "mainnet")]
+compile_error!("rsm-faucet must not be built for mainnet");
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+
+declare_id!("FCTxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+#[program]
+pub mod rsm_faucet {
+    use super::*;
+
+    // Create a faucet and its vault for `mint`
+    pub fn initialize_faucet(
+        ctx: Context<InitializeFaucet>,
+        drip_amount: u64,
+        drips_per_wallet: u8, // per day
+        daily_cap: u64,       // total tokens per day across all wallets
+    ) -> Result<()> {
+        validate_limits(drip_amount, drips_per_wallet, daily_cap)?;
+
+        let faucet = &mut ctx.accounts.faucet;
+
+        faucet.authority = ctx.accounts.authority.key();
+        faucet.mint = ctx.accounts.mint.key();
+        faucet.vault = ctx.accounts.vault.key();
+        faucet.drip_amount = drip_amount;
+        faucet.drips_per_wallet = drips_per_wallet;
+        faucet.daily_cap = daily_cap;
+        faucet.day = 0;
+        faucet.dripped_today = 0;
+        faucet.total_dripped = 0;
+        faucet.paused = false;
+        faucet.bump = ctx.bumps.faucet;
+
+        msg!("Faucet initialized: {} per drip, {} drips per wallet per day", drip_amount, drips_per_wallet);
+        Ok(())
+    }
+
+    // Update limits or pause (authority only)
+    pub fn update_faucet(
+        ctx: Context<UpdateFaucet>,
+        drip_amount: u64,
+        drips_per_wallet: u8,
+        daily_cap: u64,
+        paused: bool,
+    ) -> Result<()> {
+        validate_limits(drip_amount, drips_per_wallet, daily_cap)?;
+
+        let faucet = &mut ctx.accounts.faucet;
+        faucet.drip_amount = drip_amount;
+        faucet.drips_per_wallet = drips_per_wallet;
+        faucet.daily_cap = daily_cap;
+        faucet.paused = paused;
+
+        msg!("Faucet updated: {} per drip, paused: {}", drip_amount, paused);
+        Ok(())
+    }
+
+    // Send one drip to the caller's token account
+    pub fn drip(ctx: Context<Drip>) -> Result<()> {
+        let day = Clock::get()?.unix_timestamp / SECONDS_PER_DAY;
+        let faucet = &mut ctx.accounts.faucet;
+        require!(!faucet.paused, ErrorCode::FaucetPaused);
+
+        if faucet.day != day {
+            faucet.day = day;
+            faucet.dripped_today = 0;
+        }
+        let dripped_today = faucet
+            .dripped_today
+            .checked_add(faucet.drip_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(dripped_today <= faucet.daily_cap, ErrorCode::DailyCapReached);
+
+        let record = &mut ctx.accounts.drip_record;
+        if record.day != day {
+            record.day = day;
+            record.drips_today = 0;
+        }
+        require!(
+            record.drips_today < faucet.drips_per_wallet,
+            ErrorCode::WalletLimitReached
+        );
+
+        let amount = faucet.drip_amount;
+        let mint = faucet.mint;
+        let bump = [faucet.bump];
+        let seeds: &[&[u8]] = &[b"faucet", mint.as_ref(), &bump];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.recipient_account.to_account_info(),
+                    authority: faucet.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        faucet.dripped_today = dripped_today;
+        faucet.total_dripped = faucet
+            .total_dripped
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        record.wallet = ctx.accounts.wallet.key();
+        record.drips_today += 1;
+        record.total_received = record
+            .total_received
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        record.bump = ctx.bumps.drip_record;
+
+        msg!("Dripped {} to {} ({} today)", amount, record.wallet, record.drips_today);
+        Ok(())
+    }
+
+    // Reclaim vault tokens (authority only)
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let faucet = &ctx.accounts.faucet;
+        let mint = faucet.mint;
+        let bump = [faucet.bump];
+        let seeds: &[&[u8]] = &[b"faucet", mint.as_ref(), &bump];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: faucet.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Withdrew {} from faucet vault", amount);
+        Ok(())
+    }
+}
+
+fn validate_limits(drip_amount: u64, drips_per_wallet: u8, daily_cap: u64) -> Result<()> {
+    require!(
+        drip_amount > 0 && drips_per_wallet > 0 && daily_cap >= drip_amount,
+        ErrorCode::InvalidLimits
+    );
+    Ok(())
+}
+
+// Account structures
+#[account]
+pub struct Faucet {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub drip_amount: u64,
+    pub drips_per_wallet: u8,
+    pub daily_cap: u64,
+    pub day: i64,
+    pub dripped_today: u64,
+    pub total_dripped: u64,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct DripRecord {
+    pub wallet: Pubkey,
+    pub day: i64,
+    pub drips_today: u8,
+    pub total_received: u64,
+    pub bump: u8,
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct InitializeFaucet<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 200,
+        seeds = [b"faucet", mint.key().as_ref()],
+        bump
+    )]
+    pub faucet: Account<'info, Faucet>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vault", faucet.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = faucet
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFaucet<'info> {
+    #[account(mut, has_one = authority)]
+    pub faucet: Account<'info, Faucet>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Drip<'info> {
+    #[account(
+        mut,
+        seeds = [b"faucet", faucet.mint.as_ref()],
+        bump = faucet.bump
+    )]
+    pub faucet: Account<'info, Faucet>,
+
+    #[account(
+        mut,
+        address = faucet.vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + 64,
+        seeds = [b"drip", faucet.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub drip_record: Account<'info, DripRecord>,
+
+    #[account(
+        mut,
+        token::mint = faucet.mint,
+        token::authority = wallet
+    )]
+    pub recipient_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(has_one = authority, has_one = vault)]
+    pub faucet: Account<'info, Faucet>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = faucet.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Errors
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Drip amount, per-wallet drips and daily cap must be positive")]
+    InvalidLimits,
+    #[msg("Invalid token amount")]
+    InvalidAmount,
+    #[msg("Faucet is paused")]
+    FaucetPaused,
+    #[msg("Faucet daily cap reached")]
+    DailyCapReached,
+    #[msg("Wallet already received today's drips")]
+    WalletLimitReached,
+    #[msg("Math overflow")]
+    Overflow,
+}
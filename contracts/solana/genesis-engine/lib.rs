@@ -2,10 +2,63 @@
 // Burns RNA to create new DNA genomes
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Burn};
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Burn, CloseAccount, Transfer};
+use rsm_dna_token::program::RsmDnaToken;
+use rsm_core::{
+    Ancestor, CatalogMutation, DominanceModel, GenesisSeeds, SupplyRule, TraitVector, DEFAULT_DOMINANCE,
+    DEFAULT_MUTATION_BPS, MAX_LINEAGE_ANCESTORS, MAX_LINEAGE_GENERATIONS, RARITY_TIERS, TRAIT_COUNT,
+};
+use rsm_dna_token::{GENESIS_AUTHORITY_SEED, MAX_GENOME_NAME_LEN};
+use rsm_pause::{GlobalPause, GLOBAL_PAUSE_SEED};
 
 declare_id!("GENxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
+// Pyth pull-oracle receiver, owner of PriceUpdateV2 accounts
+pub mod pyth_receiver {
+    anchor_lang::declare_id!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+}
+
+// Common / Rare / Epic / Mythic
+pub const DEFAULT_RARITY_WEIGHTS: [u16; 4] = [7000, 2200, 700, 100];
+pub const DEFAULT_RARITY_MULTIPLIERS: [u16; 4] = [100, 150, 250, 500]; // 100 = 1.0x
+pub const DEFAULT_INCUBATION_SLOTS: u64 = 432_000; // ~2 days
+pub const POOL_SEED: &[u8] = b"pool";
+pub const USER_STATS_SEED: &[u8] = b"user_stats";
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const STREAK_DISCOUNT_BPS: u64 = 500; // 5% per consecutive epoch
+pub const MAX_STREAK_DISCOUNT_BPS: u64 = 2_500;
+pub const DEFAULT_MIN_PARENT_HOLDING: u64 = 1;
+pub const QUEUE_SEED: &[u8] = b"genesis_queue";
+pub const DEFAULT_MAX_GENESIS_PER_SLOT: u32 = 4;
+pub const MUTATION_CATALOG_SEED: &[u8] = b"mutation_catalog";
+pub const MAX_CATALOG_MUTATIONS: usize = 16;
+pub const MAX_MUTATION_NAME_LEN: usize = 32;
+pub const LINEAGE_SEED: &[u8] = b"lineage";
+pub const ENGINE_SEED: &[u8] = b"engine";
+pub const GENESIS_GENOME_ID_OFFSET: u64 = 100_000; // genesis ids start here, after the founders
+pub const GENESIS_GENOME_SUPPLY: u64 = 1_000_000; // DNA supply of a child when no offspring rule is set
+pub const GENESIS_EVENT_SEED: &[u8] = b"genesis_event";
+pub const MAX_EVENT_MUTATIONS: usize = 8;
+pub const PRICE_ORACLE_SEED: &[u8] = b"price_oracle";
+pub const USD_DECIMALS: u32 = 6; // usd_cost is in micro-dollars
+pub const GENESIS_COMMIT_SEED: &[u8] = b"genesis_commit";
+pub const COMMIT_ESCROW_SEED: &[u8] = b"commit_escrow";
+pub const REVEAL_WINDOW_SLOTS: u64 = 512; // as far back as SlotHashes reaches
+pub const GUARDIAN_SEED: &[u8] = b"genesis_guardian";
+pub const ENGINE_SNAPSHOT_SEED: &[u8] = b"engine_snapshot";
+pub const ENGINE_SNAPSHOT_VERSION: u8 = 1;
+pub const GENE_POOL_SEED: &[u8] = b"gene_pool";
+pub const MAX_GENE_POOL_GENOMES: usize = 32;
+pub const MAX_DONOR_STREAK_DISCOUNT_BPS: u64 = 5_000;
+pub const MAX_GENESIS_PARENTS: usize = 4;
+
+// Rejected anywhere in a lowercased genome name
+pub const BLOCKED_NAME_TERMS: [&str; 8] = [
+    "fuck", "shit", "cunt", "bitch", "whore", "slut", "nazi", "rape",
+];
+
 #[program]
 pub mod genesis_engine {
     use super::*;
@@ -22,21 +75,222 @@ pub mod genesis_engine {
         engine.total_genomes_created = 0;
         engine.total_rna_burned = 0;
         engine.active = true;
+        engine.rarity_weights = DEFAULT_RARITY_WEIGHTS;
+        engine.rarity_multipliers = DEFAULT_RARITY_MULTIPLIERS;
+        engine.incubation_slots = DEFAULT_INCUBATION_SLOTS;
+        engine.acceleration_cost_per_slot = 0;
+        engine.trait_dominance = [DEFAULT_DOMINANCE; TRAIT_COUNT];
+        engine.mutation_bps = DEFAULT_MUTATION_BPS;
+        engine.min_parent_holding = DEFAULT_MIN_PARENT_HOLDING;
+        engine.queue_head = 0;
+        engine.queue_tail = 0;
+        engine.max_genesis_per_slot = DEFAULT_MAX_GENESIS_PER_SLOT;
+        engine.last_genesis_slot = 0;
+        engine.geneses_in_slot = 0;
+        engine.has_mutation_catalog = false;
+        engine.consanguinity_generations = 0;
+        engine.consanguinity_penalty = 0;
+        engine.consanguinity_cost_bps = 0;
+        engine.offspring_supply_bps = 0;
+        engine.min_offspring_supply = 0;
+        engine.max_offspring_supply = 0;
+        engine.has_price_oracle = false;
+        engine.has_guardian = false;
+        engine.rna_mint = ctx.accounts.rna_mint.key();
+        engine.bump = ctx.bumps.engine;
         
         msg!("Genesis Engine initialized, cost: {} RNA", genesis_cost);
         Ok(())
     }
 
-    // Create new genome from RNA burn
-    pub fn create_genome(
-        ctx: Context<CreateGenome>,
+    // Create new genome from RNA paid into the pool's species
+    // Each parent is passed in remaining_accounts, in parent_genome_ids
+    // order, as its dna-token genome followed by the user's token account
+    // of its mint holding at least min_parent_holding; parents outside the
+    // pool's species need a hybrid permit, which is consumed when passed
+    // The pairs are followed by the Lineage of each genesis-born parent;
+    // parents sharing an ancestor within consanguinity_generations cost
+    // more and breed a less conscious child
+    // Only allowed while the genesis queue is empty, so it cannot jump it
+    // A genesis event open at this slot may be passed to breed under its
+    // cost and with its mutations rolled after the catalog's
+    // Once the engine prices genesis in USD, its price oracle and feed must
+    // be passed too
+    // Passing the engine's gene pool donates the child to it: the pool
+    // becomes the genome's founder until it is raffled, and the genesis
+    // earns the pool's boosted streak discount
+    // Like commit_genesis, the cost is escrowed under commitment =
+    // hash(entropy_seed || salt) and reveal_genesis rolls the child, so the
+    // roll cannot be ground by simulating entropy seeds
+    pub fn create_genome<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateGenome<'info>>,
         parent_genome_ids: Vec<u64>, // DNA genomes user holds
-        entropy_seed: [u8; 32], // Randomness
+        commitment: [u8; 32],
+        name: String,
+        metadata_uri_hash: [u8; 32], // sha256 of the off-chain metadata URI
+    ) -> Result<()> {
+        let engine = &ctx.accounts.engine;
+        let pool = &mut ctx.accounts.pool;
+        require!(engine.active, ErrorCode::EngineInactive);
+        require!(engine.queue_head == engine.queue_tail, ErrorCode::QueueNotEmpty);
+        require!(pool.population < pool.population_cap, ErrorCode::PopulationCapReached);
+        validate_genome_name(&name)?;
+        
+        let parents = check_parents(
+            ctx.remaining_accounts,
+            &parent_genome_ids,
+            &ctx.accounts.user.key(),
+            pool,
+            engine.min_parent_holding,
+            ctx.accounts.hybrid_permit.as_deref(),
+        )?;
+        let inbred = engine.is_inbred(parents.common_ancestor);
+        
+        let clock = Clock::get()?;
+        let genesis_event = ctx.accounts.genesis_event.as_deref_mut();
+        if let Some(genesis_event) = &genesis_event {
+            require!(genesis_event.is_open(clock.slot), ErrorCode::GenesisEventClosed);
+            require!(genesis_event.has_room(), ErrorCode::GenesisEventFull);
+        }
+        
+        // Consecutive-epoch creators get a streak discount
+        let stats = &mut ctx.accounts.user_stats;
+        let mut discount_bps = stats.record(ctx.accounts.user.key(), clock.epoch, ctx.bumps.user_stats)?;
+        if let Some(gene_pool) = &ctx.accounts.gene_pool {
+            require!(gene_pool.genomes.len() < MAX_GENE_POOL_GENOMES, ErrorCode::GenePoolFull);
+            discount_bps = donor_discount_bps(discount_bps, gene_pool.donor_streak_boost_bps);
+        }
+        let base_cost = match &genesis_event {
+            Some(genesis_event) if genesis_event.cost > 0 => genesis_event.cost,
+            _ => pool.cost(current_genesis_cost(
+                engine,
+                ctx.accounts.price_oracle.as_deref(),
+                ctx.accounts.price_feed.as_ref(),
+                ctx.accounts.rna_mint.decimals,
+                clock.unix_timestamp,
+            )?)?,
+        };
+        let full_cost = engine.breeding_cost(base_cost, inbred)?;
+        let cost = full_cost - (full_cost as u128 * discount_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        check_guardian(
+            engine,
+            ctx.accounts.genesis_guardian.as_deref(),
+            ctx.accounts.guardian.as_ref(),
+            cost,
+        )?;
+        stats.total_rna_burned = stats.total_rna_burned
+            .checked_add(cost)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        // Escrow RNA until the reveal burns it
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_rna_account.to_account_info(),
+                    to: ctx.accounts.commit_escrow.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+        
+        pool.population = pool.population
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        let event_id = match genesis_event {
+            Some(genesis_event) => {
+                genesis_event.participants = genesis_event.participants
+                    .checked_add(1)
+                    .ok_or(ErrorCode::Overflow)?;
+                Some(genesis_event.event_id)
+            }
+            None => None,
+        };
+        
+        let commit = &mut ctx.accounts.genesis_commit;
+        commit.engine = engine.key();
+        commit.creator = ctx.accounts.user.key();
+        commit.pool = pool.key();
+        commit.species = pool.species;
+        commit.commitment = commitment;
+        commit.commit_slot = clock.slot;
+        commit.parent_genome_ids = parent_genome_ids;
+        commit.parent_traits = parents.traits;
+        commit.parent_supplies = parents.supplies;
+        commit.lineage = parents.lineage;
+        commit.name = name;
+        commit.metadata_uri_hash = metadata_uri_hash;
+        commit.rna_escrowed = cost;
+        commit.consciousness_penalty = if inbred { engine.consanguinity_penalty } else { 0 };
+        commit.bump = ctx.bumps.genesis_commit;
+        commit.genesis_event = event_id;
+        commit.donation = ctx.accounts.gene_pool.as_ref().map(|_| discount_bps as u16);
+        
+        emit!(GenesisCommittedEvent {
+            creator: commit.creator,
+            species: commit.species,
+            commitment,
+            rna_escrowed: cost,
+            slot: clock.slot,
+        });
+        
+        msg!(
+            "Genome \"{}\" committed at slot {} for {} RNA ({} epoch streak)",
+            commit.name, clock.slot, cost, ctx.accounts.user_stats.streak
+        );
+        Ok(())
+    }
+
+    // Join the genesis queue: validates parents like create_genome, burns
+    // the RNA at the current price and reserves a pool slot, so the entry
+    // only waits for its turn; returns the queue position
+    pub fn enqueue_genesis<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EnqueueGenesis<'info>>,
+        parent_genome_ids: Vec<u64>,
+        entropy_seed: [u8; 32],
+        name: String,
+        metadata_uri_hash: [u8; 32],
     ) -> Result<u64> {
         let engine = &mut ctx.accounts.engine;
+        let pool = &mut ctx.accounts.pool;
         require!(engine.active, ErrorCode::EngineInactive);
+        require!(pool.population < pool.population_cap, ErrorCode::PopulationCapReached);
+        validate_genome_name(&name)?;
+        
+        let parents = check_parents(
+            ctx.remaining_accounts,
+            &parent_genome_ids,
+            &ctx.accounts.user.key(),
+            pool,
+            engine.min_parent_holding,
+            ctx.accounts.hybrid_permit.as_deref(),
+        )?;
+        let inbred = engine.is_inbred(parents.common_ancestor);
+        
+        let clock = Clock::get()?;
+        let stats = &mut ctx.accounts.user_stats;
+        let discount_bps = stats.record(ctx.accounts.user.key(), clock.epoch, ctx.bumps.user_stats)?;
+        let genesis_cost = current_genesis_cost(
+            engine,
+            ctx.accounts.price_oracle.as_deref(),
+            ctx.accounts.price_feed.as_ref(),
+            ctx.accounts.rna_mint.decimals,
+            clock.unix_timestamp,
+        )?;
+        let full_cost = engine.breeding_cost(pool.cost(genesis_cost)?, inbred)?;
+        let cost = full_cost - (full_cost as u128 * discount_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        check_guardian(
+            engine,
+            ctx.accounts.genesis_guardian.as_deref(),
+            ctx.accounts.guardian.as_ref(),
+            cost,
+        )?;
+        stats.total_rna_burned = stats.total_rna_burned
+            .checked_add(cost)
+            .ok_or(ErrorCode::Overflow)?;
         
-        // Burn RNA
         token::burn(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
@@ -46,50 +300,489 @@ pub mod genesis_engine {
                     authority: ctx.accounts.user.to_account_info(),
                 },
             ),
-            engine.genesis_cost,
+            cost,
         )?;
         
-        // Generate new genome ID
-        let new_genome_id = engine.total_genomes_created + 100_000; // Offset from original
+        engine.total_rna_burned = engine.total_rna_burned
+            .checked_add(cost)
+            .ok_or(ErrorCode::Overflow)?;
+        pool.population = pool.population
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
         
-        // Calculate genome properties based on parents
-        let avg_consciousness = if !parent_genome_ids.is_empty() {
-            50 // Simplified - real would calculate from parents
-        } else {
-            0
-        };
+        let position = engine.queue_tail;
+        engine.queue_tail = position
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
         
-        // Store genesis record
-        let genesis = &mut ctx.accounts.genesis_record;
-        genesis.genome_id = new_genome_id;
-        genesis.creator = ctx.accounts.user.key();
-        genesis.parent_genomes = parent_genome_ids;
-        genesis.rna_burned = engine.genesis_cost;
-        genesis.consciousness = avg_consciousness;
-        genesis.created_at = Clock::get()?.unix_timestamp;
-        genesis.entropy_seed = entropy_seed;
-        
-        // Update engine stats
-        engine.total_genomes_created = engine.total_genomes_created
+        let entry = &mut ctx.accounts.queued_genesis;
+        entry.engine = engine.key();
+        entry.position = position;
+        entry.creator = ctx.accounts.user.key();
+        entry.species = pool.species;
+        entry.parent_genome_ids = parent_genome_ids;
+        entry.parent_traits = parents.traits;
+        entry.parent_supplies = parents.supplies;
+        entry.entropy_seed = entropy_seed;
+        entry.name = name;
+        entry.metadata_uri_hash = metadata_uri_hash;
+        entry.rna_locked = cost;
+        entry.enqueued_slot = clock.slot;
+        entry.seed_slot = clock.slot;
+        entry.bump = ctx.bumps.queued_genesis;
+        entry.lineage = parents.lineage;
+        entry.consciousness_penalty = if inbred { engine.consanguinity_penalty } else { 0 };
+        
+        emit!(GenesisQueuedEvent {
+            position,
+            creator: entry.creator,
+            species: entry.species,
+            rna_locked: cost,
+            slot: clock.slot,
+        });
+        
+        msg!("Genesis queued at position {} for {} RNA", position, cost);
+        Ok(position)
+    }
+
+    // Finalize the entry at the head of the queue (permissionless crank)
+    // Entries finalize strictly in queue order, at most max_genesis_per_slot
+    // per slot counting direct geneses; the cranker pays for the new genome
+    // accounts and receives the entry's rent
+    // The entry rolls from its entropy mixed with its seed slot's hash, so
+    // neither the creator nor the cranker can grind it; once that hash has
+    // left SlotHashes the entry must be reseeded first
+    pub fn process_queue(
+        ctx: Context<ProcessQueue>,
+    ) -> Result<u64> {
+        let engine = &mut ctx.accounts.engine;
+        let clock = Clock::get()?;
+        let seed_slot = ctx.accounts.queued_genesis.seed_slot;
+        require!(clock.slot > seed_slot, ErrorCode::QueueSeedPending);
+        let slot_hash = slot_hash(&ctx.accounts.slot_hashes.try_borrow_data()?, seed_slot)
+            .ok_or(ErrorCode::QueueSeedExpired)?;
+        engine.take_slot_capacity(clock.slot)?;
+        engine.queue_head = engine.queue_head
             .checked_add(1)
             .ok_or(ErrorCode::Overflow)?;
-        engine.total_rna_burned = engine.total_rna_burned
-            .checked_add(engine.genesis_cost)
+        
+        let entry = &ctx.accounts.queued_genesis;
+        let position = entry.position;
+        let name = entry.name.clone();
+        let catalog = catalog_mutations(engine, ctx.accounts.mutation_catalog.as_deref())?;
+        let child_id = engine.total_genomes_created + GENESIS_GENOME_ID_OFFSET;
+        ctx.accounts.lineage.record(child_id, entry.lineage.clone(), ctx.bumps.lineage);
+        let new_genome_id = finalize_genesis(
+            engine,
+            &mut ctx.accounts.genesis_record,
+            rsm_dna_token::cpi::accounts::RegisterGenesisGenome {
+                registry: ctx.accounts.dna_registry.to_account_info(),
+                genome: ctx.accounts.dna_genome.to_account_info(),
+                genesis_authority: ctx.accounts.genesis_authority.to_account_info(),
+                payer: ctx.accounts.cranker.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                founder: None,
+            },
+            ctx.accounts.dna_program.to_account_info(),
+            ctx.bumps.genesis_authority,
+            PendingGenesis {
+                creator: entry.creator,
+                parent_genome_ids: entry.parent_genome_ids.clone(),
+                parent_traits: entry.parent_traits.clone(),
+                parent_supplies: entry.parent_supplies.clone(),
+                // Stored as the record's entropy, so the roll still replays
+                entropy_seed: revealed_entropy(&entry.entropy_seed, &slot_hash),
+                name: entry.name.clone(),
+                metadata_uri_hash: entry.metadata_uri_hash,
+                rna_burned: entry.rna_locked,
+                species: entry.species,
+                catalog,
+                consciousness_penalty: entry.consciousness_penalty,
+                genesis_event: None,
+            },
+            &clock,
+        )?;
+        
+        msg!("Queue position {} finalized as genome {} \"{}\"", position, new_genome_id, name);
+        Ok(new_genome_id)
+    }
+
+    // Reseed the head of the queue once its seed slot's hash has left
+    // SlotHashes (permissionless crank): it then rolls from this slot's
+    // hash, which is not known yet
+    pub fn reseed_queued_genesis(
+        ctx: Context<ReseedQueuedGenesis>,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.queued_genesis;
+        let clock = Clock::get()?;
+        require!(
+            slot_hash(&ctx.accounts.slot_hashes.try_borrow_data()?, entry.seed_slot).is_none()
+                && clock.slot > entry.seed_slot,
+            ErrorCode::QueueSeedAvailable
+        );
+        entry.seed_slot = clock.slot;
+        
+        msg!("Queue position {} reseeded at slot {}", entry.position, clock.slot);
+        Ok(())
+    }
+
+    // Commit to a genesis without exposing its entropy: validates parents
+    // like enqueue_genesis, escrows the RNA cost and reserves a pool slot;
+    // commitment is hash(entropy_seed || salt)
+    // One open commit per creator; it must be revealed within
+    // REVEAL_WINDOW_SLOTS or the escrow is forfeited
+    pub fn commit_genesis<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CommitGenesis<'info>>,
+        parent_genome_ids: Vec<u64>,
+        commitment: [u8; 32],
+        name: String,
+        metadata_uri_hash: [u8; 32],
+    ) -> Result<()> {
+        let engine = &ctx.accounts.engine;
+        let pool = &mut ctx.accounts.pool;
+        require!(engine.active, ErrorCode::EngineInactive);
+        require!(pool.population < pool.population_cap, ErrorCode::PopulationCapReached);
+        validate_genome_name(&name)?;
+        
+        let parents = check_parents(
+            ctx.remaining_accounts,
+            &parent_genome_ids,
+            &ctx.accounts.user.key(),
+            pool,
+            engine.min_parent_holding,
+            ctx.accounts.hybrid_permit.as_deref(),
+        )?;
+        let inbred = engine.is_inbred(parents.common_ancestor);
+        
+        let clock = Clock::get()?;
+        let stats = &mut ctx.accounts.user_stats;
+        let discount_bps = stats.record(ctx.accounts.user.key(), clock.epoch, ctx.bumps.user_stats)?;
+        let genesis_cost = current_genesis_cost(
+            engine,
+            ctx.accounts.price_oracle.as_deref(),
+            ctx.accounts.price_feed.as_ref(),
+            ctx.accounts.rna_mint.decimals,
+            clock.unix_timestamp,
+        )?;
+        let full_cost = engine.breeding_cost(pool.cost(genesis_cost)?, inbred)?;
+        let cost = full_cost - (full_cost as u128 * discount_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        check_guardian(
+            engine,
+            ctx.accounts.genesis_guardian.as_deref(),
+            ctx.accounts.guardian.as_ref(),
+            cost,
+        )?;
+        stats.total_rna_burned = stats.total_rna_burned
+            .checked_add(cost)
             .ok_or(ErrorCode::Overflow)?;
         
-        // Emit event
-        emit!(GenesisCreatedEvent {
-            genome_id: new_genome_id,
-            creator: ctx.accounts.user.key(),
-            rna_burned: engine.genesis_cost,
-            consciousness: avg_consciousness,
-            timestamp: genesis.created_at,
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_rna_account.to_account_info(),
+                    to: ctx.accounts.commit_escrow.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+        
+        pool.population = pool.population
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        let commit = &mut ctx.accounts.genesis_commit;
+        commit.engine = engine.key();
+        commit.creator = ctx.accounts.user.key();
+        commit.pool = pool.key();
+        commit.species = pool.species;
+        commit.commitment = commitment;
+        commit.commit_slot = clock.slot;
+        commit.parent_genome_ids = parent_genome_ids;
+        commit.parent_traits = parents.traits;
+        commit.parent_supplies = parents.supplies;
+        commit.lineage = parents.lineage;
+        commit.name = name;
+        commit.metadata_uri_hash = metadata_uri_hash;
+        commit.rna_escrowed = cost;
+        commit.consciousness_penalty = if inbred { engine.consanguinity_penalty } else { 0 };
+        commit.bump = ctx.bumps.genesis_commit;
+        commit.genesis_event = None;
+        commit.donation = None;
+        
+        emit!(GenesisCommittedEvent {
+            creator: commit.creator,
+            species: commit.species,
+            commitment,
+            rna_escrowed: cost,
+            slot: clock.slot,
         });
         
-        msg!("New genome {} created from {} RNA", new_genome_id, engine.genesis_cost);
+        msg!("Genesis committed at slot {} with {} RNA escrowed", clock.slot, cost);
+        Ok(())
+    }
+
+    // Reveal a committed genesis and finalize it: from the slot after the
+    // commit until REVEAL_WINDOW_SLOTS after it, the creator reveals the
+    // entropy and salt; the genesis rolls from the entropy mixed with the
+    // commit slot's hash, which nobody knew at commit, and the escrow is
+    // burned
+    // Takes slot capacity but not the queue's turn, since the RNA was paid
+    // at commit
+    // A commit from create_genome passes the genesis event it bred under,
+    // and the engine's gene pool if it donates the child; if the pool filled
+    // up since, the creator keeps the child
+    pub fn reveal_genesis(
+        ctx: Context<RevealGenesis>,
+        entropy_seed: [u8; 32],
+        salt: [u8; 32],
+    ) -> Result<u64> {
+        let engine = &mut ctx.accounts.engine;
+        let commit = &ctx.accounts.genesis_commit;
+        let clock = Clock::get()?;
+        require!(clock.slot > commit.commit_slot, ErrorCode::RevealTooEarly);
+        require!(
+            clock.slot - commit.commit_slot <= REVEAL_WINDOW_SLOTS,
+            ErrorCode::RevealWindowClosed
+        );
+        require!(
+            hashv(&[entropy_seed.as_ref(), salt.as_ref()]).to_bytes() == commit.commitment,
+            ErrorCode::CommitmentMismatch
+        );
+        let slot_hash = slot_hash(&ctx.accounts.slot_hashes.try_borrow_data()?, commit.commit_slot)
+            .ok_or(ErrorCode::RevealWindowClosed)?;
+        engine.take_slot_capacity(clock.slot)?;
+        
+        // The escrow signs as the commit PDA
+        let engine_key = engine.key();
+        let seeds: &[&[u8]] = &[
+            GENESIS_COMMIT_SEED,
+            engine_key.as_ref(),
+            commit.creator.as_ref(),
+            &[commit.bump],
+        ];
+        release_escrow(
+            &ctx.accounts.token_program,
+            &ctx.accounts.rna_mint,
+            &ctx.accounts.commit_escrow,
+            &commit.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            seeds,
+            commit.rna_escrowed,
+        )?;
+        engine.total_rna_burned = engine.total_rna_burned
+            .checked_add(commit.rna_escrowed)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        let name = commit.name.clone();
+        let mut catalog = catalog_mutations(engine, ctx.accounts.mutation_catalog.as_deref())?;
+        if let Some(event_id) = commit.genesis_event {
+            let genesis_event = ctx.accounts.genesis_event.as_deref().ok_or(ErrorCode::GenesisEventRequired)?;
+            require!(genesis_event.event_id == event_id, ErrorCode::GenesisEventRequired);
+            catalog.extend(genesis_event.mutations.iter().map(NamedMutation::to_core));
+        }
+        let donate = match commit.donation {
+            Some(_) => {
+                let gene_pool = ctx.accounts.gene_pool.as_ref().ok_or(ErrorCode::GenePoolRequired)?;
+                gene_pool.genomes.len() < MAX_GENE_POOL_GENOMES
+            }
+            None => false,
+        };
+        let founder = ctx.accounts.gene_pool.as_ref().filter(|_| donate).map(|gene_pool| gene_pool.to_account_info());
+        let child_id = engine.total_genomes_created + GENESIS_GENOME_ID_OFFSET;
+        ctx.accounts.lineage.record(child_id, commit.lineage.clone(), ctx.bumps.lineage);
+        let new_genome_id = finalize_genesis(
+            engine,
+            &mut ctx.accounts.genesis_record,
+            rsm_dna_token::cpi::accounts::RegisterGenesisGenome {
+                registry: ctx.accounts.dna_registry.to_account_info(),
+                genome: ctx.accounts.dna_genome.to_account_info(),
+                genesis_authority: ctx.accounts.genesis_authority.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                founder,
+            },
+            ctx.accounts.dna_program.to_account_info(),
+            ctx.bumps.genesis_authority,
+            PendingGenesis {
+                creator: commit.creator,
+                parent_genome_ids: commit.parent_genome_ids.clone(),
+                parent_traits: commit.parent_traits.clone(),
+                parent_supplies: commit.parent_supplies.clone(),
+                // Stored as the record's entropy, so the roll still replays
+                entropy_seed: revealed_entropy(&entropy_seed, &slot_hash),
+                name: commit.name.clone(),
+                metadata_uri_hash: commit.metadata_uri_hash,
+                rna_burned: commit.rna_escrowed,
+                species: commit.species,
+                catalog,
+                consciousness_penalty: commit.consciousness_penalty,
+                genesis_event: commit.genesis_event,
+            },
+            &clock,
+        )?;
+        
+        if let (true, Some(gene_pool)) = (donate, ctx.accounts.gene_pool.as_deref_mut()) {
+            gene_pool.genomes.push(PooledGenome {
+                genome_id: new_genome_id,
+                genome: ctx.accounts.dna_genome.key(),
+                donor: commit.creator,
+            });
+            gene_pool.donations = gene_pool.donations
+                .checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+            emit!(GenomeDonatedEvent {
+                genome_id: new_genome_id,
+                donor: commit.creator,
+                discount_bps: commit.donation.unwrap_or_default(),
+                pool_size: gene_pool.genomes.len() as u16,
+            });
+        }
+        
+        msg!("Commit from slot {} revealed as genome {} \"{}\"", commit.commit_slot, new_genome_id, name);
         Ok(new_genome_id)
     }
 
+    // Forfeit a commit left unrevealed past REVEAL_WINDOW_SLOTS
+    // (permissionless crank): its escrow is burned, its pool slot freed and
+    // the cranker receives the accounts' rent
+    pub fn forfeit_genesis_commit(
+        ctx: Context<ForfeitGenesisCommit>,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        let commit = &ctx.accounts.genesis_commit;
+        let clock = Clock::get()?;
+        require!(
+            clock.slot.saturating_sub(commit.commit_slot) > REVEAL_WINDOW_SLOTS,
+            ErrorCode::RevealWindowOpen
+        );
+        
+        let engine_key = engine.key();
+        let seeds: &[&[u8]] = &[
+            GENESIS_COMMIT_SEED,
+            engine_key.as_ref(),
+            commit.creator.as_ref(),
+            &[commit.bump],
+        ];
+        release_escrow(
+            &ctx.accounts.token_program,
+            &ctx.accounts.rna_mint,
+            &ctx.accounts.commit_escrow,
+            &commit.to_account_info(),
+            &ctx.accounts.cranker.to_account_info(),
+            seeds,
+            commit.rna_escrowed,
+        )?;
+        engine.total_rna_burned = engine.total_rna_burned
+            .checked_add(commit.rna_escrowed)
+            .ok_or(ErrorCode::Overflow)?;
+        let pool = &mut ctx.accounts.pool;
+        pool.population = pool.population.saturating_sub(1);
+        
+        emit!(GenesisCommitForfeitedEvent {
+            creator: commit.creator,
+            species: commit.species,
+            rna_forfeited: commit.rna_escrowed,
+            commit_slot: commit.commit_slot,
+            slot: clock.slot,
+        });
+        
+        msg!("Commit from slot {} forfeited {} RNA", commit.commit_slot, commit.rna_escrowed);
+        Ok(())
+    }
+
+    // Open the engine's mutation catalog (governance); from then on every
+    // genesis rolls against it
+    pub fn create_mutation_catalog(
+        ctx: Context<CreateMutationCatalog>,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let catalog = &mut ctx.accounts.mutation_catalog;
+        catalog.engine = engine.key();
+        catalog.mutations = Vec::new();
+        catalog.bump = ctx.bumps.mutation_catalog;
+        engine.has_mutation_catalog = true;
+        
+        msg!("Mutation catalog opened");
+        Ok(())
+    }
+
+    // Add or replace the catalog mutation with this id (governance)
+    pub fn set_mutation(
+        ctx: Context<EditMutationCatalog>,
+        id: u16,
+        name: String,
+        trait_deltas: [i8; TRAIT_COUNT],
+        weight_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.engine.authority,
+            ErrorCode::Unauthorized
+        );
+        let mutation = NamedMutation { id, name: name.clone(), trait_deltas, weight_bps };
+        require!(mutation.is_valid(), ErrorCode::InvalidMutation);
+        
+        let catalog = &mut ctx.accounts.mutation_catalog;
+        match catalog.mutations.iter_mut().find(|existing| existing.id == id) {
+            Some(existing) => *existing = mutation,
+            None => {
+                require!(catalog.mutations.len() < MAX_CATALOG_MUTATIONS, ErrorCode::MutationCatalogFull);
+                catalog.mutations.push(mutation);
+            }
+        }
+        
+        msg!("Mutation {} \"{}\": {:?} at {} bps", id, name, trait_deltas, weight_bps);
+        Ok(())
+    }
+
+    // Drop a mutation from the catalog (governance); children that
+    // received it keep it in their genesis record
+    pub fn remove_mutation(
+        ctx: Context<EditMutationCatalog>,
+        id: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.engine.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let catalog = &mut ctx.accounts.mutation_catalog;
+        let index = catalog.mutations
+            .iter()
+            .position(|mutation| mutation.id == id)
+            .ok_or(ErrorCode::UnknownMutation)?;
+        catalog.mutations.remove(index);
+        
+        msg!("Mutation {} removed", id);
+        Ok(())
+    }
+
+    // Cap how many geneses finalize per slot (governance)
+    pub fn update_queue_cap(
+        ctx: Context<UpdateQueueCap>,
+        max_genesis_per_slot: u32,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(max_genesis_per_slot > 0, ErrorCode::InvalidQueueCap);
+        
+        engine.max_genesis_per_slot = max_genesis_per_slot;
+        
+        msg!("Genesis throughput: {} per slot", max_genesis_per_slot);
+        Ok(())
+    }
+
     // Update genesis cost (AGI control)
     pub fn update_cost(
         ctx: Context<UpdateCost>,
@@ -109,23 +802,36 @@ pub mod genesis_engine {
         Ok(())
     }
 
-    // Get engine stats
-    pub fn get_stats(
-        ctx: Context<GetStats>,
-    ) -> Result<EngineStats> {
-        let engine = &ctx.accounts.engine;
+    // Update rarity probabilities and multipliers (governance)
+    pub fn update_rarity_weights(
+        ctx: Context<UpdateRarity>,
+        weights: [u16; 4],
+        multipliers: [u16; 4],
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
         
-        Ok(EngineStats {
-            total_genomes_created: engine.total_genomes_created,
-            total_rna_burned: engine.total_rna_burned,
-            genesis_cost: engine.genesis_cost,
-            active: engine.active,
-        })
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            weights.iter().map(|w| *w as u32).sum::<u32>() > 0,
+            ErrorCode::InvalidRarityWeights
+        );
+        require!(multipliers.iter().all(|m| *m > 0), ErrorCode::InvalidRarityWeights);
+        
+        engine.rarity_weights = weights;
+        engine.rarity_multipliers = multipliers;
+        
+        msg!("Rarity weights updated: {:?}", weights);
+        Ok(())
     }
 
-    // Pause/unpause engine
-    pub fn toggle_active(
-        ctx: Context<ToggleActive>,
+    // Set per-trait dominance and the mutation rate (governance)
+    pub fn update_dominance(
+        ctx: Context<UpdateDominance>,
+        trait_dominance: [u8; TRAIT_COUNT],
+        mutation_bps: u16,
     ) -> Result<()> {
         let engine = &mut ctx.accounts.engine;
         
@@ -133,51 +839,2099 @@ pub mod genesis_engine {
             ctx.accounts.authority.key() == engine.authority,
             ErrorCode::Unauthorized
         );
+        require!(
+            DominanceModel { dominance: trait_dominance, mutation_bps }.is_valid(),
+            ErrorCode::InvalidDominance
+        );
         
-        engine.active = !engine.active;
-        msg!("Engine active: {}", engine.active);
+        engine.trait_dominance = trait_dominance;
+        engine.mutation_bps = mutation_bps;
+        
+        msg!("Trait dominance updated: {:?}, mutation {} bps", trait_dominance, mutation_bps);
         Ok(())
     }
-}
 
-// Account structures
-#[account]
-pub struct GenesisEngine {
-    pub authority: Pubkey,
-    pub genesis_cost: u64,
-    pub total_genomes_created: u64,
-    pub total_rna_burned: u64,
-    pub active: bool,
-}
+    // Set the consanguinity penalty (governance): parents sharing an
+    // ancestor within `generations` (0 = off) cost `cost_bps` more RNA and
+    // breed a child `consciousness_penalty` points lower
+    pub fn update_consanguinity(
+        ctx: Context<UpdateConsanguinity>,
+        generations: u8,
+        consciousness_penalty: u8,
+        cost_bps: u16,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(generations <= MAX_LINEAGE_GENERATIONS, ErrorCode::InvalidConsanguinity);
+        
+        engine.consanguinity_generations = generations;
+        engine.consanguinity_penalty = consciousness_penalty;
+        engine.consanguinity_cost_bps = cost_bps;
+        
+        msg!(
+            "Consanguinity within {} generations: -{} consciousness, +{} bps cost",
+            generations, consciousness_penalty, cost_bps
+        );
+        Ok(())
+    }
 
-#[account]
-pub struct GenesisRecord {
-    pub genome_id: u64,
-    pub creator: Pubkey,
-    pub parent_genomes: Vec<u64>,
-    pub rna_burned: u64,
-    pub consciousness: u8,
-    pub created_at: i64,
-    pub entropy_seed: [u8; 32],
-}
+    // Set the offspring supply rule (governance): a child's DNA supply is
+    // the geometric mean of its parents' supplies times `scale_bps` and its
+    // rarity multiplier, clamped to [min_supply, max_supply]. A scale of 0
+    // turns the rule off and children get GENESIS_GENOME_SUPPLY
+    pub fn update_offspring_supply(
+        ctx: Context<UpdateOffspringSupply>,
+        scale_bps: u16,
+        min_supply: u64,
+        max_supply: u64,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        let rule = SupplyRule { scale_bps, min_supply, max_supply };
+        require!(scale_bps == 0 || rule.is_valid(), ErrorCode::InvalidOffspringSupply);
+        
+        engine.offspring_supply_bps = scale_bps;
+        engine.min_offspring_supply = min_supply;
+        engine.max_offspring_supply = max_supply;
+        
+        msg!("Offspring supply: {} bps of the parents' mean, {}-{}", scale_bps, min_supply, max_supply);
+        Ok(())
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct EngineStats {
-    pub total_genomes_created: u64,
-    pub total_rna_burned: u64,
-    pub genesis_cost: u64,
-    pub active: bool,
-}
+    // Record the lineage of a genome the engine bred before lineages were
+    // kept (engine authority, which vouches for the genesis record); the
+    // Lineage of each genesis-born parent is passed in remaining_accounts,
+    // in parent order
+    pub fn record_lineage<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordLineage<'info>>,
+    ) -> Result<()> {
+        let genesis = &ctx.accounts.genesis_record;
+        let lineages = parent_lineages(ctx.remaining_accounts, &ctx.accounts.engine.key(), &genesis.parent_genomes)?;
+        let (ancestors, _) = breed_lineage(&genesis.parent_genomes, &lineages);
+        let ancestor_count = ancestors.len();
+        ctx.accounts.lineage.record(genesis.genome_id, ancestors, ctx.bumps.lineage);
+        
+        msg!("Lineage of genome {} recorded: {} ancestors", genesis.genome_id, ancestor_count);
+        Ok(())
+    }
 
-// Context structures
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
+    // Burn extra RNA to shorten a child genome's incubation (creator only)
+    pub fn accelerate_incubation(
+        ctx: Context<AccelerateIncubation>,
+        slots: u64,
+    ) -> Result<()> {
+        let engine = &ctx.accounts.engine;
+        let genesis = &ctx.accounts.genesis_record;
+        require!(engine.acceleration_cost_per_slot > 0, ErrorCode::AccelerationDisabled);
+        require!(
+            ctx.accounts.user.key() == genesis.creator,
+            ErrorCode::Unauthorized
+        );
+        
+        let slot = Clock::get()?.slot;
+        let remaining = genesis.matures_at_slot.saturating_sub(slot);
+        let slots = slots.min(remaining);
+        require!(slots > 0, ErrorCode::AlreadyMature);
+        
+        let cost = slots
+            .checked_mul(engine.acceleration_cost_per_slot)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.rna_mint.to_account_info(),
+                    from: ctx.accounts.user_rna_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+        
+        let matures_at_slot = genesis.matures_at_slot - slots;
+        let engine_key = ctx.accounts.engine.key();
+        let bump = [ctx.bumps.genesis_authority];
+        let seeds: &[&[u8]] = &[GENESIS_AUTHORITY_SEED, engine_key.as_ref(), &bump];
+        rsm_dna_token::cpi::set_genome_maturity(
+            CpiContext::new_with_signer(
+                ctx.accounts.dna_program.to_account_info(),
+                rsm_dna_token::cpi::accounts::SetGenomeMaturity {
+                    registry: ctx.accounts.dna_registry.to_account_info(),
+                    genome: ctx.accounts.dna_genome.to_account_info(),
+                    genesis_authority: ctx.accounts.genesis_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            matures_at_slot,
+        )?;
+        
+        let engine = &mut ctx.accounts.engine;
+        engine.total_rna_burned = engine.total_rna_burned
+            .checked_add(cost)
+            .ok_or(ErrorCode::Overflow)?;
+        let genesis = &mut ctx.accounts.genesis_record;
+        genesis.matures_at_slot = matures_at_slot;
+        
+        emit!(IncubationAcceleratedEvent {
+            genome_id: genesis.genome_id,
+            creator: genesis.creator,
+            rna_burned: cost,
+            matures_at_slot,
+            slot,
+        });
+        
+        msg!("Genome {} incubation shortened by {} slots for {} RNA", genesis.genome_id, slots, cost);
+        Ok(())
+    }
+
+    // Set how much of each parent's DNA a creator must hold (governance)
+    pub fn update_min_parent_holding(
+        ctx: Context<UpdateMinParentHolding>,
+        min_parent_holding: u64,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(min_parent_holding > 0, ErrorCode::InvalidMinParentHolding);
+        
+        engine.min_parent_holding = min_parent_holding;
+        
+        msg!("Minimum parent holding: {} DNA", min_parent_holding);
+        Ok(())
+    }
+
+    // Update incubation length and acceleration price (governance)
+    pub fn update_incubation(
+        ctx: Context<UpdateIncubation>,
+        incubation_slots: u64,
+        acceleration_cost_per_slot: u64,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        engine.incubation_slots = incubation_slots;
+        engine.acceleration_cost_per_slot = acceleration_cost_per_slot;
+        
+        msg!("Incubation: {} slots, {} RNA per slot to accelerate", incubation_slots, acceleration_cost_per_slot);
+        Ok(())
+    }
+
+    // Open a breeding pool for one species (governance)
+    // Cost is genesis_cost * (cost_base_bps + cost_step_bps * population) / 10000
+    pub fn create_pool(
+        ctx: Context<CreatePool>,
+        species: u16,
+        cost_base_bps: u16,
+        cost_step_bps: u16,
+        population_cap: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(cost_base_bps > 0, ErrorCode::InvalidPoolCurve);
+        
+        let pool = &mut ctx.accounts.pool;
+        pool.engine = ctx.accounts.engine.key();
+        pool.species = species;
+        pool.cost_base_bps = cost_base_bps;
+        pool.cost_step_bps = cost_step_bps;
+        pool.population = 0;
+        pool.population_cap = population_cap;
+        pool.bump = ctx.bumps.pool;
+        
+        msg!("Species {} pool opened, cap {}", species, population_cap);
+        Ok(())
+    }
+
+    // Retune a pool's cost curve and population cap (governance)
+    pub fn update_pool(
+        ctx: Context<UpdatePool>,
+        cost_base_bps: u16,
+        cost_step_bps: u16,
+        population_cap: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(cost_base_bps > 0, ErrorCode::InvalidPoolCurve);
+        
+        let pool = &mut ctx.accounts.pool;
+        pool.cost_base_bps = cost_base_bps;
+        pool.cost_step_bps = cost_step_bps;
+        pool.population_cap = population_cap;
+        
+        msg!("Species {} pool updated, cap {}", pool.species, population_cap);
+        Ok(())
+    }
+
+    // Let `holder` breed parents of other species into `species` once
+    pub fn issue_hybrid_permit(
+        ctx: Context<IssueHybridPermit>,
+        holder: Pubkey,
+        species: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.engine.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let permit = &mut ctx.accounts.permit;
+        permit.engine = ctx.accounts.engine.key();
+        permit.holder = holder;
+        permit.species = species;
+        
+        msg!("Hybrid permit for species {} issued to {}", species, holder);
+        Ok(())
+    }
+
+    // Schedule a genesis event (governance): from start_slot until end_slot
+    // create_genome may breed under it at `cost` RNA (0 = pool cost) with
+    // `mutations` rolled after the catalog's, for at most max_participants
+    // geneses (0 = unlimited)
+    pub fn create_genesis_event(
+        ctx: Context<CreateGenesisEvent>,
+        event_id: u64,
+        start_slot: u64,
+        end_slot: u64,
+        cost: u64,
+        mutations: Vec<NamedMutation>,
+        max_participants: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            start_slot < end_slot
+                && mutations.len() <= MAX_EVENT_MUTATIONS
+                && mutations.iter().all(NamedMutation::is_valid),
+            ErrorCode::InvalidGenesisEvent
+        );
+        
+        let genesis_event = &mut ctx.accounts.genesis_event;
+        genesis_event.engine = ctx.accounts.engine.key();
+        genesis_event.event_id = event_id;
+        genesis_event.start_slot = start_slot;
+        genesis_event.end_slot = end_slot;
+        genesis_event.cost = cost;
+        genesis_event.mutations = mutations;
+        genesis_event.max_participants = max_participants;
+        genesis_event.participants = 0;
+        genesis_event.bump = ctx.bumps.genesis_event;
+        
+        emit!(GenesisEventCreatedEvent {
+            event_id,
+            start_slot,
+            end_slot,
+            cost,
+            max_participants,
+            mutations: genesis_event.mutations.iter().map(|mutation| mutation.id).collect(),
+        });
+        msg!("Genesis event {} scheduled for slots {}-{}", event_id, start_slot, end_slot);
+        Ok(())
+    }
+
+    // Denominate genesis cost in USD (governance): usd_cost micro-dollars
+    // are converted to RNA at the Pyth price in price_feed at each genesis,
+    // which must be at most max_staleness_secs old with a confidence
+    // interval within max_confidence_bps of the price; usd_cost 0 goes back
+    // to the RNA genesis_cost
+    pub fn set_price_oracle(
+        ctx: Context<SetPriceOracle>,
+        price_feed: Pubkey,
+        feed_id: [u8; 32],
+        usd_cost: u64,
+        max_staleness_secs: u32,
+        max_confidence_bps: u16,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            usd_cost == 0 || (max_staleness_secs > 0 && max_confidence_bps as u64 <= BPS_DENOMINATOR),
+            ErrorCode::InvalidPriceOracle
+        );
+        
+        let oracle = &mut ctx.accounts.price_oracle;
+        oracle.engine = engine.key();
+        oracle.price_feed = price_feed;
+        oracle.feed_id = feed_id;
+        oracle.usd_cost = usd_cost;
+        oracle.max_staleness_secs = max_staleness_secs;
+        oracle.max_confidence_bps = max_confidence_bps;
+        oracle.bump = ctx.bumps.price_oracle;
+        engine.has_price_oracle = usd_cost > 0;
+        
+        if usd_cost > 0 {
+            msg!("Genesis cost set to {} micro-USD via feed {}", usd_cost, price_feed);
+        } else {
+            msg!("Genesis cost back to {} RNA", engine.genesis_cost);
+        }
+        Ok(())
+    }
+
+    // Require a guardian's co-signature on any genesis costing more than
+    // threshold RNA, after discounts (governance); the guardian is passed
+    // with every genesis from then on. Pubkey::default() removes it
+    pub fn set_guardian(
+        ctx: Context<SetGuardian>,
+        guardian: Pubkey,
+        threshold: u64,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let genesis_guardian = &mut ctx.accounts.genesis_guardian;
+        genesis_guardian.engine = engine.key();
+        genesis_guardian.guardian = guardian;
+        genesis_guardian.threshold = threshold;
+        genesis_guardian.bump = ctx.bumps.genesis_guardian;
+        engine.has_guardian = guardian != Pubkey::default();
+        
+        if engine.has_guardian {
+            msg!("Geneses above {} RNA need {} to co-sign", threshold, guardian);
+        } else {
+            msg!("Genesis guardian removed");
+        }
+        Ok(())
+    }
+
+    // Snapshot the engine's counters for a redeployed engine to import
+    // The engine is deactivated and its queue must be drained first, so the
+    // genome ids it hands out cannot overlap the new engine's. Exporting
+    // again before the import refreshes the snapshot
+    pub fn export_state(
+        ctx: Context<ExportState>,
+    ) -> Result<EngineSnapshot> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(engine.queue_head == engine.queue_tail, ErrorCode::QueuePending);
+        
+        let snapshot = &mut ctx.accounts.snapshot;
+        require!(
+            snapshot.imported_into == Pubkey::default(),
+            ErrorCode::SnapshotAlreadyImported
+        );
+        
+        engine.active = false;
+        snapshot.version = ENGINE_SNAPSHOT_VERSION;
+        snapshot.engine = engine.key();
+        snapshot.authority = engine.authority;
+        snapshot.total_genomes_created = engine.total_genomes_created;
+        snapshot.total_rna_burned = engine.total_rna_burned;
+        snapshot.slot = Clock::get()?.slot;
+        snapshot.digest = snapshot_digest(snapshot);
+        snapshot.imported_into = Pubkey::default();
+        snapshot.bump = ctx.bumps.snapshot;
+        
+        emit!(EngineStateExportedEvent {
+            engine: snapshot.engine,
+            authority: snapshot.authority,
+            version: snapshot.version,
+            total_genomes_created: snapshot.total_genomes_created,
+            total_rna_burned: snapshot.total_rna_burned,
+            digest: snapshot.digest,
+            slot: snapshot.slot,
+        });
+        
+        msg!("Engine state exported: {} genomes, {} RNA burned", snapshot.total_genomes_created, snapshot.total_rna_burned);
+        Ok((**snapshot).clone())
+    }
+
+    // Carry an exported snapshot's counters into a fresh engine of the
+    // same authority; each snapshot can be imported once
+    pub fn import_state(
+        ctx: Context<ImportState>,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        let snapshot = &mut ctx.accounts.snapshot;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority
+                && snapshot.authority == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            snapshot.version == ENGINE_SNAPSHOT_VERSION
+                && snapshot.digest == snapshot_digest(snapshot),
+            ErrorCode::InvalidSnapshot
+        );
+        require!(
+            snapshot.imported_into == Pubkey::default(),
+            ErrorCode::SnapshotAlreadyImported
+        );
+        require!(
+            snapshot.engine != engine.key()
+                && engine.total_genomes_created == 0
+                && engine.total_rna_burned == 0
+                && engine.queue_tail == 0,
+            ErrorCode::EngineNotFresh
+        );
+        
+        engine.total_genomes_created = snapshot.total_genomes_created;
+        engine.total_rna_burned = snapshot.total_rna_burned;
+        snapshot.imported_into = engine.key();
+        
+        emit!(EngineStateImportedEvent {
+            engine: engine.key(),
+            source_engine: snapshot.engine,
+            total_genomes_created: engine.total_genomes_created,
+            total_rna_burned: engine.total_rna_burned,
+            slot: Clock::get()?.slot,
+        });
+        
+        msg!("Engine state imported from {}", snapshot.engine);
+        Ok(())
+    }
+
+    // Open the engine's gene pool or change its donor boost (governance);
+    // donating geneses get their streak discount raised by
+    // donor_streak_boost_bps, up to 50%
+    pub fn set_gene_pool(
+        ctx: Context<SetGenePool>,
+        donor_streak_boost_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.engine.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(donor_streak_boost_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidGenePool);
+        
+        let gene_pool = &mut ctx.accounts.gene_pool;
+        gene_pool.engine = ctx.accounts.engine.key();
+        gene_pool.donor_streak_boost_bps = donor_streak_boost_bps;
+        gene_pool.bump = ctx.bumps.gene_pool;
+        
+        msg!("Gene pool donor streak boost set to {} bps", donor_streak_boost_bps);
+        Ok(())
+    }
+
+    // Raffle the oldest pooled genome to an active DNA staker (governance)
+    // Remaining accounts are dna-token GenomeStake accounts in increasing
+    // address order; those still locked with DNA in them are drawn from,
+    // weighted by amount, with the newest slot hash. The winner becomes the
+    // genome's founder
+    pub fn distribute_gene_pool<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeGenePool<'info>>,
+    ) -> Result<Pubkey> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.engine.authority,
+            ErrorCode::Unauthorized
+        );
+        let gene_pool = &ctx.accounts.gene_pool;
+        let pooled = *gene_pool.genomes.first().ok_or(ErrorCode::GenePoolEmpty)?;
+        require_keys_eq!(ctx.accounts.dna_genome.key(), pooled.genome, ErrorCode::GenomeMismatch);
+        
+        let clock = Clock::get()?;
+        let mut stakes = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut previous: Option<Pubkey> = None;
+        for info in ctx.remaining_accounts {
+            if let Some(key) = previous {
+                require!(key < info.key(), ErrorCode::InvalidStakeAccounts);
+            }
+            previous = Some(info.key());
+            let stake = Account::<rsm_dna_token::GenomeStake>::try_from(info)
+                .map_err(|_| error!(ErrorCode::InvalidStakeAccounts))?;
+            if stake.amount > 0 && stake.unlock_epoch > clock.epoch {
+                stakes.push((info.key(), stake.staker, stake.amount));
+            }
+        }
+        let total: u128 = stakes.iter().map(|(_, _, amount)| *amount as u128).sum();
+        require!(total > 0, ErrorCode::NoActiveStakers);
+        
+        let slot_hash = newest_slot_hash(&ctx.accounts.slot_hashes.try_borrow_data()?)
+            .ok_or(ProgramError::UnsupportedSysvar)?;
+        let seed = hashv(&[gene_pool.key().as_ref(), &pooled.genome_id.to_le_bytes(), &slot_hash]).to_bytes();
+        let mut roll = u128::from_le_bytes(seed[..16].try_into().unwrap()) % total;
+        let (stake, winner, _) = *stakes
+            .iter()
+            .find(|(_, _, amount)| {
+                let hit = roll < *amount as u128;
+                roll = roll.saturating_sub(*amount as u128);
+                hit
+            })
+            .unwrap();
+        
+        let engine_key = ctx.accounts.engine.key();
+        let bump = [gene_pool.bump];
+        let seeds: &[&[u8]] = &[GENE_POOL_SEED, engine_key.as_ref(), &bump];
+        rsm_dna_token::cpi::transfer_founder(
+            CpiContext::new_with_signer(
+                ctx.accounts.dna_program.to_account_info(),
+                rsm_dna_token::cpi::accounts::TransferFounder {
+                    genome: ctx.accounts.dna_genome.to_account_info(),
+                    founder: ctx.accounts.gene_pool.to_account_info(),
+                },
+                &[seeds],
+            ),
+            winner,
+        )?;
+        
+        let gene_pool = &mut ctx.accounts.gene_pool;
+        gene_pool.genomes.remove(0);
+        gene_pool.distributed = gene_pool.distributed
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        
+        emit!(GenePoolDistributedEvent {
+            genome_id: pooled.genome_id,
+            donor: pooled.donor,
+            winner,
+            stake,
+            candidates: stakes.len() as u32,
+            slot: clock.slot,
+        });
+        msg!("Pooled genome {} raffled to {} among {} stakers", pooled.genome_id, winner, stakes.len());
+        Ok(winner)
+    }
+
+    // Get engine stats
+    pub fn get_stats(
+        ctx: Context<GetStats>,
+    ) -> Result<EngineStats> {
+        let engine = &ctx.accounts.engine;
+        
+        Ok(EngineStats {
+            total_genomes_created: engine.total_genomes_created,
+            total_rna_burned: engine.total_rna_burned,
+            genesis_cost: engine.genesis_cost,
+            active: engine.active,
+        })
+    }
+
+    // Pause/unpause engine
+    pub fn toggle_active(
+        ctx: Context<ToggleActive>,
+    ) -> Result<()> {
+        let engine = &mut ctx.accounts.engine;
+        
+        require!(
+            ctx.accounts.authority.key() == engine.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        engine.active = !engine.active;
+        msg!("Engine active: {}", engine.active);
+        Ok(())
+    }
+}
+
+// A genesis whose cost is paid, waiting to be rolled and registered
+pub struct PendingGenesis {
+    pub creator: Pubkey,
+    pub parent_genome_ids: Vec<u64>,
+    pub parent_traits: Vec<TraitVector>,
+    pub parent_supplies: Vec<u64>,
+    pub entropy_seed: [u8; 32],
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub rna_burned: u64,
+    pub species: u16,
+    pub catalog: Vec<CatalogMutation>, // empty without a mutation catalog
+    pub consciousness_penalty: u8, // consanguinity penalty, 0 unless inbred
+    pub genesis_event: Option<u64>, // id of the event bred under, if any
+}
+
+// What check_parents reads from a genesis's parents
+pub struct Parents {
+    pub traits: Vec<TraitVector>,
+    pub supplies: Vec<u64>, // DNA total_supply of each
+    pub lineage: Vec<LineageEntry>, // the child's
+    pub common_ancestor: Option<u8>, // generation of the nearest shared ancestor
+}
+
+// The RNA genesis cost before pool pricing: genesis_cost, or the price
+// oracle's USD cost at the feed's current price once the engine has one
+fn current_genesis_cost(
+    engine: &GenesisEngine,
+    oracle: Option<&PriceOracle>,
+    price_feed: Option<&UncheckedAccount>,
+    rna_decimals: u8,
+    now: i64,
+) -> Result<u64> {
+    if !engine.has_price_oracle {
+        return Ok(engine.genesis_cost);
+    }
+    let oracle = oracle.ok_or(ErrorCode::PriceOracleRequired)?;
+    let price_feed = price_feed.ok_or(ErrorCode::PriceOracleRequired)?;
+    require!(
+        price_feed.key() == oracle.price_feed && *price_feed.owner == pyth_receiver::ID,
+        ErrorCode::InvalidPriceFeed
+    );
+    let price = PythPrice::parse(&price_feed.try_borrow_data()?)?;
+    oracle.rna_cost(&price, now, rna_decimals)
+}
+
+// A genesis costing more than the guardian's threshold needs the guardian
+// to co-sign; the guardian account is required once the engine has one
+fn check_guardian(
+    engine: &GenesisEngine,
+    genesis_guardian: Option<&GenesisGuardian>,
+    guardian: Option<&Signer>,
+    cost: u64,
+) -> Result<()> {
+    if !engine.has_guardian {
+        return Ok(());
+    }
+    let genesis_guardian = genesis_guardian.ok_or(ErrorCode::GuardianRequired)?;
+    if cost > genesis_guardian.threshold {
+        require!(
+            guardian.is_some_and(|guardian| guardian.key() == genesis_guardian.guardian),
+            ErrorCode::GuardianSignatureRequired
+        );
+    }
+    Ok(())
+}
+
+// The engine's catalog as rsm-core mutations; required once it exists
+fn catalog_mutations(engine: &GenesisEngine, catalog: Option<&MutationCatalog>) -> Result<Vec<CatalogMutation>> {
+    if !engine.has_mutation_catalog {
+        return Ok(Vec::new());
+    }
+    let catalog = catalog.ok_or(ErrorCode::MutationCatalogRequired)?;
+    Ok(catalog.mutations.iter().map(NamedMutation::to_core).collect())
+}
+
+// Check each (genome, token account) pair in `accounts` against
+// parent_genome_ids and the holding rule, and that off-species parents are
+// covered by the permit; the Lineage accounts after the pairs give the
+// child's lineage and how closely the parents are related
+fn check_parents<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    parent_genome_ids: &[u64],
+    user: &Pubkey,
+    pool: &GenesisPool,
+    min_parent_holding: u64,
+    hybrid_permit: Option<&HybridPermit>,
+) -> Result<Parents> {
+    require!(parent_genome_ids.len() <= MAX_GENESIS_PARENTS, ErrorCode::TooManyParents);
+    require!(
+        accounts.len() >= parent_genome_ids.len() * 2,
+        ErrorCode::ParentMismatch
+    );
+    let (pairs, lineages) = accounts.split_at(parent_genome_ids.len() * 2);
+    let mut purebred = true;
+    let mut parent_traits = Vec::with_capacity(parent_genome_ids.len());
+    let mut supplies = Vec::with_capacity(parent_genome_ids.len());
+    for (accounts, parent_id) in pairs.chunks_exact(2).zip(parent_genome_ids.iter()) {
+        let parent_loader = AccountLoader::<rsm_dna_token::GenomeV2>::try_from(&accounts[0])?;
+        let parent = parent_loader.load()?;
+        let holding: Account<TokenAccount> = Account::try_from(&accounts[1])?;
+        require!(parent.genome_id == *parent_id, ErrorCode::ParentMismatch);
+        require!(
+            parent.is_minted()
+                && holding.mint == parent.mint
+                && holding.owner == *user
+                && holding.amount >= min_parent_holding,
+            ErrorCode::ParentNotHeld
+        );
+        purebred &= parent.species == pool.species;
+        parent_traits.push(parent.traits);
+        supplies.push(parent.total_supply);
+    }
+    if !purebred {
+        let permit = hybrid_permit.ok_or(ErrorCode::SpeciesMismatch)?;
+        require!(
+            permit.holder == *user && permit.species == pool.species,
+            ErrorCode::InvalidHybridPermit
+        );
+    }
+    let lineages = parent_lineages(lineages, &pool.engine, parent_genome_ids)?;
+    let (lineage, common_ancestor) = breed_lineage(parent_genome_ids, &lineages);
+    Ok(Parents { traits: parent_traits, supplies, lineage, common_ancestor })
+}
+
+// Each parent's ancestors, from the engine's Lineage accounts of the
+// genesis-born parents in parent order; founders have none
+fn parent_lineages<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    engine: &Pubkey,
+    parent_genome_ids: &[u64],
+) -> Result<Vec<Vec<Ancestor>>> {
+    let mut accounts = accounts.iter();
+    let mut lineages = Vec::with_capacity(parent_genome_ids.len());
+    for parent_id in parent_genome_ids {
+        if *parent_id < GENESIS_GENOME_ID_OFFSET {
+            lineages.push(Vec::new());
+            continue;
+        }
+        let lineage: Account<Lineage> = Account::try_from(accounts.next().ok_or(ErrorCode::LineageMismatch)?)?;
+        require!(
+            lineage.genome_id == *parent_id && lineage.is_of(&lineage.key(), engine),
+            ErrorCode::LineageMismatch
+        );
+        lineages.push(lineage.ancestors.iter().map(LineageEntry::to_core).collect());
+    }
+    require!(accounts.next().is_none(), ErrorCode::LineageMismatch);
+    Ok(lineages)
+}
+
+// The child's lineage and the generation of its parents' nearest shared
+// ancestor, if any
+fn breed_lineage(parent_genome_ids: &[u64], lineages: &[Vec<Ancestor>]) -> (Vec<LineageEntry>, Option<u8>) {
+    let parents: Vec<(u64, &[Ancestor])> = parent_genome_ids
+        .iter()
+        .copied()
+        .zip(lineages.iter().map(Vec::as_slice))
+        .collect();
+    let lineage = rsm_core::child_lineage(&parents).into_iter().map(LineageEntry::from).collect();
+    (lineage, rsm_core::common_ancestor_generation(&parents))
+}
+
+// Roll rarity and traits for a paid genesis, store its record, register the
+// child with dna-token and emit GenesisCreatedEvent; returns the genome id
+fn finalize_genesis<'info>(
+    engine: &mut Account<'info, GenesisEngine>,
+    genesis: &mut GenesisRecord,
+    register: rsm_dna_token::cpi::accounts::RegisterGenesisGenome<'info>,
+    dna_program: AccountInfo<'info>,
+    genesis_authority_bump: u8,
+    pending: PendingGenesis,
+    clock: &Clock,
+) -> Result<u64> {
+    // Generate new genome ID
+    let new_genome_id = engine.total_genomes_created + GENESIS_GENOME_ID_OFFSET;
+    
+    // Calculate genome properties based on parents; inbreeding costs the
+    // child consciousness
+    let avg_consciousness = derive_consciousness(&pending.parent_genome_ids)
+        .saturating_sub(pending.consciousness_penalty);
+    
+    // Roll rarity, inherit traits under the governance dominance model and
+    // roll the catalog's named mutations on top, from entropy mixed with
+    // on-chain state
+    let seeds = genesis_seeds(&pending.entropy_seed, &pending.creator, new_genome_id, clock.slot);
+    let model = DominanceModel { dominance: engine.trait_dominance, mutation_bps: engine.mutation_bps };
+    let offspring = rsm_core::simulate_genesis(
+        &pending.parent_traits, &model, &pending.catalog, &engine.rarity_weights, &seeds,
+    );
+    let rarity = Rarity::from_index(offspring.rarity_tier as usize);
+    let rna_multiplier = engine.rarity_multipliers[rarity as usize];
+    let total_supply = engine.offspring_supply(&pending.parent_supplies, rna_multiplier);
+    let traits = offspring.traits;
+    let mutations: Vec<ReceivedMutation> = offspring.mutations
+        .iter()
+        .map(|mutation| ReceivedMutation { id: mutation.id, trait_deltas: mutation.deltas })
+        .collect();
+    let mutation_ids: Vec<u16> = mutations.iter().map(|mutation| mutation.id).collect();
+    let matures_at_slot = clock.slot
+        .checked_add(engine.incubation_slots)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    // Store genesis record
+    genesis.genome_id = new_genome_id;
+    genesis.creator = pending.creator;
+    genesis.parent_genomes = pending.parent_genome_ids;
+    genesis.rna_burned = pending.rna_burned;
+    genesis.consciousness = avg_consciousness;
+    genesis.created_at = clock.unix_timestamp;
+    genesis.entropy_seed = pending.entropy_seed;
+    genesis.rarity = rarity;
+    genesis.rna_multiplier = rna_multiplier;
+    genesis.name = pending.name.clone();
+    genesis.metadata_uri_hash = pending.metadata_uri_hash;
+    genesis.matures_at_slot = matures_at_slot;
+    genesis.species = pending.species;
+    genesis.created_slot = clock.slot;
+    genesis.rarity_weights = engine.rarity_weights;
+    genesis.rarity_multipliers = engine.rarity_multipliers;
+    genesis.traits = traits;
+    genesis.trait_dominance = engine.trait_dominance;
+    genesis.mutation_bps = engine.mutation_bps;
+    genesis.mutations = mutations;
+    genesis.consciousness_penalty = pending.consciousness_penalty;
+    genesis.total_supply = total_supply;
+    genesis.genesis_event = pending.genesis_event;
+    
+    // Update engine stats
+    engine.total_genomes_created = engine.total_genomes_created
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    // Register the child genome with dna-token
+    let engine_key = engine.key();
+    let bump = [genesis_authority_bump];
+    let seeds: &[&[u8]] = &[GENESIS_AUTHORITY_SEED, engine_key.as_ref(), &bump];
+    rsm_dna_token::cpi::register_genesis_genome(
+        CpiContext::new_with_signer(dna_program, register, &[seeds]),
+        rsm_dna_token::RegisterGenesisGenomeArgs {
+            genome_id: new_genome_id,
+            name: pending.name.clone(),
+            metadata_uri_hash: pending.metadata_uri_hash,
+            consciousness: avg_consciousness,
+            rna_multiplier,
+            matures_at_slot,
+            species: pending.species,
+            traits,
+            total_supply,
+        },
+    )?;
+    
+    // Emit event
+    emit!(GenesisCreatedEvent {
+        genome_id: new_genome_id,
+        creator: pending.creator,
+        rna_burned: pending.rna_burned,
+        consciousness: avg_consciousness,
+        rarity,
+        rna_multiplier,
+        timestamp: genesis.created_at,
+        name: pending.name,
+        metadata_uri_hash: pending.metadata_uri_hash,
+        matures_at_slot,
+        species: pending.species,
+        traits,
+        mutations: mutation_ids,
+    });
+    Ok(new_genome_id)
+}
+
+// Burn a commit's escrowed RNA and close the escrow to `rent_to`, signed
+// by the commit PDA
+fn release_escrow<'info>(
+    token_program: &Program<'info, Token>,
+    rna_mint: &Account<'info, Mint>,
+    escrow: &Account<'info, TokenAccount>,
+    commit: &AccountInfo<'info>,
+    rent_to: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    token::burn(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Burn {
+                mint: rna_mint.to_account_info(),
+                from: escrow.to_account_info(),
+                authority: commit.clone(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow.to_account_info(),
+            destination: rent_to.clone(),
+            authority: commit.clone(),
+        },
+        &[seeds],
+    ))
+}
+
+// The hash SlotHashes sysvar `data` holds for `slot`; entries are 8-byte
+// slots and 32-byte hashes, newest first, after an 8-byte count
+pub fn slot_hash(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let count = u64::from_le_bytes(data.get(..8)?.try_into().ok()?) as usize;
+    data.get(8..)?
+        .chunks_exact(40)
+        .take(count)
+        .find(|entry| u64::from_le_bytes(entry[..8].try_into().unwrap()) == slot)
+        .map(|entry| entry[8..].try_into().unwrap())
+}
+
+// The newest hash in SlotHashes sysvar `data`
+pub fn newest_slot_hash(data: &[u8]) -> Option<[u8; 32]> {
+    let count = u64::from_le_bytes(data.get(..8)?.try_into().ok()?);
+    if count == 0 {
+        return None;
+    }
+    data.get(16..48)?.try_into().ok()
+}
+
+// A revealed genesis's entropy: the creator's mixed with the commit slot's
+// hash, which neither the creator nor a watcher of the reveal could choose
+pub fn revealed_entropy(entropy_seed: &[u8; 32], commit_slot_hash: &[u8; 32]) -> [u8; 32] {
+    hashv(&[entropy_seed.as_ref(), commit_slot_hash.as_ref()]).to_bytes()
+}
+
+// Consciousness of a child genome from its parents
+pub fn derive_consciousness(parent_genome_ids: &[u64]) -> u8 {
+    if !parent_genome_ids.is_empty() {
+        50 // Simplified - real would calculate from parents
+    } else {
+        0
+    }
+}
+
+// Hash binding a snapshot's counters to its version, engine and authority
+pub fn snapshot_digest(snapshot: &EngineSnapshot) -> [u8; 32] {
+    hashv(&[
+        &[snapshot.version],
+        snapshot.engine.as_ref(),
+        snapshot.authority.as_ref(),
+        &snapshot.total_genomes_created.to_le_bytes(),
+        &snapshot.total_rna_burned.to_le_bytes(),
+        &snapshot.slot.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+// Rarity roll from the creator's entropy mixed with on-chain state
+// Everything hashed is stored in the GenesisRecord so the roll can be replayed
+pub fn rarity_roll(entropy_seed: &[u8; 32], creator: &Pubkey, genome_id: u64, slot: u64) -> u64 {
+    let roll_hash = hashv(&[
+        entropy_seed,
+        creator.as_ref(),
+        &genome_id.to_le_bytes(),
+        &slot.to_le_bytes(),
+    ]);
+    let mut roll_bytes = [0u8; 8];
+    roll_bytes.copy_from_slice(&roll_hash.to_bytes()[..8]);
+    u64::from_le_bytes(roll_bytes)
+}
+
+// Seed for trait recombination, from the same replayable inputs as the
+// rarity roll
+pub fn trait_seed(entropy_seed: &[u8; 32], creator: &Pubkey, genome_id: u64, slot: u64) -> [u8; 32] {
+    hashv(&[
+        b"traits",
+        entropy_seed,
+        creator.as_ref(),
+        &genome_id.to_le_bytes(),
+        &slot.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+// Seed for catalog mutation rolls, from the same inputs as trait_seed
+pub fn mutation_seed(entropy_seed: &[u8; 32], creator: &Pubkey, genome_id: u64, slot: u64) -> [u8; 32] {
+    hashv(&[
+        b"mutations",
+        entropy_seed,
+        creator.as_ref(),
+        &genome_id.to_le_bytes(),
+        &slot.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+// All three seeds rsm_core::simulate_genesis rolls a genesis from
+pub fn genesis_seeds(entropy_seed: &[u8; 32], creator: &Pubkey, genome_id: u64, slot: u64) -> GenesisSeeds {
+    GenesisSeeds {
+        rarity_roll: rarity_roll(entropy_seed, creator, genome_id, slot),
+        traits: trait_seed(entropy_seed, creator, genome_id, slot),
+        mutations: mutation_seed(entropy_seed, creator, genome_id, slot),
+    }
+}
+
+// Pick a tier with probability weight / sum(weights)
+pub fn roll_rarity(roll: u64, weights: &[u16; RARITY_TIERS]) -> Rarity {
+    Rarity::from_index(rsm_core::rarity_tier(roll, weights) as usize)
+}
+
+// Printable ASCII only, no padding, no blocked terms
+pub fn validate_genome_name(name: &str) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_GENOME_NAME_LEN,
+        ErrorCode::InvalidGenomeName
+    );
+    require!(
+        name.bytes().all(|b| b.is_ascii_alphanumeric() || b" -_.'".contains(&b)),
+        ErrorCode::InvalidGenomeName
+    );
+    require!(name.trim() == name, ErrorCode::InvalidGenomeName);
+    
+    let lowered = name.to_ascii_lowercase();
+    require!(
+        !BLOCKED_NAME_TERMS.iter().any(|term| lowered.contains(term)),
+        ErrorCode::ProfaneGenomeName
+    );
+    Ok(())
+}
+
+// Account structures
+#[account]
+pub struct GenesisEngine {
+    pub authority: Pubkey,
+    pub genesis_cost: u64,
+    pub total_genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub active: bool,
+    pub rarity_weights: [u16; 4],
+    pub rarity_multipliers: [u16; 4],
+    pub incubation_slots: u64,
+    pub acceleration_cost_per_slot: u64,
+    pub trait_dominance: [u8; TRAIT_COUNT], // 255 = dominant allele always wins
+    pub mutation_bps: u16,
+    pub min_parent_holding: u64, // DNA of each parent a creator must hold
+    pub queue_head: u64, // next queue position to finalize
+    pub queue_tail: u64, // next queue position to assign
+    pub max_genesis_per_slot: u32,
+    pub last_genesis_slot: u64,
+    pub geneses_in_slot: u32,
+    pub has_mutation_catalog: bool,
+    pub consanguinity_generations: u8, // shared ancestor depth that counts as inbred, 0 = off
+    pub consanguinity_penalty: u8, // consciousness an inbred child loses
+    pub consanguinity_cost_bps: u16, // extra RNA an inbred genesis costs
+    pub offspring_supply_bps: u16, // 0 = every child gets GENESIS_GENOME_SUPPLY
+    pub min_offspring_supply: u64,
+    pub max_offspring_supply: u64,
+    pub has_price_oracle: bool, // genesis_cost is replaced by the oracle's USD cost
+    pub has_guardian: bool, // costly geneses need the guardian's co-signature
+    pub rna_mint: Pubkey, // the only RNA geneses burn
+    pub bump: u8, // at ["engine", creating authority]
+}
+
+impl GenesisEngine {
+    // Whether parents whose nearest shared ancestor is `common_ancestor`
+    // generations up are too closely related
+    pub fn is_inbred(&self, common_ancestor: Option<u8>) -> bool {
+        common_ancestor.is_some_and(|generation| generation <= self.consanguinity_generations)
+    }
+    
+    // Pool cost plus the consanguinity surcharge for inbred parents
+    pub fn breeding_cost(&self, pool_cost: u64, inbred: bool) -> Result<u64> {
+        if !inbred {
+            return Ok(pool_cost);
+        }
+        let surcharge = pool_cost as u128 * self.consanguinity_cost_bps as u128 / BPS_DENOMINATOR as u128;
+        u64::try_from(pool_cost as u128 + surcharge).map_err(|_| error!(ErrorCode::Overflow))
+    }
+    
+    // DNA supply of a child whose parents have `parent_supplies`
+    pub fn offspring_supply(&self, parent_supplies: &[u64], rarity_multiplier: u16) -> u64 {
+        if self.offspring_supply_bps == 0 {
+            return GENESIS_GENOME_SUPPLY;
+        }
+        let rule = SupplyRule {
+            scale_bps: self.offspring_supply_bps,
+            min_supply: self.min_offspring_supply,
+            max_supply: self.max_offspring_supply,
+        };
+        rule.offspring_supply(parent_supplies, rarity_multiplier)
+    }
+    
+
+    // Count one genesis against the per-slot throughput cap
+    pub fn take_slot_capacity(&mut self, slot: u64) -> Result<()> {
+        if self.last_genesis_slot != slot {
+            self.last_genesis_slot = slot;
+            self.geneses_in_slot = 0;
+        }
+        require!(self.geneses_in_slot < self.max_genesis_per_slot, ErrorCode::SlotCapacityReached);
+        self.geneses_in_slot += 1;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct GenesisPool {
+    pub engine: Pubkey,
+    pub species: u16,
+    pub cost_base_bps: u16,
+    pub cost_step_bps: u16,
+    pub population: u64,
+    pub population_cap: u64,
+    pub bump: u8,
+}
+
+impl GenesisPool {
+    // RNA cost of the next genome in this pool
+    pub fn cost(&self, genesis_cost: u64) -> Result<u64> {
+        let bps = (self.cost_base_bps as u128)
+            .checked_add(self.cost_step_bps as u128 * self.population as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        let cost = (genesis_cost as u128)
+            .checked_mul(bps)
+            .ok_or(ErrorCode::Overflow)?
+            / BPS_DENOMINATOR as u128;
+        u64::try_from(cost).map_err(|_| error!(ErrorCode::Overflow))
+    }
+}
+
+#[account]
+pub struct UserGenesisStats {
+    pub user: Pubkey,
+    pub genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub last_epoch: u64,
+    pub streak: u16, // consecutive epochs with a genesis, ending at last_epoch
+    pub bump: u8,
+}
+
+impl UserGenesisStats {
+    // Count a genesis in `epoch`; returns the discount earned by the
+    // consecutive epochs before it
+    pub fn record(&mut self, user: Pubkey, epoch: u64, bump: u8) -> Result<u64> {
+        let prior = if self.genomes_created == 0 {
+            self.streak = 1;
+            0
+        } else if self.last_epoch == epoch {
+            self.streak - 1
+        } else if self.last_epoch + 1 == epoch {
+            self.streak = self.streak.saturating_add(1);
+            self.streak - 1
+        } else {
+            self.streak = 1;
+            0
+        };
+        
+        self.user = user;
+        self.last_epoch = epoch;
+        self.bump = bump;
+        self.genomes_created = self.genomes_created
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(streak_discount_bps(prior))
+    }
+}
+
+// 5% off per prior consecutive epoch, capped at 25%
+pub fn streak_discount_bps(prior_epochs: u16) -> u64 {
+    (prior_epochs as u64 * STREAK_DISCOUNT_BPS).min(MAX_STREAK_DISCOUNT_BPS)
+}
+
+// A donating genesis's streak discount, raised by the gene pool's boost
+// and capped at 50%
+pub fn donor_discount_bps(discount_bps: u64, boost_bps: u16) -> u64 {
+    (discount_bps * (BPS_DENOMINATOR + boost_bps as u64) / BPS_DENOMINATOR).min(MAX_DONOR_STREAK_DISCOUNT_BPS)
+}
+
+// A time-limited breeding event, at ["genesis_event", engine, event_id]
+#[account]
+pub struct GenesisEvent {
+    pub engine: Pubkey,
+    pub event_id: u64,
+    pub start_slot: u64,
+    pub end_slot: u64, // exclusive
+    pub cost: u64, // RNA per genesis before surcharges and discounts, 0 = pool cost
+    pub mutations: Vec<NamedMutation>, // rolled after the engine catalog
+    pub max_participants: u64, // 0 = unlimited
+    pub participants: u64,
+    pub bump: u8,
+}
+
+impl GenesisEvent {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 4 + MAX_EVENT_MUTATIONS * NamedMutation::MAX_SIZE + 8 + 8 + 1;
+    
+    pub fn is_open(&self, slot: u64) -> bool {
+        self.start_slot <= slot && slot < self.end_slot
+    }
+    
+    pub fn has_room(&self) -> bool {
+        self.max_participants == 0 || self.participants < self.max_participants
+    }
+}
+
+// Co-signer of costly geneses at ["genesis_guardian", engine]
+#[account]
+pub struct GenesisGuardian {
+    pub engine: Pubkey,
+    pub guardian: Pubkey,
+    pub threshold: u64, // RNA a genesis may cost without the guardian
+    pub bump: u8,
+}
+
+impl GenesisGuardian {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+// Engine counters exported at ["engine_snapshot", engine] for a redeployed
+// engine to import
+#[account]
+pub struct EngineSnapshot {
+    pub version: u8,
+    pub engine: Pubkey, // the exporting engine
+    pub authority: Pubkey, // its authority, who alone may import
+    pub total_genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub slot: u64, // when it was exported
+    pub digest: [u8; 32], // snapshot_digest of the fields above
+    pub imported_into: Pubkey, // default until imported
+    pub bump: u8,
+}
+
+impl EngineSnapshot {
+    pub const SPACE: usize = 8 + 1 + 32 + 32 + 8 + 8 + 8 + 32 + 32 + 1;
+}
+
+// Genomes donated at genesis, awaiting the raffle, at ["gene_pool", engine]
+// The pool is their dna-token founder until then
+#[account]
+pub struct GenePool {
+    pub engine: Pubkey,
+    pub donor_streak_boost_bps: u16,
+    pub donations: u64,
+    pub distributed: u64,
+    pub genomes: Vec<PooledGenome>, // oldest first
+    pub bump: u8,
+}
+
+impl GenePool {
+    pub const SPACE: usize = 8 + 32 + 2 + 8 + 8 + 4 + MAX_GENE_POOL_GENOMES * PooledGenome::SIZE + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PooledGenome {
+    pub genome_id: u64,
+    pub genome: Pubkey, // dna-token genome
+    pub donor: Pubkey,
+}
+
+impl PooledGenome {
+    pub const SIZE: usize = 8 + 32 + 32;
+}
+
+// USD genesis pricing at ["price_oracle", engine]
+#[account]
+pub struct PriceOracle {
+    pub engine: Pubkey,
+    pub price_feed: Pubkey, // Pyth PriceUpdateV2 account
+    pub feed_id: [u8; 32],
+    pub usd_cost: u64, // micro-dollars per genesis
+    pub max_staleness_secs: u32,
+    pub max_confidence_bps: u16, // widest confidence interval accepted, relative to the price
+    pub bump: u8,
+}
+
+impl PriceOracle {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 4 + 2 + 1;
+    
+    // usd_cost in RNA base units at `price`, rounded up, after the
+    // feed, staleness and confidence checks
+    pub fn rna_cost(&self, price: &PythPrice, now: i64, rna_decimals: u8) -> Result<u64> {
+        require!(price.feed_id == self.feed_id, ErrorCode::InvalidPriceFeed);
+        require!(
+            now.saturating_sub(price.publish_time) <= self.max_staleness_secs as i64,
+            ErrorCode::StalePrice
+        );
+        require!(price.price > 0, ErrorCode::InvalidPriceFeed);
+        let unit_price = price.price as u128;
+        require!(
+            price.conf as u128 * BPS_DENOMINATOR as u128 <= unit_price * self.max_confidence_bps as u128,
+            ErrorCode::PriceTooUncertain
+        );
+        
+        // RNA = usd_cost / 10^USD_DECIMALS / (price * 10^exponent) * 10^rna_decimals
+        let scale = rna_decimals as i64 - USD_DECIMALS as i64 - price.exponent as i64;
+        let power = 10u128
+            .checked_pow(scale.unsigned_abs().try_into().map_err(|_| ErrorCode::Overflow)?)
+            .ok_or(ErrorCode::Overflow)?;
+        let (numerator, denominator) = if scale >= 0 {
+            ((self.usd_cost as u128).checked_mul(power).ok_or(ErrorCode::Overflow)?, unit_price)
+        } else {
+            (self.usd_cost as u128, unit_price.checked_mul(power).ok_or(ErrorCode::Overflow)?)
+        };
+        u64::try_from(numerator.div_ceil(denominator)).map_err(|_| error!(ErrorCode::Overflow))
+    }
+}
+
+// The price message of a fully verified Pyth PriceUpdateV2 account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PythPrice {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    // Discriminator, write authority, then the verification level: a
+    // partial one carries its signature count
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let discriminator = hashv(&[b"account:PriceUpdateV2"]).to_bytes();
+        require!(
+            data.len() > 8 + 32 && data[..8] == discriminator[..8],
+            ErrorCode::InvalidPriceFeed
+        );
+        require!(data[40] == 1, ErrorCode::PriceNotVerified);
+        let mut message = &data[41..];
+        let feed_id = <[u8; 32]>::deserialize(&mut message)?;
+        let price = i64::deserialize(&mut message)?;
+        let conf = u64::deserialize(&mut message)?;
+        let exponent = i32::deserialize(&mut message)?;
+        let publish_time = i64::deserialize(&mut message)?;
+        Ok(Self { feed_id, price, conf, exponent, publish_time })
+    }
+}
+
+#[account]
+pub struct HybridPermit {
+    pub engine: Pubkey,
+    pub holder: Pubkey,
+    pub species: u16,
+}
+
+#[account]
+pub struct GenesisRecord {
+    pub genome_id: u64,
+    pub creator: Pubkey,
+    pub parent_genomes: Vec<u64>,
+    pub rna_burned: u64,
+    pub consciousness: u8,
+    pub created_at: i64,
+    pub entropy_seed: [u8; 32],
+    pub rarity: Rarity,
+    pub rna_multiplier: u16,
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub matures_at_slot: u64,
+    pub species: u16,
+    pub created_slot: u64,
+    pub rarity_weights: [u16; 4],     // engine tables at creation, for replay
+    pub rarity_multipliers: [u16; 4],
+    pub traits: TraitVector,
+    pub trait_dominance: [u8; TRAIT_COUNT], // dominance model at creation, for replay
+    pub mutation_bps: u16,
+    pub mutations: Vec<ReceivedMutation>, // catalog mutations applied, in catalog order
+    pub consciousness_penalty: u8, // consanguinity penalty applied, for replay
+    pub total_supply: u64, // DNA supply registered for the child
+    pub genesis_event: Option<u64>, // event bred under; its mutations are in `mutations`
+}
+
+impl GenesisRecord {
+    pub const SPACE: usize = 8 + 8 + 32 + 4 + MAX_GENESIS_PARENTS * 8 + 8 + 1 + 8 + 32 + 1 + 2
+        + 4 + MAX_GENOME_NAME_LEN + 32 + 8 + 2 + 8 + 8 + 8 + TRAIT_COUNT + TRAIT_COUNT + 2
+        + 4 + (MAX_CATALOG_MUTATIONS + MAX_EVENT_MUTATIONS) * ReceivedMutation::SIZE + 1 + 8 + 1 + 8;
+}
+
+// Named mutations every genesis rolls against, at ["mutation_catalog", engine]
+#[account]
+pub struct MutationCatalog {
+    pub engine: Pubkey,
+    pub mutations: Vec<NamedMutation>,
+    pub bump: u8,
+}
+
+impl MutationCatalog {
+    pub const SPACE: usize = 8 + 32 + 4 + MAX_CATALOG_MUTATIONS * NamedMutation::MAX_SIZE + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct NamedMutation {
+    pub id: u16,
+    pub name: String,
+    pub trait_deltas: [i8; TRAIT_COUNT],
+    pub weight_bps: u16, // chance per genesis, of 10000
+}
+
+impl NamedMutation {
+    pub const MAX_SIZE: usize = 2 + 4 + MAX_MUTATION_NAME_LEN + TRAIT_COUNT + 2;
+    
+    // A 1-32 byte name and a weight of at most 10000 bps
+    pub fn is_valid(&self) -> bool {
+        !self.name.is_empty() && self.name.len() <= MAX_MUTATION_NAME_LEN && self.weight_bps as u64 <= BPS_DENOMINATOR
+    }
+    
+    pub fn to_core(&self) -> CatalogMutation {
+        CatalogMutation { id: self.id, deltas: self.trait_deltas, weight_bps: self.weight_bps }
+    }
+}
+
+// A catalog mutation as a child received it; deltas are kept since the
+// catalog entry may change later
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ReceivedMutation {
+    pub id: u16,
+    pub trait_deltas: [i8; TRAIT_COUNT],
+}
+
+impl ReceivedMutation {
+    pub const SIZE: usize = 2 + TRAIT_COUNT;
+}
+
+// Ancestors of a genesis-born genome within MAX_LINEAGE_GENERATIONS, at
+// ["lineage", engine, genome_id]; written at genesis, or by record_lineage
+// for genomes bred before lineages were kept
+#[account]
+pub struct Lineage {
+    pub genome_id: u64,
+    pub ancestors: Vec<LineageEntry>, // nearest generations first
+    pub bump: u8,
+}
+
+impl Lineage {
+    pub const SPACE: usize = 8 + 8 + 4 + MAX_LINEAGE_ANCESTORS * LineageEntry::SIZE + 1;
+    
+    pub fn record(&mut self, genome_id: u64, ancestors: Vec<LineageEntry>, bump: u8) {
+        self.genome_id = genome_id;
+        self.ancestors = ancestors;
+        self.bump = bump;
+    }
+    
+    // Whether `address` is this lineage's PDA under `engine`, so another
+    // engine's lineages cannot stand in for it
+    pub fn is_of(&self, address: &Pubkey, engine: &Pubkey) -> bool {
+        let seeds: &[&[u8]] = &[LINEAGE_SEED, engine.as_ref(), &self.genome_id.to_le_bytes(), &[self.bump]];
+        Pubkey::create_program_address(seeds, &crate::ID).is_ok_and(|expected| expected == *address)
+    }
+}
+
+// The Lineage PDA of `genome_id` under `engine`
+pub fn lineage_address(engine: &Pubkey, genome_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[LINEAGE_SEED, engine.as_ref(), &genome_id.to_le_bytes()], &crate::ID).0
+}
+
+// An rsm-core ancestor as a Lineage stores it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LineageEntry {
+    pub genome_id: u64,
+    pub generation: u8,
+}
+
+impl LineageEntry {
+    pub const SIZE: usize = 8 + 1;
+    
+    pub fn to_core(&self) -> Ancestor {
+        Ancestor { genome_id: self.genome_id, generation: self.generation }
+    }
+}
+
+impl From<Ancestor> for LineageEntry {
+    fn from(ancestor: Ancestor) -> Self {
+        Self { genome_id: ancestor.genome_id, generation: ancestor.generation }
+    }
+}
+
+// A paid genesis waiting at `position` in the queue
+#[account]
+pub struct QueuedGenesis {
+    pub engine: Pubkey,
+    pub position: u64,
+    pub creator: Pubkey,
+    pub species: u16,
+    pub parent_genome_ids: Vec<u64>,
+    pub parent_traits: Vec<TraitVector>, // read at enqueue, so parents may move after
+    pub entropy_seed: [u8; 32],
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub rna_locked: u64,
+    pub enqueued_slot: u64,
+    pub bump: u8,
+    pub lineage: Vec<LineageEntry>, // the child's, read at enqueue
+    pub consciousness_penalty: u8,
+    pub parent_supplies: Vec<u64>, // read at enqueue
+    pub seed_slot: u64, // whose hash the roll mixes in, unknown when it was set
+}
+
+impl QueuedGenesis {
+    pub const SPACE: usize = 8 + 32 + 8 + 32 + 2 + 4 + MAX_GENESIS_PARENTS * 8 + 4 + MAX_GENESIS_PARENTS * TRAIT_COUNT
+        + 32 + 4 + MAX_GENOME_NAME_LEN + 32 + 8 + 8 + 1 + 4 + MAX_LINEAGE_ANCESTORS * LineageEntry::SIZE + 1
+        + 4 + MAX_GENESIS_PARENTS * 8 + 8;
+}
+
+// A genesis committed to by hash(entropy_seed || salt) and paid into
+// escrow, at ["genesis_commit", engine, creator]; its escrow is the token
+// account at ["commit_escrow", genesis_commit]
+#[account]
+pub struct GenesisCommit {
+    pub engine: Pubkey,
+    pub creator: Pubkey,
+    pub pool: Pubkey, // holds the reserved slot, freed on forfeit
+    pub species: u16,
+    pub commitment: [u8; 32],
+    pub commit_slot: u64,
+    pub parent_genome_ids: Vec<u64>,
+    pub parent_traits: Vec<TraitVector>, // read at commit
+    pub parent_supplies: Vec<u64>, // read at commit
+    pub lineage: Vec<LineageEntry>, // the child's, read at commit
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub rna_escrowed: u64,
+    pub consciousness_penalty: u8,
+    pub bump: u8,
+    pub genesis_event: Option<u64>, // bred under, from create_genome
+    pub donation: Option<u16>, // donor discount, when the child goes to the gene pool
+}
+
+impl GenesisCommit {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 2 + 32 + 8 + 4 + MAX_GENESIS_PARENTS * 8
+        + 4 + MAX_GENESIS_PARENTS * TRAIT_COUNT + 4 + MAX_GENESIS_PARENTS * 8
+        + 4 + MAX_LINEAGE_ANCESTORS * LineageEntry::SIZE + 4 + MAX_GENOME_NAME_LEN + 32 + 8 + 1 + 1 + 9 + 3;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rarity {
+    Common,
+    Rare,
+    Epic,
+    Mythic,
+}
+
+impl Rarity {
+    pub fn from_index(index: usize) -> Self {
+        match index {
+            1 => Rarity::Rare,
+            2 => Rarity::Epic,
+            3 => Rarity::Mythic,
+            _ => Rarity::Common,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EngineStats {
+    pub total_genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub genesis_cost: u64,
+    pub active: bool,
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 200,
+        seeds = [ENGINE_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateGenome<'info> {
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        mut,
+        seeds = [POOL_SEED, engine.key().as_ref(), pool.species.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, GenesisPool>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = GenesisCommit::SPACE,
+        seeds = [GENESIS_COMMIT_SEED, engine.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub genesis_commit: Account<'info, GenesisCommit>,
+    
+    #[account(
+        init,
+        payer = user,
+        token::mint = rna_mint,
+        token::authority = genesis_commit,
+        seeds = [COMMIT_ESCROW_SEED, genesis_commit.key().as_ref()],
+        bump
+    )]
+    pub commit_escrow: Account<'info, TokenAccount>,
+    
+    #[account(address = engine.rna_mint @ ErrorCode::InvalidRnaMint)]
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub user_rna_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 72,
+        seeds = [USER_STATS_SEED, engine.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserGenesisStats>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    #[account(mut, has_one = engine, close = user)]
+    pub hybrid_permit: Option<Account<'info, HybridPermit>>,
+    
+    #[account(
+        mut,
+        has_one = engine,
+        seeds = [GENESIS_EVENT_SEED, engine.key().as_ref(), genesis_event.event_id.to_le_bytes().as_ref()],
+        bump = genesis_event.bump
+    )]
+    pub genesis_event: Option<Account<'info, GenesisEvent>>,
+    
+    #[account(
+        has_one = engine,
+        has_one = price_feed,
+        seeds = [PRICE_ORACLE_SEED, engine.key().as_ref()],
+        bump = price_oracle.bump
+    )]
+    pub price_oracle: Option<Account<'info, PriceOracle>>,
+    
+    /// CHECK: Pyth PriceUpdateV2 account; owner and layout checked on read
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        has_one = engine,
+        seeds = [GUARDIAN_SEED, engine.key().as_ref()],
+        bump = genesis_guardian.bump
+    )]
+    pub genesis_guardian: Option<Account<'info, GenesisGuardian>>,
+    
+    pub guardian: Option<Signer<'info>>,
+    
+    #[account(
+        has_one = engine,
+        seeds = [GENE_POOL_SEED, engine.key().as_ref()],
+        bump = gene_pool.bump
+    )]
+    pub gene_pool: Option<Account<'info, GenePool>>,
+}
+
+#[derive(Accounts)]
+pub struct EnqueueGenesis<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        mut,
+        seeds = [POOL_SEED, engine.key().as_ref(), pool.species.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, GenesisPool>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = QueuedGenesis::SPACE,
+        seeds = [QUEUE_SEED, engine.key().as_ref(), engine.queue_tail.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub queued_genesis: Account<'info, QueuedGenesis>,
+    
+    #[account(mut, address = engine.rna_mint @ ErrorCode::InvalidRnaMint)]
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub user_rna_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 72,
+        seeds = [USER_STATS_SEED, engine.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserGenesisStats>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    #[account(mut, has_one = engine, close = user)]
+    pub hybrid_permit: Option<Account<'info, HybridPermit>>,
+    
+    #[account(
+        has_one = engine,
+        has_one = price_feed,
+        seeds = [PRICE_ORACLE_SEED, engine.key().as_ref()],
+        bump = price_oracle.bump
+    )]
+    pub price_oracle: Option<Account<'info, PriceOracle>>,
+    
+    /// CHECK: Pyth PriceUpdateV2 account; owner and layout checked on read
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        has_one = engine,
+        seeds = [GUARDIAN_SEED, engine.key().as_ref()],
+        bump = genesis_guardian.bump
+    )]
+    pub genesis_guardian: Option<Account<'info, GenesisGuardian>>,
+    
+    pub guardian: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ProcessQueue<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        mut,
+        has_one = engine,
+        close = cranker,
+        seeds = [QUEUE_SEED, engine.key().as_ref(), engine.queue_head.to_le_bytes().as_ref()],
+        bump = queued_genesis.bump
+    )]
+    pub queued_genesis: Account<'info, QueuedGenesis>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = GenesisRecord::SPACE
+    )]
+    pub genesis_record: Account<'info, GenesisRecord>,
+    
+    pub dna_registry: Account<'info, rsm_dna_token::Registry>,
+    
+    /// CHECK: initialized by dna-token at ["genome", genome_id]
+    #[account(mut)]
+    pub dna_genome: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA signer recognized by dna-token
+    #[account(
+        seeds = [GENESIS_AUTHORITY_SEED, engine.key().as_ref()],
+        bump
+    )]
+    pub genesis_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub dna_program: Program<'info, RsmDnaToken>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    #[account(
+        seeds = [MUTATION_CATALOG_SEED, engine.key().as_ref()],
+        bump = mutation_catalog.bump
+    )]
+    pub mutation_catalog: Option<Account<'info, MutationCatalog>>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = Lineage::SPACE,
+        seeds = [
+            LINEAGE_SEED,
+            engine.key().as_ref(),
+            (engine.total_genomes_created + GENESIS_GENOME_ID_OFFSET).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lineage: Account<'info, Lineage>,
+    
+    /// CHECK: SlotHashes sysvar, read for the entry's seed slot hash
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReseedQueuedGenesis<'info> {
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        mut,
+        has_one = engine,
+        seeds = [QUEUE_SEED, engine.key().as_ref(), engine.queue_head.to_le_bytes().as_ref()],
+        bump = queued_genesis.bump
+    )]
+    pub queued_genesis: Account<'info, QueuedGenesis>,
+    
+    /// CHECK: SlotHashes sysvar
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitGenesis<'info> {
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        mut,
+        seeds = [POOL_SEED, engine.key().as_ref(), pool.species.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, GenesisPool>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = GenesisCommit::SPACE,
+        seeds = [GENESIS_COMMIT_SEED, engine.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub genesis_commit: Account<'info, GenesisCommit>,
+    
+    #[account(
+        init,
+        payer = user,
+        token::mint = rna_mint,
+        token::authority = genesis_commit,
+        seeds = [COMMIT_ESCROW_SEED, genesis_commit.key().as_ref()],
+        bump
+    )]
+    pub commit_escrow: Account<'info, TokenAccount>,
+    
+    #[account(address = engine.rna_mint @ ErrorCode::InvalidRnaMint)]
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub user_rna_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 72,
+        seeds = [USER_STATS_SEED, engine.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserGenesisStats>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    #[account(mut, has_one = engine, close = user)]
+    pub hybrid_permit: Option<Account<'info, HybridPermit>>,
+    
+    #[account(
+        has_one = engine,
+        has_one = price_feed,
+        seeds = [PRICE_ORACLE_SEED, engine.key().as_ref()],
+        bump = price_oracle.bump
+    )]
+    pub price_oracle: Option<Account<'info, PriceOracle>>,
+    
+    /// CHECK: Pyth PriceUpdateV2 account; owner and layout checked on read
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        has_one = engine,
+        seeds = [GUARDIAN_SEED, engine.key().as_ref()],
+        bump = genesis_guardian.bump
+    )]
+    pub genesis_guardian: Option<Account<'info, GenesisGuardian>>,
+    
+    pub guardian: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct RevealGenesis<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        mut,
+        has_one = engine,
+        close = user,
+        seeds = [GENESIS_COMMIT_SEED, engine.key().as_ref(), user.key().as_ref()],
+        bump = genesis_commit.bump
+    )]
+    pub genesis_commit: Account<'info, GenesisCommit>,
+    
+    #[account(
+        mut,
+        seeds = [COMMIT_ESCROW_SEED, genesis_commit.key().as_ref()],
+        bump
+    )]
+    pub commit_escrow: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = engine.rna_mint @ ErrorCode::InvalidRnaMint)]
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = GenesisRecord::SPACE
+    )]
+    pub genesis_record: Account<'info, GenesisRecord>,
+    
+    pub dna_registry: Account<'info, rsm_dna_token::Registry>,
+    
+    /// CHECK: initialized by dna-token at ["genome", genome_id]
+    #[account(mut)]
+    pub dna_genome: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA signer recognized by dna-token
+    #[account(
+        seeds = [GENESIS_AUTHORITY_SEED, engine.key().as_ref()],
+        bump
+    )]
+    pub genesis_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// CHECK: SlotHashes sysvar, read for the commit slot's hash
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    pub dna_program: Program<'info, RsmDnaToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    #[account(
+        seeds = [MUTATION_CATALOG_SEED, engine.key().as_ref()],
+        bump = mutation_catalog.bump
+    )]
+    pub mutation_catalog: Option<Account<'info, MutationCatalog>>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = Lineage::SPACE,
+        seeds = [
+            LINEAGE_SEED,
+            engine.key().as_ref(),
+            (engine.total_genomes_created + GENESIS_GENOME_ID_OFFSET).to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lineage: Account<'info, Lineage>,
+    
+    #[account(
+        has_one = engine,
+        seeds = [GENESIS_EVENT_SEED, engine.key().as_ref(), genesis_event.event_id.to_le_bytes().as_ref()],
+        bump = genesis_event.bump
+    )]
+    pub genesis_event: Option<Account<'info, GenesisEvent>>,
+    
+    #[account(
+        mut,
+        has_one = engine,
+        seeds = [GENE_POOL_SEED, engine.key().as_ref()],
+        bump = gene_pool.bump
+    )]
+    pub gene_pool: Option<Account<'info, GenePool>>,
+}
+
+#[derive(Accounts)]
+pub struct ForfeitGenesisCommit<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        mut,
+        has_one = engine,
+        has_one = pool,
+        close = cranker,
+        seeds = [GENESIS_COMMIT_SEED, engine.key().as_ref(), genesis_commit.creator.as_ref()],
+        bump = genesis_commit.bump
+    )]
+    pub genesis_commit: Account<'info, GenesisCommit>,
+    
+    #[account(
+        mut,
+        seeds = [COMMIT_ESCROW_SEED, genesis_commit.key().as_ref()],
+        bump
+    )]
+    pub commit_escrow: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub pool: Account<'info, GenesisPool>,
+    
+    #[account(mut, address = engine.rna_mint @ ErrorCode::InvalidRnaMint)]
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RecordLineage<'info> {
+    #[account(has_one = authority @ ErrorCode::Unauthorized)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub genesis_record: Account<'info, GenesisRecord>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = Lineage::SPACE,
+        seeds = [LINEAGE_SEED, engine.key().as_ref(), genesis_record.genome_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lineage: Account<'info, Lineage>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMutationCatalog<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
         init,
         payer = authority,
-        space = 8 + 200
+        space = MutationCatalog::SPACE,
+        seeds = [MUTATION_CATALOG_SEED, engine.key().as_ref()],
+        bump
     )]
-    pub engine: Account<'info, GenesisEngine>,
+    pub mutation_catalog: Account<'info, MutationCatalog>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -186,36 +2940,311 @@ pub struct Initialize<'info> {
 }
 
 #[derive(Accounts)]
-pub struct CreateGenome<'info> {
-    #[account(mut)]
+pub struct EditMutationCatalog<'info> {
     pub engine: Account<'info, GenesisEngine>,
     
     #[account(
-        init,
-        payer = user,
-        space = 8 + 500
+        mut,
+        has_one = engine,
+        seeds = [MUTATION_CATALOG_SEED, engine.key().as_ref()],
+        bump = mutation_catalog.bump
     )]
-    pub genesis_record: Account<'info, GenesisRecord>,
+    pub mutation_catalog: Account<'info, MutationCatalog>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateQueueCap<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCost<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRarity<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDominance<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConsanguinity<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOffspringSupply<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AccelerateIncubation<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
     
     #[account(mut)]
+    pub genesis_record: Account<'info, GenesisRecord>,
+    
+    #[account(mut, address = engine.rna_mint @ ErrorCode::InvalidRnaMint)]
     pub rna_mint: Account<'info, Mint>,
     
     #[account(mut)]
     pub user_rna_account: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    pub dna_registry: Account<'info, rsm_dna_token::Registry>,
+    
+    #[account(
+        mut,
+        constraint = dna_genome.load()?.genome_id == genesis_record.genome_id @ ErrorCode::GenomeMismatch
+    )]
+    pub dna_genome: AccountLoader<'info, rsm_dna_token::GenomeV2>,
+    
+    /// CHECK: PDA signer recognized by dna-token
+    #[account(
+        seeds = [GENESIS_AUTHORITY_SEED, engine.key().as_ref()],
+        bump
+    )]
+    pub genesis_authority: UncheckedAccount<'info>,
+    
     pub user: Signer<'info>,
     
+    pub dna_program: Program<'info, RsmDnaToken>,
     pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+}
+
+#[derive(Accounts)]
+#[instruction(species: u16)]
+pub struct CreatePool<'info> {
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 64,
+        seeds = [POOL_SEED, engine.key().as_ref(), species.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, GenesisPool>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateCost<'info> {
+pub struct UpdatePool<'info> {
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(mut, has_one = engine)]
+    pub pool: Account<'info, GenesisPool>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct IssueHybridPermit<'info> {
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 72
+    )]
+    pub permit: Account<'info, HybridPermit>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(event_id: u64)]
+pub struct CreateGenesisEvent<'info> {
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = GenesisEvent::SPACE,
+        seeds = [GENESIS_EVENT_SEED, engine.key().as_ref(), event_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub genesis_event: Account<'info, GenesisEvent>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPriceOracle<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PriceOracle::SPACE,
+        seeds = [PRICE_ORACLE_SEED, engine.key().as_ref()],
+        bump
+    )]
+    pub price_oracle: Account<'info, PriceOracle>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = GenesisGuardian::SPACE,
+        seeds = [GUARDIAN_SEED, engine.key().as_ref()],
+        bump
+    )]
+    pub genesis_guardian: Account<'info, GenesisGuardian>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMinParentHolding<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateIncubation<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExportState<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = EngineSnapshot::SPACE,
+        seeds = [ENGINE_SNAPSHOT_SEED, engine.key().as_ref()],
+        bump
+    )]
+    pub snapshot: Account<'info, EngineSnapshot>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ImportState<'info> {
+    #[account(mut)]
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        mut,
+        seeds = [ENGINE_SNAPSHOT_SEED, snapshot.engine.as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, EngineSnapshot>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGenePool<'info> {
+    pub engine: Account<'info, GenesisEngine>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = GenePool::SPACE,
+        seeds = [GENE_POOL_SEED, engine.key().as_ref()],
+        bump
+    )]
+    pub gene_pool: Account<'info, GenePool>,
+    
     #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeGenePool<'info> {
     pub engine: Account<'info, GenesisEngine>,
     
+    #[account(
+        mut,
+        has_one = engine,
+        seeds = [GENE_POOL_SEED, engine.key().as_ref()],
+        bump = gene_pool.bump
+    )]
+    pub gene_pool: Account<'info, GenePool>,
+    
+    /// CHECK: the oldest pooled genome, matched against the pool
+    #[account(mut)]
+    pub dna_genome: UncheckedAccount<'info>,
+    
+    /// CHECK: SlotHashes sysvar, read for the raffle seed
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
     pub authority: Signer<'info>,
+    
+    pub dna_program: Program<'info, RsmDnaToken>,
 }
 
 #[derive(Accounts)]
@@ -238,7 +3267,99 @@ pub struct GenesisCreatedEvent {
     pub creator: Pubkey,
     pub rna_burned: u64,
     pub consciousness: u8,
+    pub rarity: Rarity,
+    pub rna_multiplier: u16,
     pub timestamp: i64,
+    pub name: String,
+    pub metadata_uri_hash: [u8; 32],
+    pub matures_at_slot: u64,
+    pub species: u16,
+    pub traits: TraitVector,
+    pub mutations: Vec<u16>, // ids of the catalog mutations received
+}
+
+#[event]
+pub struct GenesisQueuedEvent {
+    pub position: u64,
+    pub creator: Pubkey,
+    pub species: u16,
+    pub rna_locked: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GenesisCommittedEvent {
+    pub creator: Pubkey,
+    pub species: u16,
+    pub commitment: [u8; 32],
+    pub rna_escrowed: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GenesisCommitForfeitedEvent {
+    pub creator: Pubkey,
+    pub species: u16,
+    pub rna_forfeited: u64,
+    pub commit_slot: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GenesisEventCreatedEvent {
+    pub event_id: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub cost: u64,
+    pub max_participants: u64,
+    pub mutations: Vec<u16>, // ids of the event's mutations
+}
+
+#[event]
+pub struct EngineStateExportedEvent {
+    pub engine: Pubkey,
+    pub authority: Pubkey,
+    pub version: u8,
+    pub total_genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub digest: [u8; 32],
+    pub slot: u64,
+}
+
+#[event]
+pub struct EngineStateImportedEvent {
+    pub engine: Pubkey,
+    pub source_engine: Pubkey,
+    pub total_genomes_created: u64,
+    pub total_rna_burned: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GenomeDonatedEvent {
+    pub genome_id: u64,
+    pub donor: Pubkey,
+    pub discount_bps: u16,
+    pub pool_size: u16,
+}
+
+#[event]
+pub struct GenePoolDistributedEvent {
+    pub genome_id: u64,
+    pub donor: Pubkey,
+    pub winner: Pubkey,
+    pub stake: Pubkey,
+    pub candidates: u32,
+    pub slot: u64,
+}
+
+#[event]
+pub struct IncubationAcceleratedEvent {
+    pub genome_id: u64,
+    pub creator: Pubkey,
+    pub rna_burned: u64,
+    pub matures_at_slot: u64,
+    pub slot: u64,
 }
 
 // Errors
@@ -250,4 +3371,116 @@ pub enum ErrorCode {
     EngineInactive,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Rarity weights must not all be zero")]
+    InvalidRarityWeights,
+    #[msg("Genome name must be 1-32 printable characters without padding")]
+    InvalidGenomeName,
+    #[msg("Genome name contains a blocked term")]
+    ProfaneGenomeName,
+    #[msg("Incubation acceleration is disabled")]
+    AccelerationDisabled,
+    #[msg("Genome has already matured")]
+    AlreadyMature,
+    #[msg("DNA genome does not match genesis record")]
+    GenomeMismatch,
+    #[msg("Species pool is at its population cap")]
+    PopulationCapReached,
+    #[msg("Parent accounts do not match parent_genome_ids")]
+    ParentMismatch,
+    #[msg("Parents are not of the pool's species")]
+    SpeciesMismatch,
+    #[msg("Hybrid permit is for another holder or species")]
+    InvalidHybridPermit,
+    #[msg("Pool base cost must be positive")]
+    InvalidPoolCurve,
+    #[msg("Ecosystem is paused")]
+    EcosystemPaused,
+    #[msg("Mutation rate exceeds 10000 bps")]
+    InvalidDominance,
+    #[msg("Parent genome is unminted or not held by the creator")]
+    ParentNotHeld,
+    #[msg("Minimum parent holding must be positive")]
+    InvalidMinParentHolding,
+    #[msg("Geneses are queued; enqueue instead of creating directly")]
+    QueueNotEmpty,
+    #[msg("Genesis throughput cap for this slot is reached")]
+    SlotCapacityReached,
+    #[msg("Genesis throughput cap must be positive")]
+    InvalidQueueCap,
+    #[msg("Engine has a mutation catalog; pass it")]
+    MutationCatalogRequired,
+    #[msg("Mutation needs a 1-32 byte name and a weight of at most 10000 bps")]
+    InvalidMutation,
+    #[msg("Mutation catalog is full")]
+    MutationCatalogFull,
+    #[msg("No mutation with this id in the catalog")]
+    UnknownMutation,
+    #[msg("Pass the Lineage of each genesis-born parent, in parent order")]
+    LineageMismatch,
+    #[msg("Consanguinity depth exceeds the generations a lineage records")]
+    InvalidConsanguinity,
+    #[msg("Offspring supply needs a positive scale and 0 < min <= max")]
+    InvalidOffspringSupply,
+    #[msg("Genesis event needs start < end and at most 8 valid mutations")]
+    InvalidGenesisEvent,
+    #[msg("Genesis event is not running at this slot")]
+    GenesisEventClosed,
+    #[msg("Genesis event has no participant slots left")]
+    GenesisEventFull,
+    #[msg("Price oracle needs a positive staleness bound and confidence of at most 10000 bps")]
+    InvalidPriceOracle,
+    #[msg("Engine prices genesis in USD; pass its price oracle and feed")]
+    PriceOracleRequired,
+    #[msg("Price feed is not the oracle's Pyth feed")]
+    InvalidPriceFeed,
+    #[msg("Price update is older than the oracle allows")]
+    StalePrice,
+    #[msg("Price confidence interval is wider than the oracle allows")]
+    PriceTooUncertain,
+    #[msg("Price update is not fully verified")]
+    PriceNotVerified,
+    #[msg("Genesis can be revealed from the slot after its commit")]
+    RevealTooEarly,
+    #[msg("Reveal window has closed; the commit can only be forfeited")]
+    RevealWindowClosed,
+    #[msg("Entropy and salt do not match the commitment")]
+    CommitmentMismatch,
+    #[msg("Commit can still be revealed")]
+    RevealWindowOpen,
+    #[msg("Engine has a genesis guardian; pass it")]
+    GuardianRequired,
+    #[msg("Genesis costs more than the guardian threshold; the guardian must co-sign")]
+    GuardianSignatureRequired,
+    #[msg("Queued geneses must be finalized before the engine state is exported")]
+    QueuePending,
+    #[msg("Snapshot has already been imported")]
+    SnapshotAlreadyImported,
+    #[msg("Snapshot version or digest does not match")]
+    InvalidSnapshot,
+    #[msg("Engine has already created genomes; import into a fresh engine")]
+    EngineNotFresh,
+    #[msg("Gene pool donor boost exceeds 10000 bps")]
+    InvalidGenePool,
+    #[msg("Gene pool is full until its genomes are raffled")]
+    GenePoolFull,
+    #[msg("Gene pool has no genomes to raffle")]
+    GenePoolEmpty,
+    #[msg("Stakes must be dna-token GenomeStake accounts in increasing address order")]
+    InvalidStakeAccounts,
+    #[msg("No stake passed is still locked with DNA in it")]
+    NoActiveStakers,
+    #[msg("RNA mint is not the engine's")]
+    InvalidRnaMint,
+    #[msg("Commit was made under a genesis event; pass it")]
+    GenesisEventRequired,
+    #[msg("Commit donates its child; pass the engine's gene pool")]
+    GenePoolRequired,
+    #[msg("Queued genesis's seed slot has left SlotHashes; reseed it first")]
+    QueueSeedExpired,
+    #[msg("Queued genesis's seed slot hash is still available")]
+    QueueSeedAvailable,
+    #[msg("Queued genesis can be processed from the slot after its seed slot")]
+    QueueSeedPending,
+    #[msg("A genesis takes at most 4 parents")]
+    TooManyParents,
 }
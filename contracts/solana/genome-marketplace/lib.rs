@@ -0,0 +1,479 @@
+// Genome Marketplace - Fixed-price DNA sales
+// Sellers escrow DNA tokens, buyers pay RNA
+// Long-tail complement to the auction flow
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, CloseAccount};
+
+declare_id!("MKTxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+
+pub const MAX_FEE_BPS: u16 = 1000; // 10%
+
+#[program]
+pub mod genome_marketplace {
+    use super::*;
+
+    // Initialize marketplace
+    pub fn initialize_marketplace(
+        ctx: Context<InitializeMarketplace>,
+        fee_bps: u16, // e.g. 250 = 2.5%
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, ErrorCode::InvalidFee);
+
+        let marketplace = &mut ctx.accounts.marketplace;
+
+        marketplace.authority = ctx.accounts.authority.key();
+        marketplace.rna_mint = ctx.accounts.rna_mint.key();
+        marketplace.fee_account = ctx.accounts.fee_account.key();
+        marketplace.fee_bps = fee_bps;
+        marketplace.total_listings = 0;
+        marketplace.total_sales = 0;
+        marketplace.total_volume = 0;
+
+        msg!("Marketplace initialized, fee: {} bps", fee_bps);
+        Ok(())
+    }
+
+    // Update protocol fee (authority only)
+    pub fn update_fee(
+        ctx: Context<UpdateFee>,
+        fee_bps: u16,
+    ) -> Result<()> {
+        let marketplace = &mut ctx.accounts.marketplace;
+
+        require!(
+            ctx.accounts.authority.key() == marketplace.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(fee_bps <= MAX_FEE_BPS, ErrorCode::InvalidFee);
+
+        let old_fee = marketplace.fee_bps;
+        marketplace.fee_bps = fee_bps;
+
+        msg!("Marketplace fee updated: {} -> {} bps", old_fee, fee_bps);
+        Ok(())
+    }
+
+    // List DNA tokens at a fixed RNA price per token
+    pub fn list_genome(
+        ctx: Context<ListGenome>,
+        genome_id: u64,
+        amount: u64,
+        price_per_token: u64, // RNA per DNA token
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(price_per_token > 0, ErrorCode::InvalidPrice);
+
+        // Escrow DNA tokens
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.seller_dna_account.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                    authority: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let listing = &mut ctx.accounts.listing;
+        listing.marketplace = ctx.accounts.marketplace.key();
+        listing.seller = ctx.accounts.seller.key();
+        listing.dna_mint = ctx.accounts.dna_mint.key();
+        listing.genome_id = genome_id;
+        listing.amount = amount;
+        listing.price_per_token = price_per_token;
+        listing.created_at = Clock::get()?.unix_timestamp;
+        listing.bump = ctx.bumps.listing;
+
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.total_listings = marketplace.total_listings
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(ListingCreatedEvent {
+            listing: listing.key(),
+            seller: listing.seller,
+            genome_id,
+            amount,
+            price_per_token,
+            timestamp: listing.created_at,
+        });
+
+        msg!("Listed {} DNA of genome {} at {} RNA each", amount, genome_id, price_per_token);
+        Ok(())
+    }
+
+    // Buy DNA tokens from a listing (partial fills allowed)
+    pub fn buy_genome(
+        ctx: Context<BuyGenome>,
+        amount: u64,
+    ) -> Result<()> {
+        let listing = &ctx.accounts.listing;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= listing.amount, ErrorCode::InsufficientListing);
+
+        let total_price = amount
+            .checked_mul(listing.price_per_token)
+            .ok_or(ErrorCode::Overflow)?;
+        let fee = calculate_fee(total_price, ctx.accounts.marketplace.fee_bps)?;
+        let seller_proceeds = total_price - fee;
+
+        // Pay seller
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_rna_account.to_account_info(),
+                    to: ctx.accounts.seller_rna_account.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            seller_proceeds,
+        )?;
+
+        // Pay protocol fee
+        if fee > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.buyer_rna_account.to_account_info(),
+                        to: ctx.accounts.fee_account.to_account_info(),
+                        authority: ctx.accounts.buyer.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+
+        // Release DNA from escrow
+        let seller = listing.seller;
+        let dna_mint = listing.dna_mint;
+        let bump = [listing.bump];
+        let seeds: &[&[u8]] = &[b"listing", seller.as_ref(), dna_mint.as_ref(), &bump];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.buyer_dna_account.to_account_info(),
+                    authority: ctx.accounts.listing.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        let listing = &mut ctx.accounts.listing;
+        listing.amount -= amount;
+
+        let marketplace = &mut ctx.accounts.marketplace;
+        marketplace.total_sales = marketplace.total_sales
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        marketplace.total_volume = marketplace.total_volume
+            .checked_add(total_price)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(ListingSoldEvent {
+            listing: listing.key(),
+            seller,
+            buyer: ctx.accounts.buyer.key(),
+            genome_id: listing.genome_id,
+            amount,
+            total_price,
+            fee,
+            remaining: listing.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Sold {} DNA of genome {} for {} RNA", amount, listing.genome_id, total_price);
+        Ok(())
+    }
+
+    // Cancel listing, return unsold DNA and close escrow
+    pub fn cancel_listing(
+        ctx: Context<CancelListing>,
+    ) -> Result<()> {
+        let listing = &ctx.accounts.listing;
+        let seller = listing.seller;
+        let dna_mint = listing.dna_mint;
+        let remaining = listing.amount;
+        let bump = [listing.bump];
+        let seeds: &[&[u8]] = &[b"listing", seller.as_ref(), dna_mint.as_ref(), &bump];
+
+        if remaining > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.escrow.to_account_info(),
+                        to: ctx.accounts.seller_dna_account.to_account_info(),
+                        authority: ctx.accounts.listing.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                remaining,
+            )?;
+        }
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow.to_account_info(),
+                destination: ctx.accounts.seller.to_account_info(),
+                authority: ctx.accounts.listing.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+
+        emit!(ListingCancelledEvent {
+            listing: ctx.accounts.listing.key(),
+            seller,
+            genome_id: ctx.accounts.listing.genome_id,
+            returned: remaining,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Listing cancelled, {} DNA returned", remaining);
+        Ok(())
+    }
+}
+
+// Fee math
+pub fn calculate_fee(total_price: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (total_price as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / 10_000;
+    Ok(fee as u64)
+}
+
+// Account structures
+#[account]
+pub struct Marketplace {
+    pub authority: Pubkey,
+    pub rna_mint: Pubkey,
+    pub fee_account: Pubkey,
+    pub fee_bps: u16,
+    pub total_listings: u64,
+    pub total_sales: u64,
+    pub total_volume: u64,
+}
+
+#[account]
+pub struct Listing {
+    pub marketplace: Pubkey, // fee and RNA mint of a sale come from here
+    pub seller: Pubkey,
+    pub dna_mint: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+    pub price_per_token: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct InitializeMarketplace<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 200
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    pub rna_mint: Account<'info, Mint>,
+
+    #[account(token::mint = rna_mint)]
+    pub fee_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFee<'info> {
+    #[account(mut)]
+    pub marketplace: Account<'info, Marketplace>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ListGenome<'info> {
+    #[account(mut)]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + 200,
+        seeds = [b"listing", seller.key().as_ref(), dna_mint.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = seller,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump,
+        token::mint = dna_mint,
+        token::authority = listing
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    pub dna_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = dna_mint,
+        token::authority = seller
+    )]
+    pub seller_dna_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuyGenome<'info> {
+    #[account(mut)]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", listing.seller.as_ref(), listing.dna_mint.as_ref()],
+        bump = listing.bump,
+        has_one = marketplace @ ErrorCode::WrongMarketplace
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = listing.dna_mint
+    )]
+    pub buyer_dna_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = marketplace.rna_mint,
+        token::authority = buyer
+    )]
+    pub buyer_rna_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = marketplace.rna_mint,
+        token::authority = listing.seller
+    )]
+    pub seller_rna_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = marketplace.fee_account
+    )]
+    pub fee_account: Account<'info, TokenAccount>,
+
+    pub buyer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(
+        mut,
+        seeds = [b"listing", seller.key().as_ref(), listing.dna_mint.as_ref()],
+        bump = listing.bump,
+        has_one = seller @ ErrorCode::Unauthorized,
+        close = seller
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = listing.dna_mint,
+        token::authority = seller
+    )]
+    pub seller_dna_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// Events
+#[event]
+pub struct ListingCreatedEvent {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+    pub price_per_token: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingSoldEvent {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub genome_id: u64,
+    pub amount: u64,
+    pub total_price: u64,
+    pub fee: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingCancelledEvent {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub genome_id: u64,
+    pub returned: u64,
+    pub timestamp: i64,
+}
+
+// Errors
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Math overflow")]
+    Overflow,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Fee exceeds maximum")]
+    InvalidFee,
+    #[msg("Invalid token amount")]
+    InvalidAmount,
+    #[msg("Invalid price")]
+    InvalidPrice,
+    #[msg("Not enough tokens left in listing")]
+    InsufficientListing,
+    #[msg("Listing belongs to another marketplace")]
+    WrongMarketplace,
+}
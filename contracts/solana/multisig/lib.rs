@@ -0,0 +1,457 @@
+// RSM Multisig - M-of-N custody for ecosystem authorities
+// Holds authority/agi_controller roles via its signer PDA
+// Proposals carry a memo and execute after a timelock
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+
+declare_id!("MSGxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+
+pub const MAX_OWNERS: usize = 10;
+pub const MAX_MEMO_LEN: usize = 128;
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+pub const SIGNER_SEED: &[u8] = b"signer";
+pub const TRANSACTION_SEED: &[u8] = b"transaction";
+
+#[program]
+pub mod rsm_multisig {
+    use super::*;
+
+    // Create a multisig; `create_key` only seeds the PDA
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+        timelock_secs: i64,
+    ) -> Result<()> {
+        validate_owners(&owners, threshold)?;
+        require!(timelock_secs >= 0, ErrorCode::InvalidTimelock);
+
+        let multisig = &mut ctx.accounts.multisig;
+
+        multisig.create_key = ctx.accounts.create_key.key();
+        multisig.owners = owners;
+        multisig.threshold = threshold;
+        multisig.timelock_secs = timelock_secs;
+        multisig.owner_set_seqno = 0;
+        multisig.transaction_count = 0;
+        multisig.bump = ctx.bumps.multisig;
+        multisig.signer_bump = ctx.bumps.multisig_signer;
+
+        msg!(
+            "Multisig created: {} of {}, timelock {}s",
+            threshold,
+            multisig.owners.len(),
+            timelock_secs
+        );
+        Ok(())
+    }
+
+    // Propose an instruction for the signer PDA to execute; counts as the
+    // proposer's approval
+    pub fn propose_transaction(
+        ctx: Context<ProposeTransaction>,
+        program_id: Pubkey,
+        accounts: Vec<TransactionAccount>,
+        data: Vec<u8>,
+        memo: String,
+    ) -> Result<()> {
+        require!(memo.len() <= MAX_MEMO_LEN, ErrorCode::MemoTooLong);
+
+        let multisig = &mut ctx.accounts.multisig;
+        let owner_index = multisig
+            .owner_index(&ctx.accounts.proposer.key())
+            .ok_or(ErrorCode::NotAnOwner)?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.multisig = multisig.key();
+        transaction.index = multisig.transaction_count;
+        transaction.proposer = ctx.accounts.proposer.key();
+        transaction.program_id = program_id;
+        transaction.accounts = accounts;
+        transaction.data = data;
+        transaction.memo = memo;
+        transaction.signers = vec![false; multisig.owners.len()];
+        transaction.signers[owner_index] = true;
+        transaction.owner_set_seqno = multisig.owner_set_seqno;
+        transaction.approved_at = 0;
+        transaction.executed = false;
+        transaction.bump = ctx.bumps.transaction;
+        transaction.mark_if_approved(multisig.threshold, Clock::get()?.unix_timestamp);
+
+        multisig.transaction_count = multisig
+            .transaction_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(TransactionProposedEvent {
+            multisig: transaction.multisig,
+            transaction: transaction.key(),
+            index: transaction.index,
+            proposer: transaction.proposer,
+            program_id,
+            memo: transaction.memo.clone(),
+        });
+
+        msg!("Transaction {} proposed: {}", transaction.index, transaction.memo);
+        Ok(())
+    }
+
+    // Approve a pending transaction; the timelock starts once the
+    // threshold is reached
+    pub fn approve_transaction(ctx: Context<ApproveTransaction>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        require!(!transaction.executed, ErrorCode::AlreadyExecuted);
+        require!(
+            transaction.owner_set_seqno == multisig.owner_set_seqno,
+            ErrorCode::StaleOwnerSet
+        );
+
+        let owner_index = multisig
+            .owner_index(&ctx.accounts.owner.key())
+            .ok_or(ErrorCode::NotAnOwner)?;
+        transaction.signers[owner_index] = true;
+        transaction.mark_if_approved(multisig.threshold, Clock::get()?.unix_timestamp);
+
+        msg!(
+            "Transaction {} approved by {} ({} of {})",
+            transaction.index,
+            ctx.accounts.owner.key(),
+            transaction.approvals(),
+            multisig.threshold
+        );
+        Ok(())
+    }
+
+    // Execute an approved transaction once its timelock has passed.
+    // Remaining accounts are the instruction's accounts followed by its program
+    pub fn execute_transaction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteTransaction<'info>>,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        require!(!transaction.executed, ErrorCode::AlreadyExecuted);
+        require!(
+            transaction.owner_set_seqno == multisig.owner_set_seqno,
+            ErrorCode::StaleOwnerSet
+        );
+        require!(transaction.approved_at != 0, ErrorCode::NotEnoughApprovals);
+
+        let unlocks_at = transaction
+            .approved_at
+            .checked_add(multisig.timelock_secs)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            Clock::get()?.unix_timestamp >= unlocks_at,
+            ErrorCode::Timelocked
+        );
+
+        let signer_key = ctx.accounts.multisig_signer.key();
+        let instruction = Instruction {
+            program_id: transaction.program_id,
+            accounts: transaction
+                .accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.pubkey,
+                    is_signer: account.is_signer || account.pubkey == signer_key,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: transaction.data.clone(),
+        };
+        transaction.executed = true;
+
+        let multisig_key = multisig.key();
+        let bump = [multisig.signer_bump];
+        let seeds: &[&[u8]] = &[SIGNER_SEED, multisig_key.as_ref(), &bump];
+        invoke_signed(&instruction, ctx.remaining_accounts, &[seeds])?;
+
+        emit!(TransactionExecutedEvent {
+            multisig: multisig_key,
+            transaction: transaction.key(),
+            index: transaction.index,
+            executor: ctx.accounts.executor.key(),
+        });
+
+        msg!("Transaction {} executed", transaction.index);
+        Ok(())
+    }
+
+    // Withdraw an unexecuted proposal (proposer only)
+    pub fn cancel_transaction(ctx: Context<CancelTransaction>) -> Result<()> {
+        require!(!ctx.accounts.transaction.executed, ErrorCode::AlreadyExecuted);
+
+        msg!("Transaction {} cancelled", ctx.accounts.transaction.index);
+        Ok(())
+    }
+
+    // Replace owners and threshold; only callable by the multisig itself
+    // through an executed transaction. Invalidates pending proposals
+    pub fn set_owners(
+        ctx: Context<Auth>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        validate_owners(&owners, threshold)?;
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.owners = owners;
+        multisig.threshold = threshold;
+        multisig.owner_set_seqno = multisig
+            .owner_set_seqno
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
+        msg!("Owners updated: {} of {}", threshold, multisig.owners.len());
+        Ok(())
+    }
+
+    // Change the execution delay; only callable by the multisig itself
+    pub fn set_timelock(ctx: Context<Auth>, timelock_secs: i64) -> Result<()> {
+        require!(timelock_secs >= 0, ErrorCode::InvalidTimelock);
+
+        ctx.accounts.multisig.timelock_secs = timelock_secs;
+
+        msg!("Timelock set to {}s", timelock_secs);
+        Ok(())
+    }
+}
+
+fn validate_owners(owners: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !owners.is_empty() && owners.len() <= MAX_OWNERS,
+        ErrorCode::InvalidOwners
+    );
+    require!(
+        threshold > 0 && threshold as usize <= owners.len(),
+        ErrorCode::InvalidThreshold
+    );
+    for (i, owner) in owners.iter().enumerate() {
+        require!(!owners[..i].contains(owner), ErrorCode::InvalidOwners);
+    }
+    Ok(())
+}
+
+// Signer PDA that holds delegated authorities
+pub fn multisig_signer_address(multisig: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SIGNER_SEED, multisig.as_ref()], &crate::ID)
+}
+
+// Account structures
+#[account]
+pub struct Multisig {
+    pub create_key: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock_secs: i64,
+    pub owner_set_seqno: u32,
+    pub transaction_count: u64,
+    pub bump: u8,
+    pub signer_bump: u8,
+}
+
+impl Multisig {
+    pub const SPACE: usize = 32 + (4 + 32 * MAX_OWNERS) + 1 + 8 + 4 + 8 + 1 + 1;
+
+    pub fn owner_index(&self, owner: &Pubkey) -> Option<usize> {
+        self.owners.iter().position(|o| o == owner)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransactionAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[account]
+pub struct MultisigTransaction {
+    pub multisig: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<TransactionAccount>,
+    pub data: Vec<u8>,
+    pub memo: String,
+    pub signers: Vec<bool>,
+    pub owner_set_seqno: u32,
+    pub approved_at: i64, // 0 until the threshold is reached
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl MultisigTransaction {
+    pub fn space(accounts: usize, data: usize, memo: usize, owners: usize) -> usize {
+        32 + 8 + 32 + 32 + (4 + accounts * 34) + (4 + data) + (4 + memo) + (4 + owners) + 4 + 8 + 1 + 1
+    }
+
+    pub fn approvals(&self) -> usize {
+        self.signers.iter().filter(|signed| **signed).count()
+    }
+
+    fn mark_if_approved(&mut self, threshold: u8, now: i64) {
+        if self.approved_at == 0 && self.approvals() >= threshold as usize {
+            self.approved_at = now;
+        }
+    }
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Multisig::SPACE,
+        seeds = [MULTISIG_SEED, create_key.key().as_ref()],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: signer PDA, holds no data
+    #[account(
+        seeds = [SIGNER_SEED, multisig.key().as_ref()],
+        bump
+    )]
+    pub multisig_signer: UncheckedAccount<'info>,
+
+    pub create_key: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<TransactionAccount>, data: Vec<u8>, memo: String)]
+pub struct ProposeTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + MultisigTransaction::space(accounts.len(), data.len(), memo.len(), multisig.owners.len()),
+        seeds = [TRANSACTION_SEED, multisig.key().as_ref(), multisig.transaction_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, MultisigTransaction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveTransaction<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut, has_one = multisig)]
+    pub transaction: Account<'info, MultisigTransaction>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTransaction<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: signer PDA, holds no data
+    #[account(
+        seeds = [SIGNER_SEED, multisig.key().as_ref()],
+        bump = multisig.signer_bump
+    )]
+    pub multisig_signer: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = multisig)]
+    pub transaction: Account<'info, MultisigTransaction>,
+
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelTransaction<'info> {
+    #[account(mut, has_one = proposer, close = proposer)]
+    pub transaction: Account<'info, MultisigTransaction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Auth<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        seeds = [SIGNER_SEED, multisig.key().as_ref()],
+        bump = multisig.signer_bump
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+// Events
+#[event]
+pub struct TransactionProposedEvent {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    pub memo: String,
+}
+
+#[event]
+pub struct TransactionExecutedEvent {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub index: u64,
+    pub executor: Pubkey,
+}
+
+// Errors
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Owners must be 1-10 distinct keys")]
+    InvalidOwners,
+    #[msg("Threshold must be between 1 and the owner count")]
+    InvalidThreshold,
+    #[msg("Timelock cannot be negative")]
+    InvalidTimelock,
+    #[msg("Memo must be at most 128 bytes")]
+    MemoTooLong,
+    #[msg("Signer is not an owner")]
+    NotAnOwner,
+    #[msg("Transaction already executed")]
+    AlreadyExecuted,
+    #[msg("Owners changed since the transaction was proposed")]
+    StaleOwnerSet,
+    #[msg("Not enough approvals")]
+    NotEnoughApprovals,
+    #[msg("Transaction is still timelocked")]
+    Timelocked,
+    #[msg("Math overflow")]
+    Overflow,
+}
@@ -0,0 +1,181 @@
+// RSM Pause - Ecosystem-wide circuit breaker
+// One GlobalPause PDA read by dna-token, rna-token and genesis-engine
+// Guardians can pause; only the authority can unpause
+
+use anchor_lang::prelude::*;
+
+declare_id!("PSExxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+
+pub const GLOBAL_PAUSE_SEED: &[u8] = b"global_pause";
+pub const MAX_GUARDIANS: usize = 5;
+
+#[program]
+pub mod rsm_pause {
+    use super::*;
+
+    // Create the GlobalPause account; the signer becomes its authority
+    pub fn initialize(ctx: Context<Initialize>, guardians: Vec<Pubkey>) -> Result<()> {
+        require!(guardians.len() <= MAX_GUARDIANS, ErrorCode::TooManyGuardians);
+
+        let pause = &mut ctx.accounts.global_pause;
+        pause.authority = ctx.accounts.authority.key();
+        pause.guardians = guardians;
+        pause.paused = false;
+        pause.paused_by = Pubkey::default();
+        pause.paused_at = 0;
+        pause.reason_code = 0;
+        pause.bump = ctx.bumps.global_pause;
+
+        msg!("Global pause initialized with {} guardians", pause.guardians.len());
+        Ok(())
+    }
+
+    // Halt the ecosystem (authority or any guardian)
+    pub fn pause(ctx: Context<Toggle>, reason_code: u32) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let pause = &mut ctx.accounts.global_pause;
+        require!(
+            signer == pause.authority || pause.guardians.contains(&signer),
+            ErrorCode::Unauthorized
+        );
+        require!(!pause.paused, ErrorCode::AlreadyPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        pause.paused = true;
+        pause.paused_by = signer;
+        pause.paused_at = now;
+        pause.reason_code = reason_code;
+
+        emit!(PauseToggledEvent {
+            paused: true,
+            signer,
+            reason_code,
+            timestamp: now,
+        });
+
+        msg!("Ecosystem paused by {} (reason {})", signer, reason_code);
+        Ok(())
+    }
+
+    // Resume the ecosystem (authority only)
+    pub fn unpause(ctx: Context<Toggle>) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let pause = &mut ctx.accounts.global_pause;
+        require!(signer == pause.authority, ErrorCode::Unauthorized);
+        require!(pause.paused, ErrorCode::NotPaused);
+
+        let now = Clock::get()?.unix_timestamp;
+        pause.paused = false;
+
+        emit!(PauseToggledEvent {
+            paused: false,
+            signer,
+            reason_code: pause.reason_code,
+            timestamp: now,
+        });
+
+        msg!("Ecosystem unpaused after {}s", now - pause.paused_at);
+        Ok(())
+    }
+
+    // Replace the guardian set (authority only)
+    pub fn set_guardians(ctx: Context<Configure>, guardians: Vec<Pubkey>) -> Result<()> {
+        require!(guardians.len() <= MAX_GUARDIANS, ErrorCode::TooManyGuardians);
+
+        let pause = &mut ctx.accounts.global_pause;
+        pause.guardians = guardians;
+
+        msg!("Guardians updated: {}", pause.guardians.len());
+        Ok(())
+    }
+
+    // Hand the authority role to a new key, e.g. the multisig signer PDA
+    pub fn set_authority(ctx: Context<Configure>, new_authority: Pubkey) -> Result<()> {
+        let pause = &mut ctx.accounts.global_pause;
+        pause.authority = new_authority;
+
+        msg!("Pause authority set to {}", new_authority);
+        Ok(())
+    }
+}
+
+// Account structures
+#[account]
+pub struct GlobalPause {
+    pub authority: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub paused: bool,
+    pub paused_by: Pubkey,
+    pub paused_at: i64,
+    pub reason_code: u32,
+    pub bump: u8,
+}
+
+impl GlobalPause {
+    pub const LEN: usize = 32 + (4 + 32 * MAX_GUARDIANS) + 1 + 32 + 8 + 4 + 1;
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GlobalPause::LEN,
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Toggle<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Configure<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        has_one = authority
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+
+    pub authority: Signer<'info>,
+}
+
+// Events
+#[event]
+pub struct PauseToggledEvent {
+    pub paused: bool,
+    pub signer: Pubkey,
+    pub reason_code: u32,
+    pub timestamp: i64,
+}
+
+// Errors
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("At most 5 guardians")]
+    TooManyGuardians,
+    #[msg("Ecosystem is already paused")]
+    AlreadyPaused,
+    #[msg("Ecosystem is not paused")]
+    NotPaused,
+}
@@ -3,10 +3,18 @@
 // Burn to create new DNA genomes
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{self, Token, TokenAccount, Mint, MintTo, Burn};
+use genesis_engine::{lineage_address, Lineage, LineageEntry, GENESIS_GENOME_ID_OFFSET};
+use rsm_core::{Ancestor, MAX_LINEAGE_GENERATIONS, ROYALTY_DENOMINATOR};
+use rsm_dna_token::program::RsmDnaToken;
+use rsm_pause::{GlobalPause, GLOBAL_PAUSE_SEED};
 
 declare_id!("RNAxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
+pub const ANCESTOR_ROYALTY_SEED: &[u8] = b"ancestor_royalty";
+pub const BALANCE_SECS_PER_RNA: u128 = 86_400 * 100; // 1% per DNA-day
+
 #[program]
 pub mod agi_rna_token {
     use super::*;
@@ -22,6 +30,7 @@ pub mod agi_rna_token {
         holder.rna_generated = 0;
         holder.last_claim = Clock::get()?.unix_timestamp;
         holder.total_rna_claimed = 0;
+        holder.royalties_paid = 0;
         
         msg!("RNA holder initialized for {}", holder.owner);
         Ok(())
@@ -41,40 +50,105 @@ pub mod agi_rna_token {
             .checked_add(amount)
             .ok_or(ErrorCode::Overflow)?;
         
-        // Store genome holding
-        let holding = GenomeHolding {
-            genome_id,
-            amount,
-            consciousness,
-        };
-        
-        // Add to holdings vector (simplified - real impl would use HashMap)
-        msg!("Updated DNA holdings: genome {} amount {}", genome_id, amount);
+        msg!("Updated DNA holdings: genome {} amount {} consciousness {}", genome_id, amount, consciousness);
         
         Ok(())
     }
 
     // Claim accumulated RNA
-    pub fn claim_rna(
-        ctx: Context<ClaimRNA>,
+    // Each tracked dna-token hold is passed in remaining_accounts as
+    // (genome, holder_state, holding); claiming settles their balance
+    // checkpoints, so RNA follows the time-weighted average balance since
+    // the last claim rather than the balance at claim time
+    // A hold of a genesis-born genome is followed by the genome's Lineage
+    // and, per ancestor the royalty reaches, (ancestor genome, its founder's
+    // RNA account); their royalty comes out of that hold's RNA
+    pub fn claim_rna<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRNA<'info>>,
     ) -> Result<()> {
-        let holder = &mut ctx.accounts.holder;
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp;
         
         // Calculate time elapsed since last claim
-        let time_elapsed = current_time - holder.last_claim;
+        let time_elapsed = current_time - ctx.accounts.holder.last_claim;
         require!(time_elapsed > 0, ErrorCode::TooSoon);
         
-        // Calculate RNA generation
-        // Formula: DNA_amount * 0.01 * (1 + consciousness/100) * days
-        let days_elapsed = time_elapsed / 86400; // seconds to days
+        let royalty = &ctx.accounts.ancestor_royalty;
+        let mut accounts = ctx.remaining_accounts;
+        let mut balance_secs: u128 = 0;
+        let mut royalties = Vec::new();
+        while !accounts.is_empty() {
+            require!(accounts.len() >= 3, ErrorCode::InvalidHoldAccounts);
+            let (hold, rest) = accounts.split_at(3);
+            accounts = rest;
+            let settled = rsm_dna_token::cpi::settle_hold_balance(CpiContext::new(
+                ctx.accounts.dna_program.to_account_info(),
+                rsm_dna_token::cpi::accounts::SettleHoldBalance {
+                    genome: hold[0].clone(),
+                    holder_state: hold[1].clone(),
+                    holding: hold[2].clone(),
+                    holder: ctx.accounts.owner.to_account_info(),
+                },
+            ))?.get();
+            balance_secs = balance_secs.saturating_add(settled);
+            
+            let genome_id = AccountLoader::<rsm_dna_token::GenomeV2>::try_from(&hold[0])?.load()?.genome_id;
+            if genome_id < GENESIS_GENOME_ID_OFFSET {
+                continue;
+            }
+            let (lineage, rest) = accounts.split_first().ok_or(ErrorCode::InvalidRoyaltyAccounts)?;
+            accounts = rest;
+            let Some(ancestors) = hold_lineage(lineage, &royalty.genesis_engine, genome_id)? else {
+                continue;
+            };
+            let hold_rna = u64::try_from(settled / BALANCE_SECS_PER_RNA).map_err(|_| ErrorCode::Overflow)?;
+            let owed = rsm_core::ancestor_royalties(hold_rna, &ancestors, royalty.bps_per_generation, royalty.generations);
+            require!(accounts.len() >= owed.len() * 2, ErrorCode::InvalidRoyaltyAccounts);
+            let (pairs, rest) = accounts.split_at(owed.len() * 2);
+            accounts = rest;
+            for ((ancestor, amount), pair) in owed.into_iter().zip(pairs.chunks_exact(2)) {
+                let founder = ancestor_founder(&pair[0], ancestor.genome_id)?;
+                let founder_rna: Account<TokenAccount> = Account::try_from(&pair[1])?;
+                require!(
+                    founder_rna.mint == ctx.accounts.rna_mint.key() && founder_rna.owner == founder,
+                    ErrorCode::InvalidRoyaltyAccounts
+                );
+                royalties.push((genome_id, ancestor, founder, pair[1].clone(), amount));
+            }
+        }
         
-        // Base rate: 1% per day
-        let base_rna = (holder.total_dna_held * days_elapsed as u64) / 100;
+        // Calculate RNA generation
+        // Formula: average_DNA * 0.01 * days, i.e. 1% per DNA-day held
+        let rna_generated = u64::try_from(balance_secs / BALANCE_SECS_PER_RNA).map_err(|_| ErrorCode::Overflow)?;
+        let royalties_paid = royalties.iter().map(|(_, _, _, _, amount)| *amount).sum::<u64>();
+        // Each hold's RNA rounds down on its own, so royalties never exceed the total
+        let rna_to_mint = rna_generated
+            .checked_sub(royalties_paid)
+            .ok_or(ErrorCode::Overflow)?;
         
-        // Apply consciousness multiplier (simplified)
-        let rna_to_mint = base_rna;
+        for (genome_id, ancestor, founder, founder_rna, amount) in royalties {
+            if amount == 0 {
+                continue;
+            }
+            token::mint_to(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.rna_mint.to_account_info(),
+                        to: founder_rna,
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            emit!(AncestorRoyaltyPaidEvent {
+                genome_id,
+                ancestor_id: ancestor.genome_id,
+                generation: ancestor.generation,
+                founder,
+                amount,
+            });
+        }
         
         // Mint RNA tokens
         token::mint_to(
@@ -90,15 +164,46 @@ pub mod agi_rna_token {
         )?;
         
         // Update holder state
+        let holder = &mut ctx.accounts.holder;
         holder.rna_generated = holder.rna_generated
-            .checked_add(rna_to_mint)
+            .checked_add(rna_generated)
             .ok_or(ErrorCode::Overflow)?;
         holder.total_rna_claimed = holder.total_rna_claimed
             .checked_add(rna_to_mint)
             .ok_or(ErrorCode::Overflow)?;
+        holder.royalties_paid = holder.royalties_paid
+            .checked_add(royalties_paid)
+            .ok_or(ErrorCode::Overflow)?;
         holder.last_claim = current_time;
         
-        msg!("Claimed {} RNA tokens", rna_to_mint);
+        msg!("Claimed {} RNA tokens, {} paid to ancestors", rna_to_mint, royalties_paid);
+        Ok(())
+    }
+
+    // Set the royalty genesis-born genomes' RNA pays their ancestors'
+    // founders: bps_per_generation of each hold's RNA per generation, up to
+    // `generations` back, split evenly within a generation. Signed by the
+    // RNA mint authority
+    pub fn set_ancestor_royalty(
+        ctx: Context<SetAncestorRoyalty>,
+        bps_per_generation: u16,
+        generations: u8,
+        genesis_engine: Pubkey,
+    ) -> Result<()> {
+        require!(
+            generations <= MAX_LINEAGE_GENERATIONS
+                && bps_per_generation as u64 * generations as u64 <= ROYALTY_DENOMINATOR,
+            ErrorCode::InvalidRoyalty
+        );
+        
+        let royalty = &mut ctx.accounts.ancestor_royalty;
+        royalty.rna_mint = ctx.accounts.rna_mint.key();
+        royalty.bps_per_generation = bps_per_generation;
+        royalty.generations = generations;
+        royalty.genesis_engine = genesis_engine;
+        royalty.bump = ctx.bumps.ancestor_royalty;
+        
+        msg!("Ancestor royalty set to {} bps over {} generations", bps_per_generation, generations);
         Ok(())
     }
 
@@ -140,27 +245,64 @@ pub mod agi_rna_token {
         let holder = &ctx.accounts.holder;
         
         // Calculate daily RNA generation
-        let daily_rate = (holder.total_dna_held * 1) / 100; // 1% base rate
+        let daily_rate = holder.total_dna_held / 100; // 1% base rate
         
         Ok(daily_rate)
     }
 }
 
+// A hold's ancestors, if `engine` kept a Lineage for its genome; one bred
+// before lineages were kept passes the empty lineage address
+fn hold_lineage<'info>(info: &'info AccountInfo<'info>, engine: &Pubkey, genome_id: u64) -> Result<Option<Vec<Ancestor>>> {
+    if info.data_is_empty() {
+        require_keys_eq!(info.key(), lineage_address(engine, genome_id), ErrorCode::InvalidRoyaltyAccounts);
+        return Ok(None);
+    }
+    let lineage: Account<Lineage> = Account::try_from(info)?;
+    require!(
+        lineage.genome_id == genome_id && lineage.is_of(info.key, engine),
+        ErrorCode::InvalidRoyaltyAccounts
+    );
+    Ok(Some(lineage.ancestors.iter().map(LineageEntry::to_core).collect()))
+}
+
+// The founder of ancestor `genome_id`; a genesis-born ancestor must be its
+// dna-token PDA, while founder genomes are matched by id as at genesis
+fn ancestor_founder<'info>(info: &'info AccountInfo<'info>, genome_id: u64) -> Result<Pubkey> {
+    let genome = AccountLoader::<rsm_dna_token::GenomeV2>::try_from(info)?;
+    let genome = genome.load()?;
+    require!(genome.genome_id == genome_id, ErrorCode::InvalidRoyaltyAccounts);
+    if genome_id >= GENESIS_GENOME_ID_OFFSET {
+        let (address, _) = Pubkey::find_program_address(&[b"genome", &genome_id.to_le_bytes()], &rsm_dna_token::ID);
+        require_keys_eq!(info.key(), address, ErrorCode::InvalidRoyaltyAccounts);
+    }
+    Ok(genome.founder)
+}
+
 // Account structures
 #[account]
 pub struct RNAHolder {
     pub owner: Pubkey,
     pub total_dna_held: u64,
-    pub rna_generated: u64,
+    pub rna_generated: u64, // before ancestor royalties
     pub last_claim: i64,
     pub total_rna_claimed: u64,
+    pub royalties_paid: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct GenomeHolding {
-    pub genome_id: u64,
-    pub amount: u64,
-    pub consciousness: u8,
+// Ancestor royalty on genesis-born genomes' RNA, at
+// ["ancestor_royalty", rna_mint]
+#[account]
+pub struct AncestorRoyalty {
+    pub rna_mint: Pubkey,
+    pub bps_per_generation: u16,
+    pub generations: u8,
+    pub genesis_engine: Pubkey, // whose lineages royalties follow
+    pub bump: u8,
+}
+
+impl AncestorRoyalty {
+    pub const SPACE: usize = 8 + 32 + 2 + 1 + 32 + 1;
 }
 
 // Context structures
@@ -183,13 +325,25 @@ pub struct InitializeHolder<'info> {
 pub struct UpdateDNAHoldings<'info> {
     #[account(mut)]
     pub holder: Account<'info, RNAHolder>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
 }
 
 #[derive(Accounts)]
 pub struct ClaimRNA<'info> {
-    #[account(mut)]
+    #[account(mut, has_one = owner)]
     pub holder: Account<'info, RNAHolder>,
     
+    // Signs the settlement of each of its holds
+    pub owner: Signer<'info>,
+    pub dna_program: Program<'info, RsmDnaToken>,
+    
     #[account(mut)]
     pub rna_mint: Account<'info, Mint>,
     
@@ -198,6 +352,42 @@ pub struct ClaimRNA<'info> {
     
     pub authority: Signer<'info>,
     pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    
+    #[account(
+        seeds = [ANCESTOR_ROYALTY_SEED, rna_mint.key().as_ref()],
+        bump = ancestor_royalty.bump
+    )]
+    pub ancestor_royalty: Account<'info, AncestorRoyalty>,
+}
+
+#[derive(Accounts)]
+pub struct SetAncestorRoyalty<'info> {
+    #[account(
+        constraint = rna_mint.mint_authority == COption::Some(authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub rna_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AncestorRoyalty::SPACE,
+        seeds = [ANCESTOR_ROYALTY_SEED, rna_mint.key().as_ref()],
+        bump
+    )]
+    pub ancestor_royalty: Account<'info, AncestorRoyalty>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -210,6 +400,14 @@ pub struct GenesisBurn<'info> {
     
     pub authority: Signer<'info>,
     pub token_program: Program<'info, Token>,
+    
+    #[account(
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump = global_pause.bump,
+        seeds::program = rsm_pause::ID,
+        constraint = !global_pause.paused @ ErrorCode::EcosystemPaused
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
 }
 
 #[derive(Accounts)]
@@ -225,6 +423,15 @@ pub struct GenesisEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AncestorRoyaltyPaidEvent {
+    pub genome_id: u64,
+    pub ancestor_id: u64,
+    pub generation: u8,
+    pub founder: Pubkey,
+    pub amount: u64,
+}
+
 // Errors
 #[error_code]
 pub enum ErrorCode {
@@ -234,4 +441,14 @@ pub enum ErrorCode {
     TooSoon,
     #[msg("Need 1000 RNA minimum for genesis")]
     InsufficientRNA,
+    #[msg("Ecosystem is paused")]
+    EcosystemPaused,
+    #[msg("Holds must be passed as (genome, holder_state, holding) triples")]
+    InvalidHoldAccounts,
+    #[msg("Signer is not the RNA mint authority")]
+    Unauthorized,
+    #[msg("Royalty exceeds 10000 bps or reaches past the lineage")]
+    InvalidRoyalty,
+    #[msg("A genesis-born hold must be followed by its Lineage and each owed ancestor's genome and founder RNA account")]
+    InvalidRoyaltyAccounts,
 }
@@ -0,0 +1,271 @@
+// RSM Token (Anchor) - Anchor rewrite of the native rsm-token program
+// Legacy TokenConfig and GenomeData accounts are verified against the native
+// program and recreated under this program's PDAs; minting stays on the
+// native program until its config hands over the mint authority
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_interface::Mint;
+use rsm_token::{config_address, genome_address, GenomeData, TokenConfig};
+
+declare_id!("RSMxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+
+pub const CONFIG_SEED: &[u8] = b"rsm_config";
+pub const GENOME_SEED: &[u8] = b"rsm_genome";
+
+#[program]
+pub mod rsm_token_anchor {
+    use super::*;
+
+    /// Recreate a native TokenConfig at ["rsm_config", mint]. The legacy
+    /// config must be the native program's config PDA for `mint` and the
+    /// mint's authority, and its authority must sign
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let legacy_program = ctx.accounts.legacy_program.key();
+        let legacy_config = &ctx.accounts.legacy_config;
+        let mint = ctx.accounts.mint.key();
+        require_keys_eq!(*legacy_config.owner, legacy_program, ErrorCode::NotLegacyAccount);
+        require_keys_eq!(legacy_config.key(), config_address(&legacy_program, &mint).0, ErrorCode::NotLegacyAccount);
+        require!(
+            ctx.accounts.mint.mint_authority == COption::Some(legacy_config.key()),
+            ErrorCode::NotMintAuthority
+        );
+
+        let legacy = decode_legacy_config(&legacy_config.try_borrow_data()?)?;
+        require_keys_eq!(legacy.mint, mint, ErrorCode::NotLegacyAccount);
+        require_keys_eq!(legacy.authority, ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+
+        let now = Clock::get()?;
+        let config = &mut ctx.accounts.config;
+        config.authority = legacy.authority;
+        config.agi_controller = legacy.agi_controller;
+        config.score_oracle = legacy.score_oracle;
+        config.mint = mint;
+        config.decimals = legacy.decimals;
+        config.max_supply = legacy.max_supply;
+        config.total_minted = legacy.total_minted;
+        config.legacy_program = legacy_program;
+        config.legacy_config = legacy_config.key();
+        config.migrated_genomes = 0;
+        config.migrated_tokens = 0;
+        config.migrated_slot = now.slot;
+        config.bump = ctx.bumps.config;
+
+        emit!(ConfigMigratedEvent {
+            config: config.key(),
+            legacy_config: legacy_config.key(),
+            mint,
+            total_minted: legacy.total_minted,
+            slot: now.slot,
+        });
+
+        msg!("Migrated config for mint {} ({} minted)", mint, legacy.total_minted);
+        Ok(())
+    }
+
+    /// Recreate a minted native genome at ["rsm_genome", config, hash]; its
+    /// owner signs as proof of ownership. Each genome migrates once, and not
+    /// while a clawback is pending on it
+    pub fn migrate_genome(ctx: Context<MigrateGenome>, genome_hash: [u8; 32]) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let legacy_genome = &ctx.accounts.legacy_genome;
+        require_keys_eq!(*legacy_genome.owner, config.legacy_program, ErrorCode::NotLegacyAccount);
+        require_keys_eq!(
+            legacy_genome.key(),
+            genome_address(&config.legacy_program, &config.legacy_config, &genome_hash).0,
+            ErrorCode::NotLegacyAccount
+        );
+
+        let legacy = decode_legacy_genome(&legacy_genome.try_borrow_data()?)?;
+        require!(legacy.hash == genome_hash, ErrorCode::NotLegacyAccount);
+        require!(legacy.is_minted, ErrorCode::GenomeNotMinted);
+        require!(legacy.pending_clawback == 0, ErrorCode::ClawbackPending);
+        require_keys_eq!(legacy.owner, ctx.accounts.owner.key(), ErrorCode::Unauthorized);
+
+        let slot = Clock::get()?.slot;
+        let genome = &mut ctx.accounts.genome;
+        genome.config = config.key();
+        genome.hash = genome_hash;
+        genome.owner = legacy.owner;
+        genome.tokens_minted = legacy.tokens_minted;
+        genome.complexity = legacy.complexity;
+        genome.uniqueness = legacy.uniqueness;
+        genome.entropy = legacy.entropy;
+        genome.blockchain_entropy = legacy.blockchain_entropy;
+        genome.mint_slot = legacy.mint_slot;
+        genome.mint_timestamp = legacy.mint_timestamp;
+        genome.scored_at = legacy.scored_at;
+        genome.bonded_amount = legacy.bonded_amount;
+        genome.emission_scale_bps = legacy.emission_scale_bps;
+        genome.registrant = legacy.registrant;
+        genome.legacy_genome = legacy_genome.key();
+        genome.migrated_slot = slot;
+        genome.bump = ctx.bumps.genome;
+
+        config.migrated_genomes += 1;
+        config.migrated_tokens = config.migrated_tokens.saturating_add(legacy.tokens_minted);
+
+        emit!(GenomeMigratedEvent {
+            genome: genome.key(),
+            legacy_genome: legacy_genome.key(),
+            genome_hash,
+            owner: legacy.owner,
+            tokens_minted: legacy.tokens_minted,
+            slot,
+        });
+
+        msg!("Migrated genome {} ({} tokens)", genome.key(), legacy.tokens_minted);
+        Ok(())
+    }
+}
+
+// Legacy accounts at an older schema are upgraded in memory before decoding
+fn decode_legacy_config(data: &[u8]) -> Result<TokenConfig> {
+    let migrated = TokenConfig::SCHEMA.migrate(data).map_err(|_| error!(ErrorCode::InvalidLegacyAccount))?;
+    TokenConfig::try_from_slice(migrated.as_deref().unwrap_or(data)).map_err(|_| error!(ErrorCode::InvalidLegacyAccount))
+}
+
+fn decode_legacy_genome(data: &[u8]) -> Result<GenomeData> {
+    let migrated = GenomeData::SCHEMA.migrate(data).map_err(|_| error!(ErrorCode::InvalidLegacyAccount))?;
+    GenomeData::try_from_slice(migrated.as_deref().unwrap_or(data)).map_err(|_| error!(ErrorCode::InvalidLegacyAccount))
+}
+
+// Account structures
+/// A migrated native TokenConfig, at ["rsm_config", mint]
+#[account]
+pub struct Config {
+    pub authority: Pubkey,
+    pub agi_controller: Pubkey,
+    pub score_oracle: Pubkey,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub max_supply: u64,
+    /// As recorded by the native program when the config migrated
+    pub total_minted: u64,
+    pub legacy_program: Pubkey,
+    pub legacy_config: Pubkey,
+    pub migrated_genomes: u64,
+    pub migrated_tokens: u64,
+    pub migrated_slot: u64,
+    pub bump: u8,
+}
+
+impl Config {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 32 + 32 + 8 + 8 + 8 + 1;
+}
+
+/// A migrated native genome, at ["rsm_genome", config, hash]. The owner's
+/// note is not carried over
+#[account]
+pub struct Genome {
+    pub config: Pubkey,
+    pub hash: [u8; 32],
+    pub owner: Pubkey,
+    pub tokens_minted: u64,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub entropy: u16,
+    pub blockchain_entropy: u8,
+    pub mint_slot: u64,
+    pub mint_timestamp: i64,
+    pub scored_at: i64,
+    pub bonded_amount: u64,
+    pub emission_scale_bps: u16,
+    /// Pubkey::default() unless the genome was pre-registered
+    pub registrant: Pubkey,
+    pub legacy_genome: Pubkey,
+    pub migrated_slot: u64,
+    pub bump: u8,
+}
+
+impl Genome {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 1 + 1 + 2 + 1 + 8 + 8 + 8 + 8 + 2 + 32 + 32 + 8 + 1;
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Config::SPACE,
+        seeds = [CONFIG_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: native TokenConfig, verified and decoded in migrate_config
+    pub legacy_config: UncheckedAccount<'info>,
+
+    /// CHECK: the native rsm-token program owning legacy_config
+    #[account(executable)]
+    pub legacy_program: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(genome_hash: [u8; 32])]
+pub struct MigrateGenome<'info> {
+    #[account(mut, seeds = [CONFIG_SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Genome::SPACE,
+        seeds = [GENOME_SEED, config.key().as_ref(), genome_hash.as_ref()],
+        bump
+    )]
+    pub genome: Account<'info, Genome>,
+
+    /// CHECK: native GenomeData, verified and decoded in migrate_genome
+    pub legacy_genome: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Events
+#[event]
+pub struct ConfigMigratedEvent {
+    pub config: Pubkey,
+    pub legacy_config: Pubkey,
+    pub mint: Pubkey,
+    pub total_minted: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GenomeMigratedEvent {
+    pub genome: Pubkey,
+    pub legacy_genome: Pubkey,
+    pub genome_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub tokens_minted: u64,
+    pub slot: u64,
+}
+
+// Errors
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Account is not the native program's account at the expected address")]
+    NotLegacyAccount,
+    #[msg("Native account could not be decoded")]
+    InvalidLegacyAccount,
+    #[msg("Legacy config is not the mint's authority")]
+    NotMintAuthority,
+    #[msg("Genome has not been minted")]
+    GenomeNotMinted,
+    #[msg("Genome has a pending clawback")]
+    ClawbackPending,
+}
@@ -0,0 +1,465 @@
+// RSM Treasury - Protocol fee sink
+// Collects RNA fees, burns a share every epoch
+// Remainder routed to stakers
+// Balance above a threshold swept to cold storage
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, Burn};
+
+declare_id!("TRSxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const SWEEP_SEED: &[u8] = b"sweep";
+
+#[program]
+pub mod rsm_treasury {
+    use super::*;
+
+    // Initialize treasury for the RNA mint
+    pub fn initialize_treasury(
+        ctx: Context<InitializeTreasury>,
+        burn_bps: u16, // e.g. 5000 = burn half
+    ) -> Result<()> {
+        require!(burn_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidSplit);
+
+        let treasury = &mut ctx.accounts.treasury;
+
+        treasury.governance = ctx.accounts.governance.key();
+        treasury.rna_mint = ctx.accounts.rna_mint.key();
+        treasury.fee_vault = ctx.accounts.fee_vault.key();
+        treasury.staker_reward_account = ctx.accounts.staker_reward_account.key();
+        treasury.burn_bps = burn_bps;
+        treasury.last_burn_epoch = 0;
+        treasury.total_fees_received = 0;
+        treasury.total_burned = 0;
+        treasury.total_to_stakers = 0;
+        treasury.bump = ctx.bumps.treasury;
+
+        msg!("Treasury initialized, burn share: {} bps", burn_bps);
+        Ok(())
+    }
+
+    // Update burn/staker split (governance only)
+    pub fn update_split(
+        ctx: Context<UpdateSplit>,
+        burn_bps: u16,
+    ) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(
+            ctx.accounts.governance.key() == treasury.governance,
+            ErrorCode::Unauthorized
+        );
+        require!(burn_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidSplit);
+
+        let old_bps = treasury.burn_bps;
+        treasury.burn_bps = burn_bps;
+        treasury.staker_reward_account = ctx.accounts.staker_reward_account.key();
+
+        msg!("Treasury split updated: {} -> {} bps burned", old_bps, burn_bps);
+        Ok(())
+    }
+
+    // Deposit protocol fee RNA into the vault
+    pub fn deposit_fees(
+        ctx: Context<DepositFees>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor_rna_account.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.depositor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_fees_received = treasury.total_fees_received
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        msg!("Deposited {} RNA in fees", amount);
+        Ok(())
+    }
+
+    // Burn the configured share of the vault and route the rest to stakers
+    // Permissionless, at most once per epoch
+    pub fn buyback_and_burn(
+        ctx: Context<BuybackAndBurn>,
+    ) -> Result<()> {
+        let epoch = Clock::get()?.epoch;
+        let treasury = &ctx.accounts.treasury;
+
+        require!(epoch > treasury.last_burn_epoch, ErrorCode::AlreadyBurnedThisEpoch);
+
+        let balance = ctx.accounts.fee_vault.amount;
+        require!(balance > 0, ErrorCode::NothingToBurn);
+
+        let (burn_amount, staker_amount) = split_amount(balance, treasury.burn_bps)?;
+
+        let rna_mint = treasury.rna_mint;
+        let bump = [treasury.bump];
+        let seeds: &[&[u8]] = &[b"treasury", rna_mint.as_ref(), &bump];
+
+        if burn_amount > 0 {
+            token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.rna_mint.to_account_info(),
+                        from: ctx.accounts.fee_vault.to_account_info(),
+                        authority: ctx.accounts.treasury.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                burn_amount,
+            )?;
+        }
+
+        if staker_amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.fee_vault.to_account_info(),
+                        to: ctx.accounts.staker_reward_account.to_account_info(),
+                        authority: ctx.accounts.treasury.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                staker_amount,
+            )?;
+        }
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.last_burn_epoch = epoch;
+        treasury.total_burned = treasury.total_burned
+            .checked_add(burn_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        treasury.total_to_stakers = treasury.total_to_stakers
+            .checked_add(staker_amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(BuybackBurnEvent {
+            epoch,
+            burned: burn_amount,
+            to_stakers: staker_amount,
+            caller: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Epoch {}: burned {} RNA, {} RNA to stakers", epoch, burn_amount, staker_amount);
+        Ok(())
+    }
+
+    // Set the cold-storage token account, the vault balance kept hot and
+    // the most swept per epoch (governance only)
+    pub fn configure_sweep(
+        ctx: Context<ConfigureSweep>,
+        threshold: u64,
+        epoch_limit: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.governance.key() == ctx.accounts.treasury.governance,
+            ErrorCode::Unauthorized
+        );
+        require!(epoch_limit > 0, ErrorCode::InvalidAmount);
+
+        let sweep = &mut ctx.accounts.sweep;
+        sweep.treasury = ctx.accounts.treasury.key();
+        sweep.cold_storage = ctx.accounts.cold_storage.key();
+        sweep.threshold = threshold;
+        sweep.epoch_limit = epoch_limit;
+        sweep.bump = ctx.bumps.sweep;
+
+        msg!("Sweep above {} RNA to {}, at most {} per epoch", threshold, sweep.cold_storage, epoch_limit);
+        Ok(())
+    }
+
+    // Move the vault balance above the threshold to cold storage, within
+    // the epoch's remaining limit. Permissionless
+    pub fn sweep_to_cold_storage(
+        ctx: Context<SweepToColdStorage>,
+    ) -> Result<()> {
+        let epoch = Clock::get()?.epoch;
+        let sweep = &ctx.accounts.sweep;
+
+        let excess = ctx.accounts.fee_vault.amount.saturating_sub(sweep.threshold);
+        require!(excess > 0, ErrorCode::NothingToSweep);
+
+        let swept_in_epoch = if sweep.epoch == epoch { sweep.swept_in_epoch } else { 0 };
+        let amount = excess.min(sweep.epoch_limit.saturating_sub(swept_in_epoch));
+        require!(amount > 0, ErrorCode::SweepLimitReached);
+
+        let treasury = &ctx.accounts.treasury;
+        let rna_mint = treasury.rna_mint;
+        let bump = [treasury.bump];
+        let seeds: &[&[u8]] = &[b"treasury", rna_mint.as_ref(), &bump];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    to: ctx.accounts.cold_storage.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        let sweep = &mut ctx.accounts.sweep;
+        sweep.epoch = epoch;
+        sweep.swept_in_epoch = swept_in_epoch + amount;
+        sweep.total_swept = sweep.total_swept
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(ColdStorageSweepEvent {
+            epoch,
+            amount,
+            cold_storage: sweep.cold_storage,
+            caller: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Epoch {}: swept {} RNA to cold storage", epoch, amount);
+        Ok(())
+    }
+}
+
+// Split a vault balance into (burn, stakers)
+pub fn split_amount(balance: u64, burn_bps: u16) -> Result<(u64, u64)> {
+    let burn = (balance as u128)
+        .checked_mul(burn_bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / BPS_DENOMINATOR as u128;
+    let burn = burn as u64;
+    Ok((burn, balance - burn))
+}
+
+// Account structures
+#[account]
+pub struct Treasury {
+    pub governance: Pubkey,
+    pub rna_mint: Pubkey,
+    pub fee_vault: Pubkey,
+    pub staker_reward_account: Pubkey,
+    pub burn_bps: u16,
+    pub last_burn_epoch: u64,
+    pub total_fees_received: u64,
+    pub total_burned: u64,
+    pub total_to_stakers: u64,
+    pub bump: u8,
+}
+
+// Cold-storage sweep settings and per-epoch tally at ["sweep", treasury]
+#[account]
+pub struct SweepConfig {
+    pub treasury: Pubkey,
+    pub cold_storage: Pubkey,
+    pub threshold: u64, // vault balance left hot
+    pub epoch_limit: u64,
+    pub epoch: u64, // epoch of the last sweep
+    pub swept_in_epoch: u64,
+    pub total_swept: u64,
+    pub bump: u8,
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        init,
+        payer = governance,
+        space = 8 + 200,
+        seeds = [b"treasury", rna_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init,
+        payer = governance,
+        seeds = [b"fee_vault", treasury.key().as_ref()],
+        bump,
+        token::mint = rna_mint,
+        token::authority = treasury
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    pub rna_mint: Account<'info, Mint>,
+
+    #[account(token::mint = rna_mint)]
+    pub staker_reward_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSplit<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(token::mint = treasury.rna_mint)]
+    pub staker_reward_account: Account<'info, TokenAccount>,
+
+    pub governance: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositFees<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        address = treasury.fee_vault
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = treasury.rna_mint,
+        token::authority = depositor
+    )]
+    pub depositor_rna_account: Account<'info, TokenAccount>,
+
+    pub depositor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct BuybackAndBurn<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.rna_mint.as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        address = treasury.fee_vault
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = treasury.rna_mint
+    )]
+    pub rna_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = treasury.staker_reward_account
+    )]
+    pub staker_reward_account: Account<'info, TokenAccount>,
+
+    pub caller: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureSweep<'info> {
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init_if_needed,
+        payer = governance,
+        space = 8 + 120,
+        seeds = [SWEEP_SEED, treasury.key().as_ref()],
+        bump
+    )]
+    pub sweep: Account<'info, SweepConfig>,
+
+    #[account(token::mint = treasury.rna_mint)]
+    pub cold_storage: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub governance: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepToColdStorage<'info> {
+    #[account(
+        seeds = [b"treasury", treasury.rna_mint.as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [SWEEP_SEED, treasury.key().as_ref()],
+        bump = sweep.bump
+    )]
+    pub sweep: Account<'info, SweepConfig>,
+
+    #[account(
+        mut,
+        address = treasury.fee_vault
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = sweep.cold_storage
+    )]
+    pub cold_storage: Account<'info, TokenAccount>,
+
+    pub caller: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Events
+#[event]
+pub struct BuybackBurnEvent {
+    pub epoch: u64,
+    pub burned: u64,
+    pub to_stakers: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ColdStorageSweepEvent {
+    pub epoch: u64,
+    pub amount: u64,
+    pub cold_storage: Pubkey,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+// Errors
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Math overflow")]
+    Overflow,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Burn share must be at most 10000 bps")]
+    InvalidSplit,
+    #[msg("Invalid token amount")]
+    InvalidAmount,
+    #[msg("Buyback already executed this epoch")]
+    AlreadyBurnedThisEpoch,
+    #[msg("Fee vault is empty")]
+    NothingToBurn,
+    #[msg("Fee vault is at or below the sweep threshold")]
+    NothingToSweep,
+    #[msg("Sweep limit for this epoch reached")]
+    SweepLimitReached,
+}
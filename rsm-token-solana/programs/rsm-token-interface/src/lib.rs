@@ -0,0 +1,844 @@
+//! CPI interface for rsm-token.
+//!
+//! Other on-chain programs (genesis-engine, third-party integrations) build
+//! rsm-token instructions from typed account lists instead of hand-encoding
+//! bytes. Every account list is generic over its key type: `Pubkey` for
+//! instruction builders, `AccountInfo` for the `cpi` helpers, which invoke
+//! rsm-token with optional PDA signer seeds.
+//!
+//! Admin instructions are signed by keypairs or the multisig and are not
+//! covered here; build those from `rsm_token::RSMInstruction` directly.
+
+pub use rsm_token::{
+    auto_stake_address, rescore_evidence_message, stake_authority_address, AccountKind, AutoStakeOptIn, BurnApproval,
+    DisputeRecord, GenomeData, GenomeOrigin, MintArgs, MintDelegate, RSMError, RSMInstruction, StreamVault, TokenConfig,
+    AUTO_STAKE_SEED, BURN_APPROVAL_SEED, CONFIG_SEED, DEDUPE_SEED, DISPUTE_SEED, GENOME_SEED, MAX_NOTE_LEN,
+    MINT_DELEGATE_SEED, STAKE_AUTHORITY_SEED, STREAM_SEED,
+};
+
+use solana_program::{account_info::AccountInfo, instruction::AccountMeta, pubkey::Pubkey};
+
+/// Anything that can stand in for an account in an account list.
+pub trait AccountKey {
+    fn account_key(&self) -> Pubkey;
+}
+
+impl AccountKey for Pubkey {
+    fn account_key(&self) -> Pubkey {
+        *self
+    }
+}
+
+impl AccountKey for AccountInfo<'_> {
+    fn account_key(&self) -> Pubkey {
+        *self.key
+    }
+}
+
+macro_rules! meta {
+    (writable, $key:expr) => {
+        AccountMeta::new($key, false)
+    };
+    (readonly, $key:expr) => {
+        AccountMeta::new_readonly($key, false)
+    };
+    (signer, $key:expr) => {
+        AccountMeta::new_readonly($key, true)
+    };
+    (writable_signer, $key:expr) => {
+        AccountMeta::new($key, true)
+    };
+}
+
+/// Declares an account list in rsm-token's positional order. Optional
+/// accounts trail the required ones and are only passed when present.
+macro_rules! accounts {
+    (
+        $(#[$doc:meta])*
+        $name:ident {
+            $($field:ident: $kind:ident),* $(,)?
+        }
+        $(optional {
+            $($opt:ident: $opt_kind:ident),* $(,)?
+        })?
+    ) => {
+        $(#[$doc])*
+        #[derive(Clone, Debug)]
+        pub struct $name<A> {
+            $(pub $field: A,)*
+            $($(pub $opt: Option<A>,)*)?
+        }
+
+        impl<A: AccountKey> $name<A> {
+            pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+                #[allow(unused_mut)]
+                let mut metas = vec![$(meta!($kind, self.$field.account_key())),*];
+                $($(
+                    if let Some(account) = &self.$opt {
+                        metas.push(meta!($opt_kind, account.account_key()));
+                    }
+                )*)?
+                metas
+            }
+        }
+
+        impl<'info> $name<AccountInfo<'info>> {
+            pub fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+                #[allow(unused_mut)]
+                let mut infos = vec![$(self.$field.clone()),*];
+                $($(
+                    if let Some(account) = &self.$opt {
+                        infos.push(account.clone());
+                    }
+                )*)?
+                infos
+            }
+        }
+    };
+}
+
+pub mod accounts {
+    use super::*;
+
+    accounts!(
+        CreateGenomeAccount {
+            config: writable,
+            genome: writable,
+            payer: writable_signer,
+            system_program: readonly,
+        }
+    );
+
+    accounts!(
+        /// `registrant` pays the rent and is the only recipient the
+        /// genome's mint will accept; `controller` is the config's
+        /// agi_controller or authority, vouching for the claim.
+        PreRegisterGenome {
+            config: writable,
+            genome: writable,
+            registrant: writable_signer,
+            system_program: readonly,
+            controller: signer,
+        }
+    );
+
+    accounts!(
+        /// `delegate` is the MintDelegate record when `controller` is a
+        /// delegate; `score` is the oracle's CanonicalScore when the config
+        /// has a score oracle; `stream`, `payer` and `system_program` create
+        /// the StreamVault when the config streams mints. `species` is the
+        /// SpeciesRecord of a species-tagged mint, created with `payer` on
+        /// the species' first mint. `membership` is the recipient's token
+        /// account of the allowlist's membership mint, needed when the config
+        /// has an allowlist and the mint carries no Merkle proof. An
+        /// auto-staked mint passes the recipient's AutoStakeOptIn as
+        /// `auto_stake`, then the staking program it names and that
+        /// program's stake config, the recipient's stake account, the stake
+        /// vault and rsm-token's stake authority PDA. An unsplit mint that is
+        /// neither streamed nor auto-staked pays `recipient_token`, the
+        /// recipient's token account of the config's mint. A mint bonded by
+        /// the config's `bond_bps` escrows the bond in `bond_vault`, after
+        /// any split accounts. `recipient` must co-sign when the config
+        /// requires recipient consent; see
+        /// `instruction::with_recipient_signature`.
+        MintFromGenome {
+            config: writable,
+            genome: writable,
+            mint: writable,
+            recipient: readonly,
+            controller: signer,
+            token_program: readonly,
+            clock: readonly,
+            dedupe: writable,
+            slot_hashes: readonly,
+        }
+        optional {
+            delegate: writable,
+            score: readonly,
+            stream: writable,
+            species: writable,
+            payer: writable_signer,
+            system_program: readonly,
+            membership: readonly,
+            auto_stake: readonly,
+            staking_program: readonly,
+            stake_config: writable,
+            stake_account: writable,
+            stake_vault: writable,
+            stake_authority: readonly,
+            recipient_token: writable,
+            bond_vault: writable,
+        }
+    );
+
+    accounts!(
+        /// `signer` burns the genome's tokens from `owner_token_account`, so
+        /// a burn delegate needs a token approval there too; `burn_approval`
+        /// is required when `signer` is a burn delegate rather than the
+        /// genome owner.
+        BurnGenome {
+            config: writable,
+            genome: writable,
+            mint: writable,
+            owner_token_account: writable,
+            signer: signer,
+            token_program: readonly,
+        }
+        optional {
+            burn_approval: writable,
+        }
+    );
+
+    accounts!(
+        CloseGenomeAccount {
+            config: writable,
+            genome: writable,
+            rent_payer: writable,
+            signer: signer,
+        }
+    );
+
+    accounts!(
+        ApproveBurn {
+            genome: readonly,
+            burn_approval: writable,
+            owner: writable_signer,
+            system_program: readonly,
+        }
+    );
+
+    accounts!(
+        RevokeBurn {
+            burn_approval: writable,
+            owner: writable_signer,
+        }
+    );
+
+    accounts!(
+        /// `opt_in` is the AutoStakeOptIn PDA ["auto_stake", config,
+        /// recipient]; the recipient pays its rent.
+        SetAutoStake {
+            config: readonly,
+            opt_in: writable,
+            recipient: writable_signer,
+            system_program: readonly,
+        }
+    );
+
+    accounts!(
+        RescoreGenome {
+            config: readonly,
+            genome: writable,
+            controller: signer,
+            clock: readonly,
+        }
+        optional {
+            score: readonly,
+        }
+    );
+
+    accounts!(
+        /// The clawback is burned from `bond_vault`.
+        ExecuteClawback {
+            config: writable,
+            genome: writable,
+            clock: readonly,
+            mint: writable,
+            bond_vault: writable,
+            token_program: readonly,
+        }
+    );
+
+    accounts!(
+        /// `payer` covers the rent of the config's bond vault.
+        CreateBondVault {
+            config: readonly,
+            bond_vault: writable,
+            mint: readonly,
+            payer: writable_signer,
+            token_program: readonly,
+            system_program: readonly,
+        }
+    );
+
+    accounts!(
+        /// The bond is paid into `owner_token_account`, the genome owner's
+        /// token account of the config's mint.
+        ReleaseBond {
+            config: readonly,
+            genome: writable,
+            bond_vault: writable,
+            mint: readonly,
+            owner_token_account: writable,
+            token_program: readonly,
+            clock: readonly,
+        }
+    );
+
+    accounts!(
+        /// `owner` pays or is refunded the rent for the note's bytes.
+        SetGenomeNote {
+            config: readonly,
+            genome: writable,
+            owner: writable_signer,
+            system_program: readonly,
+        }
+    );
+
+    accounts!(
+        /// `payer` covers the rent of any bytes the migration adds;
+        /// `config` is required for a genome and omitted for a config.
+        MigrateAccount {
+            account: writable,
+            payer: writable_signer,
+            system_program: readonly,
+        }
+        optional {
+            config: readonly,
+        }
+    );
+
+    accounts!(
+        /// `challenger` pays for the dispute record and is paid its reward
+        /// from `bond_vault` into `challenger_token_account`; `instructions`
+        /// is the instructions sysvar.
+        ChallengeMint {
+            config: writable,
+            genome: writable,
+            dispute: writable,
+            mint: writable,
+            challenger_token_account: writable,
+            challenger: writable_signer,
+            token_program: readonly,
+            instructions: readonly,
+            system_program: readonly,
+            bond_vault: writable,
+        }
+    );
+
+    accounts!(
+        /// `rent_payer` gets the stream's rent back on the last withdrawal;
+        /// the vested tokens are minted to `recipient_token`.
+        WithdrawVested {
+            config: readonly,
+            genome: writable,
+            stream: writable,
+            recipient: signer,
+            rent_payer: writable,
+            mint: writable,
+            recipient_token: writable,
+            token_program: readonly,
+        }
+    );
+}
+
+pub mod pda {
+    use super::*;
+
+    pub use rsm_token::{
+        burn_approval_address, config_address, dispute_address, genome_address, mint_delegate_address, oracle_config_address,
+        bond_vault_address, receipt_address, stream_address,
+    };
+
+    pub fn dedupe_address(program_id: &Pubkey, config: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[DEDUPE_SEED, config.as_ref()], program_id)
+    }
+
+    /// The consciousness oracle's CanonicalScore PDA for `genome_hash` under
+    /// `oracle_config` (see [`oracle_config_address`]).
+    pub fn score_address(oracle_program: &Pubkey, oracle_config: &Pubkey, genome_hash: &[u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"score", oracle_config.as_ref(), genome_hash], oracle_program)
+    }
+}
+
+pub mod instruction {
+    use super::{accounts::*, *};
+    use borsh::BorshSerialize;
+    use solana_program::{instruction::Instruction, program_error::ProgramError};
+
+    fn build(program_id: &Pubkey, data: RSMInstruction, accounts: Vec<AccountMeta>) -> Result<Instruction, ProgramError> {
+        Ok(Instruction::new_with_bytes(*program_id, &data.try_to_vec()?, accounts))
+    }
+
+    pub fn create_genome_account<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &CreateGenomeAccount<A>,
+        genome_hash: [u8; 32],
+    ) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::CreateGenomeAccount { genome_hash }, accounts.to_account_metas())
+    }
+
+    pub fn pre_register_genome<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &PreRegisterGenome<A>,
+        genome_hash: [u8; 32],
+    ) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::PreRegisterGenome { genome_hash }, accounts.to_account_metas())
+    }
+
+    /// Receipt NFTs need a fresh mint keypair, so `args.mint_receipt` must
+    /// be false for CPI callers.
+    pub fn mint_from_genome<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &MintFromGenome<A>,
+        args: MintArgs,
+    ) -> Result<Instruction, ProgramError> {
+        let pays_recipient = args.split_bps.is_empty() && !args.auto_stake && accounts.stream.is_none();
+        if args.mint_receipt
+            || args.auto_stake != accounts.auto_stake.is_some()
+            || pays_recipient != accounts.recipient_token.is_some()
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+        build(program_id, RSMInstruction::MintFromGenome(args), accounts.to_account_metas())
+    }
+
+    /// Pays `args.split_bps` shares into `split_accounts`, token accounts
+    /// of the config's mint in the same order. Split mints do not stream.
+    pub fn mint_from_genome_split<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &MintFromGenome<A>,
+        args: MintArgs,
+        split_accounts: &[A],
+    ) -> Result<Instruction, ProgramError> {
+        if args.split_bps.len() != split_accounts.len() || accounts.stream.is_some() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let mut ix = mint_from_genome(program_id, accounts, args)?;
+        // Ahead of the bond vault, the last optional account
+        let at = ix.accounts.len() - accounts.bond_vault.is_some() as usize;
+        ix.accounts
+            .splice(at..at, split_accounts.iter().map(|account| AccountMeta::new(account.account_key(), false)));
+        Ok(ix)
+    }
+
+    /// Marks the recipient of a MintFromGenome instruction as a signer,
+    /// for configs that require recipient consent.
+    pub fn with_recipient_signature(mut ix: Instruction) -> Instruction {
+        ix.accounts[3].is_signer = true;
+        ix
+    }
+
+    /// Appends the controller co-signers a MintFromGenome instruction needs
+    /// above the config's quorum threshold; they follow any split accounts.
+    pub fn with_cosigners(mut ix: Instruction, cosigners: &[Pubkey]) -> Instruction {
+        ix.accounts
+            .extend(cosigners.iter().map(|cosigner| AccountMeta::new_readonly(*cosigner, true)));
+        ix
+    }
+
+    pub fn burn_genome<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &BurnGenome<A>,
+        genome_hash: [u8; 32],
+    ) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::BurnGenome { genome_hash }, accounts.to_account_metas())
+    }
+
+    pub fn close_genome_account<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &CloseGenomeAccount<A>,
+        genome_hash: [u8; 32],
+    ) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::CloseGenomeAccount { genome_hash }, accounts.to_account_metas())
+    }
+
+    pub fn approve_burn<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &ApproveBurn<A>,
+        delegate: Pubkey,
+        amount: u64,
+        expiry_slot: u64,
+    ) -> Result<Instruction, ProgramError> {
+        build(
+            program_id,
+            RSMInstruction::ApproveBurn { delegate, amount, expiry_slot },
+            accounts.to_account_metas(),
+        )
+    }
+
+    pub fn revoke_burn<A: AccountKey>(program_id: &Pubkey, accounts: &RevokeBurn<A>) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::RevokeBurn, accounts.to_account_metas())
+    }
+
+    /// Pubkey::default() as `staking_program` opts out.
+    pub fn set_auto_stake<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &SetAutoStake<A>,
+        staking_program: Pubkey,
+    ) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::SetAutoStake { staking_program }, accounts.to_account_metas())
+    }
+
+    pub fn rescore_genome<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &RescoreGenome<A>,
+        genome_hash: [u8; 32],
+        complexity: u8,
+        uniqueness: u8,
+    ) -> Result<Instruction, ProgramError> {
+        build(
+            program_id,
+            RSMInstruction::RescoreGenome { genome_hash, complexity, uniqueness },
+            accounts.to_account_metas(),
+        )
+    }
+
+    pub fn execute_clawback<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &ExecuteClawback<A>,
+        genome_hash: [u8; 32],
+    ) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::ExecuteClawback { genome_hash }, accounts.to_account_metas())
+    }
+
+    pub fn create_bond_vault<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &CreateBondVault<A>,
+    ) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::CreateBondVault, accounts.to_account_metas())
+    }
+
+    pub fn release_bond<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &ReleaseBond<A>,
+        genome_hash: [u8; 32],
+    ) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::ReleaseBond { genome_hash }, accounts.to_account_metas())
+    }
+
+    pub fn withdraw_vested<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &WithdrawVested<A>,
+        genome_hash: [u8; 32],
+    ) -> Result<Instruction, ProgramError> {
+        build(program_id, RSMInstruction::WithdrawVested { genome_hash }, accounts.to_account_metas())
+    }
+
+    pub fn set_genome_note<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &SetGenomeNote<A>,
+        genome_hash: [u8; 32],
+        note: Vec<u8>,
+    ) -> Result<Instruction, ProgramError> {
+        if note.len() > MAX_NOTE_LEN {
+            return Err(RSMError::NoteTooLong.into());
+        }
+        build(program_id, RSMInstruction::SetGenomeNote { genome_hash, note }, accounts.to_account_metas())
+    }
+
+    pub fn migrate_account<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &MigrateAccount<A>,
+        kind: AccountKind,
+    ) -> Result<Instruction, ProgramError> {
+        if accounts.config.is_some() != (kind == AccountKind::GenomeData) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        build(program_id, RSMInstruction::MigrateAccount { kind }, accounts.to_account_metas())
+    }
+
+    /// Must directly follow the Ed25519 instruction verifying the evidence
+    /// signer over `rescore_evidence_message`, so it has no CPI helper.
+    pub fn challenge_mint<A: AccountKey>(
+        program_id: &Pubkey,
+        accounts: &ChallengeMint<A>,
+        genome_hash: [u8; 32],
+        complexity: u8,
+        uniqueness: u8,
+    ) -> Result<Instruction, ProgramError> {
+        build(
+            program_id,
+            RSMInstruction::ChallengeMint { genome_hash, complexity, uniqueness },
+            accounts.to_account_metas(),
+        )
+    }
+}
+
+/// Invokes rsm-token from another program. `signer_seeds` lets a PDA act
+/// as payer, owner, controller or burn delegate.
+pub mod cpi {
+    use super::{accounts::*, *};
+    use solana_program::{entrypoint::ProgramResult, instruction::Instruction, program::invoke_signed};
+
+    fn invoke<'info>(
+        program: &AccountInfo<'info>,
+        instruction: Instruction,
+        mut infos: Vec<AccountInfo<'info>>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        infos.push(program.clone());
+        invoke_signed(&instruction, &infos, signer_seeds)
+    }
+
+    /// A recipient that signed the calling transaction co-signs the mint.
+    fn recipient_consent(ix: Instruction, accounts: &MintFromGenome<AccountInfo<'_>>) -> Instruction {
+        if accounts.recipient.is_signer {
+            instruction::with_recipient_signature(ix)
+        } else {
+            ix
+        }
+    }
+
+    pub fn create_genome_account<'info>(
+        program: &AccountInfo<'info>,
+        accounts: CreateGenomeAccount<AccountInfo<'info>>,
+        genome_hash: [u8; 32],
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::create_genome_account(program.key, &accounts, genome_hash)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn mint_from_genome<'info>(
+        program: &AccountInfo<'info>,
+        accounts: MintFromGenome<AccountInfo<'info>>,
+        args: MintArgs,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::mint_from_genome(program.key, &accounts, args)?;
+        invoke(program, recipient_consent(ix, &accounts), accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn mint_from_genome_split<'info>(
+        program: &AccountInfo<'info>,
+        accounts: MintFromGenome<AccountInfo<'info>>,
+        args: MintArgs,
+        split_accounts: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::mint_from_genome_split(program.key, &accounts, args, split_accounts)?;
+        let ix = recipient_consent(ix, &accounts);
+        let mut infos = accounts.to_account_infos();
+        infos.extend_from_slice(split_accounts);
+        invoke(program, ix, infos, signer_seeds)
+    }
+
+    pub fn burn_genome<'info>(
+        program: &AccountInfo<'info>,
+        accounts: BurnGenome<AccountInfo<'info>>,
+        genome_hash: [u8; 32],
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::burn_genome(program.key, &accounts, genome_hash)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn close_genome_account<'info>(
+        program: &AccountInfo<'info>,
+        accounts: CloseGenomeAccount<AccountInfo<'info>>,
+        genome_hash: [u8; 32],
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::close_genome_account(program.key, &accounts, genome_hash)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn approve_burn<'info>(
+        program: &AccountInfo<'info>,
+        accounts: ApproveBurn<AccountInfo<'info>>,
+        delegate: Pubkey,
+        amount: u64,
+        expiry_slot: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::approve_burn(program.key, &accounts, delegate, amount, expiry_slot)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn revoke_burn<'info>(
+        program: &AccountInfo<'info>,
+        accounts: RevokeBurn<AccountInfo<'info>>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::revoke_burn(program.key, &accounts)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn set_auto_stake<'info>(
+        program: &AccountInfo<'info>,
+        accounts: SetAutoStake<AccountInfo<'info>>,
+        staking_program: Pubkey,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::set_auto_stake(program.key, &accounts, staking_program)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn rescore_genome<'info>(
+        program: &AccountInfo<'info>,
+        accounts: RescoreGenome<AccountInfo<'info>>,
+        genome_hash: [u8; 32],
+        complexity: u8,
+        uniqueness: u8,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::rescore_genome(program.key, &accounts, genome_hash, complexity, uniqueness)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn execute_clawback<'info>(
+        program: &AccountInfo<'info>,
+        accounts: ExecuteClawback<AccountInfo<'info>>,
+        genome_hash: [u8; 32],
+    ) -> ProgramResult {
+        let ix = instruction::execute_clawback(program.key, &accounts, genome_hash)?;
+        invoke(program, ix, accounts.to_account_infos(), &[])
+    }
+
+    pub fn release_bond<'info>(
+        program: &AccountInfo<'info>,
+        accounts: ReleaseBond<AccountInfo<'info>>,
+        genome_hash: [u8; 32],
+    ) -> ProgramResult {
+        let ix = instruction::release_bond(program.key, &accounts, genome_hash)?;
+        invoke(program, ix, accounts.to_account_infos(), &[])
+    }
+
+    pub fn set_genome_note<'info>(
+        program: &AccountInfo<'info>,
+        accounts: SetGenomeNote<AccountInfo<'info>>,
+        genome_hash: [u8; 32],
+        note: Vec<u8>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::set_genome_note(program.key, &accounts, genome_hash, note)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn migrate_account<'info>(
+        program: &AccountInfo<'info>,
+        accounts: MigrateAccount<AccountInfo<'info>>,
+        kind: AccountKind,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::migrate_account(program.key, &accounts, kind)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+
+    pub fn withdraw_vested<'info>(
+        program: &AccountInfo<'info>,
+        accounts: WithdrawVested<AccountInfo<'info>>,
+        genome_hash: [u8; 32],
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let ix = instruction::withdraw_vested(program.key, &accounts, genome_hash)?;
+        invoke(program, ix, accounts.to_account_infos(), signer_seeds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshDeserialize;
+
+    #[test]
+    fn test_burn_genome_metas() {
+        let keys: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+        let mut accounts = accounts::BurnGenome {
+            config: keys[0],
+            genome: keys[1],
+            mint: keys[2],
+            owner_token_account: keys[3],
+            signer: keys[4],
+            token_program: keys[5],
+            burn_approval: None,
+        };
+        let program_id = Pubkey::new_unique();
+        let ix = instruction::burn_genome(&program_id, &accounts, [7u8; 32]).unwrap();
+        assert_eq!(ix.accounts.len(), 6);
+        assert!(ix.accounts[3].is_writable);
+        assert!(ix.accounts[4].is_signer && !ix.accounts[4].is_writable);
+        assert!(matches!(
+            RSMInstruction::try_from_slice(&ix.data).unwrap(),
+            RSMInstruction::BurnGenome { genome_hash } if genome_hash == [7u8; 32]
+        ));
+
+        accounts.burn_approval = Some(keys[6]);
+        let metas = accounts.to_account_metas();
+        assert_eq!(metas.len(), 7);
+        assert_eq!(metas[6], AccountMeta::new(keys[6], false));
+    }
+
+    #[test]
+    fn test_mint_optional_accounts_order() {
+        let key = Pubkey::new_unique;
+        let (delegate, score) = (key(), key());
+        let accounts = accounts::MintFromGenome {
+            config: key(),
+            genome: key(),
+            mint: key(),
+            recipient: key(),
+            controller: key(),
+            token_program: key(),
+            clock: key(),
+            dedupe: key(),
+            slot_hashes: key(),
+            delegate: Some(delegate),
+            score: Some(score),
+            stream: None,
+            species: None,
+            payer: None,
+            system_program: None,
+            membership: None,
+            auto_stake: None,
+            staking_program: None,
+            stake_config: None,
+            stake_account: None,
+            stake_vault: None,
+            stake_authority: None,
+            recipient_token: None,
+            bond_vault: None,
+        };
+        let metas = accounts.to_account_metas();
+        assert_eq!(metas[9], AccountMeta::new(delegate, false));
+        assert_eq!(metas[10], AccountMeta::new_readonly(score, false));
+
+        let args = MintArgs {
+            genome_hash: [1u8; 32],
+            complexity: 90,
+            uniqueness: 90,
+            entropy: 150,
+            amount: 1,
+            mint_receipt: true,
+            nonce: 1,
+            recent_slot: 1,
+            split_bps: Vec::new(),
+            origin: GenomeOrigin::Unknown,
+            species: 0,
+            allowlist_proof: None,
+            auto_stake: false,
+        };
+        assert!(instruction::mint_from_genome(&Pubkey::new_unique(), &accounts, args.clone()).is_err());
+        // Auto-staking needs the opt-in and staking accounts
+        let staked = MintArgs { mint_receipt: false, auto_stake: true, ..args.clone() };
+        assert!(instruction::mint_from_genome(&Pubkey::new_unique(), &accounts, staked).is_err());
+        // A plain mint pays the recipient's token account, last
+        let plain = MintArgs { mint_receipt: false, ..args.clone() };
+        assert!(instruction::mint_from_genome(&Pubkey::new_unique(), &accounts, plain.clone()).is_err());
+        let recipient_token = key();
+        let paid = accounts::MintFromGenome { recipient_token: Some(recipient_token), ..accounts.clone() };
+        let ix = instruction::mint_from_genome(&Pubkey::new_unique(), &paid, plain).unwrap();
+        assert_eq!(ix.accounts.len(), 12);
+        assert_eq!(ix.accounts[11], AccountMeta::new(recipient_token, false));
+
+        // Split token accounts trail the optional accounts, one per share
+        let args = MintArgs { mint_receipt: false, split_bps: vec![7_000, 3_000], ..args };
+        let splits = [key(), key()];
+        assert!(instruction::mint_from_genome_split(&Pubkey::new_unique(), &accounts, args.clone(), &splits[..1]).is_err());
+        let ix = instruction::mint_from_genome_split(&Pubkey::new_unique(), &accounts, args.clone(), &splits).unwrap();
+        assert_eq!(ix.accounts.len(), 13);
+        assert_eq!(ix.accounts[2], AccountMeta::new(accounts.mint, false));
+        assert_eq!(ix.accounts[12], AccountMeta::new(splits[1], false));
+        let ix = instruction::with_recipient_signature(ix);
+        assert_eq!(ix.accounts[3], AccountMeta::new_readonly(accounts.recipient, true));
+
+        // A bonded split mint passes the bond vault after the shares
+        let bond_vault = key();
+        let bonded = accounts::MintFromGenome { bond_vault: Some(bond_vault), ..accounts.clone() };
+        let ix = instruction::mint_from_genome_split(&Pubkey::new_unique(), &bonded, args, &splits).unwrap();
+        assert_eq!(ix.accounts.len(), 14);
+        assert_eq!(ix.accounts[12], AccountMeta::new(splits[1], false));
+        assert_eq!(ix.accounts[13], AccountMeta::new(bond_vault, false));
+    }
+}
@@ -1,75 +1,908 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    entrypoint,
     entrypoint::ProgramResult,
     msg,
+    hash::{hash, hashv},
+    instruction::{AccountMeta, Instruction},
+    log::sol_log_data,
+    program::{invoke, invoke_signed, set_return_data},
     program_error::ProgramError,
     pubkey::Pubkey,
-    sysvar::{clock::Clock, Sysvar},
+    system_instruction,
+    sysvar::{clock::Clock, epoch_schedule::EpochSchedule, instructions as ix_sysvar, rent::Rent, slot_hashes, Sysvar},
+    ed25519_program,
 };
+use rsm_migrations::{add_version_byte, MigrationError, Schema};
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use thiserror::Error;
 
+// Defaults for the RSM mint; each TokenConfig stores its own values
 pub const MAX_SUPPLY: u64 = 100_000_666_000_000_000;
 pub const DECIMALS: u8 = 9;
+pub const DEFAULT_BREAKER_WINDOW_SLOTS: u64 = 216_000; // ~1 day
+pub const DEFAULT_BREAKER_MULTIPLE_PCT: u16 = 300;
+pub const BREAKER_FLOOR_TOKENS: u64 = 10_000; // whole tokens per window
+pub const RECEIPT_AUTHORITY_SEED: &[u8] = b"receipt_authority";
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+pub const DEDUPE_SEED: &[u8] = b"dedupe";
+pub const GENOME_SEED: &[u8] = b"genome";
+pub const AUDIT_SEED: &[u8] = b"audit";
+pub const MINT_DELEGATE_SEED: &[u8] = b"mint_delegate";
+pub const BURN_APPROVAL_SEED: &[u8] = b"burn_approval";
+pub const STREAM_SEED: &[u8] = b"stream";
+pub const SPECIES_SEED: &[u8] = b"species";
+pub const CONFIG_SEED: &[u8] = b"config";
+/// Largest ring an account created through CPI has room for
+pub const DEDUPE_CAPACITY: usize = 400;
+pub const NONCE_VALIDITY_SLOTS: u64 = 150;
+pub const SUPPLY_RAISE_TIMELOCK: i64 = 14 * 86_400; // 14 days
+pub const SUPPLY_RAISE_WINDOW: i64 = 365 * 86_400; // 1 year
+pub const SUPPLY_RAISE_MAX_PCT: u64 = 10;
+pub const SUPPLY_HISTORY_LEN: usize = 8;
+pub const PARAMS_HISTORY_LEN: usize = 16;
+pub const CLAWBACK_DELAY: i64 = 3 * 86_400; // 3 days to correct a re-score
+pub const CLAWBACK_SEED: &[u8] = b"clawback";
+pub const BOND_VAULT_SEED: &[u8] = b"bond_vault";
+pub const EMERGENCY_CLAWBACK_TIMELOCK: i64 = 2 * 86_400; // 2 days to veto
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_SPLIT_RECIPIENTS: usize = 5;
+pub const SLOT_DURATION_MS: u64 = 400;
+pub const DECAY_STEP_SECS: u64 = 3_600;
+pub const DISPUTE_SEED: &[u8] = b"dispute";
+pub const RESCORE_EVIDENCE_DOMAIN: &[u8] = b"RSM_RESCORE_EVIDENCE";
+pub const MAX_NOTE_LEN: usize = 64;
+pub const EMISSION_SCALE_MIN_BPS: u64 = 5_000;
+pub const EMISSION_SCALE_MAX_BPS: u64 = 15_000;
+pub const EMISSION_EMA_MAX_EPOCHS: u64 = 32;
+pub const MAX_DISCOVERY_BONUS_BPS: u16 = 10_000;
+pub const ALLOWLIST_DOMAIN: &[u8] = b"RSM_ALLOWLIST";
+pub const AUTO_STAKE_SEED: &[u8] = b"auto_stake";
+pub const STAKE_AUTHORITY_SEED: &[u8] = b"stake_authority";
+pub const QUALITY_BUCKETS: usize = 20;
+pub const MAX_QUORUM_CONTROLLERS: usize = 5;
+/// Weighted score of perfect inputs, with the weights summing to 100
+pub const MAX_WEIGHTED_SCORE: u64 = 10_000;
 
-entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// Where the processor reads the cluster time. On chain it is always
+/// `SysvarClock`; tests pass a fixed `Clock` to step through timelocks,
+/// vesting and emission schedules without a validator.
+pub trait ClockProvider {
+    fn now(&self) -> Result<Clock, ProgramError>;
+}
+
+/// The Clock sysvar, read through the `sol_get_clock_sysvar` syscall.
+pub struct SysvarClock;
+
+impl ClockProvider for SysvarClock {
+    fn now(&self) -> Result<Clock, ProgramError> {
+        Clock::get()
+    }
+}
+
+impl ClockProvider for Clock {
+    fn now(&self) -> Result<Clock, ProgramError> {
+        Ok(self.clone())
+    }
+}
+
+/// `account` must be a token account of `mint` held by `owner`.
+fn check_token_account(account: &AccountInfo, mint: &Pubkey, owner: &Pubkey) -> ProgramResult {
+    // Both token programs share the base account layout
+    if *account.owner != spl_token::id() && *account.owner != spl_token_2022::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = account.data.borrow();
+    let holding = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    if holding.base.mint != *mint || holding.base.owner != *owner {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+/// Instructions that list the clock sysvar still require it, though the
+/// time itself comes from the `ClockProvider`.
+fn check_clock_sysvar(account: &AccountInfo) -> ProgramResult {
+    if !solana_program::sysvar::clock::check_id(account.key) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
 
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
+) -> ProgramResult {
+    process_instruction_with_clock(program_id, accounts, instruction_data, &SysvarClock)
+}
+
+pub fn process_instruction_with_clock(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+    clock: &dyn ClockProvider,
 ) -> ProgramResult {
     let instruction = RSMInstruction::try_from_slice(instruction_data)?;
     match instruction {
-        RSMInstruction::Initialize { max_supply, agi_controller } => {
-            process_initialize(program_id, accounts, max_supply, agi_controller)
+        RSMInstruction::Initialize { max_supply, agi_controller, decimals } => {
+            process_initialize(program_id, accounts, max_supply, agi_controller, decimals)
         }
-        RSMInstruction::MintFromGenome { genome_hash, complexity, uniqueness, entropy, amount } => {
-            process_mint_from_genome(program_id, accounts, genome_hash, complexity, uniqueness, entropy, amount)
+        RSMInstruction::MintFromGenome(args) => {
+            process_mint_from_genome(program_id, accounts, args, clock)
         }
         RSMInstruction::BurnGenome { genome_hash } => {
-            process_burn_genome(program_id, accounts, genome_hash)
+            process_burn_genome(program_id, accounts, genome_hash, clock)
         }
         RSMInstruction::UpdateAGIParams { complexity_weight, uniqueness_weight, entropy_weight, blockchain_weight } => {
-            process_update_agi_params(program_id, accounts, complexity_weight, uniqueness_weight, entropy_weight, blockchain_weight)
+            process_update_agi_params(program_id, accounts, complexity_weight, uniqueness_weight, entropy_weight, blockchain_weight, clock)
+        }
+        RSMInstruction::SetScoreOracle { score_oracle } => {
+            process_set_score_oracle(program_id, accounts, score_oracle)
+        }
+        RSMInstruction::ConfigureBreaker { window_slots, max_multiple_pct } => {
+            process_configure_breaker(program_id, accounts, window_slots, max_multiple_pct)
+        }
+        RSMInstruction::ResetBreaker => {
+            process_reset_breaker(program_id, accounts, clock)
+        }
+        RSMInstruction::CreateDedupeBuffer => {
+            process_create_dedupe_buffer(program_id, accounts)
+        }
+        RSMInstruction::CreateGenomeAccount { genome_hash } => {
+            process_create_genome_account(program_id, accounts, genome_hash, false, clock)
+        }
+        RSMInstruction::CloseGenomeAccount { genome_hash } => {
+            process_close_genome_account(program_id, accounts, genome_hash)
+        }
+        RSMInstruction::StartSupplyAudit => {
+            process_start_supply_audit(program_id, accounts, clock)
+        }
+        RSMInstruction::AuditSupplyPage => {
+            process_audit_supply_page(program_id, accounts)
+        }
+        RSMInstruction::FinishSupplyAudit => {
+            process_finish_supply_audit(program_id, accounts)
+        }
+        RSMInstruction::RepairSupply => {
+            process_repair_supply(program_id, accounts)
+        }
+        RSMInstruction::CreateMintDelegate { delegate, max_amount, max_mints, expiry_slot } => {
+            process_create_mint_delegate(program_id, accounts, delegate, max_amount, max_mints, expiry_slot, clock)
+        }
+        RSMInstruction::RevokeMintDelegate => {
+            process_revoke_mint_delegate(program_id, accounts)
+        }
+        RSMInstruction::SetGovernanceExecutor { executor } => {
+            process_set_governance_executor(program_id, accounts, executor)
+        }
+        RSMInstruction::ScheduleMaxSupplyRaise { new_max_supply } => {
+            process_schedule_max_supply_raise(program_id, accounts, new_max_supply, clock)
+        }
+        RSMInstruction::RaiseMaxSupply => {
+            process_raise_max_supply(program_id, accounts, clock)
+        }
+        RSMInstruction::CancelMaxSupplyRaise => {
+            process_cancel_max_supply_raise(program_id, accounts)
+        }
+        RSMInstruction::ConfigureScoreValidity { validity_secs, bond_bps } => {
+            process_configure_score_validity(program_id, accounts, validity_secs, bond_bps)
+        }
+        RSMInstruction::RescoreGenome { genome_hash, complexity, uniqueness } => {
+            process_rescore_genome(program_id, accounts, genome_hash, complexity, uniqueness, clock)
+        }
+        RSMInstruction::ExecuteClawback { genome_hash } => {
+            process_execute_clawback(program_id, accounts, genome_hash, clock)
+        }
+        RSMInstruction::ApproveBurn { delegate, amount, expiry_slot } => {
+            process_approve_burn(program_id, accounts, delegate, amount, expiry_slot, clock)
+        }
+        RSMInstruction::RevokeBurn => {
+            process_revoke_burn(program_id, accounts)
+        }
+        RSMInstruction::ConfigureStreaming { stream_slots } => {
+            process_configure_streaming(program_id, accounts, stream_slots)
+        }
+        RSMInstruction::ConfigureDecay { threshold_secs, half_life_secs, floor_bps } => {
+            process_configure_decay(program_id, accounts, threshold_secs, half_life_secs, floor_bps)
+        }
+        RSMInstruction::WithdrawVested { genome_hash } => {
+            process_withdraw_vested(program_id, accounts, genome_hash, clock)
+        }
+        RSMInstruction::ConfigureDisputes { window_secs, reward_bps, evidence_signer } => {
+            process_configure_disputes(program_id, accounts, window_secs, reward_bps, evidence_signer)
+        }
+        RSMInstruction::ChallengeMint { genome_hash, complexity, uniqueness } => {
+            process_challenge_mint(program_id, accounts, genome_hash, complexity, uniqueness, clock)
+        }
+        RSMInstruction::SetGenomeNote { genome_hash, note } => {
+            process_set_genome_note(program_id, accounts, genome_hash, note)
+        }
+        RSMInstruction::MigrateAccount { kind } => {
+            process_migrate_account(program_id, accounts, kind)
+        }
+        RSMInstruction::ConfigureEmissionTarget { target_per_epoch, epoch_slots } => {
+            process_configure_emission_target(program_id, accounts, target_per_epoch, epoch_slots, clock)
+        }
+        RSMInstruction::ConfigureRecipientConsent { required } => {
+            process_configure_recipient_consent(program_id, accounts, required)
+        }
+        RSMInstruction::ConfigureMintWindow { start_bps, end_bps } => {
+            process_configure_mint_window(program_id, accounts, start_bps, end_bps)
+        }
+        RSMInstruction::ConfigureDust { threshold, reward_pool } => {
+            process_configure_dust(program_id, accounts, threshold, reward_pool)
+        }
+        RSMInstruction::ConsolidateDust => {
+            process_consolidate_dust(program_id, accounts)
+        }
+        RSMInstruction::ConfigureDiscoveryBonus { bonus_bps } => {
+            process_configure_discovery_bonus(program_id, accounts, bonus_bps)
+        }
+        RSMInstruction::ConfigureAllowlist { membership_mint, merkle_root } => {
+            process_configure_allowlist(program_id, accounts, membership_mint, merkle_root)
+        }
+        RSMInstruction::SetAutoStake { staking_program } => {
+            process_set_auto_stake(program_id, accounts, staking_program)
+        }
+        RSMInstruction::ConfigureQualityHistogram { reset_per_epoch } => {
+            process_configure_quality_histogram(program_id, accounts, reset_per_epoch, clock)
+        }
+        RSMInstruction::PreRegisterGenome { genome_hash } => {
+            process_create_genome_account(program_id, accounts, genome_hash, true, clock)
+        }
+        RSMInstruction::ConfigureMintQuorum { threshold, required, controllers } => {
+            process_configure_mint_quorum(program_id, accounts, threshold, required, controllers)
+        }
+        RSMInstruction::ScheduleEmergencyClawback { amount, evidence_hash, reason_code } => {
+            process_schedule_emergency_clawback(program_id, accounts, amount, evidence_hash, reason_code, clock)
+        }
+        RSMInstruction::EmergencyClawback { evidence_hash } => {
+            process_emergency_clawback(program_id, accounts, evidence_hash, clock)
+        }
+        RSMInstruction::CancelEmergencyClawback { evidence_hash } => {
+            process_cancel_emergency_clawback(program_id, accounts, evidence_hash)
+        }
+        RSMInstruction::TripBreaker { amount } => {
+            process_trip_breaker(program_id, accounts, amount, clock)
+        }
+        RSMInstruction::CreateBondVault => {
+            process_create_bond_vault(program_id, accounts)
+        }
+        RSMInstruction::ReleaseBond { genome_hash } => {
+            process_release_bond(program_id, accounts, genome_hash, clock)
         }
     }
 }
 
+/// Logs `event` as program data prefixed with the Anchor-style
+/// discriminator sha256("event:<name>")[..8].
+pub fn emit_event<T: BorshSerialize>(name: &str, event: &T) -> ProgramResult {
+    let discriminator = hash(format!("event:{}", name).as_bytes()).to_bytes();
+    let data = event.try_to_vec()?;
+    sol_log_data(&[&discriminator[..8], &data]);
+    Ok(())
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub enum RSMInstruction {
+    /// Creates the config PDA ["config", mint]; one per mint.
     Initialize {
         max_supply: u64,
         agi_controller: Pubkey,
+        decimals: u8,
     },
-    MintFromGenome {
-        genome_hash: [u8; 32],
-        complexity: u8,
-        uniqueness: u8,
-        entropy: u16,
-        amount: u64,
-    },
+    MintFromGenome(MintArgs),
     BurnGenome {
         genome_hash: [u8; 32],
     },
+    /// Logs the new weights, with the slot they take effect, in the
+    /// config's parameter history.
     UpdateAGIParams {
         complexity_weight: u8,
         uniqueness_weight: u8,
         entropy_weight: u8,
         blockchain_weight: u8,
     },
+    /// Pubkey::default() disables the oracle check
+    SetScoreOracle {
+        score_oracle: Pubkey,
+    },
+    ConfigureBreaker {
+        window_slots: u64,
+        max_multiple_pct: u16,
+    },
+    ResetBreaker,
+    /// Also grows a buffer created with fewer than DEDUPE_CAPACITY entries
+    CreateDedupeBuffer,
+    CreateGenomeAccount {
+        genome_hash: [u8; 32],
+    },
+    CloseGenomeAccount {
+        genome_hash: [u8; 32],
+    },
+    StartSupplyAudit,
+    AuditSupplyPage,
+    FinishSupplyAudit,
+    RepairSupply,
+    /// Lets `delegate` sign MintFromGenome in place of the agi_controller
+    /// until `expiry_slot`, within the amount and mint-count limits.
+    CreateMintDelegate {
+        delegate: Pubkey,
+        max_amount: u64,
+        max_mints: u32,
+        expiry_slot: u64,
+    },
+    RevokeMintDelegate,
+    /// Binds the governance executor that co-signs max supply raises.
+    /// Replacing an existing executor requires its signature too.
+    SetGovernanceExecutor {
+        executor: Pubkey,
+    },
+    /// Queues a raise behind SUPPLY_RAISE_TIMELOCK; needs the authority
+    /// and the governance executor.
+    ScheduleMaxSupplyRaise {
+        new_max_supply: u64,
+    },
+    /// Applies the queued raise once its timelock has passed; needs the
+    /// authority and the governance executor.
+    RaiseMaxSupply,
+    /// Either co-signer can veto a queued raise.
+    CancelMaxSupplyRaise,
+    /// Scores expire `validity_secs` after scoring (0 = never); `bond_bps`
+    /// of each later mint stays bonded against a lower re-score, escrowed
+    /// in the bond vault (see CreateBondVault) until ReleaseBond.
+    ConfigureScoreValidity {
+        validity_secs: i64,
+        bond_bps: u16,
+    },
+    /// Re-scores an expired genome. A lower score schedules a clawback of
+    /// the difference from the bond, executable after CLAWBACK_DELAY; until
+    /// then the agi_controller may correct it with another re-score.
+    RescoreGenome {
+        genome_hash: [u8; 32],
+        complexity: u8,
+        uniqueness: u8,
+    },
+    /// Permissionless once the clawback delay has passed; burns the
+    /// clawback from the bond vault.
+    ExecuteClawback {
+        genome_hash: [u8; 32],
+    },
+    /// Lets `delegate` sign BurnGenome for this genome in place of the
+    /// owner, up to `amount` tokens until `expiry_slot`. Re-approving
+    /// replaces the previous allowance. The burn takes the tokens from the
+    /// owner's token account, so the delegate needs a token approval there
+    /// as well.
+    ApproveBurn {
+        delegate: Pubkey,
+        amount: u64,
+        expiry_slot: u64,
+    },
+    RevokeBurn,
+    /// Mints release linearly to the recipient over `stream_slots` instead
+    /// of being owned outright (0 = instant).
+    ConfigureStreaming {
+        stream_slots: u64,
+    },
+    /// Mints the vested part of a streamed mint to the recipient's token
+    /// account; the last withdrawal hands the genome to the recipient and
+    /// closes the stream.
+    WithdrawVested {
+        genome_hash: [u8; 32],
+    },
+    /// Rewards for genomes older than `threshold_secs` halve every
+    /// `half_life_secs` beyond it, down to `floor_bps` (0 = no decay).
+    ConfigureDecay {
+        threshold_secs: i64,
+        half_life_secs: i64,
+        floor_bps: u16,
+    },
+    /// Mints can be challenged for `window_secs` after minting (0 = no
+    /// disputes) with re-scores signed by `evidence_signer`; the challenger
+    /// earns `reward_bps` of what is slashed.
+    ConfigureDisputes {
+        window_secs: i64,
+        reward_bps: u16,
+        evidence_signer: Pubkey,
+    },
+    /// Permissionless within the challenge window. The preceding instruction
+    /// must be an Ed25519 check of the evidence signer over
+    /// `rescore_evidence_message`; a lower score slashes the difference
+    /// from the escrowed bond, pays the challenger's share out of the bond
+    /// vault (passed last), burns the rest and records the dispute.
+    ChallengeMint {
+        genome_hash: [u8; 32],
+        complexity: u8,
+        uniqueness: u8,
+    },
+    /// Owner's note or reference hash of up to MAX_NOTE_LEN bytes. The
+    /// genome account is resized to fit; the owner pays the extra rent and
+    /// gets it back when the note shrinks. An empty note clears it.
+    SetGenomeNote {
+        genome_hash: [u8; 32],
+        note: Vec<u8>,
+    },
+    /// Upgrades one TokenConfig or GenomeData account to its current
+    /// schema version. Anyone may call it; the payer covers any extra rent.
+    /// A genome also takes its config, which the genome PDA is derived from.
+    MigrateAccount {
+        kind: AccountKind,
+    },
+    /// Scales mints by `target_per_epoch` over the moving average of
+    /// emissions per `epoch_slots`, within ±50% (0 = no smoothing).
+    ConfigureEmissionTarget {
+        target_per_epoch: u64,
+        epoch_slots: u64,
+    },
+    /// While `required`, MintFromGenome needs the recipient's signature too,
+    /// so the controller cannot push genomes to wallets that did not ask.
+    ConfigureRecipientConsent {
+        required: bool,
+    },
+    /// Accepts mints only from `start_bps` to `end_bps` of each Solana
+    /// epoch's slots, leaving the rest as a quiet period (end_bps 0 = always).
+    ConfigureMintWindow {
+        start_bps: u16,
+        end_bps: u16,
+    },
+    /// Sets where accumulated mint dust goes: the staking reward pool's
+    /// token account, once at least `threshold` base units have built up
+    /// (0 = dust is only counted).
+    ConfigureDust {
+        threshold: u64,
+        reward_pool: Pubkey,
+    },
+    /// Mints the whole base units of accumulated dust to the reward pool;
+    /// needs the authority and the governance executor.
+    ConsolidateDust,
+    /// Boosts the first mint of each species tag by `bonus_bps` on top of
+    /// the emission scale (0 = no bonus, at most MAX_DISCOVERY_BONUS_BPS).
+    ConfigureDiscoveryBonus {
+        bonus_bps: u16,
+    },
+    /// Restricts mint recipients to holders of `membership_mint` or keys in
+    /// the Merkle tree with `merkle_root`; both default turns it off.
+    ConfigureAllowlist {
+        membership_mint: Pubkey,
+        merkle_root: [u8; 32],
+    },
+    /// Opts the signing recipient into auto-staked mints with
+    /// `staking_program`; Pubkey::default() opts out and refunds the rent.
+    SetAutoStake {
+        staking_program: Pubkey,
+    },
+    /// Clears the quality histogram; while `reset_per_epoch` it also
+    /// restarts with the first mint of each Solana epoch.
+    ConfigureQualityHistogram {
+        reset_per_epoch: bool,
+    },
+    /// Creates the genome account for `genome_hash` with the signer as its
+    /// registrant and rent payer; the controller's mint must then pay the
+    /// registrant. The agi_controller or the authority co-signs, after the
+    /// system program.
+    PreRegisterGenome {
+        genome_hash: [u8; 32],
+    },
+    /// Mints of more than `threshold` base units need `required` of
+    /// `controllers` (at most MAX_QUORUM_CONTROLLERS) to co-sign; they
+    /// follow the split accounts (threshold 0 = no quorum).
+    ConfigureMintQuorum {
+        threshold: u64,
+        required: u8,
+        controllers: Vec<Pubkey>,
+    },
+    /// Records `evidence_hash` and `reason_code` on the ClawbackRecord PDA
+    /// and queues a burn of `amount` from the token account behind
+    /// EMERGENCY_CLAWBACK_TIMELOCK; needs the authority, who pays the rent,
+    /// and the governance executor.
+    ScheduleEmergencyClawback {
+        amount: u64,
+        evidence_hash: [u8; 32],
+        reason_code: u16,
+    },
+    /// Burns the recorded amount once the timelock has passed, through the
+    /// config's delegation on the token account, and takes it off the
+    /// genome account that minted it, passed last; needs the authority and
+    /// the governance executor.
+    EmergencyClawback {
+        evidence_hash: [u8; 32],
+    },
+    /// Either co-signer can veto a queued clawback; the record is kept.
+    CancelEmergencyClawback {
+        evidence_hash: [u8; 32],
+    },
+    /// Latches the breaker ahead of a mint of `amount` that would trip it;
+    /// fails if that mint would pass. Needs the agi_controller or the
+    /// authority.
+    TripBreaker {
+        amount: u64,
+    },
+    /// Creates the bond vault, the config's token account at PDA
+    /// ["bond_vault", config] that escrows bonded mints. Anyone may pay.
+    CreateBondVault,
+    /// Permissionless. Pays the genome's bond from the vault to the
+    /// genome owner's token account once `bond_releasable`; a streamed
+    /// genome's stream must have completed.
+    ReleaseBond {
+        genome_hash: [u8; 32],
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKind {
+    TokenConfig,
+    GenomeData,
+}
+
+impl AccountKind {
+    pub fn schema(&self) -> &'static Schema {
+        match self {
+            AccountKind::TokenConfig => &TokenConfig::SCHEMA,
+            AccountKind::GenomeData => &GenomeData::SCHEMA,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct MintArgs {
+    pub genome_hash: [u8; 32],
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub entropy: u16,
+    pub amount: u64,
+    pub mint_receipt: bool,
+    pub nonce: u64,
+    /// A slot in the SlotHashes sysvar; its hash seeds the blockchain
+    /// component of the amount
+    pub recent_slot: u64,
+    /// Shares of `amount` in bps, one per split token account; empty keeps
+    /// the whole mint with the recipient
+    pub split_bps: Vec<u16>,
+    /// When the genome was first seen; required while quality decay is on
+    pub origin: GenomeOrigin,
+    /// Species tag of the genome (0 = untagged); the first mint of a tag
+    /// records the species and earns the discovery bonus
+    pub species: u16,
+    /// Merkle proof that the recipient is allowlisted; `None` proves it
+    /// with a membership token account instead. Ignored while the config
+    /// has no allowlist
+    pub allowlist_proof: Option<Vec<[u8; 32]>>,
+    /// Mints into the stake vault of the staking program the recipient
+    /// opted into and stakes it for them there; cannot be split or streamed
+    pub auto_stake: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GenomeOrigin {
+    #[default]
+    Unknown,
+    Slot(u64),
+    Timestamp(i64),
+}
+
+impl GenomeOrigin {
+    /// Age in seconds at the given clock; slots count SLOT_DURATION_MS each.
+    /// Origins in the future count as new.
+    pub fn age_secs(&self, slot: u64, unix_timestamp: i64) -> Option<i64> {
+        match *self {
+            GenomeOrigin::Unknown => None,
+            GenomeOrigin::Slot(origin) => Some((slot.saturating_sub(origin).saturating_mul(SLOT_DURATION_MS) / 1_000) as i64),
+            GenomeOrigin::Timestamp(origin) => Some(unix_timestamp.saturating_sub(origin).max(0)),
+        }
+    }
+}
+
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RSMError {
+    #[error("Emission anomaly: circuit breaker tripped")]
+    EmissionAnomaly,
+    #[error("Mint nonce is outside the recent-slot validity window")]
+    StaleNonce,
+    #[error("Dedupe buffer full of unexpired nonces")]
+    DedupeBufferFull,
+    #[error("Genome already minted")]
+    AlreadyMinted,
+    #[error("Genome account still holds a minted genome")]
+    GenomeStillMinted,
+    #[error("Audit pages must be sorted by address and not repeat")]
+    AuditPageOutOfOrder,
+    #[error("Audit already finished")]
+    AuditFinished,
+    #[error("Audit not finished or supply changed since it started")]
+    AuditStale,
+    #[error("Mint delegate has expired")]
+    DelegateExpired,
+    #[error("Mint delegate spend limit exceeded")]
+    DelegateLimitExceeded,
+    #[error("No max supply raise is scheduled")]
+    NoPendingRaise,
+    #[error("Max supply raise is still timelocked")]
+    RaiseTimelocked,
+    #[error("Max supply raise exceeds the yearly cap")]
+    RaiseExceedsCap,
+    #[error("Genome score has not expired")]
+    ScoreNotExpired,
+    #[error("No clawback is scheduled")]
+    NoPendingClawback,
+    #[error("Clawback delay has not passed")]
+    ClawbackDelayed,
+    #[error("Clawback is due and must be executed first")]
+    ClawbackDue,
+    #[error("Burn approval has expired")]
+    BurnApprovalExpired,
+    #[error("Burn exceeds the approved amount")]
+    BurnAllowanceExceeded,
+    #[error("Nothing has vested since the last withdrawal")]
+    NothingVested,
+    #[error("Split shares must be 1-5 positive bps summing to 10000")]
+    InvalidSplit,
+    #[error("Genome origin is required while quality decay is on")]
+    MissingGenomeOrigin,
+    #[error("Disputes are not enabled")]
+    DisputesDisabled,
+    #[error("Challenge window for this mint has closed")]
+    ChallengeWindowClosed,
+    #[error("Re-score evidence is missing or not signed by the evidence signer")]
+    InvalidEvidence,
+    #[error("Evidence does not show an inflated mint")]
+    MintNotInflated,
+    #[error("Genome note exceeds 64 bytes")]
+    NoteTooLong,
+    #[error("Account data does not match a known schema version")]
+    MigrationFailed,
+    #[error("Config requires the recipient to co-sign mints")]
+    RecipientSignatureRequired,
+    #[error("Mints are closed for the rest of this epoch's window")]
+    MintWindowClosed,
+    #[error("Accumulated dust is below the consolidation threshold")]
+    DustBelowThreshold,
+    #[error("Recent slot has no entry in the SlotHashes sysvar")]
+    UnknownSlotHash,
+    #[error("Recipient is not on the config's allowlist")]
+    RecipientNotAllowlisted,
+    #[error("Recipient has not opted into auto-staking with this program")]
+    AutoStakeNotOptedIn,
+    #[error("Pre-registered genome must be minted whole to its registrant")]
+    RegistrantMismatch,
+    #[error("Mint exceeds the quorum threshold without enough controller co-signers")]
+    MintQuorumNotMet,
+    #[error("Clawback was already executed or cancelled")]
+    ClawbackNotPending,
+    #[error("Token account has not delegated the clawback amount to the config")]
+    ClawbackNotDelegated,
+    #[error("Supply audit did not count every genome under the config")]
+    AuditIncomplete,
+    #[error("Mint nonce was already processed for this genome")]
+    DuplicateMint,
+    #[error("Genome's bond is still held in escrow")]
+    BondHeld,
+    #[error("Bond stays in escrow while the mint can be challenged or re-scored")]
+    BondLocked,
+    #[error("Genome has no bond in escrow")]
+    NoBond,
+}
+
+impl From<RSMError> for ProgramError {
+    fn from(e: RSMError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct TokenConfig {
+    pub schema_version: u8,
     pub authority: Pubkey,
     pub agi_controller: Pubkey,
     pub max_supply: u64,
     pub total_minted: u64,
     pub agi_params: AGIParameters,
+    pub score_oracle: Pubkey,
+    pub breaker: EmissionBreaker,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub supply_governance: SupplyGovernance,
+    /// 0 = scores never expire
+    pub score_validity_secs: i64,
+    pub bond_bps: u16,
+    /// 0 = mints are owned outright
+    pub stream_slots: u64,
+    pub decay: QualityDecay,
+    pub disputes: DisputePolicy,
+    pub smoothing: EmissionSmoothing,
+    pub require_recipient_signature: bool,
+    pub mint_window: MintWindow,
+    pub params_history: ParamsHistory,
+    pub dust: DustAccumulator,
+    /// Boost for the first mint of a species; 0 = none
+    pub discovery_bonus_bps: u16,
+    pub allowlist: RecipientAllowlist,
+    pub quality_histogram: QualityHistogram,
+    pub mint_quorum: MintQuorum,
+    pub genome_census: GenomeCensus,
+    /// Slashed bond paid to challengers; it left the genomes' counts but
+    /// stays in supply
+    pub dispute_rewards: u64,
+}
+
+impl TokenConfig {
+    /// Version 1 added the version byte, version 2 emission smoothing,
+    /// version 3 recipient consent, version 4 mint windows, version 5 the
+    /// AGI parameter history, version 6 the dust accumulator, version 7
+    /// the discovery bonus, version 8 the recipient allowlist, version 9
+    /// the quality histogram, version 10 the mint quorum, version 11 the
+    /// genome census and version 12 the dispute rewards
+    pub const SCHEMA: Schema = Schema {
+        name: "TokenConfig",
+        upgrades: &[
+            add_version_byte,
+            add_emission_smoothing,
+            add_recipient_consent,
+            add_mint_window,
+            add_params_history,
+            add_dust_accumulator,
+            add_discovery_bonus,
+            add_recipient_allowlist,
+            add_quality_histogram,
+            add_mint_quorum,
+            add_genome_census,
+            add_dispute_rewards,
+        ],
+        is_unversioned: |data| data.len() == TokenConfig::V1_LEN - 1,
+    };
+    pub const LEN: usize = Self::V11_LEN + 8;
+    const V11_LEN: usize = Self::V10_LEN + GenomeCensus::LEN;
+    const V10_LEN: usize = Self::V9_LEN + MintQuorum::LEN;
+    const V9_LEN: usize = Self::V8_LEN + QualityHistogram::LEN;
+    const V8_LEN: usize = Self::V7_LEN + RecipientAllowlist::LEN;
+    const V7_LEN: usize = Self::V6_LEN + 2;
+    const V6_LEN: usize = Self::V5_LEN + DustAccumulator::LEN;
+    const V5_LEN: usize = Self::V4_LEN + ParamsHistory::LEN;
+    const V4_LEN: usize = Self::V3_LEN + MintWindow::LEN;
+    const V3_LEN: usize = Self::V2_LEN + 1;
+    const V2_LEN: usize = Self::V1_LEN + EmissionSmoothing::LEN;
+    const V1_LEN: usize = 1 + 32 + 32 + 8 + 8 + AGIParameters::LEN + 32 + (8 + 2 + 8 + 8 + 8 + 1) + 32 + 1 + SupplyGovernance::LEN + 8 + 2 + 8 + QualityDecay::LEN + DisputePolicy::LEN;
+    
+    /// AGI parameters in force at `slot`: the current ones if they were
+    /// never updated, None if the set has been overwritten in the history.
+    pub fn agi_params_at(&self, slot: u64) -> Option<AGIParameters> {
+        if self.params_history.recorded == 0 {
+            return Some(self.agi_params);
+        }
+        self.params_history.params_at(slot)
+    }
+}
+
+/// Smoothing starts off, so upgraded configs mint as before.
+fn add_emission_smoothing(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V1_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 2;
+    upgraded.resize(TokenConfig::V2_LEN, 0);
+    Ok(upgraded)
+}
+
+/// Upgraded configs keep pushing mints without the recipient's signature.
+fn add_recipient_consent(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V2_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 3;
+    upgraded.push(0);
+    Ok(upgraded)
+}
+
+/// Upgraded configs keep minting all epoch.
+fn add_mint_window(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V3_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 4;
+    upgraded.resize(TokenConfig::V4_LEN, 0);
+    Ok(upgraded)
+}
+
+/// The history starts empty; the first update logs the set it replaces.
+fn add_params_history(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V4_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 5;
+    upgraded.extend(ParamsHistory::default().try_to_vec().map_err(|_| MigrationError::Malformed)?);
+    Ok(upgraded)
+}
+
+/// Dust truncated before the upgrade is gone; counting starts from zero.
+fn add_dust_accumulator(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V5_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 6;
+    upgraded.resize(TokenConfig::V6_LEN, 0);
+    Ok(upgraded)
+}
+
+/// Species discovered before the upgrade earn no bonus until one is set.
+fn add_discovery_bonus(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V6_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 7;
+    upgraded.resize(TokenConfig::V7_LEN, 0);
+    Ok(upgraded)
+}
+
+/// Upgraded configs keep minting to any recipient.
+fn add_recipient_allowlist(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V7_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 8;
+    upgraded.resize(TokenConfig::V8_LEN, 0);
+    Ok(upgraded)
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy)]
+/// Upgraded configs count mints from the upgrade on, without resets.
+fn add_quality_histogram(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V8_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 9;
+    upgraded.resize(TokenConfig::V9_LEN, 0);
+    Ok(upgraded)
+}
+
+/// Upgraded configs mint any amount on the controller's signature alone.
+fn add_mint_quorum(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V9_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 10;
+    upgraded.resize(TokenConfig::V10_LEN, 0);
+    Ok(upgraded)
+}
+
+/// Upgraded configs start untracked: their existing genomes were never
+/// counted, so supply repairs can only raise their total.
+fn add_genome_census(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V10_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 11;
+    upgraded.resize(TokenConfig::V11_LEN, 0);
+    Ok(upgraded)
+}
+
+/// Upgraded configs have paid no challenger out of a slashed bond yet.
+fn add_dispute_rewards(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    if data.len() != TokenConfig::V11_LEN {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = data.to_vec();
+    upgraded[0] = 12;
+    upgraded.resize(TokenConfig::LEN, 0);
+    Ok(upgraded)
+}
+
+pub fn config_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED, mint.as_ref()], program_id)
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AGIParameters {
     pub complexity_weight: u8,
     pub uniqueness_weight: u8,
@@ -90,186 +923,4533 @@ impl Default for AGIParameters {
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct GenomeData {
-    pub hash: [u8; 32],
-    pub owner: Pubkey,
-    pub tokens_minted: u64,
-    pub complexity: u8,
-    pub uniqueness: u8,
-    pub is_minted: bool,
-    pub mint_timestamp: i64,
+impl AGIParameters {
+    pub const LEN: usize = 4 + 8;
+}
+
+/// AGI parameters in force from `activation_slot` on.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ParamsEntry {
+    pub params: AGIParameters,
+    pub activation_slot: u64,
+}
+
+impl ParamsEntry {
+    pub const LEN: usize = AGIParameters::LEN + 8;
+}
+
+/// Ring buffer of the last PARAMS_HISTORY_LEN AGI parameter sets, so a
+/// historical mint can be valued with the weights it was minted under.
+/// The first update also logs the set it replaced, from slot 0.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ParamsHistory {
+    /// Total sets logged; the last PARAMS_HISTORY_LEN are kept in `entries`
+    pub recorded: u32,
+    pub entries: [ParamsEntry; PARAMS_HISTORY_LEN],
+}
+
+impl ParamsHistory {
+    pub const LEN: usize = 4 + PARAMS_HISTORY_LEN * ParamsEntry::LEN;
+    
+    pub fn record(&mut self, params: AGIParameters, activation_slot: u64) {
+        self.entries[self.recorded as usize % PARAMS_HISTORY_LEN] = ParamsEntry { params, activation_slot };
+        self.recorded = self.recorded.saturating_add(1);
+    }
+    
+    /// Retained entries, newest first.
+    pub fn entries(&self) -> impl Iterator<Item = &ParamsEntry> {
+        let recorded = self.recorded as usize;
+        (1..=recorded.min(PARAMS_HISTORY_LEN)).map(move |back| &self.entries[(recorded - back) % PARAMS_HISTORY_LEN])
+    }
+    
+    /// Newest set activated at or before `slot`; None if that set has been
+    /// overwritten.
+    pub fn params_at(&self, slot: u64) -> Option<AGIParameters> {
+        self.entries().find(|entry| entry.activation_slot <= slot).map(|entry| entry.params)
+    }
+}
+
+/// Trips when emissions in the current window exceed `max_multiple_pct` of
+/// the trailing per-window average, which never counts as less than
+/// BREAKER_FLOOR_TOKENS so the first window is bounded too. A mint that
+/// would trip latches the breaker instead of minting, and every mint fails
+/// until ResetBreaker.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmissionBreaker {
+    pub window_slots: u64,
+    pub max_multiple_pct: u16,
+    pub window_start: u64,
+    pub window_emitted: u64,
+    pub trailing_average: u64,
+    pub tripped: bool,
+}
+
+impl Default for EmissionBreaker {
+    fn default() -> Self {
+        Self {
+            window_slots: DEFAULT_BREAKER_WINDOW_SLOTS,
+            max_multiple_pct: DEFAULT_BREAKER_MULTIPLE_PCT,
+            window_start: 0,
+            window_emitted: 0,
+            trailing_average: 0,
+            tripped: false,
+        }
+    }
+}
+
+impl EmissionBreaker {
+    /// BREAKER_FLOOR_TOKENS in base units of a mint with `decimals`
+    pub fn floor_average(decimals: u8) -> u64 {
+        BREAKER_FLOOR_TOKENS.saturating_mul(10u64.saturating_pow(decimals as u32))
+    }
+    
+    /// Records `amount` at `slot`. Returns false (and latches `tripped`) if the
+    /// emission would push the window above the allowed multiple.
+    pub fn record(&mut self, slot: u64, amount: u64, decimals: u8) -> bool {
+        if self.tripped {
+            return false;
+        }
+        
+        if slot >= self.window_start.saturating_add(self.window_slots) {
+            self.trailing_average = if self.trailing_average == 0 {
+                self.window_emitted
+            } else {
+                (self.trailing_average * 3 + self.window_emitted) / 4
+            };
+            self.window_start = slot;
+            self.window_emitted = 0;
+        }
+        
+        let emitted = self.window_emitted.saturating_add(amount);
+        let average = self.trailing_average.max(Self::floor_average(decimals));
+        let limit = (average as u128 * self.max_multiple_pct as u128) / 100;
+        if emitted as u128 > limit {
+            self.tripped = true;
+            return false;
+        }
+        
+        self.window_emitted = emitted;
+        true
+    }
+    
+    pub fn reset(&mut self, slot: u64) {
+        self.tripped = false;
+        self.window_start = slot;
+        self.window_emitted = 0;
+    }
+}
+
+/// Scales rewards down for genomes older than `threshold_secs`: the reward
+/// halves every `half_life_secs` past the threshold, interpolated linearly
+/// within each half-life, and never drops below `floor_bps`. Age past the
+/// threshold counts in whole DECAY_STEP_SECS so callers can predict it.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct QualityDecay {
+    /// 0 = no decay
+    pub threshold_secs: i64,
+    pub half_life_secs: i64,
+    pub floor_bps: u16,
+}
+
+impl QualityDecay {
+    pub const LEN: usize = 8 + 8 + 2;
+    
+    pub fn is_enabled(&self) -> bool {
+        self.threshold_secs > 0
+    }
+    
+    pub fn factor_bps(&self, age_secs: i64) -> u64 {
+        if !self.is_enabled() || age_secs <= self.threshold_secs {
+            return BPS_DENOMINATOR;
+        }
+        let excess = (age_secs - self.threshold_secs) as u64 / DECAY_STEP_SECS * DECAY_STEP_SECS;
+        let half_life = self.half_life_secs.max(1) as u64;
+        let halvings = excess / half_life;
+        let factor = if halvings >= 64 {
+            0
+        } else {
+            let start = BPS_DENOMINATOR >> halvings;
+            start - start * (excess % half_life) / (2 * half_life)
+        };
+        factor.max(self.floor_bps as u64)
+    }
+    
+    pub fn apply(&self, amount: u64, age_secs: i64) -> u64 {
+        (amount as u128 * self.factor_bps(age_secs) as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+/// Keeps emissions near `target_per_epoch` through submission bursts: mints
+/// are scaled by the target over an exponential moving average of emissions
+/// per epoch, clamped to EMISSION_SCALE_MIN_BPS..=EMISSION_SCALE_MAX_BPS.
+/// Each closed epoch, empty ones included, weighs a quarter of the average.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EmissionSmoothing {
+    /// 0 = no smoothing
+    pub target_per_epoch: u64,
+    pub epoch_slots: u64,
+    pub epoch_start: u64,
+    pub epoch_emitted: u64,
+    pub average: u64,
+}
+
+impl EmissionSmoothing {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8;
+    
+    pub fn is_enabled(&self) -> bool {
+        self.target_per_epoch > 0
+    }
+    
+    /// Folds the epochs that closed by `slot` into the average. Past
+    /// EMISSION_EMA_MAX_EPOCHS empty epochs the average stops shrinking.
+    pub fn roll(&mut self, slot: u64) {
+        let epoch_slots = self.epoch_slots.max(1);
+        let closed = slot.saturating_sub(self.epoch_start) / epoch_slots;
+        if closed == 0 {
+            return;
+        }
+        let mut emitted = self.epoch_emitted;
+        for _ in 0..closed.min(EMISSION_EMA_MAX_EPOCHS) {
+            self.average = ((self.average as u128 * 3 + emitted as u128) / 4) as u64;
+            emitted = 0;
+        }
+        self.epoch_start = self.epoch_start.saturating_add(closed.saturating_mul(epoch_slots));
+        self.epoch_emitted = 0;
+    }
+    
+    /// An average of zero is as far under target as it gets.
+    pub fn scale_bps(&self) -> u64 {
+        if !self.is_enabled() {
+            return BPS_DENOMINATOR;
+        }
+        if self.average == 0 {
+            return EMISSION_SCALE_MAX_BPS;
+        }
+        let scale = self.target_per_epoch as u128 * BPS_DENOMINATOR as u128 / self.average as u128;
+        scale.clamp(EMISSION_SCALE_MIN_BPS as u128, EMISSION_SCALE_MAX_BPS as u128) as u64
+    }
+    
+    /// The scale a mint landing at `slot` gets, for callers predicting it.
+    pub fn scale_bps_at(&self, slot: u64) -> u64 {
+        let mut rolled = *self;
+        rolled.roll(slot);
+        rolled.scale_bps()
+    }
+    
+    pub fn record(&mut self, amount: u64) {
+        if self.is_enabled() {
+            self.epoch_emitted = self.epoch_emitted.saturating_add(amount);
+        }
+    }
+    
+    pub fn apply(amount: u64, scale_bps: u64) -> u64 {
+        (amount as u128 * scale_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+/// What the bps scalings of mints (quality decay, emission smoothing and
+/// the discovery bonus) truncate away, in 1/BPS_DENOMINATOR of a base unit.
+/// Governance mints the whole units to `reward_pool` once there are
+/// `threshold` of them.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DustAccumulator {
+    pub dust: u64,
+    /// 0 = never consolidated
+    pub threshold: u64,
+    pub reward_pool: Pubkey,
+    /// Base units minted from dust so far
+    pub consolidated: u64,
+}
+
+impl DustAccumulator {
+    pub const LEN: usize = 8 + 8 + 32 + 8;
+    
+    /// The part of a base unit `amount * scale_bps / BPS_DENOMINATOR` drops.
+    pub fn remainder(amount: u64, scale_bps: u64) -> u64 {
+        (amount as u128 * scale_bps as u128 % BPS_DENOMINATOR as u128) as u64
+    }
+    
+    pub fn record(&mut self, remainder: u64) {
+        self.dust = self.dust.saturating_add(remainder);
+    }
+    
+    pub fn whole_units(&self) -> u64 {
+        self.dust / BPS_DENOMINATOR
+    }
+    
+    pub fn is_due(&self) -> bool {
+        self.threshold > 0 && self.whole_units() >= self.threshold
+    }
+    
+    /// Takes the whole units out, keeping the fraction for later.
+    pub fn take(&mut self) -> u64 {
+        let units = self.whole_units();
+        self.dust -= units * BPS_DENOMINATOR;
+        self.consolidated = self.consolidated.saturating_add(units);
+        units
+    }
+}
+
+/// Mints counted by weighted score, for governance tuning the AGI weights.
+/// Bucket `i` holds scores from `i` to `i + 1` twentieths of
+/// MAX_WEIGHTED_SCORE; a perfect score goes in the top bucket.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct QualityHistogram {
+    pub buckets: [u64; QUALITY_BUCKETS],
+    /// Solana epoch the counts started in
+    pub epoch: u64,
+    /// Counts restart with the first mint of each epoch
+    pub reset_per_epoch: bool,
+}
+
+impl QualityHistogram {
+    pub const LEN: usize = QUALITY_BUCKETS * 8 + 8 + 1;
+    
+    pub fn bucket(weighted_score: u64) -> usize {
+        (weighted_score * QUALITY_BUCKETS as u64 / MAX_WEIGHTED_SCORE).min(QUALITY_BUCKETS as u64 - 1) as usize
+    }
+    
+    pub fn clear(&mut self, epoch: u64) {
+        self.buckets = [0; QUALITY_BUCKETS];
+        self.epoch = epoch;
+    }
+    
+    pub fn record(&mut self, weighted_score: u64, epoch: u64) {
+        if self.reset_per_epoch && epoch != self.epoch {
+            self.clear(epoch);
+        }
+        let bucket = &mut self.buckets[Self::bucket(weighted_score)];
+        *bucket = bucket.saturating_add(1);
+    }
+    
+    pub fn total(&self) -> u64 {
+        self.buckets.iter().fold(0u64, |total, count| total.saturating_add(*count))
+    }
+}
+
+/// Co-signing rule for large mints: above `threshold`, `required` of the
+/// registered `controllers` must sign the mint, so no single key can issue
+/// a large emission alone. Smaller mints need only the controller.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MintQuorum {
+    /// 0 = no quorum
+    pub threshold: u64,
+    pub required: u8,
+    /// Pubkey::default() marks an empty slot
+    pub controllers: [Pubkey; MAX_QUORUM_CONTROLLERS],
+}
+
+impl MintQuorum {
+    pub const LEN: usize = 8 + 1 + 32 * MAX_QUORUM_CONTROLLERS;
+    
+    pub fn new(threshold: u64, required: u8, controllers: &[Pubkey]) -> Option<Self> {
+        if controllers.len() > MAX_QUORUM_CONTROLLERS {
+            return None;
+        }
+        let mut quorum = MintQuorum { threshold, required, ..MintQuorum::default() };
+        quorum.controllers[..controllers.len()].copy_from_slice(controllers);
+        Some(quorum)
+    }
+    
+    pub fn is_enabled(&self) -> bool {
+        self.threshold > 0
+    }
+    
+    /// At least one and at most all of the registered controllers must be
+    /// required, and none may be registered twice.
+    pub fn is_valid(&self) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+        let registered: Vec<&Pubkey> = self.controllers.iter().filter(|key| **key != Pubkey::default()).collect();
+        let distinct = registered.iter().enumerate().all(|(i, key)| !registered[..i].contains(key));
+        self.required > 0 && self.required as usize <= registered.len() && distinct
+    }
+    
+    pub fn applies_to(&self, amount: u64) -> bool {
+        self.is_enabled() && amount > self.threshold
+    }
+    
+    /// Whether `required` distinct registered controllers signed among
+    /// `signers`.
+    pub fn is_met(&self, signers: &[&AccountInfo]) -> bool {
+        let signed = self
+            .controllers
+            .iter()
+            .filter(|key| **key != Pubkey::default())
+            .filter(|key| signers.iter().any(|signer| signer.is_signer && signer.key == *key))
+            .count();
+        signed >= self.required as usize
+    }
+}
+
+/// Number of genome accounts under a config, so a supply audit can prove it
+/// counted every one. Configs upgraded from before the census are untracked
+/// and keep no count.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GenomeCensus {
+    pub tracked: bool,
+    pub count: u64,
+}
+
+impl GenomeCensus {
+    pub const LEN: usize = 1 + 8;
+    
+    pub fn tracked() -> Self {
+        GenomeCensus { tracked: true, count: 0 }
+    }
+    
+    pub fn created(&mut self) -> Result<(), ProgramError> {
+        if self.tracked {
+            self.count = self.count.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+    
+    pub fn closed(&mut self) -> Result<(), ProgramError> {
+        if self.tracked {
+            self.count = self.count.checked_sub(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+}
+
+/// Part of each Solana epoch in which mints are accepted, from `start_bps`
+/// up to (not including) `end_bps` of its slots. Outside it governance has
+/// a quiet period for parameter changes and audits.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MintWindow {
+    pub start_bps: u16,
+    /// 0 = mints are accepted all epoch
+    pub end_bps: u16,
+}
+
+impl MintWindow {
+    pub const LEN: usize = 2 + 2;
+    
+    pub fn is_enabled(&self) -> bool {
+        self.end_bps > 0
+    }
+    
+    pub fn is_valid(&self) -> bool {
+        !self.is_enabled() || (self.start_bps < self.end_bps && self.end_bps as u64 <= BPS_DENOMINATOR)
+    }
+    
+    pub fn is_open(&self, schedule: &EpochSchedule, slot: u64) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+        let (epoch, slot_index) = schedule.get_epoch_and_slot_index(slot);
+        let position_bps = slot_index as u128 * BPS_DENOMINATOR as u128 / schedule.get_slots_in_epoch(epoch) as u128;
+        position_bps >= self.start_bps as u128 && position_bps < self.end_bps as u128
+    }
+}
+
+/// Restricts who may receive mints, for compliance-restricted
+/// deployments: holders of a membership token, or keys in a Merkle tree
+/// of `allowlist_leaf`s whose nodes hash sorted pairs.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RecipientAllowlist {
+    /// Default = no membership token
+    pub membership_mint: Pubkey,
+    /// Zero = no Merkle allowlist
+    pub merkle_root: [u8; 32],
+}
+
+impl RecipientAllowlist {
+    pub const LEN: usize = 32 + 32;
+    
+    pub fn is_enabled(&self) -> bool {
+        self.membership_mint != Pubkey::default() || self.merkle_root != [0u8; 32]
+    }
+    
+    pub fn proves(&self, recipient: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        if self.merkle_root == [0u8; 32] {
+            return false;
+        }
+        let root = proof.iter().fold(allowlist_leaf(recipient), |node, sibling| {
+            if node <= *sibling {
+                hashv(&[&[1u8], &node, sibling]).to_bytes()
+            } else {
+                hashv(&[&[1u8], sibling, &node]).to_bytes()
+            }
+        });
+        root == self.merkle_root
+    }
+    
+    /// Whether `account` is a token account of the membership mint held by
+    /// `recipient` with a nonzero balance.
+    pub fn admits_member(&self, account: &AccountInfo, recipient: &Pubkey) -> Result<bool, ProgramError> {
+        if self.membership_mint == Pubkey::default() {
+            return Ok(false);
+        }
+        // Both token programs share the base account layout
+        if *account.owner != spl_token::id() && *account.owner != spl_token_2022::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let data = account.data.borrow();
+        let holding = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+        Ok(holding.base.mint == self.membership_mint && holding.base.owner == *recipient && holding.base.amount > 0)
+    }
+}
+
+pub fn allowlist_leaf(recipient: &Pubkey) -> [u8; 32] {
+    hashv(&[ALLOWLIST_DOMAIN, &[0u8], recipient.as_ref()]).to_bytes()
+}
+
+/// Who may prove a mint inflated, and for how long after minting.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DisputePolicy {
+    /// 0 = no disputes
+    pub window_secs: i64,
+    pub reward_bps: u16,
+    /// Oracle key whose signed re-scores count as evidence
+    pub evidence_signer: Pubkey,
+}
+
+impl DisputePolicy {
+    pub const LEN: usize = 8 + 2 + 32;
+    
+    pub fn is_enabled(&self) -> bool {
+        self.window_secs > 0
+    }
+    
+    pub fn is_open(&self, mint_timestamp: i64, now: i64) -> bool {
+        self.is_enabled() && now <= mint_timestamp.saturating_add(self.window_secs)
+    }
+    
+    /// Challenger's share of a slash; the rest leaves the supply.
+    pub fn reward(&self, slashed: u64) -> u64 {
+        (slashed as u128 * self.reward_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SupplyRaise {
+    pub old_max_supply: u64,
+    pub new_max_supply: u64,
+    pub executed_at: i64,
+}
+
+/// Timelocked, dual-signed max supply raises. Raises inside one
+/// SUPPLY_RAISE_WINDOW may add at most SUPPLY_RAISE_MAX_PCT to the max
+/// supply the window opened with; the window opens on the first raise after
+/// the previous one lapsed.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SupplyGovernance {
+    pub executor: Pubkey,
+    /// 0 when nothing is scheduled
+    pub pending_max_supply: u64,
+    pub pending_eta: i64,
+    pub window_start: i64,
+    pub window_base: u64,
+    /// Total raises applied; the last SUPPLY_HISTORY_LEN are kept in `history`
+    pub raises: u32,
+    pub history: [SupplyRaise; SUPPLY_HISTORY_LEN],
+}
+
+impl SupplyGovernance {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 4 + SUPPLY_HISTORY_LEN * (8 + 8 + 8);
+    
+    fn window_lapsed(&self, at: i64) -> bool {
+        self.raises == 0 || at >= self.window_start.saturating_add(SUPPLY_RAISE_WINDOW)
+    }
+    
+    /// Highest max supply a raise applied at `at` may set.
+    pub fn raise_cap(&self, current_max_supply: u64, at: i64) -> u64 {
+        let base = if self.window_lapsed(at) {
+            current_max_supply
+        } else {
+            self.window_base
+        };
+        base.saturating_add((base as u128 * SUPPLY_RAISE_MAX_PCT as u128 / 100) as u64)
+    }
+    
+    pub fn check_raise(&self, current_max_supply: u64, new_max_supply: u64, at: i64) -> Result<(), RSMError> {
+        if new_max_supply > self.raise_cap(current_max_supply, at) {
+            return Err(RSMError::RaiseExceedsCap);
+        }
+        Ok(())
+    }
+    
+    /// Applies the scheduled raise at `now` and returns it.
+    pub fn apply(&mut self, current_max_supply: u64, now: i64) -> Result<SupplyRaise, RSMError> {
+        if self.pending_max_supply == 0 {
+            return Err(RSMError::NoPendingRaise);
+        }
+        if now < self.pending_eta {
+            return Err(RSMError::RaiseTimelocked);
+        }
+        self.check_raise(current_max_supply, self.pending_max_supply, now)?;
+        
+        if self.window_lapsed(now) {
+            self.window_start = now;
+            self.window_base = current_max_supply;
+        }
+        let raise = SupplyRaise {
+            old_max_supply: current_max_supply,
+            new_max_supply: self.pending_max_supply,
+            executed_at: now,
+        };
+        self.history[self.raises as usize % SUPPLY_HISTORY_LEN] = raise;
+        self.raises = self.raises.saturating_add(1);
+        self.pending_max_supply = 0;
+        self.pending_eta = 0;
+        Ok(raise)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DedupeEntry {
+    pub nonce: u64,
+    pub genome_prefix: [u8; 8],
+    pub slot: u64,
+}
+
+/// Ring buffer of recently processed (genome, nonce) pairs at PDA
+/// ["dedupe", config]. Entries only matter while their slot is inside the
+/// validity window, so a new pair may take any expired entry. The ring is
+/// as long as the account has room for: buffers created with 64 entries
+/// keep working until CreateDedupeBuffer grows them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MintDedupe {
+    pub next: u16,
+    pub entries: Vec<DedupeEntry>,
+}
+
+impl Default for MintDedupe {
+    fn default() -> Self {
+        Self {
+            next: 0,
+            entries: vec![DedupeEntry::default(); DEDUPE_CAPACITY],
+        }
+    }
+}
+
+impl MintDedupe {
+    pub const ENTRY_LEN: usize = 8 + 8 + 8;
+    pub const LEN: usize = 2 + DEDUPE_CAPACITY * Self::ENTRY_LEN;
+    
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < 2 + Self::ENTRY_LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let next = u16::from_le_bytes([data[0], data[1]]);
+        let entries = data[2..]
+            .chunks_exact(Self::ENTRY_LEN)
+            .map(DedupeEntry::try_from_slice)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { next, entries })
+    }
+    
+    pub fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        let mut writer = data;
+        self.next.serialize(&mut writer)?;
+        for entry in &self.entries {
+            entry.serialize(&mut writer)?;
+        }
+        Ok(())
+    }
+    
+    /// Records the pair in the first expired entry from `next` on. A pair
+    /// already recorded inside the validity window is a DuplicateMint.
+    pub fn check_and_record(
+        &mut self,
+        genome_hash: &[u8; 32],
+        nonce: u64,
+        recent_slot: u64,
+        current_slot: u64,
+    ) -> Result<(), RSMError> {
+        if recent_slot > current_slot || current_slot - recent_slot > NONCE_VALIDITY_SLOTS {
+            return Err(RSMError::StaleNonce);
+        }
+        
+        let mut genome_prefix = [0u8; 8];
+        genome_prefix.copy_from_slice(&genome_hash[..8]);
+        
+        let live = |e: &DedupeEntry| e.slot != 0 && current_slot.saturating_sub(e.slot) <= NONCE_VALIDITY_SLOTS;
+        
+        if self.entries.iter().any(|e| live(e) && e.nonce == nonce && e.genome_prefix == genome_prefix) {
+            return Err(RSMError::DuplicateMint);
+        }
+        
+        let capacity = self.entries.len();
+        let index = (0..capacity)
+            .map(|offset| (self.next as usize + offset) % capacity)
+            .find(|&index| !live(&self.entries[index]))
+            .ok_or(RSMError::DedupeBufferFull)?;
+        
+        self.entries[index] = DedupeEntry { nonce, genome_prefix, slot: recent_slot };
+        self.next = ((index + 1) % capacity) as u16;
+        Ok(())
+    }
+}
+
+/// Running state of a paged supply audit at PDA ["audit", config, auditor].
+/// Pages must be passed in strictly increasing address order so no genome
+/// is counted twice.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct SupplyAudit {
+    pub auditor: Pubkey,
+    pub started_slot: u64,
+    pub recorded_at_start: u64,
+    pub computed_total: u64,
+    pub genomes_counted: u64,
+    pub cursor: Pubkey,
+    pub finished: bool,
+}
+
+impl SupplyAudit {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 32 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+pub struct SupplyAuditEvent {
+    pub config: Pubkey,
+    pub auditor: Pubkey,
+    pub recorded_total: u64,
+    pub computed_total: u64,
+    pub discrepancy: i128,
+    pub genomes_counted: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct GenomeMintedEvent {
+    pub genome_hash: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub entropy: u16,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct GenomeBurnedEvent {
+    pub genome_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SupplyRepairedEvent {
+    pub config: Pubkey,
+    pub old_total: u64,
+    pub new_total: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct GenomeRescoredEvent {
+    pub genome_hash: [u8; 32],
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub rescored_amount: u64,
+    pub clawback: u64,
+    pub clawback_eta: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ClawbackExecutedEvent {
+    pub genome_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct StreamWithdrawnEvent {
+    pub genome_hash: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub released: u64,
+    pub completed: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MintSplitPaidEvent {
+    pub genome_hash: [u8; 32],
+    pub token_account: Pubkey,
+    pub bps: u16,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MaxSupplyRaisedEvent {
+    pub config: Pubkey,
+    pub old_max_supply: u64,
+    pub new_max_supply: u64,
+    pub executed_at: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct GenomeNoteSetEvent {
+    pub genome_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub note: Vec<u8>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MintChallengedEvent {
+    pub genome_hash: [u8; 32],
+    pub challenger: Pubkey,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub slashed: u64,
+    pub reward: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct DustConsolidatedEvent {
+    pub config: Pubkey,
+    pub reward_pool: Pubkey,
+    pub amount: u64,
+    pub consolidated: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SpeciesDiscoveredEvent {
+    pub species: u16,
+    pub genome_hash: [u8; 32],
+    pub discoverer: Pubkey,
+    pub bonus_bps: u16,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MintAutoStakedEvent {
+    pub genome_hash: [u8; 32],
+    pub recipient: Pubkey,
+    pub staking_program: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct GenomePreRegisteredEvent {
+    pub genome_hash: [u8; 32],
+    pub registrant: Pubkey,
+    pub slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct EmergencyClawbackEvent {
+    pub config: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub evidence_hash: [u8; 32],
+    pub reason_code: u16,
+}
+
+/// Soulbound record of a contribution, stored at PDA ["receipt", config, genome_hash]
+/// next to the non-transferable receipt NFT.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct GenomeReceipt {
+    pub genome_hash: [u8; 32],
+    pub receipt_mint: Pubkey,
+    pub recipient: Pubkey,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub entropy: u16,
+    pub amount: u64,
+    pub slot: u64,
+}
+
+impl GenomeReceipt {
+    pub const LEN: usize = 32 + 32 + 32 + 1 + 1 + 2 + 8 + 8;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct GenomeData {
+    pub schema_version: u8,
+    pub hash: [u8; 32],
+    pub owner: Pubkey,
+    pub tokens_minted: u64,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub is_minted: bool,
+    pub mint_timestamp: i64,
+    pub rent_payer: Pubkey,
+    pub entropy: u16,
+    pub mint_slot: u64,
+    pub scored_at: i64,
+    /// Part of tokens_minted that a lower re-score or a challenge can claw
+    /// back, escrowed in the bond vault until released
+    pub bonded_amount: u64,
+    pub pending_clawback: u64,
+    pub clawback_eta: i64,
+    /// Emission smoothing applied to tokens_minted, discovery bonus
+    /// included; re-scores value the genome at the same scale
+    pub emission_scale_bps: u16,
+    /// Blockchain component of the amount, 0-99, drawn from the hash of
+    /// the mint's recent slot
+    pub blockchain_entropy: u8,
+    /// Who pre-registered the genome, and whom its mint must pay;
+    /// Pubkey::default() when it was not pre-registered
+    pub registrant: Pubkey,
+    /// Owner's free-form note; the account grows to fit it
+    pub note: Vec<u8>,
+}
+
+impl GenomeData {
+    /// Version 1 added the version byte, version 2 the emission scale,
+    /// version 3 the blockchain entropy, version 4 the registrant
+    pub const SCHEMA: Schema = Schema {
+        name: "GenomeData",
+        upgrades: &[add_version_byte, add_emission_scale, add_blockchain_entropy, add_registrant],
+        is_unversioned: is_unversioned_genome,
+    };
+    /// Size without a note
+    pub const LEN: usize = Self::V3_LEN + 32;
+    const V3_LEN: usize = Self::V2_LEN + 1;
+    const V2_LEN: usize = Self::V1_LEN + 2;
+    const V1_LEN: usize = 1 + 32 + 32 + 8 + 1 + 1 + 1 + 8 + 32 + 2 + 8 + 8 + 8 + 8 + 8 + 4;
+    
+    pub fn space(&self) -> usize {
+        Self::LEN + self.note.len()
+    }
+    
+    /// Pre-registered and awaiting its mint
+    pub fn is_pending(&self) -> bool {
+        !self.is_minted && self.registrant != Pubkey::default()
+    }
+}
+
+/// Amount a re-score worth `rescored_amount` claws back: the shortfall
+/// against what was minted, limited to the bond.
+pub fn rescore_clawback(tokens_minted: u64, rescored_amount: u64, bonded_amount: u64) -> u64 {
+    tokens_minted.saturating_sub(rescored_amount).min(bonded_amount)
+}
+
+/// Part of a mint of `amount` bonded at `bond_bps`.
+pub fn bond_amount(amount: u64, bond_bps: u16) -> u64 {
+    (amount as u128 * bond_bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// A bond leaves escrow once its mint can no longer be challenged and, while
+/// scores expire, CLAWBACK_DELAY after the score has, so the controller has
+/// time to re-score it; never while a clawback is pending.
+pub fn bond_releasable(config: &TokenConfig, genome: &GenomeData, now: i64) -> bool {
+    if genome.pending_clawback > 0 || config.disputes.is_open(genome.mint_timestamp, now) {
+        return false;
+    }
+    config.score_validity_secs == 0
+        || now >= genome.scored_at.saturating_add(config.score_validity_secs).saturating_add(CLAWBACK_DELAY)
+}
+
+/// The config's token account escrowing bonds, owned by the config PDA.
+pub fn bond_vault_address(program_id: &Pubkey, config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOND_VAULT_SEED, config.as_ref()], program_id)
+}
+
+/// Unversioned genomes end in the note, whose length prefix closes the
+/// fixed part one byte earlier than in a versioned genome.
+fn is_unversioned_genome(data: &[u8]) -> bool {
+    note_fits(data, GenomeData::V1_LEN - 1)
+}
+
+/// Whether the note length prefix ending at `fixed` accounts for the rest
+/// of `data`.
+fn note_fits(data: &[u8], fixed: usize) -> bool {
+    data.get(fixed - 4..fixed)
+        .map(|prefix| u32::from_le_bytes(prefix.try_into().expect("4-byte prefix")) as usize)
+        .is_some_and(|note_len| data.len() == fixed + note_len)
+}
+
+/// Genomes minted before smoothing were minted unscaled.
+fn add_emission_scale(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    let fixed = GenomeData::V1_LEN;
+    if !note_fits(data, fixed) {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = Vec::with_capacity(data.len() + 2);
+    upgraded.push(2);
+    upgraded.extend_from_slice(&data[1..fixed - 4]);
+    upgraded.extend_from_slice(&(BPS_DENOMINATOR as u16).to_le_bytes());
+    upgraded.extend_from_slice(&data[fixed - 4..]);
+    Ok(upgraded)
+}
+
+/// Genomes minted before slot hashes were mixed in drew the blockchain
+/// component from their mint slot.
+fn add_blockchain_entropy(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    let fixed = GenomeData::V2_LEN;
+    if !note_fits(data, fixed) {
+        return Err(MigrationError::Malformed);
+    }
+    // Read in place, as later versions no longer deserialize from here
+    const MINT_SLOT: usize = 1 + 32 + 32 + 8 + 1 + 1 + 1 + 8 + 32 + 2;
+    let mint_slot = u64::from_le_bytes(data[MINT_SLOT..MINT_SLOT + 8].try_into().expect("8-byte slot"));
+    let mut upgraded = Vec::with_capacity(data.len() + 1);
+    upgraded.push(3);
+    upgraded.extend_from_slice(&data[1..fixed - 4]);
+    upgraded.push((mint_slot % 100) as u8);
+    upgraded.extend_from_slice(&data[fixed - 4..]);
+    Ok(upgraded)
+}
+
+/// Genomes from before pre-registration have no registrant.
+fn add_registrant(data: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    let fixed = GenomeData::V3_LEN;
+    if !note_fits(data, fixed) {
+        return Err(MigrationError::Malformed);
+    }
+    let mut upgraded = Vec::with_capacity(data.len() + 32);
+    upgraded.push(4);
+    upgraded.extend_from_slice(&data[1..fixed - 4]);
+    upgraded.extend_from_slice(&[0u8; 32]);
+    upgraded.extend_from_slice(&data[fixed - 4..]);
+    Ok(upgraded)
+}
+
+/// Hash of `slot` in raw SlotHashes sysvar data: a u64 count, then
+/// (slot, hash) entries newest first.
+pub fn slot_hash_at(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    const ENTRY_LEN: usize = 8 + 32;
+    let count = u64::from_le_bytes(data.get(..8)?.try_into().ok()?) as usize;
+    let entries = data.get(8..8 + count.checked_mul(ENTRY_LEN)?)?;
+    let slot_of = |index: usize| u64::from_le_bytes(entries[index * ENTRY_LEN..index * ENTRY_LEN + 8].try_into().unwrap());
+    let (mut low, mut high) = (0, count);
+    while low < high {
+        let mid = (low + high) / 2;
+        match slot_of(mid).cmp(&slot) {
+            std::cmp::Ordering::Equal => {
+                let start = mid * ENTRY_LEN + 8;
+                return entries[start..start + 32].try_into().ok();
+            }
+            std::cmp::Ordering::Greater => low = mid + 1,
+            std::cmp::Ordering::Less => high = mid,
+        }
+    }
+    None
+}
+
+/// Newest (slot, hash) entry of raw SlotHashes sysvar data; clients mint
+/// against it as their recent_slot.
+pub fn newest_slot_hash(data: &[u8]) -> Option<(u64, [u8; 32])> {
+    if u64::from_le_bytes(data.get(..8)?.try_into().ok()?) == 0 {
+        return None;
+    }
+    let slot = u64::from_le_bytes(data.get(8..16)?.try_into().ok()?);
+    Some((slot, data.get(16..48)?.try_into().ok()?))
+}
+
+/// Blockchain component of a mint, 0-99: unpredictable until `slot_hash`
+/// exists, and different for each genome minted against it.
+pub fn blockchain_entropy(genome_hash: &[u8; 32], slot_hash: &[u8; 32]) -> u8 {
+    let mixed = hashv(&[genome_hash.as_ref(), slot_hash.as_ref()]).to_bytes();
+    (u64::from_le_bytes(mixed[..8].try_into().unwrap()) % 100) as u8
+}
+
+pub fn genome_address(program_id: &Pubkey, config: &Pubkey, genome_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GENOME_SEED, config.as_ref(), genome_hash], program_id)
+}
+
+pub fn receipt_address(program_id: &Pubkey, config: &Pubkey, genome_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECEIPT_SEED, config.as_ref(), genome_hash], program_id)
+}
+
+/// Outcome of a successful challenge, stored at PDA ["dispute", genome,
+/// mint_slot] so each mint of a genome can be disputed once.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct DisputeRecord {
+    pub genome_hash: [u8; 32],
+    pub challenger: Pubkey,
+    pub mint_slot: u64,
+    pub minted_complexity: u8,
+    pub minted_uniqueness: u8,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub slashed: u64,
+    pub reward: u64,
+    pub disputed_at: i64,
+}
+
+impl DisputeRecord {
+    pub const LEN: usize = 32 + 32 + 8 + 1 + 1 + 1 + 1 + 8 + 8 + 8;
+}
+
+pub fn dispute_address(program_id: &Pubkey, genome: &Pubkey, mint_slot: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISPUTE_SEED, genome.as_ref(), &mint_slot.to_le_bytes()], program_id)
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ClawbackStatus {
+    #[default]
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+/// Evidence behind an emergency clawback and its outcome, at PDA
+/// ["clawback", config, evidence_hash]; kept once executed or vetoed, so
+/// the same evidence cannot be used twice.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct ClawbackRecord {
+    pub config: Pubkey,
+    pub token_account: Pubkey,
+    /// Owner of the token account when scheduled
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub evidence_hash: [u8; 32],
+    pub reason_code: u16,
+    pub scheduled_at: i64,
+    pub eta: i64,
+    pub status: ClawbackStatus,
+    /// 0 unless executed
+    pub executed_at: i64,
+}
+
+impl ClawbackRecord {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 32 + 2 + 8 + 8 + 1 + 8;
+}
+
+pub fn clawback_address(program_id: &Pubkey, config: &Pubkey, evidence_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAWBACK_SEED, config.as_ref(), evidence_hash], program_id)
+}
+
+/// Bytes the evidence signer signs to attest a re-score of one mint.
+pub fn rescore_evidence_message(config: &Pubkey, genome_hash: &[u8; 32], mint_slot: u64, complexity: u8, uniqueness: u8) -> Vec<u8> {
+    let mut message = Vec::with_capacity(RESCORE_EVIDENCE_DOMAIN.len() + 32 + 32 + 8 + 2);
+    message.extend_from_slice(RESCORE_EVIDENCE_DOMAIN);
+    message.extend_from_slice(config.as_ref());
+    message.extend_from_slice(genome_hash);
+    message.extend_from_slice(&mint_slot.to_le_bytes());
+    message.extend_from_slice(&[complexity, uniqueness]);
+    message
+}
+
+/// Whether Ed25519 program data checks exactly one signature by `signer`
+/// over `message`, with every offset pointing into the instruction itself.
+pub fn ed25519_verifies(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    if data.len() < OFFSETS_START + OFFSETS_LEN || data[0] != 1 {
+        return false;
+    }
+    let field = |index: usize| {
+        let at = OFFSETS_START + index * 2;
+        u16::from_le_bytes([data[at], data[at + 1]]) as usize
+    };
+    let (signature_ix, key_offset, key_ix) = (field(1), field(2), field(3));
+    let (message_offset, message_size, message_ix) = (field(4), field(5), field(6));
+    let this_ix = u16::MAX as usize;
+    if signature_ix != this_ix || key_ix != this_ix || message_ix != this_ix {
+        return false;
+    }
+    data.get(key_offset..key_offset + 32) == Some(signer.as_ref())
+        && message_size == message.len()
+        && data.get(message_offset..message_offset + message_size) == Some(message)
+}
+
+/// Session key allowed to mint on behalf of the agi_controller, stored at
+/// PDA ["mint_delegate", config, delegate].
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct MintDelegate {
+    pub config: Pubkey,
+    pub delegate: Pubkey,
+    pub max_amount: u64,
+    pub minted_amount: u64,
+    pub max_mints: u32,
+    pub mints_used: u32,
+    pub expiry_slot: u64,
+}
+
+impl MintDelegate {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 4 + 4 + 8;
+    
+    /// Charges one mint of `amount` against the limits.
+    pub fn consume(&mut self, amount: u64, slot: u64) -> Result<(), RSMError> {
+        if slot > self.expiry_slot {
+            return Err(RSMError::DelegateExpired);
+        }
+        let minted_amount = self.minted_amount
+            .checked_add(amount)
+            .ok_or(RSMError::DelegateLimitExceeded)?;
+        if minted_amount > self.max_amount || self.mints_used >= self.max_mints {
+            return Err(RSMError::DelegateLimitExceeded);
+        }
+        
+        self.minted_amount = minted_amount;
+        self.mints_used += 1;
+        Ok(())
+    }
+}
+
+pub fn mint_delegate_address(program_id: &Pubkey, config: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_DELEGATE_SEED, config.as_ref(), delegate.as_ref()], program_id)
+}
+
+/// Owner approval for `delegate` to burn a genome without the owner's
+/// signature, stored at PDA ["burn_approval", genome, delegate]. Only
+/// valid while `owner` still owns the genome.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct BurnApproval {
+    pub genome: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub remaining_amount: u64,
+    pub expiry_slot: u64,
+}
+
+impl BurnApproval {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8;
+    
+    /// Charges one burn of `amount` against the allowance.
+    pub fn consume(&mut self, amount: u64, slot: u64) -> Result<(), RSMError> {
+        if slot > self.expiry_slot {
+            return Err(RSMError::BurnApprovalExpired);
+        }
+        self.remaining_amount = self.remaining_amount
+            .checked_sub(amount)
+            .ok_or(RSMError::BurnAllowanceExceeded)?;
+        Ok(())
+    }
+}
+
+pub fn burn_approval_address(program_id: &Pubkey, genome: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BURN_APPROVAL_SEED, genome.as_ref(), delegate.as_ref()], program_id)
+}
+
+/// Linear release of a streamed mint, stored at PDA ["stream", genome].
+/// The stream owns the genome until the last withdrawal hands it to
+/// `recipient`; a clawback shrinks what is left to vest.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct StreamVault {
+    pub genome: Pubkey,
+    pub recipient: Pubkey,
+    pub rent_payer: Pubkey,
+    pub released: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+}
+
+impl StreamVault {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 8;
+    
+    /// Part of `total` vested at `slot`.
+    pub fn vested(&self, total: u64, slot: u64) -> u64 {
+        if slot >= self.end_slot {
+            return total;
+        }
+        let elapsed = slot.saturating_sub(self.start_slot);
+        let duration = self.end_slot - self.start_slot;
+        (total as u128 * elapsed as u128 / duration as u128) as u64
+    }
+}
+
+pub fn stream_address(program_id: &Pubkey, genome: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STREAM_SEED, genome.as_ref()], program_id)
+}
+
+/// First sighting of a species tag, stored at PDA ["species", config,
+/// species] and created by the mint that discovered it.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct SpeciesRecord {
+    pub species: u16,
+    pub first_genome: [u8; 32],
+    pub discoverer: Pubkey,
+    pub discovered_slot: u64,
+    /// Discovery bonus the first genome was minted with
+    pub bonus_bps: u16,
+    pub genomes_minted: u64,
+}
+
+impl SpeciesRecord {
+    pub const LEN: usize = 2 + 32 + 32 + 8 + 2 + 8;
+}
+
+pub fn species_address(program_id: &Pubkey, config: &Pubkey, species: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SPECIES_SEED, config.as_ref(), &species.to_le_bytes()], program_id)
+}
+
+/// Recipient's consent to have its mints staked with `staking_program`,
+/// stored at PDA ["auto_stake", config, recipient].
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct AutoStakeOptIn {
+    pub config: Pubkey,
+    pub recipient: Pubkey,
+    pub staking_program: Pubkey,
+}
+
+impl AutoStakeOptIn {
+    pub const LEN: usize = 32 + 32 + 32;
+}
+
+pub fn auto_stake_address(program_id: &Pubkey, config: &Pubkey, recipient: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUTO_STAKE_SEED, config.as_ref(), recipient.as_ref()], program_id)
+}
+
+/// Signer PDA ["stake_authority"] the staking program accepts auto-staked
+/// mints from.
+pub fn stake_authority_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_AUTHORITY_SEED], program_id)
+}
+
+/// The staking program's Anchor `stake_minted(amount)` instruction; its
+/// accounts are the stake config, stake account, stake vault, recipient
+/// and this program's stake authority.
+pub fn stake_minted_instruction(staking_program: &Pubkey, accounts: [&Pubkey; 5], amount: u64) -> Instruction {
+    let [stake_config, stake_account, stake_vault, recipient, stake_authority] = accounts;
+    let mut data = hash(b"global:stake_minted").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction {
+        program_id: *staking_program,
+        accounts: vec![
+            AccountMeta::new(*stake_config, false),
+            AccountMeta::new(*stake_account, false),
+            AccountMeta::new_readonly(*stake_vault, false),
+            AccountMeta::new_readonly(*recipient, false),
+            AccountMeta::new_readonly(*stake_authority, true),
+        ],
+        data,
+    }
+}
+
+/// Divides `amount` by bps shares; the last share takes the rounding dust
+/// so the parts always sum to `amount`.
+pub fn split_amounts(amount: u64, split_bps: &[u16]) -> Result<Vec<u64>, RSMError> {
+    if split_bps.is_empty()
+        || split_bps.len() > MAX_SPLIT_RECIPIENTS
+        || split_bps.contains(&0)
+        || split_bps.iter().map(|bps| *bps as u64).sum::<u64>() != BPS_DENOMINATOR
+    {
+        return Err(RSMError::InvalidSplit);
+    }
+    
+    let mut parts: Vec<u64> = split_bps
+        .iter()
+        .map(|bps| (amount as u128 * *bps as u128 / BPS_DENOMINATOR as u128) as u64)
+        .collect();
+    let dust = amount - parts.iter().sum::<u64>();
+    if let Some(last) = parts.last_mut() {
+        *last += dust;
+    }
+    Ok(parts)
+}
+
+/// Mirror of the consciousness oracle's CanonicalScore account (after the
+/// 8-byte Anchor discriminator).
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct CanonicalScore {
+    pub genome_hash: [u8; 32],
+    pub consciousness: u8,
+    pub complexity: u8,
+    pub uniqueness: u8,
+    pub submissions: u8,
+    pub finalized_at: i64,
+    pub bump: u8,
+}
+
+impl CanonicalScore {
+    pub fn discriminator() -> [u8; 8] {
+        let mut disc = [0u8; 8];
+        disc.copy_from_slice(&hash(b"account:CanonicalScore").to_bytes()[..8]);
+        disc
+    }
+
+    /// Scores are scoped to the oracle config staking `rsm_mint`, so another
+    /// config on the same oracle program cannot vouch for this token.
+    pub fn load(
+        account: &AccountInfo,
+        oracle_program: &Pubkey,
+        rsm_mint: &Pubkey,
+        genome_hash: &[u8; 32],
+    ) -> Result<Self, ProgramError> {
+        if account.owner != oracle_program {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let data = account.data.borrow();
+        if data.len() < 8 || data[..8] != Self::discriminator() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let score = Self::deserialize(&mut &data[8..])?;
+        let (oracle_config, _) = oracle_config_address(oracle_program, rsm_mint);
+        let expected = Pubkey::create_program_address(&[b"score", oracle_config.as_ref(), genome_hash, &[score.bump]], oracle_program)
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+        if expected != *account.key || score.genome_hash != *genome_hash {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(score)
+    }
+}
+
+/// The consciousness oracle's config PDA for the token staked as `rsm_mint`.
+pub fn oracle_config_address(oracle_program: &Pubkey, rsm_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"oracle", rsm_mint.as_ref()], oracle_program)
+}
+
+pub fn process_initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_supply: u64,
+    agi_controller: Pubkey,
+    decimals: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if max_supply == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    // Both token programs share the base mint layout
+    if *mint_account.owner != spl_token::id() && *mint_account.owner != spl_token_2022::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mint_data = mint_account.data.borrow();
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    if mint.base.decimals != decimals {
+        msg!("Decimals mismatch: mint has {}, got {}", mint.base.decimals, decimals);
+        return Err(ProgramError::InvalidArgument);
+    }
+    drop(mint_data);
+    
+    let (config_key, bump) = config_address(program_id, mint_account.key);
+    if config_key != *config_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let rent = Rent::get()?;
+    invoke_signed(
+        &system_instruction::create_account(
+            authority.key,
+            config_account.key,
+            rent.minimum_balance(TokenConfig::LEN),
+            TokenConfig::LEN as u64,
+            program_id,
+        ),
+        &[authority.clone(), config_account.clone(), system_program.clone()],
+        &[&[CONFIG_SEED, mint_account.key.as_ref(), &[bump]]],
+    )?;
+    
+    let config = TokenConfig {
+        schema_version: TokenConfig::SCHEMA.current(),
+        authority: *authority.key,
+        agi_controller,
+        max_supply,
+        total_minted: 0,
+        agi_params: AGIParameters::default(),
+        score_oracle: Pubkey::default(),
+        breaker: EmissionBreaker::default(),
+        mint: *mint_account.key,
+        decimals,
+        supply_governance: SupplyGovernance::default(),
+        score_validity_secs: 0,
+        bond_bps: 0,
+        stream_slots: 0,
+        decay: QualityDecay::default(),
+        disputes: DisputePolicy::default(),
+        smoothing: EmissionSmoothing::default(),
+        require_recipient_signature: false,
+        mint_window: MintWindow::default(),
+        params_history: ParamsHistory::default(),
+        dust: DustAccumulator::default(),
+        discovery_bonus_bps: 0,
+        allowlist: RecipientAllowlist::default(),
+        quality_histogram: QualityHistogram::default(),
+        mint_quorum: MintQuorum::default(),
+        genome_census: GenomeCensus::tracked(),
+        dispute_rewards: 0,
+    };
+    
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    msg!("RSM Token initialized for mint {} with max supply: {} ({} decimals)", mint_account.key, max_supply, decimals);
+    
+    Ok(())
+}
+
+pub fn process_mint_from_genome(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MintArgs,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let MintArgs { genome_hash, complexity, uniqueness, entropy, amount, mint_receipt, nonce, recent_slot, split_bps, origin, species, allowlist_proof, auto_stake } = args;
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let agi_controller = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    let dedupe_account = next_account_info(account_info_iter)?;
+    let slot_hashes_sysvar = next_account_info(account_info_iter)?;
+    
+    if !agi_controller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *mint_account.key != config.mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if config.require_recipient_signature && !recipient_account.is_signer {
+        return Err(RSMError::RecipientSignatureRequired.into());
+    }
+    
+    // A signer other than the controller must be a delegate; its record
+    // follows the dedupe buffer
+    let delegate_account = if *agi_controller.key != config.agi_controller {
+        let delegate_account = next_account_info(account_info_iter)?;
+        let (delegate_key, _) = mint_delegate_address(program_id, config_account.key, agi_controller.key);
+        if delegate_key != *delegate_account.key || delegate_account.owner != program_id {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Some(delegate_account)
+    } else {
+        None
+    };
+    
+    let (dedupe_key, _) = Pubkey::find_program_address(&[DEDUPE_SEED, config_account.key.as_ref()], program_id);
+    if dedupe_key != *dedupe_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if genome_address(program_id, config_account.key, &genome_hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    if !config.mint_window.is_open(&EpochSchedule::get()?, clock.slot) {
+        return Err(RSMError::MintWindowClosed.into());
+    }
+    
+    // Written once the breaker lets the mint through
+    let mut dedupe = MintDedupe::unpack(&dedupe_account.data.borrow())?;
+    dedupe.check_and_record(&genome_hash, nonce, recent_slot, clock.slot)?;
+    
+    // recent_slot is within NONCE_VALIDITY_SLOTS, so its hash is still in
+    // the sysvar unless the slot was skipped
+    if *slot_hashes_sysvar.key != slot_hashes::id() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let slot_hash = slot_hash_at(&slot_hashes_sysvar.data.borrow(), recent_slot).ok_or(RSMError::UnknownSlotHash)?;
+    let blockchain = blockchain_entropy(&genome_hash, &slot_hash);
+    
+    if complexity > 100 || uniqueness > 100 || entropy > 200 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    if config.score_oracle != Pubkey::default() {
+        let score_account = next_account_info(account_info_iter)?;
+        let score = CanonicalScore::load(score_account, &config.score_oracle, &config.mint, &genome_hash)?;
+        if score.complexity != complexity || score.uniqueness != uniqueness {
+            msg!("Scores do not match oracle: complexity {}, uniqueness {}", score.complexity, score.uniqueness);
+            return Err(ProgramError::InvalidArgument);
+        }
+    }
+    
+    // A streaming config vests the mint through a stream PDA, which owns
+    // the genome meanwhile; a species-tagged mint keeps count in the
+    // species record. Stream, species record, payer and system program
+    // follow the score account, each only when used
+    let stream_account = if config.stream_slots > 0 {
+        Some(next_account_info(account_info_iter)?)
+    } else {
+        None
+    };
+    let species_account = if species != 0 {
+        let species_account = next_account_info(account_info_iter)?;
+        if species_address(program_id, config_account.key, species).0 != *species_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !species_account.data_is_empty() && species_account.owner != program_id {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Some(species_account)
+    } else {
+        None
+    };
+    let funding = if stream_account.is_some() || species_account.is_some() {
+        let payer = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        if !payer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Some((payer, system_program))
+    } else {
+        None
+    };
+    // An allowlisted config admits the recipient by Merkle proof or by a
+    // membership token account, which follows payer and system program
+    if config.allowlist.is_enabled() {
+        let admitted = match &allowlist_proof {
+            Some(proof) => config.allowlist.proves(recipient_account.key, proof),
+            None => config.allowlist.admits_member(next_account_info(account_info_iter)?, recipient_account.key)?,
+        };
+        if !admitted {
+            return Err(RSMError::RecipientNotAllowlisted.into());
+        }
+    }
+    // The bond is escrowed at mint time, streamed or not, and the rest
+    // paid out. Every mint but an unbonded streamed one pays by token CPI
+    // here; a stream pays as it vests
+    let bonded = bond_amount(amount, config.bond_bps);
+    let paid = amount - bonded;
+    if (config.stream_slots == 0 || bonded > 0) && *token_program.key != *mint_account.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    // An auto-staked mint needs the recipient's opt-in; it, the staking
+    // program and the accounts of its stake_minted follow the membership
+    // account
+    let auto_stake_accounts = if auto_stake {
+        if config.stream_slots > 0 || !split_bps.is_empty() {
+            msg!("Auto-staked mints cannot be split or streamed");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let opt_in_account = next_account_info(account_info_iter)?;
+        let staking_program = next_account_info(account_info_iter)?;
+        let stake_config = next_account_info(account_info_iter)?;
+        let stake_account = next_account_info(account_info_iter)?;
+        let stake_vault = next_account_info(account_info_iter)?;
+        let stake_authority = next_account_info(account_info_iter)?;
+        if auto_stake_address(program_id, config_account.key, recipient_account.key).0 != *opt_in_account.key
+            || opt_in_account.owner != program_id
+        {
+            return Err(RSMError::AutoStakeNotOptedIn.into());
+        }
+        let opt_in = AutoStakeOptIn::try_from_slice(&opt_in_account.data.borrow())?;
+        if opt_in.staking_program != *staking_program.key {
+            return Err(RSMError::AutoStakeNotOptedIn.into());
+        }
+        if stake_authority_address(program_id).0 != *stake_authority.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Some((staking_program, stake_config, stake_account, stake_vault, stake_authority))
+    } else {
+        None
+    };
+    // Only the mint that discovers a species earns the bonus
+    let discovery_bonus_bps = match species_account {
+        Some(species_account) if species_account.data_is_empty() => config.discovery_bonus_bps,
+        _ => 0,
+    };
+    
+    // A split mint pays each share into its own token account, in
+    // split_bps order; any other unstreamed, unstaked mint pays the
+    // recipient's token account. These come last, then the bond vault of
+    // a bonded mint
+    let split_accounts = if split_bps.is_empty() {
+        Vec::new()
+    } else {
+        if config.stream_slots > 0 {
+            msg!("Split mints cannot be streamed");
+            return Err(RSMError::InvalidSplit.into());
+        }
+        split_bps
+            .iter()
+            .map(|_| next_account_info(account_info_iter))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let recipient_token_account = if split_bps.is_empty() && !auto_stake && config.stream_slots == 0 {
+        let token_account = next_account_info(account_info_iter)?;
+        check_token_account(token_account, &config.mint, recipient_account.key)?;
+        Some(token_account)
+    } else {
+        None
+    };
+    let bond_vault = if bonded > 0 {
+        let bond_vault = next_account_info(account_info_iter)?;
+        if bond_vault_address(program_id, config_account.key).0 != *bond_vault.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Some(bond_vault)
+    } else {
+        None
+    };
+    
+    let mut calculated_amount = scale_amount(
+        calculate_token_amount(&config.agi_params, complexity, uniqueness, entropy, blockchain),
+        config.decimals,
+    ).ok_or(ProgramError::ArithmeticOverflow)?;
+    
+    // Kept aside until the mint goes through; a skipped mint leaves no dust
+    let mut dust = 0;
+    if config.decay.is_enabled() {
+        let age_secs = origin
+            .age_secs(clock.slot, clock.unix_timestamp)
+            .ok_or(RSMError::MissingGenomeOrigin)?;
+        dust += DustAccumulator::remainder(calculated_amount, config.decay.factor_bps(age_secs));
+        calculated_amount = config.decay.apply(calculated_amount, age_secs);
+    }
+    
+    // Scaled against the average as of this mint's epoch, then boosted
+    // for a species discovery
+    config.smoothing.roll(clock.slot);
+    let emission_scale_bps = config.smoothing.scale_bps() * (BPS_DENOMINATOR + discovery_bonus_bps as u64) / BPS_DENOMINATOR;
+    dust += DustAccumulator::remainder(calculated_amount, emission_scale_bps);
+    calculated_amount = EmissionSmoothing::apply(calculated_amount, emission_scale_bps);
+    
+    if amount != calculated_amount {
+        msg!("Amount mismatch: expected {}, got {}", calculated_amount, amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    // A mint above the quorum threshold passes `required` controller
+    // co-signers after the split accounts and the bond vault
+    if config.mint_quorum.applies_to(amount) {
+        let cosigners = (0..config.mint_quorum.required)
+            .map(|_| next_account_info(account_info_iter))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !config.mint_quorum.is_met(&cosigners) {
+            return Err(RSMError::MintQuorumNotMet.into());
+        }
+    }
+    
+    if config.total_minted.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)? > config.max_supply {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    if config.breaker.tripped {
+        return Err(RSMError::EmissionAnomaly.into());
+    }
+    
+    // A mint that would trip latches the breaker and succeeds without
+    // minting, so the latch persists; its dedupe record is not written, so
+    // the same mint can be retried after a reset
+    let mut breaker = config.breaker;
+    if !breaker.record(clock.slot, amount, config.decimals) {
+        msg!("EmissionAnomaly: mint of {} exceeds the emission window; breaker tripped", amount);
+        let mut stored = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+        stored.breaker = breaker;
+        stored.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+        return Ok(());
+    }
+    config.breaker = breaker;
+    dedupe.pack(&mut dedupe_account.data.borrow_mut())?;
+    
+    let existing = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    if existing.is_minted {
+        return Err(RSMError::AlreadyMinted.into());
+    }
+    // A pre-registered genome pays its registrant alone, so a mistyped
+    // recipient fails instead of paying someone else
+    if existing.registrant != Pubkey::default()
+        && (*recipient_account.key != existing.registrant || !split_bps.is_empty())
+    {
+        return Err(RSMError::RegistrantMismatch.into());
+    }
+    
+    if let (Some(species_account), Some((payer, system_program))) = (species_account, funding) {
+        if species_account.data_is_empty() {
+            let (_, bump) = species_address(program_id, config_account.key, species);
+            let rent = Rent::get()?;
+            invoke_signed(
+                &system_instruction::create_account(
+                    payer.key,
+                    species_account.key,
+                    rent.minimum_balance(SpeciesRecord::LEN),
+                    SpeciesRecord::LEN as u64,
+                    program_id,
+                ),
+                &[payer.clone(), species_account.clone(), system_program.clone()],
+                &[&[SPECIES_SEED, config_account.key.as_ref(), &species.to_le_bytes(), &[bump]]],
+            )?;
+            let record = SpeciesRecord {
+                species,
+                first_genome: genome_hash,
+                discoverer: *recipient_account.key,
+                discovered_slot: clock.slot,
+                bonus_bps: discovery_bonus_bps,
+                genomes_minted: 1,
+            };
+            record.serialize(&mut &mut species_account.data.borrow_mut()[..])?;
+            emit_event("SpeciesDiscoveredEvent", &SpeciesDiscoveredEvent {
+                species,
+                genome_hash,
+                discoverer: *recipient_account.key,
+                bonus_bps: discovery_bonus_bps,
+                slot: clock.slot,
+            })?;
+        } else {
+            let mut record = SpeciesRecord::try_from_slice(&species_account.data.borrow())?;
+            record.genomes_minted = record.genomes_minted.saturating_add(1);
+            record.serialize(&mut &mut species_account.data.borrow_mut()[..])?;
+        }
+    }
+    
+    let owner = if let (Some(stream_account), Some((payer, system_program))) = (stream_account, funding) {
+        let (stream_key, bump) = stream_address(program_id, genome_account.key);
+        if stream_key != *stream_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        
+        let rent = Rent::get()?;
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                stream_account.key,
+                rent.minimum_balance(StreamVault::LEN),
+                StreamVault::LEN as u64,
+                program_id,
+            ),
+            &[payer.clone(), stream_account.clone(), system_program.clone()],
+            &[&[STREAM_SEED, genome_account.key.as_ref(), &[bump]]],
+        )?;
+        let stream = StreamVault {
+            genome: *genome_account.key,
+            recipient: *recipient_account.key,
+            rent_payer: *payer.key,
+            released: 0,
+            start_slot: clock.slot,
+            end_slot: clock.slot.checked_add(config.stream_slots).ok_or(ProgramError::ArithmeticOverflow)?,
+        };
+        stream.serialize(&mut &mut stream_account.data.borrow_mut()[..])?;
+        stream_key
+    } else {
+        *recipient_account.key
+    };
+    
+    let genome_data = GenomeData {
+        schema_version: GenomeData::SCHEMA.current(),
+        hash: genome_hash,
+        owner,
+        tokens_minted: amount,
+        complexity,
+        uniqueness,
+        is_minted: true,
+        mint_timestamp: clock.unix_timestamp,
+        rent_payer: existing.rent_payer,
+        entropy,
+        mint_slot: clock.slot,
+        scored_at: clock.unix_timestamp,
+        bonded_amount: bonded,
+        pending_clawback: 0,
+        clawback_eta: 0,
+        emission_scale_bps: emission_scale_bps as u16,
+        blockchain_entropy: blockchain,
+        registrant: existing.registrant,
+        note: Vec::new(),
+    };
+    
+    // A note left by a previous mint does not carry over; its rent stays
+    // with the account for the rent payer
+    if genome_account.data_len() != GenomeData::LEN {
+        genome_account.realloc(GenomeData::LEN, false)?;
+    }
+    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+    config.total_minted = config.total_minted.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+    config.smoothing.record(amount);
+    config.dust.record(dust);
+    config.quality_histogram.record(
+        weighted_score(&config.agi_params, complexity, uniqueness, entropy, blockchain),
+        clock.epoch,
+    );
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    if let Some(delegate_account) = delegate_account {
+        let mut delegate = MintDelegate::try_from_slice(&delegate_account.data.borrow())?;
+        delegate.consume(amount, clock.slot)?;
+        delegate.serialize(&mut &mut delegate_account.data.borrow_mut()[..])?;
+    }
+    
+    if !split_accounts.is_empty() {
+        let parts = split_amounts(paid, &split_bps)?;
+        let (_, config_bump) = config_address(program_id, mint_account.key);
+        for ((token_account, bps), part) in split_accounts.iter().zip(&split_bps).zip(parts) {
+            invoke_signed(
+                &spl_token_2022::instruction::mint_to(
+                    token_program.key,
+                    mint_account.key,
+                    token_account.key,
+                    config_account.key,
+                    &[],
+                    part,
+                )?,
+                &[mint_account.clone(), (*token_account).clone(), config_account.clone(), token_program.clone()],
+                &[&[CONFIG_SEED, mint_account.key.as_ref(), &[config_bump]]],
+            )?;
+            emit_event("MintSplitPaidEvent", &MintSplitPaidEvent {
+                genome_hash,
+                token_account: *token_account.key,
+                bps: *bps,
+                amount: part,
+            })?;
+        }
+    }
+    
+    if let Some(token_account) = recipient_token_account {
+        let (_, config_bump) = config_address(program_id, mint_account.key);
+        invoke_signed(
+            &spl_token_2022::instruction::mint_to(
+                token_program.key,
+                mint_account.key,
+                token_account.key,
+                config_account.key,
+                &[],
+                paid,
+            )?,
+            &[mint_account.clone(), token_account.clone(), config_account.clone(), token_program.clone()],
+            &[&[CONFIG_SEED, mint_account.key.as_ref(), &[config_bump]]],
+        )?;
+    }
+    
+    if let Some((staking_program, stake_config, stake_account, stake_vault, stake_authority)) = auto_stake_accounts {
+        let (_, config_bump) = config_address(program_id, mint_account.key);
+        invoke_signed(
+            &spl_token_2022::instruction::mint_to(
+                token_program.key,
+                mint_account.key,
+                stake_vault.key,
+                config_account.key,
+                &[],
+                paid,
+            )?,
+            &[mint_account.clone(), stake_vault.clone(), config_account.clone(), token_program.clone()],
+            &[&[CONFIG_SEED, mint_account.key.as_ref(), &[config_bump]]],
+        )?;
+        let (_, authority_bump) = stake_authority_address(program_id);
+        invoke_signed(
+            &stake_minted_instruction(
+                staking_program.key,
+                [stake_config.key, stake_account.key, stake_vault.key, recipient_account.key, stake_authority.key],
+                paid,
+            ),
+            &[
+                stake_config.clone(),
+                stake_account.clone(),
+                stake_vault.clone(),
+                recipient_account.clone(),
+                stake_authority.clone(),
+                staking_program.clone(),
+            ],
+            &[&[STAKE_AUTHORITY_SEED, &[authority_bump]]],
+        )?;
+        emit_event("MintAutoStakedEvent", &MintAutoStakedEvent {
+            genome_hash,
+            recipient: *recipient_account.key,
+            staking_program: *staking_program.key,
+            amount: paid,
+        })?;
+    }
+    
+    if let Some(bond_vault) = bond_vault {
+        let (_, config_bump) = config_address(program_id, mint_account.key);
+        invoke_signed(
+            &spl_token_2022::instruction::mint_to(
+                token_program.key,
+                mint_account.key,
+                bond_vault.key,
+                config_account.key,
+                &[],
+                bonded,
+            )?,
+            &[mint_account.clone(), bond_vault.clone(), config_account.clone(), token_program.clone()],
+            &[&[CONFIG_SEED, mint_account.key.as_ref(), &[config_bump]]],
+        )?;
+    }
+    
+    if mint_receipt {
+        let receipt = GenomeReceipt {
+            genome_hash,
+            receipt_mint: Pubkey::default(),
+            recipient: *recipient_account.key,
+            complexity,
+            uniqueness,
+            entropy,
+            amount,
+            slot: clock.slot,
+        };
+        mint_genome_receipt(program_id, account_info_iter, config_account, recipient_account, receipt)?;
+    }
+    
+    emit_event("GenomeMintedEvent", &GenomeMintedEvent {
+        genome_hash,
+        recipient: *recipient_account.key,
+        amount,
+        complexity,
+        uniqueness,
+        entropy,
+        slot: clock.slot,
+    })?;
+    
+    msg!("Minted {} RSM tokens for genome", amount);
+    
+    Ok(())
+}
+
+/// Mints a non-transferable Token-2022 NFT to the recipient and writes the
+/// GenomeReceipt record. Trailing accounts, in order: receipt mint (new
+/// keypair, signer), recipient receipt ATA, receipt record PDA, receipt
+/// authority PDA, payer (signer), Token-2022 program, associated token
+/// program, system program.
+fn mint_genome_receipt<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    config: &AccountInfo<'b>,
+    recipient: &AccountInfo<'b>,
+    mut receipt: GenomeReceipt,
+) -> ProgramResult {
+    let receipt_mint = next_account_info(account_info_iter)?;
+    let recipient_receipt_account = next_account_info(account_info_iter)?;
+    let receipt_record = next_account_info(account_info_iter)?;
+    let receipt_authority = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let token_2022_program = next_account_info(account_info_iter)?;
+    let associated_token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if *token_2022_program.key != spl_token_2022::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !receipt_mint.is_signer || !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let (authority_key, authority_bump) =
+        Pubkey::find_program_address(&[RECEIPT_AUTHORITY_SEED], program_id);
+    if authority_key != *receipt_authority.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (record_key, record_bump) = receipt_address(program_id, config.key, &receipt.genome_hash);
+    if record_key != *receipt_record.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let authority_seeds: &[&[u8]] = &[RECEIPT_AUTHORITY_SEED, &[authority_bump]];
+    let rent = Rent::get()?;
+    
+    let mint_len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+        &[ExtensionType::NonTransferable],
+    )?;
+    invoke(
+        &system_instruction::create_account(
+            payer.key,
+            receipt_mint.key,
+            rent.minimum_balance(mint_len),
+            mint_len as u64,
+            token_2022_program.key,
+        ),
+        &[payer.clone(), receipt_mint.clone(), system_program.clone()],
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_non_transferable_mint(
+            token_2022_program.key,
+            receipt_mint.key,
+        )?,
+        std::slice::from_ref(receipt_mint),
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            token_2022_program.key,
+            receipt_mint.key,
+            receipt_authority.key,
+            Some(receipt_authority.key),
+            0,
+        )?,
+        std::slice::from_ref(receipt_mint),
+    )?;
+    
+    invoke(
+        &spl_associated_token_account::instruction::create_associated_token_account(
+            payer.key,
+            recipient.key,
+            receipt_mint.key,
+            token_2022_program.key,
+        ),
+        &[
+            payer.clone(),
+            recipient_receipt_account.clone(),
+            recipient.clone(),
+            receipt_mint.clone(),
+            system_program.clone(),
+            token_2022_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )?;
+    invoke_signed(
+        &spl_token_2022::instruction::mint_to(
+            token_2022_program.key,
+            receipt_mint.key,
+            recipient_receipt_account.key,
+            receipt_authority.key,
+            &[],
+            1,
+        )?,
+        &[receipt_mint.clone(), recipient_receipt_account.clone(), receipt_authority.clone()],
+        &[authority_seeds],
+    )?;
+    invoke_signed(
+        &spl_token_2022::instruction::set_authority(
+            token_2022_program.key,
+            receipt_mint.key,
+            None,
+            spl_token_2022::instruction::AuthorityType::MintTokens,
+            receipt_authority.key,
+            &[],
+        )?,
+        &[receipt_mint.clone(), receipt_authority.clone()],
+        &[authority_seeds],
+    )?;
+    
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            receipt_record.key,
+            rent.minimum_balance(GenomeReceipt::LEN),
+            GenomeReceipt::LEN as u64,
+            program_id,
+        ),
+        &[payer.clone(), receipt_record.clone(), system_program.clone()],
+        &[&[RECEIPT_SEED, config.key.as_ref(), &receipt.genome_hash, &[record_bump]]],
+    )?;
+    receipt.receipt_mint = *receipt_mint.key;
+    receipt.serialize(&mut &mut receipt_record.data.borrow_mut()[..])?;
+    
+    msg!("Genome receipt {} minted to {}", receipt_mint.key, recipient.key);
+    Ok(())
+}
+
+pub fn process_burn_genome(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    genome_hash: [u8; 32],
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let owner_token_account = next_account_info(account_info_iter)?;
+    let signer = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    if config_account.owner != program_id || genome_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *mint_account.key != config.mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *token_program.key != *mint_account.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    // Only a genome of this config counts against its total_minted
+    if genome_address(program_id, config_account.key, &genome_hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    
+    if genome_data.hash != genome_hash || !genome_data.is_minted {
+        return Err(ProgramError::IllegalOwner);
+    }
+    // The owner holds only the unbonded part until ReleaseBond
+    if genome_data.bonded_amount > 0 {
+        return Err(RSMError::BondHeld.into());
+    }
+    check_token_account(owner_token_account, &config.mint, &genome_data.owner)?;
+    
+    let burned = genome_data.tokens_minted;
+    
+    // A signer other than the owner must be a burn delegate; its approval
+    // follows the signer
+    if genome_data.owner != *signer.key {
+        let approval_account = next_account_info(account_info_iter)?;
+        let (approval_key, _) = burn_approval_address(program_id, genome_account.key, signer.key);
+        if approval_key != *approval_account.key || approval_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+        let mut approval = BurnApproval::try_from_slice(&approval_account.data.borrow())?;
+        if approval.owner != genome_data.owner {
+            return Err(ProgramError::IllegalOwner);
+        }
+        approval.consume(burned, clock.now()?.slot)?;
+        approval.serialize(&mut &mut approval_account.data.borrow_mut()[..])?;
+    }
+    // The signer burns the tokens too: the owner, or a burn delegate the
+    // owner also approved on the token account
+    invoke(
+        &spl_token_2022::instruction::burn(
+            token_program.key,
+            owner_token_account.key,
+            mint_account.key,
+            signer.key,
+            &[],
+            burned,
+        )?,
+        &[owner_token_account.clone(), mint_account.clone(), signer.clone(), token_program.clone()],
+    )?;
+    config.total_minted = config.total_minted.checked_sub(burned).ok_or(ProgramError::ArithmeticOverflow)?;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    genome_data.tokens_minted = 0;
+    genome_data.is_minted = false;
+    genome_data.bonded_amount = 0;
+    genome_data.pending_clawback = 0;
+    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+    
+    emit_event("GenomeBurnedEvent", &GenomeBurnedEvent {
+        genome_hash,
+        owner: genome_data.owner,
+        amount: burned,
+    })?;
+    
+    msg!("Burned {} RSM tokens", burned);
+    Ok(())
+}
+
+pub fn process_update_agi_params(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    complexity_weight: u8,
+    uniqueness_weight: u8,
+    entropy_weight: u8,
+    blockchain_weight: u8,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let agi_controller = next_account_info(account_info_iter)?;
+    
+    if !agi_controller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    
+    if *agi_controller.key != config.agi_controller {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    if complexity_weight + uniqueness_weight + entropy_weight + blockchain_weight != 100 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    let slot = clock.now()?.slot;
+    if config.params_history.recorded == 0 {
+        config.params_history.record(config.agi_params, 0);
+    }
+    config.agi_params.complexity_weight = complexity_weight;
+    config.agi_params.uniqueness_weight = uniqueness_weight;
+    config.agi_params.entropy_weight = entropy_weight;
+    config.agi_params.blockchain_weight = blockchain_weight;
+    config.params_history.record(config.agi_params, slot);
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("AGI parameters updated");
+    Ok(())
+}
+
+pub fn process_set_score_oracle(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    score_oracle: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    config.score_oracle = score_oracle;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Score oracle set to {}", score_oracle);
+    Ok(())
+}
+
+pub fn process_configure_breaker(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    window_slots: u64,
+    max_multiple_pct: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    if window_slots == 0 || max_multiple_pct <= 100 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    config.breaker.window_slots = window_slots;
+    config.breaker.max_multiple_pct = max_multiple_pct;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Breaker configured: {} slots, {}%", window_slots, max_multiple_pct);
+    Ok(())
+}
+
+pub fn process_reset_breaker(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    config.breaker.reset(clock.slot);
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Circuit breaker reset at slot {}", clock.slot);
+    Ok(())
+}
+
+pub fn process_trip_breaker(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let signer = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    
+    if *signer.key != config.agi_controller && *signer.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    let mut breaker = config.breaker;
+    if breaker.record(clock.slot, amount, config.decimals) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    config.breaker = breaker;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Circuit breaker tripped at slot {} by a mint of {}", clock.slot, amount);
+    Ok(())
+}
+
+pub fn process_create_dedupe_buffer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let dedupe_account = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *payer.key != config.authority && *payer.key != config.agi_controller {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    let (dedupe_key, bump) = Pubkey::find_program_address(&[DEDUPE_SEED, config_account.key.as_ref()], program_id);
+    if dedupe_key != *dedupe_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let rent = Rent::get()?;
+    if dedupe_account.owner == program_id {
+        // Grow a buffer created with fewer entries, keeping the ones it has
+        let mut dedupe = MintDedupe::unpack(&dedupe_account.data.borrow())?;
+        if dedupe.entries.len() >= DEDUPE_CAPACITY {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        let rent_exempt = rent.minimum_balance(MintDedupe::LEN);
+        let lamports = dedupe_account.lamports();
+        if rent_exempt > lamports {
+            invoke(
+                &system_instruction::transfer(payer.key, dedupe_account.key, rent_exempt - lamports),
+                &[payer.clone(), dedupe_account.clone(), system_program.clone()],
+            )?;
+        }
+        dedupe_account.realloc(MintDedupe::LEN, false)?;
+        dedupe.entries.resize(DEDUPE_CAPACITY, DedupeEntry::default());
+        dedupe.pack(&mut dedupe_account.data.borrow_mut())?;
+        
+        msg!("Mint dedupe buffer grown to {} entries", DEDUPE_CAPACITY);
+        return Ok(());
+    }
+    
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            dedupe_account.key,
+            rent.minimum_balance(MintDedupe::LEN),
+            MintDedupe::LEN as u64,
+            program_id,
+        ),
+        &[payer.clone(), dedupe_account.clone(), system_program.clone()],
+        &[&[DEDUPE_SEED, config_account.key.as_ref(), &[bump]]],
+    )?;
+    MintDedupe::default().pack(&mut dedupe_account.data.borrow_mut())?;
+    
+    msg!("Mint dedupe buffer created");
+    Ok(())
+}
+
+/// Also serves PreRegisterGenome, which records the payer as registrant
+/// once the agi_controller or the authority co-signs the claim.
+pub fn process_create_genome_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    genome_hash: [u8; 32],
+    pre_register: bool,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    
+    // Otherwise anyone could squat a hash and divert its mint to themselves
+    if pre_register {
+        let controller = next_account_info(account_info_iter)?;
+        if !controller.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if *controller.key != config.agi_controller && *controller.key != config.authority {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+    
+    let (genome_key, bump) = genome_address(program_id, config_account.key, &genome_hash);
+    if genome_key != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let rent = Rent::get()?;
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            genome_account.key,
+            rent.minimum_balance(GenomeData::LEN),
+            GenomeData::LEN as u64,
+            program_id,
+        ),
+        &[payer.clone(), genome_account.clone(), system_program.clone()],
+        &[&[GENOME_SEED, config_account.key.as_ref(), &genome_hash, &[bump]]],
+    )?;
+    
+    let genome_data = GenomeData {
+        schema_version: GenomeData::SCHEMA.current(),
+        hash: genome_hash,
+        owner: Pubkey::default(),
+        tokens_minted: 0,
+        complexity: 0,
+        uniqueness: 0,
+        is_minted: false,
+        mint_timestamp: 0,
+        rent_payer: *payer.key,
+        entropy: 0,
+        mint_slot: 0,
+        scored_at: 0,
+        bonded_amount: 0,
+        pending_clawback: 0,
+        clawback_eta: 0,
+        emission_scale_bps: BPS_DENOMINATOR as u16,
+        blockchain_entropy: 0,
+        registrant: if pre_register { *payer.key } else { Pubkey::default() },
+        note: Vec::new(),
+    };
+    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+    config.genome_census.created()?;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    if pre_register {
+        emit_event("GenomePreRegisteredEvent", &GenomePreRegisteredEvent {
+            genome_hash,
+            registrant: *payer.key,
+            slot: clock.now()?.slot,
+        })?;
+    }
+    
+    msg!("Genome account created: {}", genome_key);
+    Ok(())
+}
+
+pub fn process_close_genome_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    genome_hash: [u8; 32],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let rent_payer = next_account_info(account_info_iter)?;
+    let signer = next_account_info(account_info_iter)?;
+    
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if genome_address(program_id, config_account.key, &genome_hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    let genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    
+    if genome_data.is_minted {
+        return Err(RSMError::GenomeStillMinted.into());
+    }
+    if genome_data.rent_payer != *rent_payer.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *signer.key != genome_data.rent_payer && *signer.key != config.agi_controller {
+        return Err(ProgramError::IllegalOwner);
+    }
+    
+    let lamports = genome_account.lamports();
+    **rent_payer.lamports.borrow_mut() = rent_payer
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **genome_account.lamports.borrow_mut() = 0;
+    genome_account.data.borrow_mut().fill(0);
+    config.genome_census.closed()?;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Genome account closed, {} lamports returned", lamports);
+    Ok(())
+}
+
+pub fn process_create_mint_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    delegate: Pubkey,
+    max_amount: u64,
+    max_mints: u32,
+    expiry_slot: u64,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let delegate_account = next_account_info(account_info_iter)?;
+    let agi_controller = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if !agi_controller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *agi_controller.key != config.agi_controller {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    let clock = clock.now()?;
+    if expiry_slot <= clock.slot {
+        return Err(RSMError::DelegateExpired.into());
+    }
+    
+    let (delegate_key, bump) = mint_delegate_address(program_id, config_account.key, &delegate);
+    if delegate_key != *delegate_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let rent = Rent::get()?;
+    invoke_signed(
+        &system_instruction::create_account(
+            agi_controller.key,
+            delegate_account.key,
+            rent.minimum_balance(MintDelegate::LEN),
+            MintDelegate::LEN as u64,
+            program_id,
+        ),
+        &[agi_controller.clone(), delegate_account.clone(), system_program.clone()],
+        &[&[MINT_DELEGATE_SEED, config_account.key.as_ref(), delegate.as_ref(), &[bump]]],
+    )?;
+    
+    let record = MintDelegate {
+        config: *config_account.key,
+        delegate,
+        max_amount,
+        minted_amount: 0,
+        max_mints,
+        mints_used: 0,
+        expiry_slot,
+    };
+    record.serialize(&mut &mut delegate_account.data.borrow_mut()[..])?;
+    
+    msg!("Mint delegate {} created: {} tokens / {} mints until slot {}", delegate, max_amount, max_mints, expiry_slot);
+    Ok(())
+}
+
+pub fn process_revoke_mint_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let delegate_account = next_account_info(account_info_iter)?;
+    let agi_controller = next_account_info(account_info_iter)?;
+    
+    if !agi_controller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if delegate_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *agi_controller.key != config.agi_controller {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    let record = MintDelegate::try_from_slice(&delegate_account.data.borrow())?;
+    if record.config != *config_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    let lamports = delegate_account.lamports();
+    **agi_controller.lamports.borrow_mut() = agi_controller
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **delegate_account.lamports.borrow_mut() = 0;
+    delegate_account.data.borrow_mut().fill(0);
+    
+    msg!("Mint delegate {} revoked after {} mints", record.delegate, record.mints_used);
+    Ok(())
+}
+
+pub fn process_approve_burn(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    delegate: Pubkey,
+    amount: u64,
+    expiry_slot: u64,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let genome_account = next_account_info(account_info_iter)?;
+    let approval_account = next_account_info(account_info_iter)?;
+    let owner = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if genome_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    if genome_data.owner != *owner.key || !genome_data.is_minted {
+        return Err(ProgramError::IllegalOwner);
+    }
+    if expiry_slot <= clock.now()?.slot {
+        return Err(RSMError::BurnApprovalExpired.into());
+    }
+    
+    let (approval_key, bump) = burn_approval_address(program_id, genome_account.key, &delegate);
+    if approval_key != *approval_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if approval_account.lamports() == 0 {
+        let rent = Rent::get()?;
+        invoke_signed(
+            &system_instruction::create_account(
+                owner.key,
+                approval_account.key,
+                rent.minimum_balance(BurnApproval::LEN),
+                BurnApproval::LEN as u64,
+                program_id,
+            ),
+            &[owner.clone(), approval_account.clone(), system_program.clone()],
+            &[&[BURN_APPROVAL_SEED, genome_account.key.as_ref(), delegate.as_ref(), &[bump]]],
+        )?;
+    } else if approval_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let approval = BurnApproval {
+        genome: *genome_account.key,
+        owner: *owner.key,
+        delegate,
+        remaining_amount: amount,
+        expiry_slot,
+    };
+    approval.serialize(&mut &mut approval_account.data.borrow_mut()[..])?;
+    
+    msg!("Burn delegate {} approved for {} tokens until slot {}", delegate, amount, expiry_slot);
+    Ok(())
+}
+
+pub fn process_revoke_burn(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let approval_account = next_account_info(account_info_iter)?;
+    let owner = next_account_info(account_info_iter)?;
+    
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if approval_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let approval = BurnApproval::try_from_slice(&approval_account.data.borrow())?;
+    if approval.owner != *owner.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+    
+    let lamports = approval_account.lamports();
+    **owner.lamports.borrow_mut() = owner
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **approval_account.lamports.borrow_mut() = 0;
+    approval_account.data.borrow_mut().fill(0);
+    
+    msg!("Burn delegate {} revoked with {} tokens unused", approval.delegate, approval.remaining_amount);
+    Ok(())
+}
+
+pub fn process_set_auto_stake(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    staking_program: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let opt_in_account = next_account_info(account_info_iter)?;
+    let recipient = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if !recipient.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let (opt_in_key, bump) = auto_stake_address(program_id, config_account.key, recipient.key);
+    if opt_in_key != *opt_in_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if staking_program == Pubkey::default() {
+        if opt_in_account.owner != program_id {
+            return Err(RSMError::AutoStakeNotOptedIn.into());
+        }
+        let lamports = opt_in_account.lamports();
+        **recipient.lamports.borrow_mut() = recipient
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        **opt_in_account.lamports.borrow_mut() = 0;
+        opt_in_account.data.borrow_mut().fill(0);
+        
+        msg!("{} opted out of auto-staking", recipient.key);
+        return Ok(());
+    }
+    
+    if opt_in_account.lamports() == 0 {
+        let rent = Rent::get()?;
+        invoke_signed(
+            &system_instruction::create_account(
+                recipient.key,
+                opt_in_account.key,
+                rent.minimum_balance(AutoStakeOptIn::LEN),
+                AutoStakeOptIn::LEN as u64,
+                program_id,
+            ),
+            &[recipient.clone(), opt_in_account.clone(), system_program.clone()],
+            &[&[AUTO_STAKE_SEED, config_account.key.as_ref(), recipient.key.as_ref(), &[bump]]],
+        )?;
+    } else if opt_in_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let opt_in = AutoStakeOptIn {
+        config: *config_account.key,
+        recipient: *recipient.key,
+        staking_program,
+    };
+    opt_in.serialize(&mut &mut opt_in_account.data.borrow_mut()[..])?;
+    
+    msg!("{} opted into auto-staking with {}", recipient.key, staking_program);
+    Ok(())
+}
+
+/// Checks that both max supply co-signers signed.
+fn check_supply_signers(config: &TokenConfig, authority: &AccountInfo, executor: &AccountInfo) -> ProgramResult {
+    if !authority.is_signer || !executor.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let governance = &config.supply_governance;
+    if governance.executor == Pubkey::default()
+        || *authority.key != config.authority
+        || *executor.key != governance.executor
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+pub fn process_set_governance_executor(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    executor: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if config.supply_governance.executor == Pubkey::default() {
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if *authority.key != config.authority {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    } else {
+        let current_executor = next_account_info(account_info_iter)?;
+        check_supply_signers(&config, authority, current_executor)?;
+    }
+    if executor == Pubkey::default() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    config.supply_governance.executor = executor;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Governance executor set: {}", executor);
+    Ok(())
+}
+
+pub fn process_schedule_max_supply_raise(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_max_supply: u64,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let executor = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    check_supply_signers(&config, authority, executor)?;
+    if new_max_supply <= config.max_supply {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    let eta = clock.unix_timestamp
+        .checked_add(SUPPLY_RAISE_TIMELOCK)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    config.supply_governance.check_raise(config.max_supply, new_max_supply, eta)?;
+    
+    config.supply_governance.pending_max_supply = new_max_supply;
+    config.supply_governance.pending_eta = eta;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Max supply raise to {} scheduled for {}", new_max_supply, eta);
+    Ok(())
+}
+
+pub fn process_raise_max_supply(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let executor = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    check_supply_signers(&config, authority, executor)?;
+    
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    let raise = config.supply_governance.apply(config.max_supply, clock.unix_timestamp)?;
+    config.max_supply = raise.new_max_supply;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    emit_event("MaxSupplyRaisedEvent", &MaxSupplyRaisedEvent {
+        config: *config_account.key,
+        old_max_supply: raise.old_max_supply,
+        new_max_supply: raise.new_max_supply,
+        executed_at: raise.executed_at,
+    })?;
+    
+    msg!("Max supply raised: {} -> {}", raise.old_max_supply, raise.new_max_supply);
+    Ok(())
+}
+
+pub fn process_cancel_max_supply_raise(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let signer = next_account_info(account_info_iter)?;
+    
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    let governance = &mut config.supply_governance;
+    if *signer.key != config.authority && (governance.executor == Pubkey::default() || *signer.key != governance.executor) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if governance.pending_max_supply == 0 {
+        return Err(RSMError::NoPendingRaise.into());
+    }
+    
+    let cancelled = governance.pending_max_supply;
+    governance.pending_max_supply = 0;
+    governance.pending_eta = 0;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Max supply raise to {} cancelled", cancelled);
+    Ok(())
+}
+
+pub fn process_configure_streaming(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    stream_slots: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    config.stream_slots = stream_slots;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Mints stream over {} slots", stream_slots);
+    Ok(())
+}
+
+pub fn process_configure_decay(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold_secs: i64,
+    half_life_secs: i64,
+    floor_bps: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    if threshold_secs < 0 || floor_bps as u64 > BPS_DENOMINATOR || (threshold_secs > 0 && half_life_secs <= 0) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    config.decay = QualityDecay { threshold_secs, half_life_secs, floor_bps };
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Rewards decay after {}s with a {}s half-life, floor {} bps", threshold_secs, half_life_secs, floor_bps);
+    Ok(())
+}
+
+pub fn process_configure_disputes(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    window_secs: i64,
+    reward_bps: u16,
+    evidence_signer: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    if window_secs < 0 || reward_bps as u64 > BPS_DENOMINATOR || (window_secs > 0 && evidence_signer == Pubkey::default()) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    config.disputes = DisputePolicy { window_secs, reward_bps, evidence_signer };
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Disputes open {}s after minting, challenger reward {} bps", window_secs, reward_bps);
+    Ok(())
+}
+
+pub fn process_configure_emission_target(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    target_per_epoch: u64,
+    epoch_slots: u64,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    if target_per_epoch > 0 && epoch_slots == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    // Turning smoothing on seeds the average with the target so the first
+    // mints are unscaled; retargeting keeps the history
+    let clock = clock.now()?;
+    let smoothing = &mut config.smoothing;
+    if target_per_epoch == 0 {
+        *smoothing = EmissionSmoothing::default();
+    } else if smoothing.is_enabled() {
+        smoothing.roll(clock.slot);
+    } else {
+        *smoothing = EmissionSmoothing { average: target_per_epoch, epoch_start: clock.slot, ..EmissionSmoothing::default() };
+    }
+    smoothing.target_per_epoch = target_per_epoch;
+    smoothing.epoch_slots = epoch_slots;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Emission target {} per {} slots", target_per_epoch, epoch_slots);
+    Ok(())
+}
+
+pub fn process_configure_recipient_consent(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    required: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    config.require_recipient_signature = required;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Recipient signature on mints {}", if required { "required" } else { "not required" });
+    Ok(())
+}
+
+pub fn process_configure_mint_window(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    start_bps: u16,
+    end_bps: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    let window = MintWindow { start_bps, end_bps };
+    if !window.is_valid() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    config.mint_window = if window.is_enabled() { window } else { MintWindow::default() };
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Mint window {}-{} bps of each epoch", start_bps, end_bps);
+    Ok(())
+}
+
+pub fn process_configure_discovery_bonus(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    bonus_bps: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    if bonus_bps > MAX_DISCOVERY_BONUS_BPS {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    config.discovery_bonus_bps = bonus_bps;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Species discovery bonus {} bps", bonus_bps);
+    Ok(())
+}
+
+pub fn process_configure_allowlist(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    membership_mint: Pubkey,
+    merkle_root: [u8; 32],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    config.allowlist = RecipientAllowlist { membership_mint, merkle_root };
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    if config.allowlist.is_enabled() {
+        msg!("Recipient allowlist: membership mint {}, Merkle root set {}", membership_mint, merkle_root != [0u8; 32]);
+    } else {
+        msg!("Recipient allowlist off");
+    }
+    Ok(())
+}
+
+pub fn process_configure_quality_histogram(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reset_per_epoch: bool,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    config.quality_histogram.clear(clock.now()?.epoch);
+    config.quality_histogram.reset_per_epoch = reset_per_epoch;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Quality histogram cleared, reset per epoch: {}", reset_per_epoch);
+    Ok(())
+}
+
+pub fn process_configure_mint_quorum(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: u64,
+    required: u8,
+    controllers: Vec<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    let quorum = MintQuorum::new(threshold, required, &controllers).ok_or(ProgramError::InvalidArgument)?;
+    if !quorum.is_valid() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    config.mint_quorum = if quorum.is_enabled() { quorum } else { MintQuorum::default() };
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    if quorum.is_enabled() {
+        msg!("Mints above {} need {} of {} controllers", threshold, required, controllers.len());
+    } else {
+        msg!("Mint quorum off");
+    }
+    Ok(())
+}
+
+pub fn process_configure_dust(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: u64,
+    reward_pool: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if threshold > 0 && reward_pool == Pubkey::default() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    config.dust.threshold = threshold;
+    config.dust.reward_pool = reward_pool;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Dust consolidates to {} from {} base units", reward_pool, threshold);
+    Ok(())
+}
+
+pub fn process_consolidate_dust(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let executor = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let pool_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    check_supply_signers(&config, authority, executor)?;
+    if *mint_account.key != config.mint || *pool_account.key != config.dust.reward_pool {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *token_program.key != *mint_account.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !config.dust.is_due() {
+        return Err(RSMError::DustBelowThreshold.into());
+    }
+    
+    let amount = config.dust.take();
+    let total_minted = config.total_minted.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+    if total_minted > config.max_supply {
+        return Err(ProgramError::InvalidArgument);
+    }
+    config.total_minted = total_minted;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    let (_, config_bump) = config_address(program_id, mint_account.key);
+    invoke_signed(
+        &spl_token_2022::instruction::mint_to(
+            token_program.key,
+            mint_account.key,
+            pool_account.key,
+            config_account.key,
+            &[],
+            amount,
+        )?,
+        &[mint_account.clone(), pool_account.clone(), config_account.clone(), token_program.clone()],
+        &[&[CONFIG_SEED, mint_account.key.as_ref(), &[config_bump]]],
+    )?;
+    
+    emit_event("DustConsolidatedEvent", &DustConsolidatedEvent {
+        config: *config_account.key,
+        reward_pool: *pool_account.key,
+        amount,
+        consolidated: config.dust.consolidated,
+    })?;
+    
+    msg!("Consolidated {} base units of dust into {}", amount, pool_account.key);
+    Ok(())
+}
+
+pub fn process_challenge_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    genome_hash: [u8; 32],
+    complexity: u8,
+    uniqueness: u8,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let dispute_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let challenger_token_account = next_account_info(account_info_iter)?;
+    let challenger = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let instructions = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let bond_vault = next_account_info(account_info_iter)?;
+    
+    if !challenger.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if genome_address(program_id, config_account.key, &genome_hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *mint_account.key != config.mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *token_program.key != *mint_account.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !config.disputes.is_enabled() {
+        return Err(RSMError::DisputesDisabled.into());
+    }
+    if complexity > 100 || uniqueness > 100 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    let mut genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    if !genome_data.is_minted {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let clock = clock.now()?;
+    if !config.disputes.is_open(genome_data.mint_timestamp, clock.unix_timestamp) {
+        return Err(RSMError::ChallengeWindowClosed.into());
+    }
+    
+    // The evidence is checked by the Ed25519 program in the instruction
+    // just before this one
+    let current = ix_sysvar::load_current_index_checked(instructions)?;
+    if current == 0 {
+        return Err(RSMError::InvalidEvidence.into());
+    }
+    let verify_ix = ix_sysvar::load_instruction_at_checked(current as usize - 1, instructions)?;
+    let message = rescore_evidence_message(config_account.key, &genome_hash, genome_data.mint_slot, complexity, uniqueness);
+    if verify_ix.program_id != ed25519_program::ID
+        || !ed25519_verifies(&verify_ix.data, &config.disputes.evidence_signer, &message)
+    {
+        return Err(RSMError::InvalidEvidence.into());
+    }
+    
+    // Valued like a re-score: same weights, entropy, blockchain component
+    // and smoothing as the original mint
+    let params = config.agi_params_at(genome_data.mint_slot).unwrap_or(config.agi_params);
+    let evidence_amount = EmissionSmoothing::apply(
+        scale_amount(
+            calculate_token_amount(&params, complexity, uniqueness, genome_data.entropy, genome_data.blockchain_entropy),
+            config.decimals,
+        ).ok_or(ProgramError::ArithmeticOverflow)?,
+        genome_data.emission_scale_bps as u64,
+    );
+    let slashed = rescore_clawback(genome_data.tokens_minted, evidence_amount, genome_data.bonded_amount);
+    if slashed == 0 {
+        return Err(RSMError::MintNotInflated.into());
+    }
+    let reward = config.disputes.reward(slashed);
+    
+    // Creating the record fails if this mint was already disputed
+    let (dispute_key, dispute_bump) = dispute_address(program_id, genome_account.key, genome_data.mint_slot);
+    if dispute_key != *dispute_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let rent = Rent::get()?;
+    invoke_signed(
+        &system_instruction::create_account(
+            challenger.key,
+            dispute_account.key,
+            rent.minimum_balance(DisputeRecord::LEN),
+            DisputeRecord::LEN as u64,
+            program_id,
+        ),
+        &[challenger.clone(), dispute_account.clone(), system_program.clone()],
+        &[&[DISPUTE_SEED, genome_account.key.as_ref(), &genome_data.mint_slot.to_le_bytes(), &[dispute_bump]]],
+    )?;
+    let record = DisputeRecord {
+        genome_hash,
+        challenger: *challenger.key,
+        mint_slot: genome_data.mint_slot,
+        minted_complexity: genome_data.complexity,
+        minted_uniqueness: genome_data.uniqueness,
+        complexity,
+        uniqueness,
+        slashed,
+        reward,
+        disputed_at: clock.unix_timestamp,
+    };
+    record.serialize(&mut &mut dispute_account.data.borrow_mut()[..])?;
+    
+    // The evidence replaces the score; a pending clawback can only take
+    // what is left of the bond
+    genome_data.tokens_minted -= slashed;
+    genome_data.bonded_amount -= slashed;
+    genome_data.complexity = complexity;
+    genome_data.uniqueness = uniqueness;
+    genome_data.scored_at = clock.unix_timestamp;
+    genome_data.pending_clawback = genome_data.pending_clawback.min(genome_data.bonded_amount);
+    if genome_data.pending_clawback == 0 {
+        genome_data.clawback_eta = 0;
+    }
+    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+    
+    // The slash comes out of the escrowed bond: the challenger's share
+    // moves to them and the rest is burned, so supply only shrinks
+    let burned = slashed - reward;
+    config.total_minted = config.total_minted
+        .checked_sub(burned)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    config.dispute_rewards = config.dispute_rewards
+        .checked_add(reward)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    if reward > 0 {
+        pay_bond(program_id, config_account, bond_vault, mint_account, challenger_token_account, token_program, reward)?;
+    }
+    if burned > 0 {
+        burn_bond(program_id, config_account, bond_vault, mint_account, token_program, burned)?;
+    }
+    
+    emit_event("MintChallengedEvent", &MintChallengedEvent {
+        genome_hash,
+        challenger: *challenger.key,
+        complexity,
+        uniqueness,
+        slashed,
+        reward,
+    })?;
+    
+    msg!("Mint challenged: {} slashed, {} to challenger", slashed, reward);
+    Ok(())
+}
+
+pub fn process_set_genome_note(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    genome_hash: [u8; 32],
+    note: Vec<u8>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let owner = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if genome_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if genome_address(program_id, config_account.key, &genome_hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if note.len() > MAX_NOTE_LEN {
+        return Err(RSMError::NoteTooLong.into());
+    }
+    
+    let mut genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    if !genome_data.is_minted || genome_data.owner != *owner.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+    genome_data.note = note;
+    
+    // The owner tops the account up to rent exemption at the new size, or
+    // takes back what it no longer needs
+    let space = genome_data.space();
+    let rent_exempt = Rent::get()?.minimum_balance(space);
+    let lamports = genome_account.lamports();
+    if rent_exempt > lamports {
+        invoke(
+            &system_instruction::transfer(owner.key, genome_account.key, rent_exempt - lamports),
+            &[owner.clone(), genome_account.clone(), system_program.clone()],
+        )?;
+    }
+    genome_account.realloc(space, false)?;
+    if lamports > rent_exempt {
+        **owner.lamports.borrow_mut() = owner
+            .lamports()
+            .checked_add(lamports - rent_exempt)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        **genome_account.lamports.borrow_mut() = rent_exempt;
+    }
+    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+    
+    emit_event("GenomeNoteSetEvent", &GenomeNoteSetEvent {
+        genome_hash,
+        owner: *owner.key,
+        note: genome_data.note,
+    })?;
+    
+    msg!("Genome note set: {} bytes", space - GenomeData::LEN);
+    Ok(())
+}
+
+pub fn process_migrate_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    kind: AccountKind,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let account = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let schema = kind.schema();
+    let from = schema.version_of(&account.data.borrow()).map_err(|_| RSMError::MigrationFailed)?;
+    let migrated = match schema.migrate(&account.data.borrow()).map_err(|_| RSMError::MigrationFailed)? {
+        Some(migrated) => migrated,
+        None => {
+            msg!("{} {} already at version {}", schema.name, account.key, from);
+            return Ok(());
+        }
+    };
+    
+    // The upgraded data must parse as the current layout and belong at
+    // this address, so a wrong `kind` cannot rewrite another account
+    let expected = match kind {
+        AccountKind::TokenConfig => {
+            let config = TokenConfig::try_from_slice(&migrated).map_err(|_| RSMError::MigrationFailed)?;
+            config_address(program_id, &config.mint).0
+        }
+        AccountKind::GenomeData => {
+            let config_account = next_account_info(account_info_iter)?;
+            let genome = GenomeData::try_from_slice(&migrated).map_err(|_| RSMError::MigrationFailed)?;
+            genome_address(program_id, config_account.key, &genome.hash).0
+        }
+    };
+    if expected != *account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let rent_exempt = Rent::get()?.minimum_balance(migrated.len());
+    let lamports = account.lamports();
+    if rent_exempt > lamports {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, rent_exempt - lamports),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    account.realloc(migrated.len(), false)?;
+    account.data.borrow_mut().copy_from_slice(&migrated);
+    
+    msg!("{} {} migrated from version {} to {}", schema.name, account.key, from, schema.current());
+    Ok(())
+}
+
+pub fn process_withdraw_vested(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    genome_hash: [u8; 32],
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let stream_account = next_account_info(account_info_iter)?;
+    let recipient = next_account_info(account_info_iter)?;
+    let rent_payer = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let recipient_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    
+    if !recipient.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *mint_account.key != config.mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *token_program.key != *mint_account.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    check_token_account(recipient_token_account, &config.mint, recipient.key)?;
+    if genome_address(program_id, config_account.key, &genome_hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if stream_address(program_id, genome_account.key).0 != *stream_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if stream_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let mut stream = StreamVault::try_from_slice(&stream_account.data.borrow())?;
+    if stream.recipient != *recipient.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let mut genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    if genome_data.owner != *stream_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    // The bond sits in the bond vault, not the stream
+    let slot = clock.now()?.slot;
+    let streamed = genome_data.tokens_minted.saturating_sub(genome_data.bonded_amount);
+    let amount = stream.vested(streamed, slot).saturating_sub(stream.released);
+    stream.released += amount;
+    let completed = slot >= stream.end_slot;
+    
+    if completed {
+        if stream.rent_payer != *rent_payer.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        genome_data.owner = stream.recipient;
+        genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+        
+        let lamports = stream_account.lamports();
+        **rent_payer.lamports.borrow_mut() = rent_payer
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        **stream_account.lamports.borrow_mut() = 0;
+        stream_account.data.borrow_mut().fill(0);
+    } else {
+        if amount == 0 {
+            return Err(RSMError::NothingVested.into());
+        }
+        stream.serialize(&mut &mut stream_account.data.borrow_mut()[..])?;
+    }
+    
+    // The mint counted the whole stream in total_minted; its tokens are
+    // minted as they vest
+    if amount > 0 {
+        let (_, config_bump) = config_address(program_id, mint_account.key);
+        invoke_signed(
+            &spl_token_2022::instruction::mint_to(
+                token_program.key,
+                mint_account.key,
+                recipient_token_account.key,
+                config_account.key,
+                &[],
+                amount,
+            )?,
+            &[mint_account.clone(), recipient_token_account.clone(), config_account.clone(), token_program.clone()],
+            &[&[CONFIG_SEED, mint_account.key.as_ref(), &[config_bump]]],
+        )?;
+    }
+    
+    emit_event("StreamWithdrawnEvent", &StreamWithdrawnEvent {
+        genome_hash,
+        recipient: stream.recipient,
+        amount,
+        released: stream.released,
+        completed,
+    })?;
+    
+    msg!("Released {} of {} streamed RSM", amount, streamed);
+    Ok(())
+}
+
+pub fn process_configure_score_validity(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    validity_secs: i64,
+    bond_bps: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *authority.key != config.authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if validity_secs < 0 || bond_bps as u64 > BPS_DENOMINATOR {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    config.score_validity_secs = validity_secs;
+    config.bond_bps = bond_bps;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Score validity: {}s, bond: {} bps", validity_secs, bond_bps);
+    Ok(())
+}
+
+pub fn process_rescore_genome(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    genome_hash: [u8; 32],
+    complexity: u8,
+    uniqueness: u8,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let agi_controller = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    
+    if !agi_controller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if genome_address(program_id, config_account.key, &genome_hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *agi_controller.key != config.agi_controller {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if complexity > 100 || uniqueness > 100 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    let mut genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    if !genome_data.is_minted {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    // A pending clawback may be corrected before it executes; otherwise
+    // the score must have expired
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    if genome_data.pending_clawback > 0 {
+        if clock.unix_timestamp >= genome_data.clawback_eta {
+            return Err(RSMError::ClawbackDue.into());
+        }
+    } else {
+        let expires_at = genome_data.scored_at.saturating_add(config.score_validity_secs);
+        if config.score_validity_secs == 0 || clock.unix_timestamp < expires_at {
+            return Err(RSMError::ScoreNotExpired.into());
+        }
+    }
+    
+    if config.score_oracle != Pubkey::default() {
+        let score_account = next_account_info(account_info_iter)?;
+        let score = CanonicalScore::load(score_account, &config.score_oracle, &config.mint, &genome_hash)?;
+        if score.complexity != complexity || score.uniqueness != uniqueness {
+            msg!("Scores do not match oracle: complexity {}, uniqueness {}", score.complexity, score.uniqueness);
+            return Err(ProgramError::InvalidArgument);
+        }
+    }
+    
+    // Same weights, entropy, blockchain component and smoothing as the
+    // original mint, so only the score moves. Weights overwritten in the
+    // history fall back to the current ones
+    let params = config.agi_params_at(genome_data.mint_slot).unwrap_or(config.agi_params);
+    let rescored_amount = EmissionSmoothing::apply(
+        scale_amount(
+            calculate_token_amount(&params, complexity, uniqueness, genome_data.entropy, genome_data.blockchain_entropy),
+            config.decimals,
+        ).ok_or(ProgramError::ArithmeticOverflow)?,
+        genome_data.emission_scale_bps as u64,
+    );
+    let clawback = rescore_clawback(genome_data.tokens_minted, rescored_amount, genome_data.bonded_amount);
+    
+    genome_data.complexity = complexity;
+    genome_data.uniqueness = uniqueness;
+    genome_data.scored_at = clock.unix_timestamp;
+    genome_data.pending_clawback = clawback;
+    genome_data.clawback_eta = if clawback > 0 {
+        clock.unix_timestamp.saturating_add(CLAWBACK_DELAY)
+    } else {
+        0
+    };
+    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+    
+    emit_event("GenomeRescoredEvent", &GenomeRescoredEvent {
+        genome_hash,
+        complexity,
+        uniqueness,
+        rescored_amount,
+        clawback,
+        clawback_eta: genome_data.clawback_eta,
+    })?;
+    
+    msg!("Genome re-scored: {} of {} to be clawed back", clawback, genome_data.tokens_minted);
+    Ok(())
+}
+
+pub fn process_execute_clawback(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    genome_hash: [u8; 32],
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let bond_vault = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if genome_address(program_id, config_account.key, &genome_hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *mint_account.key != config.mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *token_program.key != *mint_account.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    if genome_data.pending_clawback == 0 {
+        return Err(RSMError::NoPendingClawback.into());
+    }
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    if clock.unix_timestamp < genome_data.clawback_eta {
+        return Err(RSMError::ClawbackDelayed.into());
+    }
+    
+    let amount = genome_data.pending_clawback;
+    genome_data.tokens_minted -= amount;
+    genome_data.bonded_amount -= amount;
+    genome_data.pending_clawback = 0;
+    genome_data.clawback_eta = 0;
+    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+    
+    config.total_minted = config.total_minted.checked_sub(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    burn_bond(program_id, config_account, bond_vault, mint_account, token_program, amount)?;
+    
+    emit_event("ClawbackExecutedEvent", &ClawbackExecutedEvent {
+        genome_hash,
+        owner: genome_data.owner,
+        amount,
+    })?;
+    
+    msg!("Clawed back {} RSM from genome", amount);
+    Ok(())
+}
+
+/// Burns `amount` of the escrowed bonds from the config's bond vault.
+fn burn_bond<'a>(
+    program_id: &Pubkey,
+    config_account: &AccountInfo<'a>,
+    bond_vault: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    if bond_vault_address(program_id, config_account.key).0 != *bond_vault.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (_, config_bump) = config_address(program_id, mint_account.key);
+    invoke_signed(
+        &spl_token_2022::instruction::burn(
+            token_program.key,
+            bond_vault.key,
+            mint_account.key,
+            config_account.key,
+            &[],
+            amount,
+        )?,
+        &[bond_vault.clone(), mint_account.clone(), config_account.clone(), token_program.clone()],
+        &[&[CONFIG_SEED, mint_account.key.as_ref(), &[config_bump]]],
+    )
+}
+
+/// Pays `amount` of the escrowed bonds from the config's bond vault into
+/// `destination`.
+fn pay_bond<'a>(
+    program_id: &Pubkey,
+    config_account: &AccountInfo<'a>,
+    bond_vault: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    if bond_vault_address(program_id, config_account.key).0 != *bond_vault.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let decimals = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data.borrow())?.base.decimals;
+    let (_, config_bump) = config_address(program_id, mint_account.key);
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            bond_vault.key,
+            mint_account.key,
+            destination.key,
+            config_account.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[bond_vault.clone(), mint_account.clone(), destination.clone(), config_account.clone(), token_program.clone()],
+        &[&[CONFIG_SEED, mint_account.key.as_ref(), &[config_bump]]],
+    )
+}
+
+pub fn process_create_bond_vault(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let bond_vault = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *mint_account.key != config.mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *token_program.key != *mint_account.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let (vault_key, bump) = bond_vault_address(program_id, config_account.key);
+    if vault_key != *bond_vault.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    // Room for the account extensions the mint's extensions require
+    let vault_len = {
+        let data = mint_account.data.borrow();
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+            &ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?),
+        )?
+    };
+    let rent = Rent::get()?;
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            bond_vault.key,
+            rent.minimum_balance(vault_len),
+            vault_len as u64,
+            token_program.key,
+        ),
+        &[payer.clone(), bond_vault.clone(), system_program.clone()],
+        &[&[BOND_VAULT_SEED, config_account.key.as_ref(), &[bump]]],
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_account3(
+            token_program.key,
+            bond_vault.key,
+            mint_account.key,
+            config_account.key,
+        )?,
+        &[bond_vault.clone(), mint_account.clone()],
+    )?;
+    
+    msg!("Bond vault created: {}", vault_key);
+    Ok(())
+}
+
+pub fn process_release_bond(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    genome_hash: [u8; 32],
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    let bond_vault = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let owner_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    
+    if config_account.owner != program_id || genome_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if genome_address(program_id, config_account.key, &genome_hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    if *mint_account.key != config.mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *token_program.key != *mint_account.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let mut genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    if genome_data.bonded_amount == 0 {
+        return Err(RSMError::NoBond.into());
+    }
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    if !bond_releasable(&config, &genome_data, clock.unix_timestamp) {
+        return Err(RSMError::BondLocked.into());
+    }
+    // A streaming genome is owned by its stream until the last withdrawal
+    check_token_account(owner_token_account, &config.mint, &genome_data.owner)?;
+    
+    let amount = genome_data.bonded_amount;
+    genome_data.bonded_amount = 0;
+    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+    pay_bond(program_id, config_account, bond_vault, mint_account, owner_token_account, token_program, amount)?;
+    
+    msg!("Released a bond of {} RSM to {}", amount, genome_data.owner);
+    Ok(())
+}
+
+/// The clawback record at its PDA for `evidence_hash`, still pending.
+fn pending_clawback_record(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+    record_account: &AccountInfo,
+    evidence_hash: &[u8; 32],
+) -> Result<ClawbackRecord, ProgramError> {
+    if clawback_address(program_id, config_account.key, evidence_hash).0 != *record_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if record_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let record = ClawbackRecord::try_from_slice(&record_account.data.borrow())?;
+    if record.status != ClawbackStatus::Pending {
+        return Err(RSMError::ClawbackNotPending.into());
+    }
+    Ok(record)
+}
+
+pub fn process_schedule_emergency_clawback(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    evidence_hash: [u8; 32],
+    reason_code: u16,
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let executor = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let record_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    check_supply_signers(&config, authority, executor)?;
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    // Both token programs share the base account layout
+    if *token_account.owner != spl_token::id() && *token_account.owner != spl_token_2022::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let owner = {
+        let data = token_account.data.borrow();
+        let holding = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+        if holding.base.mint != config.mint {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        holding.base.owner
+    };
+    
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    let eta = clock.unix_timestamp
+        .checked_add(EMERGENCY_CLAWBACK_TIMELOCK)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    
+    // Creating the record fails if this evidence was already used
+    let (record_key, record_bump) = clawback_address(program_id, config_account.key, &evidence_hash);
+    if record_key != *record_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let rent = Rent::get()?;
+    invoke_signed(
+        &system_instruction::create_account(
+            authority.key,
+            record_account.key,
+            rent.minimum_balance(ClawbackRecord::LEN),
+            ClawbackRecord::LEN as u64,
+            program_id,
+        ),
+        &[authority.clone(), record_account.clone(), system_program.clone()],
+        &[&[CLAWBACK_SEED, config_account.key.as_ref(), &evidence_hash, &[record_bump]]],
+    )?;
+    let record = ClawbackRecord {
+        config: *config_account.key,
+        token_account: *token_account.key,
+        owner,
+        amount,
+        evidence_hash,
+        reason_code,
+        scheduled_at: clock.unix_timestamp,
+        eta,
+        status: ClawbackStatus::Pending,
+        executed_at: 0,
+    };
+    record.serialize(&mut &mut record_account.data.borrow_mut()[..])?;
+    
+    msg!("Emergency clawback of {} from {} scheduled for {} (reason {})", amount, token_account.key, eta, reason_code);
+    Ok(())
+}
+
+pub fn process_emergency_clawback(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    evidence_hash: [u8; 32],
+    clock: &dyn ClockProvider,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let executor = next_account_info(account_info_iter)?;
+    let clock_sysvar = next_account_info(account_info_iter)?;
+    let record_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let genome_account = next_account_info(account_info_iter)?;
+    
+    if config_account.owner != program_id || genome_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    check_supply_signers(&config, authority, executor)?;
+    let mut record = pending_clawback_record(program_id, config_account, record_account, &evidence_hash)?;
+    if *token_account.key != record.token_account || *mint_account.key != config.mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *token_program.key != *mint_account.owner || *token_account.owner != *mint_account.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+    if genome_address(program_id, config_account.key, &genome_data.hash).0 != *genome_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    check_clock_sysvar(clock_sysvar)?;
+    let clock = clock.now()?;
+    if clock.unix_timestamp < record.eta {
+        return Err(RSMError::ClawbackDelayed.into());
+    }
+    {
+        let data = token_account.data.borrow();
+        let holding = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+        if holding.base.delegate != Some(*config_account.key).into() || holding.base.delegated_amount < record.amount {
+            return Err(RSMError::ClawbackNotDelegated.into());
+        }
+    }
+    
+    // The genome gives the tokens back too, so a supply audit still adds
+    // up to total_minted; its bond stays in escrow, so only the rest can go
+    if record.amount > genome_data.tokens_minted - genome_data.bonded_amount {
+        return Err(ProgramError::InvalidArgument);
+    }
+    genome_data.tokens_minted -= record.amount;
+    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
+    
+    record.status = ClawbackStatus::Executed;
+    record.executed_at = clock.unix_timestamp;
+    record.serialize(&mut &mut record_account.data.borrow_mut()[..])?;
+    config.total_minted = config.total_minted.checked_sub(record.amount).ok_or(ProgramError::ArithmeticOverflow)?;
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    let (_, config_bump) = config_address(program_id, mint_account.key);
+    invoke_signed(
+        &spl_token_2022::instruction::burn(
+            token_program.key,
+            token_account.key,
+            mint_account.key,
+            config_account.key,
+            &[],
+            record.amount,
+        )?,
+        &[token_account.clone(), mint_account.clone(), config_account.clone(), token_program.clone()],
+        &[&[CONFIG_SEED, mint_account.key.as_ref(), &[config_bump]]],
+    )?;
+    
+    emit_event("EmergencyClawbackEvent", &EmergencyClawbackEvent {
+        config: *config_account.key,
+        token_account: *token_account.key,
+        owner: record.owner,
+        amount: record.amount,
+        evidence_hash,
+        reason_code: record.reason_code,
+    })?;
+    
+    msg!("Emergency clawback burned {} from {}", record.amount, token_account.key);
+    Ok(())
 }
 
-pub fn process_initialize(
-    _program_id: &Pubkey,
+pub fn process_cancel_emergency_clawback(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    max_supply: u64,
-    agi_controller: Pubkey,
+    evidence_hash: [u8; 32],
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let config_account = next_account_info(account_info_iter)?;
-    let authority = next_account_info(account_info_iter)?;
+    let signer = next_account_info(account_info_iter)?;
+    let record_account = next_account_info(account_info_iter)?;
     
-    if !authority.is_signer {
+    if !signer.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    let executor = config.supply_governance.executor;
+    if *signer.key != config.authority && (executor == Pubkey::default() || *signer.key != executor) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mut record = pending_clawback_record(program_id, config_account, record_account, &evidence_hash)?;
     
-    let config = TokenConfig {
-        authority: *authority.key,
-        agi_controller,
-        max_supply,
-        total_minted: 0,
-        agi_params: AGIParameters::default(),
-    };
-    
-    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
-    msg!("RSM Token initialized with max supply: {}", max_supply);
+    record.status = ClawbackStatus::Cancelled;
+    record.serialize(&mut &mut record_account.data.borrow_mut()[..])?;
     
+    msg!("Emergency clawback of {} from {} cancelled", record.amount, record.token_account);
     Ok(())
 }
 
-pub fn process_mint_from_genome(
-    _program_id: &Pubkey,
+fn audit_address(program_id: &Pubkey, config: &Pubkey, auditor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUDIT_SEED, config.as_ref(), auditor.as_ref()], program_id)
+}
+
+pub fn process_start_supply_audit(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    genome_hash: [u8; 32],
-    complexity: u8,
-    uniqueness: u8,
-    entropy: u16,
-    amount: u64,
+    clock: &dyn ClockProvider,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let config_account = next_account_info(account_info_iter)?;
-    let genome_account = next_account_info(account_info_iter)?;
-    let _mint_account = next_account_info(account_info_iter)?;
-    let recipient_account = next_account_info(account_info_iter)?;
-    let agi_controller = next_account_info(account_info_iter)?;
-    let _token_program = next_account_info(account_info_iter)?;
-    let clock_sysvar = next_account_info(account_info_iter)?;
+    let audit_account = next_account_info(account_info_iter)?;
+    let auditor = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
     
-    if !agi_controller.is_signer {
+    if !auditor.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
-    
-    if *agi_controller.key != config.agi_controller {
-        return Err(ProgramError::InvalidAccountData);
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    let (audit_key, bump) = audit_address(program_id, config_account.key, auditor.key);
+    if audit_key != *audit_account.key {
+        return Err(ProgramError::InvalidSeeds);
     }
     
-    if complexity > 100 || uniqueness > 100 || entropy > 200 {
-        return Err(ProgramError::InvalidArgument);
+    if audit_account.data_is_empty() {
+        let rent = Rent::get()?;
+        invoke_signed(
+            &system_instruction::create_account(
+                auditor.key,
+                audit_account.key,
+                rent.minimum_balance(SupplyAudit::LEN),
+                SupplyAudit::LEN as u64,
+                program_id,
+            ),
+            &[auditor.clone(), audit_account.clone(), system_program.clone()],
+            &[&[AUDIT_SEED, config_account.key.as_ref(), auditor.key.as_ref(), &[bump]]],
+        )?;
     }
     
-    let clock = Clock::from_account_info(clock_sysvar)?;
-    let calculated_amount = calculate_token_amount(&config.agi_params, complexity, uniqueness, entropy, clock.slot);
+    // Challenger rewards are the one supply no genome counts
+    let audit = SupplyAudit {
+        auditor: *auditor.key,
+        started_slot: clock.now()?.slot,
+        recorded_at_start: config.total_minted,
+        computed_total: config.dispute_rewards,
+        ..SupplyAudit::default()
+    };
+    audit.serialize(&mut &mut audit_account.data.borrow_mut()[..])?;
     
-    if amount != calculated_amount {
-        msg!("Amount mismatch: expected {}, got {}", calculated_amount, amount);
-        return Err(ProgramError::InvalidArgument);
-    }
+    msg!("Supply audit started, recorded total {}", config.total_minted);
+    Ok(())
+}
+
+/// Remaining accounts: genome PDAs in strictly increasing address order.
+pub fn process_audit_supply_page(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_account = next_account_info(account_info_iter)?;
+    let audit_account = next_account_info(account_info_iter)?;
+    let auditor = next_account_info(account_info_iter)?;
     
-    if config.total_minted + amount > config.max_supply {
-        return Err(ProgramError::InvalidArgument);
+    if !auditor.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if audit_address(program_id, config_account.key, auditor.key).0 != *audit_account.key {
+        return Err(ProgramError::InvalidSeeds);
     }
     
-    let genome_data = GenomeData {
-        hash: genome_hash,
-        owner: *recipient_account.key,
-        tokens_minted: amount,
-        complexity,
-        uniqueness,
-        is_minted: true,
-        mint_timestamp: clock.unix_timestamp,
-    };
+    let mut audit = SupplyAudit::try_from_slice(&audit_account.data.borrow())?;
+    if audit.finished {
+        return Err(RSMError::AuditFinished.into());
+    }
     
-    genome_data.serialize(&mut &mut genome_account.data.borrow_mut()[..])?;
-    config.total_minted += amount;
-    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    for genome_account in account_info_iter {
+        if audit.genomes_counted > 0 && *genome_account.key <= audit.cursor {
+            return Err(RSMError::AuditPageOutOfOrder.into());
+        }
+        if genome_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        let genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
+        if genome_address(program_id, config_account.key, &genome_data.hash).0 != *genome_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        
+        if genome_data.is_minted {
+            audit.computed_total = audit.computed_total
+                .checked_add(genome_data.tokens_minted)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        audit.genomes_counted += 1;
+        audit.cursor = *genome_account.key;
+    }
     
-    msg!("Minted {} RSM tokens for genome", amount);
+    audit.serialize(&mut &mut audit_account.data.borrow_mut()[..])?;
     
+    msg!("Audited {} genomes, running total {}", audit.genomes_counted, audit.computed_total);
     Ok(())
 }
 
-pub fn process_burn_genome(
-    _program_id: &Pubkey,
+pub fn process_finish_supply_audit(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    genome_hash: [u8; 32],
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let config_account = next_account_info(account_info_iter)?;
-    let genome_account = next_account_info(account_info_iter)?;
-    let _mint_account = next_account_info(account_info_iter)?;
-    let _owner_token_account = next_account_info(account_info_iter)?;
-    let owner = next_account_info(account_info_iter)?;
+    let audit_account = next_account_info(account_info_iter)?;
+    let auditor = next_account_info(account_info_iter)?;
     
-    if !owner.is_signer {
+    if !auditor.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
+    if audit_address(program_id, config_account.key, auditor.key).0 != *audit_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
     
-    let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
-    let genome_data = GenomeData::try_from_slice(&genome_account.data.borrow())?;
-    
-    if genome_data.hash != genome_hash || genome_data.owner != *owner.key {
-        return Err(ProgramError::IllegalOwner);
+    let config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
+    let mut audit = SupplyAudit::try_from_slice(&audit_account.data.borrow())?;
+    if audit.finished {
+        return Err(RSMError::AuditFinished.into());
     }
     
-    config.total_minted -= genome_data.tokens_minted;
-    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    audit.finished = true;
+    audit.serialize(&mut &mut audit_account.data.borrow_mut()[..])?;
+    
+    let report = SupplyAuditEvent {
+        config: *config_account.key,
+        auditor: *auditor.key,
+        recorded_total: config.total_minted,
+        computed_total: audit.computed_total,
+        discrepancy: config.total_minted as i128 - audit.computed_total as i128,
+        genomes_counted: audit.genomes_counted,
+    };
+    set_return_data(&report.try_to_vec()?);
+    emit_event("SupplyAuditEvent", &report)?;
     
-    msg!("Burned {} RSM tokens", genome_data.tokens_minted);
+    msg!("Supply audit: recorded {}, computed {}, discrepancy {}", report.recorded_total, report.computed_total, report.discrepancy);
     Ok(())
 }
 
-pub fn process_update_agi_params(
-    _program_id: &Pubkey,
+pub fn process_repair_supply(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    complexity_weight: u8,
-    uniqueness_weight: u8,
-    entropy_weight: u8,
-    blockchain_weight: u8,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let config_account = next_account_info(account_info_iter)?;
-    let agi_controller = next_account_info(account_info_iter)?;
+    let audit_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
     
-    if !agi_controller.is_signer {
+    if !authority.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
     let mut config = TokenConfig::try_from_slice(&config_account.data.borrow())?;
     
-    if *agi_controller.key != config.agi_controller {
+    if *authority.key != config.authority {
         return Err(ProgramError::InvalidAccountData);
     }
+    if audit_address(program_id, config_account.key, authority.key).0 != *audit_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
     
-    if complexity_weight + uniqueness_weight + entropy_weight + blockchain_weight != 100 {
-        return Err(ProgramError::InvalidArgument);
+    let audit = SupplyAudit::try_from_slice(&audit_account.data.borrow())?;
+    if !audit.finished || audit.recorded_at_start != config.total_minted {
+        return Err(RSMError::AuditStale.into());
+    }
+    // Only an audit that counted every genome may lower the total; without
+    // a census nothing proves that, so the total can only be raised
+    let complete = if config.genome_census.tracked {
+        audit.genomes_counted == config.genome_census.count
+    } else {
+        audit.computed_total >= config.total_minted
+    };
+    if !complete {
+        return Err(RSMError::AuditIncomplete.into());
     }
     
-    config.agi_params.complexity_weight = complexity_weight;
-    config.agi_params.uniqueness_weight = uniqueness_weight;
-    config.agi_params.entropy_weight = entropy_weight;
-    config.agi_params.blockchain_weight = blockchain_weight;
+    let old_total = config.total_minted;
+    config.total_minted = audit.computed_total;
     config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
     
-    msg!("AGI parameters updated");
+    emit_event("SupplyRepairedEvent", &SupplyRepairedEvent {
+        config: *config_account.key,
+        old_total,
+        new_total: audit.computed_total,
+    })?;
+    
+    msg!("Supply repaired: {} -> {}", old_total, audit.computed_total);
     Ok(())
 }
 
+/// Rescales an amount in DECIMALS base units to a mint with `decimals`.
+pub fn scale_amount(amount: u64, decimals: u8) -> Option<u64> {
+    if decimals >= DECIMALS {
+        amount.checked_mul(10u64.checked_pow((decimals - DECIMALS) as u32)?)
+    } else {
+        Some(amount / 10u64.pow((DECIMALS - decimals) as u32))
+    }
+}
+
+/// Scores weighted by the AGI parameters, up to MAX_WEIGHTED_SCORE.
+pub fn weighted_score(
+    params: &AGIParameters,
+    complexity: u8,
+    uniqueness: u8,
+    entropy: u16,
+    blockchain: u8,
+) -> u64 {
+    (complexity as u64 * params.complexity_weight as u64) +
+    (uniqueness as u64 * params.uniqueness_weight as u64) +
+    (entropy as u64 * params.entropy_weight as u64 / 2) +
+    ((blockchain as u64 % 100) * params.blockchain_weight as u64)
+}
+
 pub fn calculate_token_amount(
     params: &AGIParameters,
     complexity: u8,
     uniqueness: u8,
     entropy: u16,
-    slot: u64,
+    blockchain: u8,
 ) -> u64 {
-    let weighted_score = 
-        (complexity as u64 * params.complexity_weight as u64) +
-        (uniqueness as u64 * params.uniqueness_weight as u64) +
-        (entropy as u64 * params.entropy_weight as u64 / 2) +
-        ((slot % 100) * params.blockchain_weight as u64);
+    let weighted_score = weighted_score(params, complexity, uniqueness, entropy, blockchain);
     
     let base_amount = (weighted_score * 1_000_000_000_000) / 100_000;
     
@@ -296,7 +5476,7 @@ mod tests {
     #[test]
     fn test_agi_high() {
         let params = AGIParameters::default();
-        let amt = calculate_token_amount(&params, 95, 90, 198, 12345);
+        let amt = calculate_token_amount(&params, 95, 90, 198, 45);
         assert!(amt > 100_000_000_000);
         println!("High quality: {} RSM", amt / 1_000_000_000);
     }
@@ -304,7 +5484,7 @@ mod tests {
     #[test]
     fn test_agi_medium() {
         let params = AGIParameters::default();
-        let amt = calculate_token_amount(&params, 50, 50, 100, 12345);
+        let amt = calculate_token_amount(&params, 50, 50, 100, 45);
         assert!(amt > 10_000_000_000);
         println!("Medium quality: {} RSM", amt / 1_000_000_000);
     }
@@ -312,19 +5492,938 @@ mod tests {
     #[test]
     fn test_agi_low() {
         let params = AGIParameters::default();
-        let amt = calculate_token_amount(&params, 20, 20, 40, 12345);
+        let amt = calculate_token_amount(&params, 20, 20, 40, 45);
         println!("Low quality: {} RSM", amt / 1_000_000_000);
     }
     
     #[test]
     fn test_bonus() {
         let params = AGIParameters::default();
-        let exc = calculate_token_amount(&params, 95, 95, 198, 12345);
-        let good = calculate_token_amount(&params, 85, 85, 170, 12345);
-        let norm = calculate_token_amount(&params, 75, 75, 150, 12345);
+        let exc = calculate_token_amount(&params, 95, 95, 198, 45);
+        let good = calculate_token_amount(&params, 85, 85, 170, 45);
+        let norm = calculate_token_amount(&params, 75, 75, 150, 45);
         assert!(exc > good && good > norm);
         println!("Exceptional: {} RSM", exc / 1_000_000_000);
         println!("Good: {} RSM", good / 1_000_000_000);
         println!("Normal: {} RSM", norm / 1_000_000_000);
     }
+    
+    #[test]
+    fn test_slot_hash_entropy() {
+        let entries: [(u64, [u8; 32]); 3] = [(1_002, [3; 32]), (1_001, [2; 32]), (999, [1; 32])];
+        let mut data = (entries.len() as u64).to_le_bytes().to_vec();
+        for (slot, hash) in entries {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&hash);
+        }
+        for (slot, hash) in entries {
+            assert_eq!(slot_hash_at(&data, slot), Some(hash));
+        }
+        assert_eq!(newest_slot_hash(&data), Some(entries[0]));
+        assert_eq!(newest_slot_hash(&0u64.to_le_bytes()), None);
+        // Skipped, too new and too old slots have no hash
+        assert_eq!(slot_hash_at(&data, 1_000), None);
+        assert_eq!(slot_hash_at(&data, 1_003), None);
+        assert_eq!(slot_hash_at(&data, 998), None);
+        assert_eq!(slot_hash_at(&data[..data.len() - 1], 999), None);
+        
+        let genome = [9u8; 32];
+        let entropy = blockchain_entropy(&genome, &[3; 32]);
+        assert!(entropy < 100);
+        assert_eq!(entropy, blockchain_entropy(&genome, &[3; 32]));
+        let spread: std::collections::HashSet<u8> = (0..=255u8).map(|seed| blockchain_entropy(&genome, &[seed; 32])).collect();
+        assert!(spread.len() > 50);
+    }
+    
+    #[test]
+    fn test_breaker_steady() {
+        let mut breaker = EmissionBreaker { window_slots: 100, ..EmissionBreaker::default() };
+        for window in 0..10u64 {
+            assert!(breaker.record(window * 100, 1_000, DECIMALS));
+            assert!(breaker.record(window * 100 + 50, 1_000, DECIMALS));
+        }
+        assert!(!breaker.tripped);
+        assert_eq!(breaker.trailing_average, 2_000);
+    }
+    
+    #[test]
+    fn test_breaker_trips_and_resets() {
+        let floor = EmissionBreaker::floor_average(DECIMALS);
+        let mut breaker = EmissionBreaker { window_slots: 100, ..EmissionBreaker::default() };
+        // The first window has no average yet and is bounded by the floor
+        assert!(breaker.record(0, floor * 2, DECIMALS));
+        let mut probe = breaker;
+        assert!(!probe.record(50, floor + 1, DECIMALS));
+        assert!(breaker.record(100, floor * 6, DECIMALS));
+        assert!(!breaker.record(150, 1, DECIMALS));
+        assert!(breaker.tripped);
+        assert!(!breaker.record(500, 1, DECIMALS));
+        
+        breaker.reset(500);
+        assert!(!breaker.tripped);
+        assert!(breaker.record(500, 1_000, DECIMALS));
+    }
+    
+    #[test]
+    fn test_breaker_floor_follows_decimals() {
+        assert_eq!(EmissionBreaker::floor_average(9), 10_000 * 1_000_000_000);
+        assert_eq!(EmissionBreaker::floor_average(6), 10_000 * 1_000_000);
+        assert_eq!(EmissionBreaker::floor_average(0), 10_000);
+        
+        // 20,000 six-decimal tokens are within three times the floor
+        let mut breaker = EmissionBreaker::default();
+        assert!(breaker.record(0, 20_000 * 1_000_000, 6));
+        assert!(!breaker.record(1, 20_000 * 1_000_000, 6));
+    }
+    
+    #[test]
+    fn test_dedupe_rejects_duplicates() {
+        let mut dedupe = MintDedupe::default();
+        let hash = [7u8; 32];
+        assert_eq!(dedupe.check_and_record(&hash, 1, 1_000, 1_010), Ok(()));
+        assert_eq!(dedupe.check_and_record(&hash, 1, 1_000, 1_050), Err(RSMError::DuplicateMint));
+        assert_eq!(dedupe.check_and_record(&hash, 2, 1_000, 1_050), Ok(()));
+        assert_eq!(dedupe.check_and_record(&[8u8; 32], 1, 1_000, 1_050), Ok(()));
+    }
+    
+    #[test]
+    fn test_dedupe_window() {
+        let mut dedupe = MintDedupe::default();
+        let hash = [7u8; 32];
+        assert_eq!(dedupe.check_and_record(&hash, 1, 1_000, 1_151), Err(RSMError::StaleNonce));
+        assert_eq!(dedupe.check_and_record(&hash, 1, 1_200, 1_100), Err(RSMError::StaleNonce));
+        
+        for nonce in 0..DEDUPE_CAPACITY as u64 {
+            assert_eq!(dedupe.check_and_record(&hash, nonce, 2_000, 2_000), Ok(()));
+        }
+        assert_eq!(dedupe.check_and_record(&hash, 999, 2_000, 2_000), Err(RSMError::DedupeBufferFull));
+        assert_eq!(dedupe.check_and_record(&hash, 999, 2_200, 2_200), Ok(()));
+    }
+    
+    #[test]
+    fn test_dedupe_evicts_by_slot() {
+        let mut dedupe = MintDedupe::default();
+        let hash = [7u8; 32];
+        for nonce in 0..DEDUPE_CAPACITY as u64 {
+            let slot = if nonce == 5 { 1_000 } else { 1_100 };
+            assert_eq!(dedupe.check_and_record(&hash, nonce, slot, 1_100), Ok(()));
+        }
+        
+        // Only the entry from slot 1,000 has expired; `next` points at a live one
+        assert_eq!(dedupe.next, 0);
+        assert_eq!(dedupe.check_and_record(&hash, 999, 1_160, 1_160), Ok(()));
+        assert_eq!(dedupe.entries[5].nonce, 999);
+        assert_eq!(dedupe.next, 6);
+        assert_eq!(dedupe.check_and_record(&hash, 1_000, 1_160, 1_160), Err(RSMError::DedupeBufferFull));
+    }
+    
+    #[test]
+    fn test_dedupe_unpacks_short_buffers() {
+        let mut data = vec![0u8; 2 + 64 * MintDedupe::ENTRY_LEN];
+        let mut dedupe = MintDedupe::unpack(&data).unwrap();
+        assert_eq!(dedupe.entries.len(), 64);
+        
+        let hash = [7u8; 32];
+        for nonce in 0..64 {
+            assert_eq!(dedupe.check_and_record(&hash, nonce, 1_000, 1_000), Ok(()));
+        }
+        assert_eq!(dedupe.check_and_record(&hash, 64, 1_000, 1_000), Err(RSMError::DedupeBufferFull));
+        dedupe.pack(&mut data).unwrap();
+        assert_eq!(MintDedupe::unpack(&data).unwrap(), dedupe);
+        
+        let mut full = vec![0u8; MintDedupe::LEN];
+        MintDedupe::default().pack(&mut full).unwrap();
+        assert_eq!(MintDedupe::unpack(&full).unwrap(), MintDedupe::default());
+        assert_eq!(MintDedupe::unpack(&[0u8; 2]), Err(ProgramError::InvalidAccountData));
+    }
+    
+    #[test]
+    fn test_scale_amount() {
+        assert_eq!(scale_amount(1_500_000_000, DECIMALS), Some(1_500_000_000));
+        assert_eq!(scale_amount(1_500_000_000, 6), Some(1_500_000));
+        assert_eq!(scale_amount(1_500_000_000, 0), Some(1));
+        assert_eq!(scale_amount(1_500_000_000, 12), Some(1_500_000_000_000));
+        assert_eq!(scale_amount(u64::MAX, 10), None);
+    }
+    
+    #[test]
+    fn test_config_len() {
+        let config = TokenConfig {
+            schema_version: TokenConfig::SCHEMA.current(),
+            authority: Pubkey::new_unique(),
+            agi_controller: Pubkey::new_unique(),
+            max_supply: MAX_SUPPLY,
+            total_minted: 0,
+            agi_params: AGIParameters::default(),
+            score_oracle: Pubkey::default(),
+            breaker: EmissionBreaker::default(),
+            mint: Pubkey::new_unique(),
+            decimals: DECIMALS,
+            supply_governance: SupplyGovernance::default(),
+            score_validity_secs: 0,
+            bond_bps: 0,
+            stream_slots: 0,
+            decay: QualityDecay::default(),
+            disputes: DisputePolicy::default(),
+            smoothing: EmissionSmoothing::default(),
+            require_recipient_signature: false,
+            mint_window: MintWindow::default(),
+            params_history: ParamsHistory::default(),
+            dust: DustAccumulator::default(),
+            discovery_bonus_bps: 0,
+            allowlist: RecipientAllowlist::default(),
+            quality_histogram: QualityHistogram::default(),
+            mint_quorum: MintQuorum::default(),
+            genome_census: GenomeCensus::default(),
+            dispute_rewards: 0,
+        };
+        assert_eq!(config.try_to_vec().unwrap().len(), TokenConfig::LEN);
+    }
+    
+    #[test]
+    fn test_delegate_limits() {
+        let mut delegate = MintDelegate {
+            max_amount: 1_000,
+            max_mints: 2,
+            expiry_slot: 500,
+            ..MintDelegate::default()
+        };
+        assert_eq!(delegate.consume(600, 100), Ok(()));
+        assert_eq!(delegate.consume(500, 100), Err(RSMError::DelegateLimitExceeded));
+        assert_eq!(delegate.consume(400, 500), Ok(()));
+        assert_eq!(delegate.consume(0, 500), Err(RSMError::DelegateLimitExceeded));
+        
+        delegate.max_mints = 3;
+        assert_eq!(delegate.consume(0, 501), Err(RSMError::DelegateExpired));
+        assert_eq!(delegate.minted_amount, 1_000);
+    }
+    
+    #[test]
+    fn test_burn_approval_allowance() {
+        let mut approval = BurnApproval {
+            remaining_amount: 1_000,
+            expiry_slot: 500,
+            ..BurnApproval::default()
+        };
+        assert_eq!(approval.consume(600, 100), Ok(()));
+        assert_eq!(approval.consume(500, 100), Err(RSMError::BurnAllowanceExceeded));
+        assert_eq!(approval.consume(400, 500), Ok(()));
+        assert_eq!(approval.consume(0, 501), Err(RSMError::BurnApprovalExpired));
+        assert_eq!(approval.remaining_amount, 0);
+        assert_eq!(approval.try_to_vec().unwrap().len(), BurnApproval::LEN);
+    }
+    
+    #[test]
+    fn test_supply_raise_timelock_and_cap() {
+        let mut governance = SupplyGovernance::default();
+        let max = 1_000_000;
+        assert_eq!(governance.apply(max, 0), Err(RSMError::NoPendingRaise));
+        
+        governance.pending_max_supply = 1_100_000;
+        governance.pending_eta = 100;
+        assert_eq!(governance.apply(max, 99), Err(RSMError::RaiseTimelocked));
+        let raise = governance.apply(max, 100).unwrap();
+        assert_eq!((raise.old_max_supply, raise.new_max_supply), (max, 1_100_000));
+        assert_eq!(governance.history[0], raise);
+        assert_eq!(governance.pending_max_supply, 0);
+        
+        // The 10% for this window is used up
+        assert_eq!(governance.check_raise(1_100_000, 1_100_001, 200), Err(RSMError::RaiseExceedsCap));
+        let next_window = 100 + SUPPLY_RAISE_WINDOW;
+        assert_eq!(governance.check_raise(1_100_000, 1_210_000, next_window), Ok(()));
+        assert_eq!(governance.check_raise(1_100_000, 1_210_001, next_window), Err(RSMError::RaiseExceedsCap));
+        
+        governance.pending_max_supply = 1_210_000;
+        governance.pending_eta = next_window;
+        governance.apply(1_100_000, next_window).unwrap();
+        assert_eq!(governance.raises, 2);
+        assert_eq!(governance.window_base, 1_100_000);
+    }
+    
+    #[test]
+    fn test_stream_vesting() {
+        let stream = StreamVault {
+            start_slot: 1_000,
+            end_slot: 1_400,
+            ..StreamVault::default()
+        };
+        assert_eq!(stream.vested(1_000, 900), 0);
+        assert_eq!(stream.vested(1_000, 1_000), 0);
+        assert_eq!(stream.vested(1_000, 1_100), 250);
+        assert_eq!(stream.vested(1_000, 1_399), 997);
+        assert_eq!(stream.vested(1_000, 1_400), 1_000);
+        assert_eq!(stream.vested(u64::MAX, 1_200), u64::MAX / 2);
+        assert_eq!(stream.try_to_vec().unwrap().len(), StreamVault::LEN);
+    }
+    
+    #[test]
+    fn test_species_address() {
+        let (program_id, config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(species_address(&program_id, &config, 7), species_address(&program_id, &config, 7));
+        assert_ne!(species_address(&program_id, &config, 7).0, species_address(&program_id, &config, 8).0);
+        assert_ne!(species_address(&program_id, &config, 7).0, species_address(&program_id, &Pubkey::new_unique(), 7).0);
+        assert_eq!(SpeciesRecord::default().try_to_vec().unwrap().len(), SpeciesRecord::LEN);
+    }
+    
+    #[test]
+    fn test_receipt_address() {
+        let (program_id, config, hash) = (Pubkey::new_unique(), Pubkey::new_unique(), [7u8; 32]);
+        assert_ne!(receipt_address(&program_id, &config, &hash).0, receipt_address(&program_id, &Pubkey::new_unique(), &hash).0);
+        assert_ne!(receipt_address(&program_id, &config, &hash).0, genome_address(&program_id, &config, &hash).0);
+    }
+    
+    #[test]
+    fn test_allowlist_proofs() {
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let leaves: Vec<[u8; 32]> = members.iter().map(allowlist_leaf).collect();
+        let node = |a: [u8; 32], b: [u8; 32]| {
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            hashv(&[&[1u8], &low, &high]).to_bytes()
+        };
+        let pair = node(leaves[0], leaves[1]);
+        let allowlist = RecipientAllowlist { merkle_root: node(pair, leaves[2]), ..RecipientAllowlist::default() };
+        assert!(allowlist.is_enabled());
+        assert!(allowlist.proves(&members[0], &[leaves[1], leaves[2]]));
+        assert!(allowlist.proves(&members[1], &[leaves[0], leaves[2]]));
+        assert!(allowlist.proves(&members[2], &[pair]));
+        assert!(!allowlist.proves(&members[2], &[leaves[0]]));
+        assert!(!allowlist.proves(&Pubkey::new_unique(), &[pair]));
+        
+        // A membership mint alone enables the allowlist but proves nothing
+        let allowlist = RecipientAllowlist { membership_mint: Pubkey::new_unique(), ..RecipientAllowlist::default() };
+        assert!(allowlist.is_enabled());
+        assert!(!allowlist.proves(&members[0], &[]));
+        assert!(!RecipientAllowlist::default().is_enabled());
+        assert_eq!(RecipientAllowlist::default().try_to_vec().unwrap().len(), RecipientAllowlist::LEN);
+    }
+    
+    #[test]
+    fn test_quality_histogram() {
+        let params = AGIParameters::default();
+        assert_eq!(weighted_score(&params, 100, 100, 200, 99) / 100, 99);
+        assert_eq!(QualityHistogram::bucket(0), 0);
+        assert_eq!(QualityHistogram::bucket(499), 0);
+        assert_eq!(QualityHistogram::bucket(500), 1);
+        assert_eq!(QualityHistogram::bucket(9_999), QUALITY_BUCKETS - 1);
+        assert_eq!(QualityHistogram::bucket(MAX_WEIGHTED_SCORE), QUALITY_BUCKETS - 1);
+        
+        let mut histogram = QualityHistogram::default();
+        histogram.record(5_200, 3);
+        histogram.record(5_400, 4);
+        assert_eq!((histogram.buckets[10], histogram.total(), histogram.epoch), (2, 2, 0));
+        
+        // Resetting starts over in each new epoch
+        histogram.reset_per_epoch = true;
+        histogram.record(9_000, 4);
+        assert_eq!((histogram.buckets[10], histogram.buckets[18], histogram.epoch), (0, 1, 4));
+        histogram.record(100, 4);
+        assert_eq!(histogram.total(), 2);
+        assert_eq!(histogram.try_to_vec().unwrap().len(), QualityHistogram::LEN);
+    }
+    
+    #[test]
+    fn test_stake_minted_instruction() {
+        let staking_program = Pubkey::new_unique();
+        let accounts = [(); 5].map(|_| Pubkey::new_unique());
+        let ix = stake_minted_instruction(&staking_program, accounts.each_ref(), 1_000);
+        assert_eq!(ix.program_id, staking_program);
+        assert_eq!(ix.data[..8], hash(b"global:stake_minted").to_bytes()[..8]);
+        assert_eq!(ix.data[8..], 1_000u64.to_le_bytes());
+        assert_eq!(ix.accounts.iter().map(|meta| meta.pubkey).collect::<Vec<_>>(), accounts);
+        // Only the stake authority signs, and only config and stake account are written
+        assert_eq!(ix.accounts.iter().map(|meta| (meta.is_signer, meta.is_writable)).collect::<Vec<_>>(), vec![
+            (false, true),
+            (false, true),
+            (false, false),
+            (false, false),
+            (true, false),
+        ]);
+        
+        let opt_in = AutoStakeOptIn { config: Pubkey::new_unique(), recipient: Pubkey::new_unique(), staking_program };
+        assert_eq!(opt_in.try_to_vec().unwrap().len(), AutoStakeOptIn::LEN);
+    }
+    
+    #[test]
+    fn test_split_amounts() {
+        assert_eq!(split_amounts(1_000, &[10_000]).unwrap(), vec![1_000]);
+        assert_eq!(split_amounts(1_000, &[6_000, 3_000, 1_000]).unwrap(), vec![600, 300, 100]);
+        // Rounding dust goes to the last share
+        assert_eq!(split_amounts(1_001, &[3_333, 3_333, 3_334]).unwrap(), vec![333, 333, 335]);
+        assert_eq!(split_amounts(u64::MAX, &[5_000, 5_000]).unwrap(), vec![u64::MAX / 2, u64::MAX / 2 + 1]);
+        assert_eq!(split_amounts(1_000, &[2_000; 5]).unwrap(), vec![200; 5]);
+        
+        assert_eq!(split_amounts(1_000, &[]), Err(RSMError::InvalidSplit));
+        assert_eq!(split_amounts(1_000, &[5_000, 4_999]), Err(RSMError::InvalidSplit));
+        assert_eq!(split_amounts(1_000, &[10_000, 0]), Err(RSMError::InvalidSplit));
+        assert_eq!(split_amounts(1_000, &[2_000, 2_000, 2_000, 2_000, 1_000, 1_000]), Err(RSMError::InvalidSplit));
+    }
+    
+    #[test]
+    fn test_quality_decay() {
+        let half_life = 4 * DECAY_STEP_SECS as i64;
+        let decay = QualityDecay { threshold_secs: 1_000, half_life_secs: half_life, floor_bps: 1_000 };
+        assert_eq!(QualityDecay::default().factor_bps(i64::MAX), BPS_DENOMINATOR);
+        assert_eq!(decay.factor_bps(1_000), 10_000);
+        // Partial steps past the threshold do not count
+        assert_eq!(decay.factor_bps(1_000 + DECAY_STEP_SECS as i64 - 1), 10_000);
+        assert_eq!(decay.factor_bps(1_000 + half_life / 2), 7_500);
+        assert_eq!(decay.factor_bps(1_000 + half_life), 5_000);
+        assert_eq!(decay.factor_bps(1_000 + 2 * half_life), 2_500);
+        assert_eq!(decay.factor_bps(1_000 + 4 * half_life), 1_000);
+        assert_eq!(decay.factor_bps(i64::MAX), 1_000);
+        assert_eq!(decay.apply(8_000, 1_000 + half_life), 4_000);
+        
+        assert_eq!(GenomeOrigin::Unknown.age_secs(1_000, 1_000), None);
+        assert_eq!(GenomeOrigin::Slot(500).age_secs(3_000, 0), Some(1_000));
+        assert_eq!(GenomeOrigin::Slot(5_000).age_secs(3_000, 0), Some(0));
+        assert_eq!(GenomeOrigin::Timestamp(400).age_secs(0, 1_000), Some(600));
+        assert_eq!(GenomeOrigin::Timestamp(2_000).age_secs(0, 1_000), Some(0));
+    }
+    
+    #[test]
+    fn test_rescore_clawback() {
+        // Higher or equal re-score: nothing to claw back
+        assert_eq!(rescore_clawback(1_000, 1_200, 300), 0);
+        assert_eq!(rescore_clawback(1_000, 1_000, 300), 0);
+        assert_eq!(rescore_clawback(1_000, 900, 300), 100);
+        // Limited to the bond
+        assert_eq!(rescore_clawback(1_000, 200, 300), 300);
+        assert_eq!(rescore_clawback(1_000, 200, 0), 0);
+    }
+    
+    #[test]
+    fn test_bond_releasable() {
+        assert_eq!(bond_amount(1_000, 2_500), 250);
+        assert_eq!(bond_amount(1_000, 0), 0);
+        
+        let mut config = TokenConfig::try_from_slice(&[0u8; TokenConfig::LEN]).unwrap();
+        let mut genome = GenomeData::try_from_slice(&[0u8; GenomeData::LEN]).unwrap();
+        (genome.mint_timestamp, genome.scored_at) = (1_000, 1_000);
+        // Nothing to wait for without disputes or expiring scores
+        assert!(bond_releasable(&config, &genome, 1_000));
+        
+        // Held through the challenge window
+        config.disputes.window_secs = 500;
+        assert!(!bond_releasable(&config, &genome, 1_500));
+        assert!(bond_releasable(&config, &genome, 1_501));
+        
+        // And until the controller has had CLAWBACK_DELAY to re-score
+        config.score_validity_secs = 2_000;
+        let rescore_closes = 3_000 + CLAWBACK_DELAY;
+        assert!(!bond_releasable(&config, &genome, rescore_closes - 1));
+        assert!(bond_releasable(&config, &genome, rescore_closes));
+        genome.pending_clawback = 1;
+        assert!(!bond_releasable(&config, &genome, rescore_closes));
+    }
+    
+    #[test]
+    fn test_genome_note_space() {
+        let mut genome = GenomeData::try_from_slice(&[0u8; GenomeData::LEN]).unwrap();
+        assert_eq!(genome.space(), GenomeData::LEN);
+        genome.note = vec![0xAB; MAX_NOTE_LEN];
+        let data = genome.try_to_vec().unwrap();
+        assert_eq!(data.len(), genome.space());
+        assert_eq!(GenomeData::try_from_slice(&data).unwrap().note, vec![0xAB; MAX_NOTE_LEN]);
+    }
+    
+    #[test]
+    fn test_account_schemas() {
+        let mut genome = GenomeData::try_from_slice(&[0u8; GenomeData::LEN]).unwrap();
+        genome.schema_version = GenomeData::SCHEMA.current();
+        genome.emission_scale_bps = BPS_DENOMINATOR as u16;
+        genome.mint_slot = 1_234;
+        genome.blockchain_entropy = 34;
+        for note in [Vec::new(), vec![7; MAX_NOTE_LEN]] {
+            genome.note = note;
+            let current = genome.try_to_vec().unwrap();
+            assert_eq!(GenomeData::SCHEMA.version_of(&current), Ok(4));
+            assert_eq!(GenomeData::SCHEMA.migrate(&current), Ok(None));
+            // Version 3 has no registrant in front of the note
+            let mut v3 = current.clone();
+            v3.drain(GenomeData::V3_LEN - 4..GenomeData::LEN - 4);
+            v3[0] = 3;
+            assert_eq!(GenomeData::SCHEMA.version_of(&v3), Ok(3));
+            assert_eq!(GenomeData::SCHEMA.migrate(&v3), Ok(Some(current.clone())));
+            // Version 2 has no entropy either; it came from the mint slot
+            let mut v2 = v3.clone();
+            v2.remove(GenomeData::V2_LEN - 4);
+            v2[0] = 2;
+            assert_eq!(GenomeData::SCHEMA.version_of(&v2), Ok(2));
+            assert_eq!(GenomeData::SCHEMA.migrate(&v2), Ok(Some(current.clone())));
+            // Version 1 has no scale either
+            let mut v1 = v2.clone();
+            v1.drain(GenomeData::V1_LEN - 4..GenomeData::V2_LEN - 4);
+            v1[0] = 1;
+            assert_eq!(GenomeData::SCHEMA.version_of(&v1), Ok(1));
+            assert_eq!(GenomeData::SCHEMA.migrate(&v1), Ok(Some(current.clone())));
+            assert_eq!(GenomeData::SCHEMA.version_of(&v1[1..]), Ok(0));
+            assert_eq!(GenomeData::SCHEMA.migrate(&v1[1..]), Ok(Some(current.clone())));
+        }
+        
+        let mut config = TokenConfig::try_from_slice(&[1u8; TokenConfig::LEN]).unwrap();
+        config.schema_version = TokenConfig::SCHEMA.current();
+        config.smoothing = EmissionSmoothing::default();
+        config.require_recipient_signature = false;
+        config.mint_window = MintWindow::default();
+        config.params_history = ParamsHistory::default();
+        config.dust = DustAccumulator::default();
+        config.discovery_bonus_bps = 0;
+        config.allowlist = RecipientAllowlist::default();
+        config.quality_histogram = QualityHistogram::default();
+        config.mint_quorum = MintQuorum::default();
+        config.genome_census = GenomeCensus::default();
+        config.dispute_rewards = 0;
+        let current = config.try_to_vec().unwrap();
+        let mut v11 = current[..TokenConfig::V11_LEN].to_vec();
+        v11[0] = 11;
+        let mut v10 = current[..TokenConfig::V10_LEN].to_vec();
+        v10[0] = 10;
+        let mut v9 = current[..TokenConfig::V9_LEN].to_vec();
+        v9[0] = 9;
+        let mut v8 = current[..TokenConfig::V8_LEN].to_vec();
+        v8[0] = 8;
+        let mut v7 = current[..TokenConfig::V7_LEN].to_vec();
+        v7[0] = 7;
+        let mut v6 = current[..TokenConfig::V6_LEN].to_vec();
+        v6[0] = 6;
+        let mut v5 = current[..TokenConfig::V5_LEN].to_vec();
+        v5[0] = 5;
+        let mut v4 = current[..TokenConfig::V4_LEN].to_vec();
+        v4[0] = 4;
+        let mut v3 = current[..TokenConfig::V3_LEN].to_vec();
+        v3[0] = 3;
+        let mut v2 = current[..TokenConfig::V2_LEN].to_vec();
+        v2[0] = 2;
+        let mut v1 = current[..TokenConfig::V1_LEN].to_vec();
+        v1[0] = 1;
+        assert!(!TokenConfig::SCHEMA.needs_migration(&current));
+        assert!(TokenConfig::SCHEMA.needs_migration(&v11));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v11), Ok(Some(current.clone())));
+        assert!(TokenConfig::SCHEMA.needs_migration(&v10));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v10), Ok(Some(current.clone())));
+        assert!(TokenConfig::SCHEMA.needs_migration(&v9));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v9), Ok(Some(current.clone())));
+        assert!(TokenConfig::SCHEMA.needs_migration(&v8));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v8), Ok(Some(current.clone())));
+        assert!(TokenConfig::SCHEMA.needs_migration(&v7));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v7), Ok(Some(current.clone())));
+        assert!(TokenConfig::SCHEMA.needs_migration(&v6));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v6), Ok(Some(current.clone())));
+        assert!(TokenConfig::SCHEMA.needs_migration(&v5));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v5), Ok(Some(current.clone())));
+        assert!(TokenConfig::SCHEMA.needs_migration(&v4));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v4), Ok(Some(current.clone())));
+        assert!(TokenConfig::SCHEMA.needs_migration(&v3));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v3), Ok(Some(current.clone())));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v2), Ok(Some(current.clone())));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v1), Ok(Some(current.clone())));
+        assert_eq!(TokenConfig::SCHEMA.migrate(&v1[1..]), Ok(Some(current.clone())));
+    }
+    
+    #[test]
+    fn test_emission_smoothing() {
+        assert_eq!(EmissionSmoothing::default().scale_bps(), BPS_DENOMINATOR);
+        let mut smoothing = EmissionSmoothing {
+            target_per_epoch: 1_000,
+            epoch_slots: 100,
+            average: 1_000,
+            ..EmissionSmoothing::default()
+        };
+        assert_eq!(smoothing.scale_bps(), BPS_DENOMINATOR);
+        
+        // A burst at three times the target scales later mints down
+        smoothing.record(3_000);
+        smoothing.roll(150);
+        assert_eq!((smoothing.average, smoothing.epoch_start, smoothing.epoch_emitted), (1_500, 100, 0));
+        assert_eq!(smoothing.scale_bps(), 6_666);
+        assert_eq!(EmissionSmoothing::apply(9_000, smoothing.scale_bps()), 5_999);
+        
+        // Quiet epochs pull it back up, within the bounds
+        assert_eq!(smoothing.scale_bps_at(250), 8_888);
+        assert_eq!(smoothing.scale_bps_at(1_100), EMISSION_SCALE_MAX_BPS);
+        assert_eq!(smoothing.scale_bps_at(u64::MAX), EMISSION_SCALE_MAX_BPS);
+        smoothing.record(1_000_000);
+        assert_eq!(smoothing.scale_bps_at(200), EMISSION_SCALE_MIN_BPS);
+    }
+    
+    #[test]
+    fn test_mint_window() {
+        let schedule = EpochSchedule::without_warmup();
+        let slots = schedule.slots_per_epoch;
+        let window = MintWindow { start_bps: 0, end_bps: 8_000 };
+        assert!(window.is_valid() && MintWindow::default().is_valid());
+        assert!(MintWindow::default().is_open(&schedule, slots - 1));
+        assert!(window.is_open(&schedule, 0));
+        assert!(window.is_open(&schedule, slots * 4 / 5 - 1));
+        assert!(!window.is_open(&schedule, slots * 4 / 5));
+        assert!(!window.is_open(&schedule, slots - 1));
+        assert!(window.is_open(&schedule, slots));
+        
+        let late = MintWindow { start_bps: 5_000, end_bps: 10_000 };
+        assert!(!late.is_open(&schedule, 3 * slots));
+        assert!(late.is_open(&schedule, 3 * slots + slots / 2));
+        assert!(late.is_open(&schedule, 4 * slots - 1));
+        assert!(!MintWindow { start_bps: 5_000, end_bps: 5_000 }.is_valid());
+        assert!(!MintWindow { start_bps: 0, end_bps: 10_001 }.is_valid());
+    }
+    
+    #[test]
+    fn test_mint_quorum() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(MintQuorum::default().is_valid() && !MintQuorum::default().applies_to(u64::MAX));
+        let quorum = MintQuorum::new(1_000, 2, &[a, b]).unwrap();
+        assert!(quorum.is_valid());
+        assert!(!quorum.applies_to(1_000) && quorum.applies_to(1_001));
+        assert!(!MintQuorum::new(1_000, 3, &[a, b]).unwrap().is_valid());
+        assert!(!MintQuorum::new(1_000, 0, &[a, b]).unwrap().is_valid());
+        assert!(!MintQuorum::new(1_000, 2, &[a, a]).unwrap().is_valid());
+        assert!(MintQuorum::new(1_000, 1, &[a; MAX_QUORUM_CONTROLLERS + 1]).is_none());
+    }
+    
+    #[test]
+    fn test_params_history() {
+        let mut config = TokenConfig::try_from_slice(&[0u8; TokenConfig::LEN]).unwrap();
+        config.agi_params = AGIParameters::default();
+        assert_eq!(config.agi_params_at(5), Some(AGIParameters::default()));
+        
+        // The first update logs the replaced set from slot 0
+        let weights = |weight: u8| AGIParameters { complexity_weight: weight, ..AGIParameters::default() };
+        config.params_history.record(config.agi_params, 0);
+        for update in 1..=20u8 {
+            config.params_history.record(weights(update), update as u64 * 100);
+        }
+        assert_eq!(config.params_history.recorded, 21);
+        assert_eq!(config.params_history.entries().count(), PARAMS_HISTORY_LEN);
+        assert_eq!(config.params_history.entries().next().unwrap().activation_slot, 2_000);
+        assert_eq!(config.agi_params_at(u64::MAX), Some(weights(20)));
+        assert_eq!(config.agi_params_at(1_499), Some(weights(14)));
+        assert_eq!(config.agi_params_at(500), Some(weights(5)));
+        // Sets before the oldest retained one have been overwritten
+        assert_eq!(config.agi_params_at(499), None);
+    }
+    
+    #[test]
+    fn test_dust_accumulator() {
+        assert_eq!(DustAccumulator::remainder(12_345, 7_500), 7_500);
+        assert_eq!(DustAccumulator::remainder(10_000, 7_500), 0);
+        assert_eq!(DustAccumulator::remainder(u64::MAX, 9_999), 8_385);
+        
+        let mut dust = DustAccumulator { threshold: 2, reward_pool: Pubkey::new_unique(), ..DustAccumulator::default() };
+        dust.record(DustAccumulator::remainder(12_345, 7_500));
+        dust.record(DustAccumulator::remainder(12_345, 7_500));
+        assert_eq!(dust.whole_units(), 1);
+        assert!(!dust.is_due());
+        dust.record(9_000);
+        assert!(dust.is_due());
+        assert_eq!(dust.take(), 2);
+        assert_eq!((dust.dust, dust.consolidated), (4_000, 2));
+        assert!(!DustAccumulator { dust: u64::MAX, ..DustAccumulator::default() }.is_due());
+    }
+    
+    #[test]
+    fn test_dispute_policy() {
+        let policy = DisputePolicy { window_secs: 3_600, reward_bps: 2_500, evidence_signer: Pubkey::new_unique() };
+        assert!(!DisputePolicy::default().is_open(0, 0));
+        assert!(policy.is_open(1_000, 4_600));
+        assert!(!policy.is_open(1_000, 4_601));
+        assert!(policy.is_open(i64::MAX - 10, i64::MAX));
+        assert_eq!(policy.reward(1_000), 250);
+        assert_eq!(policy.reward(u64::MAX), u64::MAX / 4);
+        assert_eq!(DisputeRecord::default().try_to_vec().unwrap().len(), DisputeRecord::LEN);
+    }
+    
+    #[test]
+    fn test_rescore_evidence() {
+        let (config, signer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let message = rescore_evidence_message(&config, &[5u8; 32], 42, 40, 60);
+        assert_ne!(message, rescore_evidence_message(&config, &[5u8; 32], 43, 40, 60));
+        
+        // Single-signature Ed25519 data with offsets into itself
+        let (key_offset, signature_offset, message_offset) = (16u16, 48u16, 112u16);
+        let mut data = vec![1u8, 0];
+        for field in [signature_offset, u16::MAX, key_offset, u16::MAX, message_offset, message.len() as u16, u16::MAX] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(&message);
+        assert!(ed25519_verifies(&data, &signer, &message));
+        assert!(!ed25519_verifies(&data, &config, &message));
+        assert!(!ed25519_verifies(&data, &signer, &rescore_evidence_message(&config, &[5u8; 32], 42, 41, 60)));
+        
+        // Key read from another instruction
+        data[8..10].copy_from_slice(&0u16.to_le_bytes());
+        assert!(!ed25519_verifies(&data, &signer, &message));
+    }
+    
+    fn at(unix_timestamp: i64, slot: u64) -> Clock {
+        Clock { slot, unix_timestamp, ..Clock::default() }
+    }
+    
+    fn config_with(authority: Pubkey, configure: impl FnOnce(&mut TokenConfig)) -> Vec<u8> {
+        let mut config = TokenConfig::try_from_slice(&[0u8; TokenConfig::LEN]).unwrap();
+        config.authority = authority;
+        configure(&mut config);
+        config.try_to_vec().unwrap()
+    }
+    
+    fn run(program_id: &Pubkey, accounts: &[AccountInfo], instruction: RSMInstruction, clock: &Clock) -> ProgramResult {
+        process_instruction_with_clock(program_id, accounts, &instruction.try_to_vec().unwrap(), clock)
+    }
+    
+    #[test]
+    fn test_supply_raise_schedule() {
+        let program_id = Pubkey::new_unique();
+        let (config_key, authority, executor) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let clock_key = solana_program::sysvar::clock::ID;
+        let mut data = config_with(authority, |config| {
+            config.max_supply = 1_000_000;
+            config.supply_governance.executor = executor;
+        });
+        let (mut lamports, mut signer_lamports, mut executor_lamports, mut clock_lamports) = (0, 0, 0, 0);
+        let (mut empty, mut empty_executor, mut empty_clock) = ([0u8; 0], [0u8; 0], [0u8; 0]);
+        let accounts = [
+            AccountInfo::new(&config_key, false, true, &mut lamports, &mut data, &program_id, false, 0),
+            AccountInfo::new(&authority, true, false, &mut signer_lamports, &mut empty, &program_id, false, 0),
+            AccountInfo::new(&executor, true, false, &mut executor_lamports, &mut empty_executor, &program_id, false, 0),
+            AccountInfo::new(&clock_key, false, false, &mut clock_lamports, &mut empty_clock, &program_id, false, 0),
+        ];
+        let config = |accounts: &[AccountInfo]| TokenConfig::try_from_slice(&accounts[0].data.borrow()).unwrap();
+        
+        let scheduled_at = 1_000;
+        run(&program_id, &accounts, RSMInstruction::ScheduleMaxSupplyRaise { new_max_supply: 1_100_000 }, &at(scheduled_at, 0))
+            .unwrap();
+        let eta = scheduled_at + SUPPLY_RAISE_TIMELOCK;
+        assert_eq!(config(&accounts).supply_governance.pending_eta, eta);
+        
+        // Locked until the eta, to the second
+        assert_eq!(
+            run(&program_id, &accounts, RSMInstruction::RaiseMaxSupply, &at(eta - 1, 0)),
+            Err(RSMError::RaiseTimelocked.into())
+        );
+        run(&program_id, &accounts, RSMInstruction::RaiseMaxSupply, &at(eta, 0)).unwrap();
+        let raised = config(&accounts);
+        assert_eq!(raised.max_supply, 1_100_000);
+        assert_eq!(raised.supply_governance.history[0].executed_at, eta);
+        
+        // The window's 10% is spent until a year after the first raise
+        let next = RSMInstruction::ScheduleMaxSupplyRaise { new_max_supply: 1_200_000 };
+        assert_eq!(run(&program_id, &accounts, next, &at(eta, 0)), Err(RSMError::RaiseExceedsCap.into()));
+        let next = RSMInstruction::ScheduleMaxSupplyRaise { new_max_supply: 1_210_000 };
+        run(&program_id, &accounts, next, &at(eta + SUPPLY_RAISE_WINDOW - SUPPLY_RAISE_TIMELOCK, 0)).unwrap();
+        run(&program_id, &accounts, RSMInstruction::RaiseMaxSupply, &at(eta + SUPPLY_RAISE_WINDOW, 0)).unwrap();
+        assert_eq!(config(&accounts).max_supply, 1_210_000);
+        
+        // The clock account is still checked
+        let mut wrong_lamports = 0;
+        let mut wrong_data = [0u8; 0];
+        let wrong_key = Pubkey::new_unique();
+        let mut wrong = accounts.to_vec();
+        wrong[3] = AccountInfo::new(&wrong_key, false, false, &mut wrong_lamports, &mut wrong_data, &program_id, false, 0);
+        assert_eq!(
+            run(&program_id, &wrong, RSMInstruction::RaiseMaxSupply, &at(eta, 0)),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+    
+    #[test]
+    fn test_clawback_delay() {
+        let program_id = Pubkey::new_unique();
+        let config_key = Pubkey::new_unique();
+        let genome_hash = [9u8; 32];
+        let genome_key = genome_address(&program_id, &config_key, &genome_hash).0;
+        let clock_key = solana_program::sysvar::clock::ID;
+        let (mint_key, token_program) = (Pubkey::new_unique(), spl_token::id());
+        let vault_key = bond_vault_address(&program_id, &config_key).0;
+        let mut config_data = config_with(Pubkey::new_unique(), |config| {
+            config.total_minted = 1_000;
+            config.mint = mint_key;
+        });
+        let mut genome = GenomeData::try_from_slice(&[0u8; GenomeData::LEN]).unwrap();
+        genome.tokens_minted = 1_000;
+        genome.bonded_amount = 300;
+        genome.pending_clawback = 100;
+        genome.clawback_eta = 5_000;
+        let mut genome_data = genome.try_to_vec().unwrap();
+        let (mut config_lamports, mut genome_lamports, mut clock_lamports) = (0, 0, 0);
+        let (mut mint_lamports, mut vault_lamports, mut program_lamports) = (0, 0, 0);
+        let (mut empty, mut empty_mint, mut empty_vault, mut empty_program) = ([0u8; 0], [0u8; 0], [0u8; 0], [0u8; 0]);
+        let accounts = [
+            AccountInfo::new(&config_key, false, true, &mut config_lamports, &mut config_data, &program_id, false, 0),
+            AccountInfo::new(&genome_key, false, true, &mut genome_lamports, &mut genome_data, &program_id, false, 0),
+            AccountInfo::new(&clock_key, false, false, &mut clock_lamports, &mut empty, &program_id, false, 0),
+            AccountInfo::new(&mint_key, false, true, &mut mint_lamports, &mut empty_mint, &token_program, false, 0),
+            AccountInfo::new(&vault_key, false, true, &mut vault_lamports, &mut empty_vault, &token_program, false, 0),
+            AccountInfo::new(&token_program, false, false, &mut program_lamports, &mut empty_program, &program_id, true, 0),
+        ];
+        let clawback = || RSMInstruction::ExecuteClawback { genome_hash };
+        
+        assert_eq!(run(&program_id, &accounts, clawback(), &at(4_999, 0)), Err(RSMError::ClawbackDelayed.into()));
+        run(&program_id, &accounts, clawback(), &at(5_000, 0)).unwrap();
+        let genome = GenomeData::try_from_slice(&accounts[1].data.borrow()).unwrap();
+        assert_eq!((genome.tokens_minted, genome.bonded_amount, genome.pending_clawback), (900, 200, 0));
+        assert_eq!(TokenConfig::try_from_slice(&accounts[0].data.borrow()).unwrap().total_minted, 900);
+        assert_eq!(run(&program_id, &accounts, clawback(), &at(6_000, 0)), Err(RSMError::NoPendingClawback.into()));
+    }
+    
+    #[test]
+    fn test_emergency_clawback_timelock_and_veto() {
+        let program_id = Pubkey::new_unique();
+        let (config_key, authority, executor) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (token_key, mint_key, token_program) = (Pubkey::new_unique(), Pubkey::new_unique(), spl_token::id());
+        let evidence_hash = [5u8; 32];
+        let record_key = clawback_address(&program_id, &config_key, &evidence_hash).0;
+        let clock_key = solana_program::sysvar::clock::ID;
+        let mut config_data = config_with(authority, |config| {
+            config.mint = mint_key;
+            config.supply_governance.executor = executor;
+        });
+        let record = ClawbackRecord { token_account: token_key, amount: 100, evidence_hash, eta: 5_000, ..ClawbackRecord::default() };
+        let mut record_data = record.try_to_vec().unwrap();
+        let mut genome = GenomeData::try_from_slice(&[0u8; GenomeData::LEN]).unwrap();
+        (genome.hash, genome.tokens_minted) = ([6u8; 32], 100);
+        let genome_key = genome_address(&program_id, &config_key, &genome.hash).0;
+        let (mut genome_lamports, mut genome_data) = (0, genome.try_to_vec().unwrap());
+        let (mut config_lamports, mut authority_lamports, mut executor_lamports, mut clock_lamports) = (0, 0, 0, 0);
+        let (mut record_lamports, mut token_lamports, mut mint_lamports, mut program_lamports) = (0, 0, 0, 0);
+        let (mut empty_authority, mut empty_executor, mut empty_clock) = ([0u8; 0], [0u8; 0], [0u8; 0]);
+        let (mut empty_token, mut empty_mint, mut empty_program) = ([0u8; 0], [0u8; 0], [0u8; 0]);
+        let accounts = [
+            AccountInfo::new(&config_key, false, true, &mut config_lamports, &mut config_data, &program_id, false, 0),
+            AccountInfo::new(&authority, true, false, &mut authority_lamports, &mut empty_authority, &program_id, false, 0),
+            AccountInfo::new(&executor, true, false, &mut executor_lamports, &mut empty_executor, &program_id, false, 0),
+            AccountInfo::new(&clock_key, false, false, &mut clock_lamports, &mut empty_clock, &program_id, false, 0),
+            AccountInfo::new(&record_key, false, true, &mut record_lamports, &mut record_data, &program_id, false, 0),
+            AccountInfo::new(&token_key, false, true, &mut token_lamports, &mut empty_token, &token_program, false, 0),
+            AccountInfo::new(&mint_key, false, true, &mut mint_lamports, &mut empty_mint, &token_program, false, 0),
+            AccountInfo::new(&token_program, false, false, &mut program_lamports, &mut empty_program, &program_id, true, 0),
+            AccountInfo::new(&genome_key, false, true, &mut genome_lamports, &mut genome_data, &program_id, false, 0),
+        ];
+        let clawback = || RSMInstruction::EmergencyClawback { evidence_hash };
+        let cancel = || RSMInstruction::CancelEmergencyClawback { evidence_hash };
+        let status = |accounts: &[AccountInfo]| ClawbackRecord::try_from_slice(&accounts[4].data.borrow()).unwrap().status;
+        
+        assert_eq!(run(&program_id, &accounts, clawback(), &at(4_999, 0)), Err(RSMError::ClawbackDelayed.into()));
+        
+        // Only the authority or the executor can veto
+        let mut stranger = [accounts[0].clone(), accounts[6].clone(), accounts[4].clone()];
+        stranger[1].is_signer = true;
+        assert_eq!(run(&program_id, &stranger, cancel(), &at(0, 0)), Err(ProgramError::InvalidAccountData));
+        let vetoing = [accounts[0].clone(), accounts[2].clone(), accounts[4].clone()];
+        run(&program_id, &vetoing, cancel(), &at(0, 0)).unwrap();
+        assert_eq!(status(&accounts), ClawbackStatus::Cancelled);
+        
+        // A vetoed record stays as evidence and cannot be executed
+        assert_eq!(run(&program_id, &accounts, clawback(), &at(6_000, 0)), Err(RSMError::ClawbackNotPending.into()));
+        assert_eq!(run(&program_id, &vetoing, cancel(), &at(0, 0)), Err(RSMError::ClawbackNotPending.into()));
+    }
+    
+    #[test]
+    fn test_repair_requires_complete_audit() {
+        let program_id = Pubkey::new_unique();
+        let (config_key, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let audit_key = audit_address(&program_id, &config_key, &authority).0;
+        let census = |tracked, count| GenomeCensus { tracked, count };
+        let audit = SupplyAudit { recorded_at_start: 500, computed_total: 300, genomes_counted: 2, finished: true, ..SupplyAudit::default() };
+        let repair = |genome_census: GenomeCensus, audit: &SupplyAudit| {
+            let mut config_data = config_with(authority, |config| {
+                config.total_minted = 500;
+                config.genome_census = genome_census;
+            });
+            let mut audit_data = audit.try_to_vec().unwrap();
+            let (mut config_lamports, mut audit_lamports, mut authority_lamports) = (0, 0, 0);
+            let mut empty = [0u8; 0];
+            let accounts = [
+                AccountInfo::new(&config_key, false, true, &mut config_lamports, &mut config_data, &program_id, false, 0),
+                AccountInfo::new(&audit_key, false, false, &mut audit_lamports, &mut audit_data, &program_id, false, 0),
+                AccountInfo::new(&authority, true, false, &mut authority_lamports, &mut empty, &program_id, false, 0),
+            ];
+            run(&program_id, &accounts, RSMInstruction::RepairSupply, &at(0, 0))?;
+            Ok::<u64, ProgramError>(TokenConfig::try_from_slice(&config_data).unwrap().total_minted)
+        };
+        
+        // An audit that skipped genomes cannot lower the total
+        assert_eq!(repair(census(true, 3), &audit), Err(RSMError::AuditIncomplete.into()));
+        assert_eq!(repair(census(true, 2), &audit), Ok(300));
+        
+        // Without a census the total can only be raised
+        assert_eq!(repair(census(false, 0), &audit), Err(RSMError::AuditIncomplete.into()));
+        let raising = SupplyAudit { computed_total: 700, ..audit };
+        assert_eq!(repair(census(false, 0), &raising), Ok(700));
+    }
+    
+    #[test]
+    fn test_genome_census() {
+        let mut census = GenomeCensus::tracked();
+        census.created().unwrap();
+        census.created().unwrap();
+        census.closed().unwrap();
+        assert_eq!(census.count, 1);
+        let mut untracked = GenomeCensus::default();
+        untracked.created().unwrap();
+        untracked.closed().unwrap();
+        untracked.closed().unwrap();
+        assert_eq!(untracked.count, 0);
+    }
+    
+    #[test]
+    fn test_trip_breaker() {
+        let program_id = Pubkey::new_unique();
+        let (config_key, authority, controller) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let clock_key = solana_program::sysvar::clock::ID;
+        let mut data = config_with(authority, |config| {
+            config.agi_controller = controller;
+            config.decimals = DECIMALS;
+            config.breaker = EmissionBreaker::default();
+        });
+        let (mut lamports, mut signer_lamports, mut clock_lamports) = (0, 0, 0);
+        let (mut empty, mut empty_clock) = ([0u8; 0], [0u8; 0]);
+        let mut accounts = [
+            AccountInfo::new(&config_key, false, true, &mut lamports, &mut data, &program_id, false, 0),
+            AccountInfo::new(&controller, true, false, &mut signer_lamports, &mut empty, &program_id, false, 0),
+            AccountInfo::new(&clock_key, false, false, &mut clock_lamports, &mut empty_clock, &program_id, false, 0),
+        ];
+        let trip = |amount| RSMInstruction::TripBreaker { amount };
+        let breaker = |accounts: &[AccountInfo]| TokenConfig::try_from_slice(&accounts[0].data.borrow()).unwrap().breaker;
+        let limit = EmissionBreaker::floor_average(DECIMALS) * DEFAULT_BREAKER_MULTIPLE_PCT as u64 / 100;
+        
+        // A mint that would pass cannot latch the breaker
+        assert_eq!(run(&program_id, &accounts, trip(limit), &at(0, 10)), Err(ProgramError::InvalidArgument));
+        run(&program_id, &accounts, trip(limit + 1), &at(0, 10)).unwrap();
+        assert!(breaker(&accounts).tripped);
+        assert_eq!(breaker(&accounts).window_emitted, 0);
+        
+        accounts[1].key = &authority;
+        run(&program_id, &accounts, trip(1), &at(0, 10)).unwrap();
+        let stranger = Pubkey::new_unique();
+        accounts[1].key = &stranger;
+        assert_eq!(run(&program_id, &accounts, trip(limit + 1), &at(0, 10)), Err(ProgramError::InvalidAccountData));
+    }
+    
+    #[test]
+    fn test_emission_target_epochs() {
+        let program_id = Pubkey::new_unique();
+        let (config_key, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = config_with(authority, |_| {});
+        let (mut lamports, mut signer_lamports) = (0, 0);
+        let mut empty = [0u8; 0];
+        let accounts = [
+            AccountInfo::new(&config_key, false, true, &mut lamports, &mut data, &program_id, false, 0),
+            AccountInfo::new(&authority, true, false, &mut signer_lamports, &mut empty, &program_id, false, 0),
+        ];
+        let target = |target_per_epoch| RSMInstruction::ConfigureEmissionTarget { target_per_epoch, epoch_slots: 100 };
+        let smoothing = |accounts: &[AccountInfo]| TokenConfig::try_from_slice(&accounts[0].data.borrow()).unwrap().smoothing;
+        
+        // Epochs count from the slot smoothing is turned on
+        run(&program_id, &accounts, target(1_000), &at(0, 250)).unwrap();
+        assert_eq!((smoothing(&accounts).epoch_start, smoothing(&accounts).average), (250, 1_000));
+        
+        // Retargeting rolls the quiet epochs since into the average
+        run(&program_id, &accounts, target(2_000), &at(0, 470)).unwrap();
+        let rolled = smoothing(&accounts);
+        assert_eq!((rolled.epoch_start, rolled.average, rolled.target_per_epoch), (450, 562, 2_000));
+        
+        run(&program_id, &accounts, target(0), &at(0, 500)).unwrap();
+        assert!(!smoothing(&accounts).is_enabled());
+    }
 }
\ No newline at end of file
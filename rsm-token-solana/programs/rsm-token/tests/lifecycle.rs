@@ -0,0 +1,1259 @@
+//! End-to-end lifecycle: mint RSM from a genome, earn RNA, burn it into a
+//! child genome, mint the child's DNA and trade it.
+//!
+//! rsm-token runs natively. The Anchor legs load the BPF builds from
+//! `contracts/target/deploy`, so `test_score_mint_breed_trade` is ignored
+//! by default: run `anchor build` and `anchor keys sync` in `contracts`,
+//! then `cargo test --test lifecycle -- --ignored`.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use rsm_token::{
+    blockchain_entropy, bond_vault_address, calculate_token_amount, clawback_address, config_address, dispute_address, genome_address,
+    newest_slot_hash, rescore_evidence_message, scale_amount, AGIParameters, AccountKind, ClawbackRecord, ClawbackStatus, DisputeRecord,
+    DustAccumulator, EmissionBreaker, EmissionSmoothing, GenomeCensus, GenomeData, GenomeOrigin, MintArgs, MintQuorum, MintWindow,
+    ParamsHistory, QualityHistogram, RSMError, RSMInstruction, RecipientAllowlist, SupplyAudit, TokenConfig, AUDIT_SEED,
+    CLAWBACK_DELAY, DECIMALS, DEDUPE_SEED, EMERGENCY_CLAWBACK_TIMELOCK, MAX_SUPPLY,
+};
+use solana_program::{hash::hash, hash::hashv, program_pack::Pack, system_program, sysvar};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    bpf_loader, bpf_loader_upgradeable,
+    bpf_loader_upgradeable::UpgradeableLoaderState,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use std::path::PathBuf;
+
+const ANCHOR_PROGRAMS: [&str; 4] = ["rsm_dna_token", "agi_rna_token", "genesis_engine", "rsm_pause"];
+const GENESIS_COST: u64 = 1_000;
+/// Market share of a 1,000,000 supply genome after the founder's cut
+const MARKET_SUPPLY: u64 = 857_100;
+
+async fn try_process(
+    ctx: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = ctx.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all: Vec<&Keypair> = vec![&ctx.payer];
+    all.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(instructions, Some(&ctx.payer.pubkey()), &all, blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+async fn process(ctx: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) {
+    try_process(ctx, instructions, signers).await.unwrap();
+}
+
+async fn get<T: BorshDeserialize>(ctx: &mut ProgramTestContext, address: &Pubkey, skip: usize) -> T {
+    let account = ctx.banks_client.get_account(*address).await.unwrap().unwrap();
+    T::deserialize(&mut &account.data[skip..]).unwrap()
+}
+
+async fn token_balance(ctx: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    let account = ctx.banks_client.get_account(*address).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+async fn create_mint(ctx: &mut ProgramTestContext, authority: &Pubkey, decimals: u8) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let payer = ctx.payer.pubkey();
+    process(
+        ctx,
+        &[
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint2(&spl_token::id(), &mint.pubkey(), authority, None, decimals).unwrap(),
+        ],
+        &[&mint],
+    )
+    .await;
+    mint.pubkey()
+}
+
+async fn create_token_account(ctx: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let account = Keypair::new();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let payer = ctx.payer.pubkey();
+    process(
+        ctx,
+        &[
+            system_instruction::create_account(
+                &payer,
+                &account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account3(&spl_token::id(), &account.pubkey(), mint, owner).unwrap(),
+        ],
+        &[&account],
+    )
+    .await;
+    account.pubkey()
+}
+
+fn rsm_ix(program_id: Pubkey, instruction: RSMInstruction, accounts: Vec<AccountMeta>) -> Instruction {
+    Instruction::new_with_bytes(program_id, &instruction.try_to_vec().unwrap(), accounts)
+}
+
+/// Anchor instruction: sha256("global:<name>")[..8] followed by borsh args.
+fn anchor_ix<A: BorshSerialize>(program_id: Pubkey, name: &str, args: A, accounts: Vec<AccountMeta>) -> Instruction {
+    let mut data = hash(format!("global:{}", name).as_bytes()).to_bytes()[..8].to_vec();
+    data.extend(args.try_to_vec().unwrap());
+    Instruction::new_with_bytes(program_id, &data, accounts)
+}
+
+fn deploy_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../../contracts/target/deploy")
+}
+
+/// Program ids and ELF bytes of the Anchor builds.
+fn anchor_builds() -> Vec<(Pubkey, Vec<u8>)> {
+    ANCHOR_PROGRAMS
+        .iter()
+        .map(|name| {
+            let keypair_path = deploy_dir().join(format!("{}-keypair.json", name));
+            let elf_path = deploy_dir().join(format!("{}.so", name));
+            let keypair = read_keypair_file(&keypair_path).unwrap_or_else(|err| {
+                panic!("{} missing ({}); run `anchor build` in contracts first", keypair_path.display(), err)
+            });
+            let elf = std::fs::read(&elf_path).unwrap_or_else(|err| {
+                panic!("{} missing ({}); run `anchor build` in contracts first", elf_path.display(), err)
+            });
+            (keypair.pubkey(), elf)
+        })
+        .collect()
+}
+
+struct RsmFixture {
+    config: Pubkey,
+    mint: Pubkey,
+    controller: Keypair,
+    recipient: Keypair,
+    /// The recipient's token account the fixture's mint paid
+    holding: Pubkey,
+}
+
+/// MintFromGenome of `genome_hash` to `recipient`, paid into its token
+/// account `holding`, against the newest slot hash with the amount its
+/// scores earn; returns the amount and the blockchain component too.
+async fn mint_instruction(
+    ctx: &mut ProgramTestContext,
+    program_id: Pubkey,
+    (config, mint, controller): (Pubkey, Pubkey, Pubkey),
+    genome_hash: [u8; 32],
+    (recipient, holding): (Pubkey, Pubkey),
+    nonce: u64,
+) -> (Instruction, u64, u8) {
+    let (dedupe, _) = Pubkey::find_program_address(&[DEDUPE_SEED, config.as_ref()], &program_id);
+    let (genome, _) = genome_address(&program_id, &config, &genome_hash);
+    let slot_hashes = ctx.banks_client.get_account(sysvar::slot_hashes::id()).await.unwrap().unwrap();
+    let (slot, slot_hash) = newest_slot_hash(&slot_hashes.data).unwrap();
+    let (complexity, uniqueness, entropy) = (92, 91, 150);
+    let blockchain = blockchain_entropy(&genome_hash, &slot_hash);
+    let amount = scale_amount(
+        calculate_token_amount(&AGIParameters::default(), complexity, uniqueness, entropy, blockchain),
+        DECIMALS,
+    )
+    .unwrap();
+
+    let ix = rsm_ix(
+        program_id,
+        RSMInstruction::MintFromGenome(MintArgs {
+            genome_hash,
+            complexity,
+            uniqueness,
+            entropy,
+            amount,
+            mint_receipt: false,
+            nonce,
+            recent_slot: slot,
+            split_bps: Vec::new(),
+            origin: GenomeOrigin::Unknown,
+            species: 0,
+            allowlist_proof: None,
+            auto_stake: false,
+        }),
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(genome, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(recipient, false),
+            AccountMeta::new_readonly(controller, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new(dedupe, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+            AccountMeta::new(holding, false),
+        ],
+    );
+    (ix, amount, blockchain)
+}
+
+/// Initialize → dedupe buffer → genome account → MintFromGenome, on a mint
+/// whose authority is the config.
+async fn mint_rsm_from_genome(ctx: &mut ProgramTestContext, program_id: Pubkey, genome_hash: [u8; 32]) -> RsmFixture {
+    let payer = ctx.payer.pubkey();
+    let controller = Keypair::new();
+    let recipient = Keypair::new();
+    let mint = create_mint(ctx, &payer, DECIMALS).await;
+    let (config, _) = config_address(&program_id, &mint);
+    let hand_over = spl_token::instruction::set_authority(
+        &spl_token::id(),
+        &mint,
+        Some(&config),
+        spl_token::instruction::AuthorityType::MintTokens,
+        &payer,
+        &[],
+    )
+    .unwrap();
+    process(ctx, &[hand_over], &[]).await;
+    let holding = create_token_account(ctx, &mint, &recipient.pubkey()).await;
+    let (dedupe, _) = Pubkey::find_program_address(&[DEDUPE_SEED, config.as_ref()], &program_id);
+    let (genome, _) = genome_address(&program_id, &config, &genome_hash);
+
+    process(
+        ctx,
+        &[
+            rsm_ix(
+                program_id,
+                RSMInstruction::Initialize { max_supply: MAX_SUPPLY, agi_controller: controller.pubkey(), decimals: DECIMALS },
+                vec![
+                    AccountMeta::new(config, false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(mint, false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+            rsm_ix(
+                program_id,
+                RSMInstruction::CreateDedupeBuffer,
+                vec![
+                    AccountMeta::new_readonly(config, false),
+                    AccountMeta::new(dedupe, false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+            rsm_ix(
+                program_id,
+                RSMInstruction::CreateGenomeAccount { genome_hash },
+                vec![
+                    AccountMeta::new(config, false),
+                    AccountMeta::new(genome, false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+        ],
+        &[],
+    )
+    .await;
+
+    // Mint against the newest slot hash, the parent of the warped-to slot
+    ctx.warp_to_slot(1_000).unwrap();
+    let (ix, amount, blockchain) =
+        mint_instruction(ctx, program_id, (config, mint, controller.pubkey()), genome_hash, (recipient.pubkey(), holding), 1).await;
+    process(ctx, &[ix], &[&controller]).await;
+    assert_eq!(token_balance(ctx, &holding).await, amount);
+
+    let data: GenomeData = get(ctx, &genome, 0).await;
+    assert!(data.is_minted);
+    assert_eq!(data.owner, recipient.pubkey());
+    assert_eq!(data.tokens_minted, amount);
+    assert_eq!(data.blockchain_entropy, blockchain);
+    let config_state: TokenConfig = get(ctx, &config, 0).await;
+    assert_eq!(config_state.total_minted, amount);
+    assert_eq!(config_state.mint, mint);
+
+    RsmFixture { config, mint, controller, recipient, holding }
+}
+
+#[tokio::test]
+async fn test_rsm_mint_burn_close() {
+    let program_id = Pubkey::new_unique();
+    let mut ctx = ProgramTest::new("rsm_token", program_id, processor!(rsm_token::process_instruction))
+        .start_with_context()
+        .await;
+
+    let genome_hash = [42u8; 32];
+    let rsm = mint_rsm_from_genome(&mut ctx, program_id, genome_hash).await;
+    let (genome, _) = genome_address(&program_id, &rsm.config, &genome_hash);
+
+    // The owner pays rent for a note and gets it back when clearing it
+    let owner = rsm.recipient.pubkey();
+    let set_note = |note: &[u8]| {
+        rsm_ix(
+            program_id,
+            RSMInstruction::SetGenomeNote { genome_hash, note: note.to_vec() },
+            vec![
+                AccountMeta::new_readonly(rsm.config, false),
+                AccountMeta::new(genome, false),
+                AccountMeta::new(owner, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        )
+    };
+    let fund = system_instruction::transfer(&ctx.payer.pubkey(), &owner, 1_000_000_000);
+    process(&mut ctx, &[fund, set_note(b"eln:2291-A")], &[&rsm.recipient]).await;
+    let data: GenomeData = get(&mut ctx, &genome, 0).await;
+    assert_eq!(data.note, b"eln:2291-A");
+    let account = ctx.banks_client.get_account(genome).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), GenomeData::LEN + 10);
+    let funded = ctx.banks_client.get_balance(owner).await.unwrap();
+
+    process(&mut ctx, &[set_note(b"")], &[&rsm.recipient]).await;
+    let account = ctx.banks_client.get_account(genome).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), GenomeData::LEN);
+    assert!(ctx.banks_client.get_balance(owner).await.unwrap() > funded);
+
+    let burn = |genome: Pubkey| {
+        rsm_ix(
+            program_id,
+            RSMInstruction::BurnGenome { genome_hash },
+            vec![
+                AccountMeta::new(rsm.config, false),
+                AccountMeta::new(genome, false),
+                AccountMeta::new(rsm.mint, false),
+                AccountMeta::new(rsm.holding, false),
+                AccountMeta::new_readonly(rsm.recipient.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+        )
+    };
+    // A copy of the genome away from its PDA, as another config's would be,
+    // cannot be burned against this config
+    let copy = Pubkey::new_unique();
+    let account = ctx.banks_client.get_account(genome).await.unwrap().unwrap();
+    ctx.set_account(&copy, &account.into());
+    let err = try_process(&mut ctx, &[burn(copy)], &[&rsm.recipient]).await.unwrap_err().unwrap();
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::InvalidSeeds));
+
+    process(&mut ctx, &[burn(genome)], &[&rsm.recipient]).await;
+    let config: TokenConfig = get(&mut ctx, &rsm.config, 0).await;
+    assert_eq!(config.total_minted, 0);
+    assert_eq!(token_balance(&mut ctx, &rsm.holding).await, 0);
+
+    // A burned genome can be closed, returning rent to whoever paid it
+    let payer = ctx.payer.pubkey();
+    process(
+        &mut ctx,
+        &[rsm_ix(
+            program_id,
+            RSMInstruction::CloseGenomeAccount { genome_hash },
+            vec![
+                AccountMeta::new(rsm.config, false),
+                AccountMeta::new(genome, false),
+                AccountMeta::new(payer, false),
+                AccountMeta::new_readonly(rsm.controller.pubkey(), true),
+            ],
+        )],
+        &[&rsm.controller],
+    )
+    .await;
+    assert!(ctx.banks_client.get_account(genome).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_migrate_unversioned_accounts() {
+    let program_id = Pubkey::new_unique();
+    let mut ctx = ProgramTest::new("rsm_token", program_id, processor!(rsm_token::process_instruction))
+        .start_with_context()
+        .await;
+
+    let genome_hash = [43u8; 32];
+    let rsm = mint_rsm_from_genome(&mut ctx, program_id, genome_hash).await;
+    let (genome, _) = genome_address(&program_id, &rsm.config, &genome_hash);
+    let config: TokenConfig = get(&mut ctx, &rsm.config, 0).await;
+    assert_eq!(config.genome_census, GenomeCensus { tracked: true, count: 1 });
+
+    // Rewrite both accounts as they were stored before the version byte,
+    // without the fields later versions added
+    for address in [rsm.config, genome] {
+        let mut account = ctx.banks_client.get_account(address).await.unwrap().unwrap();
+        if address == rsm.config {
+            let added = 8
+                + GenomeCensus::LEN
+                + MintQuorum::LEN
+                + QualityHistogram::LEN
+                + RecipientAllowlist::LEN
+                + 2
+                + DustAccumulator::LEN
+                + ParamsHistory::LEN
+                + MintWindow::LEN
+                + 1
+                + EmissionSmoothing::LEN;
+            account.data.truncate(TokenConfig::LEN - added);
+        } else {
+            account.data.drain(GenomeData::LEN - 39..GenomeData::LEN - 4);
+        }
+        account.data.remove(0);
+        ctx.set_account(&address, &account.into());
+    }
+    let payer = ctx.payer.pubkey();
+    let migrate = |kind, account, config: Option<Pubkey>| {
+        let mut accounts = vec![
+            AccountMeta::new(account, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+        accounts.extend(config.map(|config| AccountMeta::new_readonly(config, false)));
+        rsm_ix(program_id, RSMInstruction::MigrateAccount { kind }, accounts)
+    };
+    process(
+        &mut ctx,
+        &[
+            migrate(AccountKind::TokenConfig, rsm.config, None),
+            migrate(AccountKind::GenomeData, genome, Some(rsm.config)),
+        ],
+        &[],
+    )
+    .await;
+
+    let config: TokenConfig = get(&mut ctx, &rsm.config, 0).await;
+    assert_eq!(config.schema_version, TokenConfig::SCHEMA.current());
+    assert_eq!(config.mint, rsm.mint);
+    assert!(!config.smoothing.is_enabled() && !config.require_recipient_signature && !config.mint_window.is_enabled());
+    assert!(!config.genome_census.tracked);
+    let data: GenomeData = get(&mut ctx, &genome, 0).await;
+    assert_eq!(data.schema_version, GenomeData::SCHEMA.current());
+    assert_eq!((data.hash, data.owner), (genome_hash, rsm.recipient.pubkey()));
+    assert_eq!(data.emission_scale_bps, 10_000);
+    assert_eq!(data.blockchain_entropy, (data.mint_slot % 100) as u8);
+    assert_eq!(data.registrant, Pubkey::default());
+
+    // Migrating again is a no-op
+    ctx.warp_to_slot(2_000).unwrap();
+    process(&mut ctx, &[migrate(AccountKind::GenomeData, genome, Some(rsm.config))], &[]).await;
+}
+
+#[tokio::test]
+async fn test_pre_registered_genome_pays_registrant() {
+    let program_id = Pubkey::new_unique();
+    let mut ctx = ProgramTest::new("rsm_token", program_id, processor!(rsm_token::process_instruction))
+        .start_with_context()
+        .await;
+
+    let rsm = mint_rsm_from_genome(&mut ctx, program_id, [44u8; 32]).await;
+    let genome_hash = [45u8; 32];
+    let (genome, _) = genome_address(&program_id, &rsm.config, &genome_hash);
+    let registrant = Keypair::new();
+    let fund = system_instruction::transfer(&ctx.payer.pubkey(), &registrant.pubkey(), 1_000_000_000);
+    process(&mut ctx, &[fund], &[]).await;
+    let pre_register = |controller: Pubkey| {
+        rsm_ix(
+            program_id,
+            RSMInstruction::PreRegisterGenome { genome_hash },
+            vec![
+                AccountMeta::new(rsm.config, false),
+                AccountMeta::new(genome, false),
+                AccountMeta::new(registrant.pubkey(), true),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(controller, true),
+            ],
+        )
+    };
+
+    // Vouching for oneself does not claim the hash
+    let err = try_process(&mut ctx, &[pre_register(registrant.pubkey())], &[&registrant]).await.unwrap_err().unwrap();
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::InvalidAccountData));
+    process(&mut ctx, &[pre_register(rsm.controller.pubkey())], &[&registrant, &rsm.controller]).await;
+    let data: GenomeData = get(&mut ctx, &genome, 0).await;
+    assert!(data.is_pending());
+    assert_eq!((data.owner, data.registrant, data.rent_payer), (Pubkey::default(), registrant.pubkey(), registrant.pubkey()));
+
+    // A mint to any other wallet fails instead of paying it
+    let keys = (rsm.config, rsm.mint, rsm.controller.pubkey());
+    let recipient = (rsm.recipient.pubkey(), rsm.holding);
+    let (mistyped, _, _) = mint_instruction(&mut ctx, program_id, keys, genome_hash, recipient, 2).await;
+    let err = try_process(&mut ctx, &[mistyped], &[&rsm.controller]).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(RSMError::RegistrantMismatch as u32))
+    );
+
+    // Nor does it pay a token account someone else holds
+    let (misrouted, _, _) =
+        mint_instruction(&mut ctx, program_id, keys, genome_hash, (registrant.pubkey(), rsm.holding), 3).await;
+    let err = try_process(&mut ctx, &[misrouted], &[&rsm.controller]).await.unwrap_err().unwrap();
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::InvalidAccountData));
+
+    let holding = create_token_account(&mut ctx, &rsm.mint, &registrant.pubkey()).await;
+    let (mint, amount, _) = mint_instruction(&mut ctx, program_id, keys, genome_hash, (registrant.pubkey(), holding), 4).await;
+    process(&mut ctx, &[mint], &[&rsm.controller]).await;
+    assert_eq!(token_balance(&mut ctx, &holding).await, amount);
+    let data: GenomeData = get(&mut ctx, &genome, 0).await;
+    assert!(data.is_minted && !data.is_pending());
+    assert_eq!((data.owner, data.tokens_minted), (registrant.pubkey(), amount));
+}
+
+#[tokio::test]
+async fn test_large_mint_needs_quorum() {
+    let program_id = Pubkey::new_unique();
+    let mut ctx = ProgramTest::new("rsm_token", program_id, processor!(rsm_token::process_instruction))
+        .start_with_context()
+        .await;
+
+    let rsm = mint_rsm_from_genome(&mut ctx, program_id, [46u8; 32]).await;
+    let payer = ctx.payer.pubkey();
+    let (treasurer, auditor) = (Keypair::new(), Keypair::new());
+    let configure = rsm_ix(
+        program_id,
+        RSMInstruction::ConfigureMintQuorum {
+            threshold: 1,
+            required: 2,
+            controllers: vec![rsm.controller.pubkey(), treasurer.pubkey(), auditor.pubkey()],
+        },
+        vec![AccountMeta::new(rsm.config, false), AccountMeta::new_readonly(payer, true)],
+    );
+    let genome_hash = [47u8; 32];
+    let (genome, _) = genome_address(&program_id, &rsm.config, &genome_hash);
+    let create = rsm_ix(
+        program_id,
+        RSMInstruction::CreateGenomeAccount { genome_hash },
+        vec![
+            AccountMeta::new(rsm.config, false),
+            AccountMeta::new(genome, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+    process(&mut ctx, &[configure, create], &[]).await;
+
+    // The controller alone, even listed twice, is one of the two
+    let keys = (rsm.config, rsm.mint, rsm.controller.pubkey());
+    let recipient = (rsm.recipient.pubkey(), rsm.holding);
+    let (mut unilateral, _, _) = mint_instruction(&mut ctx, program_id, keys, genome_hash, recipient, 2).await;
+    unilateral.accounts.push(AccountMeta::new_readonly(rsm.controller.pubkey(), true));
+    unilateral.accounts.push(AccountMeta::new_readonly(rsm.controller.pubkey(), true));
+    let err = try_process(&mut ctx, &[unilateral], &[&rsm.controller]).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(RSMError::MintQuorumNotMet as u32))
+    );
+
+    let (mut cosigned, amount, _) = mint_instruction(&mut ctx, program_id, keys, genome_hash, recipient, 3).await;
+    cosigned.accounts.push(AccountMeta::new_readonly(auditor.pubkey(), true));
+    cosigned.accounts.push(AccountMeta::new_readonly(rsm.controller.pubkey(), true));
+    process(&mut ctx, &[cosigned], &[&rsm.controller, &auditor]).await;
+    let data: GenomeData = get(&mut ctx, &genome, 0).await;
+    assert!(data.is_minted);
+    assert_eq!(data.tokens_minted, amount);
+}
+
+#[tokio::test]
+async fn test_breaker_trip_holds_mint() {
+    let program_id = Pubkey::new_unique();
+    let mut ctx = ProgramTest::new("rsm_token", program_id, processor!(rsm_token::process_instruction))
+        .start_with_context()
+        .await;
+
+    let rsm = mint_rsm_from_genome(&mut ctx, program_id, [48u8; 32]).await;
+    let payer = ctx.payer.pubkey();
+    let genome_hash = [49u8; 32];
+    let (genome, _) = genome_address(&program_id, &rsm.config, &genome_hash);
+    let create = rsm_ix(
+        program_id,
+        RSMInstruction::CreateGenomeAccount { genome_hash },
+        vec![
+            AccountMeta::new(rsm.config, false),
+            AccountMeta::new(genome, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+    process(&mut ctx, &[create], &[]).await;
+
+    // Fill the window to its limit so the next mint trips the breaker
+    let slot = ctx.banks_client.get_sysvar::<Clock>().await.unwrap().slot;
+    let mut account = ctx.banks_client.get_account(rsm.config).await.unwrap().unwrap();
+    let mut config = TokenConfig::try_from_slice(&account.data).unwrap();
+    let limit = EmissionBreaker::floor_average(DECIMALS) * config.breaker.max_multiple_pct as u64 / 100;
+    config.breaker.window_start = slot;
+    config.breaker.window_emitted = limit;
+    config.serialize(&mut &mut account.data[..]).unwrap();
+    ctx.set_account(&rsm.config, &account.into());
+
+    // The tripping mint lands without minting, so the trip sticks
+    let keys = (rsm.config, rsm.mint, rsm.controller.pubkey());
+    let recipient = (rsm.recipient.pubkey(), rsm.holding);
+    let balance = token_balance(&mut ctx, &rsm.holding).await;
+    let (held, _, _) = mint_instruction(&mut ctx, program_id, keys, genome_hash, recipient, 2).await;
+    process(&mut ctx, &[held], &[&rsm.controller]).await;
+    assert_eq!(token_balance(&mut ctx, &rsm.holding).await, balance);
+    let config: TokenConfig = get(&mut ctx, &rsm.config, 0).await;
+    assert!(config.breaker.tripped);
+    let data: GenomeData = get(&mut ctx, &genome, 0).await;
+    assert!(!data.is_minted);
+
+    let (blocked, _, _) = mint_instruction(&mut ctx, program_id, keys, genome_hash, recipient, 3).await;
+    let err = try_process(&mut ctx, &[blocked], &[&rsm.controller]).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(RSMError::EmissionAnomaly as u32))
+    );
+
+    // After a reset the held nonce goes through
+    let reset = rsm_ix(
+        program_id,
+        RSMInstruction::ResetBreaker,
+        vec![
+            AccountMeta::new(rsm.config, false),
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    );
+    let (retry, amount, _) = mint_instruction(&mut ctx, program_id, keys, genome_hash, recipient, 2).await;
+    process(&mut ctx, &[reset, retry], &[&rsm.controller]).await;
+    assert_eq!(token_balance(&mut ctx, &rsm.holding).await, balance + amount);
+    let data: GenomeData = get(&mut ctx, &genome, 0).await;
+    assert!(data.is_minted);
+}
+
+#[tokio::test]
+async fn test_emergency_clawback_burns_delegated_tokens() {
+    let program_id = Pubkey::new_unique();
+    let mut ctx = ProgramTest::new("rsm_token", program_id, processor!(rsm_token::process_instruction))
+        .start_with_context()
+        .await;
+
+    let genome_hash = [48u8; 32];
+    let rsm = mint_rsm_from_genome(&mut ctx, program_id, genome_hash).await;
+    let (genome, _) = genome_address(&program_id, &rsm.config, &genome_hash);
+    let payer = ctx.payer.pubkey();
+    let executor = Keypair::new();
+    let holding = rsm.holding;
+    let minted = token_balance(&mut ctx, &holding).await;
+    // The recipient delegates part of the minted balance to the config
+    process(
+        &mut ctx,
+        &[
+            spl_token::instruction::approve(&spl_token::id(), &holding, &rsm.config, &rsm.recipient.pubkey(), &[], 600).unwrap(),
+            rsm_ix(
+                program_id,
+                RSMInstruction::SetGovernanceExecutor { executor: executor.pubkey() },
+                vec![AccountMeta::new(rsm.config, false), AccountMeta::new_readonly(payer, true)],
+            ),
+        ],
+        &[&rsm.recipient],
+    )
+    .await;
+
+    let evidence_hash = [49u8; 32];
+    let (record, _) = clawback_address(&program_id, &rsm.config, &evidence_hash);
+    let signers = || {
+        vec![
+            AccountMeta::new(rsm.config, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(executor.pubkey(), true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ]
+    };
+    let mut accounts = signers();
+    accounts.extend([
+        AccountMeta::new_readonly(holding, false),
+        AccountMeta::new(record, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]);
+    let schedule = rsm_ix(
+        program_id,
+        RSMInstruction::ScheduleEmergencyClawback { amount: 600, evidence_hash, reason_code: 3 },
+        accounts,
+    );
+    process(&mut ctx, &[schedule], &[&executor]).await;
+
+    let mut accounts = signers();
+    accounts.extend([
+        AccountMeta::new(record, false),
+        AccountMeta::new(holding, false),
+        AccountMeta::new(rsm.mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(genome, false),
+    ]);
+    let clawback = rsm_ix(program_id, RSMInstruction::EmergencyClawback { evidence_hash }, accounts);
+    let err = try_process(&mut ctx, std::slice::from_ref(&clawback), &[&executor]).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(RSMError::ClawbackDelayed as u32))
+    );
+
+    ctx.warp_to_slot(2_000).unwrap();
+    let mut clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += EMERGENCY_CLAWBACK_TIMELOCK;
+    ctx.set_sysvar(&clock);
+    process(&mut ctx, &[clawback], &[&executor]).await;
+    assert_eq!(token_balance(&mut ctx, &holding).await, minted - 600);
+    assert_eq!(get::<TokenConfig>(&mut ctx, &rsm.config, 0).await.total_minted, minted - 600);
+    assert_eq!(get::<GenomeData>(&mut ctx, &genome, 0).await.tokens_minted, minted - 600);
+    let record: ClawbackRecord = get(&mut ctx, &record, 0).await;
+    assert_eq!(record.status, ClawbackStatus::Executed);
+    assert_eq!((record.owner, record.reason_code, record.evidence_hash), (rsm.recipient.pubkey(), 3, evidence_hash));
+}
+
+#[tokio::test]
+async fn test_challenge_slashes_escrowed_bond() {
+    let program_id = Pubkey::new_unique();
+    let mut ctx = ProgramTest::new("rsm_token", program_id, processor!(rsm_token::process_instruction))
+        .start_with_context()
+        .await;
+
+    let rsm = mint_rsm_from_genome(&mut ctx, program_id, [52u8; 32]).await;
+    let payer = ctx.payer.pubkey();
+    let evidence_signer = Keypair::new();
+    let (bond_vault, _) = bond_vault_address(&program_id, &rsm.config);
+    let genome_hash = [53u8; 32];
+    let (genome, _) = genome_address(&program_id, &rsm.config, &genome_hash);
+    let authority = || vec![AccountMeta::new(rsm.config, false), AccountMeta::new_readonly(payer, true)];
+    process(
+        &mut ctx,
+        &[
+            rsm_ix(program_id, RSMInstruction::ConfigureScoreValidity { validity_secs: 86_400, bond_bps: 5_000 }, authority()),
+            rsm_ix(
+                program_id,
+                RSMInstruction::ConfigureDisputes { window_secs: 3_600, reward_bps: 2_000, evidence_signer: evidence_signer.pubkey() },
+                authority(),
+            ),
+            rsm_ix(
+                program_id,
+                RSMInstruction::CreateBondVault,
+                vec![
+                    AccountMeta::new_readonly(rsm.config, false),
+                    AccountMeta::new(bond_vault, false),
+                    AccountMeta::new_readonly(rsm.mint, false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(spl_token::id(), false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+            rsm_ix(
+                program_id,
+                RSMInstruction::CreateGenomeAccount { genome_hash },
+                vec![
+                    AccountMeta::new(rsm.config, false),
+                    AccountMeta::new(genome, false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+        ],
+        &[],
+    )
+    .await;
+
+    // Half of the bonded mint is escrowed in the vault, passed last
+    let keys = (rsm.config, rsm.mint, rsm.controller.pubkey());
+    let balance = token_balance(&mut ctx, &rsm.holding).await;
+    let (mut ix, amount, _) = mint_instruction(&mut ctx, program_id, keys, genome_hash, (rsm.recipient.pubkey(), rsm.holding), 2).await;
+    ix.accounts.push(AccountMeta::new(bond_vault, false));
+    process(&mut ctx, &[ix], &[&rsm.controller]).await;
+    let bond = amount / 2;
+    assert_eq!(token_balance(&mut ctx, &rsm.holding).await, balance + amount - bond);
+    assert_eq!(token_balance(&mut ctx, &bond_vault).await, bond);
+    let supply = |account: Account| spl_token::state::Mint::unpack(&account.data).unwrap().supply;
+    let minted = supply(ctx.banks_client.get_account(rsm.mint).await.unwrap().unwrap());
+    assert_eq!(get::<TokenConfig>(&mut ctx, &rsm.config, 0).await.total_minted, minted);
+
+    // A signed re-score with a lower complexity slashes part of the bond
+    let data: GenomeData = get(&mut ctx, &genome, 0).await;
+    let (complexity, uniqueness) = (80, 91);
+    let message = rescore_evidence_message(&rsm.config, &genome_hash, data.mint_slot, complexity, uniqueness);
+    let (key_offset, signature_offset, message_offset) = (16u16, 48u16, 112u16);
+    let mut evidence = vec![1u8, 0];
+    for field in [signature_offset, u16::MAX, key_offset, u16::MAX, message_offset, message.len() as u16, u16::MAX] {
+        evidence.extend_from_slice(&field.to_le_bytes());
+    }
+    evidence.extend_from_slice(evidence_signer.pubkey().as_ref());
+    evidence.extend_from_slice(evidence_signer.sign_message(&message).as_ref());
+    evidence.extend_from_slice(&message);
+    let (dispute, _) = dispute_address(&program_id, &genome, data.mint_slot);
+    let challenger_holding = create_token_account(&mut ctx, &rsm.mint, &payer).await;
+    let challenge = rsm_ix(
+        program_id,
+        RSMInstruction::ChallengeMint { genome_hash, complexity, uniqueness },
+        vec![
+            AccountMeta::new(rsm.config, false),
+            AccountMeta::new(genome, false),
+            AccountMeta::new(dispute, false),
+            AccountMeta::new(rsm.mint, false),
+            AccountMeta::new(challenger_holding, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(bond_vault, false),
+        ],
+    );
+    let verify = Instruction::new_with_bytes(solana_sdk::ed25519_program::id(), &evidence, Vec::new());
+    process(&mut ctx, &[verify, challenge], &[]).await;
+
+    // The reward moves out of the vault and the rest of the slash is
+    // burned, so supply falls by what was burned and still matches
+    let record: DisputeRecord = get(&mut ctx, &dispute, 0).await;
+    assert!(record.slashed > 0 && record.slashed < bond);
+    assert_eq!(record.reward, record.slashed / 5);
+    assert_eq!(token_balance(&mut ctx, &challenger_holding).await, record.reward);
+    assert_eq!(token_balance(&mut ctx, &bond_vault).await, bond - record.slashed);
+    let challenged = supply(ctx.banks_client.get_account(rsm.mint).await.unwrap().unwrap());
+    assert_eq!(challenged, minted - (record.slashed - record.reward));
+    let config: TokenConfig = get(&mut ctx, &rsm.config, 0).await;
+    assert_eq!((config.total_minted, config.dispute_rewards), (challenged, record.reward));
+
+    // Once the dispute window, score validity and clawback delay pass, the
+    // rest of the bond goes to the owner
+    let release = rsm_ix(
+        program_id,
+        RSMInstruction::ReleaseBond { genome_hash },
+        vec![
+            AccountMeta::new_readonly(rsm.config, false),
+            AccountMeta::new(genome, false),
+            AccountMeta::new(bond_vault, false),
+            AccountMeta::new_readonly(rsm.mint, false),
+            AccountMeta::new(rsm.holding, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    );
+    let err = try_process(&mut ctx, std::slice::from_ref(&release), &[]).await.unwrap_err().unwrap();
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::Custom(RSMError::BondLocked as u32)));
+    ctx.warp_to_slot(2_000).unwrap();
+    let mut clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += 86_400 + CLAWBACK_DELAY;
+    ctx.set_sysvar(&clock);
+    let holding = token_balance(&mut ctx, &rsm.holding).await;
+    process(&mut ctx, &[release], &[]).await;
+    assert_eq!(token_balance(&mut ctx, &rsm.holding).await, holding + bond - record.slashed);
+    assert_eq!(token_balance(&mut ctx, &bond_vault).await, 0);
+    assert_eq!(get::<GenomeData>(&mut ctx, &genome, 0).await.bonded_amount, 0);
+
+    // The on-chain audit of both genomes finds no discrepancy with the
+    // real supply
+    let (audit, _) = Pubkey::find_program_address(&[AUDIT_SEED, rsm.config.as_ref(), payer.as_ref()], &program_id);
+    let mut genomes = [genome, genome_address(&program_id, &rsm.config, &[52u8; 32]).0];
+    genomes.sort();
+    let mut page = vec![
+        AccountMeta::new_readonly(rsm.config, false),
+        AccountMeta::new(audit, false),
+        AccountMeta::new_readonly(payer, true),
+    ];
+    page.extend(genomes.iter().map(|genome| AccountMeta::new_readonly(*genome, false)));
+    let mut start = page[..3].to_vec();
+    start.push(AccountMeta::new_readonly(system_program::id(), false));
+    process(
+        &mut ctx,
+        &[
+            rsm_ix(program_id, RSMInstruction::StartSupplyAudit, start),
+            rsm_ix(program_id, RSMInstruction::AuditSupplyPage, page.clone()),
+            rsm_ix(program_id, RSMInstruction::FinishSupplyAudit, page[..3].to_vec()),
+        ],
+        &[],
+    )
+    .await;
+    let audit: SupplyAudit = get(&mut ctx, &audit, 0).await;
+    assert!(audit.finished);
+    assert_eq!(audit.genomes_counted, 2);
+    let supply = supply(ctx.banks_client.get_account(rsm.mint).await.unwrap().unwrap());
+    assert_eq!(supply, challenged);
+    assert_eq!(audit.computed_total, supply);
+    assert_eq!(get::<TokenConfig>(&mut ctx, &rsm.config, 0).await.total_minted, supply);
+}
+
+#[tokio::test]
+#[ignore = "needs the Anchor builds in contracts/target/deploy"]
+async fn test_score_mint_breed_trade() {
+    let builds = anchor_builds();
+    let (dna_id, rna_id, genesis_id, pause_id) = (builds[0].0, builds[1].0, builds[2].0, builds[3].0);
+
+    let rsm_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("rsm_token", rsm_id, processor!(rsm_token::process_instruction));
+    // dna-token is upgradeable: only its upgrade authority may initialize the registry
+    let deployer = Keypair::new();
+    program_test.add_account(deployer.pubkey(), Account::new(1_000_000_000, 0, &system_program::id()));
+    let (dna_data, _) = Pubkey::find_program_address(&[dna_id.as_ref()], &bpf_loader_upgradeable::id());
+    let mut program_data = Account::new_data_with_space(
+        1_000_000_000,
+        &UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address: Some(deployer.pubkey()) },
+        UpgradeableLoaderState::size_of_programdata_metadata(),
+        &bpf_loader_upgradeable::id(),
+    )
+    .unwrap();
+    program_data.data.extend_from_slice(&builds[0].1);
+    program_test.add_account(dna_data, program_data);
+    let mut dna_program = Account::new_data(
+        1_000_000_000,
+        &UpgradeableLoaderState::Program { programdata_address: dna_data },
+        &bpf_loader_upgradeable::id(),
+    )
+    .unwrap();
+    dna_program.executable = true;
+    program_test.add_account(dna_id, dna_program);
+    for (id, elf) in &builds[1..] {
+        program_test.add_account(
+            *id,
+            Account {
+                lamports: 1_000_000_000,
+                data: elf.clone(),
+                owner: bpf_loader::id(),
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+    }
+    let mut ctx = program_test.start_with_context().await;
+    let payer = ctx.payer.pubkey();
+
+    // Every user path checks the (unpaused) ecosystem flag
+    let (global_pause, _) = Pubkey::find_program_address(&[b"global_pause"], &pause_id);
+    process(
+        &mut ctx,
+        &[anchor_ix(
+            pause_id,
+            "initialize",
+            Vec::<Pubkey>::new(),
+            vec![
+                AccountMeta::new(global_pause, false),
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        )],
+        &[],
+    )
+    .await;
+
+    // 1. Mint RSM from a scored genome
+    let rsm = mint_rsm_from_genome(&mut ctx, rsm_id, [7u8; 32]).await;
+    let user = rsm.recipient;
+    process(&mut ctx, &[system_instruction::transfer(&payer, &user.pubkey(), 1_000_000_000)], &[]).await;
+
+    // 2. Earn RNA from a tracked hold of a genome's DNA
+    let parent = Keypair::new();
+    let parent_id: u64 = 1;
+    let parent_mint = create_mint(&mut ctx, &payer, 0).await;
+    let user_parent = create_token_account(&mut ctx, &parent_mint, &user.pubkey()).await;
+    let founder_parent = create_token_account(&mut ctx, &parent_mint, &payer).await;
+    let (parent_hold, _) = Pubkey::find_program_address(
+        &[b"hold", parent.pubkey().as_ref(), user.pubkey().as_ref()],
+        &dna_id,
+    );
+    let rna_mint = create_mint(&mut ctx, &payer, 9).await;
+    let user_rna = create_token_account(&mut ctx, &rna_mint, &user.pubkey()).await;
+    let (ancestor_royalty, _) = Pubkey::find_program_address(&[b"ancestor_royalty", rna_mint.as_ref()], &rna_id);
+    let holder = Keypair::new();
+    let (engine, _) = Pubkey::find_program_address(&[b"engine", payer.as_ref()], &genesis_id);
+    let (registry, _) = Pubkey::find_program_address(&[b"registry"], &dna_id);
+    process(
+        &mut ctx,
+        &[
+            anchor_ix(
+                dna_id,
+                "initialize_registry",
+                (),
+                vec![
+                    AccountMeta::new(registry, false),
+                    AccountMeta::new(deployer.pubkey(), true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                    AccountMeta::new_readonly(dna_id, false),
+                    AccountMeta::new_readonly(dna_data, false),
+                ],
+            ),
+            // The registry authority creates the genome for the payer as founder
+            anchor_ix(
+                dna_id,
+                "initialize_genome",
+                (parent_id, "ACGT".to_string(), 80u8, "bitcoin".to_string(), String::new(), 1_000_000u64, 100u16, [0u8; 32], 0u16, 0u64, payer),
+                vec![
+                    AccountMeta::new(parent.pubkey(), true),
+                    AccountMeta::new(deployer.pubkey(), true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                    AccountMeta::new_readonly(global_pause, false),
+                    AccountMeta::new_readonly(registry, false),
+                ],
+            ),
+            // The user takes the market supply; genesis later requires it too
+            anchor_ix(
+                dna_id,
+                "mint_genome_tokens",
+                1_000_000u64,
+                vec![
+                    AccountMeta::new(parent.pubkey(), false),
+                    AccountMeta::new(parent_mint, false),
+                    AccountMeta::new(user_parent, false),
+                    AccountMeta::new(founder_parent, false),
+                    AccountMeta::new_readonly(payer, true),
+                    AccountMeta::new_readonly(spl_token::id(), false),
+                    AccountMeta::new_readonly(global_pause, false),
+                ],
+            ),
+            anchor_ix(
+                dna_id,
+                "start_hold",
+                (),
+                vec![
+                    AccountMeta::new_readonly(parent.pubkey(), false),
+                    AccountMeta::new_readonly(user_parent, false),
+                    AccountMeta::new(parent_hold, false),
+                    AccountMeta::new(user.pubkey(), true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+            anchor_ix(
+                rna_id,
+                "initialize_holder",
+                (),
+                vec![
+                    AccountMeta::new(holder.pubkey(), true),
+                    AccountMeta::new(user.pubkey(), true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+            // 2% a generation over 3; a founder genome's hold pays none
+            anchor_ix(
+                rna_id,
+                "set_ancestor_royalty",
+                (200u16, 3u8, engine),
+                vec![
+                    AccountMeta::new_readonly(rna_mint, false),
+                    AccountMeta::new(ancestor_royalty, false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+        ],
+        &[&deployer, &parent, &holder, &user],
+    )
+    .await;
+
+    let mut clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += 2 * 86_400;
+    ctx.set_sysvar(&clock);
+    process(
+        &mut ctx,
+        &[anchor_ix(
+            rna_id,
+            "claim_rna",
+            (),
+            vec![
+                AccountMeta::new(holder.pubkey(), false),
+                AccountMeta::new_readonly(user.pubkey(), true),
+                AccountMeta::new_readonly(dna_id, false),
+                AccountMeta::new(rna_mint, false),
+                AccountMeta::new(user_rna, false),
+                AccountMeta::new_readonly(payer, true),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(global_pause, false),
+                AccountMeta::new_readonly(ancestor_royalty, false),
+                AccountMeta::new_readonly(parent.pubkey(), false),
+                AccountMeta::new(parent_hold, false),
+                AccountMeta::new_readonly(user_parent, false),
+            ],
+        )],
+        &[&user],
+    )
+    .await;
+    // 1% a day of the time-weighted balance, held throughout
+    let earned = token_balance(&mut ctx, &user_rna).await;
+    assert_eq!(earned, MARKET_SUPPLY * 2 / 100);
+
+    // 3. Escrow RNA under a commitment, then reveal it into a child genome
+    //    registered with dna-token
+    let (genesis_authority, _) = Pubkey::find_program_address(&[b"genesis_authority", engine.as_ref()], &genesis_id);
+    let species: u16 = 0;
+    let (pool, _) = Pubkey::find_program_address(
+        &[b"pool", engine.as_ref(), &species.to_le_bytes()],
+        &genesis_id,
+    );
+    process(
+        &mut ctx,
+        &[
+            anchor_ix(
+                dna_id,
+                "set_genesis_program",
+                (genesis_id, engine),
+                vec![AccountMeta::new(registry, false), AccountMeta::new_readonly(deployer.pubkey(), true)],
+            ),
+            anchor_ix(
+                genesis_id,
+                "initialize",
+                GENESIS_COST,
+                vec![
+                    AccountMeta::new(engine, false),
+                    AccountMeta::new_readonly(rna_mint, false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+            // No incubation so the child is tradable immediately
+            anchor_ix(
+                genesis_id,
+                "update_incubation",
+                (0u64, 0u64),
+                vec![AccountMeta::new(engine, false), AccountMeta::new_readonly(payer, true)],
+            ),
+            // Flat curve: every genome in the pool costs genesis_cost
+            anchor_ix(
+                genesis_id,
+                "create_pool",
+                (species, 10_000u16, 0u16, 100u64),
+                vec![
+                    AccountMeta::new_readonly(engine, false),
+                    AccountMeta::new(pool, false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                ],
+            ),
+        ],
+        &[&deployer],
+    )
+    .await;
+
+    let child_id: u64 = 100_000;
+    let (dna_genome, _) = Pubkey::find_program_address(&[b"genome", &child_id.to_le_bytes()], &dna_id);
+    let genesis_record = Keypair::new();
+    let (user_stats, _) = Pubkey::find_program_address(
+        &[b"user_stats", engine.as_ref(), user.pubkey().as_ref()],
+        &genesis_id,
+    );
+    let (lineage, _) =
+        Pubkey::find_program_address(&[b"lineage", engine.as_ref(), &child_id.to_le_bytes()], &genesis_id);
+    let (genesis_commit, _) = Pubkey::find_program_address(
+        &[b"genesis_commit", engine.as_ref(), user.pubkey().as_ref()],
+        &genesis_id,
+    );
+    let (commit_escrow, _) = Pubkey::find_program_address(&[b"commit_escrow", genesis_commit.as_ref()], &genesis_id);
+    let (entropy_seed, salt) = ([9u8; 32], [4u8; 32]);
+    let commitment = hashv(&[entropy_seed.as_ref(), salt.as_ref()]).to_bytes();
+    process(
+        &mut ctx,
+        &[anchor_ix(
+            genesis_id,
+            "create_genome",
+            (vec![parent_id], commitment, "Helix Prime".to_string(), [3u8; 32]),
+            vec![
+                AccountMeta::new_readonly(engine, false),
+                AccountMeta::new(pool, false),
+                AccountMeta::new(genesis_commit, false),
+                AccountMeta::new(commit_escrow, false),
+                AccountMeta::new_readonly(rna_mint, false),
+                AccountMeta::new(user_rna, false),
+                AccountMeta::new(user.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(user_stats, false),
+                AccountMeta::new_readonly(global_pause, false),
+                // No hybrid permit: the parent shares the pool's species;
+                // no genesis event, price oracle, guardian or donation
+                AccountMeta::new_readonly(genesis_id, false),
+                AccountMeta::new_readonly(genesis_id, false),
+                AccountMeta::new_readonly(genesis_id, false),
+                AccountMeta::new_readonly(genesis_id, false),
+                AccountMeta::new_readonly(genesis_id, false),
+                AccountMeta::new_readonly(genesis_id, false),
+                AccountMeta::new_readonly(genesis_id, false),
+                AccountMeta::new_readonly(parent.pubkey(), false),
+                AccountMeta::new_readonly(user_parent, false),
+            ],
+        )],
+        &[&user],
+    )
+    .await;
+    assert_eq!(token_balance(&mut ctx, &commit_escrow).await, GENESIS_COST);
+
+    // The roll mixes in the commit slot's hash, so it is revealed a slot later
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    ctx.warp_to_slot(clock.slot + 1).unwrap();
+    process(
+        &mut ctx,
+        &[anchor_ix(
+            genesis_id,
+            "reveal_genesis",
+            (entropy_seed, salt),
+            vec![
+                AccountMeta::new(engine, false),
+                AccountMeta::new(genesis_commit, false),
+                AccountMeta::new(commit_escrow, false),
+                AccountMeta::new(rna_mint, false),
+                AccountMeta::new(genesis_record.pubkey(), true),
+                AccountMeta::new_readonly(registry, false),
+                AccountMeta::new(dna_genome, false),
+                AccountMeta::new_readonly(genesis_authority, false),
+                AccountMeta::new(user.pubkey(), true),
+                AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+                AccountMeta::new_readonly(dna_id, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(global_pause, false),
+                // No mutation catalog, genesis event or gene pool
+                AccountMeta::new_readonly(genesis_id, false),
+                AccountMeta::new(lineage, false),
+                AccountMeta::new_readonly(genesis_id, false),
+                AccountMeta::new_readonly(genesis_id, false),
+            ],
+        )],
+        &[&genesis_record, &user],
+    )
+    .await;
+    assert_eq!(token_balance(&mut ctx, &user_rna).await, earned - GENESIS_COST);
+
+    // 4. Mint the child's DNA: market share and founder share
+    let dna_mint = create_mint(&mut ctx, &payer, 0).await;
+    let market = create_token_account(&mut ctx, &dna_mint, &payer).await;
+    let founder = create_token_account(&mut ctx, &dna_mint, &user.pubkey()).await;
+    process(
+        &mut ctx,
+        &[anchor_ix(
+            dna_id,
+            "mint_genome_tokens",
+            1_000_000u64,
+            vec![
+                AccountMeta::new(dna_genome, false),
+                AccountMeta::new(dna_mint, false),
+                AccountMeta::new(market, false),
+                AccountMeta::new(founder, false),
+                AccountMeta::new_readonly(payer, true),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(global_pause, false),
+            ],
+        )],
+        &[],
+    )
+    .await;
+    assert_eq!(token_balance(&mut ctx, &founder).await, 142_900);
+
+    // 5. Trade it through the tracked transfer path, which always takes
+    // the sender's hold PDA
+    let buyer = Keypair::new();
+    let buyer_dna = create_token_account(&mut ctx, &dna_mint, &buyer.pubkey()).await;
+    let (founder_hold, _) = Pubkey::find_program_address(&[b"hold", dna_genome.as_ref(), user.pubkey().as_ref()], &dna_id);
+    process(
+        &mut ctx,
+        &[anchor_ix(
+            dna_id,
+            "transfer_dna",
+            40_000u64,
+            vec![
+                AccountMeta::new_readonly(dna_genome, false),
+                AccountMeta::new(founder, false),
+                AccountMeta::new(buyer_dna, false),
+                AccountMeta::new_readonly(user.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(global_pause, false),
+                AccountMeta::new(founder_hold, false),
+            ],
+        )],
+        &[&user],
+    )
+    .await;
+    assert_eq!(token_balance(&mut ctx, &buyer_dna).await, 40_000);
+    assert_eq!(token_balance(&mut ctx, &founder).await, 102_900);
+}